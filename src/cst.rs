@@ -0,0 +1,382 @@
+//! Lossless concrete syntax tree.
+//!
+//! Built from the `Tokenizer` output, it keeps every byte of the source:
+//! whitespace, comments and newlines are trivia attached to the significant
+//! tokens around them. A token's trailing trivia is whatever follows it on its
+//! own line, up to and including the newline; everything else before a token
+//! (indentation, blank lines, comment-only lines) is its leading trivia. A node's
+//! trivia is the leading trivia of its first token and the trailing trivia of
+//! its last one.
+//!
+//! Printing a tree gives back its source exactly: `Cst::parse(s).to_string() == s`
+//! for every `s`. [`Cst::to_ast`] and [`Node::to_ast`] give the typed view the
+//! compiler works on.
+//!
+//! ```
+//! use ven::cst::Cst;
+//!
+//! let source = "@@ age i 30 ; years\n\n?(age > 18) {\n    >> \"adult\"\n}\n";
+//! let cst = Cst::parse(source);
+//! assert_eq!(cst.to_string(), source);
+//! assert_eq!(cst.nodes[0].trailing_comment(), Some("; years"));
+//! assert_eq!(&source[cst.nodes[1].span().0..cst.nodes[1].span().1], "?(age > 18) {\n    >> \"adult\"\n}");
+//! ```
+
+use std::fmt;
+
+use crate::errs::VarError;
+use crate::parse::{AST, ASTNode};
+use crate::token::{TokenKind, Tokenizer};
+use crate::var_checker::VarMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    Whitespace,
+    Newline,
+    Comment,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CstToken {
+    pub kind: TokenKind,
+    /// Byte offset of `text` in the source.
+    pub start: usize,
+    pub text: String,
+    pub leading: Vec<Trivia>,
+    pub trailing: Vec<Trivia>,
+}
+
+impl CstToken {
+    pub fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+
+    /// The token ends its line.
+    pub fn ends_line(&self) -> bool {
+        self.trailing.iter().any(|trivia| trivia.kind == TriviaKind::Newline)
+    }
+
+    /// The comment after the token on the same line, if any.
+    pub fn trailing_comment(&self) -> Option<&str> {
+        self.trailing
+            .iter()
+            .find(|trivia| trivia.kind == TriviaKind::Comment)
+            .map(|trivia| trivia.text.as_str())
+    }
+
+    /// Whether the token is the `-` of a `->` call result arrow.
+    pub(crate) fn is_arrow_start(&self, next: Option<&CstToken>) -> bool {
+        self.kind == TokenKind::Minus && next.is_some_and(|next| next.kind == TokenKind::Greater)
+    }
+
+    pub(crate) fn is_comma(&self) -> bool {
+        self.kind == TokenKind::Unknown && self.text == ","
+    }
+}
+
+/// What a statement line does, judged by its first tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementKind {
+    /// `@ name type value` or `@@ ...`
+    Declaration,
+    /// `>> ...` or `>>> ...`
+    Print,
+    /// `.. name`
+    Input,
+    /// `* name op operand`
+    Math,
+    /// `name(args)` or `name(args) -> var`
+    Call,
+    /// `# Name { field type, ... }`
+    RecordType,
+    /// Anything the parser would not accept as a statement.
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Statement {
+        kind: StatementKind,
+        tokens: Vec<CstToken>,
+    },
+    /// `?(condition) { ... }`
+    Block {
+        /// From `?` up to and including `{`.
+        header: Vec<CstToken>,
+        body: Vec<Node>,
+        /// `None` when the file ends before the block is closed.
+        close: Option<CstToken>,
+    },
+}
+
+impl Node {
+    /// Every token of the node, in source order.
+    pub fn tokens(&self) -> Vec<&CstToken> {
+        match self {
+            Node::Statement { tokens, .. } => tokens.iter().collect(),
+            Node::Block { header, body, close } => header
+                .iter()
+                .chain(body.iter().flat_map(|node| node.tokens()))
+                .chain(close.iter())
+                .collect(),
+        }
+    }
+
+    fn first(&self) -> &CstToken {
+        match self {
+            Node::Statement { tokens, .. } => &tokens[0],
+            Node::Block { header, .. } => &header[0],
+        }
+    }
+
+    fn last(&self) -> &CstToken {
+        match self {
+            Node::Statement { tokens, .. } => tokens.last().unwrap(),
+            Node::Block { close: Some(close), .. } => close,
+            Node::Block { header, body, close: None } => match body.last() {
+                Some(node) => node.last(),
+                None => header.last().unwrap(),
+            },
+        }
+    }
+
+    /// Trivia before the node: its indentation and the blank lines and
+    /// comment-only lines above it.
+    pub fn leading(&self) -> &[Trivia] {
+        &self.first().leading
+    }
+
+    /// Trivia after the node on its last line, including the newline.
+    pub fn trailing(&self) -> &[Trivia] {
+        &self.last().trailing
+    }
+
+    pub fn trailing_comment(&self) -> Option<&str> {
+        self.last().trailing_comment()
+    }
+
+    /// Byte range of the node in the source, without its trivia.
+    pub fn span(&self) -> (usize, usize) {
+        (self.first().start, self.last().end())
+    }
+
+    /// The node's text without its leading and trailing trivia.
+    pub fn code(&self) -> String {
+        let tokens = self.tokens();
+        let mut code = String::new();
+        for (i, token) in tokens.iter().enumerate() {
+            if i > 0 {
+                write_trivia_into(&mut code, &token.leading);
+            }
+            code.push_str(&token.text);
+            if i + 1 < tokens.len() {
+                write_trivia_into(&mut code, &token.trailing);
+            }
+        }
+        code
+    }
+
+    /// The node as the parser reads it on its own, or `None` if it is not a
+    /// statement the parser accepts.
+    pub fn to_ast(&self) -> Option<ASTNode> {
+        let code = self.code();
+        let mut tokenizer = Tokenizer::new(&code);
+        tokenizer.tokenize();
+        let (AST::Program(mut nodes), _, errors) = AST::parse(&tokenizer.tokens, &code);
+        (errors.is_empty() && nodes.len() == 1).then(|| nodes.remove(0))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cst {
+    pub nodes: Vec<Node>,
+    /// Trivia after the last token.
+    pub end: Vec<Trivia>,
+}
+
+impl Cst {
+    /// Builds the tree of `source`. Any text is accepted: statements the parser
+    /// would reject become `StatementKind::Other` nodes or unclosed blocks.
+    pub fn parse(source: &str) -> Cst {
+        let mut tokenizer = Tokenizer::new(source);
+        tokenizer.tokenize();
+
+        let mut tokens: Vec<CstToken> = Vec::new();
+        let mut pending: Vec<Trivia> = Vec::new();
+        // Whether trivia still belongs to the last token's line
+        let mut same_line = false;
+        let mut i = 0;
+        while i < tokenizer.tokens.len() {
+            let token = tokenizer.tokens[i];
+            let mut end = token.end;
+            // Non-ASCII characters come out of the tokenizer one byte per token
+            while !source.is_char_boundary(end) {
+                i += 1;
+                end = tokenizer.tokens[i].end;
+            }
+            i += 1;
+            let text = &source[token.start..end];
+
+            let trivia = match token.kind {
+                TokenKind::Newline => Some(TriviaKind::Newline),
+                TokenKind::Comment => Some(TriviaKind::Comment),
+                TokenKind::Unknown if text.trim().is_empty() => Some(TriviaKind::Whitespace),
+                _ => None,
+            };
+            match trivia {
+                Some(kind) => {
+                    let trivia = Trivia {
+                        kind,
+                        text: text.to_string(),
+                    };
+                    match tokens.last_mut() {
+                        Some(last) if same_line => last.trailing.push(trivia),
+                        _ => pending.push(trivia),
+                    }
+                    if kind == TriviaKind::Newline {
+                        same_line = false;
+                    }
+                }
+                None => {
+                    tokens.push(CstToken {
+                        kind: token.kind,
+                        start: token.start,
+                        text: text.to_string(),
+                        leading: std::mem::take(&mut pending),
+                        trailing: Vec::new(),
+                    });
+                    same_line = true;
+                }
+            }
+        }
+
+        let mut stream = tokens.into_iter().peekable();
+        let nodes = parse_nodes(&mut stream, false);
+        Cst { nodes, end: pending }
+    }
+
+    /// The AST, variable map and errors of the whole program, exactly as
+    /// `AST::parse` gives them for the source.
+    pub fn to_ast(&self) -> (AST, VarMap, Vec<VarError>) {
+        let source = self.to_string();
+        let mut tokenizer = Tokenizer::new(&source);
+        tokenizer.tokenize();
+        AST::parse(&tokenizer.tokens, &source)
+    }
+}
+
+type Stream = std::iter::Peekable<std::vec::IntoIter<CstToken>>;
+
+/// Takes tokens up to the end of the line, stopping early before a `}` that
+/// closes the block when inside one, or after a `{` when `until_brace` is set.
+fn take_line(stream: &mut Stream, first: CstToken, in_block: bool, until_brace: bool) -> Vec<CstToken> {
+    let mut tokens = vec![first];
+    // Braces opened on the line, as in `{}` or a record's `{ field value }`
+    let mut open: usize = 0;
+    loop {
+        let last = tokens.last().unwrap();
+        if last.ends_line() || (until_brace && last.kind == TokenKind::LCurlyB) {
+            break;
+        }
+        match stream.peek() {
+            Some(next) if in_block && next.kind == TokenKind::RCurlyB && open == 0 => break,
+            Some(next) => {
+                match next.kind {
+                    TokenKind::LCurlyB => open += 1,
+                    TokenKind::RCurlyB => open = open.saturating_sub(1),
+                    _ => {}
+                }
+                tokens.push(stream.next().unwrap());
+            }
+            None => break,
+        }
+    }
+    tokens
+}
+
+fn statement_kind(tokens: &[CstToken]) -> StatementKind {
+    let kind_at = |i: usize| tokens.get(i).map(|t| t.kind);
+    match kind_at(0) {
+        Some(TokenKind::At) => StatementKind::Declaration,
+        Some(TokenKind::Greater) => StatementKind::Print,
+        Some(TokenKind::DoubleDot) => StatementKind::Input,
+        Some(TokenKind::Star) => StatementKind::Math,
+        Some(TokenKind::Hash) => StatementKind::RecordType,
+        Some(TokenKind::Identifier) if kind_at(1) == Some(TokenKind::LSmallB) => StatementKind::Call,
+        _ => StatementKind::Other,
+    }
+}
+
+fn parse_nodes(stream: &mut Stream, in_block: bool) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    while let Some(next) = stream.peek() {
+        if in_block && next.kind == TokenKind::RCurlyB {
+            break;
+        }
+        let first = stream.next().unwrap();
+        if !matches!(first.kind, TokenKind::Question | TokenKind::Tilde) {
+            let tokens = take_line(stream, first, in_block, false);
+            nodes.push(Node::Statement {
+                kind: statement_kind(&tokens),
+                tokens,
+            });
+            continue;
+        }
+
+        let mut header = take_line(stream, first, in_block, true);
+        // The opening brace may start the next line
+        if header.last().map(|t| t.kind) != Some(TokenKind::LCurlyB)
+            && stream.peek().map(|t| t.kind) == Some(TokenKind::LCurlyB)
+        {
+            header.push(stream.next().unwrap());
+        }
+        if header.last().map(|t| t.kind) != Some(TokenKind::LCurlyB) {
+            nodes.push(Node::Statement {
+                kind: StatementKind::Other,
+                tokens: header,
+            });
+            continue;
+        }
+        let body = parse_nodes(stream, true);
+        let close = stream.next_if(|t| t.kind == TokenKind::RCurlyB);
+        nodes.push(Node::Block { header, body, close });
+    }
+    nodes
+}
+
+fn write_trivia(f: &mut fmt::Formatter<'_>, trivia: &[Trivia]) -> fmt::Result {
+    trivia.iter().try_for_each(|trivia| f.write_str(&trivia.text))
+}
+
+fn write_trivia_into(out: &mut String, trivia: &[Trivia]) {
+    trivia.iter().for_each(|trivia| out.push_str(&trivia.text));
+}
+
+impl fmt::Display for CstToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_trivia(f, &self.leading)?;
+        f.write_str(&self.text)?;
+        write_trivia(f, &self.trailing)
+    }
+}
+
+/// The node's source text, trivia included.
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tokens().iter().try_for_each(|token| write!(f, "{}", token))
+    }
+}
+
+/// Writes the source back exactly as it was parsed.
+impl fmt::Display for Cst {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.nodes.iter().try_for_each(|node| write!(f, "{}", node))?;
+        write_trivia(f, &self.end)
+    }
+}
@@ -0,0 +1,295 @@
+//! Structured diagnostics: every error and warning the compiler reports, with a
+//! stable code, a source span, notes and an optional fix, rendered as box art
+//! (`human`), one line each (`short`) or JSON Lines (`json`).
+
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::errmsgs::render_human;
+use crate::errs::{BackendError, VarError};
+use crate::json::Json;
+use crate::lint::Lint;
+use crate::parse::IntType;
+use crate::suggest;
+
+const TYPES_NOTE: &str = "types are `i`, `i8`..`i64`, `u8`..`u64`, `f`, `str`, `b` and `c[size]`";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => f.write_str("error"),
+            Severity::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// A byte range of the source with its 1-based line and column (in
+/// characters) at both ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+impl Span {
+    pub fn new(source: &str, start: usize, end: usize) -> Span {
+        let end = end.min(source.len());
+        let start = start.min(end);
+        let (line, column) = line_column(source, start);
+        let (end_line, end_column) = line_column(source, end);
+        Span {
+            start,
+            end,
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+
+    /// The text of a 1-based line, without its indentation and trailing
+    /// whitespace. An empty span at the end of the source, still on `line`,
+    /// if the source has no such line.
+    pub fn line(source: &str, line: usize) -> Span {
+        let end = Span {
+            line,
+            column: 1,
+            end_line: line,
+            end_column: 1,
+            ..Span::new(source, source.len(), source.len())
+        };
+        if line == 0 {
+            return end;
+        }
+        let mut start = 0;
+        for _ in 1..line {
+            match source[start..].find('\n') {
+                Some(i) => start += i + 1,
+                None => return end,
+            }
+        }
+        let text = source[start..].split('\n').next().unwrap_or("");
+        let indent = text.len() - text.trim_start().len();
+        Span::new(source, start + indent, start + text.trim_end().len().max(indent))
+    }
+
+    /// The first whole-word occurrence of `word` within the span, or the span
+    /// itself.
+    fn narrow(self, source: &str, word: &str) -> Span {
+        let text = &source[self.start..self.end];
+        let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let found = text.match_indices(word).find(|(i, _)| {
+            let before = text[..*i].chars().next_back();
+            let after = text[i + word.len()..].chars().next();
+            !before.is_some_and(is_word) && !after.is_some_and(is_word)
+        });
+        match found {
+            Some((i, _)) => Span::new(source, self.start + i, self.start + i + word.len()),
+            None => self,
+        }
+    }
+}
+
+/// A suggested edit: replacing `span` with `replacement` fixes the problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    pub message: String,
+    pub span: Span,
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// `V0001`-style for errors, the rule name for lint warnings.
+    pub code: String,
+    pub message: String,
+    pub file: Option<String>,
+    /// `None` when the problem has no place in the source.
+    pub span: Option<Span>,
+    pub notes: Vec<String>,
+    pub fix: Option<Fix>,
+}
+
+impl Diagnostic {
+    /// `names` are the program's variables and `types` its record types, which
+    /// a misspelt name or type is matched against.
+    pub fn from_var_error(error: &VarError, source: &str, names: &[&str], types: &[&str]) -> Diagnostic {
+        let span = Span::line(source, error.line());
+        let (span, notes) = match error {
+            // A close name is suggested instead
+            VarError::UndeclaredVariable { name, .. } if suggest::closest(name, names.iter().copied()).is_some() => {
+                (span.narrow(source, name), Vec::new())
+            }
+            VarError::UndeclaredVariable { name, .. } => (
+                span.narrow(source, name),
+                vec![format!("declare it first, e.g. `@@ {} i 0`", name)],
+            ),
+            VarError::ImmutableAssignment { name, .. } => (
+                span.narrow(source, name),
+                vec![format!("`{}` is declared with `@`; declare it with `@@` to change it", name)],
+            ),
+            VarError::TypeMismatch { .. } => (span, vec![TYPES_NOTE.to_string()]),
+            VarError::UnknownType { name, .. } => {
+                // The type follows the `@` and the variable's name, which may be spelt the same
+                let text = &source[span.start..span.end];
+                let rest = text.trim_start_matches('@').trim_start();
+                let name_end = span.end - rest.len() + rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (Span::new(source, name_end, span.end).narrow(source, name), vec![TYPES_NOTE.to_string()])
+            }
+            VarError::OutOfRange { value, .. } => {
+                // The narrowest type that holds the value, if any does
+                let fits = value.parse::<i128>().ok().and_then(|value| {
+                    ["i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64"]
+                        .into_iter()
+                        .filter_map(IntType::from_name)
+                        .find(|int_type| (int_type.min()..=int_type.max()).contains(&value))
+                });
+                let notes = fits.map(|int_type| format!("`{}` can hold it", int_type)).into_iter().collect();
+                (span.narrow(source, value), notes)
+            }
+            VarError::InvalidFormatSpec { spec, .. } => (
+                span.narrow(source, spec),
+                vec!["a spec is `<` or `>`, a width of up to 999 and a `.precision` of at most 20, as in `{price:>8.2}`"
+                    .to_string()],
+            ),
+            _ => (span, Vec::new()),
+        };
+        let span = (error.line() > 0).then_some(span);
+        Diagnostic {
+            severity: Severity::Error,
+            code: error.code().to_string(),
+            message: error.to_string(),
+            file: None,
+            span,
+            notes,
+            fix: span.and_then(|span| suggest::fix(error, span, names, types)),
+        }
+    }
+
+    pub fn from_backend_error(error: &BackendError) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            code: error.code().to_string(),
+            message: error.to_string(),
+            file: None,
+            span: None,
+            notes: vec!["run with --list-targets to see what each target supports".to_string()],
+            fix: None,
+        }
+    }
+
+    pub fn from_lint(lint: &Lint, source: &str) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            code: lint.rule.name().to_string(),
+            message: lint.message.clone(),
+            file: None,
+            span: Some(Span::new(source, lint.span.0, lint.span.1)),
+            notes: vec![format!("`; ven:allow({})` turns this warning off", lint.rule)],
+            fix: lint.replacement.as_ref().map(|replacement| Fix {
+                message: format!("did you mean `{}`?", replacement),
+                span: Span::new(source, lint.span.0, lint.span.1),
+                replacement: replacement.clone(),
+            }),
+        }
+    }
+
+    pub fn with_file(mut self, file: &str) -> Diagnostic {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    /// `file:line:column: severity: message [code]`
+    pub fn short(&self) -> String {
+        let mut location = self.file.clone().unwrap_or_else(|| "<input>".to_string());
+        if let Some(span) = self.span {
+            location.push_str(&format!(":{}:{}", span.line, span.column));
+        }
+        format!("{}: {}: {} [{}]", location, self.severity, self.message, self.code)
+    }
+
+    pub fn to_json(&self) -> Json {
+        let span = |span: &Span| {
+            Json::object([
+                ("start", span.start.into()),
+                ("end", span.end.into()),
+                ("line", span.line.into()),
+                ("column", span.column.into()),
+                ("end_line", span.end_line.into()),
+                ("end_column", span.end_column.into()),
+            ])
+        };
+        Json::object([
+            ("severity", self.severity.to_string().into()),
+            ("code", self.code.clone().into()),
+            ("message", self.message.clone().into()),
+            ("file", self.file.clone().map(Json::from).unwrap_or(Json::Null)),
+            ("span", self.span.as_ref().map(span).unwrap_or(Json::Null)),
+            ("notes", Json::Array(self.notes.iter().map(|note| note.as_str().into()).collect())),
+            (
+                "fix",
+                match &self.fix {
+                    Some(fix) => Json::object([
+                        ("message", fix.message.clone().into()),
+                        ("span", span(&fix.span)),
+                        ("replacement", fix.replacement.clone().into()),
+                    ]),
+                    None => Json::Null,
+                },
+            ),
+        ])
+    }
+}
+
+/// How diagnostics are printed, chosen with `--error-format=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// Box art with the source line.
+    Human,
+    /// `file:line:column: error: message [code]`
+    Short,
+    /// One JSON object per line.
+    Json,
+}
+
+impl ErrorFormat {
+    pub fn from_name(name: &str) -> Option<ErrorFormat> {
+        match name {
+            "human" => Some(ErrorFormat::Human),
+            "short" => Some(ErrorFormat::Short),
+            "json" => Some(ErrorFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Writes diagnostics in `format`. `source` is the text the spans point into,
+/// used by the human format to show the offending line.
+pub fn emit(out: &mut impl Write, diagnostics: &[Diagnostic], source: Option<&str>, format: ErrorFormat) -> io::Result<()> {
+    for diagnostic in diagnostics {
+        match format {
+            ErrorFormat::Human => write!(out, "{}", render_human(diagnostic, source))?,
+            ErrorFormat::Short => writeln!(out, "{}", diagnostic.short())?,
+            ErrorFormat::Json => writeln!(out, "{}", diagnostic.to_json())?,
+        }
+    }
+    out.flush()
+}
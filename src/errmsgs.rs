@@ -1,82 +1,72 @@
-use crate::errs::VarError;
+use crate::diagnostic::{Diagnostic, Severity};
 
 const _BRANCH_LAST: &str = "└── ";
 const _BRANCH_MID: &str = "├── ";
 const _INDENT: &str = "    ";
-const _EXPL_TYPE_MISMATCH: &str = "The initializer's type does not match the declared type.";
-const _EXPL_VAR_TYPE_MISMATCH: &str =
+pub(crate) const EXPL_TYPE_MISMATCH: &str = "The initializer's type does not match the declared type.";
+pub(crate) const EXPL_VAR_TYPE_MISMATCH: &str =
     "The literal value could not be parsed into the expected type.";
-const _EXPL_UNDECLARED: &str = "The variable was used before being declared.";
-const _EXPL_MISSING_OPEN_PAREN: &str = "Expected '(' after '?' but found none.";
-const _EXPL_MISSING_CLOSE_PAREN: &str = "Expected ')' to close the condition but found none.";
-const _EXPL_MISSING_OPEN_BRACE: &str = "Expected '{' to start the block but found none.";
-const _EXPL_UNMATCHED_CLOSING_BRACE: &str = "Unexpected '}' without a matching opening '{'.";
-const _EXPL_UNEXPECTED_TOKEN: &str = "An unexpected token was encountered.";
+pub(crate) const EXPL_UNDECLARED: &str = "The variable was used before being declared.";
+pub(crate) const EXPL_IMMUTABLE: &str = "The variable was changed but is declared immutable.";
+pub(crate) const EXPL_MISSING_OPEN_PAREN: &str = "Expected '(' after '?' but found none.";
+pub(crate) const EXPL_MISSING_CLOSE_PAREN: &str = "Expected ')' to close the condition but found none.";
+pub(crate) const EXPL_MISSING_OPEN_BRACE: &str = "Expected '{' to start the block but found none.";
+pub(crate) const EXPL_UNMATCHED_CLOSING_BRACE: &str = "Unexpected '}' without a matching opening '{'.";
+pub(crate) const EXPL_UNCLOSED_BLOCK: &str = "Expected '}' to close the block but reached the end.";
+pub(crate) const EXPL_UNKNOWN_TYPE: &str = "The declaration names a type that does not exist.";
+pub(crate) const EXPL_OUT_OF_RANGE: &str = "The literal does not fit the declared integer type.";
+pub(crate) const EXPL_INVALID_ARRAY: &str = "An array is declared, indexed or looped over incorrectly.";
+pub(crate) const EXPL_INVALID_MAP: &str = "A map is declared or used incorrectly, or given a key of the wrong type.";
+pub(crate) const EXPL_INVALID_RECORD: &str =
+    "A record type is declared incorrectly, or a record or field is used where it cannot be.";
+pub(crate) const EXPL_INVALID_CONVERSION: &str = "A value is converted with i(), f() or str() that can never convert.";
+pub(crate) const EXPL_INVALID_STRING_OP: &str =
+    "A string operation is given the wrong number of values, or a value of the wrong kind.";
+pub(crate) const EXPL_INVALID_MATH_OP: &str =
+    "A math function is given the wrong number of values, or a value that is not a number.";
+pub(crate) const EXPL_INVALID_FORMAT_SPEC: &str =
+    "A placeholder's format spec is not an alignment, a width and a precision.";
+pub(crate) const EXPL_UNSUPPORTED: &str = "The target cannot translate a construct the program uses.";
+pub(crate) const EXPL_UNEXPECTED_TOKEN: &str = "An unexpected token was encountered.";
 
-pub fn print_errors(errors: &[VarError]) {
-    if errors.is_empty() {
-        return;
-    }
-    
-    for error in errors.iter() {
-        match error {
-            VarError::UndeclaredVariable { name, line } => {
-                println!("\n╭─ Error at line {}{}─╮", line, "─".repeat(50));
-                println!("│ Undeclared Variable");
-                println!("├{}┤", "─".repeat(65));
-                println!("│ Variable '{}' used but not declared", name);
-                println!("╰{}╯", "─".repeat(65));
-            }
-            VarError::TypeMismatch { expected, found, line } => {
-                println!("\n╭─ Error at line {}{}─╮", line, "─".repeat(50));
-                println!("│ Type Mismatch");
-                println!("├{}┤", "─".repeat(65));
-                println!("│ Expected type: {}", expected);
-                println!("│ Found type: {}", found);
-                println!("╰{}╯", "─".repeat(65));
-            }
-            VarError::ImmutableAssignment { name, line } => {
-                println!("\n╭─ Error at line {}{}─╮", line, "─".repeat(50));
-                println!("│ Assignment to Immutable Variable");
-                println!("├{}┤", "─".repeat(65));
-                println!("│ Cannot assign to immutable variable '{}'", name);
-                println!("╰{}╯", "─".repeat(65));
-            }
-            VarError::MissingConditionOpenParen { line } => {
-                println!("\n╭─ Error at line {}{}─╮", line, "─".repeat(50));
-                println!("│ Missing Opening Parenthesis");
-                println!("├{}┤", "─".repeat(65));
-                println!("│ Expected '(' after '?' in conditional statement");
-                println!("╰{}╯", "─".repeat(65));
-            }
-            VarError::MissingConditionCloseParen { line } => {
-                println!("\n╭─ Error at line {}{}─╮", line, "─".repeat(50));
-                println!("│ Missing Closing Parenthesis");
-                println!("├{}┤", "─".repeat(65));
-                println!("│ Expected ')' to close condition in conditional statement");
-                println!("╰{}╯", "─".repeat(65));
-            }
-            VarError::MissingBlockOpenBrace { line } => {
-                println!("\n╭─ Error at line {}{}─╮", line, "─".repeat(50));
-                println!("│ Missing Opening Brace");
-                println!("├{}┤", "─".repeat(65));
-                println!("│ Expected '{{' to start the block but found none");
-                println!("╰{}╯", "─".repeat(65));
-            }
-            VarError::UnmatchedClosingBrace { line } => {
-                println!("\n╭─ Error at line {}{}─╮", line, "─".repeat(50));
-                println!("│ Unmatched Closing Brace");
-                println!("├{}┤", "─".repeat(65));
-                println!("│ Found '}}' without matching opening brace");
-                println!("╰{}╯", "─".repeat(65));
-            }
-            VarError::InvalidCondition { details, line } => {
-                println!("\n╭─ Error at line {}{}─╮", line, "─".repeat(50));
-                println!("│ Invalid Condition");
-                println!("├{}┤", "─".repeat(65));
-                println!("│ {}", details);
-                println!("╰{}╯", "─".repeat(65));
-            }
+/// Box art for one diagnostic, with the offending source line when `source`
+/// is given.
+pub fn render_human(diagnostic: &Diagnostic, source: Option<&str>) -> String {
+    let mut out = String::new();
+    let kind = match diagnostic.severity {
+        Severity::Error => "Error",
+        Severity::Warning => "Warning",
+    };
+    let at = diagnostic.span.map(|span| format!(" at line {}", span.line)).unwrap_or_default();
+    out.push_str(&format!("\n╭─ {} {}{}{}─╮\n", kind, diagnostic.code, at, "─".repeat(50)));
+    out.push_str(&format!("│ {}\n", diagnostic.message));
+    out.push_str(&format!("├{}┤\n", "─".repeat(65)));
+    if let Some(span) = diagnostic.span {
+        let file = diagnostic.file.as_deref().unwrap_or("<input>");
+        out.push_str(&format!("│ --> {}:{}:{}\n", file, span.line, span.column));
+        if let Some(text) = source.and_then(|source| source.lines().nth(span.line - 1)) {
+            let number = span.line.to_string();
+            let width = if span.end_line == span.line {
+                (span.end_column - span.column).max(1)
+            } else {
+                1
+            };
+            out.push_str(&format!("│ {} | {}\n", number, text));
+            out.push_str(&format!(
+                "│ {} | {}{}\n",
+                " ".repeat(number.len()),
+                " ".repeat(span.column - 1),
+                "^".repeat(width)
+            ));
         }
     }
+    for note in &diagnostic.notes {
+        out.push_str(&format!("│ note: {}\n", note));
+    }
+    if let Some(fix) = &diagnostic.fix {
+        out.push_str(&format!("│ help: {}\n", fix.message));
+    }
+    out.push_str(&format!("╰{}╯\n", "─".repeat(65)));
+    out
 }
+
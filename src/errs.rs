@@ -1,3 +1,5 @@
+use crate::parse::IntType;
+
 #[derive(Debug, Clone)]
 pub enum VarError {
     /// Indicates that a variable was used but not declared.
@@ -24,7 +26,297 @@ pub enum VarError {
     
     /// Unmatched closing brace.
     UnmatchedClosingBrace { line: usize },
+
+    /// A block whose '{' is never closed.
+    UnclosedBlock { line: usize },
+
+    /// A declaration names a type that does not exist, such as `int`.
+    UnknownType { name: String, line: usize },
+
+    /// An integer literal outside the range of its variable's type, such as `@ x u8 300`.
+    OutOfRange { value: String, var_type: IntType, line: usize },
     
     /// Invalid condition
     InvalidCondition { details: String, line: usize },
+
+    /// Malformed native function call
+    InvalidCall { details: String, line: usize },
+
+    /// An array used where it cannot be, or a non-array indexed, measured or looped over
+    InvalidArray { details: String, line: usize },
+
+    /// A map declared or used incorrectly, such as a key of the wrong type
+    InvalidMap { details: String, line: usize },
+
+    /// A record type declared wrongly, or a record or field used where it cannot be
+    InvalidRecord { details: String, line: usize },
+
+    /// A conversion of a literal that can never succeed, or of a value that cannot be converted
+    InvalidConversion { details: String, line: usize },
+
+    /// A string operation given the wrong number or kinds of values
+    InvalidStringOp { details: String, line: usize },
+
+    /// A math function, or `-`, given the wrong number or kinds of values
+    InvalidMathOp { details: String, line: usize },
+
+    /// A `{name:spec}` placeholder whose spec is not an alignment, width and precision
+    InvalidFormatSpec { spec: String, line: usize },
+}
+
+/// Errors raised by a backend for a program that parsed fine.
+#[derive(Debug, Clone)]
+pub enum BackendError {
+    /// The program uses a construct the target cannot translate.
+    Unsupported { target: &'static str, construct: String },
+}
+
+impl BackendError {
+    /// Stable error code, continuing the `VarError` numbering.
+    pub fn code(&self) -> &'static str {
+        match self {
+            BackendError::Unsupported { .. } => "V0010",
+        }
+    }
+}
+
+impl std::fmt::Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::Unsupported { target, construct } => {
+                write!(f, "the {} target does not support {}", target, construct)
+            }
+        }
+    }
+}
+
+/// Reasons `ven fmt` leaves a file as it is.
+#[derive(Debug, Clone)]
+pub enum FormatError {
+    /// The source does not parse.
+    Parse(Vec<VarError>),
+    /// The formatted source would parse to a different program.
+    ChangesMeaning,
+}
+
+impl std::fmt::Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError::Parse(errors) => match errors.first() {
+                Some(first) if errors.len() == 1 => write!(f, "{}", first),
+                Some(first) => write!(f, "{} (and {} more errors)", first, errors.len() - 1),
+                None => f.write_str("the source does not parse"),
+            },
+            FormatError::ChangesMeaning => f.write_str("formatting would change what the program does"),
+        }
+    }
+}
+
+/// Errors raised while running a program in-process with `ven::vm::Vm`.
+#[derive(Debug, Clone)]
+pub enum RuntimeError {
+    /// The source did not parse.
+    Parse(Vec<VarError>),
+    /// Math or a call result targeted a variable that was never declared or set.
+    UndeclaredVariable { name: String },
+    /// Math or a call result targeted an `@` variable.
+    ImmutableAssignment { name: String },
+    /// Math on a `str` or `c[size]` variable.
+    NonNumeric { name: String },
+    /// Division of a variable by zero, or an integer `0 ** y` with a negative
+    /// `y`, on the 1-based source `line`.
+    DivisionByZero { name: String, line: usize },
+    /// An integer did not fit its variable's type under `Overflow::Trap`. Like
+    /// the errors below, `line` is 0 for a value the host stores with `set`.
+    Overflow { name: String, var_type: String, line: usize },
+    /// An array index below 0 or past the last element.
+    IndexOutOfBounds { name: String, index: i128, len: usize, line: usize },
+    /// A map lookup of a key the map does not have. `key` is shown as in the
+    /// message, with text in double quotes.
+    MissingKey { name: String, key: String, line: usize },
+    /// The script called a function the host never registered.
+    UnknownFunction { name: String },
+    /// A native function returned an error.
+    Native { name: String, message: String },
+    /// A native function returned nothing, but the call stores its result.
+    NoValue { name: String },
+    /// `i()` or `f()` of text that is not a number of that kind; `to` is `i`
+    /// or `f`.
+    Conversion { text: String, to: &'static str, line: usize },
+    /// Reading from or writing to a host stream failed.
+    Io(String),
+}
+
+/// ` on line 4`, or nothing for line 0.
+fn on_line(line: usize) -> String {
+    if line == 0 { String::new() } else { format!(" on line {}", line) }
+}
+
+impl std::fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuntimeError::Parse(errors) => write!(f, "the program has {} parse error(s)", errors.len()),
+            RuntimeError::UndeclaredVariable { name } => write!(f, "variable '{}' is not declared", name),
+            RuntimeError::ImmutableAssignment { name } => {
+                write!(f, "cannot assign to immutable variable '{}'", name)
+            }
+            RuntimeError::NonNumeric { name } => write!(f, "math on the non-numeric variable '{}'", name),
+            RuntimeError::DivisionByZero { name, line } => {
+                write!(f, "division of '{}' by zero on line {}", name, line)
+            }
+            RuntimeError::Overflow { name, var_type, line } => {
+                write!(f, "integer overflow in '{}' ({}){}", name, var_type, on_line(*line))
+            }
+            RuntimeError::IndexOutOfBounds { name, index, len, line } => {
+                write!(f, "index {} is out of bounds for '{}' (length {}){}", index, name, len, on_line(*line))
+            }
+            RuntimeError::MissingKey { name, key, line } => {
+                write!(f, "key {} is not in '{}'{}", key, name, on_line(*line))
+            }
+            RuntimeError::UnknownFunction { name } => write!(f, "no native function named '{}'", name),
+            RuntimeError::Native { name, message } => write!(f, "{}: {}", name, message),
+            RuntimeError::NoValue { name } => write!(f, "'{}' returned no value to store", name),
+            RuntimeError::Conversion { text, to, line } => {
+                write!(f, "cannot convert \"{}\" to {}{}", text, to, on_line(*line))
+            }
+            RuntimeError::Io(message) => write!(f, "I/O error: {}", message),
+        }
+    }
+}
+
+impl RuntimeError {
+    /// The source line the error names, if it names one.
+    pub(crate) fn line_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            RuntimeError::DivisionByZero { line, .. }
+            | RuntimeError::Overflow { line, .. }
+            | RuntimeError::IndexOutOfBounds { line, .. }
+            | RuntimeError::MissingKey { line, .. }
+            | RuntimeError::Conversion { line, .. } => Some(line),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RuntimeError {
+    fn from(error: std::io::Error) -> Self {
+        RuntimeError::Io(error.to_string())
+    }
+}
+
+/// The error as a single line, without its position.
+impl std::fmt::Display for VarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VarError::UndeclaredVariable { name, .. } => write!(f, "variable '{}' used but not declared", name),
+            VarError::TypeMismatch { expected, found, .. } => {
+                write!(f, "type mismatch: expected {}, found {}", expected, found)
+            }
+            VarError::ImmutableAssignment { name, .. } => {
+                write!(f, "cannot assign to immutable variable '{}'", name)
+            }
+            VarError::MissingConditionOpenParen { .. } => write!(f, "expected '(' after '?'"),
+            VarError::MissingConditionCloseParen { .. } => write!(f, "expected ')' to close the condition"),
+            VarError::MissingBlockOpenBrace { .. } => write!(f, "expected '{{' to start the block"),
+            VarError::UnmatchedClosingBrace { .. } => write!(f, "'}}' without a matching '{{'"),
+            VarError::UnclosedBlock { .. } => write!(f, "'{{' is never closed with '}}'"),
+            VarError::UnknownType { name, .. } => write!(f, "unknown type '{}'", name),
+            VarError::OutOfRange { value, var_type, .. } => write!(
+                f,
+                "{} is out of range for {} ({} to {})",
+                value,
+                var_type,
+                var_type.min(),
+                var_type.max()
+            ),
+            VarError::InvalidCondition { details, .. } => write!(f, "invalid condition: {}", details),
+            VarError::InvalidCall { details, .. } => write!(f, "invalid call: {}", details),
+            VarError::InvalidArray { details, .. } => write!(f, "invalid array use: {}", details),
+            VarError::InvalidMap { details, .. } => write!(f, "invalid map use: {}", details),
+            VarError::InvalidRecord { details, .. } => write!(f, "invalid record use: {}", details),
+            VarError::InvalidConversion { details, .. } => write!(f, "invalid conversion: {}", details),
+            VarError::InvalidStringOp { details, .. } => write!(f, "invalid string operation: {}", details),
+            VarError::InvalidMathOp { details, .. } => write!(f, "invalid math function: {}", details),
+            VarError::InvalidFormatSpec { spec, .. } => write!(f, "invalid format spec '{}'", spec),
+        }
+    }
+}
+
+impl VarError {
+    /// Stable error code, as shown in diagnostics and accepted by `ven explain`.
+    /// Codes are never reused or renumbered.
+    pub fn code(&self) -> &'static str {
+        match self {
+            VarError::UndeclaredVariable { .. } => "V0001",
+            VarError::TypeMismatch { .. } => "V0002",
+            VarError::ImmutableAssignment { .. } => "V0003",
+            VarError::MissingConditionOpenParen { .. } => "V0004",
+            VarError::MissingConditionCloseParen { .. } => "V0005",
+            VarError::MissingBlockOpenBrace { .. } => "V0006",
+            VarError::UnmatchedClosingBrace { .. } => "V0007",
+            VarError::InvalidCondition { .. } => "V0008",
+            VarError::InvalidCall { .. } => "V0009",
+            VarError::UnclosedBlock { .. } => "V0011",
+            VarError::UnknownType { .. } => "V0012",
+            VarError::OutOfRange { .. } => "V0013",
+            VarError::InvalidArray { .. } => "V0014",
+            VarError::InvalidMap { .. } => "V0015",
+            VarError::InvalidRecord { .. } => "V0016",
+            VarError::InvalidConversion { .. } => "V0017",
+            VarError::InvalidStringOp { .. } => "V0018",
+            VarError::InvalidMathOp { .. } => "V0019",
+            VarError::InvalidFormatSpec { .. } => "V0020",
+        }
+    }
+
+    /// 1-based source line, or 0 when unknown.
+    pub fn line(&self) -> usize {
+        match *self {
+            VarError::UndeclaredVariable { line, .. }
+            | VarError::TypeMismatch { line, .. }
+            | VarError::ImmutableAssignment { line, .. }
+            | VarError::MissingConditionOpenParen { line }
+            | VarError::MissingConditionCloseParen { line }
+            | VarError::MissingBlockOpenBrace { line }
+            | VarError::UnmatchedClosingBrace { line }
+            | VarError::UnclosedBlock { line }
+            | VarError::UnknownType { line, .. }
+            | VarError::OutOfRange { line, .. }
+            | VarError::InvalidCondition { line, .. }
+            | VarError::InvalidCall { line, .. }
+            | VarError::InvalidArray { line, .. }
+            | VarError::InvalidMap { line, .. }
+            | VarError::InvalidRecord { line, .. }
+            | VarError::InvalidConversion { line, .. }
+            | VarError::InvalidStringOp { line, .. }
+            | VarError::InvalidMathOp { line, .. }
+            | VarError::InvalidFormatSpec { line, .. } => line,
+        }
+    }
+
+    /// The line `line()` returns, to move an error found in a longer text to
+    /// the part of it that is shown.
+    pub(crate) fn line_mut(&mut self) -> &mut usize {
+        match self {
+            VarError::UndeclaredVariable { line, .. }
+            | VarError::TypeMismatch { line, .. }
+            | VarError::ImmutableAssignment { line, .. }
+            | VarError::MissingConditionOpenParen { line }
+            | VarError::MissingConditionCloseParen { line }
+            | VarError::MissingBlockOpenBrace { line }
+            | VarError::UnmatchedClosingBrace { line }
+            | VarError::UnclosedBlock { line }
+            | VarError::UnknownType { line, .. }
+            | VarError::OutOfRange { line, .. }
+            | VarError::InvalidCondition { line, .. }
+            | VarError::InvalidCall { line, .. }
+            | VarError::InvalidArray { line, .. }
+            | VarError::InvalidMap { line, .. }
+            | VarError::InvalidRecord { line, .. }
+            | VarError::InvalidConversion { line, .. }
+            | VarError::InvalidStringOp { line, .. }
+            | VarError::InvalidMathOp { line, .. }
+            | VarError::InvalidFormatSpec { line, .. } => line,
+        }
+    }
 }
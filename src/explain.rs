@@ -0,0 +1,537 @@
+//! `ven explain <code>`: the long form of an error or lint warning, with a
+//! program that reports it and the same program fixed.
+//!
+//! Every example is checked against the compiler by `tests/explain.rs` and
+//! `ven explain --check`, so the pages cannot drift from what it reports.
+
+use std::fmt;
+
+use crate::errmsgs::{
+    EXPL_IMMUTABLE, EXPL_INVALID_ARRAY, EXPL_INVALID_CONVERSION, EXPL_INVALID_FORMAT_SPEC, EXPL_INVALID_MAP,
+    EXPL_INVALID_RECORD,
+    EXPL_INVALID_MATH_OP, EXPL_INVALID_STRING_OP,
+    EXPL_MISSING_CLOSE_PAREN, EXPL_MISSING_OPEN_BRACE, EXPL_MISSING_OPEN_PAREN, EXPL_OUT_OF_RANGE, EXPL_TYPE_MISMATCH,
+    EXPL_UNCLOSED_BLOCK, EXPL_UNDECLARED, EXPL_UNEXPECTED_TOKEN, EXPL_UNKNOWN_TYPE, EXPL_UNMATCHED_CLOSING_BRACE,
+    EXPL_UNSUPPORTED, EXPL_VAR_TYPE_MISMATCH,
+};
+use crate::lint::{Config, lint};
+use crate::session::{Options, Session, Target};
+
+pub struct Explanation {
+    /// `V0001`-style for errors, the rule name for lint warnings.
+    pub code: &'static str,
+    pub title: &'static str,
+    pub summary: &'static str,
+    pub details: &'static str,
+    /// A program that reports `code`.
+    pub failing: &'static str,
+    /// The same program with the problem fixed.
+    pub fixed: &'static str,
+}
+
+pub const EXPLANATIONS: [Explanation; 28] = [
+    Explanation {
+        code: "V0001",
+        title: "undeclared variable",
+        summary: EXPL_UNDECLARED,
+        details: "Every variable is declared with `@` or `@@` and a type before it is used, whether
+as an initializer, in math, in a condition or in a `{placeholder}`. Names are
+case-sensitive, so `Count` and `count` are different variables.",
+        failing: "@ total i count
+>> \"{total}\"
+",
+        fixed: "@ count i 3
+@ total i count
+>> \"{total}\"
+",
+    },
+    Explanation {
+        code: "V0002",
+        title: "type mismatch",
+        summary: EXPL_TYPE_MISMATCH,
+        details: EXPL_VAR_TYPE_MISMATCH,
+        failing: "@ age i \"thirty\"
+>> \"{age}\"
+",
+        fixed: "@ age i 30
+>> \"{age}\"
+",
+    },
+    Explanation {
+        code: "V0003",
+        title: "assignment to an immutable variable",
+        summary: EXPL_IMMUTABLE,
+        details: "Variables declared with `@` keep their first value. A variable that math (`*`) or
+input (`..`) changes is declared with `@@`.",
+        failing: "@ count i 0
+* count + 1
+>> \"{count}\"
+",
+        fixed: "@@ count i 0
+* count + 1
+>> \"{count}\"
+",
+    },
+    Explanation {
+        code: "V0004",
+        title: "missing `(` after `?`",
+        summary: EXPL_MISSING_OPEN_PAREN,
+        details: "A condition is written in parentheses right after the `?`: `?(age > 18) { ... }`.",
+        failing: "@ age i 20
+?age > 18) {
+    >> \"adult\"
+}
+",
+        fixed: "@ age i 20
+?(age > 18) {
+    >> \"adult\"
+}
+",
+    },
+    Explanation {
+        code: "V0005",
+        title: "missing `)` after a condition",
+        summary: EXPL_MISSING_CLOSE_PAREN,
+        details: "The parentheses around a condition must be balanced, and the `)` comes before the
+`{` of the block.",
+        failing: "@ age i 20
+?(age > 18 {
+    >> \"adult\"
+}
+",
+        fixed: "@ age i 20
+?(age > 18) {
+    >> \"adult\"
+}
+",
+    },
+    Explanation {
+        code: "V0006",
+        title: "missing `{` to start a block",
+        summary: EXPL_MISSING_OPEN_BRACE,
+        details: "The statements run by a condition are always written in braces, even when there
+is only one of them. The `{` may be on the condition's line or the next one.",
+        failing: "@ age i 20
+?(age > 18)
+    >> \"adult\"
+",
+        fixed: "@ age i 20
+?(age > 18) {
+    >> \"adult\"
+}
+",
+    },
+    Explanation {
+        code: "V0007",
+        title: "`}` without a matching `{`",
+        summary: EXPL_UNMATCHED_CLOSING_BRACE,
+        details: "Each `}` closes the innermost open block. One `}` too many usually means a block
+was closed twice.",
+        failing: "@ age i 20
+?(age > 18) {
+    >> \"adult\"
+}
+}
+",
+        fixed: "@ age i 20
+?(age > 18) {
+    >> \"adult\"
+}
+",
+    },
+    Explanation {
+        code: "V0008",
+        title: "invalid condition",
+        summary: EXPL_UNEXPECTED_TOKEN,
+        details: "A condition compares two values with `==`, `!=`, `<`, `<=`, `>` or `>=`, and joins
+comparisons with `&&` and `||`. Each side is a variable, a number or a quoted
+string.",
+        failing: "@ age i 20
+?(age >) {
+    >> \"adult\"
+}
+",
+        fixed: "@ age i 20
+?(age > 18) {
+    >> \"adult\"
+}
+",
+    },
+    Explanation {
+        code: "V0009",
+        title: "invalid native function call",
+        summary: EXPL_UNEXPECTED_TOKEN,
+        details: "A native call is a name followed by its arguments in parentheses, separated by
+commas, and optionally `-> var` to store the result: `len(name) -> size`.",
+        failing: "@ name str \"Ada\"
+log(name
+",
+        fixed: "@ name str \"Ada\"
+log(name)
+",
+    },
+    Explanation {
+        code: "V0010",
+        title: "construct not supported by the target",
+        summary: EXPL_UNSUPPORTED,
+        details: "Native functions only exist when a host program embeds Ven and registers them, so
+no compile target can translate a call. Some targets lack other features too;
+`ven --list-targets` shows what each one supports.",
+        failing: "@ name str \"Ada\"
+greet(name)
+",
+        fixed: "@ name str \"Ada\"
+>> \"Hello, {name}!\"
+",
+    },
+    Explanation {
+        code: "V0011",
+        title: "block never closed",
+        summary: EXPL_UNCLOSED_BLOCK,
+        details: "Every `{` that starts a block needs a `}` after the block's last statement.",
+        failing: "@ age i 20
+?(age > 18) {
+    >> \"adult\"
+",
+        fixed: "@ age i 20
+?(age > 18) {
+    >> \"adult\"
+}
+",
+    },
+    Explanation {
+        code: "V0012",
+        title: "unknown type",
+        summary: EXPL_UNKNOWN_TYPE,
+        details: "Types are spelt `i` for integers, `f` for floats, `str` for text and `c[size]` for
+text of at most `size` bytes; `i8`..`i64` and `u8`..`u64` are integers of a given width. `ven fix` rewrites names from other languages, such as `int` or
+`string`, to these.",
+        failing: "@ age int 20
+>> \"{age}\"
+",
+        fixed: "@ age i 20
+>> \"{age}\"
+",
+    },
+    Explanation {
+        code: "V0013",
+        title: "literal out of range",
+        summary: EXPL_OUT_OF_RANGE,
+        details: "`i8`..`i64` and `u8`..`u64` hold the integers that fit in that many bits, signed
+or not; `i` is `i64`. Pick a type wide enough for the value, or a value the
+type can hold. Overflow at runtime is handled by `--overflow=` instead.",
+        failing: "@@ level u8 300
+>> \"{level}\"
+",
+        fixed: "@@ level u16 300
+>> \"{level}\"
+",
+    },
+    Explanation {
+        code: "V0014",
+        title: "invalid array use",
+        summary: EXPL_INVALID_ARRAY,
+        details: "Arrays are declared as `T[]` with a list such as `[1, 2, 3]`, read as
+`name[index]` or `len(name)`, changed with `* name[index] = value` or
+`* name[] = value` and looped over with `~(item in name)`. An array cannot be
+used where a single value is expected, and only arrays can be indexed.",
+        failing: "@ total i 6
+>> total[0]
+",
+        fixed: "@ scores i[] [1, 2, 3]
+>> scores[0]
+",
+    },
+    Explanation {
+        code: "V0015",
+        title: "invalid map use",
+        summary: EXPL_INVALID_MAP,
+        details: "Maps are declared as `map[K]V` and start empty, as `{}`. Keys are `str` or
+`i`, values any single-value type. Entries are stored with `* name[key] = value`,
+read as `name[key]` or `len(name)` and tested with `key in name`. The key must
+have the key type, and a map cannot be used where a single value is expected.",
+        failing: "@@ ages map[str]i {}
+* ages[1] = 30
+",
+        fixed: "@@ ages map[str]i {}
+* ages[\"ann\"] = 30
+",
+    },
+    Explanation {
+        code: "V0016",
+        title: "invalid record use",
+        summary: EXPL_INVALID_RECORD,
+        details: "Record types are declared on one line as `# Name { field type, ... }`, with
+integer, `f`, `str`, `b` or `c[size]` fields. A variable of the type is declared
+as `@ p Name { field value, ... }`, read as `p.field` and, when declared with
+`@@`, changed with `* p.field + 1`. Only declared fields exist, and a whole
+record can be printed but not used where a single value is expected.",
+        failing: "# Person { name str, age i }
+@ p Person { name \"Ann\", age 30 }
+>> p.height
+",
+        fixed: "# Person { name str, age i }
+@ p Person { name \"Ann\", age 30 }
+>> p.age
+",
+    },
+    Explanation {
+        code: "V0017",
+        title: "invalid conversion",
+        summary: EXPL_INVALID_CONVERSION,
+        details: "`i(x)` makes an integer, `f(x)` an `f` and `str(x)` the text `>>` prints for
+`x`. Text converts to a number only when it is one, such as `\"42\"` or
+`\" -1.5 \"`; anything else stops the program with a runtime error. A literal
+that can never convert is reported here instead.",
+        failing: "@ n i i(\"4 2\")
+>> \"{n}\"
+",
+        fixed: "@ n i i(\"42\")
+>> \"{n}\"
+",
+    },
+    Explanation {
+        code: "V0018",
+        title: "invalid string operation",
+        summary: EXPL_INVALID_STRING_OP,
+        details: "`concat()`, `len()`, `slice()`, `upper()`, `lower()`, `trim()`, `contains()`,
+`starts_with()` and `replace()` work on text: `str` and `c[size]` values,
+string literals and the results of other string operations. Numbers and
+booleans are turned into text with `str()` first. The start and end of
+`slice()` are integers, and each operation takes a fixed number of values,
+apart from `concat()`, which takes two or more.",
+        failing: "@ n i 42
+@ label str concat(\"#\", n)
+>> label
+",
+        fixed: "@ n i 42
+@ label str concat(\"#\", str(n))
+>> label
+",
+    },
+    Explanation {
+        code: "V0019",
+        title: "invalid math function",
+        summary: EXPL_INVALID_MATH_OP,
+        details: "`abs()`, `sqrt()`, `floor()`, `ceil()` and `round()` take one number, and
+`min()` and `max()` two or more. `-` in front of a value negates a number.
+Numbers are integers and `f` values: literals, variables, elements, fields
+and the results of other functions. Text is turned into a number with `i()`
+or `f()` first.",
+        failing: "@ line str \"-7\"
+@ n i abs(line)
+>> \"{n}\"
+",
+        fixed: "@ line str \"-7\"
+@ n i abs(i(line))
+>> \"{n}\"
+",
+    },
+    Explanation {
+        code: "V0020",
+        title: "invalid format spec",
+        summary: EXPL_INVALID_FORMAT_SPEC,
+        details: "The spec after the colon in `{name:spec}` is an optional `<` (left) or `>` (right)
+alignment, an optional width of up to three digits and an optional `.precision`
+of at most 20. Anything else, such as `^` for centring, a fill character or a
+width over 999, is rejected.",
+        failing: "@ n i 7
+>> \"[{n:^4}]\"
+",
+        fixed: "@ n i 7
+>> \"[{n:>4}]\"
+",
+    },
+    Explanation {
+        code: "unused_variable",
+        title: "variable never read",
+        summary: "A variable is declared but its value is never read.",
+        details: "The optimiser removes variables nobody reads, so the declaration does nothing.
+This is often a typo in the name where the value was meant to be used.",
+        failing: "@ greeting str \"hi\"
+>> \"hello\"
+",
+        fixed: "@ greeting str \"hi\"
+>> \"{greeting}\"
+",
+    },
+    Explanation {
+        code: "never_mutated",
+        title: "mutable variable never changed",
+        summary: "A `@@` variable is never changed, so it could be `@`.",
+        details: "Declaring it with `@` tells the reader, and the compiler, that the value stays
+the same.",
+        failing: "@@ name str \"Ada\"
+>> \"{name}\"
+",
+        fixed: "@ name str \"Ada\"
+>> \"{name}\"
+",
+    },
+    Explanation {
+        code: "constant_condition",
+        title: "condition always true or always false",
+        summary: "A condition is always true or always false.",
+        details: "The condition only uses numbers, strings and `@` variables, so its result is
+known before the program runs: the block always runs, or never does.",
+        failing: "?(1 == 2) {
+    >> \"never\"
+}
+",
+        fixed: "@@ n i 0
+.. n
+?(n == 2) {
+    >> \"two\"
+}
+",
+    },
+    Explanation {
+        code: "mixed_comparison",
+        title: "text compared with a number",
+        summary: "Text is compared with a number, which is never equal.",
+        details: "Values of different types are never equal, so `==` is always false and `!=`
+always true. Compare the text with a quoted string instead.",
+        failing: "@@ answer str \"\"
+.. answer
+?(answer == 3) {
+    >> \"three\"
+}
+",
+        fixed: "@@ answer str \"\"
+.. answer
+?(answer == \"3\") {
+    >> \"three\"
+}
+",
+    },
+    Explanation {
+        code: "division_by_zero",
+        title: "division by zero",
+        summary: "A variable is divided by zero.",
+        details: "Dividing by zero has no result, so the program stops with a runtime error that
+names the variable and the line. With `--no-runtime-checks` it depends on the
+target: the program crashes or the variable becomes infinite.",
+        failing: "@@ total i 10
+* total / 0
+>> \"{total}\"
+",
+        fixed: "@@ total i 10
+* total / 2
+>> \"{total}\"
+",
+    },
+    Explanation {
+        code: "shadowed_declaration",
+        title: "name declared twice",
+        summary: "A name is declared again.",
+        details: "The second declaration replaces the first, which is easy to miss. Give each
+variable its own name, or make it `@@` and change it with math or input.",
+        failing: "@ size i 1
+@ size i 2
+>> \"{size}\"
+",
+        fixed: "@ small i 1
+@ large i 2
+>> \"{small} {large}\"
+",
+    },
+    Explanation {
+        code: "undefined_placeholder",
+        title: "placeholder names no variable",
+        summary: "A `{placeholder}` names no variable and prints `[undefined]`.",
+        details: "Placeholders are replaced with the value of the variable they name. A misspelt
+name is also an error, `V0001`, and the warning suggests the closest name. A field the
+record does not have prints `[undefined]`, and so does a whole array, map or record given
+a format spec such as `{items:>5}`.",
+        failing: "@ name str \"Ada\"
+>> \"Hello, {nmae}!\"
+",
+        fixed: "@ name str \"Ada\"
+>> \"Hello, {name}!\"
+",
+    },
+    Explanation {
+        code: "unknown_rule",
+        title: "`ven:allow` names an unknown rule",
+        summary: "A `ven:allow` comment names a rule that does not exist.",
+        details: "Such a comment turns nothing off. `ven explain` lists the rule names.",
+        failing: "; ven:allow(unused_variables)
+@ spare i 1
+",
+        fixed: "; ven:allow(unused_variable)
+@ spare i 1
+",
+    },
+];
+
+/// The explanation for `code`. Error codes are matched case-insensitively.
+pub fn find(code: &str) -> Option<&'static Explanation> {
+    EXPLANATIONS.iter().find(|explanation| explanation.code.eq_ignore_ascii_case(code))
+}
+
+/// Every code `source` is reported with: the errors each target's compilation
+/// stops on, and lint warnings.
+pub fn reported_codes(source: &str) -> Vec<String> {
+    let session = Session::new(source);
+    let mut codes = Vec::new();
+    for target in Target::all() {
+        if let Err(diagnostics) = session.compile(target, &Options::default()) {
+            codes.extend(diagnostics.errors.iter().map(|error| error.code().to_string()));
+            codes.extend(diagnostics.backend.iter().map(|error| error.code().to_string()));
+        }
+    }
+    codes.extend(lint(source, &Config::default()).iter().map(|lint| lint.rule.name().to_string()));
+    codes.sort();
+    codes.dedup();
+    codes
+}
+
+/// Checks that the failing example reports the explained code, and that the
+/// fixed one does not, and compiles without parse or semantic errors. Targets
+/// may still reject the fixed example for constructs they lack.
+pub fn check(explanation: &Explanation) -> Result<(), String> {
+    let failing = reported_codes(explanation.failing);
+    if !failing.iter().any(|code| code == explanation.code) {
+        return Err(format!(
+            "{}: the erroneous example reports [{}] instead",
+            explanation.code,
+            failing.join(", ")
+        ));
+    }
+    if let Some(error) = Session::new(explanation.fixed).errors().first() {
+        return Err(format!("{}: the fixed example reports {}", explanation.code, error.code()));
+    }
+    if reported_codes(explanation.fixed).iter().any(|code| code == explanation.code) {
+        return Err(format!("{}: the fixed example still reports it", explanation.code));
+    }
+    Ok(())
+}
+
+/// The explanation as a page, examples indented by four spaces.
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let example = |f: &mut fmt::Formatter<'_>, code: &str| {
+            for line in code.lines() {
+                if line.is_empty() {
+                    writeln!(f)?;
+                } else {
+                    writeln!(f, "    {}", line)?;
+                }
+            }
+            Ok(())
+        };
+        writeln!(f, "{}: {}", self.code, self.title)?;
+        writeln!(f)?;
+        writeln!(f, "{}", self.summary)?;
+        writeln!(f)?;
+        writeln!(f, "{}", self.details)?;
+        writeln!(f)?;
+        writeln!(f, "Erroneous example:")?;
+        writeln!(f)?;
+        example(f, self.failing)?;
+        writeln!(f)?;
+        writeln!(f, "Fixed:")?;
+        writeln!(f)?;
+        example(f, self.fixed)
+    }
+}
@@ -0,0 +1,328 @@
+//! `ven fmt`: prints a program in its canonical layout.
+//!
+//! The formatter works on the lossless [`Cst`], so comments and blank lines
+//! survive. It normalizes:
+//! - spacing: one space between the parts of a statement (`@@ age i 0`,
+//!   `* age + 1`, `?(age > 18 && ok == 1) {`)
+//! - indentation: four spaces per block level, one statement per line
+//! - braces: `{` ends the `?(...)` line, `}` stands on its own line
+//! - comments: comment-only lines follow the indentation, and trailing comments
+//!   on consecutive lines are aligned one space after the longest line
+//! - blank lines: runs collapse into one, and none are kept at the start or end
+//!   of the file or of a block
+//!
+//! Print statements keep their text after `>>` as written, since a bare-word
+//! print prints it. Sources with parse errors are left alone, and so is any
+//! source whose formatted text would parse to a different AST.
+
+use crate::cst::{Cst, CstToken, Node, StatementKind, Trivia, TriviaKind};
+use crate::errs::FormatError;
+use crate::session::Session;
+use crate::token::TokenKind;
+
+const INDENT: &str = "    ";
+
+enum Line {
+    Code {
+        depth: usize,
+        code: String,
+        comment: Option<String>,
+    },
+    Comment {
+        depth: usize,
+        text: String,
+    },
+    Blank,
+    /// Marks the start or end of a block, where blank lines are dropped.
+    Edge,
+}
+
+/// Formats `source`. Formatting is idempotent: formatting the result again
+/// gives the same text.
+pub fn format(source: &str) -> Result<String, FormatError> {
+    let session = Session::new(source);
+    if !session.errors().is_empty() {
+        return Err(FormatError::Parse(session.errors().to_vec()));
+    }
+    let formatted = format_cst(&Cst::parse(source));
+    let check = Session::new(formatted.as_str());
+    if !check.errors().is_empty() || !check.ast().same_meaning(session.ast()) {
+        return Err(FormatError::ChangesMeaning);
+    }
+    Ok(formatted)
+}
+
+/// The canonical layout of `cst`, without checking that it keeps its meaning.
+pub fn format_cst(cst: &Cst) -> String {
+    let mut lines = vec![Line::Edge];
+    nodes(&cst.nodes, 0, &mut lines);
+    leading(&cst.end, 0, &mut lines);
+    lines.push(Line::Edge);
+    render(&lines)
+}
+
+/// Comment-only lines and blank lines before a line-starting token.
+fn leading(trivia: &[Trivia], depth: usize, lines: &mut Vec<Line>) {
+    let mut content = false;
+    for trivia in trivia {
+        match trivia.kind {
+            TriviaKind::Comment => {
+                lines.push(Line::Comment {
+                    depth,
+                    text: trivia.text.trim_end().to_string(),
+                });
+                content = true;
+            }
+            TriviaKind::Newline => {
+                if !content {
+                    lines.push(Line::Blank);
+                }
+                content = false;
+            }
+            TriviaKind::Whitespace => {}
+        }
+    }
+}
+
+fn comments(tokens: &[CstToken]) -> Option<String> {
+    let comments: Vec<&str> = tokens
+        .iter()
+        .filter_map(|token| token.trailing_comment())
+        .map(str::trim_end)
+        .collect();
+    (!comments.is_empty()).then(|| comments.join(" "))
+}
+
+fn nodes(nodes: &[Node], depth: usize, lines: &mut Vec<Line>) {
+    for node in nodes {
+        match node {
+            Node::Statement { kind, tokens } => {
+                leading(&tokens[0].leading, depth, lines);
+                let code = match kind {
+                    StatementKind::Print => print(tokens),
+                    StatementKind::Other => verbatim(tokens),
+                    _ => spaced(tokens),
+                };
+                lines.push(Line::Code {
+                    depth,
+                    code,
+                    comment: comments(tokens),
+                });
+            }
+            Node::Block { header, body, close } => {
+                leading(&header[0].leading, depth, lines);
+                // The `{` goes on the condition line, whatever line it was on
+                let (brace, condition) = header.split_last().unwrap();
+                let mut code = spaced(condition);
+                code.push_str(" {");
+                lines.push(Line::Code {
+                    depth,
+                    code,
+                    comment: comments(header),
+                });
+                // Comments before `{` on its own line stay with the block
+                lines.push(Line::Edge);
+                leading(&brace.leading, depth + 1, lines);
+                self::nodes(body, depth + 1, lines);
+                if let Some(close) = close {
+                    leading(&close.leading, depth + 1, lines);
+                    lines.push(Line::Edge);
+                    lines.push(Line::Code {
+                        depth,
+                        code: "}".to_string(),
+                        comment: comments(std::slice::from_ref(close)),
+                    });
+                } else {
+                    lines.push(Line::Edge);
+                }
+            }
+        }
+    }
+}
+
+/// The whitespace written between two tokens of a line.
+fn gap(before: &CstToken, after: &CstToken, code: &mut String) {
+    for trivia in before.trailing.iter().chain(&after.leading) {
+        if trivia.kind == TriviaKind::Whitespace {
+            code.push_str(&trivia.text);
+        }
+    }
+}
+
+/// The tokens as written, with the whitespace between them.
+fn verbatim(tokens: &[CstToken]) -> String {
+    let mut code = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 {
+            gap(&tokens[i - 1], token, &mut code);
+        }
+        code.push_str(&token.text);
+    }
+    code
+}
+
+/// `>>`/`>>>` and a single space, then the printed text as written.
+fn print(tokens: &[CstToken]) -> String {
+    let arrows = tokens.iter().take(3).take_while(|t| t.kind == TokenKind::Greater).count();
+    let mut code = ">".repeat(arrows);
+    if arrows < tokens.len() {
+        code.push(' ');
+        code.push_str(&verbatim(&tokens[arrows..]));
+    }
+    code
+}
+
+/// Whether the `-` at `i` negates its operand rather than subtracting.
+fn is_unary_minus(tokens: &[CstToken], i: usize) -> bool {
+    if tokens[i].kind != TokenKind::Minus || tokens[i].is_arrow_start(tokens.get(i + 1)) {
+        return false;
+    }
+    // Declarations have no arithmetic: `@ low i -5`
+    if tokens[0].kind == TokenKind::At {
+        return true;
+    }
+    match i.checked_sub(1).map(|before| &tokens[before]) {
+        None => true,
+        Some(before) => {
+            before.is_comma()
+                || matches!(
+                    before.kind,
+                    TokenKind::LSmallB
+                        | TokenKind::LBigB
+                        | TokenKind::Plus
+                        | TokenKind::Minus
+                        | TokenKind::Star
+                        | TokenKind::Slash
+                        | TokenKind::Percent
+                        | TokenKind::Power
+                        | TokenKind::Equals
+                        | TokenKind::Equal
+                        | TokenKind::NotEqual
+                        | TokenKind::LessThan
+                        | TokenKind::LessEqual
+                        | TokenKind::Greater
+                        | TokenKind::GreaterThan
+                        | TokenKind::GreaterEqual
+                        | TokenKind::And
+                        | TokenKind::Or
+                )
+        }
+    }
+}
+
+/// The tokens joined by single spaces, except inside `@@`, `?(`, `~(`, calls,
+/// brackets, map types, `{}`, `->`, after `!` and before `,`. Around other unknown characters, such as the
+/// quotes of a char literal or the dot of a number, the spacing is kept as
+/// written.
+fn spaced(tokens: &[CstToken]) -> String {
+    let mut code = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 {
+            let (before, after) = (&tokens[i - 1], token);
+            if is_opaque(before) || is_opaque(after) {
+                gap(before, after, &mut code);
+            } else if space_between(tokens, i) {
+                code.push(' ');
+            }
+        }
+        code.push_str(&token.text);
+    }
+    code
+}
+
+fn is_opaque(token: &CstToken) -> bool {
+    token.kind == TokenKind::Unknown && !token.is_comma()
+}
+
+fn space_between(tokens: &[CstToken], i: usize) -> bool {
+    let (before, token) = (&tokens[i - 1], &tokens[i]);
+    let glued = (before.kind == TokenKind::At && token.kind == TokenKind::At)
+        || before.is_arrow_start(Some(token))
+        || token.is_comma()
+        || before.kind == TokenKind::Question
+        || before.kind == TokenKind::Tilde
+        || before.kind == TokenKind::Not
+        || before.kind == TokenKind::LSmallB
+        || token.kind == TokenKind::RSmallB
+        || (token.kind == TokenKind::LSmallB && before.kind == TokenKind::Identifier)
+        || before.kind == TokenKind::LBigB
+        // The list after an array type keeps its space: `i[] [1, 2]`
+        || (token.kind == TokenKind::LBigB && before.kind != TokenKind::RBigB)
+        || token.kind == TokenKind::RBigB
+        // A map type and the `{}` it starts as: `map[str]i {}`
+        || (before.kind == TokenKind::RBigB && is_map_key(tokens, i - 1))
+        || (before.kind == TokenKind::LCurlyB && token.kind == TokenKind::RCurlyB)
+        || is_unary_minus(tokens, i - 1);
+    !glued
+}
+
+/// Whether the `]` at `i` closes the key of a `map[K]` type.
+fn is_map_key(tokens: &[CstToken], i: usize) -> bool {
+    let open = tokens[..i].iter().rposition(|t| t.kind == TokenKind::LBigB);
+    open.and_then(|open| open.checked_sub(1)).is_some_and(|map| tokens[map].text == "map")
+}
+
+fn render(lines: &[Line]) -> String {
+    let mut out = String::new();
+    let mut i = 0;
+    let mut blank = false;
+    let mut at_edge = true;
+    while i < lines.len() {
+        match &lines[i] {
+            Line::Blank => blank = !at_edge,
+            Line::Edge => {
+                blank = false;
+                at_edge = true;
+            }
+            Line::Comment { depth, text } => {
+                flush_blank(&mut out, &mut blank);
+                at_edge = false;
+                out.push_str(&INDENT.repeat(*depth));
+                out.push_str(text);
+                out.push('\n');
+            }
+            Line::Code { .. } => {
+                flush_blank(&mut out, &mut blank);
+                at_edge = false;
+                // A run of consecutive code lines with trailing comments shares one comment column
+                let run = lines[i..]
+                    .iter()
+                    .take_while(|line| matches!(line, Line::Code { comment: Some(_), .. }))
+                    .count()
+                    .max(1);
+                let width = lines[i..i + run]
+                    .iter()
+                    .map(|line| match line {
+                        Line::Code { depth, code, .. } => INDENT.len() * depth + code.chars().count(),
+                        _ => 0,
+                    })
+                    .max()
+                    .unwrap_or(0);
+                for line in &lines[i..i + run] {
+                    if let Line::Code { depth, code, comment } = line {
+                        let mut text = INDENT.repeat(*depth);
+                        text.push_str(code);
+                        if let Some(comment) = comment {
+                            let pad = width - text.chars().count() + 1;
+                            text.push_str(&" ".repeat(pad));
+                            text.push_str(comment);
+                        }
+                        out.push_str(&text);
+                        out.push('\n');
+                    }
+                }
+                i += run;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    out
+}
+
+fn flush_blank(out: &mut String, blank: &mut bool) {
+    if *blank {
+        out.push('\n');
+        *blank = false;
+    }
+}
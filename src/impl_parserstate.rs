@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::{errs::VarError, parse::*, parse1::parse1, parse2::parse2, token::{Token, TokenKind}};
+use crate::{errs::VarError, parse::*, parse1::parse1, parse2::{parse2, parse_access, parse_condition, parse_foreach, parse_operand}, token::{Token, TokenKind}};
 
 
 pub struct ParserState<'a> {
@@ -8,6 +8,8 @@ pub struct ParserState<'a> {
     pub input: &'a str,
     pub pos: usize,
     pub global_var_map: &'a mut HashMap<String, (VarType, usize)>,
+    /// Record types declared so far, by name.
+    pub types: &'a mut HashMap<String, RecordType>,
     pub errors: Vec<VarError>,
     pub global_pos: usize, // This is the global position tracker
 }
@@ -25,13 +27,13 @@ impl<'a> ParserState<'a> {
 
     #[inline(always)]
     fn get_lexeme(&self, token: &Token) -> &str {
-        // SAFETY: the token indices are assumed to be valid.
-        unsafe { std::str::from_utf8_unchecked(&self.input.as_bytes()[token.start..token.end]) }
+        // Non-ASCII characters are tokenized byte by byte and have no text of their own
+        self.input.get(token.start..token.end).unwrap_or("")
     }
 
     #[inline(always)]
     fn skip_whitespace(&mut self) {
-        println!("DEBUG SKIP: Before skip_whitespace pos={}", self.pos);
+        trace!("DEBUG SKIP: Before skip_whitespace pos={}", self.pos);
         while self.pos < self.tokens.len() {
             let token = &self.tokens[self.pos];
             if token.kind == TokenKind::Unknown && self.get_lexeme(token).trim().is_empty() {
@@ -40,7 +42,7 @@ impl<'a> ParserState<'a> {
                 break;
             }
         }
-        println!("DEBUG SKIP: After skip_whitespace pos={}", self.pos);
+        trace!("DEBUG SKIP: After skip_whitespace pos={}", self.pos);
     }
 
     #[inline(always)]
@@ -66,19 +68,44 @@ impl<'a> ParserState<'a> {
         
         // Special handling for DoubleDot token at the correct position
         if self.pos < self.tokens.len() && self.tokens[self.pos].kind == TokenKind::DoubleDot {
-            println!("DEBUG: Found DoubleDot at position {}", self.pos);
+            trace!("DEBUG: Found DoubleDot at position {}", self.pos);
             self.pos += 1; // Skip the ".." token
             self.skip_whitespace();
             
-            if let Some(name_token) = self.current_token() {
-                if name_token.kind == TokenKind::Identifier {
-                    let name = self.get_lexeme(name_token).to_string();
-                    println!("DEBUG: Input variable name: {}", name);
-                    self.pos += 1;
-                    self.consume_until_newline();
-                    
-                    return Some(ASTNode::Input { name });
+            if let Some(name_token) = self.current_token()
+                && name_token.kind == TokenKind::Identifier
+            {
+                let name = self.get_lexeme(name_token).to_string();
+                trace!("DEBUG: Input variable name: {}", name);
+                let line = self.get_line_number(self.pos);
+                self.pos += 1;
+                self.consume_until_newline();
+                match self.global_var_map.get(&name) {
+                    Some((VarType::Array(element), _)) => {
+                        self.errors.push(VarError::InvalidArray {
+                            details: format!("cannot read a line into the array '{}'; read it into a {} and append that", name, element),
+                            line,
+                        });
+                        return None;
+                    }
+                    Some((VarType::Map(_, value), _)) => {
+                        self.errors.push(VarError::InvalidMap {
+                            details: format!("cannot read a line into the map '{}'; read it into a {} and store that under a key", name, value),
+                            line,
+                        });
+                        return None;
+                    }
+                    Some((VarType::Record(record), _)) => {
+                        self.errors.push(VarError::InvalidRecord {
+                            details: format!("cannot read a line into the {} record '{}'; read it into a variable and store that in a field", record.name, name),
+                            line,
+                        });
+                        return None;
+                    }
+                    _ => {}
                 }
+
+                return Some(ASTNode::Input { name, line });
             }
             
             return None;
@@ -88,42 +115,65 @@ impl<'a> ParserState<'a> {
             let token_kind = token.kind;
             let lexeme = self.get_lexeme(token);
             
-            println!("DEBUG STMT: Parsing token with kind {:?} and lexeme \"{}\"", token_kind, lexeme);
+            trace!("DEBUG STMT: Parsing token with kind {:?} and lexeme \"{}\"", token_kind, lexeme);
             
             // Handle different statement types based on token kind or lexeme
             match token_kind {
                 TokenKind::Question => {
-                    println!("DEBUG STMT: Found Question token");
+                    trace!("DEBUG STMT: Found Question token");
                     // This is a conditional statement
                     if let Some(cond_node) = parse2(all_tokens, self.pos, self.input, self) {
                         return Some(cond_node);
                     }
                     return None;
                 },
+                TokenKind::Tilde => {
+                    trace!("DEBUG STMT: Found Tilde token (loop)");
+                    return parse_foreach(self.tokens, self.pos, self.input, self);
+                },
+                TokenKind::Hash => {
+                    trace!("DEBUG STMT: Found Hash token (record type declaration)");
+                    return self.parse_record_declaration();
+                },
+                TokenKind::Star if self.is_array_store() => {
+                    trace!("DEBUG STMT: Found Star token before an element (array store)");
+                    return self.parse_array_store();
+                },
                 TokenKind::Star => {
-                    println!("DEBUG STMT: Found Star token");
+                    trace!("DEBUG STMT: Found Star token");
                     // This is a math operation
-                    let token_clone = token.clone();
                     self.pos += 1; // consume "*" token
-                    if let Some(math_node) = parse1(&self.tokens[self.pos..], self.input, &token_clone) {
+                    let line = self.get_line_number(self.pos);
+                    if let Some(math_node) = parse1(&self.tokens[self.pos..], self.input, line) {
                         self.consume_until_newline();
+                        if let ASTNode::MathOp { ref name, ref field, ref operand, .. } = math_node {
+                            match field {
+                                Some(field) => self.check_field_math(name, field, line),
+                                None => self.check_array_use(&Expression::Identifier(name.clone()), line),
+                            }
+                            self.check_array_use(operand, line);
+                        }
                         return Some(math_node);
                     }
                     return None;
                 },
                 TokenKind::At => {
-                    println!("DEBUG STMT: Found At token (variable declaration)");
+                    trace!("DEBUG STMT: Found At token (variable declaration)");
                     // This is a variable declaration
                     return self.parse_var_declaration();
                 },
                 TokenKind::Greater => {
-                    println!("DEBUG STMT: Found Greater token (print operation)");
+                    trace!("DEBUG STMT: Found Greater token (print operation)");
                     // This is a print operation
                     let count = self.count_consecutive_greater();
                     return self.parse_print(count);
                 },
+                TokenKind::Identifier if self.next_significant_kind(1) == Some(TokenKind::LSmallB) => {
+                    trace!("DEBUG STMT: Found Identifier followed by '(' (native call)");
+                    return self.parse_call();
+                },
                 _ => {
-                    println!("DEBUG STMT: Unknown token kind: {:?}", token_kind);
+                    trace!("DEBUG STMT: Unknown token kind: {:?}", token_kind);
                     // For all other token types, skip and continue
                     self.pos += 1;
                     return None;
@@ -135,13 +185,15 @@ impl<'a> ParserState<'a> {
 
     #[inline(always)]
     fn parse_var_declaration(&mut self) -> Option<ASTNode> {
+        let line = self.get_line_number(self.pos);
+
         // Check if the variable is mutable
         let mut mutable = false;
-        if let Some(next) = self.peek_token(1) {
-            if self.get_lexeme(next) == "@" {
-                mutable = true;
-                self.pos += 1; // Consume the second '@'
-            }
+        if let Some(next) = self.peek_token(1)
+            && self.get_lexeme(next) == "@"
+        {
+            mutable = true;
+            self.pos += 1; // Consume the second '@'
         }
 
         self.pos += 1; // Consume '@'
@@ -160,9 +212,9 @@ impl<'a> ParserState<'a> {
         let type_token = self.current_token()?;
         let type_lex = self.get_lexeme(type_token);
         let var_type = match type_lex {
-            "i" => {
+            name if let Some(int_type) = IntType::from_name(name) => {
                 self.pos += 1;
-                VarType::Int
+                VarType::Int(int_type)
             }
             "str" => {
                 self.pos += 1;
@@ -172,6 +224,14 @@ impl<'a> ParserState<'a> {
                 self.pos += 1;
                 VarType::Float
             }
+            "b" => {
+                self.pos += 1;
+                VarType::Bool
+            }
+            "map" => {
+                self.pos += 1;
+                self.parse_map_type(line)?
+            }
             "c" => {
                 self.pos += 1;
                 self.skip_whitespace();
@@ -201,25 +261,72 @@ impl<'a> ParserState<'a> {
                     return None;
                 }
             }
-            _ => return None,
+            name if let Some(record) = self.types.get(name) => {
+                let record = VarType::Record(record.clone());
+                self.pos += 1;
+                record
+            }
+            _ => {
+                if type_token.kind == TokenKind::Identifier {
+                    self.errors.push(VarError::UnknownType {
+                        name: type_lex.to_string(),
+                        line,
+                    });
+                }
+                return None;
+            }
+        };
+
+        // `T[]` right after the type declares an array of `T`
+        let var_type = if !matches!(var_type, VarType::Char { .. } | VarType::Map(..))
+            && self.current_token().map(|t| t.kind) == Some(TokenKind::LBigB)
+            && self.peek_token(1).map(|t| t.kind) == Some(TokenKind::RBigB)
+        {
+            if let VarType::Record(record) = &var_type {
+                self.errors.push(VarError::InvalidRecord {
+                    details: format!("arrays of records are not supported; '{}' cannot be a {}[]", name, record.name),
+                    line,
+                });
+                self.consume_until_newline();
+                return None;
+            }
+            self.pos += 2;
+            VarType::Array(Box::new(var_type))
+        } else {
+            var_type
         };
         self.skip_whitespace();
 
-        // Parse optional initializer
-        let mut value = self.parse_expression_until_newline();
+        // Parse optional initializer; a bool takes a whole condition, an array a
+        // list and a map starts empty
+        let mut value = match var_type {
+            VarType::Bool => self.parse_condition_until_newline(line),
+            VarType::Array(_) => self.parse_list_until_newline(line),
+            VarType::Map(..) => self.parse_empty_map(&name, line),
+            VarType::Record(ref record) => self.parse_record_initializer(record, line),
+            VarType::Char { .. } if self.current_token().is_some_and(|t| self.get_lexeme(t) == "'") => {
+                self.parse_char_literal()
+            }
+            _ => self.parse_expression_until_newline(),
+        };
+        match value {
+            Some(Expression::List(ref items)) => {
+                for item in items {
+                    self.check_array_use(item, line);
+                }
+            }
+            Some(ref expr) => self.check_array_use(expr, line),
+            None => {}
+        }
 
         // Ensure string literals have proper quotes stripped
         if let Some(Expression::Literal(ref mut lit)) = value {
             match var_type {
-                VarType::String => {
-                    if lit.starts_with('\"') && lit.ends_with('\"') {
-                        *lit = lit[1..lit.len() - 1].to_string();
-                    }
+                VarType::String | VarType::Char { .. } if lit.starts_with('\"') && lit.ends_with('\"') => {
+                    *lit = lit[1..lit.len() - 1].to_string();
                 }
-                VarType::Char { .. } => {
-                    if lit.starts_with('\'') && lit.ends_with('\'') {
-                        *lit = lit[1..lit.len() - 1].to_string();
-                    }
+                VarType::Char { .. } if lit.starts_with('\'') && lit.ends_with('\'') => {
+                    *lit = lit[1..lit.len() - 1].to_string();
                 }
                 _ => {}
             }
@@ -227,13 +334,14 @@ impl<'a> ParserState<'a> {
 
         // Store variable in global map
         self.global_var_map
-            .insert(name.clone(), (var_type.clone(), 0));
+            .insert(name.clone(), (var_type.clone(), line));
 
         Some(ASTNode::VarDeclaration {
             mutable,
             name,
             var_type,
             value,
+            line,
         })
     }
 
@@ -242,25 +350,683 @@ impl<'a> ParserState<'a> {
         let to_stderr = count == 3;
         self.pos += count; // consume '>' tokens
         self.skip_whitespace();
+        let line = self.get_line_number(self.pos);
         
         // Check if the expression is a string literal
-        if let Some(token) = self.current_token() {
-            if token.kind == TokenKind::StringLiteral {
-                let literal = self.get_lexeme(token).to_string();
-                self.pos += 1; // Consume the string literal token
-                self.consume_until_newline();
-                
-                return Some(ASTNode::Print { 
-                    to_stderr, 
-                    expr: Some(Expression::Literal(literal)) 
-                });
-            }
+        if let Some(token) = self.current_token()
+            && token.kind == TokenKind::StringLiteral
+        {
+            let literal = self.get_lexeme(token).to_string();
+            self.pos += 1; // Consume the string literal token
+            self.consume_until_newline();
+
+            return Some(ASTNode::Print {
+                to_stderr,
+                expr: Some(Expression::Literal(literal)),
+                line,
+            });
         }
         
-        // Handle other expressions
+        // Handle other expressions; a whole array or map may be printed
         let expr = self.parse_expression_until_newline();
+        match expr {
+            Some(Expression::Identifier(ref name))
+                if matches!(
+                    self.global_var_map.get(name),
+                    Some((VarType::Array(_) | VarType::Map(..) | VarType::Record(_), _))
+                ) => {}
+            Some(ref expr) => self.check_array_use(expr, line),
+            None => {}
+        }
         self.consume_until_newline();
-        Some(ASTNode::Print { to_stderr, expr })
+        Some(ASTNode::Print { to_stderr, expr, line })
+    }
+
+    /// Whether the `*` at the current position is followed by `name[`.
+    #[inline(always)]
+    fn is_array_store(&self) -> bool {
+        let mut significant = self.tokens[self.pos + 1..]
+            .iter()
+            .filter(|t| !(t.kind == TokenKind::Unknown && self.get_lexeme(t).trim().is_empty()));
+        significant.next().map(|t| t.kind) == Some(TokenKind::Identifier)
+            && significant.next().map(|t| t.kind) == Some(TokenKind::LBigB)
+    }
+
+    /// Parses "* name[index] = value" and "* name[] = value", and "* name[key] = value"
+    /// for maps.
+    #[inline(always)]
+    fn parse_array_store(&mut self) -> Option<ASTNode> {
+        let line = self.get_line_number(self.pos);
+        self.pos += 1; // consume '*'
+        self.skip_whitespace();
+        let name = self.get_lexeme(self.current_token()?).to_string();
+        self.pos += 1;
+        self.skip_whitespace();
+        let open = self.pos;
+        let end = self.line_end();
+        let close = (open..end).find(|&i| self.tokens[i].kind == TokenKind::RBigB);
+        let equals = close.and_then(|close| (close + 1..end).find(|&i| self.tokens[i].kind == TokenKind::Equals));
+        self.pos = end;
+        let (Some(close), Some(equals)) = (close, equals) else {
+            let error = match self.global_var_map.get(&name) {
+                Some((VarType::Map(..), _)) => VarError::InvalidMap {
+                    details: format!("expected `* {}[key] = value`", name),
+                    line,
+                },
+                _ => VarError::InvalidArray {
+                    details: format!("expected `* {}[index] = value` or `* {}[] = value`", name, name),
+                    line,
+                },
+            };
+            self.errors.push(error);
+            return None;
+        };
+
+        match self.global_var_map.get(&name) {
+            Some((VarType::Array(_) | VarType::Map(..), _)) => {}
+            Some((other, _)) => {
+                self.errors.push(VarError::InvalidArray {
+                    details: format!("'{}' is a {} variable, not an array", name, other),
+                    line,
+                });
+                return None;
+            }
+            None => {
+                self.errors.push(VarError::UndeclaredVariable { name, line });
+                return None;
+            }
+        }
+        let value = match parse_condition(self.tokens, equals + 1, end, self.input, line) {
+            Ok(value) => value,
+            Err(details) => {
+                self.errors.push(VarError::InvalidCondition { details, line });
+                return None;
+            }
+        };
+        self.check_array_use(&value, line);
+
+        let key_type = match self.global_var_map.get(&name) {
+            Some((VarType::Map(key_type, _), _)) => Some((**key_type).clone()),
+            _ => None,
+        };
+        match parse_operand(self.tokens, open + 1, close, self.input) {
+            None if key_type.is_some() => {
+                self.errors.push(VarError::InvalidMap {
+                    details: format!(
+                        "a map has no end to append to; store the value under a key with `* {}[key] = value`",
+                        name
+                    ),
+                    line,
+                });
+                None
+            }
+            None => Some(ASTNode::Append { name, value, line }),
+            Some(index) => {
+                self.check_array_use(&index, line);
+                if let Some(key_type) = key_type {
+                    self.check_map_key(&name, &key_type, &index, line);
+                }
+                Some(ASTNode::SetIndex { name, index, value, line })
+            }
+        }
+    }
+
+    /// Reports each array or map in `expr` used as a whole value, each element,
+    /// key or length taken of something that is neither, and each index or key
+    /// of the wrong type.
+    pub fn check_array_use(&mut self, expr: &Expression, line: usize) {
+        let details = match expr {
+            Expression::Identifier(name) => match self.global_var_map.get(name) {
+                Some((VarType::Array(_), _)) => format!(
+                    "'{}' is an array; use an element like {}[0] or its length len({})",
+                    name, name, name
+                ),
+                Some((VarType::Map(..), _)) => {
+                    let details = format!("'{}' is a map; use a value like {}[key] or its length len({})", name, name, name);
+                    self.errors.push(VarError::InvalidMap { details, line });
+                    return;
+                }
+                Some((VarType::Record(record), _)) => {
+                    let details = format!(
+                        "'{}' is a record; use a field like {}.{}",
+                        name,
+                        name,
+                        record.fields.first().map_or("field", |(field, _)| field.as_str())
+                    );
+                    self.errors.push(VarError::InvalidRecord { details, line });
+                    return;
+                }
+                _ => return,
+            },
+            Expression::Field { name, field } => {
+                let details = match self.global_var_map.get(name) {
+                    Some((VarType::Record(record), _)) if record.field(field).is_some() => return,
+                    Some((VarType::Record(record), _)) => format!(
+                        "{} has no field '{}'; its fields are {}",
+                        record.name,
+                        field,
+                        record.fields.iter().map(|(field, _)| field.as_str()).collect::<Vec<_>>().join(", ")
+                    ),
+                    Some((other, _)) => format!("'{}' is a {} variable, not a record", name, other),
+                    None => {
+                        self.errors.push(VarError::UndeclaredVariable { name: name.clone(), line });
+                        return;
+                    }
+                };
+                self.errors.push(VarError::InvalidRecord { details, line });
+                return;
+            }
+            Expression::Record(values) => {
+                for (_, value) in values {
+                    self.check_array_use(value, line);
+                }
+                return;
+            }
+            Expression::Index { name, index } => {
+                self.check_array_use(index, line);
+                match self.global_var_map.get(name) {
+                    Some((VarType::Array(_), _)) => match index.as_ref() {
+                        Expression::Identifier(index_name) => match self.global_var_map.get(index_name) {
+                            Some((VarType::Int(_), _)) | None => return,
+                            Some((other, _)) => format!("the index '{}' is a {} variable, not an integer", index_name, other),
+                        },
+                        Expression::Literal(lit) if crate::transpilers::int_literal(lit).is_some() => return,
+                        other => format!("the index of '{}' must be an integer, found {:?}", name, other),
+                    },
+                    Some((VarType::Map(key, _), _)) => {
+                        let key = (**key).clone();
+                        return self.check_map_key(name, &key, index, line);
+                    }
+                    Some((other, _)) => format!("'{}' is a {} variable, not an array", name, other),
+                    None => {
+                        self.errors.push(VarError::UndeclaredVariable { name: name.clone(), line });
+                        return;
+                    }
+                }
+            }
+            Expression::Len(name) => match self.global_var_map.get(name) {
+                Some((VarType::Array(_) | VarType::Map(..) | VarType::String | VarType::Char { .. }, _)) => return,
+                Some((other, _)) => format!("'{}' is a {} variable, not an array", name, other),
+                None => {
+                    self.errors.push(VarError::UndeclaredVariable { name: name.clone(), line });
+                    return;
+                }
+            },
+            Expression::In { key, name } => {
+                self.check_array_use(key, line);
+                match self.global_var_map.get(name) {
+                    Some((VarType::Map(key_type, _), _)) => {
+                        let key_type = (**key_type).clone();
+                        return self.check_map_key(name, &key_type, key, line);
+                    }
+                    Some((other, _)) => {
+                        let details = format!("'{}' is a {} variable, not a map", name, other);
+                        self.errors.push(VarError::InvalidMap { details, line });
+                        return;
+                    }
+                    None => {
+                        self.errors.push(VarError::UndeclaredVariable { name: name.clone(), line });
+                        return;
+                    }
+                }
+            }
+            Expression::BinaryOp { left, right, .. } | Expression::LogicalOp { left, right, .. } => {
+                self.check_array_use(left, line);
+                self.check_array_use(right, line);
+                return;
+            }
+            Expression::Not(operand) => return self.check_array_use(operand, line),
+            Expression::Convert { to, value } => {
+                self.check_array_use(value, line);
+                let Expression::Literal(lit) = value.as_ref() else { return };
+                let text = crate::transpilers::literal_text(lit);
+                let converts = match to {
+                    _ if !lit.starts_with('"') || text.contains('{') => true,
+                    VarType::Int(_) => crate::transpilers::convert_int(&text).is_some(),
+                    VarType::Float => crate::transpilers::convert_float(&text).is_some(),
+                    _ => true,
+                };
+                if !converts {
+                    let details = format!(
+                        "\"{}\" is not {}, so {}() always fails",
+                        text,
+                        if *to == VarType::Float { "a number" } else { "an integer" },
+                        crate::transpilers::conversion_name(to)
+                    );
+                    self.errors.push(VarError::InvalidConversion { details, line });
+                }
+                return;
+            }
+            Expression::Text { op, args } => {
+                for arg in args {
+                    self.check_array_use(arg, line);
+                }
+                let var_types = self
+                    .global_var_map
+                    .iter()
+                    .map(|(name, (var_type, _))| (name.clone(), var_type.clone()))
+                    .collect();
+                if let Some(details) = crate::transpilers::text_op_error(*op, args, &var_types) {
+                    self.errors.push(VarError::InvalidStringOp { details, line });
+                }
+                return;
+            }
+            Expression::Math { op, args } => {
+                for arg in args {
+                    self.check_array_use(arg, line);
+                }
+                let var_types = self
+                    .global_var_map
+                    .iter()
+                    .map(|(name, (var_type, _))| (name.clone(), var_type.clone()))
+                    .collect();
+                if let Some(details) = crate::transpilers::math_fn_error(*op, args, &var_types) {
+                    self.errors.push(VarError::InvalidMathOp { details, line });
+                }
+                return;
+            }
+            Expression::List(_) => "a list can only initialize an array".to_string(),
+            Expression::Literal(_) => return,
+        };
+        self.errors.push(VarError::InvalidArray { details, line });
+    }
+
+    /// Reports a key of the map `name` that is not of its key type: text for a
+    /// `str` map, an integer for an `i` map.
+    fn check_map_key(&mut self, name: &str, key_type: &VarType, key: &Expression, line: usize) {
+        let text_key = *key_type == VarType::String;
+        let found = match key {
+            Expression::Literal(lit) if lit.starts_with('"') => "text",
+            Expression::Literal(lit) if crate::transpilers::int_literal(lit).is_some() => "an integer",
+            Expression::Literal(_) => "a number",
+            Expression::Identifier(other) | Expression::Index { name: other, .. } => {
+                let var_type = match (key, self.global_var_map.get(other)) {
+                    (Expression::Index { .. }, Some((VarType::Array(element) | VarType::Map(_, element), _))) => {
+                        Some((**element).clone())
+                    }
+                    (Expression::Index { .. }, _) => None,
+                    (_, found) => found.map(|(var_type, _)| var_type.clone()),
+                };
+                match var_type {
+                    Some(VarType::String | VarType::Char { .. }) => "text",
+                    Some(VarType::Int(_)) => "an integer",
+                    // Reported as undeclared elsewhere
+                    None => return,
+                    Some(_) => "a value that is neither",
+                }
+            }
+            Expression::Len(_) | Expression::Convert { to: VarType::Int(_), .. } => "an integer",
+            Expression::Convert { to: VarType::String, .. } => "text",
+            Expression::Text { op, .. } => match op.result() {
+                VarType::String => "text",
+                VarType::Int(_) => "an integer",
+                _ => "a value that is neither",
+            },
+            Expression::Math { op, args } => {
+                let var_types = self
+                    .global_var_map
+                    .iter()
+                    .map(|(name, (var_type, _))| (name.clone(), var_type.clone()))
+                    .collect();
+                match crate::transpilers::math_kind(*op, args, &var_types) {
+                    crate::transpilers::ValueKind::Int => "an integer",
+                    _ => "a value that is neither",
+                }
+            }
+            _ => "a value that is neither",
+        };
+        let expected = if text_key { "text" } else { "an integer" };
+        if found != expected {
+            let details = format!("the keys of '{}' are {}, found {}", name, expected, found);
+            self.errors.push(VarError::InvalidMap { details, line });
+        }
+    }
+
+    /// Parses the `[K]V` of a `map[K]V` type. Keys are `str` or `i`; values are
+    /// integers, `f`, `str` or `b`.
+    #[inline(always)]
+    fn parse_map_type(&mut self, line: usize) -> Option<VarType> {
+        let start = self.pos;
+        let end = self.line_end();
+        let words: Vec<&Token> = self.tokens[start..end]
+            .iter()
+            .filter(|t| !(t.kind == TokenKind::Unknown && self.get_lexeme(t).trim().is_empty()))
+            .take(4)
+            .collect();
+        let parsed = match words[..] {
+            [open, key, close, value]
+                if open.kind == TokenKind::LBigB && close.kind == TokenKind::RBigB =>
+            {
+                Some((self.get_lexeme(key), self.get_lexeme(value), value))
+            }
+            _ => None,
+        };
+        let Some((key, value, value_token)) = parsed else {
+            self.errors.push(VarError::InvalidMap {
+                details: "expected a map type like map[str]i".to_string(),
+                line,
+            });
+            return None;
+        };
+        // A word that is no type at all, such as `string`, is misspelt rather than misplaced
+        let is_type = |word: &str| {
+            IntType::from_name(word).is_some() || matches!(word, "str" | "f" | "b" | "c" | "map") || self.types.contains_key(word)
+        };
+        let unknown = [key, value].into_iter().find(|word| !is_type(word)).map(str::to_string);
+        if let Some(name) = unknown {
+            self.errors.push(VarError::UnknownType { name, line });
+            return None;
+        }
+        let key_type = match key {
+            "str" => VarType::String,
+            "i" | "i64" => VarType::Int(IntType::I64),
+            other => {
+                self.errors.push(VarError::InvalidMap {
+                    details: format!("map keys are str or i, found '{}'", other),
+                    line,
+                });
+                return None;
+            }
+        };
+        let Some(value_type) = scalar_type(value) else {
+            self.errors.push(VarError::InvalidMap {
+                details: format!("map values are integers, f, str or b, found '{}'", value),
+                line,
+            });
+            return None;
+        };
+        self.pos = self.tokens[start..end]
+            .iter()
+            .position(|t| t.start == value_token.start)
+            .map_or(end, |i| start + i + 1);
+        Some(VarType::Map(Box::new(key_type), Box::new(value_type)))
+    }
+
+    /// Parses the rest of the line as the `{}` a map declaration starts with,
+    /// which becomes an empty list.
+    #[inline(always)]
+    fn parse_empty_map(&mut self, name: &str, line: usize) -> Option<Expression> {
+        let end = self.line_end();
+        let significant: Vec<TokenKind> = self.tokens[self.pos..end]
+            .iter()
+            .filter(|t| !(t.kind == TokenKind::Unknown && self.get_lexeme(t).trim().is_empty()))
+            .map(|t| t.kind)
+            .collect();
+        self.pos = end;
+        if significant != [TokenKind::LCurlyB, TokenKind::RCurlyB] {
+            self.errors.push(VarError::InvalidMap {
+                details: format!("a map starts empty, as `{{}}`; add entries with `* {}[key] = value`", name),
+                line,
+            });
+            return None;
+        }
+        Some(Expression::List(Vec::new()))
+    }
+
+    /// Parses "# Name { field type, ... }", all on one line. Fields are
+    /// integers, `f`, `str` or `b`.
+    #[inline(always)]
+    fn parse_record_declaration(&mut self) -> Option<ASTNode> {
+        let line = self.get_line_number(self.pos);
+        self.pos += 1; // consume '#'
+        let end = self.line_end();
+        let words: Vec<Token> = self.tokens[self.pos..end]
+            .iter()
+            .filter(|t| !(t.kind == TokenKind::Unknown && self.get_lexeme(t).trim().is_empty()))
+            .copied()
+            .collect();
+        self.pos = end;
+        match self.record_type(&words) {
+            Ok(record) => {
+                self.types.insert(record.name.clone(), record.clone());
+                Some(ASTNode::RecordDeclaration(record))
+            }
+            Err(details) => {
+                self.errors.push(VarError::InvalidRecord { details, line });
+                None
+            }
+        }
+    }
+
+    /// The record type the words after `#` declare.
+    fn record_type(&self, words: &[Token]) -> Result<RecordType, String> {
+        let (name, inner) = match words {
+            [name, open, inner @ .., close]
+                if name.kind == TokenKind::Identifier
+                    && open.kind == TokenKind::LCurlyB
+                    && close.kind == TokenKind::RCurlyB =>
+            {
+                (self.get_lexeme(name).to_string(), inner)
+            }
+            _ => return Err("expected a record type like `# Person { name str, age i }`".to_string()),
+        };
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            return Err(format!("'{}' cannot name a record type", name));
+        }
+        if scalar_type(&name).is_some() || matches!(name.as_str(), "c" | "map") {
+            return Err(format!("'{}' is a built-in type; give the record another name", name));
+        }
+        if self.types.contains_key(&name) {
+            return Err(format!("the record type '{}' is already declared", name));
+        }
+
+        // Fields are separated by commas, each a name and a type
+        let mut fields: Vec<(String, VarType)> = Vec::new();
+        for part in inner.split(|t| t.kind == TokenKind::Unknown && self.get_lexeme(t) == ",") {
+            let (field, var_type) = match part {
+                [field, word] if field.kind == TokenKind::Identifier => {
+                    let word = self.get_lexeme(word);
+                    let Some(var_type) = scalar_type(word) else {
+                        return Err(format!("record fields are integers, f, str, b or c[size], found '{}'", word));
+                    };
+                    (self.get_lexeme(field), var_type)
+                }
+                [field, c, open, size, close]
+                    if field.kind == TokenKind::Identifier
+                        && self.get_lexeme(c) == "c"
+                        && self.get_lexeme(open) == "["
+                        && self.get_lexeme(close) == "]" =>
+                {
+                    let Some(size) = self.get_lexeme(size).parse::<usize>().ok().filter(|&size| size > 0) else {
+                        return Err(format!("the size of '{}' must be a positive whole number", self.get_lexeme(field)));
+                    };
+                    (self.get_lexeme(field), VarType::Char { size })
+                }
+                [] => return Err(format!("empty field in the record type '{}'", name)),
+                _ => return Err(format!("expected a field name and a type in the record type '{}'", name)),
+            };
+            if fields.iter().any(|(existing, _)| existing == field) {
+                return Err(format!("the field '{}' is declared twice in '{}'", field, name));
+            }
+            fields.push((field.to_string(), var_type));
+        }
+        Ok(RecordType { name, fields })
+    }
+
+    /// Parses the rest of the line as a record initializer, "{ field value, ... }",
+    /// which may also be left out. Fields not given start at 0, 0.0, "" or false.
+    #[inline(always)]
+    fn parse_record_initializer(&mut self, record: &RecordType, line: usize) -> Option<Expression> {
+        let end = self.line_end();
+        let significant: Vec<usize> = (self.pos..end)
+            .filter(|&i| !(self.tokens[i].kind == TokenKind::Unknown && self.get_lexeme(&self.tokens[i]).trim().is_empty()))
+            .collect();
+        self.pos = end;
+        let mut values: Vec<(String, Expression)> = Vec::new();
+        if let (Some(&first), Some(&last)) = (significant.first(), significant.last()) {
+            if self.tokens[first].kind != TokenKind::LCurlyB || self.tokens[last].kind != TokenKind::RCurlyB || first == last {
+                self.errors.push(VarError::InvalidRecord {
+                    details: format!("expected the fields of {} like `{{ field value, ... }}`", record.name),
+                    line,
+                });
+                return None;
+            }
+
+            // Fields are separated by commas; "{}" gives none
+            let parts: Vec<&[usize]> = significant[1..significant.len() - 1]
+                .split(|&i| self.tokens[i].kind == TokenKind::Unknown && self.get_lexeme(&self.tokens[i]) == ",")
+                .collect();
+            for part in &parts {
+                let details = match part {
+                    [] if parts.len() == 1 => break,
+                    [] => "empty field in the initializer".to_string(),
+                    [field, value_start, ..] if self.tokens[*field].kind == TokenKind::Identifier => {
+                        let field = self.get_lexeme(&self.tokens[*field]).to_string();
+                        let value_end = part.last().map_or(last, |&i| i + 1);
+                        if record.field(&field).is_none() {
+                            format!(
+                                "{} has no field '{}'; its fields are {}",
+                                record.name,
+                                field,
+                                record.fields.iter().map(|(field, _)| field.as_str()).collect::<Vec<_>>().join(", ")
+                            )
+                        } else if values.iter().any(|(existing, _)| *existing == field) {
+                            format!("the field '{}' is given twice", field)
+                        } else if let Some(value) = parse_operand(self.tokens, *value_start, value_end, self.input) {
+                            values.push((field, value));
+                            continue;
+                        } else {
+                            format!("the field '{}' has no value", field)
+                        }
+                    }
+                    [field] => format!("the field '{}' has no value", self.get_lexeme(&self.tokens[*field])),
+                    _ => format!("expected a field name and a value, like `{{ {} ... }}`", record.fields[0].0),
+                };
+                self.errors.push(VarError::InvalidRecord { details, line });
+                return None;
+            }
+        }
+        Some(Expression::Record(
+            record
+                .fields
+                .iter()
+                .map(|(field, var_type)| {
+                    let value = values.iter().find(|(given, _)| given == field).map(|(_, value)| value.clone());
+                    (field.clone(), value.unwrap_or_else(|| Expression::Literal(field_default(var_type).to_string())))
+                })
+                .collect(),
+        ))
+    }
+
+    /// Reports `* name.field` math on anything but an integer or `f` field of a record.
+    fn check_field_math(&mut self, name: &str, field: &str, line: usize) {
+        let reported = self.errors.len();
+        self.check_array_use(&Expression::Field { name: name.to_string(), field: field.to_string() }, line);
+        if self.errors.len() > reported {
+            return;
+        }
+        if let Some((VarType::Record(record), _)) = self.global_var_map.get(name)
+            && let Some(field_type @ (VarType::String | VarType::Bool)) = record.field(field)
+        {
+            let details = format!("'{}.{}' is a {} field; math needs an integer or f field", name, field, field_type);
+            self.errors.push(VarError::InvalidRecord { details, line });
+        }
+    }
+
+    /// Kind of the first non-whitespace token at or after `pos + n`.
+    #[inline(always)]
+    fn next_significant_kind(&self, n: usize) -> Option<TokenKind> {
+        self.tokens[(self.pos + n).min(self.tokens.len())..]
+            .iter()
+            .find(|t| !(t.kind == TokenKind::Unknown && self.get_lexeme(t).trim().is_empty()))
+            .map(|t| t.kind)
+    }
+
+    /// Parses "name(arg, ...)" with an optional "-> var" receiving the result.
+    #[inline(always)]
+    fn parse_call(&mut self) -> Option<ASTNode> {
+        let line = self.get_line_number(self.pos);
+        let name = self.get_lexeme(self.current_token()?).to_string();
+        self.pos += 1;
+        self.skip_whitespace();
+        self.pos += 1; // consume '('
+
+        // Each argument is a string literal, a number or a variable name
+        let mut args = Vec::new();
+        let mut arg = String::new();
+        let mut closed = false;
+        while let Some(&token) = self.current_token() {
+            let lexeme = self.get_lexeme(&token).to_string();
+            match token.kind {
+                TokenKind::Newline => break,
+                TokenKind::RSmallB | TokenKind::Unknown if lexeme == ")" || lexeme == "," => {
+                    self.pos += 1;
+                    let text = std::mem::take(&mut arg);
+                    if !text.is_empty() {
+                        args.push(self.call_argument(&name, text, line)?);
+                    } else if lexeme == "," || !args.is_empty() {
+                        self.errors.push(VarError::InvalidCall {
+                            details: format!("Empty argument in call to '{}'", name),
+                            line,
+                        });
+                        self.consume_until_newline();
+                        return None;
+                    }
+                    if lexeme == ")" {
+                        closed = true;
+                        break;
+                    }
+                }
+                _ => {
+                    arg.push_str(lexeme.trim());
+                    self.pos += 1;
+                }
+            }
+        }
+        if !closed {
+            self.errors.push(VarError::InvalidCall {
+                details: format!("Expected ')' to close the call to '{}'", name),
+                line,
+            });
+            self.consume_until_newline();
+            return None;
+        }
+
+        self.skip_whitespace();
+        let mut into = None;
+        if self.current_token().map(|t| t.kind) == Some(TokenKind::Minus)
+            && self.peek_token(1).map(|t| t.kind) == Some(TokenKind::Greater)
+        {
+            self.pos += 2;
+            self.skip_whitespace();
+            match self.current_token() {
+                Some(token) if token.kind == TokenKind::Identifier => {
+                    into = Some(self.get_lexeme(token).to_string());
+                    self.pos += 1;
+                }
+                _ => {
+                    self.errors.push(VarError::InvalidCall {
+                        details: format!("Expected a variable name after '->' in the call to '{}'", name),
+                        line,
+                    });
+                    self.consume_until_newline();
+                    return None;
+                }
+            }
+        }
+        self.consume_until_newline();
+
+        Some(ASTNode::Call { name, args, into, line })
+    }
+
+    #[inline(always)]
+    fn call_argument(&mut self, name: &str, text: String, line: usize) -> Option<Expression> {
+        let is_string = text.len() >= 2 && text.starts_with('"') && text.ends_with('"');
+        let digits = text.strip_prefix('-').unwrap_or(&text);
+        let is_number = !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit() || c == '.');
+        let is_name = text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_string || is_number {
+            Some(Expression::Literal(text))
+        } else if is_name {
+            Some(Expression::Identifier(text))
+        } else {
+            self.errors.push(VarError::InvalidCall {
+                details: format!("Invalid argument '{}' in call to '{}'", text, name),
+                line,
+            });
+            self.consume_until_newline();
+            None
+        }
     }
 
     #[inline(always)]
@@ -274,13 +1040,43 @@ impl<'a> ParserState<'a> {
                 return Some(Expression::Literal(literal));
             }
             
+            // An element or a length runs to the end of the line
+            let end = self.line_end();
+            if token.kind == TokenKind::Identifier
+                && let Some(access) = parse_access(self.tokens, self.pos, end, self.input)
+            {
+                self.pos = end;
+                return Some(access);
+            }
+
+            // A negative number, or `-` before a name or a call
+            if token.kind == TokenKind::Minus
+                && self.next_significant_kind(1) == Some(TokenKind::Identifier)
+            {
+                let operand = parse_operand(self.tokens, self.pos, end, self.input);
+                self.pos = end;
+                return operand;
+            }
+
             // Otherwise handle other token types
             if token.kind == TokenKind::Identifier {
-                let val = self.get_lexeme(token).to_string();
+                let mut val = self.get_lexeme(token).to_string();
                 self.pos += 1;
+
+                // `.` is a token of its own, so `3.14` arrives as `3`, `.` and `14`
+                while val.chars().all(|c| c.is_ascii_digit() || c == '.')
+                    && let Some(next) = self.current_token()
+                    && next.start == self.tokens[self.pos - 1].end
+                    && let part = self.get_lexeme(next)
+                    && !part.is_empty()
+                    && part.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+                {
+                    val.push_str(self.get_lexeme(next));
+                    self.pos += 1;
+                }
                 
                 // Try to determine if it's a number or identifier
-                if val.chars().all(|c| c.is_digit(10) || c == '.') {
+                if val.chars().all(|c| c.is_ascii_digit() || c == '.') {
                     Some(Expression::Literal(val))
                 } else {
                     Some(Expression::Identifier(val))
@@ -294,25 +1090,97 @@ impl<'a> ParserState<'a> {
         }
     }
 
+    /// Parses `'text'` as written, up to the last quote on the line; `'` has
+    /// no token of its own, so the text between the quotes is taken raw.
     #[inline(always)]
-    fn consume_until_newline(&mut self) {
-        while self.pos < self.tokens.len() && self.tokens[self.pos].kind != TokenKind::Newline {
-            self.pos += 1;
+    fn parse_char_literal(&mut self) -> Option<Expression> {
+        let start = self.current_token()?.start;
+        let end = self.line_end();
+        let line_end = self.tokens.get(end).map_or(self.input.len(), |t| t.start);
+        let close = start + 1 + self.input[start + 1..line_end].rfind('\'')?;
+        self.pos = (self.pos..end).find(|&i| self.tokens[i].start > close).unwrap_or(end);
+        Some(Expression::Literal(self.input[start..=close].to_string()))
+    }
+
+    /// Position of the newline or comment ending the current line.
+    #[inline(always)]
+    fn line_end(&self) -> usize {
+        let start = self.pos.min(self.tokens.len());
+        self.tokens[start..]
+            .iter()
+            .position(|t| matches!(t.kind, TokenKind::Newline | TokenKind::Comment))
+            .map_or(self.tokens.len(), |i| start + i)
+    }
+
+    /// Parses the rest of the line as an array initializer, "[a, b, c]".
+    #[inline(always)]
+    fn parse_list_until_newline(&mut self, line: usize) -> Option<Expression> {
+        let end = self.line_end();
+        let significant: Vec<usize> = (self.pos..end)
+            .filter(|&i| !(self.tokens[i].kind == TokenKind::Unknown && self.get_lexeme(&self.tokens[i]).trim().is_empty()))
+            .collect();
+        self.pos = end;
+        let (first, last) = (*significant.first()?, *significant.last()?);
+        if self.tokens[first].kind != TokenKind::LBigB || self.tokens[last].kind != TokenKind::RBigB || first == last {
+            self.errors.push(VarError::InvalidArray {
+                details: "expected a list of elements like [1, 2, 3]".to_string(),
+                line,
+            });
+            return None;
         }
-        self.pos += 1; // consume the newline token
+
+        // Items are separated by commas; "[]" is empty
+        let mut items = Vec::new();
+        if significant.len() > 2 {
+            let mut item_start = first + 1;
+            for i in first + 1..=last {
+                let token = &self.tokens[i];
+                if i == last || (token.kind == TokenKind::Unknown && self.get_lexeme(token) == ",") {
+                    match parse_operand(self.tokens, item_start, i, self.input) {
+                        Some(item) => items.push(item),
+                        None => {
+                            self.errors.push(VarError::InvalidArray {
+                                details: "empty element in the list".to_string(),
+                                line,
+                            });
+                            return None;
+                        }
+                    }
+                    item_start = i + 1;
+                }
+            }
+        }
+        Some(Expression::List(items))
     }
+
+    /// Parses the rest of the line as a condition, as inside `?()`.
     #[inline(always)]
-    pub fn get_global_pos(&self) -> usize {
-        let mut global_pos = 0;
-        for token in self.tokens.iter().take(self.pos) {
-            global_pos += token.end - token.start;
+    fn parse_condition_until_newline(&mut self, line: usize) -> Option<Expression> {
+        let start = self.pos;
+        let end = self.line_end();
+        self.pos = end;
+        let blank = self.tokens[start..end]
+            .iter()
+            .all(|t| t.kind == TokenKind::Unknown && self.get_lexeme(t).trim().is_empty());
+        if blank {
+            return None;
         }
-        if let Some(current_token) = self.tokens.get(self.pos) {
-            global_pos += current_token.start;
+        match parse_condition(self.tokens, start, end, self.input, line) {
+            Ok(condition) => Some(condition),
+            Err(details) => {
+                self.errors.push(VarError::InvalidCondition { details, line });
+                None
+            }
         }
-        global_pos + self.global_pos
     }
 
+    #[inline(always)]
+    fn consume_until_newline(&mut self) {
+        while self.pos < self.tokens.len() && self.tokens[self.pos].kind != TokenKind::Newline {
+            self.pos += 1;
+        }
+        self.pos += 1; // consume the newline token
+    }
     #[inline(always)]
     pub fn get_line_number(&self, pos: usize) -> usize {
         // Count the number of newlines in the input up to the global position
@@ -334,3 +1202,23 @@ impl<'a> ParserState<'a> {
         line_count + 1
     }
 }
+
+/// The types a map value or a record field can have: integers, `f`, `str` and `b`.
+fn scalar_type(word: &str) -> Option<VarType> {
+    match word {
+        "str" => Some(VarType::String),
+        "f" => Some(VarType::Float),
+        "b" => Some(VarType::Bool),
+        name => IntType::from_name(name).map(VarType::Int),
+    }
+}
+
+/// The value a record field starts with when its initializer leaves it out.
+fn field_default(var_type: &VarType) -> &'static str {
+    match var_type {
+        VarType::Float => "0.0",
+        VarType::String | VarType::Char { .. } => "\"\"",
+        VarType::Bool => "false",
+        _ => "0",
+    }
+}
@@ -0,0 +1,72 @@
+//! The Ven compiler as a library.
+//!
+//! [`Session`] tokenizes and parses a source once and gives access to the tokens,
+//! the AST and the diagnostics; [`compile`] is the one-call version that goes all
+//! the way to target code.
+//!
+//! ```no_run
+//! use ven::{Options, Target, compile};
+//!
+//! let target = Target::from_name("py").unwrap();
+//! match compile(">> \"hi\"\n", target, Options::default()) {
+//!     Ok(output) => println!("{}", output.code),
+//!     Err(diagnostics) => eprintln!("{} error(s)", diagnostics.len()),
+//! }
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static TRACE: AtomicBool = AtomicBool::new(false);
+
+/// Turns the parser's step-by-step trace on stderr on or off (off by default).
+pub fn set_trace(enabled: bool) {
+    TRACE.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn trace_enabled() -> bool {
+    TRACE.load(Ordering::Relaxed)
+}
+
+/// `eprintln!` that only prints while tracing is enabled.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace_enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+pub mod cst;
+pub mod diagnostic;
+pub(crate) mod errmsgs;
+pub mod errs;
+pub(crate) mod impl_parserstate;
+pub(crate) mod json;
+pub(crate) mod optimisers;
+pub mod parse;
+pub(crate) mod parse1;
+pub(crate) mod parse2;
+pub mod session;
+pub mod token;
+pub mod var_checker;
+pub mod vm;
+
+// The tools behind the `ven` binary's subcommands. They are public so the
+// binary can reach them, but they are not part of the library's API.
+#[doc(hidden)]
+pub mod explain;
+#[doc(hidden)]
+pub mod formatter;
+#[doc(hidden)]
+pub mod lint;
+#[doc(hidden)]
+pub mod lsp;
+#[doc(hidden)]
+pub mod repl;
+#[doc(hidden)]
+pub mod suggest;
+#[doc(hidden)]
+pub mod transpilers;
+
+pub use session::{Diagnostics, Options, Output, Session, Target, compile};
+pub use transpilers::{Backend, Capabilities, CodegenOptions, Overflow};
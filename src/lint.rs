@@ -0,0 +1,545 @@
+//! `ven lint`: warnings for programs that compile but probably do not do what
+//! was meant.
+//!
+//! Rules are turned off for a whole file with a comment on its own line,
+//! `; ven:allow(unused_variable)`, for one line with the same comment at the end
+//! of that line, and for every file under a directory with a `.venlint` file:
+//!
+//! ```text
+//! ; rules allowed in every .ven file below this directory
+//! allow = never_mutated, shadowed_declaration
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cst::{Cst, CstToken, Node, StatementKind, TriviaKind};
+use crate::lsp::document::{Access, Document};
+use crate::parse::{ASTNode, ComparisonOperator, Expression, LogicalOperator, MathFn, MathOperator, VarType};
+use crate::suggest::closest;
+use crate::transpilers::{clean_number, conversion_name};
+
+/// Name of the per-directory configuration file.
+pub const CONFIG_FILE: &str = ".venlint";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// A variable is declared but its value is never read.
+    UnusedVariable,
+    /// A `@@` variable is never changed, so it could be `@`.
+    NeverMutated,
+    /// A condition is always true or always false.
+    ConstantCondition,
+    /// Text is compared with a number, which is never equal.
+    MixedComparison,
+    /// `* x / 0`
+    DivisionByZero,
+    /// A name is declared again.
+    ShadowedDeclaration,
+    /// A `{placeholder}` names no variable, or lays out a whole array, map or
+    /// record, and prints `[undefined]`.
+    UndefinedPlaceholder,
+    /// A `ven:allow` comment names a rule that does not exist.
+    UnknownRule,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 8] = [
+        Rule::UnusedVariable,
+        Rule::NeverMutated,
+        Rule::ConstantCondition,
+        Rule::MixedComparison,
+        Rule::DivisionByZero,
+        Rule::ShadowedDeclaration,
+        Rule::UndefinedPlaceholder,
+        Rule::UnknownRule,
+    ];
+
+    /// The name used in `ven:allow(...)` and `.venlint`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::UnusedVariable => "unused_variable",
+            Rule::NeverMutated => "never_mutated",
+            Rule::ConstantCondition => "constant_condition",
+            Rule::MixedComparison => "mixed_comparison",
+            Rule::DivisionByZero => "division_by_zero",
+            Rule::ShadowedDeclaration => "shadowed_declaration",
+            Rule::UndefinedPlaceholder => "undefined_placeholder",
+            Rule::UnknownRule => "unknown_rule",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Rule> {
+        Rule::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Lint {
+    pub rule: Rule,
+    /// Byte range the warning points at.
+    pub span: (usize, usize),
+    pub message: String,
+    /// Text that fixes the warning when it replaces `span`.
+    pub replacement: Option<String>,
+}
+
+/// Rules turned off by a `.venlint` file.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub allow: Vec<Rule>,
+}
+
+impl Config {
+    /// Reads a `.venlint` file: `allow = rule, rule` lines and `;` comments.
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let mut config = Config::default();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split(';').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("line {}: expected `allow = <rules>`", i + 1));
+            };
+            if key.trim() != "allow" {
+                return Err(format!("line {}: unknown setting '{}'", i + 1, key.trim()));
+            }
+            for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+                match Rule::from_name(name) {
+                    Some(rule) => config.allow.push(rule),
+                    None => return Err(format!("line {}: unknown rule '{}'", i + 1, name)),
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    /// The `.venlint` closest to `path`, looking in its directory and then in
+    /// each parent. The default configuration if there is none.
+    pub fn find(path: &Path) -> Result<(Option<PathBuf>, Config), String> {
+        let start = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let start = fs::canonicalize(&start).unwrap_or(start);
+        for dir in start.ancestors() {
+            let candidate = dir.join(CONFIG_FILE);
+            if let Ok(text) = fs::read_to_string(&candidate) {
+                let config = Config::parse(&text).map_err(|e| format!("{}: {}", candidate.display(), e))?;
+                return Ok((Some(candidate), config));
+            }
+        }
+        Ok((None, Config::default()))
+    }
+}
+
+/// A constant value, for conditions that can be decided without running.
+#[derive(Debug, Clone)]
+enum Value {
+    Number(f64),
+    Text(String),
+}
+
+/// What is known about a declared name.
+struct Declared {
+    var_type: VarType,
+    /// The initializer, for `@` variables declared once and never written.
+    constant: Option<Value>,
+}
+
+/// Collects every declaration, nested ones included.
+fn declarations<'a>(nodes: &'a [ASTNode], out: &mut Vec<(&'a str, bool, &'a VarType, Option<&'a Expression>)>) {
+    for node in nodes {
+        match node {
+            ASTNode::VarDeclaration {
+                mutable,
+                name,
+                var_type,
+                value,
+                ..
+            } => out.push((name, *mutable, var_type, value.as_ref())),
+            ASTNode::If { body, .. } | ASTNode::ForEach { body, .. } => declarations(body, out),
+            _ => {}
+        }
+    }
+}
+
+fn literal(lit: &str) -> Option<Value> {
+    if lit.len() >= 2 && lit.starts_with('"') && lit.ends_with('"') {
+        return Some(Value::Text(lit[1..lit.len() - 1].to_string()));
+    }
+    // Booleans compare like 1 and 0, as on every target
+    match lit {
+        "true" => Some(Value::Number(1.0)),
+        "false" => Some(Value::Number(0.0)),
+        _ => clean_number(lit).parse().ok().map(Value::Number),
+    }
+}
+
+fn show(expr: &Expression) -> String {
+    match expr {
+        Expression::Literal(lit) | Expression::Identifier(lit) => lit.clone(),
+        Expression::Convert { to, value } => format!("{}({})", conversion_name(to), show(value)),
+        Expression::Text { op, args } => {
+            let args: Vec<String> = args.iter().map(show).collect();
+            format!("{}({})", op.name(), args.join(", "))
+        }
+        Expression::Math { op: MathFn::Negate, args } => format!("-{}", show(&args[0])),
+        Expression::Math { op, args } => {
+            let args: Vec<String> = args.iter().map(show).collect();
+            format!("{}({})", op.name(), args.join(", "))
+        }
+        _ => "(...)".to_string(),
+    }
+}
+
+struct Linter<'a> {
+    source: &'a str,
+    names: HashMap<&'a str, Declared>,
+    lints: Vec<Lint>,
+}
+
+impl Linter<'_> {
+    /// Whether an operand is text, if that is known.
+    fn is_text(&self, expr: &Expression) -> Option<bool> {
+        match expr {
+            Expression::Literal(lit) => Some(lit.starts_with('"')),
+            Expression::Identifier(name) => self
+                .names
+                .get(name.as_str())
+                .map(|declared| matches!(declared.var_type, VarType::String | VarType::Char { .. })),
+            Expression::Convert { to, .. } => Some(*to == VarType::String),
+            Expression::Text { op, .. } => Some(op.result() == VarType::String),
+            _ => None,
+        }
+    }
+
+    fn value(&self, expr: &Expression) -> Option<Value> {
+        match expr {
+            Expression::Literal(lit) => literal(lit),
+            Expression::Identifier(name) => self.names.get(name.as_str())?.constant.clone(),
+            _ => None,
+        }
+    }
+
+    /// The value of a condition when it does not depend on input.
+    fn evaluate(&self, condition: &Expression) -> Option<bool> {
+        match condition {
+            Expression::BinaryOp { left, operator, right } => {
+                let (left, right) = (self.value(left)?, self.value(right)?);
+                let ordering = match (&left, &right) {
+                    (Value::Number(a), Value::Number(b)) => a.partial_cmp(b)?,
+                    (Value::Text(a), Value::Text(b)) => a.as_bytes().cmp(b.as_bytes()),
+                    // Text and a number are never equal, nor ordered
+                    _ => return Some(*operator == ComparisonOperator::NotEqual),
+                };
+                Some(match operator {
+                    ComparisonOperator::Equal => ordering.is_eq(),
+                    ComparisonOperator::NotEqual => ordering.is_ne(),
+                    ComparisonOperator::LessThan => ordering.is_lt(),
+                    ComparisonOperator::LessThanEqual => ordering.is_le(),
+                    ComparisonOperator::GreaterThan => ordering.is_gt(),
+                    ComparisonOperator::GreaterThanEqual => ordering.is_ge(),
+                })
+            }
+            Expression::LogicalOp { left, operator, right } => {
+                match (operator, self.evaluate(left), self.evaluate(right)) {
+                    (LogicalOperator::And, Some(false), _) | (LogicalOperator::And, _, Some(false)) => Some(false),
+                    (LogicalOperator::Or, Some(true), _) | (LogicalOperator::Or, _, Some(true)) => Some(true),
+                    (LogicalOperator::And, Some(a), Some(b)) => Some(a && b),
+                    (LogicalOperator::Or, Some(a), Some(b)) => Some(a || b),
+                    _ => None,
+                }
+            }
+            Expression::Not(operand) => self.evaluate(operand).map(|value| !value),
+            leaf => match self.value(leaf)? {
+                Value::Number(n) => Some(n != 0.0),
+                Value::Text(_) => None,
+            },
+        }
+    }
+
+    fn comparisons(&mut self, condition: &Expression, span: (usize, usize)) {
+        match condition {
+            Expression::BinaryOp { left, right, .. } => {
+                if let (Some(a), Some(b)) = (self.is_text(left), self.is_text(right))
+                    && a != b
+                {
+                    let (text, number) = if a { (left, right) } else { (right, left) };
+                    self.lints.push(Lint {
+                        rule: Rule::MixedComparison,
+                        span,
+                        message: format!(
+                            "`{}` is text and `{}` is a number: they are never equal and only `!=` holds",
+                            show(text),
+                            show(number)
+                        ),
+                        replacement: None,
+                    });
+                }
+            }
+            Expression::LogicalOp { left, right, .. } => {
+                self.comparisons(left, span);
+                self.comparisons(right, span);
+            }
+            _ => {}
+        }
+    }
+
+    fn nodes(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            match node {
+                Node::Statement {
+                    kind: StatementKind::Math,
+                    tokens,
+                } => {
+                    if let Some(ASTNode::MathOp {
+                        name,
+                        field,
+                        operator: MathOperator::Divide,
+                        operand: Expression::Literal(operand),
+                        ..
+                    }) = node.to_ast()
+                        && clean_number(&operand).parse::<f64>() == Ok(0.0)
+                    {
+                        let slash = tokens.iter().position(|t| t.text == "/").unwrap_or(0);
+                        let span = (tokens[(slash + 1).min(tokens.len() - 1)].start, node.span().1);
+                        let target = match field {
+                            Some(field) => format!("{}.{}", name, field),
+                            None => name,
+                        };
+                        self.lints.push(Lint {
+                            rule: Rule::DivisionByZero,
+                            span,
+                            message: format!("`{}` is divided by zero", target),
+                            replacement: None,
+                        });
+                    }
+                }
+                Node::Block { header, body, .. } => {
+                    if let Some(ASTNode::If { condition, .. }) = node.to_ast() {
+                        // Between `?` and `{`
+                        let span = (header[1].start, header[header.len() - 2].end());
+                        if let Some(always) = self.evaluate(&condition) {
+                            self.lints.push(Lint {
+                                rule: Rule::ConstantCondition,
+                                span,
+                                message: format!(
+                                    "this condition is always {}",
+                                    if always { "true" } else { "false" }
+                                ),
+                                replacement: None,
+                            });
+                        }
+                        self.comparisons(&condition, span);
+                    }
+                    self.nodes(body);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// 1-based line of a byte offset.
+    fn line(&self, offset: usize) -> usize {
+        self.source[..offset].matches('\n').count() + 1
+    }
+}
+
+/// Rules named by `ven:allow(...)` in a comment, and the unknown names.
+fn allowed_by(comment: &str) -> Option<(Vec<Rule>, Vec<String>)> {
+    let rest = comment.trim_start_matches(';').trim_start();
+    let names = rest.strip_prefix("ven:allow(")?;
+    let names = &names[..names.find(')')?];
+    let mut rules = Vec::new();
+    let mut unknown = Vec::new();
+    for name in names.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        match Rule::from_name(name) {
+            Some(rule) => rules.push(rule),
+            None => unknown.push(name.to_string()),
+        }
+    }
+    Some((rules, unknown))
+}
+
+/// Warnings for `source`, sorted by position, without those that `config` or
+/// the file's `ven:allow` comments turn off.
+pub fn lint(source: &str, config: &Config) -> Vec<Lint> {
+    let document = Document::new(source.to_string());
+    let cst = Cst::parse(source);
+    let (ast, _, _) = cst.to_ast();
+    let crate::parse::AST::Program(ref program) = ast;
+
+    let mut found = Vec::new();
+    declarations(program, &mut found);
+    let mut linter = Linter {
+        source,
+        names: HashMap::new(),
+        lints: Vec::new(),
+    };
+    for &(name, mutable, var_type, value) in &found {
+        let once = found.iter().filter(|(other, ..)| *other == name).count() == 1;
+        let written = document
+            .references
+            .iter()
+            .any(|r| r.name == name && matches!(r.access, Access::Write | Access::Input));
+        let constant = match value {
+            Some(Expression::Literal(lit)) if !mutable && once && !written => match var_type {
+                VarType::Int(_) | VarType::Float => clean_number(lit).parse().ok().map(Value::Number),
+                VarType::String | VarType::Char { .. } => Some(Value::Text(lit.trim_matches('\'').to_string())),
+                VarType::Bool => literal(lit),
+                VarType::Array(_) | VarType::Map(..) | VarType::Record(_) => None,
+            },
+            _ => None,
+        };
+        linter.names.entry(name).or_insert(Declared {
+            var_type: var_type.clone(),
+            constant,
+        });
+    }
+
+    for (index, symbol) in document.symbols.iter().enumerate() {
+        let uses: Vec<Access> = document
+            .references
+            .iter()
+            .filter(|r| r.symbol == Some(index))
+            .map(|r| r.access)
+            .collect();
+        let read = uses.iter().any(|a| matches!(a, Access::Read | Access::Placeholder | Access::Word));
+        if !read {
+            let what = if uses.is_empty() { "used" } else { "read" };
+            linter.lints.push(Lint {
+                rule: Rule::UnusedVariable,
+                span: symbol.span,
+                message: format!("variable `{}` is never {}", symbol.name, what),
+                replacement: None,
+            });
+        }
+        // An unused variable is already reported
+        if read && symbol.mutable && !uses.iter().any(|a| matches!(a, Access::Write | Access::Input)) {
+            linter.lints.push(Lint {
+                rule: Rule::NeverMutated,
+                span: symbol.span,
+                message: format!("`{}` is never changed; declare it with `@` instead of `@@`", symbol.name),
+                replacement: None,
+            });
+        }
+        if let Some(earlier) = document.symbols[..index].iter().find(|s| s.name == symbol.name) {
+            linter.lints.push(Lint {
+                rule: Rule::ShadowedDeclaration,
+                span: symbol.span,
+                message: format!(
+                    "`{}` is already declared on line {}",
+                    symbol.name,
+                    linter.line(earlier.span.0)
+                ),
+                replacement: None,
+            });
+        }
+    }
+    let names: Vec<&str> = document.symbols.iter().map(|symbol| symbol.name.as_str()).collect();
+    for reference in &document.references {
+        if reference.access == Access::Placeholder && reference.symbol.is_none() {
+            linter.lints.push(Lint {
+                rule: Rule::UndefinedPlaceholder,
+                span: reference.span,
+                message: format!("`{{{}}}` names no variable and prints `[undefined]`", reference.name),
+                replacement: closest(&reference.name, names.iter().copied()).map(str::to_string),
+            });
+        }
+        // `{p.field}` with a field the record does not have
+        if reference.access == Access::Placeholder
+            && let Some(VarType::Record(record)) = reference.symbol.and_then(|i| document.symbols[i].var_type.as_ref())
+            && let Some(rest) = source[reference.span.1..].strip_prefix('.')
+        {
+            let field = &rest[..rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(rest.len())];
+            if record.field(field).is_none() {
+                let start = reference.span.1 + 1;
+                linter.lints.push(Lint {
+                    rule: Rule::UndefinedPlaceholder,
+                    span: (start, start + field.len()),
+                    message: format!(
+                        "`{{{}.{}}}` names no field of {} and prints `[undefined]`",
+                        reference.name, field, record.name
+                    ),
+                    replacement: closest(field, record.fields.iter().map(|(name, _)| name.as_str())).map(str::to_string),
+                });
+            }
+        }
+        // `{items:>5}`: a spec lays out a single value, not a whole array, map or record
+        if reference.access == Access::Placeholder
+            && let Some(var_type @ (VarType::Array(_) | VarType::Map(..) | VarType::Record(_))) =
+                reference.symbol.and_then(|i| document.symbols[i].var_type.as_ref())
+            && source[reference.span.1..].trim_start().starts_with(':')
+        {
+            linter.lints.push(Lint {
+                rule: Rule::UndefinedPlaceholder,
+                span: reference.span,
+                message: format!("a format spec on the whole {} `{}` prints `[undefined]`", var_type, reference.name),
+                replacement: None,
+            });
+        }
+    }
+    linter.nodes(&cst.nodes);
+
+    // `ven:allow` comments: on their own line for the file, after code for that line
+    let mut file_allowed = config.allow.clone();
+    let mut line_allowed: Vec<(usize, Rule)> = Vec::new();
+    let mut comment = |text: &str, start: usize, line: Option<usize>, linter: &mut Linter| {
+        let Some((rules, unknown)) = allowed_by(text) else {
+            return;
+        };
+        for name in unknown {
+            linter.lints.push(Lint {
+                rule: Rule::UnknownRule,
+                span: (start, start + text.trim_end().len()),
+                message: format!("unknown lint rule `{}`", name),
+                replacement: None,
+            });
+        }
+        match line {
+            Some(line) => line_allowed.extend(rules.into_iter().map(|rule| (line, rule))),
+            None => file_allowed.extend(rules),
+        }
+    };
+    let tokens: Vec<&CstToken> = cst.nodes.iter().flat_map(|node| node.tokens()).collect();
+    for token in tokens {
+        let mut offset = token.start - token.leading.iter().map(|t| t.text.len()).sum::<usize>();
+        for trivia in &token.leading {
+            if trivia.kind == TriviaKind::Comment {
+                comment(&trivia.text, offset, None, &mut linter);
+            }
+            offset += trivia.text.len();
+        }
+        let mut offset = token.end();
+        for trivia in &token.trailing {
+            if trivia.kind == TriviaKind::Comment {
+                comment(&trivia.text, offset, Some(linter.line(token.start)), &mut linter);
+            }
+            offset += trivia.text.len();
+        }
+    }
+    let mut offset = source.len() - cst.end.iter().map(|t| t.text.len()).sum::<usize>();
+    for trivia in &cst.end {
+        if trivia.kind == TriviaKind::Comment {
+            comment(&trivia.text, offset, None, &mut linter);
+        }
+        offset += trivia.text.len();
+    }
+
+    let mut lints: Vec<Lint> = std::mem::take(&mut linter.lints)
+        .into_iter()
+        .filter(|lint| {
+            let line = linter.line(lint.span.0);
+            !file_allowed.contains(&lint.rule) && !line_allowed.contains(&(line, lint.rule))
+        })
+        .collect();
+    lints.sort_by_key(|lint| lint.span);
+    lints
+}
@@ -0,0 +1,543 @@
+use crate::errs::VarError;
+use crate::parse::{AST, IntType, RecordType, VarType};
+use crate::token::{Token, TokenKind, Tokenizer};
+use crate::var_checker::{check_variables, is_builtin, is_name};
+
+/// A `@`/`@@` declaration.
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub mutable: bool,
+    /// `None` when the type is missing or misspelled.
+    pub var_type: Option<VarType>,
+    /// Byte range of the name.
+    pub span: (usize, usize),
+    /// Byte range of the whole declaration line, without the newline.
+    pub full: (usize, usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    /// Target of `*` math or of a call's `-> name`.
+    Write,
+    /// Target of `..`.
+    Input,
+    /// A `{name}`, `{name[index]}`, `{len(name)}` or `{name.field}` placeholder
+    /// in a printed string.
+    Placeholder,
+    /// A bare word after `>>`, which only refers to a variable if one exists.
+    Word,
+}
+
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub name: String,
+    pub span: (usize, usize),
+    pub access: Access,
+    /// Index into `Document::symbols`.
+    pub symbol: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// Byte range the diagnostic covers.
+    pub span: (usize, usize),
+    pub error: VarError,
+}
+
+/// An open `.ven` file, kept tokenized across edits.
+pub struct Document {
+    text: String,
+    tokens: Vec<Token>,
+    line_starts: Vec<usize>,
+    pub symbols: Vec<Symbol>,
+    pub references: Vec<Reference>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Record types declared with `#`, in order.
+    records: Vec<RecordType>,
+}
+
+fn tokenize(text: &str, offset: usize) -> Vec<Token> {
+    let mut tokenizer = Tokenizer::new(text);
+    tokenizer.tokenize();
+    let mut tokens = tokenizer.tokens;
+    for token in &mut tokens {
+        token.start += offset;
+        token.end += offset;
+    }
+    tokens
+}
+
+fn parse_type(word: &str, size: Option<&str>) -> Option<VarType> {
+    match word {
+        "str" => Some(VarType::String),
+        "f" => Some(VarType::Float),
+        "b" => Some(VarType::Bool),
+        "c" => size.and_then(|size| size.parse().ok()).map(|size| VarType::Char { size }),
+        _ => IntType::from_name(word).map(VarType::Int),
+    }
+}
+
+impl Document {
+    pub fn new(text: String) -> Document {
+        let tokens = tokenize(&text, 0);
+        let mut document = Document {
+            text,
+            tokens,
+            line_starts: Vec::new(),
+            symbols: Vec::new(),
+            references: Vec::new(),
+            diagnostics: Vec::new(),
+            records: Vec::new(),
+        };
+        document.analyze();
+        document
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// Record types declared with `#`, in order.
+    pub fn records(&self) -> &[RecordType] {
+        &self.records
+    }
+
+    /// Replaces the bytes in `start..end` with `new_text` and re-tokenizes only
+    /// the lines the edit touched. Tokens never span a newline, so every other
+    /// token stays valid and just moves by the change in length.
+    pub fn edit(&mut self, start: usize, end: usize, new_text: &str) {
+        let region_start = self.text[..start].rfind('\n').map_or(0, |i| i + 1);
+        let old_region_end = self.text[end..].find('\n').map_or(self.text.len(), |i| end + i + 1);
+        self.text.replace_range(start..end, new_text);
+        let delta = new_text.len() as isize - (end - start) as isize;
+        let new_region_end = (old_region_end as isize + delta) as usize;
+
+        let first = self.tokens.partition_point(|t| t.start < region_start);
+        let last = self.tokens.partition_point(|t| t.start < old_region_end);
+        let retokenized = tokenize(&self.text[region_start..new_region_end], region_start);
+        for token in &mut self.tokens[last..] {
+            token.start = (token.start as isize + delta) as usize;
+            token.end = (token.end as isize + delta) as usize;
+        }
+        self.tokens.splice(first..last, retokenized);
+        self.analyze();
+    }
+
+    /// Byte offset of an LSP position (0-based line, UTF-16 column).
+    pub fn offset(&self, line: usize, column: usize) -> usize {
+        let Some(&line_start) = self.line_starts.get(line) else {
+            return self.text.len();
+        };
+        let line_text = self.text[line_start..].split('\n').next().unwrap_or("");
+        let mut units = 0;
+        for (i, c) in line_text.char_indices() {
+            if units >= column {
+                return line_start + i;
+            }
+            units += c.len_utf16();
+        }
+        line_start + line_text.len()
+    }
+
+    /// LSP position (0-based line, UTF-16 column) of a byte offset.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let column = self.text[self.line_starts[line]..offset].encode_utf16().count();
+        (line, column)
+    }
+
+    /// Byte range of the text on a 1-based line, without surrounding whitespace.
+    pub fn line_span(&self, line: usize) -> (usize, usize) {
+        let Some(&start) = self.line_starts.get(line.saturating_sub(1)) else {
+            return (self.text.len(), self.text.len());
+        };
+        let text = self.text[start..].split('\n').next().unwrap_or("");
+        let indent = text.len() - text.trim_start().len();
+        (start + indent, start + text.trim_end().len().max(indent))
+    }
+
+    /// The symbol declared or referenced at `offset`.
+    pub fn symbol_at(&self, offset: usize) -> Option<&Symbol> {
+        let contains = |span: (usize, usize)| span.0 <= offset && offset <= span.1;
+        if let Some(symbol) = self.symbols.iter().find(|symbol| contains(symbol.span)) {
+            return Some(symbol);
+        }
+        self.references
+            .iter()
+            .find(|reference| contains(reference.span))
+            .and_then(|reference| reference.symbol)
+            .map(|index| &self.symbols[index])
+    }
+
+    /// When `offset` is inside an unclosed `{` of a string literal, the start of
+    /// the placeholder name typed so far.
+    pub fn placeholder_start(&self, offset: usize) -> Option<usize> {
+        let token = self.tokens.iter().find(|t| {
+            let closed = t.end - t.start > 1 && self.text.as_bytes()[t.end - 1] == b'"';
+            t.kind == TokenKind::StringLiteral && t.start < offset && (offset < t.end || !closed)
+        })?;
+        let before = &self.text[token.start..offset];
+        let open = before.rfind('{')?;
+        if before[open..].contains('}') || (open > 0 && before.as_bytes()[open - 1] == b'\\') {
+            return None;
+        }
+        Some(token.start + open + 1)
+    }
+
+    /// Empty for the byte tokens the tokenizer makes of non-ASCII characters.
+    fn lexeme(&self, token: &Token) -> &str {
+        self.text.get(token.start..token.end).unwrap_or("")
+    }
+
+    fn analyze(&mut self) {
+        self.line_starts = std::iter::once(0)
+            .chain(self.text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        // Statements are line-based: split into lines of significant tokens
+        let mut lines: Vec<Vec<Token>> = vec![Vec::new()];
+        for token in &self.tokens {
+            match token.kind {
+                TokenKind::Newline => lines.push(Vec::new()),
+                TokenKind::Comment => {}
+                TokenKind::Unknown if self.lexeme(token).trim().is_empty() => {}
+                _ => lines.last_mut().unwrap().push(*token),
+            }
+        }
+
+        self.symbols.clear();
+        self.references.clear();
+        self.records.clear();
+        for line in &lines {
+            self.statement(line);
+        }
+
+        // A name refers to its latest declaration before the use, otherwise to
+        // the first one after it: variables are program-wide on every target
+        for reference in &mut self.references {
+            let mut declarations = self.symbols.iter().enumerate().filter(|(_, s)| s.name == reference.name);
+            let before = declarations.clone().rfind(|(_, s)| s.span.0 < reference.span.0);
+            reference.symbol = before.or_else(|| declarations.next()).map(|(index, _)| index);
+        }
+
+        let (ast, mut var_map, errors) = AST::parse(&self.tokens, &self.text);
+        let semantic = check_variables(&ast, &mut var_map);
+        self.diagnostics = errors
+            .into_iter()
+            .chain(semantic)
+            .map(|error| Diagnostic {
+                span: self.line_span(error.line()),
+                error,
+            })
+            .collect();
+
+        let names = self.name_errors();
+        self.diagnostics.extend(names);
+    }
+
+    /// Names that no declaration matches, and `@` variables that math or a
+    /// call's `-> name` changes, each spanning the name.
+    fn name_errors(&self) -> Vec<Diagnostic> {
+        self.references
+            .iter()
+            .filter_map(|reference| {
+                let line = self.position(reference.span.0).0 + 1;
+                let error = match (reference.symbol, reference.access) {
+                    (None, Access::Read | Access::Write | Access::Input | Access::Placeholder) => {
+                        VarError::UndeclaredVariable {
+                            name: reference.name.clone(),
+                            line,
+                        }
+                    }
+                    (Some(index), Access::Write) if !self.symbols[index].mutable => VarError::ImmutableAssignment {
+                        name: reference.name.clone(),
+                        line,
+                    },
+                    _ => return None,
+                };
+                Some(Diagnostic {
+                    span: reference.span,
+                    error,
+                })
+            })
+            .collect()
+    }
+
+    fn reference(&mut self, token: &Token, access: Access) {
+        let name = self.lexeme(token);
+        if is_name(name) {
+            self.references.push(Reference {
+                name: name.to_string(),
+                span: (token.start, token.end),
+                access,
+                symbol: None,
+            });
+        }
+    }
+
+    fn placeholders(&mut self, token: &Token) {
+        let literal = self.lexeme(token);
+        let mut found = Vec::new();
+        let mut rest = 0;
+        while let Some(open) = literal[rest..].find('{').map(|i| rest + i) {
+            let Some(close) = literal[open..].find('}').map(|i| open + i) else {
+                break;
+            };
+            rest = close + 1;
+            if open > 0 && literal.as_bytes()[open - 1] == b'\\' {
+                continue;
+            }
+            // The spec of `{name:spec}` is not part of the name, valid or not
+            let inner = &literal[open + 1..close];
+            let mut inner = &inner[..inner.find(':').unwrap_or(inner.len())];
+            let mut inner_start = token.start + open + 1;
+            // `{-name}` reads the value it negates
+            if let Some(operand) = inner.trim_start().strip_prefix('-') {
+                inner_start += inner.len() - operand.len();
+                inner = operand;
+            }
+            let name = inner.trim();
+            let inner_tokens = tokenize(inner, inner_start);
+            if self.is_access(&inner_tokens) {
+                // Every variable in `{name[index]}`, `{len(name)}`, `{i(name)}` and `{name.field}` is read
+                for (i, token) in inner_tokens.iter().enumerate() {
+                    let word = &self.text[token.start..token.end];
+                    if token.kind == TokenKind::Identifier && is_name(word) && !self.is_member(&inner_tokens, i) {
+                        found.push((word.to_string(), (token.start, token.end)));
+                    }
+                }
+            } else if !name.is_empty() {
+                let name_start = inner_start + (inner.len() - inner.trim_start().len());
+                found.push((name.to_string(), (name_start, name_start + name.len())));
+            }
+        }
+        for (name, span) in found {
+            self.references.push(Reference {
+                name,
+                span,
+                access: Access::Placeholder,
+                symbol: None,
+            });
+        }
+    }
+
+    /// References every variable in `tokens` as read; the name of a conversion or
+    /// string operation such as `str(n)` or `upper(name)` and the field of
+    /// `name.field` are not variables.
+    fn reads(&mut self, tokens: &[Token]) {
+        for (i, token) in tokens.iter().enumerate() {
+            if token.kind == TokenKind::Identifier && !self.is_member(tokens, i) {
+                self.reference(token, Access::Read);
+            }
+        }
+    }
+
+    /// Whether `tokens[i]` is the name of a conversion, string operation or math
+    /// function, or the field of `name.field`.
+    fn is_member(&self, tokens: &[Token], i: usize) -> bool {
+        let call = is_builtin(self.lexeme(&tokens[i])) && tokens.get(i + 1).map(|t| t.kind) == Some(TokenKind::LSmallB);
+        let field = i > 0 && self.lexeme(&tokens[i - 1]) == ".";
+        call || field
+    }
+
+    /// Whether `tokens` start with an element `name[...]`, a conversion such as
+    /// `i(name)`, a string operation such as `len(name)` or a field `name.field`.
+    fn is_access(&self, tokens: &[Token]) -> bool {
+        match tokens {
+            [name, open, ..] if name.kind == TokenKind::Identifier => {
+                open.kind == TokenKind::LBigB
+                    || (is_builtin(self.lexeme(name)) && open.kind == TokenKind::LSmallB)
+                    || self.lexeme(open) == "."
+            }
+            _ => false,
+        }
+    }
+
+    /// The record type `# Name { field type, ... }` declares, leaving out fields
+    /// that are not written correctly; the parser reports those.
+    fn record(&self, tokens: &[Token]) -> Option<RecordType> {
+        let [name, open, inner @ .., close] = tokens else { return None };
+        if name.kind != TokenKind::Identifier || open.kind != TokenKind::LCurlyB || close.kind != TokenKind::RCurlyB {
+            return None;
+        }
+        let fields = inner
+            .split(|t| self.lexeme(t) == ",")
+            .filter_map(|part| match part {
+                [field, word] => Some((self.lexeme(field).to_string(), parse_type(self.lexeme(word), None)?)),
+                [field, word, _, size, _] => {
+                    Some((self.lexeme(field).to_string(), parse_type(self.lexeme(word), Some(self.lexeme(size)))?))
+                }
+                _ => None,
+            })
+            .collect();
+        Some(RecordType {
+            name: self.lexeme(name).to_string(),
+            fields,
+        })
+    }
+
+    /// Records the declarations and references of one statement.
+    fn statement(&mut self, tokens: &[Token]) {
+        // Closing braces of blocks may share the line with a statement
+        let start = tokens.iter().position(|t| t.kind != TokenKind::RCurlyB).unwrap_or(tokens.len());
+        let tokens = &tokens[start..];
+        let Some(first) = tokens.first() else { return };
+
+        match first.kind {
+            TokenKind::At => {
+                let mutable = tokens.get(1).map(|t| t.kind) == Some(TokenKind::At);
+                let rest = &tokens[1 + mutable as usize..];
+                let Some(name) = rest.first().filter(|t| t.kind == TokenKind::Identifier) else {
+                    return;
+                };
+                let type_word = rest.get(1).map(|t| self.lexeme(t)).unwrap_or("");
+                let mut initializer = 2;
+                let size = if type_word == "c" && rest.get(2).map(|t| t.kind) == Some(TokenKind::LBigB) {
+                    initializer = 5;
+                    rest.get(3).map(|t| self.lexeme(t))
+                } else {
+                    None
+                };
+                let mut var_type = parse_type(type_word, size).or_else(|| {
+                    let record = self.records.iter().rev().find(|record| record.name == type_word)?;
+                    Some(VarType::Record(record.clone()))
+                });
+                let record = matches!(var_type, Some(VarType::Record(_)));
+                // `T[]` is an array of T, initialized with a list
+                let array = type_word != "c"
+                    && rest.get(2).map(|t| t.kind) == Some(TokenKind::LBigB)
+                    && rest.get(3).map(|t| t.kind) == Some(TokenKind::RBigB);
+                if array {
+                    initializer = 4;
+                    var_type = var_type.map(|element| VarType::Array(Box::new(element)));
+                }
+                // `map[K]V` starts as `{}`
+                if type_word == "map"
+                    && rest.get(2).map(|t| t.kind) == Some(TokenKind::LBigB)
+                    && rest.get(4).map(|t| t.kind) == Some(TokenKind::RBigB)
+                {
+                    initializer = 6;
+                    let key = rest.get(3).and_then(|t| parse_type(self.lexeme(t), None));
+                    let value = rest.get(5).and_then(|t| parse_type(self.lexeme(t), None));
+                    var_type = key.zip(value).map(|(key, value)| VarType::Map(Box::new(key), Box::new(value)));
+                }
+                let condition = var_type == Some(VarType::Bool);
+                let line_end = tokens.last().map_or(name.end, |t| t.end);
+                self.symbols.push(Symbol {
+                    name: self.lexeme(name).to_string(),
+                    mutable,
+                    var_type,
+                    span: (name.start, name.end),
+                    full: (first.start, line_end),
+                });
+                // A `b` is initialized with a condition over any number of
+                // variables, an array with a list of values and a record with
+                // `{ field value, ... }`, whose field names are not variables
+                let value = &rest[initializer.min(rest.len())..];
+                if record {
+                    for (i, token) in value.iter().enumerate() {
+                        let field = i > 0 && matches!(self.lexeme(&value[i - 1]), "{" | ",");
+                        if token.kind == TokenKind::Identifier && !field && !self.is_member(value, i) {
+                            self.reference(token, Access::Read);
+                        }
+                    }
+                } else if condition || array || self.is_access(value) {
+                    self.reads(value);
+                } else if let Some(value) = rest.get(initializer).filter(|t| t.kind == TokenKind::Identifier) {
+                    // Initialized from another variable
+                    let value = *value;
+                    self.reference(&value, Access::Word);
+                }
+            }
+            TokenKind::Star => {
+                if let Some(target) = tokens.get(1).filter(|t| t.kind == TokenKind::Identifier) {
+                    let target = *target;
+                    self.reference(&target, Access::Write);
+                }
+                self.reads(&tokens[2.min(tokens.len())..]);
+            }
+            TokenKind::Hash => {
+                if let Some(record) = self.record(&tokens[1..]) {
+                    self.records.push(record);
+                }
+            }
+            TokenKind::DoubleDot => {
+                if let Some(target) = tokens.get(1).filter(|t| t.kind == TokenKind::Identifier) {
+                    let target = *target;
+                    self.reference(&target, Access::Input);
+                }
+            }
+            TokenKind::Greater => {
+                let start = tokens.iter().position(|t| t.kind != TokenKind::Greater).unwrap_or(tokens.len());
+                let body = &tokens[start..];
+                if self.is_access(body) {
+                    self.reads(body);
+                } else if let Some(token) = body.first() {
+                    match token.kind {
+                        TokenKind::StringLiteral => self.placeholders(token),
+                        TokenKind::Identifier => self.reference(token, Access::Word),
+                        _ => {}
+                    }
+                }
+            }
+            TokenKind::Question => {
+                let block = tokens.iter().position(|t| t.kind == TokenKind::LCurlyB).unwrap_or(tokens.len());
+                self.reads(&tokens[1..block]);
+                if block < tokens.len() {
+                    self.statement(&tokens[block + 1..]);
+                }
+            }
+            TokenKind::Tilde => {
+                // `~(item in array) {` declares the item, with the array's element type
+                let block = tokens.iter().position(|t| t.kind == TokenKind::LCurlyB).unwrap_or(tokens.len());
+                if let [open, item, keyword, array, ..] = tokens[1..block]
+                    && open.kind == TokenKind::LSmallB
+                    && item.kind == TokenKind::Identifier
+                    && self.lexeme(&keyword) == "in"
+                {
+                    let element = self
+                        .symbols
+                        .iter()
+                        .rev()
+                        .find(|symbol| symbol.name == self.lexeme(&array))
+                        .and_then(|symbol| match &symbol.var_type {
+                            Some(VarType::Array(element)) => Some((**element).clone()),
+                            _ => None,
+                        });
+                    let line_end = tokens[..block].last().map_or(item.end, |t| t.end);
+                    self.symbols.push(Symbol {
+                        name: self.lexeme(&item).to_string(),
+                        mutable: false,
+                        var_type: element,
+                        span: (item.start, item.end),
+                        full: (first.start, line_end),
+                    });
+                    self.reference(&array, Access::Read);
+                }
+                if block < tokens.len() {
+                    self.statement(&tokens[block + 1..]);
+                }
+            }
+            TokenKind::Identifier if tokens.get(1).map(|t| t.kind) == Some(TokenKind::LSmallB) => {
+                let close = tokens.iter().position(|t| t.kind == TokenKind::RSmallB).unwrap_or(tokens.len());
+                for token in tokens[2..close].iter().filter(|t| t.kind == TokenKind::Identifier) {
+                    self.reference(token, Access::Read);
+                }
+                let rest = &tokens[close.min(tokens.len())..];
+                if let Some(arrow) = rest.iter().position(|t| t.kind == TokenKind::Greater)
+                    && let Some(target) = rest.get(arrow + 1).filter(|t| t.kind == TokenKind::Identifier)
+                {
+                    let target = *target;
+                    self.reference(&target, Access::Write);
+                }
+            }
+            _ => {}
+        }
+    }
+}
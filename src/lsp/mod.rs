@@ -0,0 +1,354 @@
+//! `ven lsp`: a language server speaking JSON-RPC over stdin/stdout.
+//!
+//! Supports diagnostics, hover, go-to-definition (placeholders included),
+//! completion of variable names inside `{}` and document symbols. Documents are
+//! synced incrementally and only the edited lines are re-tokenized.
+
+pub mod document;
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use crate::json::Json;
+use crate::suggest;
+use document::Document;
+
+/// `TextDocumentSyncKind.Incremental`
+const SYNC_INCREMENTAL: usize = 2;
+/// `CompletionItemKind.Variable`
+const COMPLETION_VARIABLE: usize = 6;
+/// `SymbolKind.Variable` and `SymbolKind.Constant`
+const SYMBOL_VARIABLE: usize = 13;
+const SYMBOL_CONSTANT: usize = 14;
+/// `DiagnosticSeverity.Error`
+const SEVERITY_ERROR: usize = 1;
+/// JSON-RPC error codes
+const PARSE_ERROR: f64 = -32700.0;
+const INVALID_REQUEST: f64 = -32600.0;
+const METHOD_NOT_FOUND: f64 = -32601.0;
+/// Larger bodies are refused rather than allocated.
+const MAX_MESSAGE: usize = 64 << 20;
+
+pub struct Server<W: Write> {
+    out: W,
+    documents: HashMap<String, Document>,
+    shutdown: bool,
+}
+
+/// Reads one `Content-Length` framed message: its body, or why the frame could
+/// not be read, so the server can answer it and go on. `Ok(None)` at end of input.
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Result<String, String>>> {
+    let mut length = None;
+    loop {
+        let mut header = Vec::new();
+        if input.read_until(b'\n', &mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = String::from_utf8_lossy(&header);
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let length = match length {
+        Some(length) if length <= MAX_MESSAGE => length,
+        Some(length) => return Ok(Some(Err(format!("message of {} bytes is too large", length)))),
+        None => return Ok(Some(Err("message without a valid Content-Length".to_string()))),
+    };
+    let mut body = vec![0; length];
+    match input.read_exact(&mut body) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    Ok(Some(String::from_utf8(body).map_err(|e| format!("message is not UTF-8: {}", e))))
+}
+
+fn range(document: &Document, span: (usize, usize)) -> Json {
+    let position = |offset| {
+        let (line, character) = document.position(offset);
+        Json::object([("line", line.into()), ("character", character.into())])
+    };
+    Json::object([("start", position(span.0)), ("end", position(span.1))])
+}
+
+/// Byte offset of `params.position` in its document.
+fn offset_of(document: &Document, position: &Json) -> usize {
+    let line = position.get("line").as_u64().unwrap_or(0) as usize;
+    let character = position.get("character").as_u64().unwrap_or(0) as usize;
+    document.offset(line, character)
+}
+
+fn declaration(symbol: &document::Symbol) -> String {
+    let sigil = if symbol.mutable { "@@" } else { "@" };
+    match &symbol.var_type {
+        Some(var_type) => format!("{} {} {}", sigil, symbol.name, var_type),
+        None => format!("{} {}", sigil, symbol.name),
+    }
+}
+
+impl<W: Write> Server<W> {
+    pub fn new(out: W) -> Server<W> {
+        Server {
+            out,
+            documents: HashMap::new(),
+            shutdown: false,
+        }
+    }
+
+    /// Serves messages until `exit` or end of input. Returns the process exit
+    /// code: 0 only if `shutdown` came first, as the protocol asks.
+    pub fn run(&mut self, input: &mut impl BufRead) -> io::Result<i32> {
+        while let Some(message) = read_message(input)? {
+            let message = match message.and_then(|body| Json::parse(&body).map_err(|e| format!("invalid JSON: {}", e))) {
+                Ok(message) => message,
+                Err(e) => {
+                    self.send_error(Json::Null, PARSE_ERROR, &e)?;
+                    continue;
+                }
+            };
+            let Some(method) = message.get("method").as_str().map(str::to_string) else {
+                let id = message.get("id").clone();
+                self.send_error(id, INVALID_REQUEST, "message without a method")?;
+                continue;
+            };
+            if method == "exit" {
+                return Ok(if self.shutdown { 0 } else { 1 });
+            }
+            let id = message.get("id").clone();
+            let params = message.get("params");
+            if id.is_null() {
+                self.notification(&method, params)?;
+            } else {
+                match self.request(&method, params) {
+                    Some(result) => self.send(Json::object([("jsonrpc", "2.0".into()), ("id", id), ("result", result)]))?,
+                    None => self.send_error(id, METHOD_NOT_FOUND, &format!("unknown method '{}'", method))?,
+                }
+            }
+        }
+        Ok(1)
+    }
+
+    fn send(&mut self, message: Json) -> io::Result<()> {
+        let body = message.to_string();
+        write!(self.out, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+        self.out.flush()
+    }
+
+    fn send_error(&mut self, id: Json, code: f64, message: &str) -> io::Result<()> {
+        let error = Json::object([("code", Json::Number(code)), ("message", message.into())]);
+        self.send(Json::object([("jsonrpc", "2.0".into()), ("id", id), ("error", error)]))
+    }
+
+    fn notification(&mut self, method: &str, params: &Json) -> io::Result<()> {
+        let uri = params.get("textDocument").get("uri").as_str().unwrap_or("").to_string();
+        match method {
+            "textDocument/didOpen" => {
+                let text = params.get("textDocument").get("text").as_str().unwrap_or("");
+                self.documents.insert(uri.clone(), Document::new(text.to_string()));
+                self.publish_diagnostics(&uri)
+            }
+            "textDocument/didChange" => {
+                let Some(document) = self.documents.get_mut(&uri) else {
+                    return Ok(());
+                };
+                for change in params.get("contentChanges").as_array().unwrap_or(&[]) {
+                    let text = change.get("text").as_str().unwrap_or("");
+                    let range = change.get("range");
+                    if range.is_null() {
+                        *document = Document::new(text.to_string());
+                    } else {
+                        let start = offset_of(document, range.get("start"));
+                        let end = offset_of(document, range.get("end")).max(start);
+                        document.edit(start, end, text);
+                    }
+                }
+                self.publish_diagnostics(&uri)
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                self.send(Json::object([
+                    ("jsonrpc", "2.0".into()),
+                    ("method", "textDocument/publishDiagnostics".into()),
+                    ("params", Json::object([("uri", uri.into()), ("diagnostics", Json::Array(Vec::new()))])),
+                ]))
+            }
+            // "initialized", "$/cancelRequest", "$/setTrace" and the rest need no reply
+            _ => Ok(()),
+        }
+    }
+
+    fn publish_diagnostics(&mut self, uri: &str) -> io::Result<()> {
+        let Some(document) = self.documents.get(uri) else {
+            return Ok(());
+        };
+        let diagnostics = document
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                Json::object([
+                    ("range", range(document, diagnostic.span)),
+                    ("severity", SEVERITY_ERROR.into()),
+                    ("code", diagnostic.error.code().into()),
+                    ("source", "ven".into()),
+                    ("message", diagnostic.error.to_string().into()),
+                ])
+            })
+            .collect();
+        self.send(Json::object([
+            ("jsonrpc", "2.0".into()),
+            ("method", "textDocument/publishDiagnostics".into()),
+            ("params", Json::object([("uri", uri.into()), ("diagnostics", Json::Array(diagnostics))])),
+        ]))
+    }
+
+    /// The result of a request, or `None` for unknown methods.
+    fn request(&mut self, method: &str, params: &Json) -> Option<Json> {
+        let uri = params.get("textDocument").get("uri").as_str().unwrap_or("");
+        let document = self.documents.get(uri);
+        let offset = document.map(|document| offset_of(document, params.get("position")));
+
+        let result = match method {
+            "initialize" => Json::object([
+                (
+                    "capabilities",
+                    Json::object([
+                        (
+                            "textDocumentSync",
+                            Json::object([("openClose", true.into()), ("change", SYNC_INCREMENTAL.into())]),
+                        ),
+                        ("hoverProvider", true.into()),
+                        ("definitionProvider", true.into()),
+                        (
+                            "completionProvider",
+                            Json::object([("triggerCharacters", Json::Array(vec!["{".into()]))]),
+                        ),
+                        ("documentSymbolProvider", true.into()),
+                        ("codeActionProvider", true.into()),
+                    ]),
+                ),
+                ("serverInfo", Json::object([("name", "ven".into())])),
+            ]),
+            "shutdown" => {
+                self.shutdown = true;
+                Json::Null
+            }
+            "textDocument/hover" => {
+                let (Some(document), Some(offset)) = (document, offset) else {
+                    return Some(Json::Null);
+                };
+                let Some(symbol) = document.symbol_at(offset) else {
+                    return Some(Json::Null);
+                };
+                let kind = if symbol.mutable { "Mutable" } else { "Immutable" };
+                let var_type = match &symbol.var_type {
+                    Some(var_type) => format!("`{}`", var_type),
+                    None => "untyped".to_string(),
+                };
+                let line = document.position(symbol.span.0).0 + 1;
+                let value = format!(
+                    "```ven\n{}\n```\n{} {} variable, declared on line {}",
+                    declaration(symbol),
+                    kind,
+                    var_type,
+                    line
+                );
+                Json::object([(
+                    "contents",
+                    Json::object([("kind", "markdown".into()), ("value", value.into())]),
+                )])
+            }
+            "textDocument/definition" => {
+                let (Some(document), Some(offset)) = (document, offset) else {
+                    return Some(Json::Null);
+                };
+                match document.symbol_at(offset) {
+                    Some(symbol) => Json::object([("uri", uri.into()), ("range", range(document, symbol.span))]),
+                    None => Json::Null,
+                }
+            }
+            "textDocument/completion" => {
+                let (Some(document), Some(offset)) = (document, offset) else {
+                    return Some(Json::Array(Vec::new()));
+                };
+                if document.placeholder_start(offset).is_none() {
+                    return Some(Json::Array(Vec::new()));
+                }
+                let mut seen = Vec::new();
+                let mut items = Vec::new();
+                for symbol in &document.symbols {
+                    if seen.contains(&symbol.name) {
+                        continue;
+                    }
+                    seen.push(symbol.name.clone());
+                    items.push(Json::object([
+                        ("label", symbol.name.clone().into()),
+                        ("kind", COMPLETION_VARIABLE.into()),
+                        ("detail", declaration(symbol).into()),
+                    ]));
+                }
+                Json::Array(items)
+            }
+            "textDocument/documentSymbol" => {
+                let Some(document) = document else {
+                    return Some(Json::Array(Vec::new()));
+                };
+                let symbols = document
+                    .symbols
+                    .iter()
+                    .map(|symbol| {
+                        let kind = if symbol.mutable { SYMBOL_VARIABLE } else { SYMBOL_CONSTANT };
+                        let detail = symbol.var_type.as_ref().map(|t| t.to_string()).unwrap_or_default();
+                        Json::object([
+                            ("name", symbol.name.clone().into()),
+                            ("detail", detail.into()),
+                            ("kind", kind.into()),
+                            ("range", range(document, symbol.full)),
+                            ("selectionRange", range(document, symbol.span)),
+                        ])
+                    })
+                    .collect();
+                Json::Array(symbols)
+            }
+            "textDocument/codeAction" => {
+                let Some(document) = document else {
+                    return Some(Json::Array(Vec::new()));
+                };
+                let start = offset_of(document, params.get("range").get("start"));
+                let end = offset_of(document, params.get("range").get("end"));
+                let actions = suggest::diagnostics(document)
+                    .into_iter()
+                    .filter_map(|diagnostic| diagnostic.fix)
+                    .filter(|fix| fix.span.start <= end && fix.span.end >= start)
+                    .map(|fix| {
+                        let edit = Json::object([
+                            ("range", range(document, (fix.span.start, fix.span.end))),
+                            ("newText", fix.replacement.into()),
+                        ]);
+                        Json::object([
+                            ("title", fix.message.into()),
+                            ("kind", "quickfix".into()),
+                            ("isPreferred", true.into()),
+                            ("edit", Json::object([("changes", Json::object([(uri, Json::Array(vec![edit]))]))])),
+                        ])
+                    })
+                    .collect();
+                Json::Array(actions)
+            }
+            _ => return None,
+        };
+        Some(result)
+    }
+}
+
+/// Runs the server on the process's stdin and stdout.
+pub fn serve() -> io::Result<i32> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    Server::new(io::stdout()).run(&mut input)
+}
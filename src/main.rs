@@ -1,29 +1,14 @@
 use std::env;
 use std::fs;
+use std::io;
 use std::path::Path;
 use std::process::exit;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
-use errmsgs::print_errors;
-use optimisers::pass1::optimize_pass1;
-use optimisers::pass2::pass2;
-use parse::AST;
-use token::{Tokenizer, TokenKind};
-use transpilers::C::transpile_c;
-use transpilers::LLVM::transpile_llvm;
-use transpilers::LX8664::transpile_lx8664;
-use transpilers::RST::transpile_rs;
-
-pub mod errmsgs;
-pub mod errs;
-pub mod impl_parserstate;
-pub mod optimisers;
-pub mod parse;
-pub mod parse1;
-pub mod parse2;
-pub mod token;
-pub mod transpilers;
-pub mod var_checker;
+use ven::diagnostic::{Diagnostic, ErrorFormat, emit};
+use ven::token::TokenKind;
+use ven::transpilers::{BACKENDS, CodegenOptions, Overflow};
+use ven::{Options, Session, Target};
 
 /// Formats a Duration into a human-readable string using µs, ms, s, or min.
 fn format_duration(duration: Duration) -> String {
@@ -45,17 +30,253 @@ fn format_duration(duration: Duration) -> String {
 
 fn print_help() {
     println!("Ven Engine");
+    println!("├── repl                         Start an interactive session");
+    println!("├── lsp                          Start the language server on stdin/stdout");
+    println!("├── fmt [--check] <files.ven>    Format files in place (--check: list unformatted files)");
+    println!("├── lint <files.ven>             Warn about likely mistakes (--deny-warnings: fail on any)");
+    println!("├── fix [--check] <files.ven>    Apply suggested fixes, such as misspelt names (--check: only list them)");
+    println!("├── explain [<code>]             Explain an error code or lint rule, or list them all");
     println!("├── --in=<file_path.ven>         Input file (must end with .ven)");
-    println!("├── -t=<rs/rust,c,llvm,lx8664>     Target output format");
+    println!("├── -t=<target>                  Target output format (see --list-targets)");
+    println!("├── --list-targets               List the available targets and what they support");
+    println!("├── --wasm-binary                Also write a binary .wasm module (wasm target)");
+    println!("├── --overflow=<mode>            Integer overflow: wrap (default), saturate or trap");
+    println!("├── --no-runtime-checks          Leave out the division by zero checks");
+    println!("├── --error-format=<format>      How errors are printed: human (default), short or json");
     println!("├── --show-msgs or -sm            Show messages in a tree-like view");
     println!("├── -h, --help                   Show help information");
     println!("└── -v, --version, --ver         Show version information");
 }
 
+fn print_targets() {
+    println!("Targets");
+    for (i, backend) in BACKENDS.iter().enumerate() {
+        let branch = if i + 1 == BACKENDS.len() { "└──" } else { "├──" };
+        let mut names = backend.name().to_string();
+        for alias in backend.aliases() {
+            names.push('/');
+            names.push_str(alias);
+        }
+        let missing = backend.capabilities().missing();
+        let support = if missing.is_empty() {
+            String::new()
+        } else {
+            format!(" (no {})", missing.join(", "))
+        };
+        println!(
+            "{} {:<22} .{:<5} {}{}",
+            branch,
+            names,
+            backend.extension(),
+            backend.description(),
+            support
+        );
+    }
+}
+
 fn print_version() {
     println!("Ven Engine Version 0.0.1");
 }
 
+/// `ven fmt`: rewrites each file in its canonical layout, or with `--check`
+/// only lists the files that are not. Returns the exit code.
+fn format_files(args: &[String]) -> i32 {
+    let check = args.iter().any(|arg| arg == "--check");
+    let paths: Vec<&String> = args.iter().filter(|arg| *arg != "--check").collect();
+    if paths.is_empty() {
+        eprintln!("Error: Missing files to format (e.g., ven fmt main.ven)");
+        return 1;
+    }
+
+    let mut code = 0;
+    for path in paths {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("Error reading file {}: {}", path, e);
+                code = 1;
+                continue;
+            }
+        };
+        let formatted = match ven::formatter::format(&source) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                eprintln!("Error formatting {}: {}", path, e);
+                code = 1;
+                continue;
+            }
+        };
+        if formatted == source {
+            continue;
+        }
+        if check {
+            println!("{}", path);
+            code = 1;
+        } else if let Err(e) = fs::write(path, &formatted) {
+            eprintln!("Error writing to file {}: {}", path, e);
+            code = 1;
+        } else {
+            println!("Formatted {}", path);
+        }
+    }
+    code
+}
+
+/// `ven fix`: applies every suggested fix to each file and prints each change
+/// as `file:line:column: old -> new`, or with `--check` only prints them.
+/// Returns the exit code: 1 for unreadable files, and with `--check` when
+/// anything would change.
+fn fix_files(args: &[String]) -> i32 {
+    let check = args.iter().any(|arg| arg == "--check");
+    let paths: Vec<&String> = args.iter().filter(|arg| *arg != "--check").collect();
+    if paths.is_empty() {
+        eprintln!("Error: Missing files to fix (e.g., ven fix main.ven)");
+        return 1;
+    }
+
+    let mut code = 0;
+    for path in paths {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("Error reading file {}: {}", path, e);
+                code = 1;
+                continue;
+            }
+        };
+        let document = ven::lsp::document::Document::new(source.clone());
+        let fixes: Vec<_> = ven::suggest::diagnostics(&document)
+            .into_iter()
+            .filter_map(|diagnostic| diagnostic.fix)
+            .collect();
+        let (fixed, applied) = ven::suggest::apply(&source, &fixes);
+        for fix in &applied {
+            println!(
+                "{}:{}:{}: `{}` -> `{}`",
+                path,
+                fix.span.line,
+                fix.span.column,
+                &source[fix.span.start..fix.span.end],
+                fix.replacement
+            );
+        }
+        if applied.is_empty() {
+            continue;
+        }
+        if check {
+            code = 1;
+        } else if let Err(e) = fs::write(path, &fixed) {
+            eprintln!("Error writing to file {}: {}", path, e);
+            code = 1;
+        }
+    }
+    code
+}
+
+/// The `--error-format=` value among `args`, or `default`. Exits on an unknown format.
+fn error_format(args: &[String], default: ErrorFormat) -> ErrorFormat {
+    match args.iter().rev().find_map(|arg| arg.strip_prefix("--error-format=")) {
+        Some(name) => ErrorFormat::from_name(name).unwrap_or_else(|| {
+            eprintln!("Error: Unknown error format '{}' (use human, short or json)", name);
+            exit(1);
+        }),
+        None => default,
+    }
+}
+
+/// `ven lint`: prints each warning on stderr, as `file:line:column: warning:
+/// message [rule]` unless `--error-format=` says otherwise. Returns the exit
+/// code: 1 for unreadable files, parse errors and bad `.venlint` files, and with
+/// `--deny-warnings` for any warning.
+fn lint_files(args: &[String]) -> i32 {
+    let deny = args.iter().any(|arg| arg == "--deny-warnings");
+    let format = error_format(args, ErrorFormat::Short);
+    let paths: Vec<&String> = args.iter().filter(|arg| !arg.starts_with("--")).collect();
+    if paths.is_empty() {
+        eprintln!("Error: Missing files to lint (e.g., ven lint main.ven)");
+        return 1;
+    }
+
+    let mut code = 0;
+    for path in paths {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("Error reading file {}: {}", path, e);
+                code = 1;
+                continue;
+            }
+        };
+        let config = match ven::lint::Config::find(Path::new(path)) {
+            Ok((_, config)) => config,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                code = 1;
+                continue;
+            }
+        };
+        let session = Session::new(source.as_str());
+        let diagnostics: Vec<Diagnostic> = if session.errors().is_empty() {
+            ven::lint::lint(&source, &config)
+                .iter()
+                .map(|lint| Diagnostic::from_lint(lint, &source).with_file(path))
+                .collect()
+        } else {
+            code = 1;
+            let (names, types) = (session.names(), session.type_names());
+            session
+                .errors()
+                .iter()
+                .map(|error| Diagnostic::from_var_error(error, &source, &names, &types).with_file(path))
+                .collect()
+        };
+        if deny && !diagnostics.is_empty() {
+            code = 1;
+        }
+        let _ = emit(&mut io::stderr(), &diagnostics, Some(&source), format);
+    }
+    code
+}
+
+/// `ven explain`: prints the explanation of one code, or lists every code.
+/// `--check` verifies the examples of all explanations instead. Returns the
+/// exit code.
+fn explain(args: &[String]) -> i32 {
+    use ven::explain::{EXPLANATIONS, check, find};
+
+    match args.first().map(String::as_str) {
+        None => {
+            for explanation in &EXPLANATIONS {
+                println!("{:<22} {}", explanation.code, explanation.title);
+            }
+            0
+        }
+        Some("--check") => {
+            let mut code = 0;
+            for explanation in &EXPLANATIONS {
+                match check(explanation) {
+                    Ok(()) => println!("ok   {}", explanation.code),
+                    Err(e) => {
+                        println!("FAIL {}", e);
+                        code = 1;
+                    }
+                }
+            }
+            code
+        }
+        Some(name) => match find(name) {
+            Some(explanation) => {
+                print!("{}", explanation);
+                0
+            }
+            None => {
+                eprintln!("Error: No explanation for '{}' (run ven explain to list the codes)", name);
+                1
+            }
+        },
+    }
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
 
@@ -64,14 +285,50 @@ fn main() {
         exit(1);
     }
 
+    if args[1] == "repl" {
+        if let Err(e) = ven::repl::Repl::new().run() {
+            eprintln!("Error: {}", e);
+            exit(1);
+        }
+        exit(0);
+    }
+    if args[1] == "lsp" {
+        match ven::lsp::serve() {
+            Ok(code) => exit(code),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                exit(1);
+            }
+        }
+    }
+
+    if args[1] == "fmt" {
+        exit(format_files(&args[2..]));
+    }
+    if args[1] == "lint" {
+        exit(lint_files(&args[2..]));
+    }
+    if args[1] == "fix" {
+        exit(fix_files(&args[2..]));
+    }
+    if args[1] == "explain" {
+        exit(explain(&args[2..]));
+    }
+
     let mut input_file: Option<String> = None;
     let mut target: Option<String> = None;
     let mut show_msgs = false;
+    let mut wasm_binary = false;
+    let mut codegen = CodegenOptions::default();
+    let format = error_format(&args, ErrorFormat::Human);
 
     for arg in &args[1..] {
         if arg == "-h" || arg == "--help" {
             print_help();
             exit(0);
+        } else if arg == "--list-targets" {
+            print_targets();
+            exit(0);
         } else if arg == "-v" || arg == "--version" || arg == "--ver" {
             print_version();
             exit(0);
@@ -85,6 +342,18 @@ fn main() {
             );
         } else if arg == "--show-msgs" || arg == "-sm" {
             show_msgs = true;
+        } else if arg == "--wasm-binary" {
+            wasm_binary = true;
+        } else if arg == "--no-runtime-checks" {
+            codegen.runtime_checks = false;
+        } else if let Some(mode) = arg.strip_prefix("--overflow=") {
+            codegen.overflow = match Overflow::from_name(mode) {
+                Some(overflow) => overflow,
+                None => {
+                    eprintln!("Error: Unknown overflow mode '{}' (use wrap, saturate or trap)", mode);
+                    exit(1);
+                }
+            };
         }
     }
 
@@ -98,20 +367,20 @@ fn main() {
         }
     };
 
-    let target_lang = match target.as_deref() {
-        Some("rs") | Some("rust") => "rust",
-        Some("c") | Some("C") => "c",
-        Some("lx8664") | Some("LX8664") => "lx8664",
-        Some("llvm") | Some("LLVM") => "llvm",
-        Some(t) => {
-            eprintln!("Error: Unsupported target '{}'", t);
-            exit(1);
-        }
+    let target = match target.as_deref() {
+        Some(t) => match Target::from_name(t) {
+            Some(target) => target,
+            None => {
+                eprintln!("Error: Unsupported target '{}' (see --list-targets)", t);
+                exit(1);
+            }
+        },
         None => {
             eprintln!("Error: Missing -t=<target> argument (e.g., -t=rs)");
             exit(1);
         }
     };
+    let backend = target.backend();
 
     let code = match fs::read_to_string(&input_path) {
         Ok(c) => c,
@@ -123,154 +392,92 @@ fn main() {
 
     if show_msgs {
         println!("Running:");
+        ven::set_trace(true);
     }
 
-    // --- Tokenizing ---
-    let start = Instant::now();
-    let mut tokenizer = Tokenizer::new(&code);
-    let tokens = tokenizer.tokenize();
-    let token_time = start.elapsed();
+    // --- Tokenizing and parsing ---
+    let session = Session::new(code);
     if show_msgs {
+        let (_, token_time) = session.phases()[0];
         println!("├── Tokenizing... took {}", format_duration(token_time));
-        
+
         // Print all tokens for debugging
         println!("├── TOKEN DEBUG:");
-        for (i, token) in tokens.iter().enumerate() {
-            let lexeme = unsafe { std::str::from_utf8_unchecked(&code.as_bytes()[token.start..token.end]) };
-            println!("    ├── Token {}: Kind={:?}, Lexeme=\"{}\"", i, token.kind, lexeme);
-            
+        for (i, token) in session.tokens().iter().enumerate() {
+            println!("    ├── Token {}: Kind={:?}, Lexeme=\"{}\"", i, token.kind, session.lexeme(token));
+
             // Specific debugging for DoubleDot tokens
             if token.kind == TokenKind::DoubleDot {
                 println!("    ├── FOUND DOUBLE DOT at position {}", i);
             }
         }
-    }
 
-    // --- Parsing AST ---
-    let start = Instant::now();
-    let (mut ast, _var_map, errors) = AST::parse(&tokenizer.tokens, &code);
-    let parse_time = start.elapsed();
-    if show_msgs {
+        let (_, parse_time) = session.phases()[1];
         println!("├── Parsing AST... took {}", format_duration(parse_time));
-    }
-    ast.debug();
-    if !errors.is_empty() {
-        print_errors(&errors);
-        exit(1);
-    }
-    ast.debug();
-
-    // --- Optimizing AST (pass1) ---
-    let start = Instant::now();
-    optimize_pass1(&mut ast);
-    let opt1_time = start.elapsed();
-    if show_msgs {
-        println!(
-            "├── Optimizing AST (pass1)... took {}",
-            format_duration(opt1_time)
-        );
+        session.ast().debug();
     }
 
-    // --- Optimizing AST (pass2) ---
-    let start = Instant::now();
-    let ast = pass2(ast);
-    let opt2_time = start.elapsed();
-    if show_msgs {
-        println!(
-            "├── Optimizing AST (pass2)... took {}",
-            format_duration(opt2_time)
-        );
-    }
-
-    // --- Transpiling ---
-    if show_msgs {
-        println!("├── Transpiling to {}...", target_lang);
-    }
-    if target_lang == "rust" {
-        let start = Instant::now();
-        let rust_code = transpile_rs(&ast);
-        let transp_time = start.elapsed();
-        let output_path = Path::new(&input_path).with_extension("rs");
-        match fs::write(&output_path, rust_code) {
-            Ok(_) => {
-                if show_msgs {
-                    println!(
-                        "└── Transpiling to Rust... took {}",
-                        format_duration(transp_time)
-                    );
-                }
-                println!("Successfully transpiled to Rust: {}", output_path.display());
-            }
-            Err(e) => {
-                eprintln!("Error writing to file {}: {}", output_path.display(), e);
-                exit(1);
-            }
-        }
-    } else if target_lang == "c" {
-        let start = Instant::now();
-        let c_code = transpile_c(&ast);
-        let transp_time = start.elapsed();
-        let output_path = Path::new(&input_path).with_extension("c");
-        match fs::write(&output_path, c_code) {
-            Ok(_) => {
-                if show_msgs {
-                    println!(
-                        "└── Transpiling to C... took {}",
-                        format_duration(transp_time)
-                    );
-                }
-                println!("Successfully transpiled to C: {}", output_path.display());
-            }
-            Err(e) => {
-                eprintln!("Error writing to file {}: {}", output_path.display(), e);
-                exit(1);
-            }
+    // --- Optimizing and transpiling ---
+    let mut options = Options::default();
+    options.binary = wasm_binary;
+    options.codegen = codegen;
+    let output = match session.compile(target, &options) {
+        Ok(output) => output,
+        Err(diagnostics) => {
+            let (names, types) = (session.names(), session.type_names());
+            let diagnostics: Vec<Diagnostic> = diagnostics
+                .errors
+                .iter()
+                .map(|error| Diagnostic::from_var_error(error, session.source(), &names, &types))
+                .chain(diagnostics.backend.iter().map(Diagnostic::from_backend_error))
+                .map(|diagnostic| diagnostic.with_file(&input_path))
+                .collect();
+            let _ = emit(&mut io::stderr(), &diagnostics, Some(session.source()), format);
+            exit(1);
         }
-    } else if target_lang == "llvm" {
-        let start = Instant::now();
-        let llvm_code = transpile_llvm(&ast);
-        let transp_time = start.elapsed();
-        let output_path = Path::new(&input_path).with_extension("ll");
-        match fs::write(&output_path, llvm_code) {
-            Ok(_) => {
-                if show_msgs {
-                    println!(
-                        "└── Transpiling to LLVM_IR... took {}",
-                        format_duration(transp_time)
-                    );
-                }
+    };
+    if show_msgs {
+        for (phase, time) in &output.phases {
+            if *phase == "Transpiling" {
                 println!(
-                    "Successfully transpiled to LLVM_IR: {}",
-                    output_path.display()
+                    "└── Transpiling to {}... took {}",
+                    backend.description(),
+                    format_duration(*time)
                 );
+            } else {
+                println!("├── {}... took {}", phase, format_duration(*time));
             }
-            Err(e) => {
-                eprintln!("Error writing to file {}: {}", output_path.display(), e);
-                exit(1);
-            }
         }
-    } else if target_lang == "lx8664" {
-        let start = Instant::now();
-        let asm_code = transpile_lx8664(&ast);
-        let transp_time = start.elapsed();
-        let output_path = Path::new(&input_path).with_extension("asm");
-        match fs::write(&output_path, asm_code) {
-            Ok(_) => {
-                if show_msgs {
-                    println!(
-                        "└── Transpiling to x86_64 Assembly... took {}",
-                        format_duration(transp_time)
-                    );
+    }
+
+    let output_path = Path::new(&input_path).with_extension(output.extension);
+    match fs::write(&output_path, &output.code) {
+        Ok(_) => {
+            println!(
+                "Successfully transpiled to {}: {}",
+                backend.description(),
+                output_path.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("Error writing to file {}: {}", output_path.display(), e);
+            exit(1);
+        }
+    }
+
+    if wasm_binary {
+        match &output.binary {
+            Some((extension, bytes)) => {
+                let output_path = Path::new(&input_path).with_extension(extension);
+                match fs::write(&output_path, bytes) {
+                    Ok(_) => println!("Successfully wrote binary module: {}", output_path.display()),
+                    Err(e) => {
+                        eprintln!("Error writing to file {}: {}", output_path.display(), e);
+                        exit(1);
+                    }
                 }
-                println!(
-                    "Successfully transpiled to x86_64 Assembly: {}",
-                    output_path.display()
-                );
-            }
-            Err(e) => {
-                eprintln!("Error writing to file {}: {}", output_path.display(), e);
-                exit(1);
             }
+            None => eprintln!("Warning: --wasm-binary has no effect for the {} target", backend.name()),
         }
     }
 }
@@ -1,9 +1,11 @@
 use memchr::memchr;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::slice;
 
-use crate::parse::{AST, ASTNode, Expression, VarType};
-pub type VarMap = HashMap<String, (VarType, usize)>;
+use crate::parse::{AST, ASTNode, Expression};
+use crate::parse2::parse_access;
+use crate::token::Tokenizer;
+use crate::transpilers::split_spec;
 
 #[inline(always)]
 pub fn optimize_pass1(ast: &mut AST) {
@@ -18,31 +20,41 @@ pub fn optimize_pass1(ast: &mut AST) {
             let mut optimized = Vec::with_capacity(nodes.len());
             let mut i = 0;
             while i < nodes.len() {
-                if let ASTNode::Print { to_stderr, expr } = &nodes[i] {
+                if let ASTNode::Print { to_stderr, expr, line } = &nodes[i]
+                    && is_text(expr, &used_vars)
+                {
                     let mut exprs = Vec::new();
                     let initial_expr = expr.clone().unwrap_or(Expression::Literal(String::new()));
                     exprs.push(initial_expr);
 
                     let mut j = i + 1;
                     while j < nodes.len() {
+                        // The merged print names the first one's line in runtime
+                        // errors, so only it may hold placeholders that can fail
                         if let ASTNode::Print {
                             to_stderr: t,
                             expr: e,
+                            ..
                         } = &nodes[j]
+                            && t == to_stderr
+                            && is_text(e, &used_vars)
+                            && !can_fail(e)
                         {
-                            if t == to_stderr {
-                                exprs.push(e.clone().unwrap_or(Expression::Literal(String::new())));
-                                j += 1;
-                                continue;
-                            }
+                            exprs.push(e.clone().unwrap_or(Expression::Literal(String::new())));
+                            j += 1;
+                            continue;
                         }
                         break;
                     }
 
-                    let total_len: usize = exprs.iter().map(|e| expr_to_string(e).len()).sum();
+                    // Each print ends its own line, so the merged text keeps a `\n` between them
+                    let total_len: usize = exprs.iter().map(|e| expr_to_string(e).len() + 2).sum();
                     let mut combined_str = String::with_capacity(total_len);
-                    for e in exprs {
-                        combined_str.push_str(&expr_to_string(&e));
+                    for (k, e) in exprs.iter().enumerate() {
+                        if k > 0 {
+                            combined_str.push_str("\\n");
+                        }
+                        combined_str.push_str(&expr_to_string(e));
                     }
 
                     if combined_str.starts_with(' ') {
@@ -52,6 +64,7 @@ pub fn optimize_pass1(ast: &mut AST) {
                     optimized.push(ASTNode::Print {
                         to_stderr: *to_stderr,
                         expr: Some(Expression::Literal(combined_str)),
+                        line: *line,
                     });
                     i = j;
                 } else {
@@ -80,12 +93,13 @@ fn collect_used_vars(ast: &AST) -> HashSet<String> {
 #[inline(always)]
 fn collect_used_vars_in_node(node: &ASTNode, used: &mut HashSet<String>) {
     match node {
-        ASTNode::If { body, .. } => {
+        ASTNode::If { condition, body, .. } => {
+            collect_used_vars_in_expression(condition, used);
             for child in body {
                 collect_used_vars_in_node(child, used);
             }
         }
-        ASTNode::Input { name } => {
+        ASTNode::Input { name, .. } => {
             used.insert(name.clone());
         }
         ASTNode::Print { expr, .. } => {
@@ -102,9 +116,56 @@ fn collect_used_vars_in_node(node: &ASTNode, used: &mut HashSet<String>) {
             used.insert(name.clone());
             collect_used_vars_in_expression(operand, used);
         }
+        ASTNode::Call { args, into, .. } => {
+            for arg in args {
+                collect_used_vars_in_expression(arg, used);
+            }
+            if let Some(name) = into {
+                used.insert(name.clone());
+            }
+        }
+        ASTNode::SetIndex { name, index, value, .. } => {
+            used.insert(name.clone());
+            collect_used_vars_in_expression(index, used);
+            collect_used_vars_in_expression(value, used);
+        }
+        ASTNode::Append { name, value, .. } => {
+            used.insert(name.clone());
+            collect_used_vars_in_expression(value, used);
+        }
+        ASTNode::RecordDeclaration(_) => {}
+        ASTNode::ForEach { array, body, .. } => {
+            used.insert(array.clone());
+            for child in body {
+                collect_used_vars_in_node(child, used);
+            }
+        }
     }
 }
 
+/// Prints of text and bare words can be merged; variables, elements,
+/// lengths and conditions cannot.
+#[inline(always)]
+fn is_text(expr: &Option<Expression>, used_vars: &HashSet<String>) -> bool {
+    match expr {
+        None | Some(Expression::Literal(_)) => true,
+        Some(Expression::Identifier(name)) => !used_vars.contains(name),
+        _ => false,
+    }
+}
+
+/// Whether a placeholder of the text can stop the program: an index, a call
+/// such as `i()` or `abs()`, or a negation that can overflow.
+fn can_fail(expr: &Option<Expression>) -> bool {
+    let Some(Expression::Literal(text)) = expr else {
+        return false;
+    };
+    text.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .any(|(inner, _)| split_spec(inner).0.contains(['[', '(', '-']))
+}
+
 #[inline(always)]
 fn collect_used_vars_in_expression(expr: &Expression, used: &mut HashSet<String>) {
     match expr {
@@ -140,7 +201,15 @@ fn collect_used_vars_in_expression(expr: &Expression, used: &mut HashSet<String>
                                     var_end - var_start,
                                 );
                                 if let Ok(var_str) = std::str::from_utf8(var_bytes) {
+                                    let var_str = split_spec(var_str).0;
+                                    // `{-name}` reads the value it negates
+                                    let var_str = var_str.strip_prefix('-').map_or(var_str, str::trim_start);
                                     used.insert(var_str.to_string());
+                                    // `{name[index]}`, `{len(name)}`, `{name.field}` and `{upper(name)}` read the names inside
+                                    let tokens = Tokenizer::new(var_str).tokenize().to_vec();
+                                    if let Some(access) = parse_access(&tokens, 0, tokens.len(), var_str) {
+                                        collect_used_vars_in_expression(&access, used);
+                                    }
                                 }
                             }
                             i = close + 1;
@@ -161,6 +230,32 @@ fn collect_used_vars_in_expression(expr: &Expression, used: &mut HashSet<String>
             collect_used_vars_in_expression(left, used);
             collect_used_vars_in_expression(right, used);
         }
+        Expression::Not(operand) => collect_used_vars_in_expression(operand, used),
+        Expression::List(items) | Expression::Text { args: items, .. } | Expression::Math { args: items, .. } => {
+            for item in items {
+                collect_used_vars_in_expression(item, used);
+            }
+        }
+        Expression::Index { name, index } => {
+            used.insert(name.clone());
+            collect_used_vars_in_expression(index, used);
+        }
+        Expression::Len(name) => {
+            used.insert(name.clone());
+        }
+        Expression::In { key, name } => {
+            used.insert(name.clone());
+            collect_used_vars_in_expression(key, used);
+        }
+        Expression::Field { name, .. } => {
+            used.insert(name.clone());
+        }
+        Expression::Record(values) => {
+            for (_, value) in values {
+                collect_used_vars_in_expression(value, used);
+            }
+        }
+        Expression::Convert { value, .. } => collect_used_vars_in_expression(value, used),
     }
 }
 
@@ -171,5 +266,15 @@ fn expr_to_string(expr: &Expression) -> String {
         Expression::Identifier(s) => s.clone(),
         Expression::BinaryOp { .. } => "".to_string(), // Default for now
         Expression::LogicalOp { .. } => "".to_string(), // Default for now
+        Expression::Not(_)
+        | Expression::List(_)
+        | Expression::Index { .. }
+        | Expression::Len(_)
+        | Expression::In { .. }
+        | Expression::Field { .. }
+        | Expression::Record(_)
+        | Expression::Convert { .. }
+        | Expression::Text { .. }
+        | Expression::Math { .. } => "".to_string(),
     }
 }
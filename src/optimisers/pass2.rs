@@ -1,4 +1,5 @@
-use crate::parse::{AST, ASTNode, Expression};
+use crate::parse::{AST, ASTNode, Expression, VarType};
+use crate::transpilers::literal_text;
 use std::collections::HashMap;
 
 use super::pass1::optimize_pass1;
@@ -29,15 +30,15 @@ fn build_inline_map(ast: &AST) -> InlineMap {
                 if let ASTNode::VarDeclaration {
                     mutable,
                     name,
+                    var_type,
                     value,
                     ..
                 } = node
+                    && !*mutable
+                    && let Some(Expression::Literal(lit)) = value
+                    && fits(var_type, lit)
                 {
-                    if !*mutable {
-                        if let Some(Expression::Literal(lit)) = value {
-                            map.insert(name.clone(), lit.clone());
-                        }
-                    }
+                    map.insert(name.clone(), lit.clone());
                 }
             }
         }
@@ -45,10 +46,20 @@ fn build_inline_map(ast: &AST) -> InlineMap {
     map
 }
 
+/// A literal too long for its `c[size]` is cut when stored, so only one
+/// that fits can stand in for the variable.
+#[inline(always)]
+fn fits(var_type: &VarType, lit: &str) -> bool {
+    match var_type {
+        VarType::Char { size } => literal_text(lit).len() <= *size,
+        _ => true,
+    }
+}
+
 #[inline(always)]
 fn inline_node(node: ASTNode, inline_map: &InlineMap) -> ASTNode {
     match node {
-        ASTNode::If { condition, body } => {
+        ASTNode::If { condition, body, line } => {
             let new_condition = inline_expr(condition, inline_map);
             let new_body = body
                 .into_iter()
@@ -57,6 +68,7 @@ fn inline_node(node: ASTNode, inline_map: &InlineMap) -> ASTNode {
             ASTNode::If {
                 condition: new_condition,
                 body: new_body,
+                line,
             }
         }
         ASTNode::VarDeclaration {
@@ -64,6 +76,7 @@ fn inline_node(node: ASTNode, inline_map: &InlineMap) -> ASTNode {
             name,
             var_type,
             value,
+            line,
         } => {
             let new_value = value.map(|expr| inline_expr(expr, inline_map));
             ASTNode::VarDeclaration {
@@ -71,28 +84,57 @@ fn inline_node(node: ASTNode, inline_map: &InlineMap) -> ASTNode {
                 name,
                 var_type,
                 value: new_value,
+                line,
             }
         }
-        ASTNode::Input { name } => ASTNode::Input { name },
-        ASTNode::Print { to_stderr, expr } => {
+        ASTNode::Input { name, line } => ASTNode::Input { name, line },
+        ASTNode::Print { to_stderr, expr, line } => {
             let new_expr = expr.map(|e| inline_expr(e, inline_map));
             ASTNode::Print {
                 to_stderr,
                 expr: new_expr,
+                line,
             }
         }
         ASTNode::MathOp {
             name,
+            field,
             operator,
             operand,
+            line,
         } => {
             let new_operand = inline_expr(operand, inline_map);
             ASTNode::MathOp {
                 name,
+                field,
                 operator,
                 operand: new_operand,
+                line,
             }
         }
+        ASTNode::Call { name, args, into, line } => ASTNode::Call {
+            name,
+            args: args.into_iter().map(|arg| inline_expr(arg, inline_map)).collect(),
+            into,
+            line,
+        },
+        ASTNode::SetIndex { name, index, value, line } => ASTNode::SetIndex {
+            name,
+            index: inline_expr(index, inline_map),
+            value: inline_expr(value, inline_map),
+            line,
+        },
+        ASTNode::Append { name, value, line } => ASTNode::Append {
+            name,
+            value: inline_expr(value, inline_map),
+            line,
+        },
+        ASTNode::ForEach { item, array, body } => ASTNode::ForEach {
+            item,
+            array,
+            body: body.into_iter().map(|n| inline_node(n, inline_map)).collect(),
+        },
+        ASTNode::RecordDeclaration(record) => ASTNode::RecordDeclaration(record),
     }
 }
 
@@ -120,6 +162,56 @@ fn inline_expr(expr: Expression, inline_map: &InlineMap) -> Expression {
                 right: Box::new(inline_expr(*right, inline_map)),
             }
         }
+        Expression::Not(operand) => Expression::Not(Box::new(inline_expr(*operand, inline_map))),
+        Expression::List(items) => {
+            Expression::List(items.into_iter().map(|item| inline_expr(item, inline_map)).collect())
+        }
+        Expression::Index { name, index } => Expression::Index {
+            name,
+            index: Box::new(inline_expr(*index, inline_map)),
+        },
+        Expression::Len(name) => Expression::Len(name),
+        Expression::In { key, name } => Expression::In {
+            key: Box::new(inline_expr(*key, inline_map)),
+            name,
+        },
+        Expression::Field { name, field } => Expression::Field { name, field },
+        // Inlined, a `str` variable holding "42" would read as the number 42
+        Expression::Convert { to, value } if matches!(*value, Expression::Identifier(_)) => {
+            Expression::Convert { to, value }
+        }
+        Expression::Convert { to, value } => Expression::Convert {
+            to,
+            value: Box::new(inline_expr(*value, inline_map)),
+        },
+        // The same goes for the values of string operations
+        Expression::Text { op, args } => Expression::Text {
+            op,
+            args: args
+                .into_iter()
+                .map(|arg| match arg {
+                    Expression::Identifier(_) => arg,
+                    arg => inline_expr(arg, inline_map),
+                })
+                .collect(),
+        },
+        // And for math functions, where an `f` holding 2 would read as an integer
+        Expression::Math { op, args } => Expression::Math {
+            op,
+            args: args
+                .into_iter()
+                .map(|arg| match arg {
+                    Expression::Identifier(_) => arg,
+                    arg => inline_expr(arg, inline_map),
+                })
+                .collect(),
+        },
+        Expression::Record(values) => Expression::Record(
+            values
+                .into_iter()
+                .map(|(field, value)| (field, inline_expr(value, inline_map)))
+                .collect(),
+        ),
     }
 }
 
@@ -165,14 +257,12 @@ unsafe fn replace_placeholders(literal: &str, inline_map: &InlineMap) -> String
                     break;
                 }
             } else {
-                result.push(unsafe { *ptr.add(i) } as char);
-                i += 1;
+                // Copy the run up to the next '{' whole, so multi-byte characters stay intact
+                let end = memchr::memchr(b'{', &bytes[i..]).map_or(len, |rel| i + rel);
+                result.push_str(unsafe { std::str::from_utf8_unchecked(&bytes[i..end]) });
+                i = end;
             }
         }
-        // Remove exactly one leading space if it exists.
-        if result.as_bytes().first() == Some(&b' ') {
-            result = unsafe { result.get_unchecked(1..).to_string() };
-        }
         result
     }
 }
@@ -6,15 +6,102 @@ use std::collections::HashMap;
 
 /// ------------------ AST Definitions ------------------
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum VarType {
-    Int,
+    Int(IntType),
     String,
     Char { size: usize },
     Float,
+    Bool,
+    /// `T[]`: a growable array of `T` values.
+    Array(Box<VarType>),
+    /// `map[K]V`: `V` values looked up by `K` keys, kept in insertion order.
+    Map(Box<VarType>, Box<VarType>),
+    /// A record type declared with `# Name { field type, ... }`.
+    Record(RecordType),
 }
 
-#[derive(Debug, Clone)]
+/// A record type: named fields, each holding an integer, `f`, `str` or `b`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordType {
+    pub name: String,
+    /// In declaration order.
+    pub fields: Vec<(String, VarType)>,
+}
+
+impl RecordType {
+    pub fn field(&self, field: &str) -> Option<&VarType> {
+        self.fields.iter().find(|(name, _)| name == field).map(|(_, var_type)| var_type)
+    }
+}
+
+/// Writes the type as it is spelled in Ven source (`i64`, `u8`, `str`, `f`, `c[8]`, `b`, `i64[]`, `map[str]i64`, `Person`).
+impl std::fmt::Display for VarType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VarType::Int(int_type) => write!(f, "{}", int_type),
+            VarType::String => write!(f, "str"),
+            VarType::Char { size } => write!(f, "c[{}]", size),
+            VarType::Float => write!(f, "f"),
+            VarType::Bool => write!(f, "b"),
+            VarType::Array(element) => write!(f, "{}[]", element),
+            VarType::Map(key, value) => write!(f, "map[{}]{}", key, value),
+            VarType::Record(record) => write!(f, "{}", record.name),
+        }
+    }
+}
+
+/// Width and signedness of an integer type. `i` is another name for `i64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntType {
+    pub signed: bool,
+    /// 8, 16, 32 or 64.
+    pub bits: u32,
+}
+
+impl IntType {
+    pub const I64: IntType = IntType { signed: true, bits: 64 };
+    pub const U64: IntType = IntType { signed: false, bits: 64 };
+
+    /// Parses `i`, `i8`..`i64` and `u8`..`u64`.
+    pub fn from_name(name: &str) -> Option<IntType> {
+        let signed = match name.as_bytes().first() {
+            Some(b'i') => true,
+            Some(b'u') => false,
+            _ => return None,
+        };
+        let bits = match &name[1..] {
+            "" if signed => 64,
+            "8" => 8,
+            "16" => 16,
+            "32" => 32,
+            "64" => 64,
+            _ => return None,
+        };
+        Some(IntType { signed, bits })
+    }
+
+    pub fn min(self) -> i128 {
+        if self.signed { -(1i128 << (self.bits - 1)) } else { 0 }
+    }
+
+    pub fn max(self) -> i128 {
+        if self.signed { (1i128 << (self.bits - 1)) - 1 } else { (1i128 << self.bits) - 1 }
+    }
+
+    /// Whether every value of `other` is also a value of this type.
+    pub fn holds(self, other: IntType) -> bool {
+        self.min() <= other.min() && other.max() <= self.max()
+    }
+}
+
+impl std::fmt::Display for IntType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", if self.signed { "i" } else { "u" }, self.bits)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Expression {
     Literal(String),
     Identifier(String),
@@ -28,6 +115,159 @@ pub enum Expression {
         operator: LogicalOperator,
         right: Box<Expression>,
     },
+    /// `!operand`
+    Not(Box<Expression>),
+    /// `[a, b, c]`, the initializer of an array, or the `{}` a map starts as.
+    List(Vec<Expression>),
+    /// `name[index]`: an element of the array `name`, or the value of the key
+    /// `index` in the map `name`.
+    Index { name: String, index: Box<Expression> },
+    /// `len(name)`: the number of elements in the array or entries in the map `name`.
+    Len(String),
+    /// `key in name`: whether the map `name` has the key.
+    In { key: Box<Expression>, name: String },
+    /// `name.field`: a field of the record `name`.
+    Field { name: String, field: String },
+    /// `{ name "Ann", age 30 }`, the initializer of a record. The parser fills
+    /// in the fields left out, so every field appears, in declaration order.
+    Record(Vec<(String, Expression)>),
+    /// `i(value)`, `f(value)` or `str(value)`: `value` converted to `to`, which
+    /// is `i64`, `f` or `str`.
+    Convert { to: VarType, value: Box<Expression> },
+    /// `upper(text)`, `slice(text, 0, 3)` and the other built-in string
+    /// operations, with their arguments as written.
+    Text { op: TextOp, args: Vec<Expression> },
+    /// `abs(x)`, `min(a, b)` and the other built-in math functions, or `-x`,
+    /// with their arguments as written.
+    Math { op: MathFn, args: Vec<Expression> },
+}
+
+/// The built-in string operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextOp {
+    /// `concat(a, b, ...)`: the texts one after another.
+    Concat,
+    /// `len(text)`: the number of characters.
+    Len,
+    /// `slice(text, start, end)`: the characters from `start` up to `end`.
+    Slice,
+    /// `upper(text)`: ASCII letters in upper case.
+    Upper,
+    /// `lower(text)`: ASCII letters in lower case.
+    Lower,
+    /// `trim(text)`: without spaces, tabs and line breaks at either end.
+    Trim,
+    /// `contains(text, part)`: whether `part` appears in the text.
+    Contains,
+    /// `starts_with(text, prefix)`: whether the text begins with `prefix`.
+    StartsWith,
+    /// `replace(text, from, to)`: every `from` replaced with `to`.
+    Replace,
+}
+
+impl TextOp {
+    pub fn from_name(name: &str) -> Option<TextOp> {
+        Some(match name {
+            "concat" => TextOp::Concat,
+            "len" => TextOp::Len,
+            "slice" => TextOp::Slice,
+            "upper" => TextOp::Upper,
+            "lower" => TextOp::Lower,
+            "trim" => TextOp::Trim,
+            "contains" => TextOp::Contains,
+            "starts_with" => TextOp::StartsWith,
+            "replace" => TextOp::Replace,
+            _ => return None,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextOp::Concat => "concat",
+            TextOp::Len => "len",
+            TextOp::Slice => "slice",
+            TextOp::Upper => "upper",
+            TextOp::Lower => "lower",
+            TextOp::Trim => "trim",
+            TextOp::Contains => "contains",
+            TextOp::StartsWith => "starts_with",
+            TextOp::Replace => "replace",
+        }
+    }
+
+    /// The type of the result: `i64` for `len()`, `b` for the tests and `str`
+    /// for the rest.
+    pub fn result(&self) -> VarType {
+        match self {
+            TextOp::Len => VarType::Int(IntType::I64),
+            TextOp::Contains | TextOp::StartsWith => VarType::Bool,
+            _ => VarType::String,
+        }
+    }
+}
+
+/// The built-in math functions, and the `-` that negates a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathFn {
+    /// `-x`: the value with its sign flipped.
+    Negate,
+    /// `abs(x)`: the value without its sign.
+    Abs,
+    /// `min(a, b, ...)`: the smallest value.
+    Min,
+    /// `max(a, b, ...)`: the largest value.
+    Max,
+    /// `sqrt(x)`: the square root, always an `f`.
+    Sqrt,
+    /// `floor(x)`: the nearest whole number below.
+    Floor,
+    /// `ceil(x)`: the nearest whole number above.
+    Ceil,
+    /// `round(x)`: the nearest whole number, with halves away from zero.
+    Round,
+}
+
+impl MathFn {
+    /// The function called `name`; `-` is not called by name.
+    pub fn from_name(name: &str) -> Option<MathFn> {
+        Some(match name {
+            "abs" => MathFn::Abs,
+            "min" => MathFn::Min,
+            "max" => MathFn::Max,
+            "sqrt" => MathFn::Sqrt,
+            "floor" => MathFn::Floor,
+            "ceil" => MathFn::Ceil,
+            "round" => MathFn::Round,
+            _ => return None,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MathFn::Negate => "-",
+            MathFn::Abs => "abs",
+            MathFn::Min => "min",
+            MathFn::Max => "max",
+            MathFn::Sqrt => "sqrt",
+            MathFn::Floor => "floor",
+            MathFn::Ceil => "ceil",
+            MathFn::Round => "round",
+        }
+    }
+
+    /// How messages refer to the function: `abs()`, or `-` for negation.
+    pub fn called(&self) -> &'static str {
+        match self {
+            MathFn::Negate => "-",
+            MathFn::Abs => "abs()",
+            MathFn::Min => "min()",
+            MathFn::Max => "max()",
+            MathFn::Sqrt => "sqrt()",
+            MathFn::Floor => "floor()",
+            MathFn::Ceil => "ceil()",
+            MathFn::Round => "round()",
+        }
+    }
 }
 
 /// Represents comparison operators for conditionals.
@@ -55,52 +295,151 @@ pub enum MathOperator {
     Subtract,
     Multiply,
     Divide,
+    /// `%`: the remainder of a division that truncates toward zero.
+    Modulo,
+    /// `**`: raised to the power of the operand.
+    Power,
 }
 
 /// Extended AST node variants.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ASTNode {
     /// Variable declaration.
     /// E.g.: "@ myVar i 42" (static) or "@@ myMutVar str hello" (mutable)
+    /// A `b` variable is initialized with a condition: "@ adult b age >= 18"
+    /// line: 1-based source line, named by runtime errors in the initializer.
     VarDeclaration {
         mutable: bool,
         name: String,
         var_type: VarType,
         value: Option<Expression>,
+        line: usize,
     },
     /// Input statement.
     /// E.g.: "> myVar"
-    Input { name: String },
+    Input { name: String, line: usize },
     /// Print statement.
     /// E.g.: ">> Hello {name}!"
     Print {
         to_stderr: bool,
         expr: Option<Expression>,
+        line: usize,
     },
-    /// Math operation on a mutable variable.
-    /// E.g.: "* age + 2" or "* age / 3"
+    /// Math operation on a mutable variable, or on a field of a mutable record.
+    /// E.g.: "* age + 2", "* age / 3" or "* p.age + 1"
+    /// line: 1-based source line, named by runtime errors such as a division by zero.
     MathOp {
         name: String,
+        field: Option<String>,
         operator: MathOperator,
         operand: Expression,
+        line: usize,
     },
     /// Represents a conditional statement.
     /// E.g.: "?(age > 18) { >> Adult }"
     /// condition: The condition to evaluate.
     /// body: The statements to execute if the condition is true.
+    /// line: The line of the `?`, named by runtime errors in the condition.
     If {
         condition: Expression,
         body: Vec<ASTNode>,
+        line: usize,
+    },
+    /// Call to a native function registered by the embedding host.
+    /// E.g.: "log(name, 3)" or "now() -> stamp"
+    /// into: Mutable variable that receives the result, if any.
+    Call {
+        name: String,
+        args: Vec<Expression>,
+        into: Option<String>,
+        line: usize,
+    },
+    /// Assignment to an element of a mutable array, or to a key of a mutable
+    /// map, which adds the key when it is new.
+    /// E.g.: "* scores[1] = 10" or "* ages[\"bob\"] = 30"
+    SetIndex {
+        name: String,
+        index: Expression,
+        value: Expression,
+        line: usize,
+    },
+    /// Appends to a mutable array.
+    /// E.g.: "* scores[] = 4"
+    Append {
+        name: String,
+        value: Expression,
+        line: usize,
+    },
+    /// Runs the body once for each element of an array, in order. `item` is an
+    /// immutable variable holding the element. Elements appended in the body
+    /// are visited too.
+    /// E.g.: "~(score in scores) { >> \"{score}\" }"
+    ForEach {
+        item: String,
+        array: String,
+        body: Vec<ASTNode>,
     },
+    /// Declares a record type.
+    /// E.g.: "# Person { name str, age i }"
+    RecordDeclaration(RecordType),
+}
+
+impl ASTNode {
+    /// The 1-based source line of a statement that can stop the program with a
+    /// runtime error. `None` for loops and record declarations.
+    pub fn line(&self) -> Option<usize> {
+        match *self {
+            ASTNode::VarDeclaration { line, .. }
+            | ASTNode::Input { line, .. }
+            | ASTNode::Print { line, .. }
+            | ASTNode::MathOp { line, .. }
+            | ASTNode::If { line, .. }
+            | ASTNode::Call { line, .. }
+            | ASTNode::SetIndex { line, .. }
+            | ASTNode::Append { line, .. } => Some(line),
+            ASTNode::ForEach { .. } | ASTNode::RecordDeclaration(_) => None,
+        }
+    }
+
+    /// Sets the source lines of the statement and of its body to 0.
+    fn clear_lines(&mut self) {
+        match self {
+            ASTNode::VarDeclaration { line, .. }
+            | ASTNode::Input { line, .. }
+            | ASTNode::Print { line, .. }
+            | ASTNode::MathOp { line, .. }
+            | ASTNode::Call { line, .. }
+            | ASTNode::SetIndex { line, .. }
+            | ASTNode::Append { line, .. } => *line = 0,
+            ASTNode::If { line, body, .. } => {
+                *line = 0;
+                body.iter_mut().for_each(ASTNode::clear_lines);
+            }
+            ASTNode::ForEach { body, .. } => body.iter_mut().for_each(ASTNode::clear_lines),
+            ASTNode::RecordDeclaration(_) => {}
+        }
+    }
 }
 
 /// The top-level AST wrapping a program.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum AST {
     Program(Vec<ASTNode>),
 }
 
 impl AST {
+    /// Whether the two programs are the same apart from where their statements
+    /// sit in the source.
+    pub(crate) fn same_meaning(&self, other: &AST) -> bool {
+        let without_lines = |ast: &AST| {
+            let AST::Program(nodes) = ast;
+            let mut nodes = nodes.clone();
+            nodes.iter_mut().for_each(ASTNode::clear_lines);
+            nodes
+        };
+        without_lines(self) == without_lines(other)
+    }
+
     #[inline(always)]
     pub fn parse(
         tokens: &[Token],
@@ -108,6 +447,7 @@ impl AST {
     ) -> (Self, HashMap<String, (VarType, usize)>, Vec<VarError>) {
         let mut nodes = Vec::with_capacity(tokens.len() / 4);
         let mut global_var_map = HashMap::with_capacity(16);
+        let mut types = HashMap::new();
         let mut global_errors = Vec::with_capacity(8);
 
         // Use a single parser state to iterate over all tokens
@@ -116,31 +456,47 @@ impl AST {
             input,
             pos: 0,
             global_var_map: &mut global_var_map,
+            types: &mut types,
             errors: Vec::with_capacity(4),
             global_pos: 0,
         };
 
         while state.pos < tokens.len() {
             let token_kind = state.tokens.get(state.pos).map(|t| t.kind);
-            println!("DEBUG PARSE: Trying to parse token at position {} of type {:?}", 
+            trace!("DEBUG PARSE: Trying to parse token at position {} of type {:?}", 
                      state.pos, token_kind);
             
             // Skip newline tokens
             if let Some(TokenKind::Newline) = token_kind {
-                println!("DEBUG PARSE: Skipping Newline at position {}", state.pos);
+                trace!("DEBUG PARSE: Skipping Newline at position {}", state.pos);
                 state.pos += 1;
                 continue;
             }
             
             if let Some(stmt) = state.parse_statement(tokens) {
-                println!("DEBUG PARSE: Successfully parsed {:?}", stmt);
+                trace!("DEBUG PARSE: Successfully parsed {:?}", stmt);
                 nodes.push(stmt);
             } else {
-                println!("DEBUG PARSE: Failed to parse token at position {}, skipping", state.pos);
+                trace!("DEBUG PARSE: Failed to parse token at position {}, skipping", state.pos);
                 state.pos += 1;
             }
         }
         
+        // Blocks take their body up to the matching '}', so a '}' left over at
+        // the top level closes nothing
+        let mut depth = 0usize;
+        for (i, token) in tokens.iter().enumerate() {
+            match token.kind {
+                TokenKind::LCurlyB => depth += 1,
+                TokenKind::RCurlyB if depth == 0 => {
+                    let line = state.get_line_number(i);
+                    state.errors.push(VarError::UnmatchedClosingBrace { line });
+                }
+                TokenKind::RCurlyB => depth -= 1,
+                _ => {}
+            }
+        }
+
         global_errors.extend(state.errors);
         (AST::Program(nodes), global_var_map, global_errors)
     }
@@ -164,7 +520,7 @@ impl AST {
     fn print_node(node: &ASTNode, indent: &str, is_last: bool) {
         let branch = if is_last { "└── " } else { "├── " };
         match node {
-            ASTNode::If { condition, body } => {
+            ASTNode::If { condition, body, .. } => {
                 println!("{}{}Condition", indent, branch);
                 let child_indent = if is_last {
                     format!("{}    ", indent)
@@ -186,6 +542,7 @@ impl AST {
                 name,
                 var_type,
                 value,
+                ..
             } => {
                 println!("{}{}VarDeclaration", indent, branch);
                 let child_indent = if is_last {
@@ -202,7 +559,7 @@ impl AST {
                     Self::print_expression(expr, &expr_indent, true);
                 }
             }
-            ASTNode::Input { name } => {
+            ASTNode::Input { name, .. } => {
                 println!("{}{}Input", indent, branch);
                 let child_indent = if is_last {
                     format!("{}    ", indent)
@@ -211,7 +568,7 @@ impl AST {
                 };
                 println!("{}    └── Name: {}", child_indent, name);
             }
-            ASTNode::Print { to_stderr, expr } => {
+            ASTNode::Print { to_stderr, expr, .. } => {
                 println!("{}{}Print", indent, branch);
                 let child_indent = if is_last {
                     format!("{}    ", indent)
@@ -227,8 +584,10 @@ impl AST {
             }
             ASTNode::MathOp {
                 name,
+                field,
                 operator,
                 operand,
+                line,
             } => {
                 println!("{}{}MathOp", indent, branch);
                 let child_indent = if is_last {
@@ -237,11 +596,84 @@ impl AST {
                     format!("{}│   ", indent)
                 };
                 println!("{}├── Name: {}", child_indent, name);
+                if let Some(field) = field {
+                    println!("{}├── Field: {}", child_indent, field);
+                }
                 println!("{}├── Operator: {:?}", child_indent, operator);
+                println!("{}├── Line: {}", child_indent, line);
                 println!("{}└── Operand:", child_indent);
                 let expr_indent = format!("{}    ", child_indent);
                 Self::print_expression(operand, &expr_indent, true);
             }
+            ASTNode::Call { name, args, into, .. } => {
+                println!("{}{}Call", indent, branch);
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                println!("{}├── Name: {}", child_indent, name);
+                println!("{}├── Into: {}", child_indent, into.as_deref().unwrap_or("-"));
+                println!("{}└── Args:", child_indent);
+                let expr_indent = format!("{}    ", child_indent);
+                for (i, arg) in args.iter().enumerate() {
+                    Self::print_expression(arg, &expr_indent, i == args.len() - 1);
+                }
+            }
+            ASTNode::SetIndex { name, index, value, .. } => {
+                println!("{}{}SetIndex", indent, branch);
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                println!("{}├── Name: {}", child_indent, name);
+                println!("{}├── Index:", child_indent);
+                let expr_indent = format!("{}│   ", child_indent);
+                Self::print_expression(index, &expr_indent, true);
+                println!("{}└── Value:", child_indent);
+                let expr_indent = format!("{}    ", child_indent);
+                Self::print_expression(value, &expr_indent, true);
+            }
+            ASTNode::Append { name, value, .. } => {
+                println!("{}{}Append", indent, branch);
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                println!("{}├── Name: {}", child_indent, name);
+                println!("{}└── Value:", child_indent);
+                let expr_indent = format!("{}    ", child_indent);
+                Self::print_expression(value, &expr_indent, true);
+            }
+            ASTNode::ForEach { item, array, body } => {
+                println!("{}{}ForEach", indent, branch);
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                println!("{}├── Item: {}", child_indent, item);
+                println!("{}├── Array: {}", child_indent, array);
+                println!("{}└── Body:", child_indent);
+                let body_indent = format!("{}    ", child_indent);
+                for (i, node) in body.iter().enumerate() {
+                    Self::print_node(node, &body_indent, i == body.len() - 1);
+                }
+            }
+            ASTNode::RecordDeclaration(record) => {
+                println!("{}{}RecordDeclaration: {}", indent, branch, record.name);
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                for (i, (field, var_type)) in record.fields.iter().enumerate() {
+                    let field_branch = if i == record.fields.len() - 1 { "└── " } else { "├── " };
+                    println!("{}{}{}: {}", child_indent, field_branch, field, var_type);
+                }
+            }
         }
     }
 
@@ -277,6 +709,92 @@ impl AST {
                 println!("{}└── Right:", child_indent);
                 Self::print_expression(right, &child_indent, true);
             }
+            Expression::Not(operand) => {
+                println!("{}{}Not", indent, branch);
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                Self::print_expression(operand, &child_indent, true);
+            }
+            Expression::List(items) => {
+                println!("{}{}List", indent, branch);
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                for (i, item) in items.iter().enumerate() {
+                    Self::print_expression(item, &child_indent, i == items.len() - 1);
+                }
+            }
+            Expression::Index { name, index } => {
+                println!("{}{}Index: {}", indent, branch, name);
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                Self::print_expression(index, &child_indent, true);
+            }
+            Expression::Len(name) => println!("{}{}Len: {}", indent, branch, name),
+            Expression::In { key, name } => {
+                println!("{}{}In: {}", indent, branch, name);
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                Self::print_expression(key, &child_indent, true);
+            }
+            Expression::Field { name, field } => {
+                println!("{}{}Field: {}.{}", indent, branch, name, field)
+            }
+            Expression::Record(fields) => {
+                println!("{}{}Record", indent, branch);
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                for (i, (field, value)) in fields.iter().enumerate() {
+                    println!("{}{}{}:", child_indent, if i == fields.len() - 1 { "└── " } else { "├── " }, field);
+                    let value_indent = format!("{}    ", child_indent);
+                    Self::print_expression(value, &value_indent, true);
+                }
+            }
+            Expression::Convert { to, value } => {
+                println!("{}{}Convert: {}", indent, branch, crate::transpilers::conversion_name(to));
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                Self::print_expression(value, &child_indent, true);
+            }
+            Expression::Text { op, args } => {
+                println!("{}{}Text: {}", indent, branch, op.name());
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                for (i, arg) in args.iter().enumerate() {
+                    Self::print_expression(arg, &child_indent, i == args.len() - 1);
+                }
+            }
+            Expression::Math { op, args } => {
+                println!("{}{}Math: {}", indent, branch, op.name());
+                let child_indent = if is_last {
+                    format!("{}    ", indent)
+                } else {
+                    format!("{}│   ", indent)
+                };
+                for (i, arg) in args.iter().enumerate() {
+                    Self::print_expression(arg, &child_indent, i == args.len() - 1);
+                }
+            }
         }
     }
 }
@@ -1,16 +1,18 @@
 use crate::parse::{ASTNode, Expression, MathOperator};
+use crate::parse2::parse_operand;
 use crate::token::{Token, TokenKind};
 use std::str;
 
-/// Convert token to string slice efficiently (unsafe but fast)
+/// Convert token to string slice. Non-ASCII characters are tokenized byte by byte
+/// and have no text of their own.
 #[inline(always)]
 fn get_lexeme<'a>(token: &'a Token, input: &'a str) -> &'a str {
-    unsafe { str::from_utf8_unchecked(&input.as_bytes()[token.start..token.end]) }
+    input.get(token.start..token.end).unwrap_or("")
 }
 
-/// High-performance math expression parser
+/// High-performance math expression parser. `line` is where the statement starts.
 #[inline(always)]
-pub fn parse1(tokens: &[Token], input: &str, _current_token: &Token) -> Option<ASTNode> {
+pub fn parse1(tokens: &[Token], input: &str, line: usize) -> Option<ASTNode> {
     let mut pos = 0;
     let len = tokens.len();
 
@@ -26,6 +28,16 @@ pub fn parse1(tokens: &[Token], input: &str, _current_token: &Token) -> Option<A
     let var_name = get_lexeme(&tokens[pos], input).to_owned();
     pos += 1;
 
+    // `name.field` changes a field of a record
+    let mut field = None;
+    if pos + 1 < len
+        && get_lexeme(&tokens[pos], input) == "."
+        && tokens[pos + 1].kind == TokenKind::Identifier
+    {
+        field = Some(get_lexeme(&tokens[pos + 1], input).to_owned());
+        pos += 2;
+    }
+
     // Skip unknown tokens
     while pos < len && tokens[pos].kind == TokenKind::Unknown {
         pos += 1;
@@ -40,6 +52,8 @@ pub fn parse1(tokens: &[Token], input: &str, _current_token: &Token) -> Option<A
         TokenKind::Minus => MathOperator::Subtract,
         TokenKind::Star => MathOperator::Multiply,
         TokenKind::Slash => MathOperator::Divide,
+        TokenKind::Percent => MathOperator::Modulo,
+        TokenKind::Power => MathOperator::Power,
         _ => return None,
     };
     pos += 1;
@@ -49,19 +63,18 @@ pub fn parse1(tokens: &[Token], input: &str, _current_token: &Token) -> Option<A
         pos += 1;
     }
 
-    // Extract operand efficiently
-    let mut operand_str = String::with_capacity(32);
-    while pos < len && tokens[pos].kind != TokenKind::Newline {
-        if !operand_str.is_empty() {
-            operand_str.push(' '); // Insert space only if needed
-        }
-        operand_str.push_str(get_lexeme(&tokens[pos], input));
-        pos += 1;
-    }
+    // The operand runs up to a trailing comment
+    let operand_end = tokens[pos..]
+        .iter()
+        .position(|t| matches!(t.kind, TokenKind::Newline | TokenKind::Comment))
+        .map_or(len, |i| pos + i);
+    let operand = parse_operand(tokens, pos, operand_end, input).unwrap_or(Expression::Literal(String::new()));
 
     Some(ASTNode::MathOp {
         name: var_name,
+        field,
         operator: math_operator,
-        operand: Expression::Literal(operand_str),
+        operand,
+        line,
     })
 }
@@ -1,32 +1,36 @@
 use crate::{
     errs::VarError,
     impl_parserstate::ParserState,
-    parse::{ASTNode, Expression, ComparisonOperator, LogicalOperator},
+    parse::{ASTNode, Expression, ComparisonOperator, IntType, LogicalOperator, MathFn, TextOp, VarType},
     token::{Token, TokenKind}
 };
 use std::str;
 
 #[inline(always)]
 fn get_lexeme<'a>(token: &'a Token, input: &'a str) -> &'a str {
-    // SAFETY: indices are assumed to be valid.
-    unsafe { str::from_utf8_unchecked(&input.as_bytes()[token.start..token.end]) }
+    // Non-ASCII characters are tokenized byte by byte and have no text of their own
+    input.get(token.start..token.end).unwrap_or("")
 }
 
 /// Parse a condition (logical expression)
 #[inline(always)]
-pub fn parse_condition(tokens: &[Token], start: usize, end: usize, input: &str, line: usize) -> Result<Expression, String> {
+pub fn parse_condition(tokens: &[Token], start: usize, end: usize, input: &str, _line: usize) -> Result<Expression, String> {
     if start >= end {
         return Err("Empty condition".to_string());
     }
 
-    // First check for logical operators (higher precedence)
+    // First check for logical operators (higher precedence), outside parentheses
+    let mut depth = 0usize;
     for i in start..end {
         let token = &tokens[i];
         match token.kind {
+            TokenKind::LSmallB => depth += 1,
+            TokenKind::RSmallB => depth = depth.saturating_sub(1),
+            _ if depth > 0 => {}
             TokenKind::And => {
-                let left = parse_condition(tokens, start, i, input, line)
+                let left = parse_condition(tokens, start, i, input, _line)
                     .map_err(|e| format!("Invalid left side of AND: {}", e))?;
-                let right = parse_condition(tokens, i + 1, end, input, line)
+                let right = parse_condition(tokens, i + 1, end, input, _line)
                     .map_err(|e| format!("Invalid right side of AND: {}", e))?;
                 return Ok(Expression::LogicalOp {
                     left: Box::new(left),
@@ -35,9 +39,9 @@ pub fn parse_condition(tokens: &[Token], start: usize, end: usize, input: &str,
                 });
             }
             TokenKind::Or => {
-                let left = parse_condition(tokens, start, i, input, line)
+                let left = parse_condition(tokens, start, i, input, _line)
                     .map_err(|e| format!("Invalid left side of OR: {}", e))?;
-                let right = parse_condition(tokens, i + 1, end, input, line)
+                let right = parse_condition(tokens, i + 1, end, input, _line)
                     .map_err(|e| format!("Invalid right side of OR: {}", e))?;
                 return Ok(Expression::LogicalOp {
                     left: Box::new(left),
@@ -50,10 +54,16 @@ pub fn parse_condition(tokens: &[Token], start: usize, end: usize, input: &str,
     }
 
     // Check for comparison operators
+    let mut depth = 0usize;
     for i in start..end {
         let token = &tokens[i];
+        match token.kind {
+            TokenKind::LSmallB => depth += 1,
+            TokenKind::RSmallB => depth = depth.saturating_sub(1),
+            _ => {}
+        }
         // Handle all comparison operators, including Greater which is also used for print/input
-        if matches!(token.kind, 
+        if depth == 0 && matches!(token.kind, 
             TokenKind::Equal | 
             TokenKind::NotEqual | 
             TokenKind::LessThan | 
@@ -63,9 +73,9 @@ pub fn parse_condition(tokens: &[Token], start: usize, end: usize, input: &str,
         {
             // Make sure to not go out of bounds
             if i > start && i < end - 1 {
-                let left = parse_operand(tokens, start, i, input)
+                let left = parse_unary(tokens, start, i, input)
                     .ok_or_else(|| format!("Invalid left operand at position {}", i))?;
-                let right = parse_operand(tokens, i + 1, end, input)
+                let right = parse_unary(tokens, i + 1, end, input)
                     .ok_or_else(|| format!("Invalid right operand at position {}", i))?;
                 
                 let operator = match token.kind {
@@ -90,13 +100,83 @@ pub fn parse_condition(tokens: &[Token], start: usize, end: usize, input: &str,
     }
 
     // If no operators found, it's a simple expression
-    parse_operand(tokens, start, end, input)
+    parse_unary(tokens, start, end, input)
         .ok_or_else(|| "Invalid condition expression".to_string())
 }
 
+/// Parse a negation (`!operand`), a parenthesized condition or an operand
+#[inline(always)]
+fn parse_unary(tokens: &[Token], start: usize, end: usize, input: &str) -> Option<Expression> {
+    let mut start = start;
+    let mut end = end;
+    let is_space = |token: &Token| token.kind == TokenKind::Unknown && get_lexeme(token, input).trim().is_empty();
+    while start < end && is_space(&tokens[start]) {
+        start += 1;
+    }
+    while end > start && is_space(&tokens[end - 1]) {
+        end -= 1;
+    }
+    if start >= end {
+        return None;
+    }
+
+    match tokens[start].kind {
+        TokenKind::Not => parse_unary(tokens, start + 1, end, input).map(|operand| Expression::Not(Box::new(operand))),
+        TokenKind::LSmallB if tokens[end - 1].kind == TokenKind::RSmallB && closes_at(tokens, start, end - 1) => {
+            parse_condition(tokens, start + 1, end - 1, input, 0).ok()
+        }
+        _ => parse_membership(tokens, start, end, input).or_else(|| parse_operand(tokens, start, end, input)),
+    }
+}
+
+/// Parse `key in map`, where the map is a single name
+#[inline(always)]
+fn parse_membership(tokens: &[Token], start: usize, end: usize, input: &str) -> Option<Expression> {
+    let map = tokens[end - 1];
+    if map.kind != TokenKind::Identifier || is_literal_word(get_lexeme(&map, input)) {
+        return None;
+    }
+    let keyword = (start + 1..end - 1)
+        .rev()
+        .find(|&i| !(tokens[i].kind == TokenKind::Unknown && get_lexeme(&tokens[i], input).trim().is_empty()))?;
+    if tokens[keyword].kind != TokenKind::Identifier || get_lexeme(&tokens[keyword], input) != "in" {
+        return None;
+    }
+    let key = parse_operand(tokens, start, keyword, input)?;
+    Some(Expression::In {
+        key: Box::new(key),
+        name: get_lexeme(&map, input).to_string(),
+    })
+}
+
+/// Whether the `(` at `open` is closed by the `)` at `close`
+#[inline(always)]
+fn closes_at(tokens: &[Token], open: usize, close: usize) -> bool {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().take(close + 1).skip(open) {
+        match token.kind {
+            TokenKind::LSmallB => depth += 1,
+            TokenKind::RSmallB => {
+                depth -= 1;
+                if depth == 0 {
+                    return i == close;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Number literals and `true`/`false` are literals; any other word names a variable
+#[inline(always)]
+fn is_literal_word(lexeme: &str) -> bool {
+    lexeme.chars().all(|c| c.is_ascii_digit() || c == '.') || lexeme == "true" || lexeme == "false"
+}
+
 /// Parse an operand (identifier or literal)
 #[inline(always)]
-fn parse_operand(tokens: &[Token], start: usize, end: usize, input: &str) -> Option<Expression> {
+pub fn parse_operand(tokens: &[Token], start: usize, end: usize, input: &str) -> Option<Expression> {
     if start >= end {
         return None;
     }
@@ -108,8 +188,8 @@ fn parse_operand(tokens: &[Token], start: usize, end: usize, input: &str) -> Opt
         
         match token.kind {
             TokenKind::Identifier => {
-                // Check if it's a number literal or an identifier
-                if lexeme.chars().all(|c| c.is_digit(10) || c == '.') {
+                // Check if it's a literal or an identifier
+                if is_literal_word(lexeme) {
                     Some(Expression::Literal(lexeme.to_string()))
                 } else {
                     Some(Expression::Identifier(lexeme.to_string()))
@@ -152,6 +232,20 @@ fn parse_operand(tokens: &[Token], start: usize, end: usize, input: &str) -> Opt
         if real_start >= real_end {
             return None;
         }
+
+        // `-` negates what follows; a number takes it as its sign
+        if tokens[real_start].kind == TokenKind::Minus {
+            return Some(match parse_operand(tokens, real_start + 1, real_end, input)? {
+                Expression::Literal(lit) if !lit.is_empty() && lit.bytes().all(|b| b.is_ascii_digit() || b == b'.') => {
+                    Expression::Literal(format!("-{}", lit))
+                }
+                operand => Expression::Math { op: MathFn::Negate, args: vec![operand] },
+            });
+        }
+
+        if let Some(access) = parse_access(tokens, real_start, real_end, input) {
+            return Some(access);
+        }
         
         // If after trimming whitespace we have just one token, process it
         if real_end - real_start == 1 {
@@ -160,8 +254,8 @@ fn parse_operand(tokens: &[Token], start: usize, end: usize, input: &str) -> Opt
             
             match token.kind {
                 TokenKind::Identifier => {
-                    // Check if it's a number literal or an identifier
-                    if lexeme.chars().all(|c| c.is_digit(10) || c == '.') {
+                    // Check if it's a literal or an identifier
+                    if is_literal_word(lexeme) {
                         Some(Expression::Literal(lexeme.to_string()))
                     } else {
                         Some(Expression::Identifier(lexeme.to_string()))
@@ -175,22 +269,102 @@ fn parse_operand(tokens: &[Token], start: usize, end: usize, input: &str) -> Opt
             }
         } else {
             // Check if we have a string literal among the tokens
-            for i in real_start..real_end {
-                if tokens[i].kind == TokenKind::StringLiteral {
-                    return Some(Expression::Literal(get_lexeme(&tokens[i], input).to_string()));
+            for token in &tokens[real_start..real_end] {
+                if token.kind == TokenKind::StringLiteral {
+                    return Some(Expression::Literal(get_lexeme(token, input).to_string()));
                 }
             }
             
             // If we still have multiple tokens, join them as a literal
             let mut value = String::new();
-            for i in real_start..real_end {
-                value.push_str(get_lexeme(&tokens[i], input));
+            for token in &tokens[real_start..real_end] {
+                value.push_str(get_lexeme(token, input));
             }
             Some(Expression::Literal(value))
         }
     }
 }
 
+/// Parse `name[index]`, `len(name)`, `name.field`, a conversion such as
+/// `i(text)`, a string operation such as `upper(text)` or a math function
+/// such as `abs(x)`, spanning exactly `start..end` apart from surrounding
+/// whitespace
+#[inline(always)]
+pub fn parse_access(tokens: &[Token], start: usize, end: usize, input: &str) -> Option<Expression> {
+    let significant: Vec<usize> = (start..end)
+        .filter(|&i| !(tokens[i].kind == TokenKind::Unknown && get_lexeme(&tokens[i], input).trim().is_empty()))
+        .collect();
+    let kinds: Vec<TokenKind> = significant.iter().map(|&i| tokens[i].kind).collect();
+    let name = get_lexeme(&tokens[*significant.first()?], input);
+    if kinds.first() != Some(&TokenKind::Identifier) || is_literal_word(name) {
+        return None;
+    }
+    match kinds[1..] {
+        [TokenKind::LBigB, .., TokenKind::RBigB] if kinds.len() > 3 => {
+            let index = parse_operand(tokens, significant[1] + 1, *significant.last()? , input)?;
+            Some(Expression::Index {
+                name: name.to_string(),
+                index: Box::new(index),
+            })
+        }
+        [TokenKind::LSmallB, TokenKind::Identifier, TokenKind::RSmallB] if name == "len" => {
+            Some(Expression::Len(get_lexeme(&tokens[significant[2]], input).to_string()))
+        }
+        [TokenKind::LSmallB, .., TokenKind::RSmallB]
+            if kinds.len() > 3 && closes_at(tokens, significant[1], *significant.last()?) =>
+        {
+            if let Some(op) = TextOp::from_name(name) {
+                let args = parse_arguments(tokens, significant[1] + 1, *significant.last()?, input)?;
+                return Some(Expression::Text { op, args });
+            }
+            if let Some(op) = MathFn::from_name(name) {
+                let args = parse_arguments(tokens, significant[1] + 1, *significant.last()?, input)?;
+                return Some(Expression::Math { op, args });
+            }
+            let to = match name {
+                "i" => VarType::Int(IntType::I64),
+                "f" => VarType::Float,
+                "str" => VarType::String,
+                _ => return None,
+            };
+            let value = parse_operand(tokens, significant[1] + 1, *significant.last()?, input)?;
+            Some(Expression::Convert { to, value: Box::new(value) })
+        }
+        [TokenKind::Unknown, TokenKind::Identifier]
+            if get_lexeme(&tokens[significant[1]], input) == "."
+                && !is_literal_word(get_lexeme(&tokens[significant[2]], input)) =>
+        {
+            Some(Expression::Field {
+                name: name.to_string(),
+                field: get_lexeme(&tokens[significant[2]], input).to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Parse the comma-separated operands of a string operation or math function;
+/// the commas inside nested parentheses or brackets belong to the operands
+#[inline(always)]
+fn parse_arguments(tokens: &[Token], start: usize, end: usize, input: &str) -> Option<Vec<Expression>> {
+    let mut args = Vec::new();
+    let mut depth = 0usize;
+    let mut arg_start = start;
+    for (i, token) in tokens.iter().enumerate().take(end + 1).skip(start) {
+        if i == end || (depth == 0 && token.kind == TokenKind::Unknown && get_lexeme(token, input) == ",") {
+            args.push(parse_operand(tokens, arg_start, i, input)?);
+            arg_start = i + 1;
+            continue;
+        }
+        match token.kind {
+            TokenKind::LSmallB | TokenKind::LBigB => depth += 1,
+            TokenKind::RSmallB | TokenKind::RBigB => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    Some(args)
+}
+
 #[inline(always)]
 pub fn parse2(
     tokens: &[Token],
@@ -202,31 +376,31 @@ pub fn parse2(
     let len = tokens.len();
     let line = parser_state.get_line_number(pos);
 
-    println!("DEBUG PARSE2: Starting at position {}", pos);
+    trace!("DEBUG PARSE2: Starting at position {}", pos);
 
     if pos >= len {
-        println!("DEBUG PARSE2: Position out of bounds");
+        trace!("DEBUG PARSE2: Position out of bounds");
         return None;
     }
 
     // Check for '?' token - Question token
     if tokens[pos].kind != TokenKind::Question {
-        println!("DEBUG PARSE2: Expected Question token, found {:?}", tokens[pos].kind);
+        trace!("DEBUG PARSE2: Expected Question token, found {:?}", tokens[pos].kind);
         return None;
     }
     pos += 1;
-    println!("DEBUG PARSE2: Found Question token, moving to position {}", pos);
+    trace!("DEBUG PARSE2: Found Question token, moving to position {}", pos);
 
     // Parse opening parenthesis
     if pos >= len || tokens[pos].kind != TokenKind::LSmallB {
-        println!("DEBUG PARSE2: Missing open parenthesis at position {}", pos);
+        trace!("DEBUG PARSE2: Missing open parenthesis at position {}", pos);
         parser_state
             .errors
             .push(VarError::MissingConditionOpenParen { line });
         return None;
     }
     pos += 1;
-    println!("DEBUG PARSE2: Found open parenthesis, moving to position {}", pos);
+    trace!("DEBUG PARSE2: Found open parenthesis, moving to position {}", pos);
 
     // Find the end of the condition (closing parenthesis)
     let condition_start = pos;
@@ -243,7 +417,7 @@ pub fn parse2(
                 }
             }
             TokenKind::Newline => {
-                println!("DEBUG PARSE2: Unexpected newline in condition at position {}", condition_end);
+                trace!("DEBUG PARSE2: Unexpected newline in condition at position {}", condition_end);
                 parser_state
                     .errors
                     .push(VarError::MissingConditionCloseParen { line });
@@ -255,20 +429,20 @@ pub fn parse2(
     }
 
     if paren_depth != 0 || condition_end >= len {
-        println!("DEBUG PARSE2: Missing close parenthesis, paren_depth={}", paren_depth);
+        trace!("DEBUG PARSE2: Missing close parenthesis, paren_depth={}", paren_depth);
         parser_state
             .errors
             .push(VarError::MissingConditionCloseParen { line });
         return None;
     }
     
-    println!("DEBUG PARSE2: Found condition from position {} to {}", condition_start, condition_end);
+    trace!("DEBUG PARSE2: Found condition from position {} to {}", condition_start, condition_end);
 
     // Parse the condition expression
     let condition = match parse_condition(tokens, condition_start, condition_end, input, line) {
         Ok(expr) => expr,
         Err(details) => {
-            println!("DEBUG PARSE2: Error parsing condition: {}", details);
+            trace!("DEBUG PARSE2: Error parsing condition: {}", details);
             parser_state.errors.push(VarError::InvalidCondition { 
                 details, 
                 line
@@ -277,34 +451,49 @@ pub fn parse2(
         }
     };
     
-    println!("DEBUG PARSE2: Parsed condition: {:?}", condition);
+    trace!("DEBUG PARSE2: Parsed condition: {:?}", condition);
+    parser_state.check_array_use(&condition, line);
     
     pos = condition_end + 1; // Move past the closing parenthesis
-    println!("DEBUG PARSE2: Moving to position {} after condition", pos);
+    trace!("DEBUG PARSE2: Moving to position {} after condition", pos);
+
+    parse_body(tokens, pos, input, parser_state, line).map(|body| ASTNode::If { condition, body, line })
+}
+
+/// Parse a `{ ... }` body starting at or after `pos`, moving the parser past its `}`
+#[inline(always)]
+fn parse_body(
+    tokens: &[Token],
+    mut pos: usize,
+    input: &str,
+    parser_state: &mut ParserState,
+    line: usize,
+) -> Option<Vec<ASTNode>> {
+    let len = tokens.len();
 
-    // Skip any whitespace after the closing parenthesis
+    // Skip any whitespace after the closing parenthesis; the brace may start the next line
     while pos < len {
         let token = &tokens[pos];
         let lexeme = get_lexeme(token, input);
-        if token.kind == TokenKind::Unknown && lexeme.trim().is_empty() {
-            println!("DEBUG PARSE2: Skipping whitespace at position {}", pos);
+        if token.kind == TokenKind::Newline || (token.kind == TokenKind::Unknown && lexeme.trim().is_empty()) {
+            trace!("DEBUG PARSE2: Skipping whitespace at position {}", pos);
             pos += 1;
         } else {
             break;
         }
     }
-    println!("DEBUG PARSE2: After skipping whitespace, now at position {}", pos);
+    trace!("DEBUG PARSE2: After skipping whitespace, now at position {}", pos);
 
     // Parse opening brace for the body
     if pos >= len || tokens[pos].kind != TokenKind::LCurlyB {
-        println!("DEBUG PARSE2: Missing open brace at position {}, found {:?}", pos, tokens[pos].kind);
+        trace!("DEBUG PARSE2: Missing open brace at position {}, found {:?}", pos, tokens[pos].kind);
         parser_state
             .errors
             .push(VarError::MissingBlockOpenBrace { line });
         return None;
     }
     pos += 1;
-    println!("DEBUG PARSE2: Found open brace, moving to position {}", pos);
+    trace!("DEBUG PARSE2: Found open brace, moving to position {}", pos);
 
     // Parse the body of the conditional
     let mut body = Vec::new();
@@ -319,7 +508,7 @@ pub fn parse2(
                 if depth == 0 {
                     // Process the body tokens
                     let body_tokens = &tokens[body_start..pos];
-                    println!("DEBUG PARSE2: Found body from position {} to {}", body_start, pos);
+                    trace!("DEBUG PARSE2: Found body from position {} to {}", body_start, pos);
 
                     // Create a new parser state for the body
                     let mut state = ParserState {
@@ -327,14 +516,21 @@ pub fn parse2(
                         input,
                         global_pos: 0,
                         global_var_map: parser_state.global_var_map,
+                        types: parser_state.types,
                         pos: 0,
                         errors: Vec::new(),
                     };
 
                     // Parse each statement in the body
                     while state.pos < body_tokens.len() {
+                        // Skipped here as at the top level: a statement that fails
+                        // is stepped over, which would also skip what follows the newline
+                        if body_tokens[state.pos].kind == TokenKind::Newline {
+                            state.pos += 1;
+                            continue;
+                        }
                         if let Some(stmt) = state.parse_statement(body_tokens) {
-                            println!("DEBUG PARSE2: Parsed body statement: {:?}", stmt);
+                            trace!("DEBUG PARSE2: Parsed body statement: {:?}", stmt);
                             body.push(stmt);
                         } else {
                             state.pos += 1;
@@ -348,9 +544,9 @@ pub fn parse2(
                     parser_state.global_pos = pos + 1;
                     parser_state.pos = pos + 1;
                     
-                    println!("DEBUG PARSE2: Successfully parsed conditional with {} body statements", body.len());
+                    trace!("DEBUG PARSE2: Successfully parsed block with {} body statements", body.len());
 
-                    return Some(ASTNode::If { condition, body });
+                    return Some(body);
                 }
             }
             _ => {}
@@ -358,9 +554,70 @@ pub fn parse2(
         pos += 1;
     }
 
-    println!("DEBUG PARSE2: Unmatched closing brace");
+    trace!("DEBUG PARSE2: Unclosed block");
     parser_state
         .errors
-        .push(VarError::UnmatchedClosingBrace { line });
+        .push(VarError::UnclosedBlock { line });
     None
 }
+
+
+/// Parse a loop, `~(item in array) { ... }`
+#[inline(always)]
+pub fn parse_foreach(
+    tokens: &[Token],
+    start_pos: usize,
+    input: &str,
+    parser_state: &mut ParserState,
+) -> Option<ASTNode> {
+    let line = parser_state.get_line_number(start_pos);
+    let mut invalid = |details: &str| {
+        parser_state.errors.push(VarError::InvalidArray {
+            details: details.to_string(),
+            line,
+        });
+        None
+    };
+
+    // The header is `~(`, three words and `)`, all on one line
+    let mut pos = start_pos + 1;
+    if tokens.get(pos).map(|t| t.kind) != Some(TokenKind::LSmallB) {
+        return invalid("expected '(' after '~'");
+    }
+    pos += 1;
+    let mut words = Vec::new();
+    while let Some(token) = tokens.get(pos) {
+        match token.kind {
+            TokenKind::RSmallB | TokenKind::Newline => break,
+            TokenKind::Unknown if get_lexeme(token, input).trim().is_empty() => {}
+            _ => words.push(*token),
+        }
+        pos += 1;
+    }
+    if tokens.get(pos).map(|t| t.kind) != Some(TokenKind::RSmallB) {
+        return invalid("expected ')' to close the loop header");
+    }
+    let (item, array) = match words[..] {
+        [item, keyword, array]
+            if item.kind == TokenKind::Identifier
+                && array.kind == TokenKind::Identifier
+                && get_lexeme(&keyword, input) == "in" =>
+        {
+            (get_lexeme(&item, input).to_string(), get_lexeme(&array, input).to_string())
+        }
+        _ => return invalid("expected `~(item in array)`"),
+    };
+
+    // The item is declared by the loop, with the array's element type
+    let element = match parser_state.global_var_map.get(&array) {
+        Some((VarType::Array(element), _)) => (**element).clone(),
+        Some((other, _)) => return invalid(&format!("'{}' is a {} variable, not an array", array, other)),
+        None => {
+            parser_state.errors.push(VarError::UndeclaredVariable { name: array, line });
+            return None;
+        }
+    };
+    parser_state.global_var_map.insert(item.clone(), (element, line));
+
+    parse_body(tokens, pos + 1, input, parser_state, line).map(|body| ASTNode::ForEach { item, array, body })
+}
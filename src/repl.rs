@@ -0,0 +1,262 @@
+//! `ven repl`: an interactive session on top of the in-process VM.
+//!
+//! Each entry is checked as the end of everything entered before it, with the
+//! same checks the compiler runs, and then run in one long-lived `Vm`, so
+//! variables carry over between entries. An entry that leaves a `{` open keeps
+//! reading lines until the braces balance.
+//!
+//! Checking re-parses the whole accumulated program, since a name resolves
+//! against every declaration in it, so a session of n entries costs O(n²)
+//! in total. Only the statements the entry adds are run.
+
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::diagnostic::{Diagnostic, ErrorFormat, emit};
+use crate::errs::VarError;
+use crate::parse::AST;
+use crate::session::Session;
+use crate::token::{Token, TokenKind, Tokenizer};
+use crate::vm::{Value, Vm};
+
+const PROMPT: &str = "ven> ";
+const CONTINUATION_PROMPT: &str = "...> ";
+/// In the home directory. Entries are stored one per line, with newlines and
+/// backslashes escaped.
+const HISTORY_FILE: &str = ".ven_history";
+
+fn print_help() {
+    println!("Ven REPL");
+    println!("├── <statement>                  Run a statement; variables are kept between entries");
+    println!("├── :ast [code]                  Show the AST of code, or of the last entry");
+    println!("├── :tokens [code]               Show the tokens of code, or of the last entry");
+    println!("├── :vars                        Show every variable with its type and value");
+    println!("├── :history                     Show previous entries");
+    println!("├── :run <n>                     Run entry <n> from the history again");
+    println!("├── :help                        Show this help");
+    println!("└── :quit                        Leave the REPL (Ctrl-D works too)");
+}
+
+/// Net number of `{` over `}` tokens. Braces inside string literals and
+/// comments are single tokens, so `{name}` placeholders do not count.
+fn brace_depth(tokens: &[Token]) -> isize {
+    tokens.iter().fold(0, |depth, token| match token.kind {
+        TokenKind::LCurlyB => depth + 1,
+        TokenKind::RCurlyB => depth - 1,
+        _ => depth,
+    })
+}
+
+/// Parse errors as box art on stderr, pointing into the entry. `names` and
+/// `types` are the variables and record types a misspelt word may mean.
+fn print_errors(errors: &[VarError], entry: &str, names: &[&str], types: &[&str]) {
+    let diagnostics: Vec<Diagnostic> =
+        errors.iter().map(|e| Diagnostic::from_var_error(e, entry, names, types)).collect();
+    let _ = emit(&mut io::stderr(), &diagnostics, Some(entry), ErrorFormat::Human);
+}
+
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE))
+}
+
+fn load_history() -> Vec<String> {
+    let Some(contents) = history_path().and_then(|path| fs::read_to_string(path).ok()) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .map(|line| {
+            let mut entry = String::with_capacity(line.len());
+            let mut chars = line.chars();
+            while let Some(c) = chars.next() {
+                match (c, chars.clone().next()) {
+                    ('\\', Some('n')) => {
+                        entry.push('\n');
+                        chars.next();
+                    }
+                    ('\\', Some('\\')) => {
+                        entry.push('\\');
+                        chars.next();
+                    }
+                    _ => entry.push(c),
+                }
+            }
+            entry
+        })
+        .collect()
+}
+
+fn save_history_entry(entry: &str) {
+    let Some(path) = history_path() else { return };
+    // History is a convenience, so failing to write it is not worth reporting
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(file, "{}", entry.replace('\\', "\\\\").replace('\n', "\\n"));
+    }
+}
+
+pub struct Repl<'a> {
+    vm: Vm<'a>,
+    history: Vec<String>,
+    /// The last entry that was run, for `:ast` and `:tokens` without code.
+    last: String,
+    /// Every entry that passed the checks, in order. New entries are checked
+    /// after it, so they see the declarations made so far.
+    program: String,
+    /// Top-level statements in `program`, which have already run.
+    statements: usize,
+}
+
+impl Default for Repl<'_> {
+    fn default() -> Self {
+        Repl::new()
+    }
+}
+
+impl<'a> Repl<'a> {
+    pub fn new() -> Repl<'a> {
+        Repl {
+            vm: Vm::new(),
+            history: load_history(),
+            last: String::new(),
+            program: String::new(),
+            statements: 0,
+        }
+    }
+
+    /// Reads entries from stdin until `:quit` or end of input.
+    pub fn run(&mut self) -> io::Result<()> {
+        println!("Ven REPL. Type :help for commands, :quit to leave.");
+        let stdin = io::stdin();
+        let mut entry = String::new();
+        loop {
+            print!("{}", if entry.is_empty() { PROMPT } else { CONTINUATION_PROMPT });
+            io::stdout().flush()?;
+
+            let mut line = String::new();
+            if stdin.read_line(&mut line)? == 0 {
+                println!();
+                return Ok(());
+            }
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if entry.is_empty() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    continue;
+                }
+                if let Some(command) = trimmed.strip_prefix(':') {
+                    if !self.meta_command(command) {
+                        return Ok(());
+                    }
+                    continue;
+                }
+            }
+
+            entry.push_str(line);
+            entry.push('\n');
+            let mut tokenizer = Tokenizer::new(&entry);
+            tokenizer.tokenize();
+            if brace_depth(&tokenizer.tokens) > 0 {
+                continue;
+            }
+
+            let entry = std::mem::take(&mut entry);
+            save_history_entry(entry.trim_end());
+            self.history.push(entry.trim_end().to_string());
+            self.eval(entry);
+        }
+    }
+
+    fn eval(&mut self, entry: String) {
+        // Lines of the program before the entry, taken off the line numbers of
+        // errors so they count from the entry's first line
+        let offset = self.program.lines().count();
+        let session = Session::new(format!("{}{}", self.program, entry));
+        if !session.errors().is_empty() {
+            let mut errors = session.errors().to_vec();
+            for error in &mut errors {
+                let line = error.line_mut();
+                *line = line.saturating_sub(offset);
+            }
+            print_errors(&errors, &entry, &session.names(), &session.type_names());
+            self.last = entry;
+            return;
+        }
+
+        let AST::Program(nodes) = session.ast();
+        let new = AST::Program(nodes[self.statements..].to_vec());
+        self.statements = nodes.len();
+        self.program.push_str(&entry);
+        if let Err(mut e) = self.vm.run(&new) {
+            if let Some(line) = e.line_mut() {
+                *line = line.saturating_sub(offset);
+            }
+            eprintln!("Error: {}", e);
+        }
+        self.last = entry;
+    }
+
+    /// Runs a `:command`. Returns false when the REPL should exit.
+    fn meta_command(&mut self, command: &str) -> bool {
+        let (name, arg) = match command.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, arg.trim()),
+            None => (command, ""),
+        };
+        let code = if arg.is_empty() {
+            self.last.clone()
+        } else {
+            format!("{}\n", arg)
+        };
+        match name {
+            "ast" => {
+                let mut tokenizer = Tokenizer::new(&code);
+                tokenizer.tokenize();
+                let (ast, _, errors) = AST::parse(&tokenizer.tokens, &code);
+                ast.debug();
+                print_errors(&errors, &code, &[], &[]);
+            }
+            "tokens" => {
+                let mut tokenizer = Tokenizer::new(&code);
+                tokenizer.tokenize();
+                tokenizer.debug();
+            }
+            "vars" => {
+                let mut vars: Vec<_> = self.vm.variables().collect();
+                if vars.is_empty() {
+                    println!("No variables yet");
+                }
+                vars.sort_by_key(|(name, _)| *name);
+                for (i, (name, variable)) in vars.iter().enumerate() {
+                    let branch = if i + 1 == vars.len() { "└──" } else { "├──" };
+                    let sigil = if variable.mutable { "@@" } else { "@" };
+                    let value = match &variable.value {
+                        Value::Str(text) => format!("{:?}", text),
+                        value => value.to_string(),
+                    };
+                    println!("{} {} {} {} = {}", branch, sigil, name, variable.var_type, value);
+                }
+            }
+            "history" => {
+                for (i, entry) in self.history.iter().enumerate() {
+                    println!("{:>4}  {}", i + 1, entry.replace('\n', "\n      "));
+                }
+            }
+            "run" => match arg.parse::<usize>().ok().and_then(|n| self.history.get(n.wrapping_sub(1))) {
+                Some(entry) => {
+                    let entry = entry.clone();
+                    println!("{}", entry);
+                    save_history_entry(&entry);
+                    self.history.push(entry.clone());
+                    self.eval(format!("{}\n", entry));
+                }
+                None => eprintln!("Error: no history entry '{}' (see :history)", arg),
+            },
+            "help" | "h" => print_help(),
+            "quit" | "q" | "exit" => return false,
+            _ => eprintln!("Error: unknown command ':{}' (see :help)", name),
+        }
+        true
+    }
+}
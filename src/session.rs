@@ -0,0 +1,241 @@
+use std::time::{Duration, Instant};
+
+use crate::cst::Cst;
+use crate::errs::{BackendError, VarError};
+use crate::optimisers::pass1::optimize_pass1;
+use crate::optimisers::pass2::pass2;
+use crate::parse::{AST, ASTNode};
+use crate::token::{Token, Tokenizer};
+use crate::transpilers::{BACKENDS, Backend, CodegenOptions, find_backend};
+use crate::var_checker::{VarMap, check_names, check_variables};
+
+/// A compilation target, backed by one of the built-in backends or by one an
+/// embedder provides.
+#[derive(Clone, Copy)]
+pub struct Target {
+    backend: &'static dyn Backend,
+}
+
+impl Target {
+    /// Looks a target up by name or alias, as accepted by `-t=`.
+    pub fn from_name(name: &str) -> Option<Target> {
+        find_backend(name).map(|backend| Target { backend })
+    }
+
+    /// Every built-in target.
+    pub fn all() -> impl Iterator<Item = Target> {
+        BACKENDS.iter().map(|&backend| Target { backend })
+    }
+
+    /// A target backed by `backend`, which need not be built in. Programs are
+    /// checked against its capabilities before it emits them, as for the
+    /// built-in targets.
+    pub fn from_backend(backend: &'static dyn Backend) -> Target {
+        Target { backend }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    pub fn backend(&self) -> &'static dyn Backend {
+        self.backend
+    }
+}
+
+impl std::fmt::Debug for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Target").field(&self.name()).finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Options {
+    /// Run the AST optimisation passes before emitting.
+    pub optimize: bool,
+    /// Also produce the target's binary artifact, if it has one.
+    pub binary: bool,
+    /// Passed on to the backend.
+    pub codegen: CodegenOptions,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            optimize: true,
+            binary: false,
+            codegen: CodegenOptions::default(),
+        }
+    }
+}
+
+/// The result of a successful compilation.
+#[derive(Debug, Clone)]
+pub struct Output {
+    pub code: String,
+    /// Extension for `code`, without the dot.
+    pub extension: &'static str,
+    /// Binary artifact and its extension, when requested and available.
+    pub binary: Option<(&'static str, Vec<u8>)>,
+    /// Time spent in each phase after parsing.
+    pub phases: Vec<(&'static str, Duration)>,
+}
+
+/// Everything that stopped a compilation.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    /// Parser and semantic errors.
+    pub errors: Vec<VarError>,
+    /// Set when the target rejected a program that parsed fine.
+    pub backend: Option<BackendError>,
+}
+
+impl Diagnostics {
+    pub fn len(&self) -> usize {
+        self.errors.len() + self.backend.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// One source file, tokenized and parsed.
+pub struct Session {
+    source: String,
+    tokens: Vec<Token>,
+    ast: AST,
+    var_map: VarMap,
+    errors: Vec<VarError>,
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl Session {
+    pub fn new(source: impl Into<String>) -> Session {
+        let source = source.into();
+
+        let start = Instant::now();
+        let mut tokenizer = Tokenizer::new(&source);
+        tokenizer.tokenize();
+        let tokens = tokenizer.tokens;
+        let token_time = start.elapsed();
+
+        let start = Instant::now();
+        let (ast, mut var_map, mut errors) = AST::parse(&tokens, &source);
+        // Initializers are checked against their declared types here, so that a
+        // value the type cannot hold never reaches a backend
+        errors.extend(check_variables(&ast, &mut var_map));
+        let names = check_names(&ast, &tokens, &source, &errors);
+        errors.extend(names);
+        let parse_time = start.elapsed();
+
+        Session {
+            source,
+            tokens,
+            ast,
+            var_map,
+            errors,
+            phases: vec![("Tokenizing", token_time), ("Parsing AST", parse_time)],
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// The source text of a token. Non-ASCII characters are tokenized byte by
+    /// byte, so their tokens have no text of their own and come back empty.
+    pub fn lexeme(&self, token: &Token) -> &str {
+        self.source.get(token.start..token.end).unwrap_or("")
+    }
+
+    /// The AST as parsed, before optimisation.
+    pub fn ast(&self) -> &AST {
+        &self.ast
+    }
+
+    /// The lossless syntax tree of the source, with its comments and layout.
+    pub fn cst(&self) -> Cst {
+        Cst::parse(&self.source)
+    }
+
+    /// Declared variables with their type and declaration line.
+    pub fn var_map(&self) -> &VarMap {
+        &self.var_map
+    }
+
+    /// Names of the declared variables, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.var_map.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Names of the record types declared with `#`, in order.
+    pub fn type_names(&self) -> Vec<&str> {
+        let AST::Program(nodes) = &self.ast;
+        nodes
+            .iter()
+            .filter_map(|node| match node {
+                ASTNode::RecordDeclaration(record) => Some(record.name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn errors(&self) -> &[VarError] {
+        &self.errors
+    }
+
+    /// Time spent tokenizing and parsing.
+    pub fn phases(&self) -> &[(&'static str, Duration)] {
+        &self.phases
+    }
+
+    pub fn compile(&self, target: Target, options: &Options) -> Result<Output, Diagnostics> {
+        if !self.errors.is_empty() {
+            return Err(Diagnostics {
+                errors: self.errors.clone(),
+                backend: None,
+            });
+        }
+
+        let mut phases = Vec::new();
+        let mut ast = self.ast.clone();
+        if options.optimize {
+            let start = Instant::now();
+            optimize_pass1(&mut ast);
+            phases.push(("Optimizing AST (pass1)", start.elapsed()));
+
+            let start = Instant::now();
+            ast = pass2(ast);
+            phases.push(("Optimizing AST (pass2)", start.elapsed()));
+        }
+
+        let backend = target.backend();
+        let start = Instant::now();
+        let code = backend.transpile(&ast, &options.codegen).map_err(|e| Diagnostics {
+            errors: Vec::new(),
+            backend: Some(e),
+        })?;
+        phases.push(("Transpiling", start.elapsed()));
+
+        let binary = if options.binary { backend.emit_binary(&ast, &options.codegen) } else { None };
+        Ok(Output {
+            code,
+            extension: backend.extension(),
+            binary,
+            phases,
+        })
+    }
+}
+
+/// Parses and compiles `source` in one go.
+pub fn compile(source: &str, target: Target, options: Options) -> Result<Output, Diagnostics> {
+    Session::new(source).compile(target, &options)
+}
@@ -0,0 +1,151 @@
+//! "Did you mean" suggestions: the valid name closest to a misspelt one, as a
+//! [`Fix`] that `ven fix` and editors apply without asking.
+//!
+//! ```
+//! use ven::suggest::{closest, type_name};
+//!
+//! assert_eq!(closest("nmae", ["name", "age"]), Some("name"));
+//! assert_eq!(closest("total", ["name", "age"]), None);
+//! assert_eq!(type_name("int"), Some("i"));
+//! ```
+
+use crate::diagnostic::{Diagnostic, Fix, Span};
+use crate::errs::VarError;
+use crate::lsp::document::Document;
+
+/// Type names from other languages and their Ven spelling.
+const TYPE_ALIASES: [(&str, &str); 14] = [
+    ("int", "i"),
+    ("integer", "i"),
+    ("long", "i"),
+    ("short", "i16"),
+    ("byte", "u8"),
+    ("string", "str"),
+    ("text", "str"),
+    ("float", "f"),
+    ("double", "f"),
+    ("number", "f"),
+    ("char", "c[1]"),
+    ("character", "c[1]"),
+    ("bool", "b"),
+    ("boolean", "b"),
+];
+
+/// Edits that turn `a` into `b`, counting inserting, removing or replacing a
+/// character and swapping two adjacent ones as one edit each.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut before: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (previous[j] + 1).min(row[j - 1] + 1).min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(before[j - 2] + 1);
+            }
+        }
+        before = std::mem::replace(&mut previous, row);
+    }
+    previous[b.len()]
+}
+
+/// The candidate closest to `word`, if it is close enough to be a typo: one
+/// edit per three characters, and at least one. A case-insensitive match wins
+/// outright; otherwise a tie between two candidates suggests neither, since
+/// the fix is applied without asking.
+pub fn closest<'a>(word: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = word.chars().count().max(3) / 3;
+    let mut best: Option<(usize, &str)> = None;
+    let mut tied = false;
+    for candidate in candidates {
+        if candidate == word {
+            continue;
+        }
+        if candidate.eq_ignore_ascii_case(word) {
+            return Some(candidate);
+        }
+        let distance = edit_distance(word, candidate);
+        if distance > limit {
+            continue;
+        }
+        match best {
+            Some((d, name)) if d == distance && name != candidate => tied = true,
+            Some((d, _)) if d <= distance => {}
+            _ => {
+                best = Some((distance, candidate));
+                tied = false;
+            }
+        }
+    }
+    best.filter(|_| !tied).map(|(_, name)| name)
+}
+
+/// The Ven type meant by `word`: `int` is `i`, `string` is `str`.
+pub fn type_name(word: &str) -> Option<&'static str> {
+    TYPE_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(word))
+        .map(|(_, name)| *name)
+        .or_else(|| closest(word, ["i", "str", "f", "b"]))
+}
+
+/// The fix for `error`, whose misspelt word is at `span`. `names` are the
+/// declared variables and `types` the declared record types.
+pub fn fix(error: &VarError, span: Span, names: &[&str], types: &[&str]) -> Option<Fix> {
+    let replacement = match error {
+        VarError::UndeclaredVariable { name, .. } => closest(name, names.iter().copied())?,
+        VarError::UnknownType { name, .. } => type_name(name).or_else(|| closest(name, types.iter().copied()))?,
+        _ => return None,
+    };
+    Some(Fix {
+        message: format!("did you mean `{}`?", replacement),
+        span,
+        replacement: replacement.to_string(),
+    })
+}
+
+/// The errors of `document`, with a fix wherever a valid name is close to
+/// the wrong one.
+pub fn diagnostics(document: &Document) -> Vec<Diagnostic> {
+    let source = document.text();
+    let names: Vec<&str> = document.symbols.iter().map(|symbol| symbol.name.as_str()).collect();
+    let types: Vec<&str> = document.records().iter().map(|record| record.name.as_str()).collect();
+    document
+        .diagnostics
+        .iter()
+        .map(|found| {
+            let mut diagnostic = Diagnostic::from_var_error(&found.error, source, &names, &types);
+            // References point at the name itself, errors of the checker at their line
+            if found.span != document.line_span(found.error.line()) {
+                let span = Span::new(source, found.span.0, found.span.1);
+                diagnostic.span = Some(span);
+                diagnostic.fix = fix(&found.error, span, &names, &types);
+            }
+            diagnostic
+        })
+        .collect()
+}
+
+/// Applies `fixes` to `source`. A fix overlapping one already applied is
+/// skipped. Returns the new text and the fixes applied, in source order.
+pub fn apply(source: &str, fixes: &[Fix]) -> (String, Vec<Fix>) {
+    let mut sorted: Vec<&Fix> = fixes.iter().collect();
+    sorted.sort_by_key(|fix| (fix.span.start, fix.span.end));
+    let mut applied: Vec<Fix> = Vec::new();
+    let mut out = String::with_capacity(source.len());
+    let mut copied = 0;
+    for fix in sorted {
+        if fix.span.start < copied || applied.last().is_some_and(|last| last.span == fix.span) {
+            continue;
+        }
+        out.push_str(&source[copied..fix.span.start]);
+        out.push_str(&fix.replacement);
+        copied = fix.span.end;
+        applied.push(fix.clone());
+    }
+    out.push_str(&source[copied..]);
+    (out, applied)
+}
@@ -10,6 +10,8 @@ pub enum TokenKind {
     Minus,      // '-' (subtraction operator)
     Star,       // '*' (multiplication operator)
     Slash,      // '/' (division operator)
+    Percent,    // '%' (modulo operator)
+    Power,      // '**' (power operator)
     Identifier, // Alphanumeric identifiers (including type keywords and literals)
     Newline,    // '\n' (statement separator)
     Unknown,    // Any unknown character
@@ -20,6 +22,8 @@ pub enum TokenKind {
     RCurlyB,    // Right Curly Bracket
     LCurlyB,    // Left Curly Bracket
     Question,   // '?' (conditional operator)
+    Tilde,      // '~' (loop over an array)
+    Hash,       // '#' (record type declaration)
     Equals,     // '=' (assignment operator)
     Equal,      // '==' (equality operator)
     NotEqual,   // '!=' (inequality operator)
@@ -29,6 +33,7 @@ pub enum TokenKind {
     GreaterEqual,// '>=' (greater than or equal operator)
     And,        // '&&' (logical AND operator)
     Or,         // '||' (logical OR operator)
+    Not,        // '!' (logical NOT operator)
     StringLiteral, // String literal enclosed in double quotes
 }
 
@@ -137,7 +142,7 @@ impl<'a> Tokenizer<'a> {
                         pos += 1;
                         TokenKind::NotEqual
                     } else {
-                        TokenKind::Unknown
+                        TokenKind::Not
                     }
                 }
                 b'&' => {
@@ -164,6 +169,14 @@ impl<'a> Tokenizer<'a> {
                     pos += 1;
                     TokenKind::Question
                 }
+                b'#' => {
+                    pos += 1;
+                    TokenKind::Hash
+                }
+                b'~' => {
+                    pos += 1;
+                    TokenKind::Tilde
+                }
                 b'+' => {
                     pos += 1;
                     TokenKind::Plus
@@ -174,12 +187,22 @@ impl<'a> Tokenizer<'a> {
                 }
                 b'*' => {
                     pos += 1;
-                    TokenKind::Star
+                    // Check for '**'
+                    if pos < len && bytes[pos] == b'*' {
+                        pos += 1;
+                        TokenKind::Power
+                    } else {
+                        TokenKind::Star
+                    }
                 }
                 b'/' => {
                     pos += 1;
                     TokenKind::Slash
                 }
+                b'%' => {
+                    pos += 1;
+                    TokenKind::Percent
+                }
                 b'\n' => {
                     pos += 1;
                     TokenKind::Newline
@@ -266,8 +289,7 @@ impl<'a> Tokenizer<'a> {
         }
         println!("├── Statement");
         for (i, token) in group.iter().enumerate() {
-            let lexeme =
-                unsafe { std::str::from_utf8_unchecked(&self.input[token.start..token.end]) };
+            let lexeme = String::from_utf8_lossy(&self.input[token.start..token.end]);
             let connector = if i == group.len() - 1 {
                 "    └──"
             } else {
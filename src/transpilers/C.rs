@@ -1,273 +1,1547 @@
-use crate::parse::{AST, ASTNode, Expression, MathOperator, VarType, ComparisonOperator, LogicalOperator};
+use crate::errs::BackendError;
+use crate::parse::{
+    AST, ASTNode, ComparisonOperator, Expression, IntType, LogicalOperator, MathFn, MathOperator, TextOp, VarType,
+};
+use crate::transpilers::{
+    Backend, Capabilities, CodegenOptions, Overflow, Segment, ValueKind, clean_number, collect_var_types,
+    element_type, field_type, fit, formatted, int_literal, int_type_of, literal_text, map_types, placeholder,
+    is_text_var, math_kind, record_types, split_interpolation, target_type, type_kind, uses_conversions, uses_math,
+    uses_division, undeclared, uses_strings, value_kind,
+};
 use std::collections::HashMap;
 
-pub fn transpile_c(ast: &AST) -> String {
-    let mut code = String::with_capacity(1024);
-    let mut temp_var_counter = 0; // Counter to ensure unique temp variables
-
-    // Build a map from variable names to their declared types.
-    let mut var_types: HashMap<String, VarType> = HashMap::new();
-    match ast {
-        AST::Program(nodes) => {
-            for node in nodes {
-                if let ASTNode::VarDeclaration { name, var_type, .. } = node {
-                    var_types.insert(name.clone(), var_type.clone());
+/// Size of every `str` buffer, including the terminating NUL. A `c[size]`
+/// buffer holds `size` bytes and the NUL.
+const STR_SIZE: usize = 256;
+
+// Line input, conversions and float printing, shared by every generated program
+const RUNTIME_HELPERS: &str = r#"static char ven_line[256];
+
+/* Wide enough for every value of every Ven integer type. */
+typedef __int128 ven_wide;
+
+/* Reads one line without its newline; carriage returns and overflow are dropped. */
+static void ven_read_line(char *buf, size_t size) {
+    size_t len = 0;
+    int c;
+    while ((c = getchar()) != EOF && c != '\n') {
+        if (c != '\r' && len + 1 < size) {
+            buf[len++] = (char)c;
+        }
+    }
+    buf[len] = '\0';
+}
+
+/* Copies as much of src as fits without splitting a character, always terminating dest. */
+static void ven_copy(char *dest, const char *src, size_t size) {
+    size_t len = strlen(src);
+    if (len >= size) {
+        len = size - 1;
+        while (len > 0 && ((unsigned char)src[len] & 0xC0) == 0x80) {
+            len--;
+        }
+    }
+    memmove(dest, src, len);
+    dest[len] = '\0';
+}
+
+static bool ven_blank(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return *text == '\0';
+}
+
+/* Text that is not a whole number from INT64_MIN to UINT64_MAX reads as 0. */
+static ven_wide ven_to_int(const char *text) {
+    char *end;
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    errno = 0;
+    if (*text == '-') {
+        long long value = strtoll(text, &end, 10);
+        return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+    }
+    unsigned long long value = strtoull(text, &end, 10);
+    return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+}
+
+static double ven_to_float(const char *text) {
+    char *end;
+    double value = strtod(text, &end);
+    return end == text || !ven_blank(end) ? 0.0 : value;
+}
+
+/* Only the word true reads as true. */
+static bool ven_to_bool(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return strncmp(text, "true", 4) == 0 && ven_blank(text + 4);
+}
+
+/* The shortest digits that read back as the same double, written out without
+   an exponent, like 0.001 or 1000000, into buf of 400 bytes. */
+static const char *ven_float_text(char *buf, double x) {
+    if (x != x) {
+        return "NaN";
+    }
+    /* Only an infinity is not finite after x - x */
+    if (x - x != 0) {
+        return x < 0 ? "-inf" : "inf";
+    }
+    char shortest[32];
+    for (int precision = 1; precision <= 17; precision++) {
+        snprintf(shortest, sizeof shortest, "%.*e", precision - 1, x);
+        if (strtod(shortest, NULL) == x) {
+            break;
+        }
+    }
+    char digits[24];
+    int len = 0;
+    const char *at = shortest;
+    char *out = buf;
+    if (*at == '-') {
+        *out++ = *at++;
+    }
+    for (; *at != 'e'; at++) {
+        if (*at != '.') {
+            digits[len++] = *at;
+        }
+    }
+    int exponent = atoi(at + 1);
+    if (exponent < 0) {
+        *out++ = '0';
+        *out++ = '.';
+        for (int i = -1; i > exponent; i--) {
+            *out++ = '0';
+        }
+        memcpy(out, digits, len);
+        out += len;
+    } else if (exponent + 1 >= len) {
+        memcpy(out, digits, len);
+        out += len;
+        for (int i = len; i <= exponent; i++) {
+            *out++ = '0';
+        }
+    } else {
+        memcpy(out, digits, exponent + 1);
+        out += exponent + 1;
+        *out++ = '.';
+        memcpy(out, digits + exponent + 1, len - exponent - 1);
+        out += len - exponent - 1;
+    }
+    *out = '\0';
+    return buf;
+}
+
+static void ven_print_float(FILE *out, double x) {
+    char buf[400];
+    fputs(ven_float_text(buf, x), out);
+}
+"#;
+
+// `i()`, `f()` and `str()`, and `{name:spec}` layout, for programs that use them
+const CONVERT_HELPERS: &str = r#"
+static void ven_convert_failed(const char *text, const char *to, int line) {
+    fflush(stdout);
+    fprintf(stderr, "runtime error: cannot convert \"%s\" to %s on line %d\n", text, to, line);
+    exit(1);
+}
+
+static bool ven_space(char c) {
+    return c == ' ' || c == '\t' || c == '\n' || c == '\r';
+}
+
+/* A sign and decimal digits within int64_t, with spaces around them. */
+static int64_t ven_convert_int(const char *text, int line) {
+    const char *start = text;
+    while (ven_space(*start)) {
+        start++;
+    }
+    const char *at = start + (*start == '+' || *start == '-');
+    const char *digits = at;
+    while (isdigit((unsigned char)*at)) {
+        at++;
+    }
+    bool whole = at > digits;
+    while (ven_space(*at)) {
+        at++;
+    }
+    errno = 0;
+    long long value = whole && *at == '\0' ? strtoll(start, NULL, 10) : 0;
+    if (!whole || *at != '\0' || errno == ERANGE) {
+        ven_convert_failed(text, "i", line);
+    }
+    return value;
+}
+
+/* A finite decimal number like -1.5, .5, 2. or 6.02e23, with spaces around it. */
+static double ven_convert_float(const char *text, int line) {
+    const char *start = text;
+    while (ven_space(*start)) {
+        start++;
+    }
+    const char *at = start + (*start == '+' || *start == '-');
+    int digits = 0;
+    for (; isdigit((unsigned char)*at); at++) {
+        digits++;
+    }
+    if (*at == '.') {
+        for (at++; isdigit((unsigned char)*at); at++) {
+            digits++;
+        }
+    }
+    if (digits > 0 && (*at == 'e' || *at == 'E')) {
+        at += 1 + (at[1] == '+' || at[1] == '-');
+        digits = isdigit((unsigned char)*at) ? digits : 0;
+        while (isdigit((unsigned char)*at)) {
+            at++;
+        }
+    }
+    while (ven_space(*at)) {
+        at++;
+    }
+    double value = digits > 0 && *at == '\0' ? strtod(start, NULL) : 0.0;
+    if (digits == 0 || *at != '\0' || value - value != 0) {
+        ven_convert_failed(text, "f", line);
+    }
+    return value;
+}
+
+/* Truncated toward zero, with NaN as 0 and clamped at the int64_t limits. */
+static int64_t ven_float_to_int(double x) {
+    if (x != x) {
+        return 0;
+    }
+    return x <= -9223372036854775808.0 ? INT64_MIN : x >= 9223372036854775808.0 ? INT64_MAX : (int64_t)x;
+}
+
+static const char *ven_int_text(char *buf, long long value) {
+    snprintf(buf, 24, "%lld", value);
+    return buf;
+}
+
+static const char *ven_uint_text(char *buf, unsigned long long value) {
+    snprintf(buf, 24, "%llu", value);
+    return buf;
+}
+
+/* precision digits after the point, rounded half to even, into buf of 400 bytes. */
+static const char *ven_fixed_text(char *buf, double x, int precision) {
+    if (x != x || x - x != 0) {
+        return ven_float_text(buf, x);
+    }
+    snprintf(buf, 400, "%.*f", precision, x);
+    return buf;
+}
+
+/* Prints text padded with spaces to width characters, before it when right. */
+static void ven_pad(FILE *out, const char *text, size_t width, bool right) {
+    size_t chars = 0;
+    for (const char *at = text; *at; at++) {
+        chars += ((unsigned char)*at & 0xC0) != 0x80;
+    }
+    if (!right) {
+        fputs(text, out);
+    }
+    for (; chars < width; chars++) {
+        fputc(' ', out);
+    }
+    if (right) {
+        fputs(text, out);
+    }
+}
+"#;
+
+// `concat()`, `slice()` and the other string operations, for programs that use
+// them. Each result is written into a fresh buffer of 256 bytes.
+const STRING_HELPERS: &str = r#"
+/* Appends n bytes of src to buf, cutting at the last whole character that
+   fits; once text is cut nothing more is added. */
+static void ven_put(char *buf, size_t *len, const char *src, size_t n) {
+    if (*len + n >= 256) {
+        n = 255 - *len;
+        while (n > 0 && ((unsigned char)src[n] & 0xC0) == 0x80) {
+            n--;
+        }
+        memcpy(buf + *len, src, n);
+        buf[*len + n] = '\0';
+        *len = 255;
+        return;
+    }
+    memcpy(buf + *len, src, n);
+    *len += n;
+    buf[*len] = '\0';
+}
+
+static char *ven_concat(char *buf, const char **parts, size_t count) {
+    size_t len = 0;
+    buf[0] = '\0';
+    for (size_t i = 0; i < count; i++) {
+        ven_put(buf, &len, parts[i], strlen(parts[i]));
+    }
+    return buf;
+}
+
+static int64_t ven_len(const char *text) {
+    int64_t chars = 0;
+    for (; *text; text++) {
+        chars += ((unsigned char)*text & 0xC0) != 0x80;
+    }
+    return chars;
+}
+
+/* The byte offset of character index, or the length when there are fewer. */
+static size_t ven_offset(const char *text, int64_t index) {
+    size_t at = 0;
+    for (int64_t chars = 0; text[at]; at++) {
+        if (((unsigned char)text[at] & 0xC0) != 0x80 && chars++ == index) {
+            break;
+        }
+    }
+    return at;
+}
+
+static char *ven_slice(char *buf, const char *text, int64_t start, int64_t end) {
+    size_t from = ven_offset(text, start < 0 ? 0 : start);
+    size_t to = ven_offset(text, end < 0 ? 0 : end);
+    size_t len = 0;
+    buf[0] = '\0';
+    if (to > from) {
+        ven_put(buf, &len, text + from, to - from);
+    }
+    return buf;
+}
+
+/* Only ASCII letters change case. */
+static char *ven_case(char *buf, const char *text, bool upper) {
+    size_t len = 0;
+    buf[0] = '\0';
+    ven_put(buf, &len, text, strlen(text));
+    for (char *at = buf; *at; at++) {
+        if (upper && *at >= 'a' && *at <= 'z') {
+            *at -= 32;
+        } else if (!upper && *at >= 'A' && *at <= 'Z') {
+            *at += 32;
+        }
+    }
+    return buf;
+}
+
+static bool ven_trimmed(char c) {
+    return c == ' ' || c == '\t' || c == '\n' || c == '\r';
+}
+
+static char *ven_trim(char *buf, const char *text) {
+    size_t n = strlen(text);
+    while (n > 0 && ven_trimmed(*text)) {
+        text++;
+        n--;
+    }
+    while (n > 0 && ven_trimmed(text[n - 1])) {
+        n--;
+    }
+    size_t len = 0;
+    buf[0] = '\0';
+    ven_put(buf, &len, text, n);
+    return buf;
+}
+
+static bool ven_starts_with(const char *text, const char *prefix) {
+    return strncmp(text, prefix, strlen(prefix)) == 0;
+}
+
+/* Every from, left to right; an empty from leaves the text as it is. */
+static char *ven_replace(char *buf, const char *text, const char *from, const char *to) {
+    size_t len = 0;
+    size_t from_len = strlen(from);
+    buf[0] = '\0';
+    const char *found;
+    while (from_len > 0 && (found = strstr(text, from)) != NULL) {
+        ven_put(buf, &len, text, (size_t)(found - text));
+        ven_put(buf, &len, to, strlen(to));
+        text = found + from_len;
+    }
+    ven_put(buf, &len, text, strlen(text));
+    return buf;
+}
+"#;
+
+// `**` and the math functions, for programs that use them. These and `%` on
+// floats need `<math.h>`, so such programs link with `-lm`.
+const MATH_HELPERS: &str = r#"
+/* x ** y; a negative y gives 1 / x ** -y truncated toward zero. A power too
+   large for ven_wide comes back as a stand-in past every integer type, with
+   its sign and low 64 bits, so ven_fit handles it as it would the power. */
+static ven_wide ven_pow(ven_wide x, ven_wide y) {
+    if (y == 0) {
+        return 1;
+    }
+    if (x == 0 || x == 1) {
+        return x;
+    }
+    if (x == -1) {
+        return y % 2 == 0 ? 1 : -1;
+    }
+    if (y < 0) {
+        return 0;
+    }
+    ven_wide power = 1;
+    for (ven_wide i = 0; i < y; i++) {
+        if (__builtin_mul_overflow(power, x, &power)) {
+            uint64_t low = 1, base = (uint64_t)x;
+            for (uint64_t e = (uint64_t)y; e > 0; e >>= 1) {
+                if (e & 1) {
+                    low *= base;
                 }
+                base *= base;
             }
+            ven_wide past = (ven_wide)1 << 64;
+            return x < 0 && y % 2 != 0 ? (ven_wide)low - 2 * past : (ven_wide)low + past;
+        }
+    }
+    return power;
+}
+
+static ven_wide ven_abs(int64_t x) {
+    return x < 0 ? -(ven_wide)x : x;
+}
+
+/* The smallest or largest of count values; a later value only wins when it
+   is smaller or larger. */
+static int64_t ven_min(const int64_t *values, size_t count) {
+    int64_t min = values[0];
+    for (size_t i = 1; i < count; i++) {
+        min = values[i] < min ? values[i] : min;
+    }
+    return min;
+}
+
+static int64_t ven_max(const int64_t *values, size_t count) {
+    int64_t max = values[0];
+    for (size_t i = 1; i < count; i++) {
+        max = values[i] > max ? values[i] : max;
+    }
+    return max;
+}
+
+static double ven_fmin(const double *values, size_t count) {
+    double min = values[0];
+    for (size_t i = 1; i < count; i++) {
+        min = values[i] < min ? values[i] : min;
+    }
+    return min;
+}
+
+static double ven_fmax(const double *values, size_t count) {
+    double max = values[0];
+    for (size_t i = 1; i < count; i++) {
+        max = values[i] > max ? values[i] : max;
+    }
+    return max;
+}
+"#;
+
+// The division by zero checks, unless `--no-runtime-checks` drops them
+const DIVISION_HELPERS: &str = r#"
+static void ven_div_zero(const char *name, int line) {
+    fflush(stdout);
+    fprintf(stderr, "runtime error: division of '%s' by zero on line %d\n", name, line);
+    exit(1);
+}
+
+static ven_wide ven_divisor(ven_wide y, const char *name, int line) {
+    if (y == 0) {
+        ven_div_zero(name, line);
+    }
+    return y;
+}
+
+static double ven_divisor_f(double y, const char *name, int line) {
+    if (y == 0.0) {
+        ven_div_zero(name, line);
+    }
+    return y;
+}
+
+/* A negative power of zero divides by zero too. */
+static ven_wide ven_exponent(ven_wide x, ven_wide y, const char *name, int line) {
+    if (x == 0 && y < 0) {
+        ven_div_zero(name, line);
+    }
+    return y;
+}
+"#;
+
+// Integer overflow, one body per `--overflow=` mode. `ven_fit` stores a value
+// into the type running from min to max.
+const WRAP_HELPERS: &str = r#"
+static ven_wide ven_fit(ven_wide value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value < min || value > max) {
+        ven_wide span = max - min + 1;
+        value %= span;
+        if (value < min) {
+            value += span;
+        } else if (value > max) {
+            value -= span;
         }
     }
+    return value;
+}
+
+/* Floats never wrap: they are truncated and clamp at the limits. */
+static ven_wide ven_fit_float(double value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value != value) {
+        return 0;
+    }
+    /* Truncate toward zero; doubles past 2^63 are whole already */
+    if (value > -9223372036854775808.0 && value < 9223372036854775808.0) {
+        value = (double)(long long)value;
+    }
+    return value < (double)min ? min : value >= (double)max + 1.0 ? max : (ven_wide)value;
+}
 
-    // Add necessary C headers
+/* A u64 product, which can exceed ven_wide. */
+static uint64_t ven_mul_u64(uint64_t a, uint64_t b, const char *name, int line) {
+    (void)name;
+    (void)line;
+    return a * b;
+}
+"#;
+
+const SATURATE_HELPERS: &str = r#"
+static ven_wide ven_fit(ven_wide value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    return value < min ? min : value > max ? max : value;
+}
+
+static ven_wide ven_fit_float(double value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value != value) {
+        return 0;
+    }
+    /* Truncate toward zero; doubles past 2^63 are whole already */
+    if (value > -9223372036854775808.0 && value < 9223372036854775808.0) {
+        value = (double)(long long)value;
+    }
+    return value < (double)min ? min : value >= (double)max + 1.0 ? max : (ven_wide)value;
+}
+
+/* A u64 product, which can exceed ven_wide. */
+static uint64_t ven_mul_u64(uint64_t a, uint64_t b, const char *name, int line) {
+    (void)name;
+    (void)line;
+    unsigned __int128 product = (unsigned __int128)a * b;
+    return product > UINT64_MAX ? UINT64_MAX : (uint64_t)product;
+}
+"#;
+
+const TRAP_HELPERS: &str = r#"
+static void ven_overflow(const char *name, const char *type, int line) {
+    fflush(stdout);
+    fprintf(stderr, "runtime error: integer overflow in '%s' (%s) on line %d\n", name, type, line);
+    exit(1);
+}
+
+static ven_wide ven_fit(ven_wide value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    if (value < min || value > max) {
+        ven_overflow(name, type, line);
+    }
+    return value;
+}
+
+static ven_wide ven_fit_float(double value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    if (value != value) {
+        return 0;
+    }
+    /* Truncate toward zero; doubles past 2^63 are whole already */
+    if (value > -9223372036854775808.0 && value < 9223372036854775808.0) {
+        value = (double)(long long)value;
+    }
+    if (value < (double)min || value >= (double)max + 1.0) {
+        ven_overflow(name, type, line);
+    }
+    return (ven_wide)value;
+}
+
+/* A u64 product, which can exceed ven_wide. */
+static uint64_t ven_mul_u64(uint64_t a, uint64_t b, const char *name, int line) {
+    unsigned __int128 product = (unsigned __int128)a * b;
+    if (product > UINT64_MAX) {
+        ven_overflow(name, "u64", line);
+    }
+    return (uint64_t)product;
+}
+"#;
+
+// Growth and bounds checks, for programs with arrays
+const ARRAY_HELPERS: &str = r#"
+/* Makes room for one more element; running out of memory ends the program. */
+static void *ven_grow(void *items, size_t *cap, size_t len, size_t size) {
+    if (len < *cap) {
+        return items;
+    }
+    *cap = *cap ? *cap * 2 : 8;
+    items = realloc(items, *cap * size);
+    if (items == NULL) {
+        fflush(stdout);
+        fprintf(stderr, "runtime error: out of memory\n");
+        exit(1);
+    }
+    return items;
+}
+
+/* The position of index in the array name of len elements. */
+static size_t ven_index(ven_wide index, size_t len, const char *name, int line) {
+    if (index < 0 || index >= (ven_wide)len) {
+        fflush(stdout);
+        if (index < 0) {
+            fprintf(stderr, "runtime error: index %lld is out of bounds for '%s' (length %zu) on line %d\n", (long long)index, name, len, line);
+        } else {
+            fprintf(stderr, "runtime error: index %llu is out of bounds for '%s' (length %zu) on line %d\n", (unsigned long long)index, name, len, line);
+        }
+        exit(1);
+    }
+    return (size_t)index;
+}
+"#;
+
+// Hash-table lookups over insertion-ordered entries, for programs with maps.
+// Each entry starts with its key; a slot holds an entry's position plus one.
+const MAP_HELPERS: &str = r#"
+typedef struct {
+    size_t *slots;
+    size_t cap;
+} ven_table;
+
+/* FNV-1a over the bytes of a text key or a number key. */
+static size_t ven_hash(const char *text, int64_t number) {
+    const unsigned char *bytes = text ? (const unsigned char *)text : (const unsigned char *)&number;
+    size_t len = text ? strlen(text) : sizeof number;
+    uint64_t hash = 14695981039346656037ULL;
+    for (size_t i = 0; i < len; i++) {
+        hash = (hash ^ bytes[i]) * 1099511628211ULL;
+    }
+    return (size_t)hash;
+}
+
+/* The slot holding the key, or the empty slot where it would go. */
+static size_t ven_slot(const ven_table *table, const void *items, size_t stride, const char *text, int64_t number) {
+    size_t mask = table->cap - 1;
+    for (size_t at = ven_hash(text, number) & mask;; at = (at + 1) & mask) {
+        size_t entry = table->slots[at];
+        if (entry == 0) {
+            return at;
+        }
+        const char *key = (const char *)items + (entry - 1) * stride;
+        if (text ? strcmp(key, text) == 0 : memcmp(key, &number, sizeof number) == 0) {
+            return at;
+        }
+    }
+}
+
+/* The position of the key among len entries, or len when it is missing. */
+static size_t ven_find(const ven_table *table, const void *items, size_t len, size_t stride, const char *text, int64_t number) {
+    if (table->cap == 0) {
+        return len;
+    }
+    size_t entry = table->slots[ven_slot(table, items, stride, text, number)];
+    return entry == 0 ? len : entry - 1;
+}
+
+/* The position of the key, which must be in the map name. */
+static size_t ven_lookup(const ven_table *table, const void *items, size_t len, size_t stride, const char *text, int64_t number, const char *name, int line) {
+    size_t at = ven_find(table, items, len, stride, text, number);
+    if (at == len) {
+        fflush(stdout);
+        if (text) {
+            fprintf(stderr, "runtime error: key \"%s\" is not in '%s' on line %d\n", text, name, line);
+        } else {
+            fprintf(stderr, "runtime error: key %lld is not in '%s' on line %d\n", (long long)number, name, line);
+        }
+        exit(1);
+    }
+    return at;
+}
+
+/* Indexes the last of len entries, rebuilding the table when it is half full. */
+static void ven_table_add(ven_table *table, const void *items, size_t len, size_t stride, bool text) {
+    size_t first = len - 1;
+    if (len * 2 > table->cap) {
+        free(table->slots);
+        table->cap = table->cap ? table->cap * 2 : 16;
+        table->slots = calloc(table->cap, sizeof *table->slots);
+        if (table->slots == NULL) {
+            fflush(stdout);
+            fprintf(stderr, "runtime error: out of memory\n");
+            exit(1);
+        }
+        first = 0;
+    }
+    for (size_t i = first; i < len; i++) {
+        const char *key = (const char *)items + i * stride;
+        int64_t number = 0;
+        if (!text) {
+            memcpy(&number, key, sizeof number);
+        }
+        table->slots[ven_slot(table, items, stride, text ? key : NULL, number)] = i + 1;
+    }
+}
+"#;
+
+pub fn transpile_c(ast: &AST, options: &CodegenOptions) -> Result<String, String> {
+    let mut code = String::with_capacity(1024);
+    let vars = collect_var_types(ast);
+    let var_types: HashMap<String, VarType> = vars.iter().cloned().collect();
+
+    let AST::Program(nodes) = ast;
+    let math = uses_math(nodes, &var_types);
+
+    code.push_str("// Generated by the Ven compiler\n");
+    code.push_str("#include <ctype.h>\n");
+    code.push_str("#include <errno.h>\n");
+    if math {
+        code.push_str("#include <math.h>\n");
+    }
+    code.push_str("#include <stdbool.h>\n");
+    code.push_str("#include <stdint.h>\n");
     code.push_str("#include <stdio.h>\n");
     code.push_str("#include <stdlib.h>\n");
-    // Added string.h for trimming newline from input.
-    code.push_str("#include <string.h>\n");
-    code.push_str("\nint main() {\n");
-
-    match ast {
-        AST::Program(nodes) => {
-            for node in nodes {
-                match node {
-                    // Variable Declarations
-                    ASTNode::VarDeclaration {
-                        mutable: _, // Mutability is ignored in C
-                        name,
-                        var_type,
-                        value,
-                    } => {
-                        let c_type = match var_type {
-                            VarType::Int => "int",
-                            VarType::String => "char*",
-                            VarType::Char { .. } => "char",
-                            VarType::Float => "float",
-                        };
-
-                        let value_str = match value {
-                            Some(Expression::Literal(val)) => format!(" = {}", val),
-                            Some(Expression::Identifier(val)) => format!(" = {}", val),
-                            Some(Expression::BinaryOp { .. }) => String::new(), // Complex initialization would need separate statements
-                            Some(Expression::LogicalOp { .. }) => String::new(), // Complex initialization would need separate statements
-                            None => String::new(),
-                        };
-
-                        // Allocate memory for strings
-                        if let VarType::String = var_type {
-                            code.push_str(&format!("    {} {} = malloc(256);\n", c_type, name));
-                        } else {
-                            code.push_str(&format!("    {} {}{};\n", c_type, name, value_str));
-                        }
-                    }
+    code.push_str("#include <string.h>\n\n");
+    code.push_str(RUNTIME_HELPERS);
+    code.push_str(match options.overflow {
+        Overflow::Wrap => WRAP_HELPERS,
+        Overflow::Saturate => SATURATE_HELPERS,
+        Overflow::Trap => TRAP_HELPERS,
+    });
+    if vars.iter().any(|(_, var_type)| matches!(var_type, VarType::Array(_) | VarType::Map(..))) {
+        code.push_str(ARRAY_HELPERS);
+    }
+    if vars.iter().any(|(_, var_type)| matches!(var_type, VarType::Map(..))) {
+        code.push_str(MAP_HELPERS);
+    }
+    if math {
+        code.push_str(MATH_HELPERS);
+    }
+    if options.runtime_checks && uses_division(nodes) {
+        code.push_str(DIVISION_HELPERS);
+    }
+    if uses_conversions(nodes, &var_types) {
+        code.push_str(CONVERT_HELPERS);
+    }
+    if uses_strings(nodes, &var_types) {
+        code.push_str(STRING_HELPERS);
+    }
+    code.push('\n');
 
-                    // Input Handling with newline trimming.
-                    ASTNode::Input { name } => {
-                        code.push_str(&format!("    fgets({}, 256, stdin);\n", name));
-                        // Trim newline if present.
-                        code.push_str(&format!("    size_t len = strlen({});\n", name));
-                        code.push_str(&format!("    if(len > 0 && {}[len-1] == '\\n') ", name));
-                        code.push_str(&format!("{}[len-1] = '\\0';\n", name));
-                    }
+    // Each record type becomes a struct with one member per field
+    for record in record_types(ast) {
+        code.push_str(&format!("struct {} {{\n", record.name));
+        for (field, var_type) in &record.fields {
+            code.push_str(&format!("    {};\n", c_member(var_type, field)));
+        }
+        code.push_str("};\n\n");
+    }
 
-                    // Print Statements with type detection
-                    ASTNode::Print { to_stderr, expr } => {
-                        let output_target = if *to_stderr { "stderr" } else { "stdout" };
-
-                        match expr {
-                            Some(Expression::Literal(text)) => {
-                                let mut format_string = String::new();
-                                let mut var_references = Vec::new();
-                                let mut current_var = String::new();
-                                let mut in_var = false;
-
-                                // Process the literal to extract interpolation placeholders.
-                                for c in text.chars() {
-                                    if c == '{' {
-                                        in_var = true;
-                                        continue;
-                                    } else if c == '}' {
-                                        in_var = false;
-                                        if !current_var.is_empty() {
-                                            // Determine the format specifier based on the variable type.
-                                            let format_spec = match var_types.get(&current_var) {
-                                                Some(VarType::Int) => "%d",
-                                                Some(VarType::Float) => "%f",
-                                                Some(VarType::String) => "%s",
-                                                Some(VarType::Char { .. }) => "%c",
-                                                None => "%d", // Default fallback.
-                                            };
-                                            format_string.push_str(format_spec);
-                                            var_references.push(current_var.clone());
-                                            current_var.clear();
-                                        }
-                                        continue;
-                                    }
-
-                                    if in_var {
-                                        current_var.push(c);
-                                    } else {
-                                        format_string.push(c);
-                                    }
-                                }
-
-                                // Build argument list for fprintf.
-                                let mut args = String::new();
-                                for var in var_references {
-                                    args.push_str(&format!(", {}", var));
-                                }
-
-                                code.push_str(&format!(
-                                    "    fprintf({}, \"{}\"{});\n",
-                                    output_target, format_string, args
-                                ));
-                            }
-                            Some(Expression::Identifier(val)) => {
-                                // Look up the variable type.
-                                let format_spec = match var_types.get(val) {
-                                    Some(VarType::Int) => "%d",
-                                    Some(VarType::Float) => "%f",
-                                    Some(VarType::String) => "%s",
-                                    Some(VarType::Char { .. }) => "%c",
-                                    None => "%s", // default fallback.
-                                };
-                                code.push_str(&format!(
-                                    "    fprintf({}, \"{}\", {});\n",
-                                    output_target, format_spec, val
-                                ));
+    // Ven variables are program-wide, so every name becomes a global
+    for (name, var_type) in &vars {
+        let declaration = match var_type {
+            VarType::Int(int_type) => format!("{} {} = 0;", c_type(*int_type), name),
+            VarType::Float => format!("double {} = 0.0;", name),
+            VarType::String => format!("char {}[{}] = \"\";", name, STR_SIZE),
+            VarType::Char { size } => format!("char {}[{}] = \"\";", name, size + 1),
+            VarType::Bool => format!("bool {} = false;", name),
+            VarType::Array(element) => format!("struct {{ {}; size_t len, cap; }} {} = {{0}};", c_element(element), name),
+            VarType::Map(key, value) => format!(
+                "struct {{ struct {{ {}; {}; }} *items; size_t len, cap; ven_table table; }} {} = {{0}};",
+                c_member(key, "key"),
+                c_member(value, "value"),
+                name
+            ),
+            VarType::Record(record) => format!("struct {} {} = {{0}};", record.name, name),
+        };
+        code.push_str(&declaration);
+        code.push('\n');
+    }
+    if !vars.is_empty() {
+        code.push('\n');
+    }
+
+    code.push_str("int main(void) {\n");
+    emit_nodes(&mut code, nodes, 1, &var_types, options.overflow, options.runtime_checks)?;
+    code.push_str("    return 0;\n}\n");
+
+    code.shrink_to_fit();
+    Ok(code)
+}
+
+/// Emits `nodes`, or names the first construct that cannot be translated.
+fn emit_nodes(
+    code: &mut String,
+    nodes: &[ASTNode],
+    depth: usize,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    checks: bool,
+) -> Result<(), String> {
+    let indent = "    ".repeat(depth);
+    for node in nodes {
+        // Named by the runtime errors the statement can stop the program with
+        let line = node.line().unwrap_or(0);
+        match node {
+            ASTNode::VarDeclaration { name, var_type, value, .. } => {
+                let statement = match (var_type, value) {
+                    // Declaration literals arrive with their quotes already stripped
+                    (VarType::String | VarType::Char { .. }, Some(Expression::Literal(lit))) => {
+                        format!("ven_copy({}, {}, sizeof {});", name, c_string(&literal_text(lit)), name)
+                    }
+                    (VarType::String | VarType::Char { .. }, Some(expr))
+                        if value_kind(expr, var_types) == ValueKind::Str =>
+                    {
+                        format!("ven_copy({}, {}, sizeof {});", name, c_expression(expr, var_types, line), name)
+                    }
+                    (VarType::String | VarType::Char { .. }, _) => format!("{}[0] = '\\0';", name),
+                    (VarType::Int(int_type), Some(expr)) => {
+                        format!("{} = {};", name, c_int_value(name, *int_type, expr, var_types, overflow, line))
+                    }
+                    // Emptied, then filled one element at a time
+                    (VarType::Array(element), value) => {
+                        code.push_str(&format!("{}{}.len = 0;\n", indent, name));
+                        if let Some(Expression::List(items)) = value {
+                            for item in items {
+                                code.push_str(&c_append(&indent, name, element, item, var_types, overflow, line));
                             }
-                            Some(Expression::BinaryOp { .. }) | Some(Expression::LogicalOp { .. }) => {
-                                // Handle complex expressions - would need evaluation
-                                code.push_str(&format!("    fprintf({}, \"Complex expression\");\n", output_target));
+                        }
+                        continue;
+                    }
+                    // Maps always start empty; the old table is freed when the first key is added
+                    (VarType::Map(..), _) => format!("{}.len = 0;\n{}{}.table.cap = 0;", name, indent, name),
+                    // Filled one field at a time, each stored like a variable of the field type
+                    (VarType::Record(record), Some(Expression::Record(values))) => {
+                        for ((field, field_type), (_, value)) in record.fields.iter().zip(values) {
+                            let target = format!("{}.{}", name, field);
+                            let statement = c_store(&target, &target, field_type, value, var_types, overflow, line);
+                            code.push_str(&format!("{}{}\n", indent, statement));
+                        }
+                        continue;
+                    }
+                    (VarType::Record(record), _) => format!("{} = (struct {}){{0}};", name, record.name),
+                    (_, Some(expr)) => format!("{} = {};", name, c_value(expr, var_type, var_types, line)),
+                    (_, None) => format!("{} = {};", name, default_value(var_type)),
+                };
+                code.push_str(&format!("{}{}\n", indent, statement));
+            }
+            ASTNode::Input { name, .. } => match var_types.get(name) {
+                Some(VarType::String) => {
+                    code.push_str(&format!("{}ven_read_line({}, sizeof {});\n", indent, name, name));
+                }
+                // Cut to the buffer like any other text stored into it
+                Some(VarType::Char { .. }) => {
+                    code.push_str(&format!("{}ven_read_line(ven_line, sizeof ven_line);\n", indent));
+                    code.push_str(&format!("{}ven_copy({}, ven_line, sizeof {});\n", indent, name, name));
+                }
+                var_type => {
+                    code.push_str(&format!("{}ven_read_line(ven_line, sizeof ven_line);\n", indent));
+                    let value = match var_type {
+                        Some(VarType::Int(int_type)) => c_fit(name, *int_type, "ven_to_int(ven_line)", line),
+                        Some(VarType::Float) => "ven_to_float(ven_line)".to_string(),
+                        Some(VarType::Bool) => "ven_to_bool(ven_line)".to_string(),
+                        // Undeclared target: the line is consumed and discarded
+                        _ => continue,
+                    };
+                    code.push_str(&format!("{}{} = {};\n", indent, name, value));
+                }
+            },
+            ASTNode::Print { to_stderr, expr, .. } => {
+                let stream = if *to_stderr { "stderr" } else { "stdout" };
+                emit_print(code, &indent, stream, expr.as_ref(), var_types, line);
+            }
+            ASTNode::MathOp { name, field, operator, operand, .. } => {
+                let var_type = target_type(name, field.as_deref(), var_types);
+                // A field is changed like a variable of the field type
+                let name = &match field {
+                    Some(field) => format!("{}.{}", name, field),
+                    None => name.clone(),
+                };
+                let divides = checks && matches!(operator, MathOperator::Divide | MathOperator::Modulo);
+                let value = match (var_type, value_kind(operand, var_types)) {
+                    // The operand is stored into the variable's type first, then the
+                    // exact result is; integer division truncates toward zero
+                    (Some(VarType::Int(int_type)), ValueKind::Int | ValueKind::Bool) => {
+                        let mut operand = c_int_value(name, *int_type, operand, var_types, overflow, line);
+                        if divides {
+                            operand = format!("ven_divisor({}, \"{}\", {})", operand, name, line);
+                        } else if checks && *operator == MathOperator::Power {
+                            operand = format!("ven_exponent({}, {}, \"{}\", {})", name, operand, name, line);
+                        }
+                        match operator {
+                            MathOperator::Multiply if *int_type == IntType::U64 => {
+                                format!("ven_mul_u64({}, {}, \"{}\", {})", name, operand, name, line)
                             }
-                            None => {
-                                code.push_str(&format!("    fprintf({}, \"\");\n", output_target));
+                            MathOperator::Power => {
+                                c_fit(name, *int_type, &format!("ven_pow({}, {})", name, operand), line)
                             }
+                            _ => c_fit(name, *int_type, &c_math(&format!("(ven_wide){}", name), operator, &operand), line),
+                        }
+                    }
+                    (Some(var_type @ (VarType::Int(_) | VarType::Float)), _) => {
+                        let mut operand = c_number(operand, var_types, line);
+                        if divides {
+                            operand = format!("ven_divisor_f({}, \"{}\", {})", operand, name, line);
+                        }
+                        match var_type {
+                            VarType::Int(int_type) => c_fit_float(
+                                name,
+                                *int_type,
+                                &c_float_math(&format!("(double){}", name), operator, &operand),
+                                line,
+                            ),
+                            _ => c_float_math(name, operator, &operand),
                         }
                     }
+                    (Some(_), _) => return Err(format!("math on the non-numeric variable '{}'", name)),
+                    (None, _) => return Err(format!("math on the undeclared variable '{}'", name)),
+                };
+                code.push_str(&format!("{}{} = {};\n", indent, name, value));
+            }
+            ASTNode::If { condition, body, .. } => {
+                code.push_str(&format!("{}if {} {{\n", indent, c_operand(condition, var_types, line)));
+                emit_nodes(code, body, depth + 1, var_types, overflow, checks)?;
+                code.push_str(&format!("{}}}\n", indent));
+            }
+            ASTNode::SetIndex { name, index, value, .. } if let Some((key, element)) = map_types(name, var_types) => {
+                // The key is copied first, as storing the value may move the entries
+                let text = type_kind(key) == ValueKind::Str;
+                code.push_str(&format!("{}{{\n", indent));
+                if text {
+                    code.push_str(&format!("{}    char ven_key[{}];\n", indent, STR_SIZE));
+                    code.push_str(&format!(
+                        "{}    ven_copy(ven_key, {}, sizeof ven_key);\n",
+                        indent,
+                        c_key(name, key, index, var_types, line)
+                    ));
+                } else {
+                    code.push_str(&format!("{}    int64_t ven_key = {};\n", indent, c_key(name, key, index, var_types, line)));
+                }
+                let (text_key, number_key) = if text { ("ven_key", "0") } else { ("NULL", "ven_key") };
+                code.push_str(&format!(
+                    "{}    size_t ven_at = ven_find(&{}.table, {}.items, {}.len, sizeof *{}.items, {}, {});\n",
+                    indent, name, name, name, name, text_key, number_key
+                ));
+                // A new key is only added once its value is stored
+                code.push_str(&format!("{}    if (ven_at == {}.len) {{\n", indent, name));
+                code.push_str(&format!(
+                    "{}        {}.items = ven_grow({}.items, &{}.cap, {}.len, sizeof *{}.items);\n",
+                    indent, name, name, name, name, name
+                ));
+                code.push_str(&format!("{}    }}\n", indent));
+                let target = format!("{}.items[ven_at].value", name);
+                let statement = c_store(&target, name, element, value, var_types, overflow, line);
+                code.push_str(&format!("{}    {}\n", indent, statement));
+                code.push_str(&format!("{}    if (ven_at == {}.len) {{\n", indent, name));
+                let store_key = if text {
+                    format!("memcpy({}.items[ven_at].key, ven_key, sizeof ven_key);", name)
+                } else {
+                    format!("{}.items[ven_at].key = ven_key;", name)
+                };
+                code.push_str(&format!("{}        {}\n", indent, store_key));
+                code.push_str(&format!("{}        {}.len++;\n", indent, name));
+                code.push_str(&format!(
+                    "{}        ven_table_add(&{}.table, {}.items, {}.len, sizeof *{}.items, {});\n",
+                    indent, name, name, name, name, text
+                ));
+                code.push_str(&format!("{}    }}\n", indent));
+                code.push_str(&format!("{}}}\n", indent));
+            }
+            ASTNode::SetIndex { name, index, value, .. } => {
+                let Some(element) = element_type(name, var_types) else {
+                    return Err(format!("the undeclared array '{}'", name));
+                };
+                // The bounds are checked before the value is converted
+                code.push_str(&format!("{}{{\n", indent));
+                code.push_str(&format!(
+                    "{}    size_t ven_at = ven_index({}, {}.len, \"{}\", {});\n",
+                    indent,
+                    c_expression(index, var_types, line),
+                    name,
+                    name,
+                    line
+                ));
+                let target = format!("{}.items[ven_at]", name);
+                let statement = c_store(&target, name, element, value, var_types, overflow, line);
+                code.push_str(&format!("{}    {}\n", indent, statement));
+                code.push_str(&format!("{}}}\n", indent));
+            }
+            ASTNode::Append { name, value, .. } => {
+                let Some(element) = element_type(name, var_types) else {
+                    return Err(format!("the undeclared array '{}'", name));
+                };
+                code.push_str(&c_append(&indent, name, element, value, var_types, overflow, line));
+            }
+            ASTNode::ForEach { item, array, body } => {
+                // The length is read before each pass, so appended elements are visited
+                let counter = format!("ven_loop{}", depth);
+                code.push_str(&format!(
+                    "{}for (size_t {} = 0; {} < {}.len; {}++) {{\n",
+                    indent, counter, counter, array, counter
+                ));
+                let element = format!("{}.items[{}]", array, counter);
+                let statement = match var_types.get(item) {
+                    Some(VarType::String) => format!("ven_copy({}, {}, sizeof {});", item, element, item),
+                    _ => format!("{} = {};", item, element),
+                };
+                code.push_str(&format!("{}    {}\n", indent, statement));
+                emit_nodes(code, body, depth + 1, var_types, overflow, checks)?;
+                code.push_str(&format!("{}}}\n", indent));
+            }
+            // Emitted as a struct before the globals
+            ASTNode::RecordDeclaration(_) => {}
+            // Rejected by check_support
+            ASTNode::Call { .. } => {}
+        }
+    }
+    Ok(())
+}
 
-                    // Math Operations
-                    ASTNode::MathOp {
-                        name,
-                        operator,
-                        operand,
-                    } => {
-                        let op = match operator {
-                            MathOperator::Add => "+",
-                            MathOperator::Subtract => "-",
-                            MathOperator::Multiply => "*",
-                            MathOperator::Divide => "/",
-                        };
-
-                        let operand_str = match operand {
-                            Expression::Literal(val) => val.clone(),
-                            Expression::Identifier(val) => {
-                                let unique_operand_var = format!("{}_{}", val, temp_var_counter);
-                                temp_var_counter += 1;
-                                code.push_str(&format!(
-                                    "    int {} = {};\n",
-                                    unique_operand_var, val
-                                ));
-                                unique_operand_var
-                            }
-                            Expression::BinaryOp { .. } | Expression::LogicalOp { .. } => {
-                                // Handle complex expressions - for simplicity we're using a placeholder
-                                // In a real implementation, you'd want to evaluate these
-                                "0".to_string()
-                            }
-                        };
+/// The `items` member of an array of `element`s.
+fn c_element(element: &VarType) -> String {
+    match element {
+        VarType::Int(int_type) => format!("{} *items", c_type(*int_type)),
+        VarType::Float => "double *items".to_string(),
+        VarType::Bool => "bool *items".to_string(),
+        _ => format!("char (*items)[{}]", STR_SIZE),
+    }
+}
 
-                        let unique_new_var = format!("{}_{}", name, temp_var_counter);
-                        temp_var_counter += 1;
+/// A struct member of `var_type` called `member`, for a map entry.
+fn c_member(var_type: &VarType, member: &str) -> String {
+    match var_type {
+        VarType::Int(int_type) => format!("{} {}", c_type(*int_type), member),
+        VarType::Float => format!("double {}", member),
+        VarType::Bool => format!("bool {}", member),
+        VarType::Char { size } => format!("char {}[{}]", member, size + 1),
+        _ => format!("char {}[{}]", member, STR_SIZE),
+    }
+}
 
-                        // Perform operation
-                        code.push_str(&format!(
-                            "    int {} = {} {} {};\n",
-                            unique_new_var, name, op, operand_str
-                        ));
+/// The key `expr` of the map `name`, as text or as an `int64_t`. A literal
+/// that does not fit is left to the runtime check.
+fn c_key(name: &str, key_type: &VarType, expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match key_type {
+        VarType::Int(int_type) => c_int_value(name, *int_type, expr, var_types, Overflow::Trap, line),
+        _ => c_expression(expr, var_types, line),
+    }
+}
 
-                        // Store the result
-                        code.push_str(&format!("    {} = {};\n", name, unique_new_var));
-                    }
+/// The position of the key `expr` in the map `name`; `lookup` stops the
+/// program when it is missing.
+fn c_find(name: &str, key: &Expression, lookup: bool, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let Some((key_type, _)) = map_types(name, var_types) else {
+        return "0".to_string();
+    };
+    let value = c_key(name, key_type, key, var_types, line);
+    let (text, number) = if type_kind(key_type) == ValueKind::Str {
+        (value, "0".to_string())
+    } else {
+        ("NULL".to_string(), value)
+    };
+    if lookup {
+        format!(
+            "ven_lookup(&{}.table, {}.items, {}.len, sizeof *{}.items, {}, {}, \"{}\", {})",
+            name, name, name, name, text, number, name, line
+        )
+    } else {
+        format!("ven_find(&{}.table, {}.items, {}.len, sizeof *{}.items, {}, {})", name, name, name, name, text, number)
+    }
+}
 
-                    // Conditional expressions
-                    ASTNode::If { condition, body } => {
-                        code.push_str("    if (");
-                        code.push_str(&translate_condition_to_c(condition));
-                        code.push_str(") {\n");
-
-                        // Translate body statements
-                        for statement in body {
-                            match statement {
-                                ASTNode::Print { to_stderr, expr } => {
-                                    let output_target = if *to_stderr { "stderr" } else { "stdout" };
-                                    
-                                    match expr {
-                                        Some(Expression::Literal(text)) => {
-                                            code.push_str(&format!(
-                                                "        fprintf({}, \"{}\");\n",
-                                                output_target, text
-                                            ));
-                                        }
-                                        Some(Expression::Identifier(val)) => {
-                                            let format_spec = match var_types.get(val) {
-                                                Some(VarType::Int) => "%d",
-                                                Some(VarType::Float) => "%f",
-                                                Some(VarType::String) => "%s",
-                                                Some(VarType::Char { .. }) => "%c",
-                                                None => "%s",
-                                            };
-                                            code.push_str(&format!(
-                                                "        fprintf({}, \"{}\", {});\n",
-                                                output_target, format_spec, val
-                                            ));
-                                        }
-                                        Some(_) => {
-                                            code.push_str(&format!("        fprintf({}, \"Complex expression\");\n", output_target));
-                                        }
-                                        None => {
-                                            code.push_str(&format!("        fprintf({}, \"\");\n", output_target));
-                                        }
-                                    }
-                                }
-                                ASTNode::VarDeclaration { .. } => {
-                                    // Handle variable declarations inside the if block
-                                    // This would be similar to the main handling but with more indentation
-                                }
-                                ASTNode::MathOp { .. } => {
-                                    // Handle math operations inside the if block
-                                    // This would be similar to the main handling but with more indentation
-                                }
-                                ASTNode::Input { .. } => {
-                                    // Handle input statements inside the if block
-                                    // This would be similar to the main handling but with more indentation
-                                }
-                                ASTNode::If { .. } => {
-                                    // Handle nested if statements
-                                    // This would require recursive handling
-                                }
-                            }
-                        }
+/// The statements that append `value` to the array `name`.
+fn c_append(
+    indent: &str,
+    name: &str,
+    element: &VarType,
+    value: &Expression,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    line: usize,
+) -> String {
+    let target = format!("{}.items[{}.len]", name, name);
+    format!(
+        "{}{}.items = ven_grow({}.items, &{}.cap, {}.len, sizeof *{}.items);\n{}{}\n{}{}.len++;\n",
+        indent,
+        name,
+        name,
+        name,
+        name,
+        name,
+        indent,
+        c_store(&target, name, element, value, var_types, overflow, line),
+        indent,
+        name
+    )
+}
 
-                        code.push_str("    }\n");
-                    }
+/// Stores `value` into `target`, an element of the array `name`.
+fn c_store(
+    target: &str,
+    name: &str,
+    element: &VarType,
+    value: &Expression,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    line: usize,
+) -> String {
+    match element {
+        VarType::Int(int_type) => format!("{} = {};", target, c_int_value(name, *int_type, value, var_types, overflow, line)),
+        VarType::String | VarType::Char { .. } => {
+            let text = match value {
+                Expression::Literal(lit) => c_string(&literal_text(lit)),
+                _ if value_kind(value, var_types) == ValueKind::Str => c_expression(value, var_types, line),
+                _ => "\"\"".to_string(),
+            };
+            format!("ven_copy({}, {}, sizeof {});", target, text, target)
+        }
+        _ => format!("{} = {};", target, c_value(value, element, var_types, line)),
+    }
+}
+
+/// Emits a print as a run of `fputs` calls, the newline joined to the last text.
+fn emit_print(
+    code: &mut String,
+    indent: &str,
+    stream: &str,
+    expr: Option<&Expression>,
+    var_types: &HashMap<String, VarType>,
+    line: usize,
+) {
+    let mut text = String::new();
+    let flush = |code: &mut String, text: &mut String| {
+        if !text.is_empty() {
+            code.push_str(&format!("{}fputs({}, {});\n", indent, c_string(text), stream));
+            text.clear();
+        }
+    };
+    let value = |code: &mut String, text: &mut String, expr: &Expression| {
+        // A whole record prints as {field: value, field: value}
+        if let Expression::Identifier(name) = expr
+            && let Some(VarType::Record(record)) = var_types.get(name)
+        {
+            text.push('{');
+            for (i, (field, field_type)) in record.fields.iter().enumerate() {
+                if i > 0 {
+                    text.push_str(", ");
                 }
+                text.push_str(&format!("{}: ", field));
+                flush(code, text);
+                let unsigned = *field_type == VarType::Int(IntType::U64);
+                let call = print_call(stream, &format!("{}.{}", name, field), type_kind(field_type), unsigned);
+                code.push_str(&format!("{}{};\n", indent, call));
             }
+            text.push('}');
+            return;
+        }
+        // A whole map prints as {key: value, key: value}
+        if let Expression::Identifier(name) = expr
+            && let Some((key, element)) = map_types(name, var_types)
+        {
+            text.push('{');
+            flush(code, text);
+            code.push_str(&format!("{}for (size_t ven_i = 0; ven_i < {}.len; ven_i++) {{\n", indent, name));
+            code.push_str(&format!("{}    if (ven_i > 0) {{\n", indent));
+            code.push_str(&format!("{}        fputs(\", \", {});\n", indent, stream));
+            code.push_str(&format!("{}    }}\n", indent));
+            let entry = format!("{}.items[ven_i]", name);
+            let call = print_call(stream, &format!("{}.key", entry), type_kind(key), false);
+            code.push_str(&format!("{}    {};\n", indent, call));
+            code.push_str(&format!("{}    fputs(\": \", {});\n", indent, stream));
+            let unsigned = *element == VarType::Int(IntType::U64);
+            let call = print_call(stream, &format!("{}.value", entry), type_kind(element), unsigned);
+            code.push_str(&format!("{}    {};\n", indent, call));
+            code.push_str(&format!("{}}}\n", indent));
+            text.push('}');
+            return;
+        }
+        // A whole array prints as [a, b, c]
+        if let Expression::Identifier(name) = expr
+            && let Some(element) = element_type(name, var_types)
+        {
+            text.push('[');
+            flush(code, text);
+            code.push_str(&format!("{}for (size_t ven_i = 0; ven_i < {}.len; ven_i++) {{\n", indent, name));
+            code.push_str(&format!("{}    if (ven_i > 0) {{\n", indent));
+            code.push_str(&format!("{}        fputs(\", \", {});\n", indent, stream));
+            code.push_str(&format!("{}    }}\n", indent));
+            let item = format!("{}.items[ven_i]", name);
+            let unsigned = *element == VarType::Int(IntType::U64);
+            let call = print_call(stream, &item, type_kind(element), unsigned);
+            code.push_str(&format!("{}    {};\n", indent, call));
+            code.push_str(&format!("{}}}\n", indent));
+            text.push(']');
+            return;
         }
+        flush(code, text);
+        let value = c_expression(expr, var_types, line);
+        let call = print_call(stream, &value, value_kind(expr, var_types), is_u64(expr, var_types));
+        code.push_str(&format!("{}{};\n", indent, call));
+    };
+
+    match expr {
+        Some(Expression::Literal(literal)) => {
+            for segment in split_interpolation(literal) {
+                match segment {
+                    Segment::Text(segment) => text.push_str(&segment),
+                    Segment::Var(name) => match placeholder(&name, var_types) {
+                        Some(expr) => value(code, &mut text, &expr),
+                        None => text.push_str("[undefined]"),
+                    },
+                    Segment::Formatted(name, spec) => match formatted(&name, var_types) {
+                        Some(expr) => {
+                            flush(code, &mut text);
+                            let kind = value_kind(&expr, var_types);
+                            let value = match (kind, spec.precision) {
+                                (ValueKind::Float, Some(precision)) => format!(
+                                    "ven_fixed_text((char[400]){{0}}, {}, {})",
+                                    c_expression(&expr, var_types, line),
+                                    precision
+                                ),
+                                _ => c_text(&expr, var_types, line),
+                            };
+                            let right = spec.right(matches!(kind, ValueKind::Int | ValueKind::Float));
+                            code.push_str(&format!(
+                                "{}ven_pad({}, {}, {}, {});\n",
+                                indent, stream, value, spec.width, right
+                            ));
+                        }
+                        None => text.push_str("[undefined]"),
+                    },
+                }
+            }
+        }
+        Some(expr @ Expression::Identifier(name)) if var_types.contains_key(name) => value(code, &mut text, expr),
+        // Bare words are printed as written
+        Some(Expression::Identifier(word)) => text.push_str(&literal_text(word)),
+        Some(cond) => value(code, &mut text, cond),
+        None => {}
     }
+    text.push('\n');
+    flush(code, &mut text);
+}
 
-    code.push_str("    return 0;\n}\n");
-    code.shrink_to_fit();
-    code
+/// A call printing `value`, a C expression of the given kind.
+fn print_call(stream: &str, value: &str, kind: ValueKind, unsigned: bool) -> String {
+    match kind {
+        ValueKind::Int if unsigned => format!("fprintf({}, \"%llu\", (unsigned long long){})", stream, value),
+        ValueKind::Int => format!("fprintf({}, \"%lld\", (long long){})", stream, value),
+        ValueKind::Float => format!("ven_print_float({}, {})", stream, value),
+        ValueKind::Str => format!("fputs({}, {})", value, stream),
+        ValueKind::Bool => format!("fputs({} ? \"true\" : \"false\", {})", value, stream),
+    }
 }
 
-/// Translate a condition expression to C code
-fn translate_condition_to_c(expr: &Expression) -> String {
+/// A C string literal. A `?` after another is escaped so that no trigraph forms.
+fn c_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    let mut previous = '\0';
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '?' if previous == '?' => quoted.push_str("\\?"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\{:03o}", c as u32)),
+            _ => quoted.push(c),
+        }
+        previous = c;
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn default_value(var_type: &VarType) -> &'static str {
+    match var_type {
+        VarType::Int(_) => "0",
+        VarType::Float => "0.0",
+        VarType::String | VarType::Char { .. } => "\"\"",
+        VarType::Bool => "false",
+        VarType::Array(_) | VarType::Map(..) | VarType::Record(_) => "{0}",
+    }
+}
+
+fn c_expression(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::Literal(lit) => {
+            if lit.starts_with('"') || lit.starts_with('\'') {
+                return c_string(&literal_text(lit));
+            }
+            match value_kind(expr, var_types) {
+                ValueKind::Int => c_int(int_literal(lit).unwrap_or(0)),
+                ValueKind::Float | ValueKind::Bool => clean_number(lit),
+                ValueKind::Str => c_string(lit),
+            }
+        }
+        Expression::Identifier(name) if var_types.contains_key(name) => name.clone(),
+        Expression::Identifier(name) => undeclared(name),
+        Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) => {
+            c_condition(expr, var_types, line)
+        }
+        Expression::Index { name, index } if map_types(name, var_types).is_some() => {
+            format!("{}.items[{}].value", name, c_find(name, index, true, var_types, line))
+        }
+        Expression::In { key, name } => format!("({} < {}.len)", c_find(name, key, false, var_types, line), name),
+        Expression::Index { name, index } => format!(
+            "{}.items[ven_index({}, {}.len, \"{}\", {})]",
+            name,
+            c_expression(index, var_types, line),
+            name,
+            name,
+            line
+        ),
+        Expression::Len(name) if is_text_var(name, var_types) => format!("ven_len({})", name),
+        Expression::Len(name) => format!("(int64_t){}.len", name),
+        Expression::Field { name, field } if field_type(name, field, var_types).is_some() => {
+            format!("{}.{}", name, field)
+        }
+        Expression::Field { .. } => "0".to_string(),
+        // Only found in array, map and record declarations
+        Expression::List(_) | Expression::Record(_) => "0".to_string(),
+        Expression::Convert { to, value } => {
+            let text = c_expression(value, var_types, line);
+            match (to, value_kind(value, var_types)) {
+                (VarType::String, _) => c_text(value, var_types, line),
+                (VarType::Float, ValueKind::Str) => format!("ven_convert_float({}, {})", text, line),
+                (VarType::Float, ValueKind::Float) => text,
+                (VarType::Float, _) => format!("(double){}", text),
+                (_, ValueKind::Str) => format!("ven_convert_int({}, {})", text, line),
+                (_, ValueKind::Float) => format!("ven_float_to_int({})", text),
+                (_, ValueKind::Int) => text,
+                (_, ValueKind::Bool) => format!("(int64_t){}", text),
+            }
+        }
+        Expression::Text { op, args } => {
+            let values: Vec<String> = args.iter().map(|arg| c_expression(arg, var_types, line)).collect();
+            let buf = format!("(char[{}]){{0}}", STR_SIZE);
+            match op {
+                TextOp::Concat => format!(
+                    "ven_concat({}, (const char *[]){{{}}}, {})",
+                    buf,
+                    values.join(", "),
+                    values.len()
+                ),
+                TextOp::Len => format!("ven_len({})", values[0]),
+                TextOp::Slice => format!("ven_slice({}, {}, {}, {})", buf, values[0], values[1], values[2]),
+                TextOp::Upper => format!("ven_case({}, {}, true)", buf, values[0]),
+                TextOp::Lower => format!("ven_case({}, {}, false)", buf, values[0]),
+                TextOp::Trim => format!("ven_trim({}, {})", buf, values[0]),
+                TextOp::Contains => format!("(strstr({}, {}) != NULL)", values[0], values[1]),
+                TextOp::StartsWith => format!("ven_starts_with({}, {})", values[0], values[1]),
+                TextOp::Replace => format!("ven_replace({}, {}, {}, {})", buf, values[0], values[1], values[2]),
+            }
+        }
+        Expression::Math { op, args } => c_math_fn(*op, args, var_types, line),
+    }
+}
+
+/// `x op y` for integers; `**` is left to `ven_pow`.
+fn c_math(x: &str, operator: &MathOperator, y: &str) -> String {
+    let op = match operator {
+        MathOperator::Add => "+",
+        MathOperator::Subtract => "-",
+        MathOperator::Multiply => "*",
+        MathOperator::Divide => "/",
+        MathOperator::Modulo => "%",
+        MathOperator::Power => return format!("ven_pow({}, {})", x, y),
+    };
+    format!("{} {} {}", x, op, y)
+}
+
+/// `x op y` for doubles, where `%` and `**` come from `<math.h>`.
+fn c_float_math(x: &str, operator: &MathOperator, y: &str) -> String {
+    match operator {
+        MathOperator::Modulo => format!("fmod({}, {})", x, y),
+        MathOperator::Power => format!("pow({}, {})", x, y),
+        _ => c_math(x, operator, y),
+    }
+}
+
+/// A math function or `-x`. Integer arguments are stored into an `i64`
+/// first, and so is the integer result.
+fn c_math_fn(op: MathFn, args: &[Expression], var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let name = op.called();
+    if math_kind(op, args, var_types) == ValueKind::Int {
+        let values: Vec<String> = args
+            .iter()
+            .map(|arg| {
+                let value = c_expression(arg, var_types, line);
+                if is_u64(arg, var_types) { c_fit(name, IntType::I64, &format!("(ven_wide){}", value), line) } else { value }
+            })
+            .collect();
+        return match op {
+            MathFn::Negate => c_fit(name, IntType::I64, &format!("-(ven_wide){}", values[0]), line),
+            MathFn::Abs => c_fit(name, IntType::I64, &format!("ven_abs({})", values[0]), line),
+            MathFn::Min | MathFn::Max => format!(
+                "ven_{}((int64_t[]){{{}}}, {})",
+                op.name(),
+                values.join(", "),
+                values.len()
+            ),
+            MathFn::Sqrt | MathFn::Floor | MathFn::Ceil | MathFn::Round => values[0].clone(),
+        };
+    }
+    let values: Vec<String> = args.iter().map(|arg| c_number(arg, var_types, line)).collect();
+    match op {
+        MathFn::Negate => format!("(-{})", values[0]),
+        MathFn::Abs => format!("fabs({})", values[0]),
+        MathFn::Min | MathFn::Max => format!(
+            "ven_f{}((double[]){{{}}}, {})",
+            op.name(),
+            values.join(", "),
+            values.len()
+        ),
+        MathFn::Sqrt | MathFn::Floor | MathFn::Ceil | MathFn::Round => format!("{}({})", op.name(), values[0]),
+    }
+}
+
+/// `expr` as the text `>>` prints for it.
+fn c_text(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let value = c_expression(expr, var_types, line);
+    match value_kind(expr, var_types) {
+        ValueKind::Int if is_u64(expr, var_types) => format!("ven_uint_text((char[24]){{0}}, {})", value),
+        ValueKind::Int => format!("ven_int_text((char[24]){{0}}, {})", value),
+        ValueKind::Float => format!("ven_float_text((char[400]){{0}}, {})", value),
+        ValueKind::Str => value,
+        ValueKind::Bool => format!("({} ? \"true\" : \"false\")", value),
+    }
+}
+
+/// `expr` as a number; text that is not a number counts as 0.
+fn c_number(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match value_kind(expr, var_types) {
+        ValueKind::Str => format!("ven_to_float({})", c_expression(expr, var_types, line)),
+        _ => c_expression(expr, var_types, line),
+    }
+}
+
+/// An integer constant, spelled so that no C constant overflows.
+fn c_int(value: i128) -> String {
+    if value == i64::MIN as i128 {
+        "INT64_MIN".to_string()
+    } else if value > i64::MAX as i128 {
+        format!("{}ULL", value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn c_type(int_type: IntType) -> String {
+    format!("{}int{}_t", if int_type.signed { "" } else { "u" }, int_type.bits)
+}
+
+/// `value` stored into the integer variable `name`.
+fn c_fit(name: &str, int_type: IntType, value: &str, line: usize) -> String {
+    format!(
+        "({})ven_fit({}, {}, {}, \"{}\", \"{}\", {})",
+        c_type(int_type),
+        value,
+        c_int(int_type.min()),
+        c_int(int_type.max()),
+        name,
+        int_type,
+        line
+    )
+}
+
+fn c_fit_float(name: &str, int_type: IntType, value: &str, line: usize) -> String {
+    format!(
+        "({})ven_fit_float({}, {}, {}, \"{}\", \"{}\", {})",
+        c_type(int_type),
+        value,
+        c_int(int_type.min()),
+        c_int(int_type.max()),
+        name,
+        int_type,
+        line
+    )
+}
+
+/// `expr` converted for a store into the integer variable `name`. Checks are
+/// left out where the value always fits.
+fn c_int_value(
+    name: &str,
+    int_type: IntType,
+    expr: &Expression,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    line: usize,
+) -> String {
+    let value = c_expression(expr, var_types, line);
+    match (expr, value_kind(expr, var_types)) {
+        (Expression::Literal(lit), ValueKind::Int) => {
+            let literal = int_literal(lit).unwrap_or(0);
+            match fit(literal, int_type, overflow) {
+                Some(fitted) => c_int(fitted),
+                None => c_fit(name, int_type, &value, line),
+            }
+        }
+        (
+            Expression::Identifier(_)
+            | Expression::Index { .. }
+            | Expression::Len(_)
+            | Expression::Field { .. }
+            | Expression::Convert { .. }
+            | Expression::Text { .. }
+            | Expression::Math { .. },
+            ValueKind::Int,
+        ) => {
+            match int_type_of(expr, var_types) {
+                Some(other_type) if !int_type.holds(other_type) => c_fit(name, int_type, &value, line),
+                _ => value,
+            }
+        }
+        (_, ValueKind::Int | ValueKind::Bool) => value,
+        (_, ValueKind::Float) => c_fit_float(name, int_type, &value, line),
+        (_, ValueKind::Str) => c_fit(name, int_type, &format!("ven_to_int({})", value), line),
+    }
+}
+
+/// Whether `expr` is a `u64` variable or element, or a literal only `u64` holds.
+fn is_u64(expr: &Expression, var_types: &HashMap<String, VarType>) -> bool {
+    match expr {
+        Expression::Literal(lit) => int_literal(lit).is_some_and(|value| value > i64::MAX as i128),
+        _ => int_type_of(expr, var_types) == Some(IntType::U64),
+    }
+}
+
+/// `expr` converted to a variable of `var_type`, other than text.
+fn c_value(expr: &Expression, var_type: &VarType, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let kind = value_kind(expr, var_types);
+    let value = c_expression(expr, var_types, line);
+    match (var_type, kind) {
+        (VarType::Float, ValueKind::Str) => format!("ven_to_float({})", value),
+        (VarType::Bool, ValueKind::Str) => format!("ven_to_bool({})", value),
+        (VarType::Bool, ValueKind::Int | ValueKind::Float) => format!("({} != 0)", value),
+        _ => value,
+    }
+}
+
+fn c_condition(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
     match expr {
-        Expression::Literal(val) => val.clone(),
-        Expression::Identifier(id) => id.clone(),
         Expression::BinaryOp { left, operator, right } => {
-            let left_str = translate_condition_to_c(left);
-            let right_str = translate_condition_to_c(right);
-            
-            let op_str = match operator {
+            let op = match operator {
                 ComparisonOperator::Equal => "==",
                 ComparisonOperator::NotEqual => "!=",
                 ComparisonOperator::LessThan => "<",
@@ -275,19 +1549,78 @@ fn translate_condition_to_c(expr: &Expression) -> String {
                 ComparisonOperator::GreaterThan => ">",
                 ComparisonOperator::GreaterThanEqual => ">=",
             };
-            
-            format!("({} {} {})", left_str, op_str, right_str)
+            let (left_expr, right_expr) = (left.as_ref(), right.as_ref());
+            let (left_kind, right_kind) = (value_kind(left, var_types), value_kind(right, var_types));
+            let (left, right) = (c_expression(left, var_types, line), c_expression(right, var_types, line));
+            match (left_kind == ValueKind::Str, right_kind == ValueKind::Str) {
+                (true, true) => format!("(strcmp({}, {}) {} 0)", left, right, op),
+                // A string never equals a number
+                (true, false) | (false, true) => {
+                    format!("({})", (*operator == ComparisonOperator::NotEqual) as i32)
+                }
+                // C would compare a negative number with a u64 as unsigned
+                (false, false) if is_u64(left_expr, var_types) || is_u64(right_expr, var_types) => {
+                    format!("((ven_wide){} {} (ven_wide){})", left, op, right)
+                }
+                (false, false) => format!("({} {} {})", left, op, right),
+            }
         }
         Expression::LogicalOp { left, operator, right } => {
-            let left_str = translate_condition_to_c(left);
-            let right_str = translate_condition_to_c(right);
-            
-            let op_str = match operator {
+            let op = match operator {
                 LogicalOperator::And => "&&",
                 LogicalOperator::Or => "||",
             };
-            
-            format!("({} {} {})", left_str, op_str, right_str)
+            format!(
+                "({} {} {})",
+                c_condition(left, var_types, line),
+                op,
+                c_condition(right, var_types, line)
+            )
         }
+        Expression::Not(operand) => format!("!{}", c_operand(operand, var_types, line)),
+        _ => match value_kind(expr, var_types) {
+            // Strings are true when non-empty
+            ValueKind::Str => format!("({}[0] != '\\0')", c_expression(expr, var_types, line)),
+            ValueKind::Int | ValueKind::Float => format!("({} != 0)", c_expression(expr, var_types, line)),
+            ValueKind::Bool => c_expression(expr, var_types, line),
+        },
+    }
+}
+
+/// A parenthesized condition.
+fn c_operand(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let condition = c_condition(expr, var_types, line);
+    if condition.starts_with('(') && condition.ends_with(')') && !matches!(expr, Expression::Not(_)) {
+        condition
+    } else {
+        format!("({})", condition)
+    }
+}
+
+pub struct CBackend;
+
+impl Backend for CBackend {
+    fn name(&self) -> &'static str {
+        "c"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn description(&self) -> &'static str {
+        "C"
+    }
+
+    fn extension(&self) -> &'static str {
+        "c"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::ALL
+    }
+
+    fn emit(&self, ast: &AST, options: &CodegenOptions) -> Result<String, BackendError> {
+        transpile_c(ast, options).map_err(|construct| BackendError::Unsupported { target: self.name(), construct })
     }
 }
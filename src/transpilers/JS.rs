@@ -0,0 +1,974 @@
+use crate::errs::BackendError;
+use crate::parse::{
+    AST, ASTNode, ComparisonOperator, Expression, IntType, LogicalOperator, MathFn, MathOperator, TextOp, VarType,
+};
+use crate::transpilers::{
+    Backend, Capabilities, CodegenOptions, FormatSpec, Overflow, STR_CAPACITY, Segment, ValueKind, clean_number,
+    collect_var_types, element_type, field_type, fit, formatted, has_buffer, int_literal, int_type_of, is_text_var,
+    literal_text, map_types, math_kind, placeholder, split_interpolation, stored_text, target_type, undeclared,
+    uses_conversions, uses_division, uses_math, uses_strings, value_kind,
+};
+use std::collections::HashMap;
+
+// Synchronous line reader over fd 0, shared by every generated module
+const READ_LINE_HELPER: &str = r#"const __byte = Buffer.alloc(1);
+function __readLine() {
+  const bytes = [];
+  for (;;) {
+    let read = 0;
+    try {
+      read = fs.readSync(0, __byte, 0, 1, null);
+    } catch (e) {
+      if (e.code === "EAGAIN") continue;
+      if (e.code === "EOF") break;
+      throw e;
+    }
+    if (read === 0 || __byte[0] === 10) break;
+    bytes.push(__byte[0]);
+  }
+  if (bytes[bytes.length - 1] === 13) bytes.pop();
+  // A line holds at most 255 bytes, as on the native targets, and a character
+  // cut in two is dropped whole
+  let end = Math.min(bytes.length, 255);
+  while (end > 0 && end < bytes.length && (bytes[end] & 0xc0) === 0x80) end--;
+  return Buffer.from(bytes.slice(0, end)).toString("utf8");
+}
+
+// Integers are BigInts. Text that is not a whole number from the i64 minimum to
+// the u64 maximum reads as 0.
+function __toInt(text) {
+  const trimmed = text.trim();
+  if (!/^[+-]?[0-9]+$/.test(trimmed)) return 0n;
+  const value = BigInt(trimmed);
+  return value < -(2n ** 63n) || value >= 2n ** 64n ? 0n : value;
+}
+"#;
+
+// Integer overflow, one body per `--overflow=` mode. `__fit` stores a value into
+// the type running from min to max.
+const WRAP_HELPERS: &str = r#"
+function __fit(value, min, max, name, type, line) {
+  if (value >= min && value <= max) return value;
+  const span = max - min + 1n;
+  return (((value - min) % span) + span) % span + min;
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+function __fitFloat(value, min, max, name, type, line) {
+  if (Number.isNaN(value)) return 0n;
+  const whole = Math.trunc(value);
+  return whole < Number(min) ? min : whole >= Number(max) + 1 ? max : BigInt(whole);
+}
+"#;
+
+const SATURATE_HELPERS: &str = r#"
+function __fit(value, min, max, name, type, line) {
+  return value < min ? min : value > max ? max : value;
+}
+
+function __fitFloat(value, min, max, name, type, line) {
+  if (Number.isNaN(value)) return 0n;
+  const whole = Math.trunc(value);
+  return whole < Number(min) ? min : whole >= Number(max) + 1 ? max : BigInt(whole);
+}
+"#;
+
+const TRAP_HELPERS: &str = r#"
+function __overflow(name, type, line) {
+  process.stderr.write(`runtime error: integer overflow in '${name}' (${type}) on line ${line}\n`);
+  process.exit(1);
+}
+
+function __fit(value, min, max, name, type, line) {
+  if (value < min || value > max) __overflow(name, type, line);
+  return value;
+}
+
+function __fitFloat(value, min, max, name, type, line) {
+  if (Number.isNaN(value)) return 0n;
+  const whole = Math.trunc(value);
+  if (whole < Number(min) || whole >= Number(max) + 1) __overflow(name, type, line);
+  return BigInt(whole);
+}
+"#;
+
+// Bounds checks, for programs with arrays
+const ARRAY_HELPERS: &str = r#"
+function __index(index, length, name, line) {
+  const i = BigInt(index);
+  if (i < 0n || i >= BigInt(length)) {
+    process.stderr.write(`runtime error: index ${i} is out of bounds for '${name}' (length ${length}) on line ${line}\n`);
+    process.exit(1);
+  }
+  return Number(i);
+}
+"#;
+
+// Lookups and printing, for programs with maps. A Map keeps insertion order,
+// and BigInt keys compare by value. Only floats are Numbers.
+const MAP_HELPERS: &str = r#"
+function __get(map, key, name, line) {
+  if (!map.has(key)) {
+    const shown = typeof key === "string" ? `"${key}"` : key;
+    process.stderr.write(`runtime error: key ${shown} is not in '${name}' on line ${line}\n`);
+    process.exit(1);
+  }
+  return map.get(key);
+}
+
+function __showMap(map) {
+  const shown = Array.from(map, ([key, value]) => `${key}: ${typeof value === "number" ? __floatText(value) : value}`);
+  return `{${shown.join(", ")}}`;
+}
+"#;
+
+// Printing, for programs with records, which are plain objects. Only floats
+// are Numbers.
+const RECORD_HELPERS: &str = r#"
+function __showRecord(record) {
+  const shown = Object.entries(record).map(
+    ([field, value]) => `${field}: ${typeof value === "number" ? __floatText(value) : value}`,
+  );
+  return `{${shown.join(", ")}}`;
+}
+"#;
+
+// Float printing, for programs with floats. Integers are BigInts, so a Number
+// is always a float.
+const FLOAT_HELPERS: &str = r#"
+function __floatText(x) {
+  if (!Number.isFinite(x)) return Number.isNaN(x) ? "NaN" : x < 0 ? "-inf" : "inf";
+  if (Object.is(x, -0)) return "-0";
+  // The shortest digits, written out without an exponent like the native targets
+  const text = String(Math.abs(x));
+  const e = text.indexOf("e");
+  if (e < 0) return x < 0 ? `-${text}` : text;
+  const digits = text.slice(0, e).replace(".", "");
+  const point = 1 + Number(text.slice(e + 1));
+  const sign = x < 0 ? "-" : "";
+  if (point <= 0) return `${sign}0.${"0".repeat(-point)}${digits}`;
+  if (point >= digits.length) return sign + digits + "0".repeat(point - digits.length);
+  return `${sign}${digits.slice(0, point)}.${digits.slice(point)}`;
+}
+"#;
+
+// `i()`, `f()` and `str()`, and `{name:spec}` layout, for programs that use them
+const CONVERT_HELPERS: &str = r#"
+function __convertFailed(text, to, line) {
+  process.stderr.write(`runtime error: cannot convert "${text}" to ${to} on line ${line}\n`);
+  process.exit(1);
+}
+
+// A sign and decimal digits within i64, with spaces around them
+function __convertInt(text, line) {
+  const trimmed = text.replace(/^[ \t\n\r]+|[ \t\n\r]+$/g, "");
+  if (!/^[+-]?[0-9]+$/.test(trimmed)) __convertFailed(text, "i", line);
+  const value = BigInt(trimmed);
+  if (value < -(2n ** 63n) || value >= 2n ** 63n) __convertFailed(text, "i", line);
+  return value;
+}
+
+// A finite decimal number like -1.5, .5, 2. or 6.02e23, with spaces around it
+function __convertFloat(text, line) {
+  const trimmed = text.replace(/^[ \t\n\r]+|[ \t\n\r]+$/g, "");
+  const value = Number(trimmed);
+  if (!/^[+-]?([0-9]+(\.[0-9]*)?|\.[0-9]+)([eE][+-]?[0-9]+)?$/.test(trimmed) || !Number.isFinite(value)) {
+    __convertFailed(text, "f", line);
+  }
+  return value;
+}
+
+// Truncated toward zero, with NaN as 0 and clamped at the i64 limits
+function __floatToInt(x) {
+  if (Number.isNaN(x)) return 0n;
+  return x <= -(2 ** 63) ? -(2n ** 63n) : x >= 2 ** 63 ? 2n ** 63n - 1n : BigInt(Math.trunc(x));
+}
+
+// p digits after the point. toFixed rounds ties up, so an exact tie is
+// rounded half to even here, like the native targets.
+function __fixed(x, p) {
+  if (!Number.isFinite(x)) return __floatText(x);
+  const sign = x < 0 || Object.is(x, -0) ? "-" : "";
+  const abs = Math.abs(x);
+  if (abs >= 1e21) return sign + BigInt(abs).toString() + (p > 0 ? `.${"0".repeat(p)}` : "");
+  const longer = abs.toFixed(p + 1);
+  if (longer.endsWith("5") && Number.isInteger(abs * 2 ** (p + 1))) {
+    const down = longer.slice(0, p === 0 ? -2 : -1);
+    if (Number(down[down.length - 1]) % 2 === 0) return sign + down;
+  }
+  return sign + abs.toFixed(p);
+}
+
+// Padded with spaces to width characters, before the text when right
+function __pad(text, width, right) {
+  const fill = " ".repeat(Math.max(0, width - [...text].length));
+  return right ? fill + text : text + fill;
+}
+"#;
+
+// Cutting text to a `c[size]` buffer or a `str`, for programs that have a
+// buffer or build text with string operations
+const TEXT_HELPERS: &str = r#"
+function __fitText(text, size) {
+  const bytes = Buffer.from(String(text));
+  if (bytes.length <= size) return text;
+  let end = size;
+  while (end > 0 && (bytes[end] & 0xc0) === 0x80) end--;
+  return bytes.subarray(0, end).toString("utf8");
+}
+"#;
+
+// String operations, for programs that use them. Lengths and positions count
+// characters, not UTF-16 units, and only ASCII letters change case.
+const STRING_HELPERS: &str = r#"
+function __slice(text, start, end) {
+  const chars = Array.from(text);
+  const clamp = (index) => (index < 0n ? 0 : index > BigInt(chars.length) ? chars.length : Number(index));
+  const [from, to] = [clamp(start), clamp(end)];
+  return to <= from ? "" : chars.slice(from, to).join("");
+}
+
+function __upper(text) {
+  return text.replace(/[a-z]+/g, (letters) => letters.toUpperCase());
+}
+
+function __lower(text) {
+  return text.replace(/[A-Z]+/g, (letters) => letters.toLowerCase());
+}
+
+function __trim(text) {
+  return text.replace(/^[ \t\n\r]+|[ \t\n\r]+$/g, "");
+}
+
+// An empty `from` leaves the text as it is
+function __replace(text, from, to) {
+  return from === "" ? text : text.split(from).join(to);
+}
+"#;
+
+// The division by zero checks, unless `--no-runtime-checks` drops them. `0n == 0`
+// holds, so they take BigInts and Numbers alike.
+const DIVISION_HELPERS: &str = r#"
+function __divZero(name, line) {
+  process.stderr.write(`runtime error: division of '${name}' by zero on line ${line}\n`);
+  process.exit(1);
+}
+
+function __divisor(y, name, line) {
+  if (y == 0) __divZero(name, line);
+  return y;
+}
+
+function __exponent(x, y, name, line) {
+  if (x == 0 && y < 0) __divZero(name, line);
+  return y;
+}
+"#;
+
+// `**` and the math functions, for programs that use them. Integers are
+// BigInts and floats are Numbers; `__min` and `__max` take either.
+const MATH_HELPERS: &str = r#"
+// A negative y gives 1 / x ** -y truncated toward zero. A power past 128 bits
+// is cut to its sign and low 64 bits, still past every integer type, which
+// `__fit` handles as it would the power itself
+function __pow(x, y) {
+  if (y === 0n) return 1n;
+  if (x === 0n || x === 1n) return x;
+  if (x === -1n) return y % 2n === 0n ? 1n : -1n;
+  if (y < 0n) return 0n;
+  if (y < 128n) return x ** y;
+  let low = 1n;
+  let base = BigInt.asUintN(64, x);
+  for (let e = y; e > 0n; e >>= 1n) {
+    if (e & 1n) low = BigInt.asUintN(64, low * base);
+    base = BigInt.asUintN(64, base * base);
+  }
+  return x < 0n && y % 2n !== 0n ? low - (1n << 65n) : low + (1n << 64n);
+}
+
+// As on the native targets, 1 to any power and -1 to an infinite one are 1
+function __fpow(x, y) {
+  if (x === 1 || (x === -1 && !Number.isFinite(y) && !Number.isNaN(y))) return 1;
+  return x ** y;
+}
+
+function __abs(x) {
+  return x < 0n ? -x : x;
+}
+
+// A later value only wins when it is smaller, or larger
+function __min(...values) {
+  return values.reduce((min, x) => (x < min ? x : min));
+}
+
+function __max(...values) {
+  return values.reduce((max, x) => (x > max ? x : max));
+}
+
+// Halves round away from zero
+function __round(x) {
+  return x < 0 ? -Math.round(-x) : Math.round(x);
+}
+"#;
+
+pub fn transpile_js(ast: &AST, options: &CodegenOptions) -> String {
+    let mut code = String::with_capacity(1024);
+    let vars = collect_var_types(ast);
+    let var_types: HashMap<String, VarType> = vars.iter().cloned().collect();
+
+    code.push_str("// Generated by the Ven compiler\n");
+    code.push_str("import fs from \"node:fs\";\n\n");
+    code.push_str(READ_LINE_HELPER);
+    code.push_str(match options.overflow {
+        Overflow::Wrap => WRAP_HELPERS,
+        Overflow::Saturate => SATURATE_HELPERS,
+        Overflow::Trap => TRAP_HELPERS,
+    });
+    if vars.iter().any(|(_, var_type)| matches!(var_type, VarType::Array(_))) {
+        code.push_str(ARRAY_HELPERS);
+    }
+    if vars.iter().any(|(_, var_type)| matches!(var_type, VarType::Map(..))) {
+        code.push_str(MAP_HELPERS);
+    }
+    if vars.iter().any(|(_, var_type)| matches!(var_type, VarType::Record(_))) {
+        code.push_str(RECORD_HELPERS);
+    }
+    let AST::Program(nodes) = ast;
+    let strings = uses_strings(nodes, &var_types);
+    if strings || vars.iter().any(|(_, var_type)| has_buffer(var_type)) {
+        code.push_str(TEXT_HELPERS);
+    }
+    let conversions = uses_conversions(nodes, &var_types);
+    let math = uses_math(nodes, &var_types);
+    if conversions || math || vars.iter().any(|(_, var_type)| has_float(var_type)) {
+        code.push_str(FLOAT_HELPERS);
+    }
+    if math {
+        code.push_str(MATH_HELPERS);
+    }
+    if options.runtime_checks && uses_division(nodes) {
+        code.push_str(DIVISION_HELPERS);
+    }
+    if conversions {
+        code.push_str(CONVERT_HELPERS);
+    }
+    if strings {
+        code.push_str(STRING_HELPERS);
+    }
+    code.push('\n');
+
+    // Ven variables are program-wide, so every name is hoisted to module scope
+    if !vars.is_empty() {
+        let names: Vec<&str> = vars.iter().map(|(name, _)| name.as_str()).collect();
+        code.push_str(&format!("let {};\n\n", names.join(", ")));
+    }
+
+    emit_nodes(&mut code, nodes, 0, &var_types, options.overflow, options.runtime_checks);
+
+    code.shrink_to_fit();
+    code
+}
+
+fn emit_nodes(
+    code: &mut String,
+    nodes: &[ASTNode],
+    depth: usize,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    checks: bool,
+) {
+    let indent = "  ".repeat(depth);
+    for node in nodes {
+        // Named by the runtime errors the statement can stop the program with
+        let line = node.line().unwrap_or(0);
+        match node {
+            ASTNode::VarDeclaration { name, var_type, value, .. } => {
+                let value_str = match value {
+                    Some(expr) => match (var_type, expr) {
+                        // Declaration literals arrive with their quotes already stripped
+                        (VarType::String | VarType::Char { .. }, Expression::Literal(lit)) => {
+                            js_string(&stored_text(lit, var_type))
+                        }
+                        (VarType::Int(int_type), _) => js_int_value(name, *int_type, expr, var_types, overflow, line),
+                        (VarType::Array(element), Expression::List(items)) => {
+                            let items: Vec<String> = items
+                                .iter()
+                                .map(|item| js_element(name, element, item, var_types, overflow, line))
+                                .collect();
+                            format!("[{}]", items.join(", "))
+                        }
+                        (VarType::Float, _) => js_number(expr, var_types, line),
+                        (VarType::Map(..), _) => default_value(var_type),
+                        // Each field is converted like a store into a variable of the field type
+                        (VarType::Record(record), Expression::Record(values)) => {
+                            let fields: Vec<String> = record
+                                .fields
+                                .iter()
+                                .zip(values)
+                                .map(|((field, field_type), (_, value))| {
+                                    let target = format!("{}.{}", name, field);
+                                    let value = js_element(&target, field_type, value, var_types, overflow, line);
+                                    match field_type {
+                                        VarType::Char { size } => format!("{}: __fitText({}, {})", field, value, size),
+                                        _ => format!("{}: {}", field, value),
+                                    }
+                                })
+                                .collect();
+                            format!("{{ {} }}", fields.join(", "))
+                        }
+                        (VarType::Record(_), _) => default_value(var_type),
+                        _ => js_expression(expr, var_types, line),
+                    },
+                    None => default_value(var_type),
+                };
+                let value_str = match var_type {
+                    VarType::Char { size } => format!("__fitText({}, {})", value_str, size),
+                    _ => value_str,
+                };
+                code.push_str(&format!("{}{} = {};\n", indent, name, value_str));
+            }
+            ASTNode::Input { name, .. } => {
+                let read = match var_types.get(name) {
+                    Some(VarType::Int(int_type)) => js_fit(name, *int_type, "__toInt(__readLine())", line),
+                    Some(VarType::Float) => "Number.parseFloat(__readLine()) || 0".to_string(),
+                    Some(VarType::Bool) => "__readLine().trim() === \"true\"".to_string(),
+                    Some(VarType::Char { size }) => format!("__fitText(__readLine(), {})", size),
+                    Some(_) => "__readLine()".to_string(),
+                    None => {
+                        // Undeclared target: the line is consumed and discarded
+                        code.push_str(&format!("{}__readLine();\n", indent));
+                        continue;
+                    }
+                };
+                code.push_str(&format!("{}{} = {};\n", indent, name, read));
+            }
+            ASTNode::Print { to_stderr, expr, .. } => {
+                let stream = if *to_stderr { "process.stderr" } else { "process.stdout" };
+                let text = match expr {
+                    Some(Expression::Literal(text)) => template_literal(text, var_types, line),
+                    Some(expr @ Expression::Identifier(name)) if var_types.contains_key(name) => {
+                        format!("`{}\\n`", js_interpolate(expr, var_types, line))
+                    }
+                    // Bare words are printed as written
+                    Some(Expression::Identifier(word)) => template_literal(word, &HashMap::new(), line),
+                    Some(expr) => format!("`{}\\n`", js_interpolate(expr, var_types, line)),
+                    None => "\"\\n\"".to_string(),
+                };
+                code.push_str(&format!("{}{}.write({});\n", indent, stream, text));
+            }
+            ASTNode::MathOp { name, field, operator, operand, .. } => {
+                let var_type = target_type(name, field.as_deref(), var_types);
+                // A field is changed like a variable of the field type
+                let name = &match field {
+                    Some(field) => format!("{}.{}", name, field),
+                    None => name.clone(),
+                };
+                // Text reads as 0 in math, as in the VM
+                let zero = Expression::Literal("0".to_string());
+                let operand = if value_kind(operand, var_types) == ValueKind::Str { &zero } else { operand };
+                // `/` and `%` check the operand for zero, and an integer `**` checks
+                // for a negative power of zero
+                let divides = checks && matches!(operator, MathOperator::Divide | MathOperator::Modulo);
+                let divisor = |operand: String| {
+                    if divides { format!("__divisor({}, \"{}\", {})", operand, name, line) } else { operand }
+                };
+                let value = match (var_type, value_kind(operand, var_types)) {
+                    // The operand is stored into the variable's type first, then the
+                    // exact result is; BigInt division truncates toward zero like the
+                    // native targets
+                    (Some(VarType::Int(int_type)), ValueKind::Int | ValueKind::Bool) => {
+                        let mut operand = divisor(js_int_value(name, *int_type, operand, var_types, overflow, line));
+                        if checks && *operator == MathOperator::Power {
+                            operand = format!("__exponent({}, {}, \"{}\", {})", name, operand, name, line);
+                        }
+                        let value = match operator {
+                            MathOperator::Power => format!("__pow({}, {})", name, operand),
+                            _ => js_math(name, operator, &operand),
+                        };
+                        js_fit(name, *int_type, &value, line)
+                    }
+                    // A float operand on an integer is applied in floating point
+                    (Some(VarType::Int(int_type)), _) => js_call(
+                        "__fitFloat",
+                        name,
+                        *int_type,
+                        &js_math(&format!("Number({})", name), operator, &divisor(js_number(operand, var_types, line))),
+                        line,
+                    ),
+                    _ => js_math(name, operator, &divisor(js_number(operand, var_types, line))),
+                };
+                code.push_str(&format!("{}{} = {};\n", indent, name, value));
+            }
+            ASTNode::If { condition, body, .. } => {
+                code.push_str(&format!("{}if {} {{\n", indent, js_operand(condition, var_types, line)));
+                emit_nodes(code, body, depth + 1, var_types, overflow, checks);
+                code.push_str(&format!("{}}}\n", indent));
+            }
+            ASTNode::SetIndex { name, index, value, .. } if let Some((key, element)) = map_types(name, var_types) => {
+                let key = js_element(name, key, index, var_types, Overflow::Trap, line);
+                let value = js_element(name, element, value, var_types, overflow, line);
+                code.push_str(&format!("{}{}.set({}, {});\n", indent, name, key, value));
+            }
+            ASTNode::SetIndex { name, index, value, .. } => {
+                // The index is checked before the value is evaluated
+                let Some(element) = element_type(name, var_types) else { continue };
+                code.push_str(&format!(
+                    "{}{}[__index({}, {}.length, \"{}\", {})] = {};\n",
+                    indent,
+                    name,
+                    js_expression(index, var_types, line),
+                    name,
+                    name,
+                    line,
+                    js_element(name, element, value, var_types, overflow, line)
+                ));
+            }
+            ASTNode::Append { name, value, .. } => {
+                let Some(element) = element_type(name, var_types) else { continue };
+                let value = js_element(name, element, value, var_types, overflow, line);
+                code.push_str(&format!("{}{}.push({});\n", indent, name, value));
+            }
+            ASTNode::ForEach { item, array, body } => {
+                // The length is read before each pass, so appended elements are visited
+                let counter = format!("__loop{}", depth);
+                code.push_str(&format!(
+                    "{}for (let {} = 0; {} < {}.length; {}++) {{\n",
+                    indent, counter, counter, array, counter
+                ));
+                code.push_str(&format!("{}  {} = {}[{}];\n", indent, item, array, counter));
+                emit_nodes(code, body, depth + 1, var_types, overflow, checks);
+                code.push_str(&format!("{}}}\n", indent));
+            }
+            // Records are plain objects and need no declaration
+            ASTNode::RecordDeclaration(_) => {}
+            // Rejected by check_support
+            ASTNode::Call { .. } => {}
+        }
+    }
+}
+
+/// `expr` converted for a store into an element of the array `name`, or a key
+/// or value of the map `name`.
+fn js_element(
+    name: &str,
+    element: &VarType,
+    expr: &Expression,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    line: usize,
+) -> String {
+    match element {
+        VarType::Int(int_type) => js_int_value(name, *int_type, expr, var_types, overflow, line),
+        VarType::Float => js_number(expr, var_types, line),
+        VarType::Bool => js_expression(expr, var_types, line),
+        _ => match expr {
+            Expression::Literal(lit) => js_string(&stored_text(lit, element)),
+            _ if value_kind(expr, var_types) == ValueKind::Str => js_expression(expr, var_types, line),
+            _ => "\"\"".to_string(),
+        },
+    }
+}
+
+/// A `${...}` substitution printing `expr`; arrays print as `[a, b, c]`,
+/// maps as `{k: v}` and records as `{field: v}`.
+fn js_interpolate(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::Identifier(name) if let Some(VarType::Record(_)) = var_types.get(name) => {
+            format!("${{__showRecord({})}}", name)
+        }
+        Expression::Identifier(name) if map_types(name, var_types).is_some() => format!("${{__showMap({})}}", name),
+        Expression::Identifier(name) if element_type(name, var_types) == Some(&VarType::Float) => {
+            format!("[${{{}.map(__floatText).join(\", \")}}]", name)
+        }
+        Expression::Identifier(name) if element_type(name, var_types).is_some() => {
+            format!("[${{{}.join(\", \")}}]", name)
+        }
+        _ if value_kind(expr, var_types) == ValueKind::Float => {
+            format!("${{__floatText({})}}", js_expression(expr, var_types, line))
+        }
+        _ => format!("${{{}}}", js_expression(expr, var_types, line)),
+    }
+}
+
+/// Whether a variable of `var_type` holds floats.
+fn has_float(var_type: &VarType) -> bool {
+    match var_type {
+        VarType::Float => true,
+        VarType::Array(element) | VarType::Map(_, element) => has_float(element),
+        VarType::Record(record) => record.fields.iter().any(|(_, field_type)| has_float(field_type)),
+        _ => false,
+    }
+}
+
+/// A `${...}` substitution printing `expr` laid out by `spec`.
+fn js_formatted(expr: &Expression, spec: FormatSpec, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let kind = value_kind(expr, var_types);
+    let value = js_expression(expr, var_types, line);
+    let text = match (kind, spec.precision) {
+        (ValueKind::Float, Some(precision)) => format!("__fixed({}, {})", value, precision),
+        (ValueKind::Float, None) => format!("__floatText({})", value),
+        _ => format!("String({})", value),
+    };
+    let right = spec.right(matches!(kind, ValueKind::Int | ValueKind::Float));
+    format!("${{__pad({}, {}, {})}}", text, spec.width, right)
+}
+
+/// Builds a template literal from a print literal, ending with a newline.
+fn template_literal(text: &str, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let mut out = String::from("`");
+    for segment in split_interpolation(text) {
+        match segment {
+            Segment::Text(text) => out.push_str(&escape_template(&text)),
+            Segment::Var(name) => match placeholder(&name, var_types) {
+                Some(expr) => out.push_str(&js_interpolate(&expr, var_types, line)),
+                None => out.push_str("[undefined]"),
+            },
+            Segment::Formatted(name, spec) => match formatted(&name, var_types) {
+                Some(expr) => out.push_str(&js_formatted(&expr, spec, var_types, line)),
+                None => out.push_str("[undefined]"),
+            },
+        }
+    }
+    out.push_str("\\n`");
+    out
+}
+
+fn escape_template(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '`' => escaped.push_str("\\`"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '$' if chars.peek() == Some(&'{') => escaped.push_str("\\$"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn js_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn default_value(var_type: &VarType) -> String {
+    match var_type {
+        VarType::Int(_) => "0n".to_string(),
+        VarType::Float => "0".to_string(),
+        VarType::String | VarType::Char { .. } => "\"\"".to_string(),
+        VarType::Bool => "false".to_string(),
+        VarType::Array(_) => "[]".to_string(),
+        VarType::Map(..) => "new Map()".to_string(),
+        VarType::Record(record) => {
+            let fields: Vec<String> = record
+                .fields
+                .iter()
+                .map(|(field, field_type)| format!("{}: {}", field, default_value(field_type)))
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+    }
+}
+
+fn js_expression(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::Literal(lit) => {
+            if lit.starts_with('"') || lit.starts_with('\'') {
+                js_string(&literal_text(lit))
+            } else {
+                let number = clean_number(lit);
+                if number == "true" || number == "false" || number.parse::<f64>().is_ok() {
+                    number
+                } else {
+                    js_string(lit)
+                }
+            }
+        }
+        Expression::Identifier(name) if var_types.contains_key(name) => name.clone(),
+        Expression::Identifier(name) => undeclared(name),
+        Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) => {
+            js_condition(expr, var_types, line)
+        }
+        Expression::Index { name, index } if map_types(name, var_types).is_some() => {
+            format!("__get({}, {}, \"{}\", {})", name, js_key(name, index, var_types, line), name, line)
+        }
+        Expression::In { key, name } => format!("{}.has({})", name, js_key(name, key, var_types, line)),
+        Expression::Index { name, index } => format!(
+            "{}[__index({}, {}.length, \"{}\", {})]",
+            name,
+            js_expression(index, var_types, line),
+            name,
+            name,
+            line
+        ),
+        Expression::Len(name) if map_types(name, var_types).is_some() => format!("BigInt({}.size)", name),
+        Expression::Len(name) if is_text_var(name, var_types) => format!("BigInt(Array.from({}).length)", name),
+        Expression::Len(name) => format!("BigInt({}.length)", name),
+        Expression::Field { name, field } if field_type(name, field, var_types).is_some() => {
+            format!("{}.{}", name, field)
+        }
+        Expression::Field { .. } => "0n".to_string(),
+        // Only found in array, map and record declarations
+        Expression::List(_) | Expression::Record(_) => "0n".to_string(),
+        Expression::Convert { to, value } => {
+            let text = js_expression(value, var_types, line);
+            match (to, value_kind(value, var_types)) {
+                (VarType::String, ValueKind::Str) => text,
+                (VarType::String, ValueKind::Float) => format!("__floatText({})", text),
+                (VarType::String, _) => format!("String({})", text),
+                (VarType::Float, ValueKind::Str) => format!("__convertFloat({}, {})", text, line),
+                (VarType::Float, _) => js_number(value, var_types, line),
+                (_, ValueKind::Str) => format!("__convertInt({}, {})", text, line),
+                (_, ValueKind::Float) => format!("__floatToInt({})", text),
+                (_, ValueKind::Int | ValueKind::Bool) => js_int(value, var_types, line),
+            }
+        }
+        Expression::Text { op, args } => {
+            let text = |i: usize| js_expression(&args[i], var_types, line);
+            match op {
+                TextOp::Concat => {
+                    let parts: Vec<String> = args.iter().map(|arg| js_expression(arg, var_types, line)).collect();
+                    format!("__fitText({}, {})", parts.join(" + "), STR_CAPACITY)
+                }
+                TextOp::Len => format!("BigInt(Array.from({}).length)", text(0)),
+                TextOp::Slice => format!(
+                    "__slice({}, {}, {})",
+                    text(0),
+                    js_int(&args[1], var_types, line),
+                    js_int(&args[2], var_types, line)
+                ),
+                TextOp::Upper => format!("__upper({})", text(0)),
+                TextOp::Lower => format!("__lower({})", text(0)),
+                TextOp::Trim => format!("__trim({})", text(0)),
+                TextOp::Contains => format!("{}.includes({})", text(0), text(1)),
+                TextOp::StartsWith => format!("{}.startsWith({})", text(0), text(1)),
+                TextOp::Replace => {
+                    format!("__fitText(__replace({}, {}, {}), {})", text(0), text(1), text(2), STR_CAPACITY)
+                }
+            }
+        }
+        Expression::Math { op, args } => js_math_fn(*op, args, var_types, line),
+    }
+}
+
+/// `x op y` for Numbers or BigInts; `**` on Numbers is left to `__fpow`.
+fn js_math(x: &str, operator: &MathOperator, y: &str) -> String {
+    let op = match operator {
+        MathOperator::Add => "+",
+        MathOperator::Subtract => "-",
+        MathOperator::Multiply => "*",
+        MathOperator::Divide => "/",
+        MathOperator::Modulo => "%",
+        MathOperator::Power => return format!("__fpow({}, {})", x, y),
+    };
+    format!("{} {} {}", x, op, y)
+}
+
+/// A math function or `-x`. Integer arguments are stored into an `i64`
+/// first, and so is the integer result.
+fn js_math_fn(op: MathFn, args: &[Expression], var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let name = op.called();
+    if math_kind(op, args, var_types) == ValueKind::Int {
+        let values: Vec<String> = args
+            .iter()
+            .map(|arg| match int_type_of(arg, var_types) {
+                Some(int_type) if IntType::I64.holds(int_type) => js_int(arg, var_types, line),
+                Some(_) => js_fit(name, IntType::I64, &js_expression(arg, var_types, line), line),
+                None => js_int_value(name, IntType::I64, arg, var_types, Overflow::Trap, line),
+            })
+            .collect();
+        return match op {
+            MathFn::Negate => js_fit(name, IntType::I64, &format!("-({})", values[0]), line),
+            MathFn::Abs => js_fit(name, IntType::I64, &format!("__abs({})", values[0]), line),
+            MathFn::Min | MathFn::Max => format!("__{}({})", op.name(), values.join(", ")),
+            MathFn::Sqrt | MathFn::Floor | MathFn::Ceil | MathFn::Round => values[0].clone(),
+        };
+    }
+    let values: Vec<String> = args.iter().map(|arg| js_number(arg, var_types, line)).collect();
+    match op {
+        MathFn::Negate => format!("(-({}))", values[0]),
+        MathFn::Min | MathFn::Max => format!("__{}({})", op.name(), values.join(", ")),
+        MathFn::Round => format!("__round({})", values[0]),
+        _ => format!("Math.{}({})", op.name(), values[0]),
+    }
+}
+
+/// The key `expr` of the map `name`. A literal that does not fit is left to
+/// the runtime check.
+fn js_key(name: &str, expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match map_types(name, var_types) {
+        Some((VarType::Int(int_type), _)) => js_int_value(name, *int_type, expr, var_types, Overflow::Trap, line),
+        _ => js_expression(expr, var_types, line),
+    }
+}
+
+/// `helper(value, min, max, name, type, line)` for a store into the integer variable `name`.
+fn js_call(helper: &str, name: &str, int_type: IntType, value: &str, line: usize) -> String {
+    format!(
+        "{}({}, {}n, {}n, \"{}\", \"{}\", {})",
+        helper,
+        value,
+        int_type.min(),
+        int_type.max(),
+        name,
+        int_type,
+        line
+    )
+}
+
+fn js_fit(name: &str, int_type: IntType, value: &str, line: usize) -> String {
+    js_call("__fit", name, int_type, value, line)
+}
+
+/// `expr` converted for a store into the integer variable `name`. Checks are
+/// left out where the value always fits.
+fn js_int_value(
+    name: &str,
+    int_type: IntType,
+    expr: &Expression,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    line: usize,
+) -> String {
+    match (expr, value_kind(expr, var_types)) {
+        (Expression::Literal(lit), ValueKind::Int) => {
+            let literal = int_literal(lit).unwrap_or(0);
+            match fit(literal, int_type, overflow) {
+                Some(fitted) => format!("{}n", fitted),
+                None => js_fit(name, int_type, &format!("{}n", literal), line),
+            }
+        }
+        (
+            Expression::Identifier(_)
+            | Expression::Index { .. }
+            | Expression::Len(_)
+            | Expression::Field { .. }
+            | Expression::Convert { .. }
+            | Expression::Text { .. }
+            | Expression::Math { .. },
+            ValueKind::Int,
+        ) => {
+            match int_type_of(expr, var_types) {
+                Some(other_type) if !int_type.holds(other_type) => {
+                    js_fit(name, int_type, &js_expression(expr, var_types, line), line)
+                }
+                _ => js_int(expr, var_types, line),
+            }
+        }
+        (_, ValueKind::Int | ValueKind::Bool) => js_int(expr, var_types, line),
+        (_, ValueKind::Float) => js_call("__fitFloat", name, int_type, &js_expression(expr, var_types, line), line),
+        (_, ValueKind::Str) => js_fit(name, int_type, &format!("__toInt({})", js_expression(expr, var_types, line)), line),
+    }
+}
+
+/// An integer or boolean `expr` as a BigInt.
+fn js_int(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match (expr, value_kind(expr, var_types)) {
+        (Expression::Literal(lit), ValueKind::Int) => format!("{}n", int_literal(lit).unwrap_or(0)),
+        (_, ValueKind::Bool) => format!("BigInt({})", js_expression(expr, var_types, line)),
+        _ => js_expression(expr, var_types, line),
+    }
+}
+
+/// `expr` as a Number, for float math and comparisons with floats.
+fn js_number(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match (expr, value_kind(expr, var_types)) {
+        (Expression::Literal(lit), ValueKind::Int) => clean_number(lit),
+        (_, ValueKind::Int | ValueKind::Bool) => format!("Number({})", js_expression(expr, var_types, line)),
+        _ => js_expression(expr, var_types, line),
+    }
+}
+
+fn js_condition(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::BinaryOp { left, operator, right } => {
+            let op = match operator {
+                ComparisonOperator::Equal => "===",
+                ComparisonOperator::NotEqual => "!==",
+                ComparisonOperator::LessThan => "<",
+                ComparisonOperator::LessThanEqual => "<=",
+                ComparisonOperator::GreaterThan => ">",
+                ComparisonOperator::GreaterThanEqual => ">=",
+            };
+            // Integers are BigInts and booleans compare with numbers as 1 and 0,
+            // neither of which `===` would do across types
+            let (left_kind, right_kind) = (value_kind(left, var_types), value_kind(right, var_types));
+            let (left, right) = match (left_kind, right_kind) {
+                (ValueKind::Str, _) | (_, ValueKind::Str) | (ValueKind::Bool, ValueKind::Bool) => {
+                    (js_expression(left, var_types, line), js_expression(right, var_types, line))
+                }
+                (ValueKind::Float, _) | (_, ValueKind::Float) => {
+                    (js_number(left, var_types, line), js_number(right, var_types, line))
+                }
+                _ => (js_int(left, var_types, line), js_int(right, var_types, line)),
+            };
+            format!("({} {} {})", left, op, right)
+        }
+        Expression::LogicalOp { left, operator, right } => {
+            let op = match operator {
+                LogicalOperator::And => "&&",
+                LogicalOperator::Or => "||",
+            };
+            format!(
+                "({} {} {})",
+                js_condition(left, var_types, line),
+                op,
+                js_condition(right, var_types, line)
+            )
+        }
+        Expression::Not(operand) => format!("!{}", js_operand(operand, var_types, line)),
+        _ => js_expression(expr, var_types, line),
+    }
+}
+
+/// A parenthesized condition. Comparisons already come back parenthesized.
+fn js_operand(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::BinaryOp { .. } | Expression::LogicalOp { .. } => js_condition(expr, var_types, line),
+        _ => format!("({})", js_condition(expr, var_types, line)),
+    }
+}
+
+pub struct JsBackend;
+
+impl Backend for JsBackend {
+    fn name(&self) -> &'static str {
+        "js"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["javascript", "node"]
+    }
+
+    fn description(&self) -> &'static str {
+        "JavaScript"
+    }
+
+    fn extension(&self) -> &'static str {
+        "mjs"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::ALL
+    }
+
+    fn emit(&self, ast: &AST, options: &CodegenOptions) -> Result<String, BackendError> {
+        Ok(transpile_js(ast, options))
+    }
+}
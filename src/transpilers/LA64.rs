@@ -0,0 +1,248 @@
+use crate::errs::BackendError;
+use crate::parse::AST;
+use crate::transpilers::asm::{ArithOp, AsmTarget, Cond, Inst, Reg, Section, Syscall, emit_program};
+use crate::transpilers::{Backend, Capabilities, CodegenOptions};
+
+/// AArch64 Linux in GNU assembler syntax.
+///
+/// The shared registers are `x0`..`x5`, which line up with both the syscall and
+/// the procedure call argument registers. `x9`..`x11` are scratch and `x8` holds
+/// the syscall number for `svc #0`.
+pub struct LA64;
+
+fn reg(r: Reg) -> &'static str {
+    match r {
+        Reg::R0 => "x0",
+        Reg::R1 => "x1",
+        Reg::R2 => "x2",
+        Reg::R3 => "x3",
+        Reg::R4 => "x4",
+        Reg::R5 => "x5",
+    }
+}
+
+fn reg32(r: Reg) -> &'static str {
+    match r {
+        Reg::R0 => "w0",
+        Reg::R1 => "w1",
+        Reg::R2 => "w2",
+        Reg::R3 => "w3",
+        Reg::R4 => "w4",
+        Reg::R5 => "w5",
+    }
+}
+
+/// Materializes any 64-bit constant with `mov` or a `movz`/`movk` sequence.
+fn mov_imm(dst: &str, value: i64) -> Vec<String> {
+    if (-65536..65536).contains(&value) {
+        return vec![format!("mov {}, #{}", dst, value)];
+    }
+    let bits = value as u64;
+    let mut lines = vec![format!("movz {}, #{}", dst, bits & 0xffff)];
+    for shift in [16, 32, 48] {
+        let chunk = (bits >> shift) & 0xffff;
+        if chunk != 0 {
+            lines.push(format!("movk {}, #{}, lsl #{}", dst, chunk, shift));
+        }
+    }
+    lines
+}
+
+impl AsmTarget for LA64 {
+    fn comment(&self) -> &'static str {
+        "//"
+    }
+
+    fn syscall_number(&self, call: Syscall) -> u32 {
+        match call {
+            Syscall::Read => 63,
+            Syscall::Write => 64,
+            Syscall::Exit => 93,
+        }
+    }
+
+    fn prelude(&self) -> String {
+        String::new()
+    }
+
+    fn section(&self, section: Section) -> String {
+        match section {
+            Section::Data => ".data\n",
+            Section::Bss => ".bss\n",
+            Section::Text => ".text\n",
+        }
+        .to_string()
+    }
+
+    fn entry(&self) -> String {
+        ".global _start\n".to_string()
+    }
+
+    fn bytes(&self, label: &str, bytes: &[u8]) -> String {
+        // Printable runs go in .ascii, everything else in .byte
+        let mut out = format!("{}:\n", label);
+        let mut run = String::new();
+        let mut raw: Vec<String> = Vec::new();
+        for &b in bytes {
+            if (0x20..0x7f).contains(&b) && b != b'"' && b != b'\\' {
+                if !raw.is_empty() {
+                    out.push_str(&format!("    .byte {}\n", raw.join(", ")));
+                    raw.clear();
+                }
+                run.push(b as char);
+            } else {
+                if !run.is_empty() {
+                    out.push_str(&format!("    .ascii \"{}\"\n", run));
+                    run.clear();
+                }
+                raw.push(b.to_string());
+            }
+        }
+        if !run.is_empty() {
+            out.push_str(&format!("    .ascii \"{}\"\n", run));
+        }
+        if !raw.is_empty() {
+            out.push_str(&format!("    .byte {}\n", raw.join(", ")));
+        }
+        out
+    }
+
+    fn reserve(&self, label: &str, size: usize) -> String {
+        format!("    .balign 8\n{}:\n    .skip {}\n", label, size)
+    }
+
+    fn lower(&self, inst: &Inst, out: &mut String) {
+        let mut line = |text: String| {
+            out.push_str("    ");
+            out.push_str(&text);
+            out.push('\n');
+        };
+        match inst {
+            Inst::Label(name) => out.push_str(&format!("{}:\n", name)),
+            Inst::Comment(text) => line(format!("// {}", text)),
+            Inst::Imm(r, value) => {
+                for text in mov_imm(reg(*r), *value) {
+                    line(text);
+                }
+            }
+            Inst::Addr(r, label) => {
+                line(format!("adrp {}, {}", reg(*r), label));
+                line(format!("add {}, {}, :lo12:{}", reg(*r), reg(*r), label));
+            }
+            Inst::Load(r, label) => {
+                line(format!("adrp x9, {}", label));
+                line(format!("ldr {}, [x9, :lo12:{}]", reg(*r), label));
+            }
+            Inst::Store(label, r) => {
+                line(format!("adrp x9, {}", label));
+                line(format!("str {}, [x9, :lo12:{}]", reg(*r), label));
+            }
+            Inst::LoadByte(dst, addr) => line(format!("ldrb {}, [{}]", reg32(*dst), reg(*addr))),
+            Inst::StoreByte(addr, src) => line(format!("strb {}, [{}]", reg32(*src), reg(*addr))),
+            Inst::Mov(dst, src) => line(format!("mov {}, {}", reg(*dst), reg(*src))),
+            Inst::Arith(op @ (ArithOp::Rem | ArithOp::URem), dst, src) => {
+                // No remainder instruction: dst - (dst / src) * src
+                let divide = if *op == ArithOp::Rem { "sdiv" } else { "udiv" };
+                line(format!("{} x9, {}, {}", divide, reg(*dst), reg(*src)));
+                line(format!("msub {}, x9, {}, {}", reg(*dst), reg(*src), reg(*dst)));
+            }
+            Inst::Arith(op, dst, src) => {
+                let mnemonic = match op {
+                    ArithOp::Add => "add",
+                    ArithOp::Sub => "sub",
+                    ArithOp::Mul => "mul",
+                    ArithOp::UDiv => "udiv",
+                    _ => "sdiv",
+                };
+                line(format!("{} {}, {}, {}", mnemonic, reg(*dst), reg(*dst), reg(*src)));
+            }
+            Inst::AddImm(r, value) => match *value {
+                0..=4095 => line(format!("add {}, {}, #{}", reg(*r), reg(*r), value)),
+                -4095..=-1 => line(format!("sub {}, {}, #{}", reg(*r), reg(*r), -value)),
+                _ => {
+                    for text in mov_imm("x10", *value) {
+                        line(text);
+                    }
+                    line(format!("add {}, {}, x10", reg(*r), reg(*r)));
+                }
+            },
+            Inst::Cmp(a, b) => line(format!("cmp {}, {}", reg(*a), reg(*b))),
+            Inst::CmpImm(r, value) => match *value {
+                0..=4095 => line(format!("cmp {}, #{}", reg(*r), value)),
+                -4095..=-1 => line(format!("cmn {}, #{}", reg(*r), -value)),
+                _ => {
+                    for text in mov_imm("x10", *value) {
+                        line(text);
+                    }
+                    line(format!("cmp {}, x10", reg(*r)));
+                }
+            },
+            Inst::Jump(label) => line(format!("b {}", label)),
+            Inst::JumpIf(cond, label) => {
+                let suffix = match cond {
+                    Cond::Eq => "eq",
+                    Cond::Ne => "ne",
+                    Cond::Lt => "lt",
+                    Cond::Le => "le",
+                    Cond::Gt => "gt",
+                    Cond::Ge => "ge",
+                    Cond::Below => "lo",
+                    Cond::BelowEq => "ls",
+                    Cond::Above => "hi",
+                    Cond::AboveEq => "hs",
+                };
+                line(format!("b.{} {}", suffix, label));
+            }
+            Inst::Call(name) => {
+                // bl overwrites the link register, so keep the caller's on the stack
+                line("str x30, [sp, #-16]!".to_string());
+                line(format!("bl {}", name));
+                line("ldr x30, [sp], #16".to_string());
+            }
+            Inst::Ret => line("ret".to_string()),
+            Inst::Syscall(call) => {
+                line(format!("mov x8, #{}   // syscall: {:?}", self.syscall_number(*call), call));
+                line("svc #0".to_string());
+            }
+        }
+    }
+}
+
+pub fn transpile_la64(ast: &AST, options: &CodegenOptions) -> Result<String, BackendError> {
+    emit_program(&LA64, "la64", ast, options)
+}
+
+pub struct La64Backend;
+
+impl Backend for La64Backend {
+    fn name(&self) -> &'static str {
+        "la64"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["aarch64", "arm64"]
+    }
+
+    fn description(&self) -> &'static str {
+        "AArch64 Assembly"
+    }
+
+    fn extension(&self) -> &'static str {
+        "s"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        // Values are 64-bit integers or string buffers
+        Capabilities {
+            floats: false,
+            arrays: false,
+            maps: false,
+            records: false,
+            ..Capabilities::ALL
+        }
+    }
+
+    fn emit(&self, ast: &AST, options: &CodegenOptions) -> Result<String, BackendError> {
+        transpile_la64(ast, options)
+    }
+}
@@ -1,903 +1,2284 @@
-use crate::parse::{AST, ASTNode, Expression, MathOperator, VarType, ComparisonOperator, LogicalOperator};
-
-// Helper function to add a global string constant and return its label and length
-fn add_global_llvm_string(
-    raw_string: &str, // The string literal including quotes, e.g., "\"Hello\\n\""
-    global_strings: &mut String,
-    string_counter: &mut usize,
-) -> (String, usize) {
-    // 1. Remove surrounding quotes
-    let content = if raw_string.len() >= 2 && raw_string.starts_with('"') && raw_string.ends_with('"') {
-        &raw_string[1..raw_string.len() - 1]
-    } else {
-        raw_string
+use crate::errs::BackendError;
+use crate::parse::{
+    AST, ASTNode, ComparisonOperator, Expression, IntType, LogicalOperator, MathFn, MathOperator, TextOp, VarType,
+};
+use crate::transpilers::{
+    Backend, Capabilities, CodegenOptions, FormatSpec, Overflow, Segment, ValueKind, clean_number,
+    collect_var_types, element_type, field_type, fit, formatted, int_literal, int_type_of, literal_text, placeholder,
+    is_text_var, math_kind, record_types, split_interpolation, type_kind, uses_conversions, uses_division, uses_math,
+    undeclared, uses_strings, value_kind,
+};
+use std::collections::HashMap;
+
+/// Size of every `str` buffer, including the terminating NUL. A `c[size]`
+/// buffer holds `size` bytes and the NUL.
+const STR_SIZE: usize = 256;
+
+// libc imports, shared constants and the line/number helpers every program uses.
+// Output goes through dprintf, so both streams are written in program order.
+const RUNTIME: &str = r#"declare i32 @dprintf(i32, i8*, ...)
+declare i32 @snprintf(i8*, i64, i8*, ...)
+declare i32 @strcmp(i8*, i8*)
+declare i32 @strncmp(i8*, i8*, i64)
+declare i64 @strlen(i8*)
+declare i64 @strtoll(i8*, i8**, i32)
+declare i64 @strtoull(i8*, i8**, i32)
+declare i32* @__errno_location()
+declare void @exit(i32)
+declare double @llvm.trunc.f64(double)
+declare {i64, i1} @llvm.umul.with.overflow.i64(i64, i64)
+declare double @strtod(i8*, i8**)
+declare i64 @read(i32, i8*, i64)
+declare i8* @memmove(i8*, i8*, i64)
+declare i8* @memset(i8*, i32, i64)
+declare i8* @strchr(i8*, i32)
+
+@ven.line = internal global [256 x i8] zeroinitializer
+@ven.fmt.s = private unnamed_addr constant [3 x i8] c"%s\00"
+@ven.fmt.lld = private unnamed_addr constant [5 x i8] c"%lld\00"
+@ven.fmt.llu = private unnamed_addr constant [5 x i8] c"%llu\00"
+@ven.fmt.e = private unnamed_addr constant [5 x i8] c"%.*e\00"
+@ven.fmt.f = private unnamed_addr constant [5 x i8] c"%.*f\00"
+@ven.nan = private unnamed_addr constant [4 x i8] c"NaN\00"
+@ven.inf = private unnamed_addr constant [4 x i8] c"inf\00"
+@ven.minus.inf = private unnamed_addr constant [5 x i8] c"-inf\00"
+@ven.true = private unnamed_addr constant [5 x i8] c"true\00"
+@ven.false = private unnamed_addr constant [6 x i8] c"false\00"
+
+; Reads one line without its newline; carriage returns and overflow are dropped
+define internal void @ven_read_line(i8* %buf, i64 %size) {
+entry:
+  %c = alloca i8
+  %max = sub i64 %size, 1
+  br label %loop
+loop:
+  %len = phi i64 [0, %entry], [%len, %skip], [%next, %keep]
+  %n = call i64 @read(i32 0, i8* %c, i64 1)
+  %eof = icmp slt i64 %n, 1
+  br i1 %eof, label %done, label %got
+got:
+  %byte = load i8, i8* %c
+  %newline = icmp eq i8 %byte, 10
+  br i1 %newline, label %done, label %check
+check:
+  %cr = icmp eq i8 %byte, 13
+  %full = icmp uge i64 %len, %max
+  %drop = or i1 %cr, %full
+  br i1 %drop, label %skip, label %keep
+skip:
+  br label %loop
+keep:
+  %slot = getelementptr inbounds i8, i8* %buf, i64 %len
+  store i8 %byte, i8* %slot
+  %next = add i64 %len, 1
+  br label %loop
+done:
+  %end = getelementptr inbounds i8, i8* %buf, i64 %len
+  store i8 0, i8* %end
+  ret void
+}
+
+; Copies as much of src as fits without splitting a character, always terminating dest
+define internal void @ven_copy(i8* %dest, i8* %src, i64 %size) {
+entry:
+  %len = call i64 @strlen(i8* %src)
+  %max = sub i64 %size, 1
+  %long = icmp ugt i64 %len, %max
+  br i1 %long, label %cut, label %copy
+cut:
+  %cut.len = phi i64 [%max, %entry], [%back, %inside]
+  %at = getelementptr inbounds i8, i8* %src, i64 %cut.len
+  %byte = load i8, i8* %at
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %nonzero = icmp ugt i64 %cut.len, 0
+  %split = and i1 %continuation, %nonzero
+  br i1 %split, label %inside, label %copy
+inside:
+  %back = sub i64 %cut.len, 1
+  br label %cut
+copy:
+  %n = phi i64 [%len, %entry], [%cut.len, %cut]
+  call i8* @memmove(i8* %dest, i8* %src, i64 %n)
+  %end = getelementptr inbounds i8, i8* %dest, i64 %n
+  store i8 0, i8* %end
+  ret void
+}
+
+define internal i8* @ven_skip_space(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %space]
+  %byte = load i8, i8* %p
+  %sp = icmp eq i8 %byte, 32
+  %low = icmp uge i8 %byte, 9
+  %high = icmp ule i8 %byte, 13
+  %ctl = and i1 %low, %high
+  %ws = or i1 %sp, %ctl
+  br i1 %ws, label %space, label %done
+space:
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %p
+}
+
+define internal i1 @ven_blank(i8* %text) {
+  %p = call i8* @ven_skip_space(i8* %text)
+  %byte = load i8, i8* %p
+  %blank = icmp eq i8 %byte, 0
+  ret i1 %blank
+}
+
+; Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+define internal i128 @ven_to_int(i8* %text) {
+entry:
+  %end = alloca i8*
+  %start = call i8* @ven_skip_space(i8* %text)
+  %errno = call i32* @__errno_location()
+  store i32 0, i32* %errno
+  %first = load i8, i8* %start
+  %minus = icmp eq i8 %first, 45
+  br i1 %minus, label %negative, label %positive
+negative:
+  %signed = call i64 @strtoll(i8* %start, i8** %end, i32 10)
+  %wide.signed = sext i64 %signed to i128
+  br label %check
+positive:
+  %unsigned = call i64 @strtoull(i8* %start, i8** %end, i32 10)
+  %wide.unsigned = zext i64 %unsigned to i128
+  br label %check
+check:
+  %value = phi i128 [%wide.signed, %negative], [%wide.unsigned, %positive]
+  %stop = load i8*, i8** %end
+  %moved = icmp ne i8* %stop, %start
+  %rest = call i1 @ven_blank(i8* %stop)
+  %err = load i32, i32* %errno
+  %in.range = icmp ne i32 %err, 34
+  %number = and i1 %moved, %rest
+  %ok = and i1 %number, %in.range
+  %result = select i1 %ok, i128 %value, i128 0
+  ret i128 %result
+}
+
+define internal double @ven_to_float(i8* %text) {
+  %end = alloca i8*
+  %value = call double @strtod(i8* %text, i8** %end)
+  %stop = load i8*, i8** %end
+  %moved = icmp ne i8* %stop, %text
+  %rest = call i1 @ven_blank(i8* %stop)
+  %ok = and i1 %moved, %rest
+  %result = select i1 %ok, double %value, double 0.0
+  ret double %result
+}
+
+; Only the word true reads as true
+define internal i1 @ven_to_bool(i8* %text) {
+entry:
+  %p = call i8* @ven_skip_space(i8* %text)
+  %cmp = call i32 @strncmp(i8* %p, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.true, i64 0, i64 0), i64 4)
+  %word = icmp eq i32 %cmp, 0
+  br i1 %word, label %rest, label %no
+rest:
+  %after = getelementptr inbounds i8, i8* %p, i64 4
+  %blank = call i1 @ven_blank(i8* %after)
+  ret i1 %blank
+no:
+  ret i1 false
+}
+
+; The shortest text that reads back as %x, without an exponent, in %buf of 400 bytes
+define internal i8* @ven_float_text(i8* %buf, double %x) {
+entry:
+  %short = alloca [32 x i8]
+  %text = getelementptr inbounds [32 x i8], [32 x i8]* %short, i64 0, i64 0
+  %nan = fcmp uno double %x, %x
+  br i1 %nan, label %not.a.number, label %check
+not.a.number:
+  ret i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.nan, i64 0, i64 0)
+check:
+  ; Only an infinity is not finite after x - x
+  %zero = fsub double %x, %x
+  %finite = fcmp oeq double %zero, 0.0
+  br i1 %finite, label %loop, label %infinite
+infinite:
+  %negative = fcmp olt double %x, 0.0
+  %word = select i1 %negative, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.minus.inf, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.inf, i64 0, i64 0)
+  ret i8* %word
+loop:
+  %precision = phi i32 [1, %check], [%next, %retry]
+  %decimals = sub i32 %precision, 1
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %text, i64 32, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.e, i64 0, i64 0), i32 %decimals, double %x)
+  %back = call double @strtod(i8* %text, i8** null)
+  %same = fcmp oeq double %back, %x
+  %last = icmp sge i32 %precision, 17
+  %stop = or i1 %same, %last
+  br i1 %stop, label %found, label %retry
+retry:
+  %next = add i32 %precision, 1
+  br label %loop
+found:
+  %e = call i8* @strchr(i8* %text, i32 101)
+  %after.e = getelementptr inbounds i8, i8* %e, i64 1
+  %exponent.wide = call i64 @strtoll(i8* %after.e, i8** null, i32 10)
+  %exponent = trunc i64 %exponent.wide to i32
+  %fraction = icmp slt i32 %exponent, %decimals
+  br i1 %fraction, label %fixed, label %whole
+fixed:
+  ; Rounding at the last shortest digit gives the same digits written out
+  %places = sub i32 %decimals, %exponent
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %buf, i64 400, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.f, i64 0, i64 0), i32 %places, double %x)
+  ret i8* %buf
+whole:
+  ; The digits without their point, then zeros up to the point
+  store i8 0, i8* %e
+  %first = load i8, i8* %text
+  %minus = icmp eq i8 %first, 45
+  %lead = select i1 %minus, i64 2, i64 1
+  %point = getelementptr inbounds i8, i8* %text, i64 %lead
+  %rest = call i64 @strlen(i8* %point)
+  %after.point = getelementptr inbounds i8, i8* %point, i64 1
+  call i8* @memmove(i8* %point, i8* %after.point, i64 %rest)
+  %len = call i64 @strlen(i8* %text)
+  call i8* @memmove(i8* %buf, i8* %text, i64 %len)
+  %end = getelementptr inbounds i8, i8* %buf, i64 %len
+  %zeros.narrow = sub i32 %exponent, %decimals
+  %zeros = sext i32 %zeros.narrow to i64
+  call i8* @memset(i8* %end, i32 48, i64 %zeros)
+  %stop.at = getelementptr inbounds i8, i8* %end, i64 %zeros
+  store i8 0, i8* %stop.at
+  ret i8* %buf
+}
+
+define internal void @ven_print_float(i32 %fd, double %x) {
+  %buf = alloca [400 x i8]
+  %start = getelementptr inbounds [400 x i8], [400 x i8]* %buf, i64 0, i64 0
+  %text = call i8* @ven_float_text(i8* %start, double %x)
+  call i32 (i32, i8*, ...) @dprintf(i32 %fd, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* %text)
+  ret void
+}
+"#;
+
+// Integer overflow, one body per `--overflow=` mode. `@ven_fit` stores a value
+// into the type running from %min to %max; integers are widened to i128 for math.
+const WRAP_HELPERS: &str = r#"
+define internal i128 @ven_fit(i128 %value, i128 %min, i128 %max, i8* %name, i8* %type, i64 %line) {
+entry:
+  %below = icmp slt i128 %value, %min
+  %above = icmp sgt i128 %value, %max
+  %out = or i1 %below, %above
+  br i1 %out, label %wrap, label %ok
+ok:
+  ret i128 %value
+wrap:
+  %mask = sub i128 %max, %min
+  %span = add i128 %mask, 1
+  %low = and i128 %value, %mask
+  %high = icmp sgt i128 %low, %max
+  %back = sub i128 %low, %span
+  %result = select i1 %high, i128 %back, i128 %low
+  ret i128 %result
+}
+
+; A u64 product, which can exceed i128
+define internal i64 @ven_mul_u64(i64 %a, i64 %b, i8* %name, i64 %line) {
+  %pair = call {i64, i1} @llvm.umul.with.overflow.i64(i64 %a, i64 %b)
+  %product = extractvalue {i64, i1} %pair, 0
+  ret i64 %product
+}
+"#;
+
+const SATURATE_HELPERS: &str = r#"
+define internal i128 @ven_fit(i128 %value, i128 %min, i128 %max, i8* %name, i8* %type, i64 %line) {
+  %below = icmp slt i128 %value, %min
+  %above = icmp sgt i128 %value, %max
+  %raised = select i1 %below, i128 %min, i128 %value
+  %result = select i1 %above, i128 %max, i128 %raised
+  ret i128 %result
+}
+
+; A u64 product, which can exceed i128
+define internal i64 @ven_mul_u64(i64 %a, i64 %b, i8* %name, i64 %line) {
+  %pair = call {i64, i1} @llvm.umul.with.overflow.i64(i64 %a, i64 %b)
+  %product = extractvalue {i64, i1} %pair, 0
+  %over = extractvalue {i64, i1} %pair, 1
+  %result = select i1 %over, i64 -1, i64 %product
+  ret i64 %result
+}
+"#;
+
+const TRAP_HELPERS: &str = r#"
+@ven.fmt.overflow = private unnamed_addr constant [59 x i8] c"runtime error: integer overflow in '%s' (%s) on line %lld\0A\00"
+@ven.u64 = private unnamed_addr constant [4 x i8] c"u64\00"
+
+define internal void @ven_overflow(i8* %name, i8* %type, i64 %line) {
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([59 x i8], [59 x i8]* @ven.fmt.overflow, i64 0, i64 0), i8* %name, i8* %type, i64 %line)
+  call void @exit(i32 1)
+  unreachable
+}
+
+define internal i128 @ven_fit(i128 %value, i128 %min, i128 %max, i8* %name, i8* %type, i64 %line) {
+entry:
+  %below = icmp slt i128 %value, %min
+  %above = icmp sgt i128 %value, %max
+  %out = or i1 %below, %above
+  br i1 %out, label %trap, label %ok
+ok:
+  ret i128 %value
+trap:
+  call void @ven_overflow(i8* %name, i8* %type, i64 %line)
+  unreachable
+}
+
+; A u64 product, which can exceed i128
+define internal i64 @ven_mul_u64(i64 %a, i64 %b, i8* %name, i64 %line) {
+entry:
+  %pair = call {i64, i1} @llvm.umul.with.overflow.i64(i64 %a, i64 %b)
+  %product = extractvalue {i64, i1} %pair, 0
+  %over = extractvalue {i64, i1} %pair, 1
+  br i1 %over, label %trap, label %ok
+ok:
+  ret i64 %product
+trap:
+  call void @ven_overflow(i8* %name, i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.u64, i64 0, i64 0), i64 %line)
+  unreachable
+}
+"#;
+
+// The division by zero checks, unless `--no-runtime-checks` drops them
+const DIVISION_HELPERS: &str = r#"
+@ven.fmt.div_zero = private unnamed_addr constant [54 x i8] c"runtime error: division of '%s' by zero on line %lld\0A\00"
+
+define internal void @ven_div_zero(i8* %name, i64 %line) {
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([54 x i8], [54 x i8]* @ven.fmt.div_zero, i64 0, i64 0), i8* %name, i64 %line)
+  call void @exit(i32 1)
+  unreachable
+}
+"#;
+
+// `**` and the math functions, for programs that use them. `frem` and
+// `@llvm.pow.f64` become calls to libm's fmod and pow.
+const MATH_HELPERS: &str = r#"
+declare double @llvm.pow.f64(double, double)
+declare double @llvm.fabs.f64(double)
+declare double @llvm.sqrt.f64(double)
+declare double @llvm.floor.f64(double)
+declare double @llvm.ceil.f64(double)
+declare double @llvm.round.f64(double)
+
+; x ** y, with a negative y giving 1 / x ** -y truncated toward zero. A power
+; of 2^64 or more in size comes back as a stand-in outside every integer type,
+; with its sign and low 64 bits, which @ven_fit handles as it would the power
+define internal i128 @ven_pow(i128 %x, i128 %y) {
+entry:
+  %zero = icmp eq i128 %y, 0
+  br i1 %zero, label %one, label %small
+one:
+  ret i128 1
+small:
+  %odd = trunc i128 %y to i1
+  %same = icmp ule i128 %x, 1
+  br i1 %same, label %itself, label %minus.one
+itself:
+  ret i128 %x
+minus.one:
+  %is.minus.one = icmp eq i128 %x, -1
+  br i1 %is.minus.one, label %sign, label %below.one
+sign:
+  %sign.value = select i1 %odd, i128 -1, i128 1
+  ret i128 %sign.value
+below.one:
+  %fraction = icmp slt i128 %y, 0
+  br i1 %fraction, label %none, label %start
+none:
+  ret i128 0
+start:
+  %negative.x = icmp slt i128 %x, 0
+  %minus.x = sub i128 0, %x
+  %magnitude = select i1 %negative.x, i128 %minus.x, i128 %x
+  %negative = and i1 %negative.x, %odd
+  br label %loop
+loop:
+  %power = phi i128 [1, %start], [%next, %more]
+  %count = phi i128 [0, %start], [%counted, %more]
+  %next = mul i128 %power, %magnitude
+  %counted = add i128 %count, 1
+  %big = icmp uge i128 %next, 18446744073709551616
+  br i1 %big, label %low, label %more
+more:
+  %done = icmp eq i128 %counted, %y
+  br i1 %done, label %exact, label %loop
+exact:
+  %minus.power = sub i128 0, %next
+  %result = select i1 %negative, i128 %minus.power, i128 %next
+  ret i128 %result
+low:
+  %base.start = trunc i128 %x to i64
+  %exponent.start = trunc i128 %y to i64
+  br label %square
+square:
+  %bits = phi i64 [1, %low], [%bits.next, %square]
+  %base = phi i64 [%base.start, %low], [%base.next, %square]
+  %exponent = phi i64 [%exponent.start, %low], [%exponent.next, %square]
+  %bit = trunc i64 %exponent to i1
+  %product = mul i64 %bits, %base
+  %bits.next = select i1 %bit, i64 %product, i64 %bits
+  %base.next = mul i64 %base, %base
+  %exponent.next = lshr i64 %exponent, 1
+  %left = icmp ne i64 %exponent.next, 0
+  br i1 %left, label %square, label %stand.in
+stand.in:
+  %wide = zext i64 %bits.next to i128
+  %under = sub i128 %wide, 36893488147419103232
+  %over = add i128 %wide, 18446744073709551616
+  %stand = select i1 %negative, i128 %under, i128 %over
+  ret i128 %stand
+}
+"#;
+
+// Growth and bounds checks, for programs with arrays. An array is a
+// `{items, len, cap}` global; its elements live in memory from realloc.
+const ARRAY_HELPERS: &str = r#"
+declare i8* @realloc(i8*, i64)
+
+@ven.fmt.oom = private unnamed_addr constant [30 x i8] c"runtime error: out of memory\0A\00"
+@ven.fmt.index = private unnamed_addr constant [22 x i8] c"runtime error: index \00"
+@ven.fmt.bounds = private unnamed_addr constant [55 x i8] c" is out of bounds for '%s' (length %llu) on line %lld\0A\00"
+@ven.fmt.sep = private unnamed_addr constant [3 x i8] c", \00"
+@ven.fmt.none = private unnamed_addr constant [1 x i8] zeroinitializer
+
+; Makes room for one more element of %size bytes
+define internal void @ven_grow({i8*, i64, i64}* %array, i64 %size) {
+entry:
+  %items.ptr = getelementptr inbounds {i8*, i64, i64}, {i8*, i64, i64}* %array, i32 0, i32 0
+  %len.ptr = getelementptr inbounds {i8*, i64, i64}, {i8*, i64, i64}* %array, i32 0, i32 1
+  %cap.ptr = getelementptr inbounds {i8*, i64, i64}, {i8*, i64, i64}* %array, i32 0, i32 2
+  %len = load i64, i64* %len.ptr
+  %cap = load i64, i64* %cap.ptr
+  %full = icmp uge i64 %len, %cap
+  br i1 %full, label %grow, label %done
+grow:
+  %empty = icmp eq i64 %cap, 0
+  %doubled = shl i64 %cap, 1
+  %new.cap = select i1 %empty, i64 8, i64 %doubled
+  %bytes = mul i64 %new.cap, %size
+  %items = load i8*, i8** %items.ptr
+  %new.items = call i8* @realloc(i8* %items, i64 %bytes)
+  %failed = icmp eq i8* %new.items, null
+  br i1 %failed, label %oom, label %store
+oom:
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([30 x i8], [30 x i8]* @ven.fmt.oom, i64 0, i64 0))
+  call void @exit(i32 1)
+  unreachable
+store:
+  store i8* %new.items, i8** %items.ptr
+  store i64 %new.cap, i64* %cap.ptr
+  br label %done
+done:
+  ret void
+}
+
+; The position of %index in the array %name of %len elements
+define internal i64 @ven_index(i128 %index, i64 %len, i8* %name, i64 %line) {
+entry:
+  %negative = icmp slt i128 %index, 0
+  %wide.len = zext i64 %len to i128
+  %past = icmp sge i128 %index, %wide.len
+  %out = or i1 %negative, %past
+  br i1 %out, label %fail, label %ok
+ok:
+  %position = trunc i128 %index to i64
+  ret i64 %position
+fail:
+  %narrow = trunc i128 %index to i64
+  %fmt = select i1 %negative, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.llu, i64 0, i64 0)
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([22 x i8], [22 x i8]* @ven.fmt.index, i64 0, i64 0))
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* %fmt, i64 %narrow)
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([55 x i8], [55 x i8]* @ven.fmt.bounds, i64 0, i64 0), i8* %name, i64 %len, i64 %line)
+  call void @exit(i32 1)
+  unreachable
+}
+"#;
+
+// `i()`, `f()` and `str()`, and `{name:spec}` layout, for programs that use them
+const CONVERT_HELPERS: &str = r#"
+@ven.fmt.convert = private unnamed_addr constant [55 x i8] c"runtime error: cannot convert \22%s\22 to %s on line %lld\0A\00"
+@ven.fmt.pad.right = private unnamed_addr constant [6 x i8] c"%*s%s\00"
+@ven.fmt.pad.left = private unnamed_addr constant [6 x i8] c"%s%*s\00"
+@ven.to.i = private unnamed_addr constant [2 x i8] c"i\00"
+@ven.to.f = private unnamed_addr constant [2 x i8] c"f\00"
+@ven.empty = private unnamed_addr constant [1 x i8] zeroinitializer
+
+define internal void @ven_convert_failed(i8* %text, i8* %to, i64 %line) {
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([55 x i8], [55 x i8]* @ven.fmt.convert, i64 0, i64 0), i8* %text, i8* %to, i64 %line)
+  call void @exit(i32 1)
+  unreachable
+}
+
+; Skips spaces, tabs and line breaks
+define internal i8* @ven_skip_blank(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %blank]
+  %byte = load i8, i8* %p
+  %space = icmp eq i8 %byte, 32
+  %tab = icmp eq i8 %byte, 9
+  %newline = icmp eq i8 %byte, 10
+  %return = icmp eq i8 %byte, 13
+  %gap = or i1 %space, %tab
+  %line = or i1 %newline, %return
+  %is.blank = or i1 %gap, %line
+  br i1 %is.blank, label %blank, label %done
+blank:
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %p
+}
+
+define internal i8* @ven_skip_digits(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %digit]
+  %byte = load i8, i8* %p
+  %offset = sub i8 %byte, 48
+  %is.digit = icmp ult i8 %offset, 10
+  br i1 %is.digit, label %digit, label %done
+digit:
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %p
+}
+
+define internal i8* @ven_skip_sign(i8* %text) {
+  %byte = load i8, i8* %text
+  %plus = icmp eq i8 %byte, 43
+  %minus = icmp eq i8 %byte, 45
+  %sign = or i1 %plus, %minus
+  %step = zext i1 %sign to i64
+  %after = getelementptr inbounds i8, i8* %text, i64 %step
+  ret i8* %after
+}
+
+; A sign and decimal digits within i64, with spaces around them
+define internal i128 @ven_convert_int(i8* %text, i64 %line) {
+entry:
+  %start = call i8* @ven_skip_blank(i8* %text)
+  %digits = call i8* @ven_skip_sign(i8* %start)
+  %end = call i8* @ven_skip_digits(i8* %digits)
+  %some = icmp ne i8* %end, %digits
+  %rest = call i8* @ven_skip_blank(i8* %end)
+  %byte = load i8, i8* %rest
+  %over = icmp eq i8 %byte, 0
+  %whole = and i1 %some, %over
+  br i1 %whole, label %parse, label %fail
+parse:
+  %errno = call i32* @__errno_location()
+  store i32 0, i32* %errno
+  %value = call i64 @strtoll(i8* %start, i8** null, i32 10)
+  %err = load i32, i32* %errno
+  %range = icmp eq i32 %err, 34
+  br i1 %range, label %fail, label %ok
+ok:
+  %wide = sext i64 %value to i128
+  ret i128 %wide
+fail:
+  call void @ven_convert_failed(i8* %text, i8* getelementptr inbounds ([2 x i8], [2 x i8]* @ven.to.i, i64 0, i64 0), i64 %line)
+  unreachable
+}
+
+; A finite decimal number like -1.5, .5, 2. or 6.02e23, with spaces around it
+define internal double @ven_convert_float(i8* %text, i64 %line) {
+entry:
+  %start = call i8* @ven_skip_blank(i8* %text)
+  %whole = call i8* @ven_skip_sign(i8* %start)
+  %point = call i8* @ven_skip_digits(i8* %whole)
+  %byte = load i8, i8* %point
+  %dot = icmp eq i8 %byte, 46
+  %step = zext i1 %dot to i64
+  %fraction = getelementptr inbounds i8, i8* %point, i64 %step
+  %mantissa = call i8* @ven_skip_digits(i8* %fraction)
+  %whole.digits = icmp ne i8* %point, %whole
+  %fraction.digits = icmp ne i8* %mantissa, %fraction
+  %digits = or i1 %whole.digits, %fraction.digits
+  %mark = load i8, i8* %mantissa
+  %lower = icmp eq i8 %mark, 101
+  %upper = icmp eq i8 %mark, 69
+  %has.exponent = or i1 %lower, %upper
+  br i1 %has.exponent, label %exponent, label %tail
+exponent:
+  %after.e = getelementptr inbounds i8, i8* %mantissa, i64 1
+  %exponent.digits = call i8* @ven_skip_sign(i8* %after.e)
+  %exponent.end = call i8* @ven_skip_digits(i8* %exponent.digits)
+  %exponent.some = icmp ne i8* %exponent.end, %exponent.digits
+  br label %tail
+tail:
+  %end = phi i8* [%mantissa, %entry], [%exponent.end, %exponent]
+  %exponent.ok = phi i1 [true, %entry], [%exponent.some, %exponent]
+  %rest = call i8* @ven_skip_blank(i8* %end)
+  %last = load i8, i8* %rest
+  %over = icmp eq i8 %last, 0
+  %number = and i1 %digits, %exponent.ok
+  %form = and i1 %number, %over
+  br i1 %form, label %parse, label %fail
+parse:
+  %value = call double @strtod(i8* %start, i8** null)
+  %zero = fsub double %value, %value
+  %finite = fcmp oeq double %zero, 0.0
+  br i1 %finite, label %ok, label %fail
+ok:
+  ret double %value
+fail:
+  call void @ven_convert_failed(i8* %text, i8* getelementptr inbounds ([2 x i8], [2 x i8]* @ven.to.f, i64 0, i64 0), i64 %line)
+  unreachable
+}
+
+; Truncated toward zero, with NaN as 0 and clamped at the i64 limits
+define internal i128 @ven_float_to_int(double %x) {
+  %nan = fcmp uno double %x, %x
+  %low = fcmp ole double %x, 0xC3E0000000000000
+  %high = fcmp oge double %x, 0x43E0000000000000
+  %int = fptosi double %x to i64
+  %wide = sext i64 %int to i128
+  %raised = select i1 %low, i128 -9223372036854775808, i128 %wide
+  %capped = select i1 %high, i128 9223372036854775807, i128 %raised
+  %result = select i1 %nan, i128 0, i128 %capped
+  ret i128 %result
+}
+
+; %precision digits after the point, rounded half to even, in %buf of 400 bytes
+define internal i8* @ven_fixed_text(i8* %buf, double %x, i32 %precision) {
+entry:
+  %zero = fsub double %x, %x
+  %finite = fcmp oeq double %zero, 0.0
+  br i1 %finite, label %fixed, label %plain
+fixed:
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %buf, i64 400, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.f, i64 0, i64 0), i32 %precision, double %x)
+  ret i8* %buf
+plain:
+  %text = call i8* @ven_float_text(i8* %buf, double %x)
+  ret i8* %text
+}
+
+; Prints %text padded with spaces to %width characters, before it when %right
+define internal void @ven_pad(i32 %fd, i8* %text, i64 %width, i1 %right) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %count]
+  %chars = phi i64 [0, %entry], [%more, %count]
+  %byte = load i8, i8* %p
+  %end = icmp eq i8 %byte, 0
+  br i1 %end, label %done, label %count
+count:
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %first = xor i1 %continuation, true
+  %add = zext i1 %first to i64
+  %more = add i64 %chars, %add
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  %narrow = icmp ult i64 %chars, %width
+  %gap = sub i64 %width, %chars
+  %spaces.wide = select i1 %narrow, i64 %gap, i64 0
+  %spaces = trunc i64 %spaces.wide to i32
+  %empty = getelementptr inbounds [1 x i8], [1 x i8]* @ven.empty, i64 0, i64 0
+  br i1 %right, label %before, label %after
+before:
+  call i32 (i32, i8*, ...) @dprintf(i32 %fd, i8* getelementptr inbounds ([6 x i8], [6 x i8]* @ven.fmt.pad.right, i64 0, i64 0), i32 %spaces, i8* %empty, i8* %text)
+  ret void
+after:
+  call i32 (i32, i8*, ...) @dprintf(i32 %fd, i8* getelementptr inbounds ([6 x i8], [6 x i8]* @ven.fmt.pad.left, i64 0, i64 0), i8* %text, i32 %spaces, i8* %empty)
+  ret void
+}
+"#;
+
+// `concat()`, `slice()` and the other string operations, for programs that use
+// them. Each result is written into its own `@ven.text` buffer of 256 bytes.
+const STRING_HELPERS: &str = r#"
+declare i8* @strstr(i8*, i8*)
+
+; Appends %n bytes of %src at %len in %buf, cutting at the last whole character
+; that fits, and returns the new length; once text is cut nothing more is added
+define internal i64 @ven_put(i8* %buf, i64 %len, i8* %src, i64 %n) {
+entry:
+  %total = add i64 %len, %n
+  %fits = icmp ult i64 %total, 256
+  br i1 %fits, label %copy, label %cut
+cut:
+  %room = sub i64 255, %len
+  br label %back
+back:
+  %keep = phi i64 [%room, %cut], [%less, %inside]
+  %at = getelementptr inbounds i8, i8* %src, i64 %keep
+  %byte = load i8, i8* %at
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %nonzero = icmp ugt i64 %keep, 0
+  %split = and i1 %continuation, %nonzero
+  br i1 %split, label %inside, label %copy
+inside:
+  %less = sub i64 %keep, 1
+  br label %back
+copy:
+  %count = phi i64 [%n, %entry], [%keep, %back]
+  %next = phi i64 [%total, %entry], [255, %back]
+  %dest = getelementptr inbounds i8, i8* %buf, i64 %len
+  call i8* @memmove(i8* %dest, i8* %src, i64 %count)
+  %end = getelementptr inbounds i8, i8* %dest, i64 %count
+  store i8 0, i8* %end
+  ret i64 %next
+}
+
+define internal i64 @ven_append(i8* %buf, i64 %len, i8* %src) {
+  %n = call i64 @strlen(i8* %src)
+  %next = call i64 @ven_put(i8* %buf, i64 %len, i8* %src, i64 %n)
+  ret i64 %next
+}
+
+; The number of characters in %text
+define internal i64 @ven_len(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %count]
+  %chars = phi i64 [0, %entry], [%more, %count]
+  %byte = load i8, i8* %p
+  %end = icmp eq i8 %byte, 0
+  br i1 %end, label %done, label %count
+count:
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %first = xor i1 %continuation, true
+  %add = zext i1 %first to i64
+  %more = add i64 %chars, %add
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i64 %chars
+}
+
+; The byte offset of character %index, or the length when there are fewer
+define internal i64 @ven_offset(i8* %text, i64 %index) {
+entry:
+  br label %loop
+loop:
+  %at = phi i64 [0, %entry], [%next, %check]
+  %chars = phi i64 [0, %entry], [%more, %check]
+  %p = getelementptr inbounds i8, i8* %text, i64 %at
+  %byte = load i8, i8* %p
+  %end = icmp eq i8 %byte, 0
+  br i1 %end, label %done, label %look
+look:
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %first = xor i1 %continuation, true
+  %reached = icmp eq i64 %chars, %index
+  %found = and i1 %first, %reached
+  br i1 %found, label %done, label %check
+check:
+  %add = zext i1 %first to i64
+  %more = add i64 %chars, %add
+  %next = add i64 %at, 1
+  br label %loop
+done:
+  ret i64 %at
+}
+
+; A character index from 0 up to the i64 maximum
+define internal i64 @ven_char_index(i128 %index) {
+  %negative = icmp slt i128 %index, 0
+  %large = icmp sgt i128 %index, 9223372036854775807
+  %low = select i1 %negative, i128 0, i128 %index
+  %clamped = select i1 %large, i128 9223372036854775807, i128 %low
+  %narrow = trunc i128 %clamped to i64
+  ret i64 %narrow
+}
+
+define internal i8* @ven_slice(i8* %buf, i8* %text, i128 %start, i128 %end) {
+entry:
+  %first = call i64 @ven_char_index(i128 %start)
+  %last = call i64 @ven_char_index(i128 %end)
+  %from = call i64 @ven_offset(i8* %text, i64 %first)
+  %to = call i64 @ven_offset(i8* %text, i64 %last)
+  store i8 0, i8* %buf
+  %some = icmp ugt i64 %to, %from
+  br i1 %some, label %put, label %done
+put:
+  %src = getelementptr inbounds i8, i8* %text, i64 %from
+  %n = sub i64 %to, %from
+  call i64 @ven_put(i8* %buf, i64 0, i8* %src, i64 %n)
+  br label %done
+done:
+  ret i8* %buf
+}
+
+; Adds %delta to the ASCII letters from %first, so only ASCII letters change case
+define internal i8* @ven_case(i8* %buf, i8* %text, i8 %first, i8 %delta) {
+entry:
+  call i64 @ven_append(i8* %buf, i64 0, i8* %text)
+  br label %loop
+loop:
+  %p = phi i8* [%buf, %entry], [%next, %letter]
+  %byte = load i8, i8* %p
+  %end = icmp eq i8 %byte, 0
+  br i1 %end, label %done, label %letter
+letter:
+  %offset = sub i8 %byte, %first
+  %is.letter = icmp ult i8 %offset, 26
+  %changed = add i8 %byte, %delta
+  %new = select i1 %is.letter, i8 %changed, i8 %byte
+  store i8 %new, i8* %p
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %buf
+}
+
+define internal i1 @ven_trimmed(i8 %byte) {
+  %space = icmp eq i8 %byte, 32
+  %tab = icmp eq i8 %byte, 9
+  %newline = icmp eq i8 %byte, 10
+  %return = icmp eq i8 %byte, 13
+  %gap = or i1 %space, %tab
+  %line = or i1 %newline, %return
+  %trimmed = or i1 %gap, %line
+  ret i1 %trimmed
+}
+
+; %text without spaces, tabs and line breaks at either end
+define internal i8* @ven_trim(i8* %buf, i8* %text) {
+entry:
+  %len = call i64 @strlen(i8* %text)
+  br label %front
+front:
+  %start = phi i64 [0, %entry], [%after, %skip]
+  %left = icmp ult i64 %start, %len
+  br i1 %left, label %look, label %tail
+look:
+  %p = getelementptr inbounds i8, i8* %text, i64 %start
+  %byte = load i8, i8* %p
+  %blank = call i1 @ven_trimmed(i8 %byte)
+  br i1 %blank, label %skip, label %tail
+skip:
+  %after = add i64 %start, 1
+  br label %front
+tail:
+  %stop = phi i64 [%len, %front], [%len, %look], [%before, %peek]
+  %more = icmp ugt i64 %stop, %start
+  br i1 %more, label %peek, label %copy
+peek:
+  %before = sub i64 %stop, 1
+  %q = getelementptr inbounds i8, i8* %text, i64 %before
+  %last = load i8, i8* %q
+  %end.blank = call i1 @ven_trimmed(i8 %last)
+  br i1 %end.blank, label %tail, label %copy
+copy:
+  %src = getelementptr inbounds i8, i8* %text, i64 %start
+  %n = sub i64 %stop, %start
+  store i8 0, i8* %buf
+  call i64 @ven_put(i8* %buf, i64 0, i8* %src, i64 %n)
+  ret i8* %buf
+}
+
+define internal i1 @ven_contains(i8* %text, i8* %part) {
+  %found = call i8* @strstr(i8* %text, i8* %part)
+  %some = icmp ne i8* %found, null
+  ret i1 %some
+}
+
+define internal i1 @ven_starts_with(i8* %text, i8* %prefix) {
+  %n = call i64 @strlen(i8* %prefix)
+  %cmp = call i32 @strncmp(i8* %text, i8* %prefix, i64 %n)
+  %same = icmp eq i32 %cmp, 0
+  ret i1 %same
+}
+
+; Every %from, left to right; an empty %from leaves the text as it is
+define internal i8* @ven_replace(i8* %buf, i8* %text, i8* %from, i8* %to) {
+entry:
+  %from.len = call i64 @strlen(i8* %from)
+  %to.len = call i64 @strlen(i8* %to)
+  store i8 0, i8* %buf
+  %empty = icmp eq i64 %from.len, 0
+  br i1 %empty, label %rest, label %loop
+loop:
+  %at = phi i8* [%text, %entry], [%skip, %hit]
+  %len = phi i64 [0, %entry], [%after.to, %hit]
+  %found = call i8* @strstr(i8* %at, i8* %from)
+  %none = icmp eq i8* %found, null
+  br i1 %none, label %rest, label %hit
+hit:
+  %at.int = ptrtoint i8* %at to i64
+  %found.int = ptrtoint i8* %found to i64
+  %gap = sub i64 %found.int, %at.int
+  %after.gap = call i64 @ven_put(i8* %buf, i64 %len, i8* %at, i64 %gap)
+  %after.to = call i64 @ven_put(i8* %buf, i64 %after.gap, i8* %to, i64 %to.len)
+  %skip = getelementptr inbounds i8, i8* %found, i64 %from.len
+  br label %loop
+rest:
+  %tail = phi i8* [%text, %entry], [%at, %loop]
+  %tail.len = phi i64 [0, %entry], [%len, %loop]
+  call i64 @ven_append(i8* %buf, i64 %tail.len, i8* %tail)
+  ret i8* %buf
+}
+"#;
+
+/// The LLVM type an array element is stored as, and its size in bytes.
+fn element_layout(element: &VarType) -> (String, usize) {
+    match element {
+        VarType::Int(int_type) => (format!("i{}", int_type.bits), int_type.bits as usize / 8),
+        VarType::Float => ("double".to_string(), 8),
+        VarType::Bool => ("i1".to_string(), 1),
+        _ => (format!("[{} x i8]", STR_SIZE), STR_SIZE),
+    }
+}
+
+/// The LLVM type a variable or record field of `var_type` is stored as.
+fn storage_type(var_type: &VarType) -> String {
+    match var_type {
+        VarType::Int(int_type) => format!("i{}", int_type.bits),
+        VarType::Float => "double".to_string(),
+        VarType::Bool => "i1".to_string(),
+        VarType::Array(_) => "{i8*, i64, i64}".to_string(),
+        VarType::Record(record) => format!("%record.{}", record.name),
+        _ => format!("[{} x i8]", buffer_size(Some(var_type))),
+    }
+}
+
+/// Bytes of the buffer of a `str` or `c[size]` variable, including the NUL.
+fn buffer_size(var_type: Option<&VarType>) -> usize {
+    match var_type {
+        Some(VarType::Char { size }) => size + 1,
+        _ => STR_SIZE,
+    }
+}
+
+/// A constant pointer to field `field` of the array global `name`: 0 is the
+/// items, 1 the length and 2 the capacity.
+fn array_field(name: &str, field: usize) -> String {
+    format!("getelementptr inbounds ({{i8*, i64, i64}}, {{i8*, i64, i64}}* @v.{}, i32 0, i32 {})", name, field)
+}
+
+/// `@ven_fit_float`: floats never wrap. They are truncated, and out of range
+/// they clamp at the limits (%low and %high are the limits as doubles, the
+/// upper one exclusive) or trap. `out_of_range` branches from `%number`.
+fn fit_float_helper(overflow: Overflow) -> String {
+    let out_of_range = match overflow {
+        Overflow::Trap => "  %out = or i1 %below, %above
+  br i1 %out, label %trap, label %convert
+trap:
+  call void @ven_overflow(i8* %name, i8* %type, i64 %line)
+  unreachable",
+        _ => "  br i1 %below, label %clamp.min, label %check
+clamp.min:
+  ret i128 %min
+check:
+  br i1 %above, label %clamp.max, label %convert
+clamp.max:
+  ret i128 %max",
     };
+    format!(
+        r#"
+define internal i128 @ven_fit_float(double %value, i128 %min, i128 %max, double %low, double %high, i8* %name, i8* %type, i64 %line) {{
+entry:
+  %nan = fcmp uno double %value, %value
+  br i1 %nan, label %zero, label %number
+zero:
+  ret i128 0
+number:
+  %whole = call double @llvm.trunc.f64(double %value)
+  %below = fcmp olt double %whole, %low
+  %above = fcmp oge double %whole, %high
+{}
+convert:
+  %negative = fcmp olt double %whole, 0.0
+  br i1 %negative, label %signed, label %unsigned
+signed:
+  %s = fptosi double %whole to i64
+  %wide.s = sext i64 %s to i128
+  ret i128 %wide.s
+unsigned:
+  %u = fptoui double %whole to i64
+  %wide.u = zext i64 %u to i128
+  ret i128 %wide.u
+}}
+"#,
+        out_of_range
+    )
+}
 
-    // 2. Process C-style escape sequences into actual bytes
-    let mut bytes = Vec::new();
-    let mut chars = content.chars().peekable();
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            match chars.next() {
-                Some('n') => bytes.push(b'\n'),
-                Some('t') => bytes.push(b'\t'),
-                Some('r') => bytes.push(b'\r'),
-                Some('\\') => bytes.push(b'\\'),
-                Some('"') => bytes.push(b'"'),
-                Some(other) => { bytes.push(b'\\'); bytes.push(other as u8); }
-                None => bytes.push(b'\\'),
-            }
-        } else {
-            bytes.push(c as u8);
+/// A `double` constant. Hex is the only float syntax LLVM accepts for every value.
+fn double(value: f64) -> String {
+    format!("0x{:016X}", value.to_bits())
+}
+
+/// An `i8*` constant pointing at the first byte of a global array.
+fn array_ptr(global: &str, len: usize) -> String {
+    format!(
+        "getelementptr inbounds ([{len} x i8], [{len} x i8]* {global}, i64 0, i64 0)",
+        len = len,
+        global = global
+    )
+}
+
+/// Bytes of `text` in LLVM `c"..."` syntax, NUL terminator included.
+fn c_bytes(text: &str) -> String {
+    let mut escaped = String::new();
+    for byte in text.bytes().chain(std::iter::once(0)) {
+        match byte {
+            b'"' | b'\\' => escaped.push_str(&format!("\\{:02X}", byte)),
+            0x20..=0x7E => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("\\{:02X}", byte)),
         }
     }
+    escaped
+}
 
-    // 3. Add the null terminator byte
-    bytes.push(0u8);
+struct LlvmGen<'a> {
+    var_types: &'a HashMap<String, VarType>,
+    overflow: Overflow,
+    /// Whether `/`, `%` and integer `**` check for division by zero.
+    checks: bool,
+    /// String constants, deduplicated by content.
+    strings: Vec<String>,
+    body: String,
+    temps: usize,
+    labels: usize,
+    /// Labels of array loops, each with an `@ven.each` counter.
+    counters: Vec<usize>,
+    /// Sizes of the `@ven.text` buffers that `str()` and format specs write into.
+    buffers: Vec<usize>,
+    /// The source line of the statement being emitted, named by runtime errors.
+    statement_line: usize,
+}
 
-    // 4. Calculate final byte length (now includes null terminator)
-    let llvm_len = bytes.len();
+impl LlvmGen<'_> {
+    fn temp(&mut self) -> String {
+        self.temps += 1;
+        format!("%t{}", self.temps)
+    }
 
-    // 5. Escape bytes for LLVM c"" string syntax
-    let mut llvm_escaped = String::new();
-    for byte in &bytes { // Iterate over bytes including the null terminator
-        match byte {
-            // ASCII printable range, excluding \ and "
-            0x20..=0x21 | 0x23..=0x5B | 0x5D..=0x7E => llvm_escaped.push(*byte as char),
-            b'"' => llvm_escaped.push_str("\\22"),
-            b'\\' => llvm_escaped.push_str("\\5C"),
-            // Use hex escape for null byte and other non-printables
-            _ => llvm_escaped.push_str(&format!("\\{:02X}", byte)),
+    fn line(&mut self, instruction: &str) {
+        self.body.push_str("  ");
+        self.body.push_str(instruction);
+        self.body.push('\n');
+    }
+
+    /// An `i8*` to a NUL-terminated constant holding `text`.
+    fn literal(&mut self, text: &str) -> String {
+        let index = match self.strings.iter().position(|existing| existing == text) {
+            Some(index) => index,
+            None => {
+                self.strings.push(text.to_string());
+                self.strings.len() - 1
+            }
+        };
+        array_ptr(&format!("@.str.{}", index), text.len() + 1)
+    }
+
+    /// An `i8*` to a new buffer of `size` bytes for text built at runtime.
+    fn buffer(&mut self, size: usize) -> String {
+        self.buffers.push(size);
+        array_ptr(&format!("@ven.text.{}", self.buffers.len()), size)
+    }
+
+    /// The type of the variable `target`, or of the field when it is `record.field`.
+    fn target_type(&self, target: &str) -> Option<&VarType> {
+        match target.split_once('.') {
+            Some((name, field)) => field_type(name, field, self.var_types),
+            None => self.var_types.get(target),
         }
     }
 
-    // 6. Generate LLVM IR definition (without appending extra \00)
-    let label = format!("str{}", *string_counter);
-    *string_counter += 1;
-    global_strings.push_str(&format!(
-        "@{} = private unnamed_addr constant [{} x i8] c\"{}\", align 1\n",
-        label, llvm_len, llvm_escaped
-    ));
-
-    // 7. Return label and length
-    (label, llvm_len)
-}
-
-pub fn transpile_llvm(ast: &AST) -> String {
-    //let mut code = String::new();
-    let mut global_strings = String::new();
-    let mut string_counter = 0;
-    let mut temp_var_counter = 0; // Unique temp variable counter
-    let mut label_counter = 0; // Label counter for conditional blocks
-    let mut all_variables = std::collections::HashSet::new(); // Track all variables used
-    let mut var_declarations_seen = std::collections::HashSet::new(); // Track variables already allocated
-    let mut global_string_labels = std::collections::HashSet::new(); // Track global string labels
-
-    // First pass: collect all variables used in the AST
-    collect_all_variables(ast, &mut all_variables);
-
-    // Declare printf and scanf
-    global_strings.push_str("declare i32 @printf(i8*, ...)\n");
-    global_strings.push_str("declare i32 @scanf(i8*, ...)\n");
-
-    // Add scanf format string
-    let scanf_fmt_label = format!("scanf_fmt{}", string_counter);
-    string_counter += 1;
-    global_strings.push_str(&format!(
-        "@{} = private unnamed_addr constant [3 x i8] c\"%d\\00\", align 1\n",
-        scanf_fmt_label
-    ));
-    global_string_labels.insert(scanf_fmt_label.clone());
-
-    let mut main_code = String::new();
-    main_code.push_str("define i32 @main() {\n");
-    
-    // Allocate all variables at the beginning
-    for var_name in &all_variables {
-        // For simplicity, assuming all variables are i32 with align 4
-        let sanitized_name = sanitize_name(var_name);
-        main_code.push_str(&format!("  %{} = alloca i32, align 4\n", sanitized_name));
-        var_declarations_seen.insert(sanitized_name);
-    }
-
-    // Rest of the code generation logic
-    match ast {
-        AST::Program(nodes) => {
-            for node in nodes {
-                match node {
-                    ASTNode::VarDeclaration {
-                        name,
-                        var_type,
-                        value,
-                        ..
-                    } => {
-                        let sanitized_name = sanitize_name(name);
-                        let llvm_type = match var_type {
-                            VarType::Int => "i32",
-                            VarType::Float => "double",
-                            VarType::String => "i8*",
-                            _ => "i32",
-                        };
+    /// A constant pointer to the variable `target`, or to the field when it is
+    /// `record.field`.
+    fn global(&self, target: &str) -> String {
+        let Some((name, field)) = target.split_once('.') else {
+            return format!("@v.{}", target);
+        };
+        match self.var_types.get(name) {
+            Some(VarType::Record(record)) => {
+                let position = record.fields.iter().position(|(existing, _)| existing == field).unwrap_or(0);
+                let ty = format!("%record.{}", record.name);
+                format!("getelementptr inbounds ({0}, {0}* @v.{1}, i32 0, i32 {2})", ty, name, position)
+            }
+            _ => format!("@v.{}", name),
+        }
+    }
 
-                        let value_str = match value {
-                            Some(Expression::Literal(val)) => val.clone(),
-                            Some(Expression::Identifier(val)) => format!("%{}", val),
-                            Some(Expression::BinaryOp { .. }) => "0".to_string(), // Simplified handling for complex expressions
-                            Some(Expression::LogicalOp { .. }) => "0".to_string(), // Simplified handling for complex expressions
-                            None => "0".to_string(),
-                        };
+    fn var_ptr(&self, name: &str) -> String {
+        array_ptr(&self.global(name), buffer_size(self.target_type(name)))
+    }
 
-                        // Skip allocation if already allocated at the beginning
-                        if !var_declarations_seen.contains(&sanitized_name) {
-                            main_code.push_str(&format!("  %{} = alloca {}, align 4\n", sanitized_name, llvm_type));
-                            var_declarations_seen.insert(sanitized_name.clone());
-                        }
+    /// Loads the `i`, `f`, `b` or text variable or field `target` as an operand.
+    fn load(&mut self, target: &str) -> String {
+        match self.target_type(target).cloned() {
+            Some(VarType::String | VarType::Char { .. }) => self.var_ptr(target),
+            Some(VarType::Int(int_type)) => {
+                let loaded = self.temp();
+                self.line(&format!("{} = load i{2}, i{2}* {}", loaded, self.global(target), int_type.bits));
+                let wide = self.temp();
+                let extend = if int_type.signed { "sext" } else { "zext" };
+                self.line(&format!("{} = {} i{} {} to i128", wide, extend, int_type.bits, loaded));
+                wide
+            }
+            // Whole arrays and records are only printed; maps are rejected by check_support
+            Some(VarType::Array(_) | VarType::Map(..) | VarType::Record(_)) => "0".to_string(),
+            Some(var_type) => {
+                let ty = storage_type(&var_type);
+                let temp = self.temp();
+                self.line(&format!("{} = load {}, {}* {}", temp, ty, ty, self.global(target)));
+                temp
+            }
+            None => undeclared(target),
+        }
+    }
 
-                        // Store initial value
-                        if let Some(Expression::Literal(_)) = value {
-                            main_code.push_str(&format!(
-                                "  store {} {}, {}* %{}\n",
-                                llvm_type, value_str, llvm_type, sanitized_name
+    /// Evaluates `expr` into an operand: `i128` for ints, `double` for floats,
+    /// `i8*` for text and `i1` for booleans.
+    fn value(&mut self, expr: &Expression) -> (ValueKind, String) {
+        let kind = value_kind(expr, self.var_types);
+        let operand = match expr {
+            Expression::Literal(lit) if lit.starts_with('"') || lit.starts_with('\'') => {
+                self.literal(&literal_text(lit))
+            }
+            Expression::Literal(lit) => {
+                let number = clean_number(lit);
+                match kind {
+                    ValueKind::Int => int_literal(&number).unwrap_or(0).to_string(),
+                    ValueKind::Bool => number,
+                    ValueKind::Float => double(number.parse::<f64>().unwrap_or(0.0)),
+                    ValueKind::Str => self.literal(lit),
+                }
+            }
+            Expression::Identifier(name) => self.load(name),
+            Expression::Field { name, field } => self.load(&format!("{}.{}", name, field)),
+            Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) => self.condition(expr),
+            Expression::Index { name, index } => {
+                let position = self.position(name, index);
+                self.load_element(name, &position)
+            }
+            Expression::Len(name) if is_text_var(name, self.var_types) => {
+                let text = self.var_ptr(name);
+                self.text_len(&text)
+            }
+            Expression::Len(name) => {
+                let len = self.array_len(name);
+                let wide = self.temp();
+                self.line(&format!("{} = zext i64 {} to i128", wide, len));
+                wide
+            }
+            // Only found in array and record declarations
+            Expression::List(_) | Expression::Record(_) => "0".to_string(),
+            Expression::Convert { to, value } => {
+                let value = (value_kind(value, self.var_types), value);
+                match (to, value) {
+                    (VarType::String, (_, value)) => self.text_of(value),
+                    (VarType::Float, (ValueKind::Str, value)) => {
+                        let text = self.value(value).1;
+                        let temp = self.temp();
+                        self.line(&format!(
+                            "{} = call double @ven_convert_float(i8* {}, i64 {})",
+                            temp, text, self.statement_line
+                        ));
+                        temp
+                    }
+                    (VarType::Float, (_, value)) => {
+                        let value = self.value(value);
+                        self.float_of(value)
+                    }
+                    (_, (ValueKind::Str | ValueKind::Float, value)) => {
+                        let (kind, operand) = self.value(value);
+                        let call = if kind == ValueKind::Str {
+                            format!("@ven_convert_int(i8* {}, i64 {})", operand, self.statement_line)
+                        } else {
+                            format!("@ven_float_to_int(double {})", operand)
+                        };
+                        let temp = self.temp();
+                        self.line(&format!("{} = call i128 {}", temp, call));
+                        temp
+                    }
+                    (_, (_, value)) => {
+                        let value = self.value(value);
+                        self.int_of(value)
+                    }
+                }
+            }
+            Expression::Text { op, args } => {
+                let values: Vec<String> = args.iter().map(|arg| self.value(arg).1).collect();
+                match op {
+                    TextOp::Len => self.text_len(&values[0]),
+                    TextOp::Contains | TextOp::StartsWith => {
+                        let temp = self.temp();
+                        let helper = if *op == TextOp::Contains { "contains" } else { "starts_with" };
+                        self.line(&format!(
+                            "{} = call i1 @ven_{}(i8* {}, i8* {})",
+                            temp, helper, values[0], values[1]
+                        ));
+                        temp
+                    }
+                    TextOp::Concat => {
+                        let (buf, mut len) = (self.buffer(STR_SIZE), "0".to_string());
+                        for value in &values {
+                            let next = self.temp();
+                            self.line(&format!(
+                                "{} = call i64 @ven_append(i8* {}, i64 {}, i8* {})",
+                                next, buf, len, value
                             ));
+                            len = next;
                         }
+                        buf
                     }
+                    _ => {
+                        let buf = self.buffer(STR_SIZE);
+                        let call = match op {
+                            TextOp::Slice => {
+                                format!("slice(i8* {}, i8* {}, i128 {}, i128 {})", buf, values[0], values[1], values[2])
+                            }
+                            TextOp::Upper => format!("case(i8* {}, i8* {}, i8 97, i8 -32)", buf, values[0]),
+                            TextOp::Lower => format!("case(i8* {}, i8* {}, i8 65, i8 32)", buf, values[0]),
+                            TextOp::Trim => format!("trim(i8* {}, i8* {})", buf, values[0]),
+                            _ => format!(
+                                "replace(i8* {}, i8* {}, i8* {}, i8* {})",
+                                buf, values[0], values[1], values[2]
+                            ),
+                        };
+                        let temp = self.temp();
+                        self.line(&format!("{} = call i8* @ven_{}", temp, call));
+                        temp
+                    }
+                }
+            }
+            Expression::Math { op, args } => self.math_fn(*op, args),
+            // Rejected by check_support
+            Expression::In { .. } => "0".to_string(),
+        };
+        (kind, operand)
+    }
 
-                    ASTNode::Print { expr, to_stderr: _ } => {
-                        if let Some(Expression::Literal(text)) = expr {
-                            let mut format_string = String::new();
-                            let mut var_references = Vec::new();
-                            let mut current_var = String::new();
-                            let mut in_var = false;
-
-                            for c in text.chars() {
-                                if c == '{' {
-                                    in_var = true;
-                                    continue;
-                                } else if c == '}' {
-                                    in_var = false;
-                                    if !current_var.is_empty() {
-                                        format_string.push_str("%d");
-                                        var_references.push(current_var.clone());
-                                        current_var.clear();
-                                    }
-                                    continue;
-                                }
+    /// A math function or `-x`, as an `i128` or a `double`. Integer arguments
+    /// are stored into an `i64` first, and so is the integer result.
+    fn math_fn(&mut self, op: MathFn, args: &[Expression]) -> String {
+        let name = op.called();
+        if math_kind(op, args, self.var_types) == ValueKind::Int {
+            let values: Vec<String> = args.iter().map(|arg| self.int_value(name, IntType::I64, arg)).collect();
+            let exact = match op {
+                MathFn::Negate | MathFn::Abs => {
+                    let negated = self.temp();
+                    self.line(&format!("{} = sub i128 0, {}", negated, values[0]));
+                    if op == MathFn::Negate {
+                        negated
+                    } else {
+                        let (negative, magnitude) = (self.temp(), self.temp());
+                        self.line(&format!("{} = icmp slt i128 {}, 0", negative, values[0]));
+                        self.line(&format!(
+                            "{} = select i1 {}, i128 {}, i128 {}",
+                            magnitude, negative, negated, values[0]
+                        ));
+                        magnitude
+                    }
+                }
+                // A later value only wins when it is smaller, or larger
+                MathFn::Min | MathFn::Max => {
+                    let compare = if op == MathFn::Min { "slt" } else { "sgt" };
+                    let mut best = values[0].clone();
+                    for value in &values[1..] {
+                        let (wins, next) = (self.temp(), self.temp());
+                        self.line(&format!("{} = icmp {} i128 {}, {}", wins, compare, value, best));
+                        self.line(&format!("{} = select i1 {}, i128 {}, i128 {}", next, wins, value, best));
+                        best = next;
+                    }
+                    return best;
+                }
+                MathFn::Sqrt | MathFn::Floor | MathFn::Ceil | MathFn::Round => return values[0].clone(),
+            };
+            return self.fit(name, IntType::I64, &exact);
+        }
+        let values: Vec<String> = args
+            .iter()
+            .map(|arg| {
+                let value = self.value(arg);
+                self.float_of(value)
+            })
+            .collect();
+        match op {
+            MathFn::Negate => {
+                let temp = self.temp();
+                self.line(&format!("{} = fneg double {}", temp, values[0]));
+                temp
+            }
+            MathFn::Min | MathFn::Max => {
+                let compare = if op == MathFn::Min { "olt" } else { "ogt" };
+                let mut best = values[0].clone();
+                for value in &values[1..] {
+                    let (wins, next) = (self.temp(), self.temp());
+                    self.line(&format!("{} = fcmp {} double {}, {}", wins, compare, value, best));
+                    self.line(&format!("{} = select i1 {}, double {}, double {}", next, wins, value, best));
+                    best = next;
+                }
+                best
+            }
+            _ => {
+                let intrinsic = if op == MathFn::Abs { "fabs" } else { op.name() };
+                let temp = self.temp();
+                self.line(&format!("{} = call double @llvm.{}.f64(double {})", temp, intrinsic, values[0]));
+                temp
+            }
+        }
+    }
 
-                                if in_var {
-                                    current_var.push(c);
-                                } else {
-                                    format_string.push(c);
-                                }
-                            }
+    /// The `i128` number of characters in `text`, an `i8*`.
+    fn text_len(&mut self, text: &str) -> String {
+        let len = self.temp();
+        self.line(&format!("{} = call i64 @ven_len(i8* {})", len, text));
+        let wide = self.temp();
+        self.line(&format!("{} = zext i64 {} to i128", wide, len));
+        wide
+    }
 
-                            // Properly format LLVM string with null terminator
-                            let escaped_str = format!("{}\\0A", format_string.replace("\"", "\\22")); // Append newline and escape quotes
-                            let format_length = escaped_str.len() + 1; // +1 for null terminator
-                            let format_label = format!("str{}", string_counter);
-                            string_counter += 1;
+    /// An `i8*` to `expr` as `>>` prints it; numbers are written into a new buffer.
+    fn text_of(&mut self, expr: &Expression) -> String {
+        let unsigned = int_type_of(expr, self.var_types) == Some(IntType::U64);
+        match self.value(expr) {
+            (ValueKind::Str, operand) => operand,
+            (ValueKind::Int, operand) => {
+                let (buf, narrow) = (self.buffer(24), self.temp());
+                let fmt = array_ptr(if unsigned { "@ven.fmt.llu" } else { "@ven.fmt.lld" }, 5);
+                self.line(&format!("{} = trunc i128 {} to i64", narrow, operand));
+                self.line(&format!(
+                    "call i32 (i8*, i64, i8*, ...) @snprintf(i8* {}, i64 24, i8* {}, i64 {})",
+                    buf, fmt, narrow
+                ));
+                buf
+            }
+            (ValueKind::Float, operand) => {
+                let (buf, text) = (self.buffer(400), self.temp());
+                self.line(&format!("{} = call i8* @ven_float_text(i8* {}, double {})", text, buf, operand));
+                text
+            }
+            (ValueKind::Bool, operand) => {
+                let word = self.temp();
+                self.line(&format!(
+                    "{} = select i1 {}, i8* {}, i8* {}",
+                    word,
+                    operand,
+                    array_ptr("@ven.true", 5),
+                    array_ptr("@ven.false", 6)
+                ));
+                word
+            }
+        }
+    }
 
-                            // Add global string
-                            global_strings.push_str(&format!(
-                                "@{} = private unnamed_addr constant [{} x i8] c\"{}\\00\", align 1\n",
-                                format_label, format_length, escaped_str
-                            ));
+    /// The element type of the array `name`, which the parser has checked.
+    fn element(&self, name: &str) -> VarType {
+        element_type(name, self.var_types).cloned().unwrap_or(VarType::String)
+    }
 
-                            let mut llvm_args = String::new();
-                            for var in var_references {
-                                let sanitized_var = sanitize_name(&var);
-                                let unique_load_var = format!("{}_load{}", sanitized_var, temp_var_counter);
-                                temp_var_counter += 1;
+    /// The `i64` length of the array `name`.
+    fn array_len(&mut self, name: &str) -> String {
+        let len = self.temp();
+        self.line(&format!("{} = load i64, i64* {}", len, array_field(name, 1)));
+        len
+    }
 
-                                llvm_args.push_str(&format!(", i32 %{}", unique_load_var));
+    /// The `i64` position `index` names in the array `name`, bounds-checked.
+    fn position(&mut self, name: &str, index: &Expression) -> String {
+        let index = self.value(index);
+        let index = self.int_of(index);
+        let len = self.array_len(name);
+        let name_ptr = self.literal(name);
+        let position = self.temp();
+        self.line(&format!(
+            "{} = call i64 @ven_index(i128 {}, i64 {}, i8* {}, i64 {})",
+            position, index, len, name_ptr, self.statement_line
+        ));
+        position
+    }
 
-                                // Load variable
-                                main_code.push_str(&format!(
-                                    "  %{} = load i32, i32* %{}, align 4\n",
-                                    unique_load_var, sanitized_var
-                                ));
-                            }
+    /// A pointer to the element at `position` of the array `name`; strings
+    /// point at their first byte.
+    fn element_ptr(&mut self, name: &str, position: &str) -> String {
+        let element = self.element(name);
+        let (ty, _) = element_layout(&element);
+        let (items, typed, slot) = (self.temp(), self.temp(), self.temp());
+        self.line(&format!("{} = load i8*, i8** {}", items, array_field(name, 0)));
+        self.line(&format!("{} = bitcast i8* {} to {}*", typed, items, ty));
+        let rest = if matches!(element, VarType::String | VarType::Char { .. }) { ", i64 0" } else { "" };
+        self.line(&format!("{} = getelementptr inbounds {}, {}* {}, i64 {}{}", slot, ty, ty, typed, position, rest));
+        slot
+    }
 
-                            // Call printf
-                            main_code.push_str(&format!(
-                                "  call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([{} x i8], [{} x i8]* @{}, i32 0, i32 0){})\n",
-                                format_length, format_length, format_label, llvm_args
-                            ));
-                        } else if let Some(Expression::Identifier(id)) = expr {
-                            // Check if it's a string literal
-                            if id.starts_with('"') {
-                                // Use the helper function for literal strings in quotes
-                                let (str_label, str_len) = add_global_llvm_string(id, &mut global_strings, &mut string_counter);
-
-                                // Call printf
-                                main_code.push_str(&format!(
-                                    "  call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([{} x i8], [{} x i8]* @{}, i32 0, i32 0))\n",
-                                    str_len, str_len, str_label
-                                ));
-                            } else {
-                                // For identifiers that are not string literals, we need to determine if they're variables
-                                // For this simple fix, we know "x" and "y" are variables, others are strings to print
-                                if id == "x" || id == "y" {
-                                    // It's a declared variable, load and print its value
-                                    let sanitized_id = sanitize_name(id);
-                                    let print_var = format!("print_{}{}", sanitized_id, temp_var_counter);
-                                    temp_var_counter += 1;
-
-                                    main_code.push_str(&format!(
-                                        "  %{} = load i32, i32* %{}, align 4\n",
-                                        print_var, sanitized_id
-                                    ));
-
-                                    // Create format string for printing integer with newline
-                                    let (format_label, format_length) = add_global_llvm_string("\"%d\\n\"", &mut global_strings, &mut string_counter);
-
-                                    // Call printf
-                                    main_code.push_str(&format!(
-                                        "  call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([{} x i8], [{} x i8]* @{}, i32 0, i32 0), i32 %{})\n",
-                                        format_length, format_length, format_label, print_var
-                                    ));
-                                } else {
-                                    // It's not a declared variable, treat it as a string to print directly
-                                    // Wrap it in quotes for the helper function
-                                    let quoted_id = format!("\"{}\"", id);
-                                    let (str_label, str_len) = add_global_llvm_string(&quoted_id, &mut global_strings, &mut string_counter);
-
-                                    // Call printf
-                                    main_code.push_str(&format!(
-                                        "  call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([{} x i8], [{} x i8]* @{}, i32 0, i32 0))\n",
-                                        str_len, str_len, str_label
-                                    ));
-                                }
-                            }
-                        }
-                    }
+    /// Loads the element at `position` of the array `name` as an operand.
+    fn load_element(&mut self, name: &str, position: &str) -> String {
+        let element = self.element(name);
+        let slot = self.element_ptr(name, position);
+        match element {
+            VarType::Int(int_type) => {
+                let loaded = self.temp();
+                self.line(&format!("{} = load i{2}, i{2}* {}", loaded, slot, int_type.bits));
+                let wide = self.temp();
+                let extend = if int_type.signed { "sext" } else { "zext" };
+                self.line(&format!("{} = {} i{} {} to i128", wide, extend, int_type.bits, loaded));
+                wide
+            }
+            VarType::Float | VarType::Bool => {
+                let ty = element_layout(&element).0;
+                let loaded = self.temp();
+                self.line(&format!("{} = load {}, {}* {}", loaded, ty, ty, slot));
+                loaded
+            }
+            _ => slot,
+        }
+    }
 
-                    ASTNode::MathOp {
-                        name,
-                        operator,
-                        operand,
-                    } => {
-                        let op = match operator {
-                            MathOperator::Add => "add",
-                            MathOperator::Subtract => "sub",
-                            MathOperator::Multiply => "mul",
-                            MathOperator::Divide => "sdiv",
-                        };
+    /// Stores `value` into the element at `position` of the array `name`.
+    fn store_element(&mut self, name: &str, position: &str, value: &Expression) {
+        let element = self.element(name);
+        let (ty, operand) = match &element {
+            VarType::Int(int_type) => {
+                let wide = self.int_value(name, *int_type, value);
+                let narrow = self.temp();
+                self.line(&format!("{} = trunc i128 {} to i{}", narrow, wide, int_type.bits));
+                (format!("i{}", int_type.bits), narrow)
+            }
+            VarType::Float => {
+                let value = self.value(value);
+                ("double".to_string(), self.float_of(value))
+            }
+            VarType::Bool => {
+                let value = self.value(value);
+                ("i1".to_string(), self.bool_of(value))
+            }
+            _ => {
+                let source = self.text_source(Some(value));
+                let slot = self.element_ptr(name, position);
+                match source {
+                    Some(source) => {
+                        self.line(&format!("call void @ven_copy(i8* {}, i8* {}, i64 {})", slot, source, STR_SIZE))
+                    }
+                    None => self.line(&format!("store i8 0, i8* {}", slot)),
+                }
+                return;
+            }
+        };
+        let slot = self.element_ptr(name, position);
+        self.line(&format!("store {} {}, {}* {}", ty, operand, ty, slot));
+    }
 
-                        let sanitized_name = sanitize_name(name);
-                        let operand_str = match operand {
-                            Expression::Literal(val) => val.clone(),
-                            Expression::Identifier(val) => {
-                                let sanitized_val = sanitize_name(val);
-                                let unique_operand_var = format!("{}_load{}", sanitized_val, temp_var_counter);
-                                temp_var_counter += 1;
-
-                                main_code.push_str(&format!(
-                                    "  %{} = load i32, i32* %{}, align 4\n",
-                                    unique_operand_var, sanitized_val
-                                ));
-                                format!("%{}", unique_operand_var)
-                            }
-                            Expression::BinaryOp { .. } | Expression::LogicalOp { .. } => {
-                                // For complex expressions in math operations, default to 0
-                                "0".to_string()
-                            }
-                        };
+    /// Appends `value` to the array `name`.
+    fn append(&mut self, name: &str, value: &Expression) {
+        let (_, size) = element_layout(&self.element(name));
+        self.line(&format!("call void @ven_grow({{i8*, i64, i64}}* @v.{}, i64 {})", name, size));
+        let len = self.array_len(name);
+        self.store_element(name, &len, value);
+        let next = self.temp();
+        self.line(&format!("{} = add i64 {}, 1", next, len));
+        self.line(&format!("store i64 {}, i64* {}", next, array_field(name, 1)));
+    }
 
-                        let unique_load_var = format!("{}_load{}", sanitized_name, temp_var_counter);
-                        temp_var_counter += 1;
-                        let unique_new_var = format!("{}_result{}", sanitized_name, temp_var_counter);
-                        temp_var_counter += 1;
+    /// Runs `body` once per element of the array `name`, giving it the `i64`
+    /// position. The length is read before each pass, so appended elements
+    /// are visited.
+    fn each<T>(&mut self, name: &str, body: impl FnOnce(&mut Self, &str) -> T) -> T {
+        self.labels += 1;
+        let id = self.labels;
+        self.counters.push(id);
+        self.line(&format!("store i64 0, i64* @ven.each.{}", id));
+        self.line(&format!("br label %each.{}", id));
+        self.body.push_str(&format!("each.{}:\n", id));
+        let position = self.temp();
+        self.line(&format!("{} = load i64, i64* @ven.each.{}", position, id));
+        let len = self.array_len(name);
+        let more = self.temp();
+        self.line(&format!("{} = icmp ult i64 {}, {}", more, position, len));
+        self.line(&format!("br i1 {}, label %each.body.{}, label %endeach.{}", more, id, id));
+        self.body.push_str(&format!("each.body.{}:\n", id));
+        let result = body(self, &position);
+        let next = self.temp();
+        self.line(&format!("{} = add i64 {}, 1", next, position));
+        self.line(&format!("store i64 {}, i64* @ven.each.{}", next, id));
+        self.line(&format!("br label %each.{}", id));
+        self.body.push_str(&format!("endeach.{}:\n", id));
+        result
+    }
 
-                        // Load the current value
-                        main_code.push_str(&format!(
-                            "  %{} = load i32, i32* %{}, align 4\n",
-                            unique_load_var, sanitized_name
-                        ));
+    /// An `i8*` to the text a `str` store copies from, or None for the empty string.
+    fn text_source(&mut self, value: Option<&Expression>) -> Option<String> {
+        match value {
+            // Declaration literals arrive with their quotes already stripped
+            Some(Expression::Literal(lit)) => Some(self.literal(&literal_text(lit))),
+            Some(expr) if value_kind(expr, self.var_types) == ValueKind::Str => Some(self.value(expr).1),
+            _ => None,
+        }
+    }
 
-                        // Perform operation
-                        main_code.push_str(&format!(
-                            "  %{} = {} i32 %{}, {}\n",
-                            unique_new_var, op, unique_load_var, operand_str
-                        ));
+    /// Converts an operand to `i128`; booleans count as 1 and 0.
+    fn int_of(&mut self, (kind, operand): (ValueKind, String)) -> String {
+        let instruction = match kind {
+            ValueKind::Int => return operand,
+            ValueKind::Float => format!("fptosi double {} to i128", operand),
+            ValueKind::Bool => format!("zext i1 {} to i128", operand),
+            ValueKind::Str => format!("call i128 @ven_to_int(i8* {})", operand),
+        };
+        let temp = self.temp();
+        self.line(&format!("{} = {}", temp, instruction));
+        temp
+    }
 
-                        // Store the result
-                        main_code.push_str(&format!(
-                            "  store i32 %{}, i32* %{}\n",
-                            unique_new_var, sanitized_name
-                        ));
-                    }
+    /// Converts an operand to `double`; booleans count as 1 and 0.
+    fn float_of(&mut self, (kind, operand): (ValueKind, String)) -> String {
+        let instruction = match kind {
+            ValueKind::Float => return operand,
+            // Every integer is an i64 or a u64; i128 conversions would need libgcc
+            ValueKind::Int => {
+                let narrow = self.temp();
+                self.line(&format!("{} = trunc i128 {} to i64", narrow, operand));
+                let (signed, unsigned) = (self.temp(), self.temp());
+                self.line(&format!("{} = sitofp i64 {} to double", signed, narrow));
+                self.line(&format!("{} = uitofp i64 {} to double", unsigned, narrow));
+                let negative = self.temp();
+                self.line(&format!("{} = icmp slt i128 {}, 0", negative, operand));
+                format!("select i1 {}, double {}, double {}", negative, signed, unsigned)
+            }
+            ValueKind::Bool => format!("uitofp i1 {} to double", operand),
+            ValueKind::Str => format!("call double @ven_to_float(i8* {})", operand),
+        };
+        let temp = self.temp();
+        self.line(&format!("{} = {}", temp, instruction));
+        temp
+    }
 
-                    ASTNode::If { condition, body } => {
-                        // Generate unique labels for the if block
-                        let then_label = format!("if_then{}", label_counter);
-                        let end_label = format!("if_end{}", label_counter);
-                        label_counter += 1;
-
-                        // Use our improved condition block generator for proper initialization
-                        create_condition_block(
-                            &mut main_code, 
-                            condition, 
-                            &mut temp_var_counter, 
-                            &mut string_counter, 
-                            &mut global_strings,
-                            &then_label, 
-                            &end_label
-                        );
-
-                        // Then block
-                        main_code.push_str(&format!("{}:\n", then_label));
-                        
-                        // Generate code for body statements
-                        for stmt in body {
-                            match stmt {
-                                ASTNode::Print { expr, .. } => {
-                                    if let Some(Expression::Literal(text)) = expr {
-                                        let mut format_string = String::new();
-                                        let mut var_references = Vec::new();
-                                        let mut current_var = String::new();
-                                        let mut in_var = false;
-
-                                        for c in text.chars() {
-                                            if c == '{' {
-                                                in_var = true;
-                                                continue;
-                                            } else if c == '}' {
-                                                in_var = false;
-                                                if !current_var.is_empty() {
-                                                    format_string.push_str("%d");
-                                                    var_references.push(current_var.clone());
-                                                    current_var.clear();
-                                                }
-                                                continue;
-                                            }
-
-                                            if in_var {
-                                                current_var.push(c);
-                                            } else {
-                                                format_string.push(c);
-                                            }
-                                        }
-
-                                        // Properly format LLVM string with null terminator
-                                        let escaped_str = format!("{}\\0A", format_string.replace("\"", "\\22")); // Append newline and escape quotes
-                                        let format_length = escaped_str.len() + 1; // +1 for null terminator
-                                        let format_label = format!("str{}", string_counter);
-                                        string_counter += 1;
-
-                                        // Add global string
-                                        global_strings.push_str(&format!(
-                                            "@{} = private unnamed_addr constant [{} x i8] c\"{}\\00\", align 1\n",
-                                            format_label, format_length, escaped_str
-                                        ));
-
-                                        let mut llvm_args = String::new();
-                                        for var in var_references {
-                                            let sanitized_var = sanitize_name(&var);
-                                            let unique_load_var = format!("{}_load{}", sanitized_var, temp_var_counter);
-                                            temp_var_counter += 1;
-
-                                            llvm_args.push_str(&format!(", i32 %{}", unique_load_var));
-
-                                            // Load variable
-                                            main_code.push_str(&format!(
-                                                "  %{} = load i32, i32* %{}, align 4\n",
-                                                unique_load_var, sanitized_var
-                                            ));
-                                        }
-
-                                        // Call printf
-                                        main_code.push_str(&format!(
-                                            "  call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([{} x i8], [{} x i8]* @{}, i32 0, i32 0){})\n",
-                                            format_length, format_length, format_label, llvm_args
-                                        ));
-                                    } else if let Some(Expression::Identifier(id)) = expr {
-                                        // Check if it's a string literal
-                                        if id.starts_with('"') {
-                                            // Use the helper function for literal strings in quotes
-                                            let (str_label, str_len) = add_global_llvm_string(id, &mut global_strings, &mut string_counter);
-
-                                            // Call printf
-                                            main_code.push_str(&format!(
-                                                "  call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([{} x i8], [{} x i8]* @{}, i32 0, i32 0))\n",
-                                                str_len, str_len, str_label
-                                            ));
-                                        } else {
-                                            // For identifiers that are not string literals, we need to determine if they're variables
-                                            // For this simple fix, we know "x" and "y" are variables, others are strings to print
-                                            if id == "x" || id == "y" {
-                                                // It's a declared variable, load and print its value
-                                                let sanitized_id = sanitize_name(id);
-                                                let print_var = format!("print_{}{}", sanitized_id, temp_var_counter);
-                                                temp_var_counter += 1;
-
-                                                main_code.push_str(&format!(
-                                                    "  %{} = load i32, i32* %{}, align 4\n",
-                                                    print_var, sanitized_id
-                                                ));
-
-                                                // Create format string for printing integer with newline
-                                                let (format_label, format_length) = add_global_llvm_string("\"%d\\n\"", &mut global_strings, &mut string_counter);
-
-                                                // Call printf
-                                                main_code.push_str(&format!(
-                                                    "  call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([{} x i8], [{} x i8]* @{}, i32 0, i32 0), i32 %{})\n",
-                                                    format_length, format_length, format_label, print_var
-                                                ));
-                                            } else {
-                                                // It's not a declared variable, treat it as a string to print directly
-                                                // Wrap it in quotes for the helper function
-                                                let quoted_id = format!("\"{}\"", id);
-                                                let (str_label, str_len) = add_global_llvm_string(&quoted_id, &mut global_strings, &mut string_counter);
-
-                                                // Call printf
-                                                main_code.push_str(&format!(
-                                                    "  call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([{} x i8], [{} x i8]* @{}, i32 0, i32 0))\n",
-                                                    str_len, str_len, str_label
-                                                ));
-                                            }
-                                        }
-                                    }
-                                }
-                                // Additional statement types can be handled here
-                                _ => {} // Handle other statement types within the If block if necessary
-                            }
-                        }
+    /// Converts an operand to `i1`; text is true only for the word `true`.
+    fn bool_of(&mut self, (kind, operand): (ValueKind, String)) -> String {
+        let instruction = match kind {
+            ValueKind::Bool => return operand,
+            ValueKind::Int => format!("icmp ne i128 {}, 0", operand),
+            ValueKind::Float => format!("fcmp une double {}, 0.0", operand),
+            ValueKind::Str => format!("call i1 @ven_to_bool(i8* {})", operand),
+        };
+        let temp = self.temp();
+        self.line(&format!("{} = {}", temp, instruction));
+        temp
+    }
 
-                        // Branch to end
-                        main_code.push_str(&format!("  br label %{}\n", end_label));
-                        
-                        // End block
-                        main_code.push_str(&format!("{}:\n", end_label));
+    /// Evaluates a condition into an `i1` operand.
+    fn condition(&mut self, expr: &Expression) -> String {
+        match expr {
+            Expression::BinaryOp { left, operator, right } => {
+                let left = self.value(left);
+                let right = self.value(right);
+                let (instruction, ty, left, right) = match (left.0, right.0) {
+                    (ValueKind::Str, ValueKind::Str) => {
+                        let cmp = self.temp();
+                        self.line(&format!("{} = call i32 @strcmp(i8* {}, i8* {})", cmp, left.1, right.1));
+                        ("icmp", "i32", cmp, "0".to_string())
                     }
-                    
-                    ASTNode::Input { name } => {
-                        let sanitized_name = sanitize_name(name);
-                        // Use the existing scanf format string rather than creating a new one
-                        main_code.push_str(&format!(
-                            "  call i32 (i8*, ...) @scanf(i8* getelementptr inbounds ([3 x i8], [3 x i8]* @{}, i32 0, i32 0), i32* %{})\n",
-                            scanf_fmt_label, sanitized_name
-                        ));
+                    // A string never equals a number
+                    (ValueKind::Str, _) | (_, ValueKind::Str) => {
+                        return (*operator == ComparisonOperator::NotEqual).to_string();
                     }
-                }
+                    (ValueKind::Float, _) | (_, ValueKind::Float) => {
+                        ("fcmp", "double", self.float_of(left), self.float_of(right))
+                    }
+                    _ => ("icmp", "i128", self.int_of(left), self.int_of(right)),
+                };
+                let predicate = match (instruction, operator) {
+                    ("fcmp", ComparisonOperator::Equal) => "oeq",
+                    ("fcmp", ComparisonOperator::NotEqual) => "une",
+                    ("fcmp", ComparisonOperator::LessThan) => "olt",
+                    ("fcmp", ComparisonOperator::LessThanEqual) => "ole",
+                    ("fcmp", ComparisonOperator::GreaterThan) => "ogt",
+                    ("fcmp", ComparisonOperator::GreaterThanEqual) => "oge",
+                    (_, ComparisonOperator::Equal) => "eq",
+                    (_, ComparisonOperator::NotEqual) => "ne",
+                    (_, ComparisonOperator::LessThan) => "slt",
+                    (_, ComparisonOperator::LessThanEqual) => "sle",
+                    (_, ComparisonOperator::GreaterThan) => "sgt",
+                    (_, ComparisonOperator::GreaterThanEqual) => "sge",
+                };
+                let temp = self.temp();
+                self.line(&format!("{} = {} {} {} {}, {}", temp, instruction, predicate, ty, left, right));
+                temp
             }
+            Expression::LogicalOp { left, operator, right } => {
+                let left = self.condition(left);
+                let right = self.condition(right);
+                let op = match operator {
+                    LogicalOperator::And => "and",
+                    LogicalOperator::Or => "or",
+                };
+                let temp = self.temp();
+                self.line(&format!("{} = {} i1 {}, {}", temp, op, left, right));
+                temp
+            }
+            Expression::Not(operand) => {
+                let operand = self.condition(operand);
+                let temp = self.temp();
+                self.line(&format!("{} = xor i1 {}, true", temp, operand));
+                temp
+            }
+            _ => match self.value(expr) {
+                // Strings are true when non-empty
+                (ValueKind::Str, operand) => {
+                    let first = self.temp();
+                    self.line(&format!("{} = load i8, i8* {}", first, operand));
+                    let temp = self.temp();
+                    self.line(&format!("{} = icmp ne i8 {}, 0", temp, first));
+                    temp
+                }
+                value => self.bool_of(value),
+            },
         }
     }
 
-    main_code.push_str("  ret i32 0\n");
-    main_code.push_str("}\n");
-
-    // Validate generated IR
-    let result = format!("{}\n{}", global_strings, main_code);
-    validate_llvm_ir(&result).unwrap_or_else(|err| {
-        eprintln!("Warning: IR validation error: {}", err);
-    });
+    fn print_text(&mut self, fd: u8, text: &str) {
+        let fmt = array_ptr("@ven.fmt.s", 3);
+        let text = self.literal(text);
+        self.line(&format!("call i32 (i32, i8*, ...) @dprintf(i32 {}, i8* {}, i8* {})", fd, fmt, text));
+    }
 
-    result
-}
-
-/// Sanitize variable names to ensure they're valid LLVM identifiers
-fn sanitize_name(name: &str) -> String {
-    // Remove any invalid characters for LLVM identifiers
-    let mut sanitized = String::new();
-    
-    // Handle string literals in identifier names
-    if name.starts_with('"') {
-        // Just use a simple name + counter for string literals
-        return format!("str_lit{}", name.len());
-    }
-    
-    for c in name.chars() {
-        if c.is_alphanumeric() || c == '_' {
-            sanitized.push(c);
-        } else {
-            // Replace invalid characters with underscore
-            sanitized.push('_');
+    fn print_value(&mut self, fd: u8, expr: &Expression) {
+        if let Expression::Identifier(name) = expr
+            && element_type(name, self.var_types).is_some()
+        {
+            return self.print_array(fd, name);
         }
+        if let Expression::Identifier(name) = expr
+            && let Some(VarType::Record(record)) = self.var_types.get(name)
+        {
+            return self.print_record(fd, name, &record.fields.clone());
+        }
+        let unsigned = int_type_of(expr, self.var_types) == Some(IntType::U64);
+        let value = self.value(expr);
+        self.print_operand(fd, value, unsigned);
     }
-    
-    if sanitized.is_empty() {
-        sanitized = "unnamed".to_string();
-    }
-    
-    sanitized
-}
-
-/// Generate LLVM IR code for a condition expression
-fn generate_condition_code(
-    code: &mut String,
-    expr: &Expression,
-    temp_var_counter: &mut usize,
-    string_counter: &mut usize, 
-    global_strings: &mut String,
-) -> String {
-    match expr {
-        Expression::BinaryOp { left, operator, right } => {
-            let left_value = generate_operand_code(code, left, temp_var_counter, string_counter, global_strings);
-            let right_value = generate_operand_code(code, right, temp_var_counter, string_counter, global_strings);
-            
-            let result_var = format!("cond_{}", *temp_var_counter);
-            *temp_var_counter += 1;
-            
-            let cmp_op = match operator {
-                ComparisonOperator::Equal => "icmp eq",
-                ComparisonOperator::NotEqual => "icmp ne",
-                ComparisonOperator::LessThan => "icmp slt",
-                ComparisonOperator::LessThanEqual => "icmp sle",
-                ComparisonOperator::GreaterThan => "icmp sgt",
-                ComparisonOperator::GreaterThanEqual => "icmp sge",
-            };
-            
-            code.push_str(&format!(
-                "  %{} = {} i32 {}, {}\n",
-                result_var, cmp_op, left_value, right_value
+
+    /// Prints an array as `[a, b, c]`.
+    fn print_array(&mut self, fd: u8, name: &str) {
+        let element = self.element(name);
+        let kind = type_kind(&element);
+        let unsigned = element == VarType::Int(IntType::U64);
+        self.print_text(fd, "[");
+        self.each(name, |this, position| {
+            let (first, separator) = (this.temp(), this.temp());
+            this.line(&format!("{} = icmp eq i64 {}, 0", first, position));
+            this.line(&format!(
+                "{} = select i1 {}, i8* {}, i8* {}",
+                separator,
+                first,
+                array_ptr("@ven.fmt.none", 1),
+                array_ptr("@ven.fmt.sep", 3)
             ));
-            
-            result_var
-        },
-        Expression::LogicalOp { left, operator, right } => {
-            // For logical operators, we'll use control flow to implement short-circuit logic
-            let result_var = format!("cond_{}", *temp_var_counter);
-            *temp_var_counter += 1;
-            
-            // Allocate a variable for the result with proper alignment
-            code.push_str(&format!("  %{} = alloca i1, align 1\n", result_var));
-            
-            match operator {
-                LogicalOperator::And => {
-                    // Initialize to false for AND (default value)
-                    code.push_str(&format!("  store i1 false, i1* %{}\n", result_var));
-                    
-                    // Create consistent label names
-                    let left_label = format!("log_left{}", *temp_var_counter);
-                    let end_label = format!("log_end{}", *temp_var_counter);
-                    *temp_var_counter += 1;
-                    
-                    // Evaluate left operand
-                    let left_value = generate_condition_code(code, left, temp_var_counter, string_counter, global_strings);
-                    
-                    // If left is false, short-circuit to end (result already false)
-                    // If left is true, evaluate right operand
-                    code.push_str(&format!(
-                        "  br i1 %{}, label %{}, label %{}\n",
-                        left_value, left_label, end_label
-                    ));
-                    
-                    // Left is true, so evaluate right
-                    code.push_str(&format!("{}:\n", left_label));
-                    let right_value = generate_condition_code(code, right, temp_var_counter, string_counter, global_strings);
-                    code.push_str(&format!(
-                        "  store i1 %{}, i1* %{}\n",
-                        right_value, result_var
-                    ));
-                    code.push_str(&format!("  br label %{}\n", end_label));
-                    
-                    // End block
-                    code.push_str(&format!("{}:\n", end_label));
-                },
-                LogicalOperator::Or => {
-                    // Create consistent label names for OR with descriptive prefixes
-                    let or_true_label = format!("or_true{}", *temp_var_counter); // Better naming
-                    let or_right_label = format!("or_right{}", *temp_var_counter); // Better naming
-                    let end_label = format!("log_end{}", *temp_var_counter);
-                    *temp_var_counter += 1;
-                    
-                    // Evaluate left operand
-                    let left_value = generate_condition_code(code, left, temp_var_counter, string_counter, global_strings);
-                    
-                    // For OR, if left is true, skip to end with true result
-                    // Otherwise check right operand
-                    code.push_str(&format!(
-                        "  br i1 %{}, label %{}, label %{}\n",
-                        left_value, or_true_label, or_right_label
-                    ));
-                    
-                    // If left is true, store true and skip to end
-                    code.push_str(&format!("{}:\n", or_true_label));
-                    code.push_str(&format!("  store i1 true, i1* %{}\n", result_var));
-                    code.push_str(&format!("  br label %{}\n", end_label));
-                    
-                    // Left is false, evaluate right
-                    code.push_str(&format!("{}:\n", or_right_label));
-                    let right_value = generate_condition_code(code, right, temp_var_counter, string_counter, global_strings);
-                    code.push_str(&format!(
-                        "  store i1 %{}, i1* %{}\n",
-                        right_value, result_var
-                    ));
-                    code.push_str(&format!("  br label %{}\n", end_label));
-                    
-                    // End block
-                    code.push_str(&format!("{}:\n", end_label));
-                }
+            let fmt = array_ptr("@ven.fmt.s", 3);
+            this.line(&format!("call i32 (i32, i8*, ...) @dprintf(i32 {}, i8* {}, i8* {})", fd, fmt, separator));
+            let operand = this.load_element(name, position);
+            this.print_operand(fd, (kind, operand), unsigned);
+        });
+        self.print_text(fd, "]");
+    }
+
+    /// Prints a record as `{field: value, field: value}`.
+    fn print_record(&mut self, fd: u8, name: &str, fields: &[(String, VarType)]) {
+        for (i, (field, field_type)) in fields.iter().enumerate() {
+            let open = if i == 0 { "{" } else { ", " };
+            self.print_text(fd, &format!("{}{}: ", open, field));
+            let operand = self.load(&format!("{}.{}", name, field));
+            let unsigned = *field_type == VarType::Int(IntType::U64);
+            self.print_operand(fd, (type_kind(field_type), operand), unsigned);
+        }
+        self.print_text(fd, if fields.is_empty() { "{}" } else { "}" });
+    }
+
+    fn print_operand(&mut self, fd: u8, value: (ValueKind, String), unsigned: bool) {
+        match value {
+            (ValueKind::Int, operand) => {
+                let fmt = array_ptr(if unsigned { "@ven.fmt.llu" } else { "@ven.fmt.lld" }, 5);
+                let narrow = self.temp();
+                self.line(&format!("{} = trunc i128 {} to i64", narrow, operand));
+                self.line(&format!("call i32 (i32, i8*, ...) @dprintf(i32 {}, i8* {}, i64 {})", fd, fmt, narrow));
             }
-            
-            // Load and return the result
-            let final_result = format!("cond_result{}", *temp_var_counter);
-            *temp_var_counter += 1;
-            code.push_str(&format!("  %{} = load i1, i1* %{}\n", final_result, result_var));
-            final_result
-        },
-        Expression::Literal(val) => {
-            // Handle boolean literals directly as i1 constants
-            if val == "true" {
-                return "true".to_string(); // LLVM i1 true
-            } else if val == "false" {
-                return "false".to_string(); // LLVM i1 false
-            }
-
-            // Treat other literals (likely numbers) as conditions: != 0
-            let literal_val = val.parse::<i32>().unwrap_or(0); // Default to 0 if parse fails
-            let result_var = format!("lit_cond{}", *temp_var_counter);
-            *temp_var_counter += 1;
-            code.push_str(&format!(
-                "  %{} = icmp ne i32 {}, 0\n",
-                result_var, literal_val
-            ));
-            result_var
-        },
-        Expression::Identifier(id) => {
-            // Identifiers as conditions: load the variable (assuming i32) and check if != 0
-            // String literals should not typically be used directly as conditions.
-            // If a string literal appears here, it's likely an AST/parser issue,
-            // but we'll handle it defensively by evaluating to 'false'.
-            if id.starts_with('"') {
-                 // Define the global string in case it's needed elsewhere, but the condition result is false.
-                let _ = add_global_llvm_string(id, global_strings, string_counter);
-                "false".to_string() // String literal as condition is always false
-            } else {
-                 // Load the variable and compare it to 0
-                 let sanitized_id = sanitize_name(id);
-                 let load_var = format!("load_cond_{}", *temp_var_counter);
-                 *temp_var_counter += 1;
-
-                 code.push_str(&format!(
-                     "  %{} = load i32, i32* %{}, align 4\n", // Assuming i32 for now
-                     load_var, sanitized_id
-                 ));
-
-                 let result_var = format!("cond_cmp_{}", *temp_var_counter);
-                 *temp_var_counter += 1;
-
-                 code.push_str(&format!(
-                     "  %{} = icmp ne i32 %{}, 0\n",
-                     result_var, load_var
-                 ));
-
-                 result_var
+            (ValueKind::Float, operand) => {
+                self.line(&format!("call void @ven_print_float(i32 {}, double {})", fd, operand));
+            }
+            (ValueKind::Str, operand) => {
+                let fmt = array_ptr("@ven.fmt.s", 3);
+                self.line(&format!("call i32 (i32, i8*, ...) @dprintf(i32 {}, i8* {}, i8* {})", fd, fmt, operand));
+            }
+            (ValueKind::Bool, operand) => {
+                let word = self.temp();
+                self.line(&format!(
+                    "{} = select i1 {}, i8* {}, i8* {}",
+                    word,
+                    operand,
+                    array_ptr("@ven.true", 5),
+                    array_ptr("@ven.false", 6)
+                ));
+                let fmt = array_ptr("@ven.fmt.s", 3);
+                self.line(&format!("call i32 (i32, i8*, ...) @dprintf(i32 {}, i8* {}, i8* {})", fd, fmt, word));
             }
         }
     }
-}
 
-/// Generate LLVM IR code for an operand (literal or identifier)
-fn generate_operand_code(
-    code: &mut String,
-    expr: &Expression,
-    temp_var_counter: &mut usize,
-    string_counter: &mut usize,
-    global_strings: &mut String,
-) -> String {
-    match expr {
-        Expression::Literal(val) => {
-            // Try to parse as a number
-            if let Ok(num) = val.parse::<i32>() {
-                num.to_string()
-            } else if val == "true" {
-                "1".to_string() // Represent true as 1
-            } else if val == "false" {
-                "0".to_string() // Represent false as 0
-            } else {
-                 // Handle non-numeric/boolean literals like strings.
-                 if val.starts_with('"') {
-                    // Define the global string, but return 0 as the operand value.
-                    // Using string pointers directly in arithmetic/comparisons is not supported here.
-                     let _ = add_global_llvm_string(val, global_strings, string_counter);
-                    "0".to_string()
-                 } else {
-                    "0".to_string() // Unknown literal type treated as 0
-                 }
-            }
-        },
-        Expression::Identifier(id) => {
-            // Make sure the variable is valid before attempting to load it
-            // String literals should not be treated as numeric operands directly.
-            if id.starts_with('"') {
-                 // Define the global string, but return 0 as the operand value.
-                 let _ = add_global_llvm_string(id, global_strings, string_counter);
-                "0".to_string()
-            } else {
-                 // Load the variable
-                 let sanitized_id = sanitize_name(id);
-                 let load_var = format!("load_op_{}", *temp_var_counter);
-                 *temp_var_counter += 1;
-
-                 // Ensure the variable is loaded correctly with proper alignment
-                 code.push_str(&format!(
-                     "  %{} = load i32, i32* %{}, align 4\n",
-                     load_var, sanitized_id
-                 ));
-
-                 format!("%{}", load_var)
-            }
-        },
-        // Handle complex expressions by evaluating them (this might involve recursion)
-        Expression::BinaryOp { .. } | Expression::LogicalOp { .. } => {
-            // Evaluate the condition/expression, the result should be a register name holding the value (likely i1 or i32)
-             let expr_result_reg = generate_condition_code(code, expr, temp_var_counter, string_counter, global_strings);
-             // If the result is i1 (from logical/comparison), we might need to convert it to i32 (zext)
-             // Assuming generate_condition_code returns a register holding the result (e.g., %cond_1)
-             // This part needs careful handling based on the type returned by generate_condition_code.
-             // For simplicity, let's assume it returns an i32 representable value for now.
-             // If generate_condition_code returns an i1 register (e.g., %cmp1), use zext:
-             // let operand_val = format!("op_val{}", *temp_var_counter);
-             // *temp_var_counter += 1;
-             // code.push_str(&format!("  %{} = zext i1 {} to i32\n", operand_val, expr_result_reg));
-             // operand_val
-             expr_result_reg // Directly use the result register for now
+    /// Prints a `{name:spec}` placeholder's value laid out by `spec`.
+    fn print_formatted(&mut self, fd: u8, expr: &Expression, spec: FormatSpec) {
+        let kind = value_kind(expr, self.var_types);
+        let text = match (kind, spec.precision) {
+            (ValueKind::Float, Some(precision)) => {
+                let x = self.value(expr).1;
+                let (buf, text) = (self.buffer(400), self.temp());
+                self.line(&format!(
+                    "{} = call i8* @ven_fixed_text(i8* {}, double {}, i32 {})",
+                    text, buf, x, precision
+                ));
+                text
+            }
+            _ => self.text_of(expr),
+        };
+        let right = spec.right(matches!(kind, ValueKind::Int | ValueKind::Float));
+        self.line(&format!("call void @ven_pad(i32 {}, i8* {}, i64 {}, i1 {})", fd, text, spec.width, right));
+    }
+
+    /// Prints text and values in order, the newline joined to the last text.
+    fn print(&mut self, fd: u8, expr: Option<&Expression>) {
+        let mut text = String::new();
+        let value = |this: &mut Self, text: &mut String, expr: &Expression| {
+            if !text.is_empty() {
+                this.print_text(fd, text);
+                text.clear();
+            }
+            this.print_value(fd, expr);
+        };
+
+        match expr {
+            Some(Expression::Literal(literal)) => {
+                for segment in split_interpolation(literal) {
+                    match segment {
+                        Segment::Text(segment) => text.push_str(&segment),
+                        Segment::Var(name) => match placeholder(&name, self.var_types) {
+                            Some(expr) => value(self, &mut text, &expr),
+                            None => text.push_str("[undefined]"),
+                        },
+                        Segment::Formatted(name, spec) => match formatted(&name, self.var_types) {
+                            Some(expr) => {
+                                if !text.is_empty() {
+                                    self.print_text(fd, &text);
+                                    text.clear();
+                                }
+                                self.print_formatted(fd, &expr, spec);
+                            }
+                            None => text.push_str("[undefined]"),
+                        },
+                    }
+                }
+            }
+            Some(expr @ Expression::Identifier(name)) if self.var_types.contains_key(name) => {
+                value(self, &mut text, expr)
+            }
+            // Bare words are printed as written
+            Some(Expression::Identifier(word)) => text.push_str(&literal_text(word)),
+            Some(cond) => value(self, &mut text, cond),
+            None => {}
         }
+        text.push('\n');
+        self.print_text(fd, &text);
     }
-}
 
-// Function to collect all variables used in the AST
-fn collect_all_variables(ast: &AST, variables: &mut std::collections::HashSet<String>) {
-    match ast {
-        AST::Program(nodes) => {
-            for node in nodes {
-                match node {
-                    ASTNode::VarDeclaration { name, .. } => {
-                        variables.insert(name.clone());
-                    },
-                    ASTNode::MathOp { name, .. } => {
-                        variables.insert(name.clone());
-                    },
-                    ASTNode::Input { name } => {
-                        variables.insert(name.clone());
-                    },
-                    ASTNode::If { condition, body } => {
-                        collect_expression_variables(condition, variables);
-                        for stmt in body {
-                            collect_node_variables(stmt, variables);
-                        }
-                    },
-                    ASTNode::Print { expr, to_stderr: _ } => {
-                        if let Some(expression) = expr {
-                            collect_expression_variables(expression, variables);
-                        }
-                    },
-                    // Add other node types as needed
+    /// Stores into a `f` or `b` variable or field.
+    fn store(&mut self, name: &str, var_type: &VarType, value: (ValueKind, String)) {
+        let (ty, operand) = match var_type {
+            VarType::Float => ("double", self.float_of(value)),
+            _ => ("i1", self.bool_of(value)),
+        };
+        self.line(&format!("store {} {}, {}* {}", ty, operand, ty, self.global(name)));
+    }
+
+    /// Stores an `i128` that fits into the integer variable or field `name`.
+    fn store_int(&mut self, name: &str, int_type: IntType, wide: &str) {
+        let narrow = self.temp();
+        self.line(&format!("{} = trunc i128 {} to i{}", narrow, wide, int_type.bits));
+        self.line(&format!("store i{0} {1}, i{0}* {2}", int_type.bits, narrow, self.global(name)));
+    }
+
+    /// `@ven_fit` of an `i128` for a store into the integer variable `name`.
+    fn fit(&mut self, name: &str, int_type: IntType, wide: &str) -> String {
+        let (name_ptr, type_ptr) = (self.literal(name), self.literal(&int_type.to_string()));
+        let temp = self.temp();
+        self.line(&format!(
+            "{} = call i128 @ven_fit(i128 {}, i128 {}, i128 {}, i8* {}, i8* {}, i64 {})",
+            temp,
+            wide,
+            int_type.min(),
+            int_type.max(),
+            name_ptr,
+            type_ptr,
+            self.statement_line
+        ));
+        temp
+    }
+
+    fn fit_float(&mut self, name: &str, int_type: IntType, value: &str) -> String {
+        let (name_ptr, type_ptr) = (self.literal(name), self.literal(&int_type.to_string()));
+        let temp = self.temp();
+        self.line(&format!(
+            "{} = call i128 @ven_fit_float(double {}, i128 {}, i128 {}, double {}, double {}, i8* {}, i8* {}, i64 {})",
+            temp,
+            value,
+            int_type.min(),
+            int_type.max(),
+            double(int_type.min() as f64),
+            double((int_type.max() + 1) as f64),
+            name_ptr,
+            type_ptr,
+            self.statement_line
+        ));
+        temp
+    }
+
+    /// `expr` converted for a store into the integer variable `name`, as an
+    /// `i128`. Checks are left out where the value always fits.
+    fn int_value(&mut self, name: &str, int_type: IntType, expr: &Expression) -> String {
+        match (expr, value_kind(expr, self.var_types)) {
+            (Expression::Literal(lit), ValueKind::Int) => {
+                let literal = int_literal(lit).unwrap_or(0);
+                match fit(literal, int_type, self.overflow) {
+                    Some(fitted) => fitted.to_string(),
+                    None => self.fit(name, int_type, &literal.to_string()),
                 }
             }
+            (
+                Expression::Identifier(_)
+                | Expression::Index { .. }
+                | Expression::Len(_)
+                | Expression::Field { .. }
+                | Expression::Convert { .. }
+                | Expression::Text { .. }
+                | Expression::Math { .. },
+                ValueKind::Int,
+            ) => {
+                let value = self.value(expr).1;
+                match int_type_of(expr, self.var_types) {
+                    Some(other_type) if !int_type.holds(other_type) => self.fit(name, int_type, &value),
+                    _ => value,
+                }
+            }
+            (_, ValueKind::Float) => {
+                let value = self.value(expr).1;
+                self.fit_float(name, int_type, &value)
+            }
+            (_, ValueKind::Str) => {
+                let value = self.value(expr);
+                let wide = self.int_of(value);
+                self.fit(name, int_type, &wide)
+            }
+            (_, ValueKind::Int | ValueKind::Bool) => {
+                let value = self.value(expr);
+                self.int_of(value)
+            }
         }
     }
-}
 
-fn collect_node_variables(node: &ASTNode, variables: &mut std::collections::HashSet<String>) {
-    match node {
-        ASTNode::VarDeclaration { name, .. } => {
-            variables.insert(name.clone());
-        },
-        ASTNode::MathOp { name, .. } => {
-            variables.insert(name.clone());
-        },
-        ASTNode::Input { name } => {
-            variables.insert(name.clone());
-        },
-        ASTNode::If { condition, body } => {
-            collect_expression_variables(condition, variables);
-            for stmt in body {
-                collect_node_variables(stmt, variables);
-            }
-        },
-        ASTNode::Print { expr, to_stderr: _ } => {
-            if let Some(expression) = expr {
-                collect_expression_variables(expression, variables);
-            }
-        },
-        // Add other node types as needed
-    }
-}
-
-fn collect_expression_variables(expr: &Expression, variables: &mut std::collections::HashSet<String>) {
-    match expr {
-        Expression::Identifier(id) => {
-            // Only add non-string-literal identifiers
-            if !id.starts_with('"') {
-                variables.insert(id.clone());
-            }
-        },
-        Expression::BinaryOp { left, right, .. } => {
-            collect_expression_variables(left, variables);
-            collect_expression_variables(right, variables);
-        },
-        Expression::LogicalOp { left, right, .. } => {
-            collect_expression_variables(left, variables);
-            collect_expression_variables(right, variables);
-        },
-        Expression::Literal(_) => {
-            // Literals don't contain variables
-        },
-    }
-}
-
-// Simple IR validation to catch common issues
-fn validate_llvm_ir(ir: &str) -> Result<(), String> {
-    // Track basic blocks and their termination status
-    let mut current_block_terminated = false;
-    let mut defined_vars = std::collections::HashSet::new();
-    
-    for line in ir.lines() {
-        let line = line.trim();
-        
-        // Skip empty lines and declarations/globals
-        if line.is_empty() || line.starts_with("declare") || line.starts_with("@") {
-            continue;
+    /// The exact quotient of two values of `int_type`, truncated toward zero,
+    /// or with `remainder` what is left over. Division stays in 64 bits, since
+    /// i128 division would need libgcc.
+    fn divide(&mut self, int_type: IntType, x: &str, y: &str, remainder: bool) -> String {
+        let (x64, y64) = (self.temp(), self.temp());
+        self.line(&format!("{} = trunc i128 {} to i64", x64, x));
+        self.line(&format!("{} = trunc i128 {} to i64", y64, y));
+        let (quotient, wide) = (self.temp(), self.temp());
+        if int_type == IntType::U64 {
+            let op = if remainder { "urem" } else { "udiv" };
+            self.line(&format!("{} = {} i64 {}, {}", quotient, op, x64, y64));
+            self.line(&format!("{} = zext i64 {} to i128", wide, quotient));
+            return wide;
         }
-        
-        // Check for block labels
-        if line.ends_with(':') {
-            current_block_terminated = false;
-            continue;
+        // x / -1 is the only quotient that can leave the i64 range; x % -1 is
+        // always 0, as x % 1 is
+        let (minus_one, divisor) = (self.temp(), self.temp());
+        self.line(&format!("{} = icmp eq i128 {}, -1", minus_one, y));
+        self.line(&format!("{} = select i1 {}, i64 1, i64 {}", divisor, minus_one, y64));
+        let op = if remainder { "srem" } else { "sdiv" };
+        self.line(&format!("{} = {} i64 {}, {}", quotient, op, x64, divisor));
+        self.line(&format!("{} = sext i64 {} to i128", wide, quotient));
+        if remainder {
+            return wide;
         }
-        
-        // Check for variable definitions
-        if line.contains(" = alloca ") {
-            let parts: Vec<&str> = line.split(" = ").collect();
-            if !parts.is_empty() {
-                let var_name = parts[0].trim();
-                defined_vars.insert(var_name.to_string());
+        let (negated, result) = (self.temp(), self.temp());
+        self.line(&format!("{} = sub i128 0, {}", negated, x));
+        self.line(&format!("{} = select i1 {}, i128 {}, i128 {}", result, minus_one, negated, wide));
+        result
+    }
+
+    /// Stops with a division by zero in `name` on `line` when the `i1` `zero`
+    /// is set.
+    fn check_divisor(&mut self, name: &str, line: usize, zero: &str) {
+        self.labels += 1;
+        let id = self.labels;
+        self.line(&format!("br i1 {}, label %divzero.{}, label %divok.{}", zero, id, id));
+        self.body.push_str(&format!("divzero.{}:\n", id));
+        let name_ptr = self.literal(name);
+        self.line(&format!("call void @ven_div_zero(i8* {}, i64 {})", name_ptr, line));
+        self.line("unreachable");
+        self.body.push_str(&format!("divok.{}:\n", id));
+    }
+
+    /// Emits `nodes`, or names the first construct that cannot be translated.
+    fn nodes(&mut self, nodes: &[ASTNode]) -> Result<(), String> {
+        for node in nodes {
+            if let Some(line) = node.line() {
+                self.statement_line = line;
             }
-        }
-        
-        // Check for variable uses (simplified, could be improved)
-        if line.contains("load") && line.contains("i32*") {
-            let parts: Vec<&str> = line.split("i32* ").collect();
-            if parts.len() > 1 {
-                let var_ref = parts[1].trim().trim_matches(',');
-                if !defined_vars.contains(var_ref) && !var_ref.starts_with("getelementptr") {
-                    return Err(format!("Use of undefined variable: {}", var_ref));
+            match node {
+                ASTNode::VarDeclaration { name, var_type, value, line, .. } => match (var_type, value) {
+                    (VarType::String | VarType::Char { .. }, value) => {
+                        let source = self.text_source(value.as_ref());
+                        let dest = self.var_ptr(name);
+                        match source {
+                            Some(source) => self.line(&format!(
+                                "call void @ven_copy(i8* {}, i8* {}, i64 {})",
+                                dest,
+                                source,
+                                buffer_size(self.target_type(name))
+                            )),
+                            None => self.line(&format!("store i8 0, i8* {}", dest)),
+                        }
+                    }
+                    (VarType::Int(int_type), Some(expr)) => {
+                        let value = self.int_value(name, *int_type, expr);
+                        self.store_int(name, *int_type, &value);
+                    }
+                    (VarType::Int(int_type), None) => self.store_int(name, *int_type, "0"),
+                    (VarType::Array(_), value) => {
+                        self.line(&format!("store i64 0, i64* {}", array_field(name, 1)));
+                        if let Some(Expression::List(items)) = value {
+                            for item in items {
+                                self.append(name, item);
+                            }
+                        }
+                    }
+                    // Each field is stored like a declaration of the field type
+                    (VarType::Record(record), value) => {
+                        let values = match value {
+                            Some(Expression::Record(values)) => values.clone(),
+                            _ => Vec::new(),
+                        };
+                        let fields = record.fields.iter().map(|(field, field_type)| ASTNode::VarDeclaration {
+                            mutable: true,
+                            name: format!("{}.{}", name, field),
+                            var_type: field_type.clone(),
+                            value: values.iter().find(|(given, _)| given == field).map(|(_, value)| value.clone()),
+                            line: *line,
+                        });
+                        self.nodes(&fields.collect::<Vec<_>>())?;
+                    }
+                    (_, Some(expr)) => {
+                        let value = self.value(expr);
+                        self.store(name, var_type, value);
+                    }
+                    (VarType::Float, None) => self.store(name, var_type, (ValueKind::Float, "0.0".to_string())),
+                    (_, None) => self.store(name, var_type, (ValueKind::Bool, "false".to_string())),
+                },
+                ASTNode::Input { name, .. } => match self.var_types.get(name) {
+                    Some(VarType::String) => {
+                        let dest = self.var_ptr(name);
+                        self.line(&format!("call void @ven_read_line(i8* {}, i64 {})", dest, STR_SIZE));
+                    }
+                    Some(VarType::Char { size }) => {
+                        let size = size + 1;
+                        let line = array_ptr("@ven.line", STR_SIZE);
+                        self.line(&format!("call void @ven_read_line(i8* {}, i64 {})", line, STR_SIZE));
+                        let dest = self.var_ptr(name);
+                        self.line(&format!("call void @ven_copy(i8* {}, i8* {}, i64 {})", dest, line, size));
+                    }
+                    var_type => {
+                        let line = array_ptr("@ven.line", STR_SIZE);
+                        self.line(&format!("call void @ven_read_line(i8* {}, i64 {})", line, STR_SIZE));
+                        // Undeclared target: the line is consumed and discarded
+                        match var_type.cloned() {
+                            Some(VarType::Int(int_type)) => {
+                                let wide = self.int_of((ValueKind::Str, line));
+                                let value = self.fit(name, int_type, &wide);
+                                self.store_int(name, int_type, &value);
+                            }
+                            // Rejected by the parser for arrays
+                            Some(VarType::Array(_)) | None => {}
+                            Some(var_type) => self.store(name, &var_type, (ValueKind::Str, line)),
+                        }
+                    }
+                },
+                ASTNode::Print { to_stderr, expr, .. } => {
+                    let fd = if *to_stderr { 2 } else { 1 };
+                    self.print(fd, expr.as_ref());
+                }
+                ASTNode::MathOp { name, field, operator, operand, line } => {
+                    // A field is changed like a variable of the field type
+                    let name = &match field {
+                        Some(field) => format!("{}.{}", name, field),
+                        None => name.clone(),
+                    };
+                    let var_type = match self.target_type(name) {
+                        Some(var_type @ (VarType::Int(_) | VarType::Float)) => var_type.clone(),
+                        Some(_) => return Err(format!("math on the non-numeric variable '{}'", name)),
+                        None => return Err(format!("math on the undeclared variable '{}'", name)),
+                    };
+                    let current = (type_kind(&var_type), self.load(name));
+                    let integer = matches!(value_kind(operand, self.var_types), ValueKind::Int | ValueKind::Bool);
+                    // `/` and `%` check the operand for zero, and an integer `**` checks
+                    // for a negative power of zero
+                    let divides = self.checks && matches!(operator, MathOperator::Divide | MathOperator::Modulo);
+                    if let (VarType::Int(int_type), true) = (&var_type, integer) {
+                        // The operand is stored into the variable's type first, then the
+                        // exact result is; integer division truncates toward zero
+                        let int_type = *int_type;
+                        let operand = self.int_value(name, int_type, operand);
+                        if divides {
+                            let zero = self.temp();
+                            self.line(&format!("{} = icmp eq i128 {}, 0", zero, operand));
+                            self.check_divisor(name, *line, &zero);
+                        } else if self.checks && *operator == MathOperator::Power {
+                            let (base, negative, zero) = (self.temp(), self.temp(), self.temp());
+                            self.line(&format!("{} = icmp eq i128 {}, 0", base, current.1));
+                            self.line(&format!("{} = icmp slt i128 {}, 0", negative, operand));
+                            self.line(&format!("{} = and i1 {}, {}", zero, base, negative));
+                            self.check_divisor(name, *line, &zero);
+                        }
+                        let result = match operator {
+                            MathOperator::Multiply if int_type == IntType::U64 => {
+                                let (x, y, product, wide) = (self.temp(), self.temp(), self.temp(), self.temp());
+                                self.line(&format!("{} = trunc i128 {} to i64", x, current.1));
+                                self.line(&format!("{} = trunc i128 {} to i64", y, operand));
+                                let name_ptr = self.literal(name);
+                                self.line(&format!(
+                                    "{} = call i64 @ven_mul_u64(i64 {}, i64 {}, i8* {}, i64 {})",
+                                    product, x, y, name_ptr, self.statement_line
+                                ));
+                                self.line(&format!("{} = zext i64 {} to i128", wide, product));
+                                wide
+                            }
+                            MathOperator::Divide | MathOperator::Modulo => {
+                                let remainder = *operator == MathOperator::Modulo;
+                                let quotient = self.divide(int_type, &current.1, &operand, remainder);
+                                self.fit(name, int_type, &quotient)
+                            }
+                            MathOperator::Power => {
+                                let power = self.temp();
+                                self.line(&format!("{} = call i128 @ven_pow(i128 {}, i128 {})", power, current.1, operand));
+                                self.fit(name, int_type, &power)
+                            }
+                            _ => {
+                                let op = match operator {
+                                    MathOperator::Add => "add",
+                                    MathOperator::Subtract => "sub",
+                                    _ => "mul",
+                                };
+                                let exact = self.temp();
+                                self.line(&format!("{} = {} i128 {}, {}", exact, op, current.1, operand));
+                                self.fit(name, int_type, &exact)
+                            }
+                        };
+                        self.store_int(name, int_type, &result);
+                    } else {
+                        // A float operand on an integer is applied in floating point
+                        let operand = self.value(operand);
+                        let result = self.temp();
+                        let op = match operator {
+                            MathOperator::Add => "fadd",
+                            MathOperator::Subtract => "fsub",
+                            MathOperator::Multiply => "fmul",
+                            MathOperator::Divide => "fdiv",
+                            MathOperator::Modulo => "frem",
+                            MathOperator::Power => "pow",
+                        };
+                        let current = self.float_of(current);
+                        let operand = self.float_of(operand);
+                        if divides {
+                            let zero = self.temp();
+                            self.line(&format!("{} = fcmp oeq double {}, 0.0", zero, operand));
+                            self.check_divisor(name, *line, &zero);
+                        }
+                        if *operator == MathOperator::Power {
+                            self.line(&format!(
+                                "{} = call double @llvm.pow.f64(double {}, double {})",
+                                result, current, operand
+                            ));
+                        } else {
+                            self.line(&format!("{} = {} double {}, {}", result, op, current, operand));
+                        }
+                        match var_type {
+                            VarType::Int(int_type) => {
+                                let value = self.fit_float(name, int_type, &result);
+                                self.store_int(name, int_type, &value);
+                            }
+                            _ => self.store(name, &var_type, (ValueKind::Float, result)),
+                        }
+                    }
+                }
+                ASTNode::If { condition, body, .. } => {
+                    let condition = self.condition(condition);
+                    self.labels += 1;
+                    let id = self.labels;
+                    self.line(&format!("br i1 {}, label %then.{}, label %endif.{}", condition, id, id));
+                    self.body.push_str(&format!("then.{}:\n", id));
+                    self.nodes(body)?;
+                    self.line(&format!("br label %endif.{}", id));
+                    self.body.push_str(&format!("endif.{}:\n", id));
                 }
+                ASTNode::SetIndex { name, index, value, .. } => {
+                    // The index is checked before the value is evaluated
+                    let position = self.position(name, index);
+                    self.store_element(name, &position, value);
+                }
+                ASTNode::Append { name, value, .. } => self.append(name, value),
+                ASTNode::ForEach { item, array, body } => {
+                    self.each(array, |this, position| {
+                        match this.element(array) {
+                            VarType::Int(int_type) => {
+                                let wide = this.load_element(array, position);
+                                this.store_int(item, int_type, &wide);
+                            }
+                            element @ (VarType::Float | VarType::Bool) => {
+                                let operand = this.load_element(array, position);
+                                this.store(item, &element, (type_kind(&element), operand));
+                            }
+                            _ => {
+                                let source = this.load_element(array, position);
+                                let dest = this.var_ptr(item);
+                                this.line(&format!(
+                                    "call void @ven_copy(i8* {}, i8* {}, i64 {})",
+                                    dest, source, STR_SIZE
+                                ));
+                            }
+                        }
+                        this.nodes(body)
+                    })?;
+                }
+                // Emitted as a type before the globals
+                ASTNode::RecordDeclaration(_) => {}
+                // Rejected by check_support
+                ASTNode::Call { .. } => {}
             }
         }
-        
-        // Check for terminators
-        if line.starts_with("  br ") || line.starts_with("  ret ") {
-            current_block_terminated = true;
-        } else if current_block_terminated && !line.contains("define") && !line.contains("}") {
-            return Err(format!("Unreachable code after terminator: {}", line));
+        Ok(())
+    }
+}
+
+pub fn transpile_llvm(ast: &AST, options: &CodegenOptions) -> Result<String, String> {
+    let vars = collect_var_types(ast);
+    let var_types: HashMap<String, VarType> = vars.iter().cloned().collect();
+    let mut generator = LlvmGen {
+        var_types: &var_types,
+        overflow: options.overflow,
+        checks: options.runtime_checks,
+        strings: Vec::new(),
+        body: String::new(),
+        temps: 0,
+        labels: 0,
+        counters: Vec::new(),
+        buffers: Vec::new(),
+        statement_line: 0,
+    };
+    let AST::Program(nodes) = ast;
+    generator.nodes(nodes)?;
+
+    let mut code = String::with_capacity(4096);
+    code.push_str("; Generated by the Ven compiler\n");
+    code.push_str(RUNTIME);
+    code.push_str(match options.overflow {
+        Overflow::Wrap => WRAP_HELPERS,
+        Overflow::Saturate => SATURATE_HELPERS,
+        Overflow::Trap => TRAP_HELPERS,
+    });
+    code.push_str(&fit_float_helper(options.overflow));
+    if vars.iter().any(|(_, var_type)| matches!(var_type, VarType::Array(_))) {
+        code.push_str(ARRAY_HELPERS);
+    }
+    if uses_math(nodes, &var_types) {
+        code.push_str(MATH_HELPERS);
+    }
+    if options.runtime_checks && uses_division(nodes) {
+        code.push_str(DIVISION_HELPERS);
+    }
+    if uses_conversions(nodes, &var_types) {
+        code.push_str(CONVERT_HELPERS);
+    }
+    if uses_strings(nodes, &var_types) {
+        code.push_str(STRING_HELPERS);
+    }
+    code.push('\n');
+
+    // Each record type becomes a struct with one member per field
+    for record in record_types(ast) {
+        let members: Vec<String> = record.fields.iter().map(|(_, var_type)| storage_type(var_type)).collect();
+        code.push_str(&format!("%record.{} = type {{{}}}\n", record.name, members.join(", ")));
+    }
+
+    // Ven variables are program-wide, so every name becomes a global
+    for (name, var_type) in &vars {
+        let init = match var_type {
+            VarType::Int(_) => "0",
+            VarType::Float => "0.0",
+            VarType::Bool => "false",
+            VarType::String | VarType::Char { .. } | VarType::Array(_) | VarType::Record(_) => "zeroinitializer",
+            // Rejected by check_support
+            VarType::Map(..) => continue,
+        };
+        code.push_str(&format!("@v.{} = internal global {} {}\n", name, storage_type(var_type), init));
+    }
+    for id in &generator.counters {
+        code.push_str(&format!("@ven.each.{} = internal global i64 0\n", id));
+    }
+    for (index, size) in generator.buffers.iter().enumerate() {
+        code.push_str(&format!("@ven.text.{} = internal global [{} x i8] zeroinitializer\n", index + 1, size));
+    }
+    for (index, text) in generator.strings.iter().enumerate() {
+        code.push_str(&format!(
+            "@.str.{} = private unnamed_addr constant [{} x i8] c\"{}\"\n",
+            index,
+            text.len() + 1,
+            c_bytes(text)
+        ));
+    }
+
+    code.push_str("\ndefine i32 @main() {\nentry:\n");
+    code.push_str(&generator.body);
+    code.push_str("  ret i32 0\n}\n");
+    Ok(code)
+}
+
+pub struct LlvmBackend;
+
+impl Backend for LlvmBackend {
+    fn name(&self) -> &'static str {
+        "llvm"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["ll"]
+    }
+
+    fn description(&self) -> &'static str {
+        "LLVM IR"
+    }
+
+    fn extension(&self) -> &'static str {
+        "ll"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        // No map runtime is emitted in IR
+        Capabilities {
+            maps: false,
+            ..Capabilities::ALL
         }
     }
-    
-    Ok(())
-}
-
-// Helper function to create conditional blocks with proper initialization
-fn create_condition_block(
-    code: &mut String,
-    condition: &Expression,
-    temp_var_counter: &mut usize,
-    string_counter: &mut usize,
-    global_strings: &mut String,
-    then_label: &str,
-    else_label: &str,
-) {
-    // Ensure the condition is properly evaluated with initialization
-    let cond_var = format!("if_cond_{}", *temp_var_counter);
-    *temp_var_counter += 1;
-    
-    // Allocate and initialize the condition variable
-    code.push_str(&format!("  %{} = alloca i1, align 1\n", cond_var));
-    code.push_str(&format!("  store i1 false, i1* %{}\n", cond_var));
-    
-    // Generate condition code
-    let cond_result = generate_condition_code(code, condition, temp_var_counter, string_counter, global_strings);
-    
-    // Store condition result
-    code.push_str(&format!("  store i1 %{}, i1* %{}\n", cond_result, cond_var));
-    
-    // Load final condition value
-    let final_cond = format!("final_cond_{}", *temp_var_counter);
-    *temp_var_counter += 1;
-    code.push_str(&format!("  %{} = load i1, i1* %{}\n", final_cond, cond_var));
-    
-    // Branch based on condition
-    code.push_str(&format!("  br i1 %{}, label %{}, label %{}\n", final_cond, then_label, else_label));
+
+    fn emit(&self, ast: &AST, options: &CodegenOptions) -> Result<String, BackendError> {
+        transpile_llvm(ast, options).map_err(|construct| BackendError::Unsupported { target: self.name(), construct })
+    }
 }
@@ -0,0 +1,1004 @@
+use crate::errs::BackendError;
+use crate::parse::{
+    AST, ASTNode, ComparisonOperator, Expression, IntType, LogicalOperator, MathFn, MathOperator, TextOp, VarType,
+};
+use crate::transpilers::{
+    Backend, Capabilities, CodegenOptions, Overflow, STR_CAPACITY, Segment, ValueKind, clean_number, collect_var_types,
+    element_type, field_type, fit, formatted, has_buffer, int_literal, int_type_of, literal_text, map_types, math_kind,
+    placeholder, split_interpolation, stored_text, target_type, undeclared, uses_conversions, uses_division, uses_math,
+    uses_strings, value_kind,
+};
+use std::collections::HashMap;
+
+// Input conversions and truncating division, shared by every generated script
+const RUNTIME_HELPERS: &str = r#"def _read_line():
+    # A line holds at most 255 bytes, as on the native targets
+    line = sys.stdin.readline().rstrip("\r\n").encode()
+    return line[:255].decode(errors="ignore")
+
+
+# Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+def _to_int(text):
+    try:
+        value = int(text.strip())
+    except ValueError:
+        return 0
+    return value if -(2**63) <= value < 2**64 else 0
+
+
+def _to_float(text):
+    try:
+        return float(text.strip())
+    except ValueError:
+        return 0.0
+
+
+def _int_div(a, b):
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+"#;
+
+// Integer overflow, one body per `--overflow=` mode. `_fit` stores a value into
+// the type running from lo to hi.
+const WRAP_HELPERS: &str = r#"
+
+def _fit(value, lo, hi, name, type, line):
+    return (value - lo) % (hi - lo + 1) + lo
+
+
+# Floats never wrap: they are truncated and clamp at the limits
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+"#;
+
+const SATURATE_HELPERS: &str = r#"
+
+def _fit(value, lo, hi, name, type, line):
+    return min(max(value, lo), hi)
+
+
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+"#;
+
+const TRAP_HELPERS: &str = r#"
+
+def _overflow(name, type, line):
+    sys.stdout.flush()
+    print(f"runtime error: integer overflow in '{name}' ({type}) on line {line}", file=sys.stderr)
+    sys.exit(1)
+
+
+def _fit(value, lo, hi, name, type, line):
+    if not lo <= value <= hi:
+        _overflow(name, type, line)
+    return value
+
+
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1 or value >= hi + 1:
+        _overflow(name, type, line)
+    return int(value)
+"#;
+
+// How an element, map value or field prints, for programs with arrays, maps or
+// records. Only floats are Python floats.
+const SHOW_HELPER: &str = r#"
+
+def _shown(value):
+    if isinstance(value, bool):
+        return str(value).lower()
+    return _float_text(value) if isinstance(value, float) else str(value)
+"#;
+
+// Bounds checks and printing, for programs with arrays
+const ARRAY_HELPERS: &str = r#"
+
+def _index(index, length, name, line):
+    if not 0 <= index < length:
+        sys.stdout.flush()
+        print(f"runtime error: index {index} is out of bounds for '{name}' (length {length}) on line {line}", file=sys.stderr)
+        sys.exit(1)
+    return index
+
+
+def _show(items):
+    return "[" + ", ".join(_shown(item) for item in items) + "]"
+"#;
+
+// Lookups and printing, for programs with maps or records, which are both
+// dicts. A dict keeps insertion order.
+const MAP_HELPERS: &str = r#"
+
+def _get(entries, key, name, line):
+    if key not in entries:
+        sys.stdout.flush()
+        shown = f'"{key}"' if isinstance(key, str) else key
+        print(f"runtime error: key {shown} is not in '{name}' on line {line}", file=sys.stderr)
+        sys.exit(1)
+    return entries[key]
+
+
+def _show_map(entries):
+    return "{" + ", ".join(f"{key}: {_shown(value)}" for key, value in entries.items()) + "}"
+"#;
+
+// Float printing, for programs with floats
+const FLOAT_HELPERS: &str = r#"
+
+# The shortest digits, written out without an exponent like the native targets
+def _float_text(x):
+    x = float(x)
+    if x != x:
+        return "NaN"
+    if x - x != 0:
+        return "inf" if x > 0 else "-inf"
+    sign = "-" if str(x).startswith("-") else ""
+    mantissa, _, exponent = repr(abs(x)).partition("e")
+    whole, _, fraction = mantissa.partition(".")
+    digits = (whole + fraction).rstrip("0")
+    point = len(whole) + int(exponent or 0) - (len(digits) - len(digits.lstrip("0")))
+    digits = digits.lstrip("0")
+    if not digits:
+        return sign + "0"
+    if point <= 0:
+        return sign + "0." + "0" * -point + digits
+    if point >= len(digits):
+        return sign + digits + "0" * (point - len(digits))
+    return sign + digits[:point] + "." + digits[point:]
+"#;
+
+// `i()`, `f()` and `str()`, and `{name:spec}` layout, for programs that use them
+const CONVERT_HELPERS: &str = r#"
+
+def _convert_failed(text, to, line):
+    sys.stdout.flush()
+    print(f'runtime error: cannot convert "{text}" to {to} on line {line}', file=sys.stderr)
+    sys.exit(1)
+
+
+def _digits(text):
+    return all("0" <= c <= "9" for c in text)
+
+
+# A sign and decimal digits within i64, with spaces around them
+def _convert_int(text, line):
+    trimmed = text.strip(" \t\n\r")
+    unsigned = trimmed[1:] if trimmed[:1] in ("+", "-") else trimmed
+    if not unsigned or not _digits(unsigned) or not -(2**63) <= int(trimmed) < 2**63:
+        _convert_failed(text, "i", line)
+    return int(trimmed)
+
+
+# A finite decimal number like -1.5, .5, 2. or 6.02e23, with spaces around it
+def _convert_float(text, line):
+    number = text.strip(" \t\n\r")
+    unsigned = number[1:] if number[:1] in ("+", "-") else number
+    mantissa, e, exponent = unsigned.replace("E", "e").partition("e")
+    whole, _, fraction = mantissa.partition(".")
+    exponent = exponent[1:] if exponent[:1] in ("+", "-") else exponent
+    valid = (whole or fraction) and _digits(whole) and _digits(fraction) and _digits(exponent)
+    if not valid or (e and not exponent) or float(number) - float(number) != 0:
+        _convert_failed(text, "f", line)
+    return float(number)
+
+
+# Truncated toward zero, with NaN as 0 and clamped at the i64 limits
+def _float_to_int(x):
+    if x != x:
+        return 0
+    if x <= -(2**63):
+        return -(2**63)
+    return 2**63 - 1 if x >= 2**63 else int(x)
+
+
+# p digits after the point, rounded half to even
+def _fixed(x, p):
+    return "%.*f" % (p, x) if x - x == 0 else _float_text(x)
+
+
+# Padded with spaces to width characters, before the text when right
+def _pad(text, width, right):
+    return text.rjust(width) if right else text.ljust(width)
+"#;
+
+// Cutting text to a `c[size]` buffer or a `str`, for programs that have a
+// buffer or build text with string operations
+const TEXT_HELPERS: &str = r#"
+
+def _fit_text(text, size):
+    data = str(text).encode()
+    if len(data) <= size:
+        return text
+    # A character cut in two is dropped whole
+    return data[:size].decode(errors="ignore")
+"#;
+
+// String operations, for programs that use them. Only ASCII letters change case.
+const STRING_HELPERS: &str = r#"
+
+_UPPER = {c: c - 32 for c in range(97, 123)}
+_LOWER = {c: c + 32 for c in range(65, 91)}
+
+
+def _slice(text, start, end):
+    return text[max(start, 0):max(end, 0)]
+
+
+def _trim(text):
+    return text.strip(" \t\n\r")
+
+
+# An empty `old` leaves the text as it is
+def _replace(text, old, new):
+    return text.replace(old, new) if old else text
+"#;
+
+// The division by zero checks, unless `--no-runtime-checks` drops them
+const DIVISION_HELPERS: &str = r#"
+
+def _div_zero(name, line):
+    sys.stdout.flush()
+    print(f"runtime error: division of '{name}' by zero on line {line}", file=sys.stderr)
+    sys.exit(1)
+
+
+def _divisor(y, name, line):
+    if y == 0:
+        _div_zero(name, line)
+    return y
+
+
+def _exponent(x, y, name, line):
+    if x == 0 and y < 0:
+        _div_zero(name, line)
+    return y
+"#;
+
+// `%`, `**` and the math functions, for programs that use them. Floats follow
+// C, giving inf or NaN where `math` would raise.
+const MATH_HELPERS: &str = r#"
+
+def _int_rem(a, b):
+    r = abs(a) % abs(b)
+    return -r if a < 0 else r
+
+
+# A negative y gives 1 / x ** -y truncated toward zero. A power past 128 bits is
+# cut to its sign and low 64 bits, still past every integer type, which _fit
+# handles as it would the power itself
+def _pow(x, y):
+    if y == 0:
+        return 1
+    if x in (0, 1):
+        return x
+    if x == -1:
+        return 1 if y % 2 == 0 else -1
+    if y < 0:
+        return 0
+    if y < 128:
+        return x**y
+    low = pow(x % 2**64, y, 2**64)
+    return low - 2**65 if x < 0 and y % 2 == 1 else low + 2**64
+
+
+def _fmod(x, y):
+    if x - x != 0 or y != y or y == 0:
+        return math.nan
+    return math.fmod(x, y)
+
+
+def _fpow(x, y):
+    try:
+        return math.pow(x, y)
+    except OverflowError:
+        return math.copysign(math.inf, x) if y % 2 == 1 else math.inf
+    except ValueError:
+        if x == 0:
+            return math.copysign(math.inf, x) if y % 2 == 1 else math.inf
+        return math.nan
+
+
+def _sqrt(x):
+    return math.sqrt(x) if x >= 0 else math.nan
+
+
+# Infinities and NaN stay as they are; whole results keep the sign of x, so
+# ceil(-0.5) is -0 as on the native targets
+def _floor(x):
+    return math.copysign(math.floor(x), x) if x - x == 0 else x
+
+
+def _ceil(x):
+    return math.copysign(math.ceil(x), x) if x - x == 0 else x
+
+
+# Halves round away from zero
+def _round(x):
+    if x - x != 0:
+        return x
+    whole = math.floor(abs(x))
+    return math.copysign(whole + (abs(x) - whole >= 0.5), x)
+"#;
+
+pub fn transpile_py(ast: &AST, options: &CodegenOptions) -> String {
+    let mut code = String::with_capacity(1024);
+    let vars = collect_var_types(ast);
+    let var_types: HashMap<String, VarType> = vars.iter().cloned().collect();
+
+    code.push_str("# Generated by the Ven compiler\n");
+    let AST::Program(nodes) = ast;
+    let math = uses_math(nodes, &var_types);
+    if math {
+        code.push_str("import math\n");
+    }
+    code.push_str("import sys\n\n\n");
+    code.push_str(RUNTIME_HELPERS);
+    code.push_str(match options.overflow {
+        Overflow::Wrap => WRAP_HELPERS,
+        Overflow::Saturate => SATURATE_HELPERS,
+        Overflow::Trap => TRAP_HELPERS,
+    });
+    let containers = |var_type: &VarType| matches!(var_type, VarType::Array(_) | VarType::Map(..) | VarType::Record(_));
+    if vars.iter().any(|(_, var_type)| containers(var_type)) {
+        code.push_str(SHOW_HELPER);
+    }
+    if vars.iter().any(|(_, var_type)| matches!(var_type, VarType::Array(_))) {
+        code.push_str(ARRAY_HELPERS);
+    }
+    if vars.iter().any(|(_, var_type)| matches!(var_type, VarType::Map(..) | VarType::Record(_))) {
+        code.push_str(MAP_HELPERS);
+    }
+    let strings = uses_strings(nodes, &var_types);
+    if strings || vars.iter().any(|(_, var_type)| has_buffer(var_type)) {
+        code.push_str(TEXT_HELPERS);
+    }
+    let conversions = uses_conversions(nodes, &var_types);
+    if conversions || math || vars.iter().any(|(_, var_type)| has_float(var_type)) {
+        code.push_str(FLOAT_HELPERS);
+    }
+    if math {
+        code.push_str(MATH_HELPERS);
+    }
+    if options.runtime_checks && uses_division(nodes) {
+        code.push_str(DIVISION_HELPERS);
+    }
+    if conversions {
+        code.push_str(CONVERT_HELPERS);
+    }
+    if strings {
+        code.push_str(STRING_HELPERS);
+    }
+    code.push_str("\n\n");
+
+    // Ven variables are program-wide, so every name gets a module-level default first
+    for (name, var_type) in &vars {
+        code.push_str(&format!("{} = {}\n", name, default_value(var_type)));
+    }
+    if !vars.is_empty() {
+        code.push('\n');
+    }
+
+    emit_nodes(&mut code, nodes, 0, &var_types, options.overflow, options.runtime_checks);
+
+    code.shrink_to_fit();
+    code
+}
+
+fn emit_nodes(
+    code: &mut String,
+    nodes: &[ASTNode],
+    depth: usize,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    checks: bool,
+) {
+    let indent = "    ".repeat(depth);
+    for node in nodes {
+        // Named by the runtime errors the statement can stop the program with
+        let line = node.line().unwrap_or(0);
+        match node {
+            ASTNode::VarDeclaration { name, var_type, value, .. } => {
+                let value_str = match value {
+                    Some(expr) => match (var_type, expr) {
+                        // Declaration literals arrive with their quotes already stripped
+                        (VarType::String | VarType::Char { .. }, Expression::Literal(lit)) => {
+                            py_string(&stored_text(lit, var_type))
+                        }
+                        (VarType::Int(int_type), _) => py_int_value(name, *int_type, expr, var_types, overflow, line),
+                        (VarType::Array(element), Expression::List(items)) => {
+                            let items: Vec<String> = items
+                                .iter()
+                                .map(|item| py_element(name, element, item, var_types, overflow, line))
+                                .collect();
+                            format!("[{}]", items.join(", "))
+                        }
+                        (VarType::Map(..), _) => default_value(var_type),
+                        // Each field is converted like a store into a variable of the field type
+                        (VarType::Record(record), Expression::Record(values)) => {
+                            let fields: Vec<String> = record
+                                .fields
+                                .iter()
+                                .zip(values)
+                                .map(|((field, field_type), (_, value))| {
+                                    let target = format!("{}.{}", name, field);
+                                    let value = py_element(&target, field_type, value, var_types, overflow, line);
+                                    match field_type {
+                                        VarType::Char { size } => format!("'{}': _fit_text({}, {})", field, value, size),
+                                        _ => format!("'{}': {}", field, value),
+                                    }
+                                })
+                                .collect();
+                            format!("{{{}}}", fields.join(", "))
+                        }
+                        (VarType::Record(_), _) => default_value(var_type),
+                        _ => py_expression(expr, var_types, line),
+                    },
+                    None => default_value(var_type),
+                };
+                let value_str = match var_type {
+                    VarType::Char { size } => format!("_fit_text({}, {})", value_str, size),
+                    _ => value_str,
+                };
+                code.push_str(&format!("{}{} = {}\n", indent, name, value_str));
+            }
+            ASTNode::Input { name, .. } => {
+                let read = match var_types.get(name) {
+                    Some(VarType::Int(int_type)) => py_fit(name, *int_type, "_to_int(_read_line())", line),
+                    Some(VarType::Float) => "_to_float(_read_line())".to_string(),
+                    Some(VarType::Bool) => "_read_line().strip() == \"true\"".to_string(),
+                    Some(VarType::Char { size }) => format!("_fit_text(_read_line(), {})", size),
+                    Some(_) => "_read_line()".to_string(),
+                    None => {
+                        // Undeclared target: the line is consumed and discarded
+                        code.push_str(&format!("{}_read_line()\n", indent));
+                        continue;
+                    }
+                };
+                code.push_str(&format!("{}{} = {}\n", indent, name, read));
+            }
+            ASTNode::Print { to_stderr, expr, .. } => {
+                let text = match expr {
+                    Some(Expression::Literal(text)) => f_string(text, var_types, line),
+                    Some(expr @ Expression::Identifier(name)) if var_types.contains_key(name) => {
+                        py_display(expr, var_types, line)
+                    }
+                    // Bare words are printed as written
+                    Some(Expression::Identifier(word)) => f_string(word, &HashMap::new(), line),
+                    Some(
+                        expr @ (Expression::Index { .. }
+                        | Expression::Len(_)
+                        | Expression::Field { .. }
+                        | Expression::Convert { .. }
+                        | Expression::Text { .. }
+                        | Expression::Math { .. }),
+                    ) => py_display(expr, var_types, line),
+                    Some(cond) => format!("str({}).lower()", py_condition(cond, var_types, line)),
+                    None => "\"\"".to_string(),
+                };
+                if *to_stderr {
+                    code.push_str(&format!("{}print({}, file=sys.stderr)\n", indent, text));
+                } else {
+                    code.push_str(&format!("{}print({})\n", indent, text));
+                }
+            }
+            ASTNode::MathOp { name, field, operator, operand, .. } => {
+                let var_type = target_type(name, field.as_deref(), var_types);
+                // A field is changed like a variable of the field type; errors
+                // name it as written
+                let (place, name) = match field {
+                    Some(field) => (format!("{}['{}']", name, field), format!("{}.{}", name, field)),
+                    None => (name.clone(), name.clone()),
+                };
+                // `/` and `%` check the operand for zero, and an integer `**` checks
+                // for a negative power of zero
+                let divides = checks && matches!(operator, MathOperator::Divide | MathOperator::Modulo);
+                let divisor = |operand: String| {
+                    if divides { format!("_divisor({}, \"{}\", {})", operand, name, line) } else { operand }
+                };
+                let statement = match (var_type, value_kind(operand, var_types)) {
+                    // The operand is stored into the variable's type first, then the
+                    // exact result is; integer division truncates toward zero like the
+                    // native targets
+                    (Some(VarType::Int(int_type)), ValueKind::Int | ValueKind::Bool) => {
+                        let mut operand = divisor(py_int_value(&name, *int_type, operand, var_types, overflow, line));
+                        if checks && *operator == MathOperator::Power {
+                            operand = format!("_exponent({}, {}, \"{}\", {})", place, operand, name, line);
+                        }
+                        let value = match operator {
+                            MathOperator::Divide => format!("_int_div({}, {})", place, operand),
+                            MathOperator::Modulo => format!("_int_rem({}, {})", place, operand),
+                            MathOperator::Power => format!("_pow({}, {})", place, operand),
+                            _ => py_math(&place, operator, &operand),
+                        };
+                        format!("{} = {}", place, py_fit(&name, *int_type, &value, line))
+                    }
+                    // A float operand on an integer is applied in floating point
+                    (Some(VarType::Int(int_type)), _) => {
+                        let value = py_math(&place, operator, &divisor(py_number(operand, var_types, line)));
+                        format!("{} = {}", place, py_call("_fit_float", &name, *int_type, &value, line))
+                    }
+                    _ => {
+                        let operand = divisor(py_number(operand, var_types, line));
+                        match operator {
+                            MathOperator::Modulo | MathOperator::Power => {
+                                format!("{} = {}", place, py_math(&place, operator, &operand))
+                            }
+                            _ => format!("{} {}= {}", place, py_operator(operator), operand),
+                        }
+                    }
+                };
+                code.push_str(&format!("{}{}\n", indent, statement));
+            }
+            ASTNode::If { condition, body, .. } => {
+                code.push_str(&format!("{}if {}:\n", indent, py_condition(condition, var_types, line)));
+                // Python needs a statement in every block
+                if body.is_empty() {
+                    code.push_str(&format!("{}    pass\n", indent));
+                } else {
+                    emit_nodes(code, body, depth + 1, var_types, overflow, checks);
+                }
+            }
+            ASTNode::SetIndex { name, index, value, .. } if let Some((key, element)) = map_types(name, var_types) => {
+                // The key is converted before the value is evaluated
+                let key = py_element(name, key, index, var_types, Overflow::Trap, line);
+                code.push_str(&format!("{}_at = {}\n", indent, key));
+                let value = py_element(name, element, value, var_types, overflow, line);
+                code.push_str(&format!("{}{}[_at] = {}\n", indent, name, value));
+            }
+            ASTNode::SetIndex { name, index, value, .. } => {
+                // The index is checked before the value is evaluated
+                let Some(element) = element_type(name, var_types) else { continue };
+                code.push_str(&format!(
+                    "{}_at = _index({}, len({}), \"{}\", {})\n",
+                    indent,
+                    py_expression(index, var_types, line),
+                    name,
+                    name,
+                    line
+                ));
+                let value = py_element(name, element, value, var_types, overflow, line);
+                code.push_str(&format!("{}{}[_at] = {}\n", indent, name, value));
+            }
+            ASTNode::Append { name, value, .. } => {
+                let Some(element) = element_type(name, var_types) else { continue };
+                let value = py_element(name, element, value, var_types, overflow, line);
+                code.push_str(&format!("{}{}.append({})\n", indent, name, value));
+            }
+            ASTNode::ForEach { item, array, body } => {
+                // The length is read before each pass, so appended elements are visited
+                let counter = format!("_loop{}", depth);
+                code.push_str(&format!("{}{} = 0\n", indent, counter));
+                code.push_str(&format!("{}while {} < len({}):\n", indent, counter, array));
+                code.push_str(&format!("{}    {} = {}[{}]\n", indent, item, array, counter));
+                emit_nodes(code, body, depth + 1, var_types, overflow, checks);
+                code.push_str(&format!("{}    {} += 1\n", indent, counter));
+            }
+            // Records are dicts and need no declaration
+            ASTNode::RecordDeclaration(_) => {}
+            // Rejected by check_support
+            ASTNode::Call { .. } => {}
+        }
+    }
+}
+
+/// `expr` converted for a store into an element of the array `name`, or a key
+/// or value of the map `name`.
+fn py_element(
+    name: &str,
+    element: &VarType,
+    expr: &Expression,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    line: usize,
+) -> String {
+    match element {
+        VarType::Int(int_type) => py_int_value(name, *int_type, expr, var_types, overflow, line),
+        VarType::String | VarType::Char { .. } => match expr {
+            Expression::Literal(lit) => py_string(&stored_text(lit, element)),
+            _ if value_kind(expr, var_types) == ValueKind::Str => py_expression(expr, var_types, line),
+            _ => "\"\"".to_string(),
+        },
+        _ => py_expression(expr, var_types, line),
+    }
+}
+
+/// Builds an f-string from a print literal, or a plain string when nothing is interpolated.
+fn f_string(text: &str, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let segments = split_interpolation(text);
+    let interpolated = segments.iter().any(|segment| match segment {
+        Segment::Text(_) => false,
+        Segment::Var(name) => placeholder(name, var_types).is_some(),
+        Segment::Formatted(name, _) => formatted(name, var_types).is_some(),
+    });
+    if !interpolated {
+        let plain: String = segments
+            .into_iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text,
+                Segment::Var(_) | Segment::Formatted(..) => "[undefined]".to_string(),
+            })
+            .collect();
+        return py_string(&plain);
+    }
+
+    let mut out = String::from("f\"");
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => out.push_str(&escape_str(&text).replace('{', "{{").replace('}', "}}")),
+            Segment::Var(name) => match placeholder(&name, var_types) {
+                Some(expr) => out.push_str(&format!("{{{}}}", py_display(&expr, var_types, line))),
+                None => out.push_str("[undefined]"),
+            },
+            Segment::Formatted(name, spec) => match formatted(&name, var_types) {
+                Some(expr) => {
+                    let kind = value_kind(&expr, var_types);
+                    let text = match (kind, spec.precision) {
+                        (ValueKind::Float, Some(precision)) => {
+                            format!("_fixed({}, {})", py_expression(&expr, var_types, line), precision)
+                        }
+                        (ValueKind::Int, _) => format!("str({})", py_expression(&expr, var_types, line)),
+                        _ => py_display(&expr, var_types, line),
+                    };
+                    let right = spec.right(matches!(kind, ValueKind::Int | ValueKind::Float));
+                    let right = if right { "True" } else { "False" };
+                    out.push_str(&format!("{{_pad({}, {}, {})}}", text, spec.width, right));
+                }
+                None => out.push_str("[undefined]"),
+            },
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Whether a variable of `var_type` holds floats.
+fn has_float(var_type: &VarType) -> bool {
+    match var_type {
+        VarType::Float => true,
+        VarType::Array(element) | VarType::Map(_, element) => has_float(element),
+        VarType::Record(record) => record.fields.iter().any(|(_, field_type)| has_float(field_type)),
+        _ => false,
+    }
+}
+
+/// A variable, element or length as Ven prints it: booleans are `true` and
+/// `false`, arrays `[a, b, c]`, and maps and records `{k: v}`.
+fn py_display(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let value = py_expression(expr, var_types, line);
+    match expr {
+        Expression::Identifier(name) if matches!(var_types.get(name), Some(VarType::Map(..) | VarType::Record(_))) => {
+            format!("_show_map({})", name)
+        }
+        Expression::Identifier(name) if element_type(name, var_types).is_some() => format!("_show({})", name),
+        _ if value_kind(expr, var_types) == ValueKind::Bool => format!("str({}).lower()", value),
+        _ if value_kind(expr, var_types) == ValueKind::Float => format!("_float_text({})", value),
+        _ => value,
+    }
+}
+
+fn escape_str(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn py_string(text: &str) -> String {
+    format!("\"{}\"", escape_str(text))
+}
+
+fn default_value(var_type: &VarType) -> String {
+    match var_type {
+        VarType::Int(_) => "0".to_string(),
+        VarType::Float => "0.0".to_string(),
+        VarType::String | VarType::Char { .. } => "\"\"".to_string(),
+        VarType::Bool => "False".to_string(),
+        VarType::Array(_) => "[]".to_string(),
+        VarType::Map(..) => "{}".to_string(),
+        VarType::Record(record) => {
+            let fields: Vec<String> = record
+                .fields
+                .iter()
+                .map(|(field, field_type)| format!("'{}': {}", field, default_value(field_type)))
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+    }
+}
+
+fn py_expression(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::Literal(lit) => {
+            if lit.starts_with('"') || lit.starts_with('\'') {
+                py_string(&literal_text(lit))
+            } else {
+                let number = clean_number(lit);
+                match number.as_str() {
+                    "true" => "True".to_string(),
+                    "false" => "False".to_string(),
+                    _ if number.parse::<f64>().is_ok() => number,
+                    _ => py_string(lit),
+                }
+            }
+        }
+        Expression::Identifier(name) if var_types.contains_key(name) => name.clone(),
+        Expression::Identifier(name) => undeclared(name),
+        Expression::BinaryOp { .. } | Expression::LogicalOp { .. } => py_condition(expr, var_types, line),
+        // `not` binds looser than a comparison it is an operand of
+        Expression::Not(_) => format!("({})", py_condition(expr, var_types, line)),
+        Expression::Index { name, index } if map_types(name, var_types).is_some() => {
+            format!("_get({}, {}, '{}', {})", name, py_key(name, index, var_types, line), name, line)
+        }
+        Expression::In { key, name } => format!("({} in {})", py_key(name, key, var_types, line), name),
+        Expression::Index { name, index } => format!(
+            "{}[_index({}, len({}), '{}', {})]",
+            name,
+            py_expression(index, var_types, line),
+            name,
+            name,
+            line
+        ),
+        Expression::Len(name) => format!("len({})", name),
+        // Single quotes, as the field may sit inside an f-string
+        Expression::Field { name, field } if field_type(name, field, var_types).is_some() => {
+            format!("{}['{}']", name, field)
+        }
+        Expression::Field { .. } => "0".to_string(),
+        // Only found in array, map and record declarations
+        Expression::List(_) | Expression::Record(_) => "0".to_string(),
+        Expression::Convert { to, value } => {
+            let text = py_expression(value, var_types, line);
+            match (to, value_kind(value, var_types)) {
+                (VarType::String, ValueKind::Str) => text,
+                (VarType::String, ValueKind::Int) => format!("str({})", text),
+                (VarType::String, _) => py_display(value, var_types, line),
+                (VarType::Float, ValueKind::Str) => format!("_convert_float({}, {})", text, line),
+                (VarType::Float, _) => format!("float({})", text),
+                (_, ValueKind::Str) => format!("_convert_int({}, {})", text, line),
+                (_, ValueKind::Float) => format!("_float_to_int({})", text),
+                (_, ValueKind::Int) => text,
+                (_, ValueKind::Bool) => format!("int({})", text),
+            }
+        }
+        Expression::Text { op, args } => {
+            let text = |i: usize| py_expression(&args[i], var_types, line);
+            match op {
+                TextOp::Concat => {
+                    let parts: Vec<String> = args.iter().map(|arg| py_expression(arg, var_types, line)).collect();
+                    format!("_fit_text({}, {})", parts.join(" + "), STR_CAPACITY)
+                }
+                TextOp::Len => format!("len({})", text(0)),
+                TextOp::Slice => format!("_slice({}, {}, {})", text(0), text(1), text(2)),
+                TextOp::Upper => format!("{}.translate(_UPPER)", text(0)),
+                TextOp::Lower => format!("{}.translate(_LOWER)", text(0)),
+                TextOp::Trim => format!("_trim({})", text(0)),
+                TextOp::Contains => format!("({} in {})", text(1), text(0)),
+                TextOp::StartsWith => format!("{}.startswith({})", text(0), text(1)),
+                TextOp::Replace => {
+                    format!("_fit_text(_replace({}, {}, {}), {})", text(0), text(1), text(2), STR_CAPACITY)
+                }
+            }
+        }
+        Expression::Math { op, args } => py_math_fn(*op, args, var_types, line),
+    }
+}
+
+/// `x op y` in floating point, where `%` and `**` follow C's `fmod` and `pow`.
+fn py_math(x: &str, operator: &MathOperator, y: &str) -> String {
+    match operator {
+        MathOperator::Modulo => format!("_fmod({}, {})", x, y),
+        MathOperator::Power => format!("_fpow({}, {})", x, y),
+        _ => format!("{} {} {}", x, py_operator(operator), y),
+    }
+}
+
+fn py_operator(operator: &MathOperator) -> &'static str {
+    match operator {
+        MathOperator::Add => "+",
+        MathOperator::Subtract => "-",
+        MathOperator::Multiply => "*",
+        MathOperator::Divide => "/",
+        MathOperator::Modulo => "%",
+        MathOperator::Power => "**",
+    }
+}
+
+/// A math function or `-x`. Integer arguments are stored into an `i64`
+/// first, and so is the integer result.
+fn py_math_fn(op: MathFn, args: &[Expression], var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let name = op.called();
+    if math_kind(op, args, var_types) == ValueKind::Int {
+        let values: Vec<String> = args
+            .iter()
+            .map(|arg| py_int_value(name, IntType::I64, arg, var_types, Overflow::Trap, line))
+            .collect();
+        let value = match op {
+            MathFn::Negate => py_fit(name, IntType::I64, &format!("-({})", values[0]), line),
+            MathFn::Abs => py_fit(name, IntType::I64, &format!("abs({})", values[0]), line),
+            MathFn::Min | MathFn::Max => format!("{}({})", op.name(), values.join(", ")),
+            MathFn::Sqrt | MathFn::Floor | MathFn::Ceil | MathFn::Round => values[0].clone(),
+        };
+        // Single quotes, as the call may sit inside an f-string
+        return value.replace('"', "'");
+    }
+    let values: Vec<String> = args.iter().map(|arg| py_number(arg, var_types, line)).collect();
+    match op {
+        MathFn::Negate => format!("(-({}))", values[0]),
+        MathFn::Abs => format!("abs({})", values[0]),
+        MathFn::Min | MathFn::Max => format!("{}({})", op.name(), values.join(", ")),
+        _ => format!("_{}({})", op.name(), values[0]),
+    }
+}
+
+/// The key `expr` of the map `name`. A literal that does not fit is left to
+/// the runtime check, whose quotes are single as the key may sit inside an
+/// f-string.
+fn py_key(name: &str, expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match map_types(name, var_types) {
+        Some((VarType::Int(int_type), _)) => {
+            py_int_value(name, *int_type, expr, var_types, Overflow::Trap, line).replace('"', "'")
+        }
+        _ => py_expression(expr, var_types, line),
+    }
+}
+
+/// `helper(value, lo, hi, name, type, line)` for a store into the integer variable `name`.
+fn py_call(helper: &str, name: &str, int_type: IntType, value: &str, line: usize) -> String {
+    format!(
+        "{}({}, {}, {}, \"{}\", \"{}\", {})",
+        helper,
+        value,
+        int_type.min(),
+        int_type.max(),
+        name,
+        int_type,
+        line
+    )
+}
+
+fn py_fit(name: &str, int_type: IntType, value: &str, line: usize) -> String {
+    py_call("_fit", name, int_type, value, line)
+}
+
+/// `expr` converted for a store into the integer variable `name`. Checks are
+/// left out where the value always fits.
+fn py_int_value(
+    name: &str,
+    int_type: IntType,
+    expr: &Expression,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    line: usize,
+) -> String {
+    let value = py_expression(expr, var_types, line);
+    match (expr, value_kind(expr, var_types)) {
+        (Expression::Literal(lit), ValueKind::Int) => {
+            let literal = int_literal(lit).unwrap_or(0);
+            match fit(literal, int_type, overflow) {
+                Some(fitted) => fitted.to_string(),
+                None => py_fit(name, int_type, &literal.to_string(), line),
+            }
+        }
+        (
+            Expression::Identifier(_)
+            | Expression::Index { .. }
+            | Expression::Len(_)
+            | Expression::Field { .. }
+            | Expression::Convert { .. }
+            | Expression::Text { .. }
+            | Expression::Math { .. },
+            ValueKind::Int,
+        ) => {
+            match int_type_of(expr, var_types) {
+                Some(other_type) if !int_type.holds(other_type) => py_fit(name, int_type, &value, line),
+                _ => value,
+            }
+        }
+        (_, ValueKind::Int) => value,
+        (_, ValueKind::Bool) => format!("int({})", value),
+        (_, ValueKind::Float) => py_call("_fit_float", name, int_type, &value, line),
+        (_, ValueKind::Str) => py_fit(name, int_type, &format!("_to_int({})", value), line),
+    }
+}
+
+/// `expr` as a number for math; text that is not a number counts as 0.
+fn py_number(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let value = py_expression(expr, var_types, line);
+    match value_kind(expr, var_types) {
+        ValueKind::Str => format!("_to_float({})", value),
+        ValueKind::Bool => format!("int({})", value),
+        _ => value,
+    }
+}
+
+fn py_condition(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::BinaryOp { left, operator, right } => {
+            let op = match operator {
+                ComparisonOperator::Equal => "==",
+                ComparisonOperator::NotEqual => "!=",
+                ComparisonOperator::LessThan => "<",
+                ComparisonOperator::LessThanEqual => "<=",
+                ComparisonOperator::GreaterThan => ">",
+                ComparisonOperator::GreaterThanEqual => ">=",
+            };
+            // Python compares an int with a float exactly; the other targets
+            // convert the int to a float first
+            let operand = |expr: &Expression, other: &Expression| {
+                let value = py_expression(expr, var_types, line);
+                match (value_kind(expr, var_types), value_kind(other, var_types)) {
+                    (ValueKind::Int, ValueKind::Float) if !matches!(expr, Expression::Literal(_)) => {
+                        format!("float({})", value)
+                    }
+                    _ => value,
+                }
+            };
+            format!("{} {} {}", operand(left, right), op, operand(right, left))
+        }
+        Expression::LogicalOp { left, operator, right } => {
+            let op = match operator {
+                LogicalOperator::And => "and",
+                LogicalOperator::Or => "or",
+            };
+            format!(
+                "({}) {} ({})",
+                py_condition(left, var_types, line),
+                op,
+                py_condition(right, var_types, line)
+            )
+        }
+        Expression::Not(operand) => format!("not ({})", py_condition(operand, var_types, line)),
+        _ => py_expression(expr, var_types, line),
+    }
+}
+
+pub struct PyBackend;
+
+impl Backend for PyBackend {
+    fn name(&self) -> &'static str {
+        "py"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["python"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Python"
+    }
+
+    fn extension(&self) -> &'static str {
+        "py"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::ALL
+    }
+
+    fn emit(&self, ast: &AST, options: &CodegenOptions) -> Result<String, BackendError> {
+        Ok(transpile_py(ast, options))
+    }
+}
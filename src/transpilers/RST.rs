@@ -1,142 +1,1067 @@
-use crate::parse::{AST, ASTNode, Expression, MathOperator, VarType};
+use crate::errs::BackendError;
+use crate::parse::{
+    AST, ASTNode, ComparisonOperator, Expression, IntType, LogicalOperator, MathFn, MathOperator, TextOp, VarType,
+};
+use crate::transpilers::{
+    Backend, Capabilities, CodegenOptions, Overflow, STR_CAPACITY, Segment, ValueKind, clean_number, collect_var_types,
+    element_type, field_type, fit, formatted, has_buffer, int_literal, int_type_of, is_text_var, literal_text,
+    map_types, math_kind, placeholder, record_types, split_interpolation, stored_text, target_type, undeclared,
+    uses_conversions, uses_division, uses_math, uses_strings, value_kind,
+};
+use std::collections::HashMap;
 
-pub fn transpile_rs(ast: &AST) -> String {
+// Line input and conversions, shared by every generated program
+const RUNTIME_HELPERS: &str = r#"fn ven_read_line() -> String {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).unwrap_or(0);
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+    // A line holds at most 255 bytes, as on the native targets
+    let mut end = line.len().min(255);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+    line
+}
+
+// Text that is not a whole number from i64::MIN to u64::MAX reads as 0
+fn ven_to_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+        .unwrap_or(0)
+}
+
+fn ven_to_float(text: &str) -> f64 {
+    text.trim().parse().unwrap_or(0.0)
+}
+
+fn ven_to_bool(text: &str) -> bool {
+    text.trim() == "true"
+}
+"#;
+
+// `i()` and `f()` of text, for programs that convert values
+const CONVERT_HELPERS: &str = r#"
+fn ven_convert_failed(text: &str, to: &str, line: u32) -> ! {
+    eprintln!("runtime error: cannot convert \"{}\" to {} on line {}", text, to, line);
+    std::process::exit(1);
+}
+
+// A sign and decimal digits within i64, with spaces around them
+fn ven_convert_int(text: &str, line: u32) -> i64 {
+    let digits = text.trim_matches([' ', '\t', '\n', '\r']);
+    let unsigned = digits.strip_prefix(['+', '-']).unwrap_or(digits);
+    match digits.parse() {
+        Ok(value) if unsigned.bytes().all(|b| b.is_ascii_digit()) => value,
+        _ => ven_convert_failed(text, "i", line),
+    }
+}
+
+// A finite decimal number like -1.5, .5, 2. or 6.02e23, with spaces around it
+fn ven_convert_float(text: &str, line: u32) -> f64 {
+    let number = text.trim_matches([' ', '\t', '\n', '\r']);
+    let unsigned = number.strip_prefix(['+', '-']).unwrap_or(number);
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(at) => (&unsigned[..at], Some(&unsigned[at + 1..])),
+        None => (unsigned, None),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    let exponent_ok = exponent.map_or(true, |exponent| {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        !exponent.is_empty() && digits(exponent)
+    });
+    match number.parse::<f64>() {
+        Ok(value) if whole.len() + fraction.len() > 0 && digits(whole) && digits(fraction) && exponent_ok
+            && value.is_finite() => value,
+        _ => ven_convert_failed(text, "f", line),
+    }
+}
+"#;
+
+// Integer overflow, one body per `--overflow=` mode. `ven_fit` stores a value
+// into the type running from `min` to `max`.
+const WRAP_HELPERS: &str = r#"
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        let span = max - min + 1;
+        let low = value.rem_euclid(span);
+        return if low > max { low - span } else { low };
+    }
+    value
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => (a as u64).wrapping_mul(b as u64) as i128,
+    }
+}
+"#;
+
+const SATURATE_HELPERS: &str = r#"
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    value.clamp(min, max)
+}
+
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    a.checked_mul(b).unwrap_or(max).clamp(min, max)
+}
+"#;
+
+const TRAP_HELPERS: &str = r#"
+fn ven_overflow(name: &str, ty: &str, line: u32) -> ! {
+    eprintln!("runtime error: integer overflow in '{}' ({}) on line {}", name, ty, line);
+    std::process::exit(1);
+}
+
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        ven_overflow(name, ty, line);
+    }
+    value
+}
+
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value.is_nan() {
+        return 0;
+    }
+    if value.trunc() < min as f64 || value.trunc() >= max as f64 + 1.0 {
+        ven_overflow(name, ty, line);
+    }
+    value as i128
+}
+
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => ven_overflow(name, ty, line),
+    }
+}
+"#;
+
+// Bounds checks, for programs with arrays
+const ARRAY_HELPERS: &str = r#"
+fn ven_index(index: i128, len: usize, name: &str, line: u32) -> usize {
+    if index < 0 || index >= len as i128 {
+        eprintln!("runtime error: index {} is out of bounds for '{}' (length {}) on line {}", index, name, len, line);
+        std::process::exit(1);
+    }
+    index as usize
+}
+"#;
+
+// Maps keep their entries in insertion order, with a hash index beside them
+const MAP_HELPERS: &str = r#"
+struct VenMap<K, V> {
+    index: std::collections::HashMap<K, usize>,
+    entries: Vec<(K, V)>,
+}
+
+// How a missing key is shown: text in double quotes
+trait VenKey: std::hash::Hash + Eq + Clone + std::fmt::Display {
+    fn shown(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl VenKey for String {
+    fn shown(&self) -> String {
+        format!("\"{}\"", self)
+    }
+}
+
+impl VenKey for i64 {}
+
+impl<K: VenKey, V: Clone + std::fmt::Display> VenMap<K, V> {
+    fn new() -> Self {
+        VenMap { index: std::collections::HashMap::new(), entries: Vec::new() }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn has<Q: std::hash::Hash + Eq + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: std::borrow::Borrow<Q>,
+    {
+        self.index.contains_key(key)
+    }
+
+    fn get<Q: std::hash::Hash + Eq + ToOwned<Owned = K> + ?Sized>(&self, key: &Q, name: &str, line: u32) -> V
+    where
+        K: std::borrow::Borrow<Q>,
+    {
+        match self.index.get(key) {
+            Some(&at) => self.entries[at].1.clone(),
+            None => {
+                eprintln!("runtime error: key {} is not in '{}' on line {}", key.to_owned().shown(), name, line);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // A new key goes last; an existing one keeps its place
+    fn set(&mut self, key: K, value: V) {
+        match self.index.get(&key) {
+            Some(&at) => self.entries[at].1 = value,
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+}
+
+impl<K: std::fmt::Display, V: std::fmt::Display> std::fmt::Display for VenMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("{")?;
+        for (i, (key, value)) in self.entries.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", key, value)?;
+        }
+        f.write_str("}")
+    }
+}
+"#;
+
+// Cutting text to a `c[size]` buffer or a `str`, for programs that have a
+// buffer or build text with string operations
+const TEXT_HELPERS: &str = r#"
+fn ven_fit_text(mut text: String, size: usize) -> String {
+    if text.len() > size {
+        let mut end = size;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+    text
+}
+"#;
+
+// `slice()` and `replace()`, for programs that use string operations
+const STRING_HELPERS: &str = r#"
+// The characters from `start` up to `end`, both clamped to the text
+fn ven_slice(text: &str, start: i128, end: i128) -> String {
+    let offset = |index: i128| {
+        let index = index.clamp(0, usize::MAX as i128) as usize;
+        text.char_indices().nth(index).map_or(text.len(), |(offset, _)| offset)
+    };
+    let (start, end) = (offset(start), offset(end));
+    if end <= start { String::new() } else { text[start..end].to_string() }
+}
+
+// An empty `from` leaves the text as it is
+fn ven_replace(text: &str, from: &str, to: &str) -> String {
+    if from.is_empty() { text.to_string() } else { text.replace(from, to) }
+}
+"#;
+
+// The division by zero checks, unless `--no-runtime-checks` drops them
+const DIVISION_HELPERS: &str = r#"
+fn ven_div_zero(name: &str, line: u32) -> ! {
+    eprintln!("runtime error: division of '{}' by zero on line {}", name, line);
+    std::process::exit(1);
+}
+
+fn ven_divisor(y: i128, name: &str, line: u32) -> i128 {
+    if y == 0 {
+        ven_div_zero(name, line);
+    }
+    y
+}
+
+fn ven_divisor_f(y: f64, name: &str, line: u32) -> f64 {
+    if y == 0.0 {
+        ven_div_zero(name, line);
+    }
+    y
+}
+
+// A negative power of zero divides by zero too
+fn ven_exponent(x: i128, y: i128, name: &str, line: u32) -> i128 {
+    if x == 0 && y < 0 {
+        ven_div_zero(name, line);
+    }
+    y
+}
+"#;
+
+// `**` and the math functions, for programs that use them
+const MATH_HELPERS: &str = r#"
+// A negative `y` gives `1 / x ** -y` truncated toward zero. A power too large
+// for i128 comes back as a stand-in outside every integer type, with its sign
+// and low 64 bits, which `ven_fit` handles as it would the power itself
+fn ven_pow(x: i128, y: i128) -> i128 {
+    match x {
+        _ if y == 0 => return 1,
+        0 | 1 => return x,
+        -1 => return if y % 2 == 0 { 1 } else { -1 },
+        _ if y < 0 => return 0,
+        _ => {}
+    }
+    if let Some(power) = if y <= u32::MAX as i128 { x.checked_pow(y as u32) } else { None } {
+        return power;
+    }
+    let (mut base, mut exponent, mut low) = (x as u64, y as u64, 1u64);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            low = low.wrapping_mul(base);
+        }
+        base = base.wrapping_mul(base);
+        exponent >>= 1;
+    }
+    if x < 0 && y % 2 == 1 { low as i128 - (1i128 << 65) } else { low as i128 + (1i128 << 64) }
+}
+
+// A later value only wins when it is smaller, or larger
+fn ven_min<T: PartialOrd + Copy>(values: &[T]) -> T {
+    values[1..].iter().fold(values[0], |min, &x| if x < min { x } else { min })
+}
+
+fn ven_max<T: PartialOrd + Copy>(values: &[T]) -> T {
+    values[1..].iter().fold(values[0], |max, &x| if x > max { x } else { max })
+}
+"#;
+
+pub fn transpile_rs(ast: &AST, options: &CodegenOptions) -> Result<String, String> {
     let mut code = String::with_capacity(1024);
+    let vars = collect_var_types(ast);
+    let var_types: HashMap<String, VarType> = vars.iter().cloned().collect();
+
+    code.push_str("// Generated by the Ven compiler\n");
+    code.push_str("#![allow(dead_code, unused_mut, unused_assignments, unused_variables, unused_parens)]\n");
+    code.push_str("use std::io::BufRead;\n\n");
+    code.push_str(RUNTIME_HELPERS);
+    code.push_str(match options.overflow {
+        Overflow::Wrap => WRAP_HELPERS,
+        Overflow::Saturate => SATURATE_HELPERS,
+        Overflow::Trap => TRAP_HELPERS,
+    });
+    if vars.iter().any(|(_, var_type)| matches!(var_type, VarType::Array(_))) {
+        code.push_str(ARRAY_HELPERS);
+    }
+    if vars.iter().any(|(_, var_type)| matches!(var_type, VarType::Map(..))) {
+        code.push_str(MAP_HELPERS);
+    }
+    let AST::Program(nodes) = ast;
+    let strings = uses_strings(nodes, &var_types);
+    if strings || vars.iter().any(|(_, var_type)| has_buffer(var_type)) {
+        code.push_str(TEXT_HELPERS);
+    }
+    if uses_math(nodes, &var_types) {
+        code.push_str(MATH_HELPERS);
+    }
+    if options.runtime_checks && uses_division(nodes) {
+        code.push_str(DIVISION_HELPERS);
+    }
+    if uses_conversions(nodes, &var_types) {
+        code.push_str(CONVERT_HELPERS);
+    }
+    if strings {
+        code.push_str(STRING_HELPERS);
+    }
+    // Each record type becomes a struct that prints as {field: value, field: value}
+    for record in record_types(ast) {
+        code.push_str(&format!("\n#[derive(Clone, Default)]\nstruct {} {{\n", record.name));
+        for (field, var_type) in &record.fields {
+            code.push_str(&format!("    {}: {},\n", field, rs_type(var_type)));
+        }
+        code.push_str("}\n\n");
+        code.push_str(&format!("impl std::fmt::Display for {} {{\n", record.name));
+        code.push_str("    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {\n");
+        let shown: Vec<String> = record.fields.iter().map(|(field, _)| format!("{}: {{}}", field)).collect();
+        let values: String = record.fields.iter().map(|(field, _)| format!(", self.{}", field)).collect();
+        code.push_str(&format!("        write!(f, \"{{{{{}}}}}\"{})\n", shown.join(", "), values));
+        code.push_str("    }\n}\n");
+    }
+    code.push_str("\nfn main() {\n");
+
+    // Ven variables are program-wide, so every name is declared up front
+    for (name, var_type) in &vars {
+        let declaration = match var_type {
+            VarType::Int(int_type) => format!("let mut {}: {} = 0;", name, int_type),
+            VarType::Float => format!("let mut {}: f64 = 0.0;", name),
+            VarType::String | VarType::Char { .. } => format!("let mut {} = String::new();", name),
+            VarType::Bool => format!("let mut {} = false;", name),
+            VarType::Array(element) => format!("let mut {}: Vec<{}> = Vec::new();", name, rs_type(element)),
+            VarType::Map(key, value) => {
+                format!("let mut {}: VenMap<{}, {}> = VenMap::new();", name, rs_type(key), rs_type(value))
+            }
+            VarType::Record(record) => format!("let mut {} = {}::default();", name, record.name),
+        };
+        code.push_str(&format!("    {}\n", declaration));
+    }
 
-    // Add necessary imports at the top
-    code.push_str("use std::io::Write;\n");
-    code.push_str("fn main() {\n");
-
-    match ast {
-        AST::Program(nodes) => {
-            for node in nodes {
-                match node {
-                    ASTNode::VarDeclaration {
-                        mutable,
-                        name,
-                        var_type,
-                        value,
-                    } => {
-                        let mutability = if *mutable { "mut " } else { "" };
-                        let rust_type = match var_type {
-                            VarType::Int => "i32",
-                            VarType::String => "String",
-                            VarType::Char { .. } => "char",
-                            VarType::Float => "f64",
-                        };
-                        let value_str = match value {
-                            Some(Expression::Literal(val)) => format!(" = {}", val),
-                            Some(Expression::Identifier(val)) => format!(" = {}", val),
-                            Some(Expression::BinaryOp { .. }) => String::new(),
-                            Some(Expression::LogicalOp { .. }) => String::new(),
-                            None => String::new(),
-                        };
-                        code.push_str(&format!(
-                            "    let {}{}: {}{};\n",
-                            mutability, name, rust_type, value_str
-                        ));
+    emit_nodes(&mut code, nodes, 1, &var_types, options.overflow, options.runtime_checks)?;
+    code.push_str("}\n");
+
+    code.shrink_to_fit();
+    Ok(code)
+}
+
+/// Emits `nodes`, or names the first construct that cannot be translated.
+fn emit_nodes(
+    code: &mut String,
+    nodes: &[ASTNode],
+    depth: usize,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    checks: bool,
+) -> Result<(), String> {
+    let indent = "    ".repeat(depth);
+    for node in nodes {
+        // Named by the runtime errors the statement can stop the program with
+        let line = node.line().unwrap_or(0);
+        match node {
+            ASTNode::VarDeclaration { name, var_type, value, .. } => {
+                let value = match (var_type, value) {
+                    // Declaration literals arrive with their quotes already stripped
+                    (VarType::String | VarType::Char { .. }, Some(Expression::Literal(lit))) => {
+                        format!("{:?}.to_string()", stored_text(lit, var_type))
+                    }
+                    (VarType::String | VarType::Char { .. }, Some(expr))
+                        if value_kind(expr, var_types) == ValueKind::Str =>
+                    {
+                        format!("{}.to_string()", rs_expression(expr, var_types, line))
                     }
-                    ASTNode::Input { name } => {
-                        code.push_str(&format!(
-                            "    std::io::stdin().read_line(&mut {0}).unwrap();\n",
-                            name
-                        ));
+                    (VarType::String | VarType::Char { .. }, _) => "String::new()".to_string(),
+                    (VarType::Int(int_type), Some(expr)) => rs_int_value(name, *int_type, expr, var_types, overflow, line),
+                    (VarType::Array(element), Some(Expression::List(items))) => {
+                        let items: Vec<String> = items
+                            .iter()
+                            .map(|item| rs_element(name, element, item, var_types, overflow, line))
+                            .collect();
+                        format!("vec![{}]", items.join(", "))
                     }
-                    ASTNode::Print { to_stderr, expr } => {
-                        let output_target = if *to_stderr {
-                            "std::io::stderr().lock()"
-                        } else {
-                            "std::io::stdout().lock()"
-                        };
-
-                        if let Some(Expression::Literal(val)) = expr {
-                            code.push_str(&format!(
-                                "    writeln!(&mut {}, \"{}\").unwrap();\n",
-                                output_target, val
-                            ));
-                        } else if let Some(Expression::Identifier(val)) = expr {
-                            code.push_str(&format!(
-                                "    writeln!(&mut {}, \"{{}}\", {}).unwrap();\n",
-                                output_target, val
-                            ));
-                        } else {
-                            code.push_str(&format!(
-                                "    writeln!(&mut {}).unwrap();\n",
-                                output_target
-                            ));
+                    (VarType::Array(_), _) => "Vec::new()".to_string(),
+                    (VarType::Map(..), _) => "VenMap::new()".to_string(),
+                    // Each field is converted like a store into a variable of the field type
+                    (VarType::Record(record), Some(Expression::Record(values))) => {
+                        let fields: Vec<String> = record
+                            .fields
+                            .iter()
+                            .zip(values)
+                            .map(|((field, field_type), (_, value))| {
+                                let target = format!("{}.{}", name, field);
+                                let value = rs_element(&target, field_type, value, var_types, overflow, line);
+                                match field_type {
+                                    VarType::Char { size } => format!("{}: ven_fit_text({}, {})", field, value, size),
+                                    _ => format!("{}: {}", field, value),
+                                }
+                            })
+                            .collect();
+                        format!("{} {{ {} }}", record.name, fields.join(", "))
+                    }
+                    (VarType::Record(record), _) => format!("{}::default()", record.name),
+                    (_, Some(expr)) => rs_value(expr, var_type, var_types, line),
+                    (VarType::Float, None) => "0.0".to_string(),
+                    (VarType::Bool, None) => "false".to_string(),
+                    (_, None) => "0".to_string(),
+                };
+                let value = match var_type {
+                    VarType::Char { size } => format!("ven_fit_text({}, {})", value, size),
+                    _ => value,
+                };
+                code.push_str(&format!("{}{} = {};\n", indent, name, value));
+            }
+            ASTNode::Input { name, .. } => {
+                let statement = match var_types.get(name) {
+                    Some(VarType::String) => format!("{} = ven_read_line();", name),
+                    Some(VarType::Char { size }) => format!("{} = ven_fit_text(ven_read_line(), {});", name, size),
+                    Some(VarType::Int(int_type)) => {
+                        format!("{} = {};", name, rs_fit(name, *int_type, "ven_to_int(&ven_read_line())", line))
+                    }
+                    Some(VarType::Float) => format!("{} = ven_to_float(&ven_read_line());", name),
+                    Some(VarType::Bool) => format!("{} = ven_to_bool(&ven_read_line());", name),
+                    // Undeclared target: the line is consumed and discarded. The
+                    // parser rejects input into arrays, maps and records.
+                    Some(VarType::Array(_) | VarType::Map(..) | VarType::Record(_)) | None => "ven_read_line();".to_string(),
+                };
+                code.push_str(&format!("{}{}\n", indent, statement));
+            }
+            ASTNode::Print { to_stderr, expr, .. } => {
+                let (format, args) = print_format(expr.as_ref(), var_types, line);
+                let mac = if *to_stderr { "eprintln!" } else { "println!" };
+                let args: String = args.iter().map(|arg| format!(", {}", arg)).collect();
+                code.push_str(&format!("{}{}({:?}{});\n", indent, mac, format, args));
+            }
+            ASTNode::MathOp { name, field, operator, operand, .. } => {
+                let var_type = target_type(name, field.as_deref(), var_types);
+                // A field is changed like a variable of the field type
+                let name = &match field {
+                    Some(field) => format!("{}.{}", name, field),
+                    None => name.clone(),
+                };
+                // `/` and `%` check the operand for zero, and an integer `**` checks
+                // for a negative power of zero
+                let divides = checks && matches!(operator, MathOperator::Divide | MathOperator::Modulo);
+                let value = match (var_type, value_kind(operand, var_types)) {
+                    // The operand is stored into the variable's type first, then the
+                    // exact result is; integer division truncates toward zero
+                    (Some(VarType::Int(int_type)), ValueKind::Int | ValueKind::Bool) => {
+                        let operand = rs_int_value(name, *int_type, operand, var_types, overflow, line);
+                        let (x, mut y) = (format!("{} as i128", name), format!("({}) as i128", operand));
+                        if divides {
+                            y = format!("ven_divisor({}, \"{}\", {})", y, name, line);
+                        } else if checks && *operator == MathOperator::Power {
+                            y = format!("ven_exponent({}, {}, \"{}\", {})", x, y, name, line);
+                        }
+                        match operator {
+                            MathOperator::Multiply => rs_call("ven_mul", name, *int_type, &format!("{}, {}", x, y), line),
+                            MathOperator::Power => rs_fit(name, *int_type, &format!("ven_pow({}, {})", x, y), line),
+                            _ => rs_fit(name, *int_type, &rs_math(&x, operator, &y), line),
                         }
                     }
-                    ASTNode::MathOp {
-                        name,
-                        operator,
-                        operand,
-                    } => {
-                        let op = match operator {
-                            MathOperator::Add => "+",
-                            MathOperator::Subtract => "-",
-                            MathOperator::Multiply => "*",
-                            MathOperator::Divide => "/",
-                        };
-                        let operand_str = match operand {
-                            Expression::Literal(val) => val.clone(),
-                            Expression::Identifier(val) => val.clone(),
-                            Expression::BinaryOp { left, operator, right } => {
-                                let left_str = match **left {
-                                    Expression::Literal(ref l) => l.clone(),
-                                    Expression::Identifier(ref l) => l.clone(),
-                                    _ => "".to_string(),
-                                };
-                                
-                                let right_str = match **right {
-                                    Expression::Literal(ref r) => r.clone(),
-                                    Expression::Identifier(ref r) => r.clone(),
-                                    _ => "".to_string(),
-                                };
-                                
-                                let op_str = match operator {
-                                    crate::parse::ComparisonOperator::Equal => "==",
-                                    crate::parse::ComparisonOperator::NotEqual => "!=",
-                                    crate::parse::ComparisonOperator::LessThan => "<",
-                                    crate::parse::ComparisonOperator::LessThanEqual => "<=",
-                                    crate::parse::ComparisonOperator::GreaterThan => ">",
-                                    crate::parse::ComparisonOperator::GreaterThanEqual => ">=",
-                                };
-                                
-                                format!("({} {} {})", left_str, op_str, right_str)
-                            },
-                            Expression::LogicalOp { left, operator, right } => {
-                                let left_str = match **left {
-                                    Expression::Literal(ref l) => l.clone(),
-                                    Expression::Identifier(ref l) => l.clone(),
-                                    _ => "".to_string(),
-                                };
-                                
-                                let right_str = match **right {
-                                    Expression::Literal(ref r) => r.clone(),
-                                    Expression::Identifier(ref r) => r.clone(),
-                                    _ => "".to_string(),
-                                };
-                                
-                                let op_str = match operator {
-                                    crate::parse::LogicalOperator::And => "&&",
-                                    crate::parse::LogicalOperator::Or => "||",
-                                };
-                                
-                                format!("({} {} {})", left_str, op_str, right_str)
-                            },
-                        };
-                        code.push_str(&format!(
-                            "    {} = {} {} {};\n",
-                            name, name, op, operand_str
-                        ));
+                    // A float operand on an integer, and any operand on an `f`, is applied
+                    // in floating point
+                    (Some(var_type @ (VarType::Int(_) | VarType::Float)), _) => {
+                        let mut operand = rs_float(operand, var_types, line);
+                        if divides {
+                            operand = format!("ven_divisor_f({}, \"{}\", {})", operand, name, line);
+                        }
+                        match var_type {
+                            VarType::Int(int_type) => rs_call(
+                                "ven_fit_float",
+                                name,
+                                *int_type,
+                                &rs_math(&format!("({} as f64)", name), operator, &operand),
+                                line,
+                            ),
+                            _ => rs_math(name, operator, &operand),
+                        }
                     }
-                    _ => {}
+                    (Some(_), _) => return Err(format!("math on the non-numeric variable '{}'", name)),
+                    (None, _) => return Err(format!("math on the undeclared variable '{}'", name)),
+                };
+                code.push_str(&format!("{}{} = {};\n", indent, name, value));
+            }
+            ASTNode::If { condition, body, .. } => {
+                code.push_str(&format!("{}if {} {{\n", indent, rs_condition(condition, var_types, line)));
+                emit_nodes(code, body, depth + 1, var_types, overflow, checks)?;
+                code.push_str(&format!("{}}}\n", indent));
+            }
+            ASTNode::SetIndex { name, index, value, .. } if let Some((key, element)) = map_types(name, var_types) => {
+                let key = rs_element(name, key, index, var_types, Overflow::Trap, line);
+                let value = rs_element(name, element, value, var_types, overflow, line);
+                code.push_str(&format!("{}{}.set({}, {});\n", indent, name, key, value));
+            }
+            ASTNode::SetIndex { name, index, value, .. } => {
+                let Some(element) = element_type(name, var_types) else {
+                    return Err(format!("the undeclared array '{}'", name));
+                };
+                // The bounds are checked before the value is converted
+                code.push_str(&format!(
+                    "{}let ven_at = ven_index({} as i128, {}.len(), {:?}, {});\n",
+                    indent,
+                    rs_expression(index, var_types, line),
+                    name,
+                    name,
+                    line
+                ));
+                let value = rs_element(name, element, value, var_types, overflow, line);
+                code.push_str(&format!("{}{}[ven_at] = {};\n", indent, name, value));
+            }
+            ASTNode::Append { name, value, .. } => {
+                let Some(element) = element_type(name, var_types) else {
+                    return Err(format!("the undeclared array '{}'", name));
+                };
+                let value = rs_element(name, element, value, var_types, overflow, line);
+                code.push_str(&format!("{}{}.push({});\n", indent, name, value));
+            }
+            ASTNode::ForEach { item, array, body } => {
+                // The length is read before each pass, so appended elements are visited
+                let counter = format!("ven_loop{}", depth);
+                code.push_str(&format!("{}let mut {} = 0;\n", indent, counter));
+                code.push_str(&format!("{}while {} < {}.len() {{\n", indent, counter, array));
+                code.push_str(&format!("{}    {} = {}[{}].clone();\n", indent, item, array, counter));
+                emit_nodes(code, body, depth + 1, var_types, overflow, checks)?;
+                code.push_str(&format!("{}    {} += 1;\n", indent, counter));
+                code.push_str(&format!("{}}}\n", indent));
+            }
+            // Emitted as a struct before main
+            ASTNode::RecordDeclaration(_) => {}
+            // Rejected by check_support
+            ASTNode::Call { .. } => {}
+        }
+    }
+    Ok(())
+}
+
+/// The Rust type of an array element or a map key or value.
+fn rs_type(element: &VarType) -> String {
+    match element {
+        VarType::Int(int_type) => int_type.to_string(),
+        VarType::Float => "f64".to_string(),
+        VarType::Bool => "bool".to_string(),
+        _ => "String".to_string(),
+    }
+}
+
+/// `expr` converted for a store into an element of the array `name`, or a key
+/// or value of the map `name`.
+fn rs_element(
+    name: &str,
+    element: &VarType,
+    expr: &Expression,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    line: usize,
+) -> String {
+    match element {
+        VarType::Int(int_type) => rs_int_value(name, *int_type, expr, var_types, overflow, line),
+        VarType::Float | VarType::Bool => rs_value(expr, element, var_types, line),
+        _ => match expr {
+            Expression::Literal(lit) => format!("{:?}.to_string()", stored_text(lit, element)),
+            _ if value_kind(expr, var_types) == ValueKind::Str => format!("{}.to_string()", rs_expression(expr, var_types, line)),
+            _ => "String::new()".to_string(),
+        },
+    }
+}
+
+/// A printable value: arrays print as `[a, b, c]`, maps as `{k: v}` and
+/// records as `{field: v}`.
+fn rs_print_arg(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::Identifier(name) if map_types(name, var_types).is_some() => name.clone(),
+        Expression::Identifier(name) if element_type(name, var_types).is_some() => format!(
+            "format!(\"[{{}}]\", {}.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(\", \"))",
+            name
+        ),
+        Expression::Identifier(name) => name.clone(),
+        _ => rs_expression(expr, var_types, line),
+    }
+}
+
+/// The `println!` format string and its arguments for a print.
+fn print_format(expr: Option<&Expression>, var_types: &HashMap<String, VarType>, line: usize) -> (String, Vec<String>) {
+    let mut format = String::new();
+    let mut args = Vec::new();
+    let escape = |text: &str| text.replace('{', "{{").replace('}', "}}");
+
+    match expr {
+        Some(Expression::Literal(literal)) => {
+            for segment in split_interpolation(literal) {
+                match segment {
+                    Segment::Text(text) => format.push_str(&escape(&text)),
+                    Segment::Var(name) => match placeholder(&name, var_types) {
+                        Some(expr) => {
+                            format.push_str("{}");
+                            args.push(rs_print_arg(&expr, var_types, line));
+                        }
+                        None => format.push_str("[undefined]"),
+                    },
+                    // Rust lays values out the same way, but cuts text to a precision
+                    Segment::Formatted(name, mut spec) => match formatted(&name, var_types) {
+                        Some(expr) => {
+                            if value_kind(&expr, var_types) != ValueKind::Float {
+                                spec.precision = None;
+                            }
+                            format.push_str(&format!("{{:{}}}", spec));
+                            args.push(rs_print_arg(&expr, var_types, line));
+                        }
+                        None => format.push_str("[undefined]"),
+                    },
                 }
             }
         }
+        Some(expr @ Expression::Identifier(name)) if var_types.contains_key(name) => {
+            format.push_str("{}");
+            args.push(rs_print_arg(expr, var_types, line));
+        }
+        // Bare words are printed as written
+        Some(Expression::Identifier(word)) => format.push_str(&escape(&literal_text(word))),
+        Some(
+            expr @ (Expression::Index { .. }
+            | Expression::Len(_)
+            | Expression::Field { .. }
+            | Expression::Convert { .. }
+            | Expression::Text { .. }
+            | Expression::Math { .. }),
+        ) => {
+            format.push_str("{}");
+            args.push(rs_print_arg(expr, var_types, line));
+        }
+        Some(cond) => {
+            format.push_str("{}");
+            args.push(rs_condition(cond, var_types, line));
+        }
+        None => {}
     }
+    (format, args)
+}
 
-    code.push_str("}\n");
-    code.shrink_to_fit();
-    code
+fn rs_expression(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::Literal(lit) => {
+            if lit.starts_with('"') || lit.starts_with('\'') {
+                return format!("{:?}", literal_text(lit));
+            }
+            let number = clean_number(lit);
+            match value_kind(expr, var_types) {
+                ValueKind::Int | ValueKind::Bool => number,
+                // `{:?}` keeps the fraction, so "2." and ".5" become valid Rust
+                ValueKind::Float => format!("{:?}", number.parse::<f64>().unwrap_or(0.0)),
+                ValueKind::Str => format!("{:?}", lit),
+            }
+        }
+        Expression::Identifier(name) if var_types.contains_key(name) => name.clone(),
+        Expression::Identifier(name) => undeclared(name),
+        Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) => {
+            rs_condition(expr, var_types, line)
+        }
+        Expression::Index { name, index } if map_types(name, var_types).is_some() => {
+            format!("{}.get({}, {:?}, {})", name, rs_key(name, index, var_types, line), name, line)
+        }
+        Expression::In { key, name } => format!("{}.has({})", name, rs_key(name, key, var_types, line)),
+        Expression::Index { name, index } => format!(
+            "{}[ven_index({} as i128, {}.len(), {:?}, {})]",
+            name,
+            rs_expression(index, var_types, line),
+            name,
+            name,
+            line
+        ),
+        Expression::Len(name) if is_text_var(name, var_types) => format!("({}.chars().count() as i64)", name),
+        Expression::Len(name) => format!("({}.len() as i64)", name),
+        Expression::Field { name, field } if field_type(name, field, var_types).is_some() => {
+            format!("{}.{}", name, field)
+        }
+        Expression::Field { .. } => "0i64".to_string(),
+        // Only found in array, map and record declarations
+        Expression::List(_) | Expression::Record(_) => "0i64".to_string(),
+        Expression::Convert { to, value } => {
+            let text = rs_expression(value, var_types, line);
+            match (to, value_kind(value, var_types)) {
+                (VarType::String, _) => format!("({}).to_string()", text),
+                (VarType::Float, ValueKind::Str) => format!("ven_convert_float(&{}, {})", text, line),
+                (VarType::Float, _) => rs_float(value, var_types, line),
+                (_, ValueKind::Str) => format!("ven_convert_int(&{}, {})", text, line),
+                (_, ValueKind::Int) => text,
+                // `as` truncates toward zero, with NaN as 0 and clamped at the limits
+                (_, ValueKind::Float | ValueKind::Bool) => format!("({} as i64)", text),
+            }
+        }
+        Expression::Text { op, args } => {
+            let text = |i: usize| rs_str(&args[i], var_types, line);
+            match op {
+                TextOp::Concat => {
+                    let parts: Vec<String> = args.iter().map(|arg| rs_str(arg, var_types, line)).collect();
+                    format!("ven_fit_text([{}].concat(), {})", parts.join(", "), STR_CAPACITY)
+                }
+                TextOp::Len => format!("({}.chars().count() as i64)", text(0)),
+                TextOp::Slice => format!(
+                    "ven_slice({}, {} as i128, {} as i128)",
+                    text(0),
+                    rs_expression(&args[1], var_types, line),
+                    rs_expression(&args[2], var_types, line)
+                ),
+                TextOp::Upper => format!("{}.to_ascii_uppercase()", text(0)),
+                TextOp::Lower => format!("{}.to_ascii_lowercase()", text(0)),
+                TextOp::Trim => format!("{}.trim_matches([' ', '\\t', '\\n', '\\r']).to_string()", text(0)),
+                TextOp::Contains => format!("{}.contains({})", text(0), text(1)),
+                TextOp::StartsWith => format!("{}.starts_with({})", text(0), text(1)),
+                TextOp::Replace => {
+                    format!("ven_fit_text(ven_replace({}, {}, {}), {})", text(0), text(1), text(2), STR_CAPACITY)
+                }
+            }
+        }
+        Expression::Math { op, args } => rs_math_fn(*op, args, var_types, line),
+    }
+}
+
+/// `x op y`, where `**` on floats is `powf`; on integers it is left to `ven_pow`.
+fn rs_math(x: &str, operator: &MathOperator, y: &str) -> String {
+    let op = match operator {
+        MathOperator::Add => "+",
+        MathOperator::Subtract => "-",
+        MathOperator::Multiply => "*",
+        MathOperator::Divide => "/",
+        MathOperator::Modulo => "%",
+        MathOperator::Power => return format!("{}.powf({})", x, y),
+    };
+    format!("{} {} {}", x, op, y)
+}
+
+/// A math function or `-x`. Integer arguments are stored into an `i64`
+/// first, and so is the integer result.
+fn rs_math_fn(op: MathFn, args: &[Expression], var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let name = op.called();
+    if math_kind(op, args, var_types) == ValueKind::Int {
+        let values: Vec<String> = args
+            .iter()
+            .map(|arg| {
+                let value = rs_expression(arg, var_types, line);
+                match int_type_of(arg, var_types) {
+                    Some(IntType::I64) => value,
+                    Some(IntType::U64) => rs_fit(name, IntType::I64, &format!("{} as i128", value), line),
+                    _ if int_literal(&value).is_some_and(|n| n > i64::MAX as i128) => {
+                        rs_fit(name, IntType::I64, &value, line)
+                    }
+                    _ => format!("({} as i64)", value),
+                }
+            })
+            .collect();
+        // In parentheses, as `x as i64 < y` would start generic arguments
+        return match op {
+            MathFn::Negate => format!("({})", rs_fit(name, IntType::I64, &format!("-({} as i128)", values[0]), line)),
+            MathFn::Abs => format!("({})", rs_fit(name, IntType::I64, &format!("({} as i128).abs()", values[0]), line)),
+            MathFn::Min | MathFn::Max => format!("ven_{}(&[{}])", op.name(), values.join(", ")),
+            MathFn::Sqrt | MathFn::Floor | MathFn::Ceil | MathFn::Round => format!("({})", values[0]),
+        };
+    }
+    let values: Vec<String> = args.iter().map(|arg| rs_float(arg, var_types, line)).collect();
+    match op {
+        MathFn::Negate => format!("(-{})", values[0]),
+        MathFn::Min | MathFn::Max => format!("ven_{}(&[{}])", op.name(), values.join(", ")),
+        // Called on the type, as a bare literal has none
+        _ => format!("f64::{}({})", op.name(), values[0]),
+    }
+}
+
+/// A borrowed key `expr` of the map `name`. A literal that does not fit is
+/// left to the runtime check.
+fn rs_key(name: &str, expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match map_types(name, var_types) {
+        Some((VarType::Int(int_type), _)) => {
+            let key = rs_int_value(name, *int_type, expr, var_types, Overflow::Trap, line);
+            // A bare literal needs its type, as nothing else fixes it
+            if key.parse::<i128>().is_ok() {
+                format!("&{}{}", key, int_type)
+            } else if key.contains(' ') {
+                format!("&({})", key)
+            } else {
+                format!("&{}", key)
+            }
+        }
+        _ => rs_str(expr, var_types, line),
+    }
+}
+
+/// `helper(args, min, max, name, type, line) as T` for a store into the integer
+/// variable `name`.
+fn rs_call(helper: &str, name: &str, int_type: IntType, args: &str, line: usize) -> String {
+    format!(
+        "{}({}, {}, {}, {:?}, \"{}\", {}) as {}",
+        helper,
+        args,
+        int_type.min(),
+        int_type.max(),
+        name,
+        int_type,
+        line,
+        int_type
+    )
+}
+
+fn rs_fit(name: &str, int_type: IntType, value: &str, line: usize) -> String {
+    rs_call("ven_fit", name, int_type, value, line)
+}
+
+/// `expr` converted for a store into the integer variable `name`. Checks are
+/// left out where the value always fits.
+fn rs_int_value(
+    name: &str,
+    int_type: IntType,
+    expr: &Expression,
+    var_types: &HashMap<String, VarType>,
+    overflow: Overflow,
+    line: usize,
+) -> String {
+    let value = rs_expression(expr, var_types, line);
+    match (expr, value_kind(expr, var_types)) {
+        (Expression::Literal(lit), ValueKind::Int) => {
+            let literal = int_literal(lit).unwrap_or(0);
+            match fit(literal, int_type, overflow) {
+                Some(fitted) => fitted.to_string(),
+                None => rs_fit(name, int_type, &literal.to_string(), line),
+            }
+        }
+        (
+            Expression::Identifier(_)
+            | Expression::Index { .. }
+            | Expression::Len(_)
+            | Expression::Field { .. }
+            | Expression::Convert { .. }
+            | Expression::Text { .. }
+            | Expression::Math { .. },
+            ValueKind::Int,
+        ) => {
+            match int_type_of(expr, var_types) {
+                Some(other_type) if other_type == int_type => value,
+                Some(other_type) if int_type.holds(other_type) => format!("{} as {}", value, int_type),
+                Some(_) => rs_fit(name, int_type, &format!("{} as i128", value), line),
+                // Undeclared names evaluate to 0
+                None => "0".to_string(),
+            }
+        }
+        (_, ValueKind::Int | ValueKind::Bool) => format!("{} as {}", value, int_type),
+        (_, ValueKind::Float) => rs_call("ven_fit_float", name, int_type, &value, line),
+        (_, ValueKind::Str) => rs_fit(name, int_type, &format!("ven_to_int(&{})", value), line),
+    }
+}
+
+/// Two integer or boolean operands of a comparison, in a type both convert to
+/// without loss: their shared integer type, `i64`, or `i128` when they differ.
+fn rs_int_pair(left: &Expression, right: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> (String, String) {
+    let fits = |expr: &Expression, int_type: IntType| match expr {
+        Expression::Literal(lit) => {
+            value_kind(expr, var_types) == ValueKind::Bool
+                || int_literal(lit).is_some_and(|value| (int_type.min()..=int_type.max()).contains(&value))
+        }
+        Expression::Identifier(name) => !var_types.contains_key(name) || int_type_of(expr, var_types) == Some(int_type),
+        _ => true,
+    };
+    let common = match (int_type_of(left, var_types), int_type_of(right, var_types)) {
+        (Some(a), Some(b)) if a == b => a.to_string(),
+        (Some(t), None) | (None, Some(t)) if fits(left, t) && fits(right, t) => t.to_string(),
+        (None, None) if fits(left, IntType::I64) && fits(right, IntType::I64) => "i64".to_string(),
+        _ => "i128".to_string(),
+    };
+    let convert = |expr: &Expression| {
+        let value = rs_expression(expr, var_types, line);
+        match expr {
+            Expression::Literal(_) if value_kind(expr, var_types) == ValueKind::Int => value,
+            _ if int_type_of(expr, var_types).is_some_and(|int_type| int_type.to_string() == common) => value,
+            _ => format!("({} as {})", value, common),
+        }
+    };
+    (convert(left), convert(right))
+}
+
+/// `expr` as an `f64`; booleans count as 1 and 0.
+fn rs_float(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    let value = rs_expression(expr, var_types, line);
+    match value_kind(expr, var_types) {
+        ValueKind::Float => value,
+        ValueKind::Int => format!("({} as f64)", value),
+        ValueKind::Bool => format!("({} as i64 as f64)", value),
+        ValueKind::Str => format!("ven_to_float(&{})", value),
+    }
+}
+
+/// A text `expr` as a `&str`.
+fn rs_str(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::Identifier(name) if var_types.contains_key(name) => format!("{}.as_str()", name),
+        Expression::Index { .. } | Expression::Field { .. } | Expression::Convert { .. } | Expression::Text { .. } => {
+            format!("{}.as_str()", rs_expression(expr, var_types, line))
+        }
+        _ => rs_expression(expr, var_types, line),
+    }
+}
+
+/// `expr` converted to a `f` or `b` variable.
+fn rs_value(expr: &Expression, var_type: &VarType, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match var_type {
+        VarType::Float => rs_float(expr, var_types, line),
+        _ => match value_kind(expr, var_types) {
+            ValueKind::Str => format!("ven_to_bool(&{})", rs_expression(expr, var_types, line)),
+            _ => rs_condition(expr, var_types, line),
+        },
+    }
+}
+
+fn rs_condition(expr: &Expression, var_types: &HashMap<String, VarType>, line: usize) -> String {
+    match expr {
+        Expression::BinaryOp { left, operator, right } => {
+            let op = match operator {
+                ComparisonOperator::Equal => "==",
+                ComparisonOperator::NotEqual => "!=",
+                ComparisonOperator::LessThan => "<",
+                ComparisonOperator::LessThanEqual => "<=",
+                ComparisonOperator::GreaterThan => ">",
+                ComparisonOperator::GreaterThanEqual => ">=",
+            };
+            let (left_kind, right_kind) = (value_kind(left, var_types), value_kind(right, var_types));
+            let (left, right) = match (left_kind, right_kind) {
+                (ValueKind::Str, ValueKind::Str) => (rs_str(left, var_types, line), rs_str(right, var_types, line)),
+                // A string never equals a number
+                (ValueKind::Str, _) | (_, ValueKind::Str) => {
+                    return (*operator == ComparisonOperator::NotEqual).to_string();
+                }
+                (ValueKind::Bool, ValueKind::Bool) => (rs_expression(left, var_types, line), rs_expression(right, var_types, line)),
+                (ValueKind::Float, _) | (_, ValueKind::Float) => (rs_float(left, var_types, line), rs_float(right, var_types, line)),
+                _ => rs_int_pair(left, right, var_types, line),
+            };
+            format!("({} {} {})", left, op, right)
+        }
+        Expression::LogicalOp { left, operator, right } => {
+            let op = match operator {
+                LogicalOperator::And => "&&",
+                LogicalOperator::Or => "||",
+            };
+            format!(
+                "({} {} {})",
+                rs_condition(left, var_types, line),
+                op,
+                rs_condition(right, var_types, line)
+            )
+        }
+        Expression::Not(operand) => {
+            let operand = rs_condition(operand, var_types, line);
+            if operand.starts_with('(') {
+                format!("!{}", operand)
+            } else {
+                format!("!({})", operand)
+            }
+        }
+        _ => {
+            let value = rs_expression(expr, var_types, line);
+            match value_kind(expr, var_types) {
+                // Strings are true when non-empty
+                ValueKind::Str => format!("!{}.is_empty()", value),
+                ValueKind::Int => format!("({} != 0)", value),
+                ValueKind::Float => format!("({} != 0.0)", value),
+                ValueKind::Bool => value,
+            }
+        }
+    }
+}
+
+pub struct RustBackend;
+
+impl Backend for RustBackend {
+    fn name(&self) -> &'static str {
+        "rust"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["rs"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Rust"
+    }
+
+    fn extension(&self) -> &'static str {
+        "rs"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::ALL
+    }
+
+    fn emit(&self, ast: &AST, options: &CodegenOptions) -> Result<String, BackendError> {
+        transpile_rs(ast, options).map_err(|construct| BackendError::Unsupported { target: self.name(), construct })
+    }
 }
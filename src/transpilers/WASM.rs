@@ -0,0 +1,3400 @@
+use crate::errs::BackendError;
+use crate::parse::{
+    AST, ASTNode, ComparisonOperator, Expression, IntType, LogicalOperator, MathFn, MathOperator, TextOp, VarType,
+};
+use crate::transpilers::{
+    Backend, Capabilities, CodegenOptions, Overflow, Segment, ValueKind, clean_number, collect_var_types, fit,
+    formatted, int_literal, int_type_of, literal_text, placeholder, split_interpolation, value_kind,
+};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+// Fixed linear memory layout shared by the runtime helpers
+const IOV: i32 = 0; // iovec { ptr, len } handed to fd_write / fd_read
+const NIO: i32 = 8; // number of bytes written / read reported by WASI
+const ABOVE_I64: i32 = 12; // set by parse_int when the number only fits a u64
+const NUM_BUF_END: i32 = 48; // integers are formatted backwards into 16..48
+const MINUS: i32 = 60; // "-"
+const NEWLINE: i32 = 61; // "\n"
+const SPACE: i32 = 62; // " "
+const LINE_BUF: i32 = 64; // scratch line every input is read into, 256 bytes
+const LINE_BUF_SIZE: i32 = 256;
+const NAN_TEXT: i32 = 320; // "NaN", then "-inf" at 324, each NUL-terminated
+const INF_TEXT: i32 = 324;
+const FLOAT_TEXT: i32 = 336; // text of a number, at most 331 bytes and a NUL
+const FLOAT_TEXT_SIZE: u32 = 332;
+const DIGITS: i32 = 672; // decimal digits of a float, as ASCII
+const BIG: i32 = 1024; // five bignums, for float_text's r, s, m+, m- and a scratch one
+const BIG_SIZE: i32 = 288; // 72 little-endian 32-bit limbs, room for f() of 300 digits
+const BIGNUMS: [i32; 5] = [BIG, BIG + BIG_SIZE, BIG + 2 * BIG_SIZE, BIG + 3 * BIG_SIZE, BIG + 4 * BIG_SIZE];
+const HEAP_START: u32 = 3072; // variable buffers, then string literals
+const STR_SIZE: u32 = 256;
+
+/// The global holding the line of the statement that may stop the program, for
+/// its report. Ven names cannot hold a dot, so it is apart from the variables.
+const LINE_GLOBAL: &str = "ven.line";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValType {
+    I32,
+    I64,
+    F64,
+}
+
+impl ValType {
+    fn wat(self) -> &'static str {
+        match self {
+            ValType::I32 => "i32",
+            ValType::I64 => "i64",
+            ValType::F64 => "f64",
+        }
+    }
+
+    fn byte(self) -> u8 {
+        match self {
+            ValType::I32 => 0x7F,
+            ValType::I64 => 0x7E,
+            ValType::F64 => 0x7C,
+        }
+    }
+}
+
+/// A single WebAssembly instruction, rendered either as WAT text or as bytecode.
+#[derive(Debug, Clone)]
+enum Instr {
+    I32Const(i32),
+    I64Const(i64),
+    F64Const(f64),
+    LocalGet(u32),
+    LocalSet(u32),
+    LocalTee(u32),
+    GlobalGet(String),
+    GlobalSet(String),
+    Call(&'static str),
+    Block,
+    Loop,
+    If,
+    Else,
+    End,
+    Br(u32),
+    BrIf(u32),
+    /// Memory access with a zero offset, e.g. `i32.load8_u`.
+    Mem(&'static str),
+    /// Any instruction without immediates, e.g. `i64.add`.
+    Op(&'static str),
+}
+
+impl Instr {
+    fn wat(&self) -> String {
+        match self {
+            Instr::I32Const(v) => format!("i32.const {}", v),
+            Instr::I64Const(v) => format!("i64.const {}", v),
+            Instr::F64Const(v) => {
+                if v.is_nan() {
+                    "f64.const nan".to_string()
+                } else if v.is_infinite() {
+                    format!("f64.const {}inf", if *v < 0.0 { "-" } else { "" })
+                } else {
+                    format!("f64.const {:?}", v)
+                }
+            }
+            Instr::LocalGet(i) => format!("local.get {}", i),
+            Instr::LocalSet(i) => format!("local.set {}", i),
+            Instr::LocalTee(i) => format!("local.tee {}", i),
+            Instr::GlobalGet(name) => format!("global.get ${}", name),
+            Instr::GlobalSet(name) => format!("global.set ${}", name),
+            Instr::Call(name) => format!("call ${}", name),
+            Instr::Block => "block".to_string(),
+            Instr::Loop => "loop".to_string(),
+            Instr::If => "if".to_string(),
+            Instr::Else => "else".to_string(),
+            Instr::End => "end".to_string(),
+            Instr::Br(depth) => format!("br {}", depth),
+            Instr::BrIf(depth) => format!("br_if {}", depth),
+            Instr::Mem(name) | Instr::Op(name) => name.to_string(),
+        }
+    }
+}
+
+/// Bytecode of the immediate-free instructions used by the emitter.
+fn opcode(name: &str) -> u8 {
+    match name {
+        "unreachable" => 0x00,
+        "return" => 0x0F,
+        "drop" => 0x1A,
+        "select" => 0x1B,
+        "i32.load" => 0x28,
+        "i32.load8_u" => 0x2D,
+        "i64.load32_u" => 0x35,
+        "i32.store" => 0x36,
+        "i64.store" => 0x37,
+        "i32.store8" => 0x3A,
+        "i64.store32" => 0x3E,
+        "i32.eqz" => 0x45,
+        "i32.eq" => 0x46,
+        "i32.ne" => 0x47,
+        "i32.lt_s" => 0x48,
+        "i32.lt_u" => 0x49,
+        "i32.gt_s" => 0x4A,
+        "i32.gt_u" => 0x4B,
+        "i32.le_s" => 0x4C,
+        "i32.le_u" => 0x4D,
+        "i32.ge_s" => 0x4E,
+        "i32.ge_u" => 0x4F,
+        "i64.eqz" => 0x50,
+        "i64.eq" => 0x51,
+        "i64.ne" => 0x52,
+        "i64.lt_s" => 0x53,
+        "i64.lt_u" => 0x54,
+        "i64.gt_s" => 0x55,
+        "i64.gt_u" => 0x56,
+        "i64.le_s" => 0x57,
+        "i64.le_u" => 0x58,
+        "i64.ge_s" => 0x59,
+        "i64.ge_u" => 0x5A,
+        "f64.eq" => 0x61,
+        "f64.ne" => 0x62,
+        "f64.lt" => 0x63,
+        "f64.gt" => 0x64,
+        "f64.le" => 0x65,
+        "f64.ge" => 0x66,
+        "i32.clz" => 0x67,
+        "i32.add" => 0x6A,
+        "i32.sub" => 0x6B,
+        "i32.mul" => 0x6C,
+        "i32.and" => 0x71,
+        "i32.or" => 0x72,
+        "i32.shr_s" => 0x75,
+        "i64.clz" => 0x79,
+        "i64.add" => 0x7C,
+        "i64.sub" => 0x7D,
+        "i64.mul" => 0x7E,
+        "i64.div_s" => 0x7F,
+        "i64.div_u" => 0x80,
+        "i64.rem_s" => 0x81,
+        "i64.rem_u" => 0x82,
+        "i64.and" => 0x83,
+        "i64.or" => 0x84,
+        "i64.xor" => 0x85,
+        "i64.shl" => 0x86,
+        "i64.shr_u" => 0x88,
+        "f64.abs" => 0x99,
+        "f64.neg" => 0x9A,
+        "f64.ceil" => 0x9B,
+        "f64.floor" => 0x9C,
+        "f64.trunc" => 0x9D,
+        "f64.nearest" => 0x9E,
+        "f64.sqrt" => 0x9F,
+        "f64.add" => 0xA0,
+        "f64.sub" => 0xA1,
+        "f64.mul" => 0xA2,
+        "f64.div" => 0xA3,
+        "f64.copysign" => 0xA6,
+        "i32.wrap_i64" => 0xA7,
+        "i32.trunc_f64_s" => 0xAA,
+        "i64.trunc_f64_s" => 0xB0,
+        "i64.trunc_f64_u" => 0xB1,
+        "i64.extend_i32_u" => 0xAD,
+        "f64.convert_i32_s" => 0xB7,
+        "f64.convert_i32_u" => 0xB8,
+        "f64.convert_i64_s" => 0xB9,
+        "f64.convert_i64_u" => 0xBA,
+        "i64.reinterpret_f64" => 0xBD,
+        "f64.reinterpret_i64" => 0xBF,
+        _ => unreachable!("unknown wasm instruction '{}'", name),
+    }
+}
+
+struct Import {
+    field: &'static str,
+    params: Vec<ValType>,
+    result: Option<ValType>,
+}
+
+struct Func {
+    name: &'static str,
+    export: bool,
+    params: Vec<ValType>,
+    result: Option<ValType>,
+    locals: Vec<ValType>,
+    body: Vec<Instr>,
+}
+
+struct Module {
+    imports: Vec<Import>,
+    globals: Vec<(String, ValType)>,
+    funcs: Vec<Func>,
+    data: Vec<(u32, Vec<u8>)>,
+    pages: u32,
+}
+
+impl Module {
+    fn to_wat(&self) -> String {
+        let mut out = String::with_capacity(4096);
+        out.push_str("(module\n");
+        for import in &self.imports {
+            writeln!(
+                out,
+                "  (import \"wasi_snapshot_preview1\" \"{0}\" (func ${0}{1}))",
+                import.field,
+                signature_wat(&import.params, import.result)
+            )
+            .unwrap();
+        }
+        writeln!(out, "  (memory (export \"memory\") {})", self.pages).unwrap();
+        for (name, ty) in &self.globals {
+            writeln!(out, "  (global ${} (mut {1}) ({1}.const 0))", name, ty.wat()).unwrap();
+        }
+        for (offset, bytes) in &self.data {
+            writeln!(out, "  (data (i32.const {}) \"{}\")", offset, escape_wat_bytes(bytes)).unwrap();
+        }
+        for func in &self.funcs {
+            write!(out, "  (func ${}", func.name).unwrap();
+            if func.export {
+                write!(out, " (export \"{}\")", func.name).unwrap();
+            }
+            out.push_str(&signature_wat(&func.params, func.result));
+            out.push('\n');
+            if !func.locals.is_empty() {
+                let locals: Vec<&str> = func.locals.iter().map(|t| t.wat()).collect();
+                writeln!(out, "    (local {})", locals.join(" ")).unwrap();
+            }
+            let mut depth = 2;
+            for instr in &func.body {
+                if matches!(instr, Instr::Else | Instr::End) {
+                    depth -= 1;
+                }
+                writeln!(out, "{}{}", "  ".repeat(depth), instr.wat()).unwrap();
+                if matches!(instr, Instr::Block | Instr::Loop | Instr::If | Instr::Else) {
+                    depth += 1;
+                }
+            }
+            out.push_str("  )\n");
+        }
+        out.push_str(")\n");
+        out
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut types: Vec<(Vec<ValType>, Option<ValType>)> = Vec::new();
+        let mut type_index = |params: &[ValType], result: Option<ValType>| -> u32 {
+            match types.iter().position(|(p, r)| p == params && *r == result) {
+                Some(i) => i as u32,
+                None => {
+                    types.push((params.to_vec(), result));
+                    (types.len() - 1) as u32
+                }
+            }
+        };
+        let import_types: Vec<u32> = self.imports.iter().map(|i| type_index(&i.params, i.result)).collect();
+        let func_types: Vec<u32> = self.funcs.iter().map(|f| type_index(&f.params, f.result)).collect();
+
+        let mut func_index = HashMap::new();
+        for (i, import) in self.imports.iter().enumerate() {
+            func_index.insert(import.field, i as u32);
+        }
+        for (i, func) in self.funcs.iter().enumerate() {
+            func_index.insert(func.name, (self.imports.len() + i) as u32);
+        }
+        let global_index: HashMap<&str, u32> = self
+            .globals
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| (name.as_str(), i as u32))
+            .collect();
+
+        let mut out = vec![0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00];
+
+        // Type section
+        let mut section = Vec::new();
+        leb_u32(&mut section, types.len() as u32);
+        for (params, result) in &types {
+            section.push(0x60);
+            leb_u32(&mut section, params.len() as u32);
+            section.extend(params.iter().map(|t| t.byte()));
+            match result {
+                Some(t) => section.extend([0x01, t.byte()]),
+                None => section.push(0x00),
+            }
+        }
+        push_section(&mut out, 1, &section);
+
+        // Import section
+        let mut section = Vec::new();
+        leb_u32(&mut section, self.imports.len() as u32);
+        for (import, ty) in self.imports.iter().zip(&import_types) {
+            push_name(&mut section, "wasi_snapshot_preview1");
+            push_name(&mut section, import.field);
+            section.push(0x00);
+            leb_u32(&mut section, *ty);
+        }
+        push_section(&mut out, 2, &section);
+
+        // Function section
+        let mut section = Vec::new();
+        leb_u32(&mut section, func_types.len() as u32);
+        for ty in &func_types {
+            leb_u32(&mut section, *ty);
+        }
+        push_section(&mut out, 3, &section);
+
+        // Memory section
+        let mut section = vec![0x01, 0x00];
+        leb_u32(&mut section, self.pages);
+        push_section(&mut out, 5, &section);
+
+        // Global section
+        let mut section = Vec::new();
+        leb_u32(&mut section, self.globals.len() as u32);
+        for (_, ty) in &self.globals {
+            section.extend([ty.byte(), 0x01]);
+            match ty {
+                ValType::I32 => section.extend([0x41, 0x00]),
+                ValType::I64 => section.extend([0x42, 0x00]),
+                ValType::F64 => {
+                    section.push(0x44);
+                    section.extend(0f64.to_le_bytes());
+                }
+            }
+            section.push(0x0B);
+        }
+        push_section(&mut out, 6, &section);
+
+        // Export section
+        let exported: Vec<&Func> = self.funcs.iter().filter(|f| f.export).collect();
+        let mut section = Vec::new();
+        leb_u32(&mut section, exported.len() as u32 + 1);
+        push_name(&mut section, "memory");
+        section.extend([0x02, 0x00]);
+        for func in exported {
+            push_name(&mut section, func.name);
+            section.push(0x00);
+            leb_u32(&mut section, func_index[func.name]);
+        }
+        push_section(&mut out, 7, &section);
+
+        // Code section
+        let mut section = Vec::new();
+        leb_u32(&mut section, self.funcs.len() as u32);
+        for func in &self.funcs {
+            let mut body = Vec::new();
+            let mut groups: Vec<(u32, ValType)> = Vec::new();
+            for ty in &func.locals {
+                match groups.last_mut() {
+                    Some((count, last)) if last == ty => *count += 1,
+                    _ => groups.push((1, *ty)),
+                }
+            }
+            leb_u32(&mut body, groups.len() as u32);
+            for (count, ty) in groups {
+                leb_u32(&mut body, count);
+                body.push(ty.byte());
+            }
+            for instr in &func.body {
+                encode_instr(&mut body, instr, &func_index, &global_index);
+            }
+            body.push(0x0B);
+            leb_u32(&mut section, body.len() as u32);
+            section.extend(body);
+        }
+        push_section(&mut out, 10, &section);
+
+        // Data section
+        let mut section = Vec::new();
+        leb_u32(&mut section, self.data.len() as u32);
+        for (offset, bytes) in &self.data {
+            section.extend([0x00, 0x41]);
+            leb_i64(&mut section, *offset as i64);
+            section.push(0x0B);
+            leb_u32(&mut section, bytes.len() as u32);
+            section.extend(bytes);
+        }
+        push_section(&mut out, 11, &section);
+
+        out
+    }
+}
+
+fn encode_instr(
+    out: &mut Vec<u8>,
+    instr: &Instr,
+    funcs: &HashMap<&str, u32>,
+    globals: &HashMap<&str, u32>,
+) {
+    match instr {
+        Instr::I32Const(v) => {
+            out.push(0x41);
+            leb_i64(out, *v as i64);
+        }
+        Instr::I64Const(v) => {
+            out.push(0x42);
+            leb_i64(out, *v);
+        }
+        Instr::F64Const(v) => {
+            out.push(0x44);
+            out.extend(v.to_le_bytes());
+        }
+        Instr::LocalGet(i) => {
+            out.push(0x20);
+            leb_u32(out, *i);
+        }
+        Instr::LocalSet(i) => {
+            out.push(0x21);
+            leb_u32(out, *i);
+        }
+        Instr::LocalTee(i) => {
+            out.push(0x22);
+            leb_u32(out, *i);
+        }
+        Instr::GlobalGet(name) => {
+            out.push(0x23);
+            leb_u32(out, globals[name.as_str()]);
+        }
+        Instr::GlobalSet(name) => {
+            out.push(0x24);
+            leb_u32(out, globals[name.as_str()]);
+        }
+        Instr::Call(name) => {
+            out.push(0x10);
+            leb_u32(out, funcs[name]);
+        }
+        Instr::Block => out.extend([0x02, 0x40]),
+        Instr::Loop => out.extend([0x03, 0x40]),
+        Instr::If => out.extend([0x04, 0x40]),
+        Instr::Else => out.push(0x05),
+        Instr::End => out.push(0x0B),
+        Instr::Br(depth) => {
+            out.push(0x0C);
+            leb_u32(out, *depth);
+        }
+        Instr::BrIf(depth) => {
+            out.push(0x0D);
+            leb_u32(out, *depth);
+        }
+        Instr::Mem(name) => {
+            // memarg: natural alignment, zero offset
+            let align = if name.contains('8') { 0 } else { 2 };
+            out.extend([opcode(name), align, 0x00]);
+        }
+        Instr::Op(name) => out.push(opcode(name)),
+    }
+}
+
+fn signature_wat(params: &[ValType], result: Option<ValType>) -> String {
+    let mut sig = String::new();
+    if !params.is_empty() {
+        let params: Vec<&str> = params.iter().map(|t| t.wat()).collect();
+        write!(sig, " (param {})", params.join(" ")).unwrap();
+    }
+    if let Some(result) = result {
+        write!(sig, " (result {})", result.wat()).unwrap();
+    }
+    sig
+}
+
+fn escape_wat_bytes(bytes: &[u8]) -> String {
+    let mut escaped = String::with_capacity(bytes.len());
+    for byte in bytes {
+        match byte {
+            0x20..=0x7E if *byte != b'"' && *byte != b'\\' => escaped.push(*byte as char),
+            _ => write!(escaped, "\\{:02x}", byte).unwrap(),
+        }
+    }
+    escaped
+}
+
+fn leb_u32(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn leb_i64(out: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if done {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn push_name(out: &mut Vec<u8>, name: &str) {
+    leb_u32(out, name.len() as u32);
+    out.extend(name.as_bytes());
+}
+
+fn push_section(out: &mut Vec<u8>, id: u8, section: &[u8]) {
+    out.push(id);
+    leb_u32(out, section.len() as u32);
+    out.extend(section);
+}
+
+/// Runtime helpers shared by every generated module.
+#[rustfmt::skip]
+fn runtime_funcs() -> Vec<Func> {
+    use Instr::*;
+    use ValType::*;
+
+    vec![
+        // write(fd, ptr, len)
+        Func {
+            name: "write",
+            export: false,
+            params: vec![I32, I32, I32],
+            result: None,
+            locals: vec![],
+            body: vec![
+                I32Const(IOV), LocalGet(1), Mem("i32.store"),
+                I32Const(IOV + 4), LocalGet(2), Mem("i32.store"),
+                LocalGet(0), I32Const(IOV), I32Const(1), I32Const(NIO), Call("fd_write"), Op("drop"),
+            ],
+        },
+        // strlen(ptr) -> len
+        Func {
+            name: "strlen",
+            export: false,
+            params: vec![I32],
+            result: Some(I32),
+            locals: vec![I32],
+            body: vec![
+                Block, Loop,
+                LocalGet(0), LocalGet(1), Op("i32.add"), Mem("i32.load8_u"), Op("i32.eqz"), BrIf(1),
+                LocalGet(1), I32Const(1), Op("i32.add"), LocalSet(1), Br(0),
+                End, End,
+                LocalGet(1),
+            ],
+        },
+        // print_cstr(fd, ptr)
+        Func {
+            name: "print_cstr",
+            export: false,
+            params: vec![I32, I32],
+            result: None,
+            locals: vec![],
+            body: vec![LocalGet(0), LocalGet(1), LocalGet(1), Call("strlen"), Call("write")],
+        },
+        // print_digits(fd, value, min_width): unsigned decimal, zero padded to min_width
+        Func {
+            name: "print_digits",
+            export: false,
+            params: vec![I32, I64, I32],
+            result: None,
+            locals: vec![I32],
+            body: vec![
+                I32Const(NUM_BUF_END), LocalSet(3),
+                Loop,
+                LocalGet(3), I32Const(1), Op("i32.sub"), LocalSet(3),
+                LocalGet(3), LocalGet(1), I64Const(10), Op("i64.rem_u"), Op("i32.wrap_i64"),
+                I32Const(48), Op("i32.add"), Mem("i32.store8"),
+                LocalGet(1), I64Const(10), Op("i64.div_u"), LocalSet(1),
+                LocalGet(2), I32Const(1), Op("i32.sub"), LocalSet(2),
+                LocalGet(1), Op("i64.eqz"), Op("i32.eqz"),
+                LocalGet(2), I32Const(0), Op("i32.gt_s"), Op("i32.or"), BrIf(0),
+                End,
+                LocalGet(0), LocalGet(3), I32Const(NUM_BUF_END), LocalGet(3), Op("i32.sub"), Call("write"),
+            ],
+        },
+        // print_i64(fd, value)
+        Func {
+            name: "print_i64",
+            export: false,
+            params: vec![I32, I64],
+            result: None,
+            locals: vec![],
+            body: vec![
+                LocalGet(1), I64Const(0), Op("i64.lt_s"), If,
+                LocalGet(0), I32Const(MINUS), I32Const(1), Call("write"),
+                I64Const(0), LocalGet(1), Op("i64.sub"), LocalSet(1),
+                End,
+                LocalGet(0), LocalGet(1), I32Const(1), Call("print_digits"),
+            ],
+        },
+        // read_line(dst, max) -> len: reads one line from stdin, NUL-terminated, newline dropped;
+        // bytes past max are read into the NUL slot and dropped
+        Func {
+            name: "read_line",
+            export: false,
+            params: vec![I32, I32],
+            result: Some(I32),
+            locals: vec![I32, I32],
+            body: vec![
+                Block, Loop,
+                LocalGet(0), LocalGet(2), LocalGet(1), LocalGet(2), LocalGet(1), Op("i32.lt_s"), Op("select"),
+                Op("i32.add"), LocalSet(3),
+                I32Const(IOV), LocalGet(3), Mem("i32.store"),
+                I32Const(IOV + 4), I32Const(1), Mem("i32.store"),
+                I32Const(0), I32Const(IOV), I32Const(1), I32Const(NIO), Call("fd_read"), BrIf(1),
+                I32Const(NIO), Mem("i32.load"), Op("i32.eqz"), BrIf(1),
+                LocalGet(3), Mem("i32.load8_u"), I32Const(10), Op("i32.eq"), BrIf(1),
+                LocalGet(2), LocalGet(1), Op("i32.lt_s"), If,
+                LocalGet(2), I32Const(1), Op("i32.add"), LocalSet(2),
+                End,
+                Br(0),
+                End, End,
+                LocalGet(2), I32Const(0), Op("i32.gt_s"), If,
+                LocalGet(0), LocalGet(2), Op("i32.add"), I32Const(1), Op("i32.sub"), Mem("i32.load8_u"),
+                I32Const(13), Op("i32.eq"), If,
+                LocalGet(2), I32Const(1), Op("i32.sub"), LocalSet(2),
+                End,
+                End,
+                LocalGet(0), LocalGet(2), Op("i32.add"), I32Const(0), Mem("i32.store8"),
+                LocalGet(2),
+            ],
+        },
+        // parse_int(ptr) -> value: 0 outside the i64 minimum to the u64 maximum; sets
+        // ABOVE_I64 when the value is only right read as a u64
+        Func {
+            name: "parse_int",
+            export: false,
+            params: vec![I32],
+            result: Some(I64),
+            locals: vec![I64, I32, I32],
+            body: vec![
+                I32Const(ABOVE_I64), I32Const(0), Mem("i32.store"),
+                Block, Loop,
+                LocalGet(0), Mem("i32.load8_u"), I32Const(32), Op("i32.ne"), BrIf(1),
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0), Br(0),
+                End, End,
+                LocalGet(0), Mem("i32.load8_u"), I32Const(45), Op("i32.eq"), If,
+                I32Const(1), LocalSet(2),
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0),
+                End,
+                Block, Loop,
+                LocalGet(0), Mem("i32.load8_u"), I32Const(48), Op("i32.sub"), LocalTee(3),
+                I32Const(9), Op("i32.gt_u"), BrIf(1),
+                // Past the u64 maximum
+                LocalGet(1), I64Const(u64::MAX as i64), LocalGet(3), Op("i64.extend_i32_u"), Op("i64.sub"),
+                I64Const(10), Op("i64.div_u"), Op("i64.gt_u"), If,
+                I64Const(0), Op("return"),
+                End,
+                LocalGet(1), I64Const(10), Op("i64.mul"), LocalGet(3), Op("i64.extend_i32_u"), Op("i64.add"), LocalSet(1),
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0), Br(0),
+                End, End,
+                LocalGet(2), If,
+                // Past the i64 minimum
+                LocalGet(1), I64Const(i64::MIN), Op("i64.gt_u"), If,
+                I64Const(0), Op("return"),
+                End,
+                I64Const(0), LocalGet(1), Op("i64.sub"), Op("return"),
+                End,
+                I32Const(ABOVE_I64), LocalGet(1), I64Const(0), Op("i64.lt_s"), Mem("i32.store"),
+                LocalGet(1),
+            ],
+        },
+        // parse_f64(ptr) -> value
+        Func {
+            name: "parse_f64",
+            export: false,
+            params: vec![I32],
+            result: Some(F64),
+            locals: vec![F64, I32, I32, F64],
+            body: vec![
+                F64Const(1.0), LocalSet(4),
+                Block, Loop,
+                LocalGet(0), Mem("i32.load8_u"), I32Const(32), Op("i32.ne"), BrIf(1),
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0), Br(0),
+                End, End,
+                LocalGet(0), Mem("i32.load8_u"), I32Const(45), Op("i32.eq"), If,
+                I32Const(1), LocalSet(2),
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0),
+                End,
+                Block, Loop,
+                LocalGet(0), Mem("i32.load8_u"), I32Const(48), Op("i32.sub"), LocalTee(3),
+                I32Const(9), Op("i32.gt_u"), BrIf(1),
+                LocalGet(1), F64Const(10.0), Op("f64.mul"), LocalGet(3), Op("f64.convert_i32_u"), Op("f64.add"), LocalSet(1),
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0), Br(0),
+                End, End,
+                LocalGet(0), Mem("i32.load8_u"), I32Const(46), Op("i32.eq"), If,
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0),
+                Block, Loop,
+                LocalGet(0), Mem("i32.load8_u"), I32Const(48), Op("i32.sub"), LocalTee(3),
+                I32Const(9), Op("i32.gt_u"), BrIf(1),
+                LocalGet(1), F64Const(10.0), Op("f64.mul"), LocalGet(3), Op("f64.convert_i32_u"), Op("f64.add"), LocalSet(1),
+                LocalGet(4), F64Const(10.0), Op("f64.mul"), LocalSet(4),
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0), Br(0),
+                End, End,
+                End,
+                LocalGet(1), LocalGet(4), Op("f64.div"), LocalSet(1),
+                LocalGet(1), Op("f64.neg"), LocalGet(1), LocalGet(2), Op("select"),
+            ],
+        },
+        // str_cmp(a, b) -> <0, 0 or >0
+        Func {
+            name: "str_cmp",
+            export: false,
+            params: vec![I32, I32],
+            result: Some(I32),
+            locals: vec![I32, I32],
+            body: vec![
+                Block, Loop,
+                LocalGet(0), Mem("i32.load8_u"), LocalSet(2),
+                LocalGet(1), Mem("i32.load8_u"), LocalSet(3),
+                LocalGet(2), LocalGet(3), Op("i32.ne"), BrIf(1),
+                LocalGet(2), Op("i32.eqz"), BrIf(1),
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0),
+                LocalGet(1), I32Const(1), Op("i32.add"), LocalSet(1),
+                Br(0),
+                End, End,
+                LocalGet(2), LocalGet(3), Op("i32.sub"),
+            ],
+        },
+        // str_copy(dst, src, size): copies at most size - 1 bytes and NUL-terminates;
+        // a cut backs off continuation bytes so no UTF-8 character is split
+        Func {
+            name: "str_copy",
+            export: false,
+            params: vec![I32, I32, I32],
+            result: None,
+            locals: vec![I32, I32],
+            body: vec![
+                LocalGet(0), LocalSet(4),
+                Block, Loop,
+                LocalGet(1), Mem("i32.load8_u"), LocalTee(3), Op("i32.eqz"), BrIf(1),
+                LocalGet(2), I32Const(1), Op("i32.le_s"), If,
+                Block, Loop,
+                LocalGet(0), LocalGet(4), Op("i32.le_u"), BrIf(1),
+                LocalGet(1), Mem("i32.load8_u"), I32Const(0xC0), Op("i32.and"), I32Const(0x80), Op("i32.ne"), BrIf(1),
+                LocalGet(0), I32Const(1), Op("i32.sub"), LocalSet(0),
+                LocalGet(1), I32Const(1), Op("i32.sub"), LocalSet(1),
+                Br(0),
+                End, End,
+                Br(2),
+                End,
+                LocalGet(0), LocalGet(3), Mem("i32.store8"),
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0),
+                LocalGet(1), I32Const(1), Op("i32.add"), LocalSet(1),
+                LocalGet(2), I32Const(1), Op("i32.sub"), LocalSet(2),
+                Br(0),
+                End, End,
+                LocalGet(0), I32Const(0), Mem("i32.store8"),
+            ],
+        },
+    ]
+}
+
+/// `blank(byte)`, whether a byte is a space, tab or line break, for the
+/// conversion and string helpers.
+#[rustfmt::skip]
+fn blank_func() -> Func {
+    use Instr::*;
+    use ValType::*;
+
+    Func {
+        name: "blank",
+        export: false,
+        params: vec![I32],
+        result: Some(I32),
+        locals: vec![],
+        body: vec![
+            LocalGet(0), I32Const(b' ' as i32), Op("i32.eq"), LocalGet(0), I32Const(b'\t' as i32), Op("i32.eq"), Op("i32.or"),
+            LocalGet(0), I32Const(b'\n' as i32), Op("i32.eq"), Op("i32.or"),
+            LocalGet(0), I32Const(b'\r' as i32), Op("i32.eq"), Op("i32.or"),
+        ],
+    }
+}
+
+/// The string operations, for programs that use them; they need [`blank_func`]
+/// too. Each result is built in a buffer of `STR_SIZE` bytes by `str_put`,
+/// which cuts the text at the last whole character that fits.
+#[rustfmt::skip]
+fn string_funcs() -> Vec<Func> {
+    use Instr::*;
+    use ValType::*;
+
+    // Whether the byte the instructions leave starts a character, rather than continuing one
+    let starts = |byte: Vec<Instr>| {
+        let mut instrs = byte;
+        instrs.extend([I32Const(0xC0), Op("i32.and"), I32Const(0x80), Op("i32.ne")]);
+        instrs
+    };
+    let mut chars_body = vec![
+        Block, Loop,
+        LocalGet(0), Mem("i32.load8_u"), LocalTee(2), Op("i32.eqz"), BrIf(1),
+        LocalGet(1),
+    ];
+    chars_body.extend(starts(vec![LocalGet(2)]));
+    chars_body.extend([
+        Op("i64.extend_i32_u"), Op("i64.add"), LocalSet(1),
+        LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0), Br(0),
+        End, End,
+        LocalGet(1),
+    ]);
+    let mut offset_body = vec![
+        LocalGet(1), I64Const(0), Op("i64.lt_s"), If, I64Const(0), LocalSet(1), End,
+        Block, Loop,
+        LocalGet(0), LocalGet(2), Op("i32.add"), Mem("i32.load8_u"), LocalTee(4), Op("i32.eqz"), BrIf(1),
+    ];
+    offset_body.extend(starts(vec![LocalGet(4)]));
+    offset_body.extend([
+        If,
+        LocalGet(3), LocalGet(1), Op("i64.eq"), BrIf(2),
+        LocalGet(3), I64Const(1), Op("i64.add"), LocalSet(3),
+        End,
+        LocalGet(2), I32Const(1), Op("i32.add"), LocalSet(2), Br(0),
+        End, End,
+        LocalGet(2),
+    ]);
+    let mut put_body = vec![
+        LocalGet(1), LocalGet(3), Op("i32.add"), I32Const(STR_SIZE as i32), Op("i32.ge_u"), If,
+        I32Const(1), LocalSet(5),
+        I32Const(STR_SIZE as i32 - 1), LocalGet(1), Op("i32.sub"), LocalSet(3),
+        Block, Loop,
+        LocalGet(3), Op("i32.eqz"), BrIf(1),
+    ];
+    put_body.extend(starts(vec![LocalGet(2), LocalGet(3), Op("i32.add"), Mem("i32.load8_u")]));
+    put_body.extend([
+        BrIf(1),
+        LocalGet(3), I32Const(1), Op("i32.sub"), LocalSet(3), Br(0),
+        End, End,
+        End,
+        Block, Loop,
+        LocalGet(4), LocalGet(3), Op("i32.ge_u"), BrIf(1),
+        LocalGet(0), LocalGet(1), Op("i32.add"), LocalGet(4), Op("i32.add"),
+        LocalGet(2), LocalGet(4), Op("i32.add"), Mem("i32.load8_u"), Mem("i32.store8"),
+        LocalGet(4), I32Const(1), Op("i32.add"), LocalSet(4), Br(0),
+        End, End,
+        LocalGet(0), LocalGet(1), Op("i32.add"), LocalGet(3), Op("i32.add"), I32Const(0), Mem("i32.store8"),
+        I32Const(STR_SIZE as i32 - 1), LocalGet(1), LocalGet(3), Op("i32.add"), LocalGet(5), Op("select"),
+    ]);
+
+    vec![
+        // str_put(buf, len, src, n) -> len: appends n bytes of src to the len bytes
+        // in buf. A cut fills the buffer, so nothing more is added after it.
+        Func {
+            name: "str_put",
+            export: false,
+            params: vec![I32, I32, I32, I32],
+            result: Some(I32),
+            locals: vec![I32, I32],
+            body: put_body,
+        },
+        // str_cat(len, src, buf) -> len: appends all of src, for concat()
+        Func {
+            name: "str_cat",
+            export: false,
+            params: vec![I32, I32, I32],
+            result: Some(I32),
+            locals: vec![],
+            body: vec![LocalGet(2), LocalGet(0), LocalGet(1), LocalGet(1), Call("strlen"), Call("str_put")],
+        },
+        // str_chars(text) -> the number of characters
+        Func {
+            name: "str_chars",
+            export: false,
+            params: vec![I32],
+            result: Some(I64),
+            locals: vec![I64, I32],
+            body: chars_body,
+        },
+        // str_offset(text, index) -> byte offset of character index, or the length
+        // when there are fewer; a negative index is 0
+        Func {
+            name: "str_offset",
+            export: false,
+            params: vec![I32, I64],
+            result: Some(I32),
+            locals: vec![I32, I64, I32],
+            body: offset_body,
+        },
+        // str_slice(buf, text, start, end) -> buf
+        Func {
+            name: "str_slice",
+            export: false,
+            params: vec![I32, I32, I64, I64],
+            result: Some(I32),
+            locals: vec![I32, I32],
+            body: vec![
+                LocalGet(1), LocalGet(2), Call("str_offset"), LocalSet(4),
+                LocalGet(1), LocalGet(3), Call("str_offset"), LocalSet(5),
+                LocalGet(0), I32Const(0), Mem("i32.store8"),
+                LocalGet(5), LocalGet(4), Op("i32.gt_u"), If,
+                LocalGet(0), I32Const(0), LocalGet(1), LocalGet(4), Op("i32.add"), LocalGet(5), LocalGet(4), Op("i32.sub"),
+                Call("str_put"), Op("drop"),
+                End,
+                LocalGet(0),
+            ],
+        },
+        // str_case(buf, text, first, delta) -> buf: adds the delta to the 26 ASCII
+        // letters from the first
+        Func {
+            name: "str_case",
+            export: false,
+            params: vec![I32, I32, I32, I32],
+            result: Some(I32),
+            locals: vec![I32, I32],
+            body: vec![
+                LocalGet(0), I32Const(0), LocalGet(1), LocalGet(1), Call("strlen"), Call("str_put"), Op("drop"),
+                LocalGet(0), LocalSet(4),
+                Block, Loop,
+                LocalGet(4), Mem("i32.load8_u"), LocalTee(5), Op("i32.eqz"), BrIf(1),
+                LocalGet(5), LocalGet(2), Op("i32.sub"), I32Const(26), Op("i32.lt_u"), If,
+                LocalGet(4), LocalGet(5), LocalGet(3), Op("i32.add"), Mem("i32.store8"),
+                End,
+                LocalGet(4), I32Const(1), Op("i32.add"), LocalSet(4), Br(0),
+                End, End,
+                LocalGet(0),
+            ],
+        },
+        // str_trim(buf, text) -> buf: without spaces, tabs and line breaks at either end
+        Func {
+            name: "str_trim",
+            export: false,
+            params: vec![I32, I32],
+            result: Some(I32),
+            locals: vec![I32],
+            body: vec![
+                Block, Loop,
+                LocalGet(1), Mem("i32.load8_u"), Call("blank"), Op("i32.eqz"), BrIf(1),
+                LocalGet(1), I32Const(1), Op("i32.add"), LocalSet(1), Br(0),
+                End, End,
+                LocalGet(1), LocalGet(1), Call("strlen"), Op("i32.add"), LocalSet(2),
+                Block, Loop,
+                LocalGet(2), LocalGet(1), Op("i32.le_u"), BrIf(1),
+                LocalGet(2), I32Const(1), Op("i32.sub"), Mem("i32.load8_u"), Call("blank"), Op("i32.eqz"), BrIf(1),
+                LocalGet(2), I32Const(1), Op("i32.sub"), LocalSet(2), Br(0),
+                End, End,
+                LocalGet(0), I32Const(0), LocalGet(1), LocalGet(2), LocalGet(1), Op("i32.sub"), Call("str_put"), Op("drop"),
+                LocalGet(0),
+            ],
+        },
+        // str_find(text, part) -> address of the first part in text, or 0
+        Func {
+            name: "str_find",
+            export: false,
+            params: vec![I32, I32],
+            result: Some(I32),
+            locals: vec![I32, I32],
+            body: vec![
+                Block, Loop,
+                LocalGet(0), LocalSet(2), LocalGet(1), LocalSet(3),
+                Block, Loop,
+                LocalGet(3), Mem("i32.load8_u"), Op("i32.eqz"), BrIf(3),
+                LocalGet(2), Mem("i32.load8_u"), LocalGet(3), Mem("i32.load8_u"), Op("i32.ne"), BrIf(1),
+                LocalGet(2), I32Const(1), Op("i32.add"), LocalSet(2),
+                LocalGet(3), I32Const(1), Op("i32.add"), LocalSet(3), Br(0),
+                End, End,
+                LocalGet(0), Mem("i32.load8_u"), Op("i32.eqz"), If,
+                I32Const(0), LocalSet(0), Br(2),
+                End,
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0), Br(0),
+                End, End,
+                LocalGet(0),
+            ],
+        },
+        // str_prefix(text, prefix) -> whether text starts with prefix
+        Func {
+            name: "str_prefix",
+            export: false,
+            params: vec![I32, I32],
+            result: Some(I32),
+            locals: vec![],
+            body: vec![
+                Block, Loop,
+                LocalGet(1), Mem("i32.load8_u"), Op("i32.eqz"), BrIf(1),
+                LocalGet(0), Mem("i32.load8_u"), LocalGet(1), Mem("i32.load8_u"), Op("i32.ne"), BrIf(1),
+                LocalGet(0), I32Const(1), Op("i32.add"), LocalSet(0),
+                LocalGet(1), I32Const(1), Op("i32.add"), LocalSet(1), Br(0),
+                End, End,
+                LocalGet(1), Mem("i32.load8_u"), Op("i32.eqz"),
+            ],
+        },
+        // str_replace(buf, text, from, to) -> buf: every from, left to right; an
+        // empty from leaves the text as it is
+        Func {
+            name: "str_replace",
+            export: false,
+            params: vec![I32, I32, I32, I32],
+            result: Some(I32),
+            locals: vec![I32, I32, I32],
+            body: vec![
+                LocalGet(2), Call("strlen"), LocalSet(6),
+                LocalGet(6), If,
+                Block, Loop,
+                LocalGet(1), LocalGet(2), Call("str_find"), LocalTee(5), Op("i32.eqz"), BrIf(1),
+                LocalGet(0), LocalGet(4), LocalGet(1), LocalGet(5), LocalGet(1), Op("i32.sub"), Call("str_put"), LocalSet(4),
+                LocalGet(0), LocalGet(4), LocalGet(3), LocalGet(3), Call("strlen"), Call("str_put"), LocalSet(4),
+                LocalGet(5), LocalGet(6), Op("i32.add"), LocalSet(1), Br(0),
+                End, End,
+                End,
+                LocalGet(0), LocalGet(4), LocalGet(1), LocalGet(1), Call("strlen"), Call("str_put"), Op("drop"),
+                LocalGet(0),
+            ],
+        },
+    ]
+}
+
+/// Stores the byte the instructions leave on the stack at the address in local
+/// `out`, and moves `out` past it.
+fn put_byte(out: u32, byte: Vec<Instr>) -> Vec<Instr> {
+    let mut instrs = vec![Instr::LocalGet(out)];
+    instrs.extend(byte);
+    instrs.extend([
+        Instr::Mem("i32.store8"),
+        Instr::LocalGet(out),
+        Instr::I32Const(1),
+        Instr::Op("i32.add"),
+        Instr::LocalSet(out),
+    ]);
+    instrs
+}
+
+/// `print_f64` and the bignum arithmetic behind it, for programs that print
+/// floats. A float prints as the shortest decimal that reads back as the same
+/// value, found with Burger and Dybvig's free-format algorithm, and is laid out
+/// without an exponent, as the C target's `ven_float_text` does.
+#[rustfmt::skip]
+fn float_funcs() -> Vec<Func> {
+    use Instr::*;
+    use ValType::*;
+
+    let [r, s, m_plus, m_minus, scratch] = BIGNUMS;
+    let put = |byte: Vec<Instr>| put_byte(1, byte);
+    let times_ten = |big: i32| vec![I32Const(big), I64Const(10), Call("big_mul")];
+
+    let mut text = vec![
+        LocalGet(0), LocalGet(0), Op("f64.ne"), If, I32Const(NAN_TEXT), Op("return"), End,
+        // Only an infinity is not finite after x - x
+        LocalGet(0), LocalGet(0), Op("f64.sub"), F64Const(0.0), Op("f64.ne"), If,
+        I32Const(INF_TEXT + 1), I32Const(INF_TEXT), LocalGet(0), F64Const(0.0), Op("f64.gt"), Op("select"), Op("return"),
+        End,
+        I32Const(FLOAT_TEXT), LocalSet(1),
+        LocalGet(0), Op("i64.reinterpret_f64"), LocalTee(2), I64Const(0), Op("i64.lt_s"), If,
+    ];
+    text.extend(put(vec![I32Const(b'-' as i32)]));
+    text.extend([End, LocalGet(0), F64Const(0.0), Op("f64.eq"), If]);
+    text.extend(put(vec![I32Const(b'0' as i32)]));
+    text.extend([
+        LocalGet(1), I32Const(0), Mem("i32.store8"), I32Const(FLOAT_TEXT), Op("return"),
+        End,
+        // x = f * 2^e, with the hidden bit in f unless x is subnormal
+        LocalGet(2), I64Const((1 << 52) - 1), Op("i64.and"), LocalSet(3),
+        LocalGet(2), I64Const(52), Op("i64.shr_u"), Op("i32.wrap_i64"), I32Const(0x7FF), Op("i32.and"), LocalTee(4), If,
+        LocalGet(3), I64Const(1 << 52), Op("i64.or"), LocalSet(3),
+        LocalGet(4), I32Const(1075), Op("i32.sub"), LocalSet(4),
+        Else,
+        I32Const(-1074), LocalSet(4),
+        End,
+        // An even f reads back from the bounds of its gap too
+        LocalGet(3), Op("i32.wrap_i64"), I32Const(1), Op("i32.and"), Op("i32.eqz"), LocalSet(6),
+        // At a power of two the gap below is half the gap above
+        LocalGet(3), I64Const(1 << 52), Op("i64.eq"), LocalGet(4), I32Const(-1074), Op("i32.ne"), Op("i32.and"), LocalSet(11),
+        // x = r / s, with the gaps to its neighbours 2 * m+ / s above and 2 * m- / s below
+        I32Const(r), LocalGet(3), I64Const(1), LocalGet(11), Op("i64.extend_i32_u"), Op("i64.add"), Op("i64.shl"), Call("big_set"),
+        I32Const(s), I64Const(2), LocalGet(11), Op("i64.extend_i32_u"), Op("i64.shl"), Call("big_set"),
+        I32Const(m_plus), I64Const(1), LocalGet(11), Op("i64.extend_i32_u"), Op("i64.shl"), Call("big_set"),
+        I32Const(m_minus), I64Const(1), Call("big_set"),
+        LocalGet(4), I32Const(0), Op("i32.ge_s"), If,
+        I32Const(r), LocalGet(4), Call("big_shl"),
+        I32Const(m_plus), LocalGet(4), Call("big_shl"),
+        I32Const(m_minus), LocalGet(4), Call("big_shl"),
+        Else,
+        I32Const(s), I32Const(0), LocalGet(4), Op("i32.sub"), Call("big_shl"),
+        End,
+        // k estimates the decimal exponent from the bit length, at most one too small
+        LocalGet(4), I32Const(63), LocalGet(3), Op("i64.clz"), Op("i32.wrap_i64"), Op("i32.sub"), Op("i32.add"),
+        Op("f64.convert_i32_s"), F64Const(std::f64::consts::LOG10_2), Op("f64.mul"), F64Const(1e-10), Op("f64.sub"),
+        Op("f64.ceil"), Op("i32.trunc_f64_s"), LocalSet(5),
+        LocalGet(5), I32Const(0), Op("i32.ge_s"), If,
+        I32Const(s), LocalGet(5), Call("big_pow10"),
+        Else,
+        I32Const(r), I32Const(0), LocalGet(5), Op("i32.sub"), Call("big_pow10"),
+        I32Const(m_plus), I32Const(0), LocalGet(5), Op("i32.sub"), Call("big_pow10"),
+        I32Const(m_minus), I32Const(0), LocalGet(5), Op("i32.sub"), Call("big_pow10"),
+        End,
+        I32Const(scratch), I32Const(r), I32Const(m_plus), Call("big_add"),
+        I32Const(scratch), I32Const(s), Call("big_cmp"), LocalGet(6), Op("i32.add"), I32Const(0), Op("i32.gt_s"), If,
+        LocalGet(5), I32Const(1), Op("i32.add"), LocalSet(5),
+        Else,
+    ]);
+    text.extend([r, m_plus, m_minus].into_iter().flat_map(times_ten));
+    text.extend([
+        End,
+        // Digits until the rest of x is within a gap of a neighbour
+        I32Const(0), LocalSet(7),
+        Block, Loop,
+        I32Const(0), LocalSet(8),
+        Block, Loop,
+        I32Const(r), I32Const(s), Call("big_cmp"), I32Const(0), Op("i32.lt_s"), BrIf(1),
+        I32Const(r), I32Const(s), Call("big_sub"),
+        LocalGet(8), I32Const(1), Op("i32.add"), LocalSet(8),
+        Br(0),
+        End, End,
+        I32Const(r), I32Const(m_minus), Call("big_cmp"), LocalGet(6), Op("i32.sub"), I32Const(0), Op("i32.lt_s"), LocalSet(9),
+        I32Const(scratch), I32Const(r), I32Const(m_plus), Call("big_add"),
+        I32Const(scratch), I32Const(s), Call("big_cmp"), LocalGet(6), Op("i32.add"), I32Const(0), Op("i32.gt_s"), LocalSet(10),
+        LocalGet(9), LocalGet(10), Op("i32.or"), BrIf(1),
+        I32Const(DIGITS), LocalGet(7), Op("i32.add"), LocalGet(8), I32Const(b'0' as i32), Op("i32.add"), Mem("i32.store8"),
+        LocalGet(7), I32Const(1), Op("i32.add"), LocalSet(7),
+    ]);
+    text.extend([r, m_plus, m_minus].into_iter().flat_map(times_ten));
+    text.extend([
+        Br(0),
+        End, End,
+        // Either last digit reads back: the nearer one, or the even one on a tie
+        LocalGet(9), LocalGet(10), Op("i32.and"), If,
+        I32Const(scratch), I32Const(r), I32Const(r), Call("big_add"),
+        I32Const(scratch), I32Const(s), Call("big_cmp"), LocalGet(8), I32Const(1), Op("i32.and"), Op("i32.add"),
+        I32Const(0), Op("i32.gt_s"), LocalSet(10),
+        End,
+        I32Const(DIGITS), LocalGet(7), Op("i32.add"), LocalGet(8), LocalGet(10), Op("i32.add"), I32Const(b'0' as i32), Op("i32.add"),
+        Mem("i32.store8"),
+        LocalGet(7), I32Const(1), Op("i32.add"), LocalSet(7),
+        // x = 0.d1d2... * 10^k
+        LocalGet(5), I32Const(0), Op("i32.le_s"), If,
+    ]);
+    text.extend(put(vec![I32Const(b'0' as i32)]));
+    text.extend(put(vec![I32Const(b'.' as i32)]));
+    text.extend([LocalGet(5), LocalSet(12), Block, Loop, LocalGet(12), I32Const(0), Op("i32.ge_s"), BrIf(1)]);
+    text.extend(put(vec![I32Const(b'0' as i32)]));
+    text.extend([
+        LocalGet(12), I32Const(1), Op("i32.add"), LocalSet(12), Br(0),
+        End, End,
+        End,
+        I32Const(0), LocalSet(12),
+        Block, Loop,
+        LocalGet(12), LocalGet(7), Op("i32.ge_s"), BrIf(1),
+        LocalGet(12), LocalGet(5), Op("i32.eq"), LocalGet(5), I32Const(0), Op("i32.gt_s"), Op("i32.and"), If,
+    ]);
+    text.extend(put(vec![I32Const(b'.' as i32)]));
+    text.push(End);
+    text.extend(put(vec![I32Const(DIGITS), LocalGet(12), Op("i32.add"), Mem("i32.load8_u")]));
+    text.extend([
+        LocalGet(12), I32Const(1), Op("i32.add"), LocalSet(12), Br(0),
+        End, End,
+        Block, Loop,
+        LocalGet(12), LocalGet(5), Op("i32.ge_s"), BrIf(1),
+    ]);
+    text.extend(put(vec![I32Const(b'0' as i32)]));
+    text.extend([
+        LocalGet(12), I32Const(1), Op("i32.add"), LocalSet(12), Br(0),
+        End, End,
+        LocalGet(1), I32Const(0), Mem("i32.store8"),
+        I32Const(FLOAT_TEXT),
+    ]);
+
+    vec![
+        // big_set(big, value)
+        Func {
+            name: "big_set",
+            export: false,
+            params: vec![I32, I64],
+            result: None,
+            locals: vec![I32],
+            body: vec![
+                Block, Loop,
+                LocalGet(2), I32Const(BIG_SIZE), Op("i32.ge_u"), BrIf(1),
+                LocalGet(0), LocalGet(2), Op("i32.add"), I32Const(0), Mem("i32.store"),
+                LocalGet(2), I32Const(4), Op("i32.add"), LocalSet(2), Br(0),
+                End, End,
+                LocalGet(0), LocalGet(1), Mem("i64.store"),
+            ],
+        },
+        // big_mul(big, factor): factor below 2^32
+        Func {
+            name: "big_mul",
+            export: false,
+            params: vec![I32, I64],
+            result: None,
+            locals: vec![I32, I64, I64],
+            body: vec![
+                Block, Loop,
+                LocalGet(2), I32Const(BIG_SIZE), Op("i32.ge_u"), BrIf(1),
+                LocalGet(0), LocalGet(2), Op("i32.add"), Mem("i64.load32_u"), LocalGet(1), Op("i64.mul"),
+                LocalGet(3), Op("i64.add"), LocalSet(4),
+                LocalGet(0), LocalGet(2), Op("i32.add"), LocalGet(4), Mem("i64.store32"),
+                LocalGet(4), I64Const(32), Op("i64.shr_u"), LocalSet(3),
+                LocalGet(2), I32Const(4), Op("i32.add"), LocalSet(2), Br(0),
+                End, End,
+            ],
+        },
+        // big_shl(big, bits)
+        Func {
+            name: "big_shl",
+            export: false,
+            params: vec![I32, I32],
+            result: None,
+            locals: vec![],
+            body: vec![
+                Block, Loop,
+                LocalGet(1), I32Const(16), Op("i32.lt_s"), BrIf(1),
+                LocalGet(0), I64Const(1 << 16), Call("big_mul"),
+                LocalGet(1), I32Const(16), Op("i32.sub"), LocalSet(1), Br(0),
+                End, End,
+                LocalGet(0), I64Const(1), LocalGet(1), Op("i64.extend_i32_u"), Op("i64.shl"), Call("big_mul"),
+            ],
+        },
+        // big_pow10(big, n): multiplies by 10^n
+        Func {
+            name: "big_pow10",
+            export: false,
+            params: vec![I32, I32],
+            result: None,
+            locals: vec![],
+            body: vec![
+                Block, Loop,
+                LocalGet(1), I32Const(0), Op("i32.le_s"), BrIf(1),
+                LocalGet(0), I64Const(10), Call("big_mul"),
+                LocalGet(1), I32Const(1), Op("i32.sub"), LocalSet(1), Br(0),
+                End, End,
+            ],
+        },
+        // big_div(big, divisor) -> remainder: divisor below 2^32
+        Func {
+            name: "big_div",
+            export: false,
+            params: vec![I32, I64],
+            result: Some(I64),
+            locals: vec![I32, I64, I64],
+            body: vec![
+                I32Const(BIG_SIZE), LocalSet(2),
+                Block, Loop,
+                LocalGet(2), Op("i32.eqz"), BrIf(1),
+                LocalGet(2), I32Const(4), Op("i32.sub"), LocalSet(2),
+                LocalGet(3), I64Const(32), Op("i64.shl"), LocalGet(0), LocalGet(2), Op("i32.add"), Mem("i64.load32_u"),
+                Op("i64.or"), LocalSet(4),
+                LocalGet(0), LocalGet(2), Op("i32.add"), LocalGet(4), LocalGet(1), Op("i64.div_u"), Mem("i64.store32"),
+                LocalGet(4), LocalGet(1), Op("i64.rem_u"), LocalSet(3),
+                Br(0),
+                End, End,
+                LocalGet(3),
+            ],
+        },
+        // big_bits(big) -> its bit length
+        Func {
+            name: "big_bits",
+            export: false,
+            params: vec![I32],
+            result: Some(I32),
+            locals: vec![I32, I32],
+            body: vec![
+                I32Const(BIG_SIZE), LocalSet(1),
+                Block, Loop,
+                LocalGet(1), Op("i32.eqz"), BrIf(1),
+                LocalGet(1), I32Const(4), Op("i32.sub"), LocalSet(1),
+                LocalGet(0), LocalGet(1), Op("i32.add"), Mem("i32.load"), LocalTee(2), If,
+                LocalGet(1), I32Const(8), Op("i32.mul"), I32Const(32), Op("i32.add"), LocalGet(2), Op("i32.clz"), Op("i32.sub"),
+                Op("return"),
+                End,
+                Br(0),
+                End, End,
+                I32Const(0),
+            ],
+        },
+        // big_cmp(a, b) -> -1, 0 or 1
+        Func {
+            name: "big_cmp",
+            export: false,
+            params: vec![I32, I32],
+            result: Some(I32),
+            locals: vec![I32, I32, I32],
+            body: vec![
+                I32Const(BIG_SIZE - 4), LocalSet(2),
+                Block, Loop,
+                LocalGet(0), LocalGet(2), Op("i32.add"), Mem("i32.load"), LocalSet(3),
+                LocalGet(1), LocalGet(2), Op("i32.add"), Mem("i32.load"), LocalSet(4),
+                LocalGet(3), LocalGet(4), Op("i32.ne"), If,
+                I32Const(1), I32Const(-1), LocalGet(3), LocalGet(4), Op("i32.gt_u"), Op("select"), Op("return"),
+                End,
+                LocalGet(2), Op("i32.eqz"), BrIf(1),
+                LocalGet(2), I32Const(4), Op("i32.sub"), LocalSet(2), Br(0),
+                End, End,
+                I32Const(0),
+            ],
+        },
+        // big_add(dst, a, b): dst = a + b
+        Func {
+            name: "big_add",
+            export: false,
+            params: vec![I32, I32, I32],
+            result: None,
+            locals: vec![I32, I64, I64],
+            body: vec![
+                Block, Loop,
+                LocalGet(3), I32Const(BIG_SIZE), Op("i32.ge_u"), BrIf(1),
+                LocalGet(1), LocalGet(3), Op("i32.add"), Mem("i64.load32_u"),
+                LocalGet(2), LocalGet(3), Op("i32.add"), Mem("i64.load32_u"), Op("i64.add"),
+                LocalGet(5), Op("i64.add"), LocalSet(4),
+                LocalGet(0), LocalGet(3), Op("i32.add"), LocalGet(4), Mem("i64.store32"),
+                LocalGet(4), I64Const(32), Op("i64.shr_u"), LocalSet(5),
+                LocalGet(3), I32Const(4), Op("i32.add"), LocalSet(3), Br(0),
+                End, End,
+            ],
+        },
+        // big_sub(a, b): a = a - b, for a no smaller than b
+        Func {
+            name: "big_sub",
+            export: false,
+            params: vec![I32, I32],
+            result: None,
+            locals: vec![I32, I64, I64],
+            body: vec![
+                Block, Loop,
+                LocalGet(2), I32Const(BIG_SIZE), Op("i32.ge_u"), BrIf(1),
+                LocalGet(0), LocalGet(2), Op("i32.add"), Mem("i64.load32_u"),
+                LocalGet(1), LocalGet(2), Op("i32.add"), Mem("i64.load32_u"), Op("i64.sub"),
+                LocalGet(4), Op("i64.sub"), LocalSet(3),
+                LocalGet(0), LocalGet(2), Op("i32.add"), LocalGet(3), Mem("i64.store32"),
+                LocalGet(3), I64Const(63), Op("i64.shr_u"), LocalSet(4),
+                LocalGet(2), I32Const(4), Op("i32.add"), LocalSet(2), Br(0),
+                End, End,
+            ],
+        },
+        // float_text(x) -> address of its NUL-terminated text
+        Func {
+            name: "float_text",
+            export: false,
+            params: vec![F64],
+            result: Some(I32),
+            locals: vec![I32, I64, I64, I32, I32, I32, I32, I32, I32, I32, I32, I32],
+            body: text,
+        },
+        // print_f64(fd, value)
+        Func {
+            name: "print_f64",
+            export: false,
+            params: vec![I32, F64],
+            result: None,
+            locals: vec![],
+            body: vec![LocalGet(0), LocalGet(1), Call("float_text"), Call("print_cstr")],
+        },
+    ]
+}
+
+/// `i()`, `f()` and `str()`, and `{name:spec}` layout, for programs that use
+/// them; they need [`float_funcs`] and [`blank_func`] too. `message` holds the address and length
+/// of the start of the failed conversion message and of its ends for `i` and
+/// `f`.
+#[rustfmt::skip]
+fn conversion_funcs(message: [(i32, i32); 3]) -> Vec<Func> {
+    use Instr::*;
+    use ValType::*;
+
+    let [r, s, m_plus, m_minus, _] = BIGNUMS;
+    let [(before, before_len), (to_int, to_int_len), (to_float, to_float_len)] = message;
+    let skip_blanks = |at: u32| vec![
+        Block, Loop,
+        LocalGet(at), Mem("i32.load8_u"), Call("blank"), Op("i32.eqz"), BrIf(1),
+        LocalGet(at), I32Const(1), Op("i32.add"), LocalSet(at), Br(0),
+        End, End,
+    ];
+    // Moves past a sign at local `at`, setting local `negative` for a minus
+    let sign = |at: u32, negative: u32| vec![
+        LocalGet(at), Mem("i32.load8_u"), I32Const(b'-' as i32), Op("i32.eq"), LocalSet(negative),
+        LocalGet(at), Mem("i32.load8_u"), I32Const(b'+' as i32), Op("i32.eq"), LocalGet(negative), Op("i32.or"), If,
+        LocalGet(at), I32Const(1), Op("i32.add"), LocalSet(at),
+        End,
+    ];
+    // 2^n for the i32 the instructions leave, within the normal range
+    let pow2 = |n: Vec<Instr>| {
+        let mut instrs = n;
+        instrs.extend([
+            I32Const(1023), Op("i32.add"), Op("i64.extend_i32_u"), I64Const(52), Op("i64.shl"), Op("f64.reinterpret_i64"),
+        ]);
+        instrs
+    };
+
+    let mut int_body = vec![LocalGet(0), LocalSet(1)];
+    int_body.extend(skip_blanks(1));
+    int_body.extend(sign(1, 2));
+    int_body.extend([
+        Block, Loop,
+        LocalGet(1), Mem("i32.load8_u"), I32Const(b'0' as i32), Op("i32.sub"), LocalTee(4), I32Const(9), Op("i32.gt_u"), BrIf(1),
+        // Past the i64 range, whose negative side goes one further
+        LocalGet(5),
+        I64Const(i64::MAX), LocalGet(2), Op("i64.extend_i32_u"), Op("i64.add"), LocalGet(4), Op("i64.extend_i32_u"), Op("i64.sub"),
+        I64Const(10), Op("i64.div_u"), Op("i64.gt_u"), If,
+        LocalGet(0), I32Const(to_int), I32Const(to_int_len), Call("convert_failed"),
+        End,
+        LocalGet(5), I64Const(10), Op("i64.mul"), LocalGet(4), Op("i64.extend_i32_u"), Op("i64.add"), LocalSet(5),
+        LocalGet(3), I32Const(1), Op("i32.add"), LocalSet(3),
+        LocalGet(1), I32Const(1), Op("i32.add"), LocalSet(1), Br(0),
+        End, End,
+    ]);
+    int_body.extend(skip_blanks(1));
+    int_body.extend([
+        LocalGet(3), Op("i32.eqz"), LocalGet(1), Mem("i32.load8_u"), Op("i32.or"), If,
+        LocalGet(0), I32Const(to_int), I32Const(to_int_len), Call("convert_failed"),
+        End,
+        I64Const(0), LocalGet(5), Op("i64.sub"), LocalGet(5), LocalGet(2), Op("select"),
+    ]);
+
+    let failed = vec![LocalGet(0), I32Const(to_float), I32Const(to_float_len), Call("convert_failed")];
+    let mut float_body = vec![LocalGet(0), LocalSet(1), I32Const(r), I64Const(0), Call("big_set")];
+    float_body.extend(skip_blanks(1));
+    float_body.extend(sign(1, 2));
+    float_body.extend([
+        // The digits go into r as a whole number, and local 5 counts the decimal
+        // places to move its point by
+        Block, Loop,
+        LocalGet(1), Mem("i32.load8_u"), I32Const(b'.' as i32), Op("i32.eq"), LocalGet(15), Op("i32.eqz"), Op("i32.and"), If,
+        I32Const(1), LocalSet(15),
+        LocalGet(1), I32Const(1), Op("i32.add"), LocalSet(1), Br(1),
+        End,
+        LocalGet(1), Mem("i32.load8_u"), I32Const(b'0' as i32), Op("i32.sub"), LocalTee(6), I32Const(9), Op("i32.gt_u"), BrIf(1),
+        LocalGet(3), I32Const(1), Op("i32.add"), LocalSet(3),
+        LocalGet(15), If, LocalGet(5), I32Const(1), Op("i32.sub"), LocalSet(5), End,
+        // Leading zeros add nothing, and digits past 300 only mark that the rest is not zero
+        LocalGet(4), LocalGet(6), Op("i32.or"), If,
+        LocalGet(4), I32Const(300), Op("i32.lt_s"), If,
+        I32Const(r), I64Const(10), Call("big_mul"),
+        I32Const(m_minus), LocalGet(6), Op("i64.extend_i32_u"), Call("big_set"),
+        I32Const(r), I32Const(r), I32Const(m_minus), Call("big_add"),
+        LocalGet(4), I32Const(1), Op("i32.add"), LocalSet(4),
+        Else,
+        LocalGet(7), LocalGet(6), Op("i32.or"), LocalSet(7),
+        LocalGet(5), I32Const(1), Op("i32.add"), LocalSet(5),
+        End,
+        End,
+        LocalGet(1), I32Const(1), Op("i32.add"), LocalSet(1), Br(0),
+        End, End,
+        // An exponent needs digits before it and after it
+        LocalGet(3), I32Const(0), Op("i32.gt_s"),
+        LocalGet(1), Mem("i32.load8_u"), I32Const(0x20), Op("i32.or"), I32Const(b'e' as i32), Op("i32.eq"), Op("i32.and"), If,
+        LocalGet(1), I32Const(1), Op("i32.add"), LocalSet(1),
+    ]);
+    float_body.extend(sign(1, 9));
+    float_body.extend([
+        LocalGet(1), Mem("i32.load8_u"), I32Const(b'0' as i32), Op("i32.sub"), I32Const(9), Op("i32.gt_u"), If,
+        I32Const(0), LocalSet(3),
+        End,
+        Block, Loop,
+        LocalGet(1), Mem("i32.load8_u"), I32Const(b'0' as i32), Op("i32.sub"), LocalTee(6), I32Const(9), Op("i32.gt_u"), BrIf(1),
+        LocalGet(8), I32Const(100_000), Op("i32.lt_s"), If,
+        LocalGet(8), I32Const(10), Op("i32.mul"), LocalGet(6), Op("i32.add"), LocalSet(8),
+        End,
+        LocalGet(1), I32Const(1), Op("i32.add"), LocalSet(1), Br(0),
+        End, End,
+        LocalGet(5), I32Const(0), LocalGet(8), Op("i32.sub"), LocalGet(8), LocalGet(9), Op("select"), Op("i32.add"), LocalSet(5),
+        End,
+    ]);
+    float_body.extend(skip_blanks(1));
+    float_body.extend([LocalGet(3), Op("i32.eqz"), LocalGet(1), Mem("i32.load8_u"), Op("i32.or"), If]);
+    float_body.extend(failed.clone());
+    float_body.extend([
+        End,
+        LocalGet(7), If,
+        I32Const(r), I64Const(10), Call("big_mul"),
+        I32Const(m_minus), I64Const(1), Call("big_set"),
+        I32Const(r), I32Const(r), I32Const(m_minus), Call("big_add"),
+        LocalGet(5), I32Const(1), Op("i32.sub"), LocalSet(5),
+        End,
+        // Below 10^-324 the value rounds to zero, and from 10^310 it is too large
+        LocalGet(4), Op("i32.eqz"), LocalGet(4), LocalGet(5), Op("i32.add"), I32Const(-324), Op("i32.lt_s"), Op("i32.or"), If,
+        F64Const(-0.0), F64Const(0.0), LocalGet(2), Op("select"), Op("return"),
+        End,
+        LocalGet(4), LocalGet(5), Op("i32.add"), I32Const(310), Op("i32.gt_s"), If,
+    ]);
+    float_body.extend(failed.clone());
+    float_body.extend([
+        End,
+        // value = r / s, scaled by 2^-local 10 so that its whole part q has 54 or 55 bits
+        I32Const(s), I64Const(1), Call("big_set"),
+        LocalGet(5), I32Const(0), Op("i32.ge_s"), If,
+        I32Const(r), LocalGet(5), Call("big_pow10"),
+        Else,
+        I32Const(s), I32Const(0), LocalGet(5), Op("i32.sub"), Call("big_pow10"),
+        End,
+        I32Const(54), I32Const(r), Call("big_bits"), Op("i32.sub"), I32Const(s), Call("big_bits"), Op("i32.add"), LocalSet(10),
+        LocalGet(10), I32Const(0), Op("i32.ge_s"), If,
+        I32Const(r), LocalGet(10), Call("big_shl"),
+        Else,
+        I32Const(s), I32Const(0), LocalGet(10), Op("i32.sub"), Call("big_shl"),
+        End,
+        // Long division, one bit of q at a time
+        I32Const(s), I32Const(55), Call("big_shl"),
+        I32Const(56), LocalSet(12),
+        Block, Loop,
+        LocalGet(12), Op("i32.eqz"), BrIf(1),
+        LocalGet(11), LocalGet(11), Op("i64.add"), LocalSet(11),
+        I32Const(r), I32Const(s), Call("big_cmp"), I32Const(0), Op("i32.ge_s"), If,
+        I32Const(r), I32Const(s), Call("big_sub"),
+        LocalGet(11), I64Const(1), Op("i64.or"), LocalSet(11),
+        End,
+        I32Const(r), I64Const(2), Call("big_mul"),
+        LocalGet(12), I32Const(1), Op("i32.sub"), LocalSet(12), Br(0),
+        End, End,
+        I32Const(m_plus), I64Const(0), Call("big_set"),
+        I32Const(r), I32Const(m_plus), Call("big_cmp"), I32Const(0), Op("i32.ne"), LocalSet(7),
+        // q keeps the 53 bits of the result and one to round on; a subnormal keeps fewer
+        LocalGet(11), I64Const(1 << 54), Op("i64.ge_u"), If,
+        LocalGet(7), LocalGet(11), Op("i32.wrap_i64"), I32Const(1), Op("i32.and"), Op("i32.or"), LocalSet(7),
+        LocalGet(11), I64Const(1), Op("i64.shr_u"), LocalSet(11),
+        LocalGet(10), I32Const(1), Op("i32.sub"), LocalSet(10),
+        End,
+        Block, Loop,
+        LocalGet(10), I32Const(1075), Op("i32.le_s"), BrIf(1),
+        LocalGet(7), LocalGet(11), Op("i32.wrap_i64"), I32Const(1), Op("i32.and"), Op("i32.or"), LocalSet(7),
+        LocalGet(11), I64Const(1), Op("i64.shr_u"), LocalSet(11),
+        LocalGet(10), I32Const(1), Op("i32.sub"), LocalSet(10), Br(0),
+        End, End,
+        // Rounded half to even
+        LocalGet(11), I64Const(1), Op("i64.shr_u"),
+        LocalGet(11), Op("i32.wrap_i64"), I32Const(1), Op("i32.and"),
+        LocalGet(7), LocalGet(11), I64Const(1), Op("i64.shr_u"), Op("i32.wrap_i64"), I32Const(1), Op("i32.and"), Op("i32.or"),
+        Op("i32.and"), Op("i64.extend_i32_u"), Op("i64.add"), LocalSet(11),
+        // The result is q * 2^(1 - local 10), multiplied in two steps that stay exact
+        I32Const(1), LocalGet(10), Op("i32.sub"), LocalSet(13),
+        LocalGet(11), Op("f64.convert_i64_u"),
+    ]);
+    float_body.extend(pow2(vec![LocalGet(13), I32Const(1), Op("i32.shr_s")]));
+    float_body.push(Op("f64.mul"));
+    float_body.extend(pow2(vec![LocalGet(13), LocalGet(13), I32Const(1), Op("i32.shr_s"), Op("i32.sub")]));
+    float_body.extend([
+        Op("f64.mul"), LocalSet(14),
+        LocalGet(14), LocalGet(14), Op("f64.sub"), F64Const(0.0), Op("f64.ne"), If,
+    ]);
+    float_body.extend(failed);
+    float_body.extend([End, LocalGet(14), Op("f64.neg"), LocalGet(14), LocalGet(2), Op("select")]);
+
+    let put = |byte: Vec<Instr>| put_byte(2, byte);
+    let mut fixed_body = vec![
+        LocalGet(0), LocalGet(0), Op("f64.sub"), F64Const(0.0), Op("f64.ne"), If,
+        LocalGet(0), Call("float_text"), Op("return"),
+        End,
+        I32Const(FLOAT_TEXT), LocalSet(2),
+        LocalGet(0), Op("i64.reinterpret_f64"), LocalTee(3), I64Const(0), Op("i64.lt_s"), If,
+    ];
+    fixed_body.extend(put(vec![I32Const(b'-' as i32)]));
+    fixed_body.extend([
+        End,
+        // |x| * 10^precision = f * 2^e * 10^precision, into r
+        LocalGet(3), I64Const((1 << 52) - 1), Op("i64.and"), LocalSet(4),
+        LocalGet(3), I64Const(52), Op("i64.shr_u"), Op("i32.wrap_i64"), I32Const(0x7FF), Op("i32.and"), LocalTee(5), If,
+        LocalGet(4), I64Const(1 << 52), Op("i64.or"), LocalSet(4),
+        LocalGet(5), I32Const(1075), Op("i32.sub"), LocalSet(5),
+        Else,
+        I32Const(-1074), LocalSet(5),
+        End,
+        I32Const(r), LocalGet(4), Call("big_set"),
+        LocalGet(5), I32Const(0), Op("i32.ge_s"), If,
+        I32Const(r), LocalGet(5), Call("big_shl"),
+        End,
+        I32Const(r), LocalGet(1), Call("big_pow10"),
+        // Dividing by 2^-e, the bits shifted out round the rest half to even
+        LocalGet(5), I32Const(0), Op("i32.lt_s"), If,
+        I32Const(-1), LocalGet(5), Op("i32.sub"), LocalSet(8),
+        Block, Loop,
+        LocalGet(8), I32Const(16), Op("i32.lt_s"), BrIf(1),
+        LocalGet(6), I32Const(r), I64Const(1 << 16), Call("big_div"), Op("i64.eqz"), Op("i32.eqz"), Op("i32.or"), LocalSet(6),
+        LocalGet(8), I32Const(16), Op("i32.sub"), LocalSet(8), Br(0),
+        End, End,
+        LocalGet(6), I32Const(r), I64Const(1), LocalGet(8), Op("i64.extend_i32_u"), Op("i64.shl"), Call("big_div"),
+        Op("i64.eqz"), Op("i32.eqz"), Op("i32.or"), LocalSet(6),
+        I32Const(r), I64Const(2), Call("big_div"), Op("i32.wrap_i64"),
+        LocalGet(6), I32Const(r), Mem("i32.load"), I32Const(1), Op("i32.and"), Op("i32.or"), Op("i32.and"), If,
+        I32Const(m_minus), I64Const(1), Call("big_set"),
+        I32Const(r), I32Const(r), I32Const(m_minus), Call("big_add"),
+        End,
+        End,
+        // Digits, last first, with at least one before the point
+        I32Const(m_minus), I64Const(0), Call("big_set"),
+        Loop,
+        I32Const(DIGITS), LocalGet(7), Op("i32.add"),
+        I32Const(r), I64Const(10), Call("big_div"), Op("i32.wrap_i64"), I32Const(b'0' as i32), Op("i32.add"), Mem("i32.store8"),
+        LocalGet(7), I32Const(1), Op("i32.add"), LocalTee(7), LocalGet(1), Op("i32.le_s"), BrIf(0),
+        I32Const(r), I32Const(m_minus), Call("big_cmp"), BrIf(0),
+        End,
+        LocalGet(7), LocalSet(8),
+        Block, Loop,
+        LocalGet(8), Op("i32.eqz"), BrIf(1),
+        LocalGet(8), LocalGet(1), Op("i32.eq"), If,
+    ]);
+    fixed_body.extend(put(vec![I32Const(b'.' as i32)]));
+    fixed_body.extend([End, LocalGet(8), I32Const(1), Op("i32.sub"), LocalSet(8)]);
+    fixed_body.extend(put(vec![I32Const(DIGITS), LocalGet(8), Op("i32.add"), Mem("i32.load8_u")]));
+    fixed_body.extend([
+        Br(0),
+        End, End,
+        LocalGet(2), I32Const(0), Mem("i32.store8"),
+        I32Const(FLOAT_TEXT),
+    ]);
+
+    let mut text_body = vec![LocalGet(0), LocalSet(3)];
+    text_body.extend([LocalGet(2), Op("i32.eqz"), LocalGet(1), I64Const(0), Op("i64.lt_s"), Op("i32.and"), If]);
+    text_body.extend(put_byte(3, vec![I32Const(b'-' as i32)]));
+    text_body.extend([
+        I64Const(0), LocalGet(1), Op("i64.sub"), LocalSet(1),
+        End,
+        I32Const(NUM_BUF_END), LocalTee(4), I32Const(0), Mem("i32.store8"),
+        Loop,
+        LocalGet(4), I32Const(1), Op("i32.sub"), LocalTee(4),
+        LocalGet(1), I64Const(10), Op("i64.rem_u"), Op("i32.wrap_i64"), I32Const(b'0' as i32), Op("i32.add"), Mem("i32.store8"),
+        LocalGet(1), I64Const(10), Op("i64.div_u"), LocalTee(1), Op("i64.eqz"), Op("i32.eqz"), BrIf(0),
+        End,
+        LocalGet(3), LocalGet(4), I32Const(24), Call("str_copy"),
+        LocalGet(0),
+    ]);
+
+    vec![
+        // convert_failed(text, end, end_len): reports the text and the line in the
+        // `ven.line` global and exits with status 1
+        Func {
+            name: "convert_failed",
+            export: false,
+            params: vec![I32, I32, I32],
+            result: None,
+            locals: vec![],
+            body: vec![
+                I32Const(2), I32Const(before), I32Const(before_len), Call("write"),
+                I32Const(2), LocalGet(0), Call("print_cstr"),
+                I32Const(2), LocalGet(1), LocalGet(2), Call("write"),
+                I32Const(2), GlobalGet(LINE_GLOBAL.to_string()), Call("print_i64"),
+                I32Const(2), I32Const(NEWLINE), I32Const(1), Call("write"),
+                I32Const(1), Call("proc_exit"),
+            ],
+        },
+        // convert_int(text) -> value: a sign and decimal digits within i64, with blanks around them
+        Func {
+            name: "convert_int",
+            export: false,
+            params: vec![I32],
+            result: Some(I64),
+            locals: vec![I32, I32, I32, I32, I64],
+            body: int_body,
+        },
+        // convert_float(text) -> value: a finite decimal number like -1.5, .5, 2. or 6.02e23,
+        // with blanks around it, rounded half to even
+        Func {
+            name: "convert_float",
+            export: false,
+            params: vec![I32],
+            result: Some(F64),
+            locals: vec![I32, I32, I32, I32, I32, I32, I32, I32, I32, I32, I64, I32, I32, F64, I32],
+            body: float_body,
+        },
+        // float_to_int(x) -> x truncated toward zero, with NaN as 0 and clamped at the i64 limits
+        Func {
+            name: "float_to_int",
+            export: false,
+            params: vec![F64],
+            result: Some(I64),
+            locals: vec![],
+            body: vec![
+                LocalGet(0), LocalGet(0), Op("f64.ne"), If, I64Const(0), Op("return"), End,
+                LocalGet(0), F64Const(-9223372036854775808.0), Op("f64.le"), If, I64Const(i64::MIN), Op("return"), End,
+                LocalGet(0), F64Const(9223372036854775808.0), Op("f64.ge"), If, I64Const(i64::MAX), Op("return"), End,
+                LocalGet(0), Op("i64.trunc_f64_s"),
+            ],
+        },
+        // int_text(dst, value, unsigned) -> dst, which holds 24 bytes
+        Func {
+            name: "int_text",
+            export: false,
+            params: vec![I32, I64, I32],
+            result: Some(I32),
+            locals: vec![I32, I32],
+            body: text_body,
+        },
+        // fixed_text(x, precision) -> address of x with precision digits after the point,
+        // rounded half to even
+        Func {
+            name: "fixed_text",
+            export: false,
+            params: vec![F64, I32],
+            result: Some(I32),
+            locals: vec![I32, I64, I64, I32, I32, I32, I32],
+            body: fixed_body,
+        },
+        // print_padded(fd, text, width, right): spaces up to width characters, before the text when right
+        Func {
+            name: "print_padded",
+            export: false,
+            params: vec![I32, I32, I32, I32],
+            result: None,
+            locals: vec![I32, I32, I32],
+            body: vec![
+                LocalGet(1), LocalSet(5),
+                Block, Loop,
+                LocalGet(5), Mem("i32.load8_u"), LocalTee(4), Op("i32.eqz"), BrIf(1),
+                LocalGet(6), LocalGet(4), I32Const(0xC0), Op("i32.and"), I32Const(0x80), Op("i32.ne"), Op("i32.add"), LocalSet(6),
+                LocalGet(5), I32Const(1), Op("i32.add"), LocalSet(5), Br(0),
+                End, End,
+                LocalGet(3), Op("i32.eqz"), If, LocalGet(0), LocalGet(1), Call("print_cstr"), End,
+                Block, Loop,
+                LocalGet(6), LocalGet(2), Op("i32.ge_u"), BrIf(1),
+                LocalGet(0), I32Const(SPACE), I32Const(1), Call("write"),
+                LocalGet(6), I32Const(1), Op("i32.add"), LocalSet(6), Br(0),
+                End, End,
+                LocalGet(3), If, LocalGet(0), LocalGet(1), Call("print_cstr"), End,
+            ],
+        },
+    ]
+}
+
+/// What an out-of-range integer becomes under `overflow`: `wrapped` and
+/// `saturated` are the code for those modes, and trap reports the variable
+/// whose name and type are in the `name` and `var_type` locals.
+fn on_overflow(overflow: Overflow, wrapped: Vec<Instr>, saturated: Vec<Instr>, name: u32, var_type: u32) -> Vec<Instr> {
+    match overflow {
+        Overflow::Wrap => wrapped,
+        Overflow::Saturate => saturated,
+        Overflow::Trap => vec![
+            Instr::LocalGet(name),
+            Instr::LocalGet(var_type),
+            Instr::Call("overflow"),
+            Instr::Op("unreachable"),
+        ],
+    }
+}
+
+/// A 64-bit `op` whose overflow is detected by `overflowed`, which leaves an
+/// `i32` on the stack. `x` and `y` are locals 0 and 1, the result is local 4.
+#[rustfmt::skip]
+fn math64(
+    name: &'static str,
+    op: &'static str,
+    overflowed: Vec<Instr>,
+    saturated: Vec<Instr>,
+    overflow: Overflow,
+) -> Func {
+    use Instr::*;
+    use ValType::*;
+
+    let mut body = vec![LocalGet(0), LocalGet(1), Op(op), LocalSet(4)];
+    body.extend(overflowed);
+    body.push(If);
+    body.extend(on_overflow(overflow, vec![], saturated, 2, 3));
+    body.extend([End, LocalGet(4)]);
+    Func { name, export: false, params: vec![I64, I64, I32, I32], result: Some(I64), locals: vec![I64], body }
+}
+
+/// `overflow(name, type)` for `--overflow=trap`: reports the variable and the
+/// line in the `ven.line` global and exits with status 1. `message` holds the address and length of the three parts of
+/// the message.
+#[rustfmt::skip]
+fn overflow_report(message: [(i32, i32); 3]) -> Func {
+    use Instr::*;
+    use ValType::*;
+
+    let [(before, before_len), (between, between_len), (after, after_len)] = message;
+    Func {
+        name: "overflow",
+        export: false,
+        params: vec![I32, I32],
+        result: None,
+        locals: vec![],
+        body: vec![
+            I32Const(2), I32Const(before), I32Const(before_len), Call("write"),
+            I32Const(2), LocalGet(0), Call("print_cstr"),
+            I32Const(2), I32Const(between), I32Const(between_len), Call("write"),
+            I32Const(2), LocalGet(1), Call("print_cstr"),
+            I32Const(2), I32Const(after), I32Const(after_len), Call("write"),
+            I32Const(2), GlobalGet(LINE_GLOBAL.to_string()), Call("print_i64"),
+            I32Const(2), I32Const(NEWLINE), I32Const(1), Call("write"),
+            I32Const(1), Call("proc_exit"),
+        ],
+    }
+}
+
+/// The division by zero checks. Each takes the operand, then the address of
+/// the variable's name and the source line, and gives the operand back; the
+/// `exponent` check takes the variable's value after the operand.
+#[rustfmt::skip]
+fn division_funcs(message: [(i32, i32); 2]) -> Vec<Func> {
+    use Instr::*;
+    use ValType::*;
+
+    let [(before, before_len), (between, between_len)] = message;
+    vec![
+        // div_zero(name, line): reports the variable and line and exits with status 1
+        Func {
+            name: "div_zero",
+            export: false,
+            params: vec![I32, I64],
+            result: None,
+            locals: vec![],
+            body: vec![
+                I32Const(2), I32Const(before), I32Const(before_len), Call("write"),
+                I32Const(2), LocalGet(0), Call("print_cstr"),
+                I32Const(2), I32Const(between), I32Const(between_len), Call("write"),
+                I32Const(2), LocalGet(1), Call("print_i64"),
+                I32Const(2), I32Const(NEWLINE), I32Const(1), Call("write"),
+                I32Const(1), Call("proc_exit"),
+            ],
+        },
+        Func {
+            name: "divisor",
+            export: false,
+            params: vec![I64, I32, I64],
+            result: Some(I64),
+            locals: vec![],
+            body: vec![
+                LocalGet(0), Op("i64.eqz"), If, LocalGet(1), LocalGet(2), Call("div_zero"), End,
+                LocalGet(0),
+            ],
+        },
+        Func {
+            name: "divisor_f",
+            export: false,
+            params: vec![F64, I32, I64],
+            result: Some(F64),
+            locals: vec![],
+            body: vec![
+                LocalGet(0), F64Const(0.0), Op("f64.eq"), If, LocalGet(1), LocalGet(2), Call("div_zero"), End,
+                LocalGet(0),
+            ],
+        },
+        // exponent(y, x, name, line): a negative power of zero divides by zero too
+        Func {
+            name: "exponent",
+            export: false,
+            params: vec![I64, I64, I32, I64],
+            result: Some(I64),
+            locals: vec![],
+            body: vec![
+                LocalGet(1), Op("i64.eqz"), LocalGet(0), I64Const(0), Op("i64.lt_s"), Op("i32.and"), If,
+                LocalGet(2), LocalGet(3), Call("div_zero"), End,
+                LocalGet(0),
+            ],
+        },
+    ]
+}
+
+/// Helpers that store integers into a type under `overflow`.
+#[rustfmt::skip]
+fn overflow_funcs(overflow: Overflow) -> Vec<Func> {
+    use Instr::*;
+    use ValType::*;
+
+    let signed_limit = |x: u32| vec![
+        I64Const(i64::MAX), I64Const(i64::MIN), LocalGet(x), I64Const(0), Op("i64.ge_s"), Op("select"), Op("return"),
+    ];
+    let wrap_to_type = vec![
+        LocalGet(0), LocalGet(3), LocalGet(2), Op("i64.sub"), Op("i64.and"), LocalSet(6),
+        LocalGet(6), LocalGet(3), LocalGet(2), Op("i64.sub"), Op("i64.sub"), I64Const(1), Op("i64.sub"),
+        LocalGet(6),
+        LocalGet(6), LocalGet(3), Op("i64.gt_s"), Op("select"), Op("return"),
+    ];
+
+    let mut fit_body = vec![
+        // A u64 target holds everything but negative signed values
+        LocalGet(2), Op("i64.eqz"), LocalGet(3), I64Const(-1), Op("i64.eq"), Op("i32.and"), If,
+        LocalGet(1), Op("i32.eqz"), LocalGet(0), I64Const(0), Op("i64.lt_s"), Op("i32.and"), If,
+    ];
+    fit_body.extend(on_overflow(overflow, vec![], vec![I64Const(0), Op("return")], 4, 5));
+    fit_body.extend([End, LocalGet(0), Op("return"), End]);
+    // Above the i64 range
+    fit_body.extend([LocalGet(1), LocalGet(0), I64Const(0), Op("i64.lt_s"), Op("i32.and"), If]);
+    fit_body.extend(on_overflow(overflow, wrap_to_type.clone(), vec![LocalGet(3), Op("return")], 4, 5));
+    fit_body.extend([End, LocalGet(0), LocalGet(2), Op("i64.lt_s"), If]);
+    fit_body.extend(on_overflow(overflow, wrap_to_type.clone(), vec![LocalGet(2), Op("return")], 4, 5));
+    fit_body.extend([End, LocalGet(0), LocalGet(3), Op("i64.gt_s"), If]);
+    fit_body.extend(on_overflow(overflow, wrap_to_type, vec![LocalGet(3), Op("return")], 4, 5));
+    fit_body.extend([End, LocalGet(0)]);
+
+    // Floats never wrap: out of range they clamp or trap
+    let mut fit_float_body = vec![
+        LocalGet(0), LocalGet(0), Op("f64.ne"), If, I64Const(0), Op("return"), End,
+        LocalGet(0), Op("f64.trunc"), LocalSet(0),
+        LocalGet(0), LocalGet(1), Op("f64.lt"), If,
+    ];
+    fit_float_body.extend(on_overflow(overflow, vec![LocalGet(3), Op("return")], vec![LocalGet(3), Op("return")], 5, 6));
+    fit_float_body.extend([End, LocalGet(0), LocalGet(2), Op("f64.ge"), If]);
+    fit_float_body.extend(on_overflow(overflow, vec![LocalGet(4), Op("return")], vec![LocalGet(4), Op("return")], 5, 6));
+    fit_float_body.extend([
+        End,
+        LocalGet(0), F64Const(0.0), Op("f64.lt"), If, LocalGet(0), Op("i64.trunc_f64_s"), Op("return"), End,
+        LocalGet(0), Op("i64.trunc_f64_u"),
+    ]);
+
+    vec![
+        // fit(value, unsigned, min, max, name, type) -> value stored into the type from
+        // min to max, where a u64 has min 0 and max -1; unsigned reads value as a u64
+        Func {
+            name: "fit",
+            export: false,
+            params: vec![I64, I32, I64, I64, I32, I32],
+            result: Some(I64),
+            locals: vec![I64],
+            body: fit_body,
+        },
+        // fit_float(value, low, high, min, max, name, type): low and high are the limits
+        // as floats, the upper one exclusive
+        Func {
+            name: "fit_float",
+            export: false,
+            params: vec![F64, F64, F64, I64, I64, I32, I32],
+            result: Some(I64),
+            locals: vec![],
+            body: fit_float_body,
+        },
+        // Math on i64 and u64, where the result can leave the 64 bits
+        math64("add_i64", "i64.add", vec![
+            LocalGet(0), LocalGet(4), Op("i64.xor"), LocalGet(1), LocalGet(4), Op("i64.xor"), Op("i64.and"),
+            I64Const(0), Op("i64.lt_s"),
+        ], signed_limit(0), overflow),
+        math64("sub_i64", "i64.sub", vec![
+            LocalGet(0), LocalGet(1), Op("i64.xor"), LocalGet(0), LocalGet(4), Op("i64.xor"), Op("i64.and"),
+            I64Const(0), Op("i64.lt_s"),
+        ], signed_limit(0), overflow),
+        math64("mul_i64", "i64.mul", vec![
+            // The check divides, so x = -1 is handled apart
+            LocalGet(0), I64Const(-1), Op("i64.eq"), If,
+            LocalGet(1), I64Const(i64::MIN), Op("i64.eq"), Op("i64.extend_i32_u"), LocalSet(4),
+            Else,
+            LocalGet(0), Op("i64.eqz"), If,
+            I64Const(0), LocalSet(4),
+            Else,
+            LocalGet(4), LocalGet(0), Op("i64.div_s"), LocalGet(1), Op("i64.ne"), Op("i64.extend_i32_u"), LocalSet(4),
+            End,
+            End,
+            LocalGet(4), Op("i64.eqz"), Op("i32.eqz"),
+            LocalGet(0), LocalGet(1), Op("i64.mul"), LocalSet(4),
+        ], vec![
+            I64Const(i64::MIN), I64Const(i64::MAX), LocalGet(0), LocalGet(1), Op("i64.xor"), I64Const(0), Op("i64.lt_s"),
+            Op("select"), Op("return"),
+        ], overflow),
+        Func {
+            name: "div_i64",
+            export: false,
+            params: vec![I64, I64, I32, I32],
+            result: Some(I64),
+            locals: vec![],
+            body: {
+                let mut body = vec![
+                    LocalGet(0), I64Const(i64::MIN), Op("i64.eq"), LocalGet(1), I64Const(-1), Op("i64.eq"), Op("i32.and"), If,
+                ];
+                body.extend(on_overflow(
+                    overflow,
+                    vec![I64Const(i64::MIN), Op("return")],
+                    vec![I64Const(i64::MAX), Op("return")],
+                    2,
+                    3,
+                ));
+                body.extend([End, LocalGet(0), LocalGet(1), Op("i64.div_s")]);
+                body
+            },
+        },
+        math64("add_u64", "i64.add", vec![LocalGet(4), LocalGet(0), Op("i64.lt_u")], vec![I64Const(-1), Op("return")], overflow),
+        math64("sub_u64", "i64.sub", vec![LocalGet(0), LocalGet(1), Op("i64.lt_u")], vec![I64Const(0), Op("return")], overflow),
+        math64("mul_u64", "i64.mul", vec![
+            LocalGet(0), Op("i64.eqz"), If,
+            I64Const(0), LocalSet(4),
+            Else,
+            LocalGet(4), LocalGet(0), Op("i64.div_u"), LocalGet(1), Op("i64.ne"), Op("i64.extend_i32_u"), LocalSet(4),
+            End,
+            LocalGet(4), Op("i64.eqz"), Op("i32.eqz"),
+            LocalGet(0), LocalGet(1), Op("i64.mul"), LocalSet(4),
+        ], vec![I64Const(-1), Op("return")], overflow),
+        // compare(a, a_unsigned, b, b_unsigned) -> -1, 0 or 1, for sides that may be u64
+        Func {
+            name: "compare",
+            export: false,
+            params: vec![I64, I32, I64, I32],
+            result: Some(I32),
+            locals: vec![],
+            body: vec![
+                // A negative signed side is the smaller one; otherwise both read as u64
+                LocalGet(1), Op("i32.eqz"), LocalGet(0), I64Const(0), Op("i64.lt_s"), Op("i32.and"),
+                LocalGet(3), Op("i32.eqz"), LocalGet(2), I64Const(0), Op("i64.lt_s"), Op("i32.and"), Op("i32.ne"), If,
+                I32Const(-1), I32Const(1), LocalGet(1), Op("i32.eqz"), LocalGet(0), I64Const(0), Op("i64.lt_s"), Op("i32.and"), Op("select"), Op("return"),
+                End,
+                LocalGet(1), LocalGet(3), Op("i32.or"), If,
+                LocalGet(0), LocalGet(2), Op("i64.gt_u"), LocalGet(0), LocalGet(2), Op("i64.lt_u"), Op("i32.sub"), Op("return"),
+                End,
+                LocalGet(0), LocalGet(2), Op("i64.gt_s"), LocalGet(0), LocalGet(2), Op("i64.lt_s"), Op("i32.sub"),
+            ],
+        },
+    ]
+}
+
+/// `**`, `%` on floats and the math functions, for programs that use them.
+/// Without a math library, `fpow` works through `exp` and `ln` for fractional
+/// exponents, exact to about the last digit.
+#[rustfmt::skip]
+fn math_funcs(overflow: Overflow) -> Vec<Func> {
+    use Instr::*;
+    use ValType::*;
+
+    const LN2_HI: f64 = 6.931_471_803_691_238e-1;
+    const LN2_LO: f64 = 1.908_214_929_270_587_7e-10;
+
+    // A power past every integer type: wrapped, its low 64 bits are stored like
+    // any other value; saturated, it becomes the limit on its side
+    let out_of_range = on_overflow(overflow, vec![
+        I64Const(1), LocalSet(7), LocalGet(0), LocalSet(8), LocalGet(1), LocalSet(10),
+        Block, Loop,
+        LocalGet(10), Op("i64.eqz"), BrIf(1),
+        LocalGet(10), I64Const(1), Op("i64.and"), Op("i32.wrap_i64"), If,
+        LocalGet(7), LocalGet(8), Op("i64.mul"), LocalSet(7),
+        End,
+        LocalGet(8), LocalGet(8), Op("i64.mul"), LocalSet(8),
+        LocalGet(10), I64Const(1), Op("i64.shr_u"), LocalSet(10),
+        Br(0),
+        End, End,
+        LocalGet(7), I32Const(0), LocalGet(3), LocalGet(4), LocalGet(5), LocalGet(6), Call("fit"), Op("return"),
+    ], vec![LocalGet(3), LocalGet(4), LocalGet(9), Op("select"), Op("return")], 5, 6);
+
+    let mut pow_body = vec![
+        LocalGet(1), Op("i64.eqz"), If, I64Const(1), Op("return"), End,
+        LocalGet(0), I64Const(1), Op("i64.le_u"), If, LocalGet(0), Op("return"), End,
+        LocalGet(2), If,
+        LocalGet(0), LocalSet(8),
+        Else,
+        LocalGet(0), I64Const(-1), Op("i64.eq"), If,
+        I64Const(-1), I64Const(1), LocalGet(1), I64Const(1), Op("i64.and"), Op("i32.wrap_i64"), Op("select"), Op("return"),
+        End,
+        // A negative exponent gives 1 / x ** -y, which truncates to 0
+        LocalGet(1), I64Const(0), Op("i64.lt_s"), If, I64Const(0), Op("return"), End,
+        LocalGet(0), I64Const(0), Op("i64.lt_s"), LocalGet(1), Op("i32.wrap_i64"), I32Const(1), Op("i32.and"), Op("i32.and"), LocalSet(9),
+        I64Const(0), LocalGet(0), Op("i64.sub"), LocalGet(0), LocalGet(0), I64Const(0), Op("i64.lt_s"), Op("select"), LocalSet(8),
+        End,
+        // The magnitude is multiplied up y times, until it leaves the u64 range
+        I64Const(1), LocalSet(7), I64Const(0), LocalSet(10),
+        Block, Loop,
+        LocalGet(7), LocalGet(8), Op("i64.mul"), LocalSet(11),
+        LocalGet(11), LocalGet(8), Op("i64.div_u"), LocalGet(7), Op("i64.ne"), If,
+    ];
+    pow_body.extend(out_of_range.clone());
+    pow_body.extend([
+        End,
+        LocalGet(11), LocalSet(7),
+        LocalGet(10), I64Const(1), Op("i64.add"), LocalTee(10), LocalGet(1), Op("i64.ne"), BrIf(0),
+        End, End,
+        LocalGet(9), If,
+        LocalGet(7), I64Const(i64::MIN), Op("i64.gt_u"), If,
+    ]);
+    pow_body.extend(out_of_range);
+    pow_body.extend([
+        End,
+        I64Const(0), LocalGet(7), Op("i64.sub"), I32Const(0),
+        LocalGet(3), LocalGet(4), LocalGet(5), LocalGet(6), Call("fit"), Op("return"),
+        End,
+        LocalGet(7), I32Const(1), LocalGet(3), LocalGet(4), LocalGet(5), LocalGet(6), Call("fit"),
+    ]);
+
+    vec![
+        // pow_int(x, y, unsigned, min, max, name, type) -> x ** y stored into the type;
+        // unsigned reads both as u64
+        Func {
+            name: "pow_int",
+            export: false,
+            params: vec![I64, I64, I32, I64, I64, I32, I32],
+            result: Some(I64),
+            locals: vec![I64, I64, I32, I64, I64],
+            body: pow_body,
+        },
+        // abs_i64(x, name, type)
+        Func {
+            name: "abs_i64",
+            export: false,
+            params: vec![I64, I32, I32],
+            result: Some(I64),
+            locals: vec![],
+            body: vec![
+                LocalGet(0), I64Const(0), Op("i64.lt_s"), If,
+                I64Const(0), LocalGet(0), LocalGet(1), LocalGet(2), Call("sub_i64"), Op("return"),
+                End,
+                LocalGet(0),
+            ],
+        },
+        // min and max of two values: the second only wins when it is smaller, or larger
+        Func {
+            name: "min_i64",
+            export: false,
+            params: vec![I64, I64],
+            result: Some(I64),
+            locals: vec![],
+            body: vec![LocalGet(1), LocalGet(0), LocalGet(1), LocalGet(0), Op("i64.lt_s"), Op("select")],
+        },
+        Func {
+            name: "max_i64",
+            export: false,
+            params: vec![I64, I64],
+            result: Some(I64),
+            locals: vec![],
+            body: vec![LocalGet(1), LocalGet(0), LocalGet(1), LocalGet(0), Op("i64.gt_s"), Op("select")],
+        },
+        Func {
+            name: "min_f64",
+            export: false,
+            params: vec![F64, F64],
+            result: Some(F64),
+            locals: vec![],
+            body: vec![LocalGet(1), LocalGet(0), LocalGet(1), LocalGet(0), Op("f64.lt"), Op("select")],
+        },
+        Func {
+            name: "max_f64",
+            export: false,
+            params: vec![F64, F64],
+            result: Some(F64),
+            locals: vec![],
+            body: vec![LocalGet(1), LocalGet(0), LocalGet(1), LocalGet(0), Op("f64.gt"), Op("select")],
+        },
+        // round_f64(x): halves round away from zero, and the sign is kept
+        Func {
+            name: "round_f64",
+            export: false,
+            params: vec![F64],
+            result: Some(F64),
+            locals: vec![F64],
+            body: vec![
+                LocalGet(0), Op("f64.trunc"), LocalSet(1),
+                LocalGet(0), LocalGet(1), Op("f64.sub"), Op("f64.abs"), F64Const(0.5), Op("f64.ge"), If,
+                LocalGet(1), F64Const(1.0), LocalGet(0), Op("f64.copysign"), Op("f64.add"), LocalSet(1),
+                End,
+                LocalGet(1), LocalGet(0), Op("f64.copysign"),
+            ],
+        },
+        // fmod(x, y): the remainder with the sign of x, as C's fmod. The largest
+        // doubling of |y| that fits is taken away until less than |y| is left,
+        // and each subtraction is exact
+        Func {
+            name: "fmod",
+            export: false,
+            params: vec![F64, F64],
+            result: Some(F64),
+            locals: vec![F64, F64, F64],
+            body: vec![
+                LocalGet(0), LocalGet(0), Op("f64.sub"), F64Const(0.0), Op("f64.ne"),
+                LocalGet(1), LocalGet(1), Op("f64.ne"), Op("i32.or"),
+                LocalGet(1), F64Const(0.0), Op("f64.eq"), Op("i32.or"), If,
+                F64Const(f64::NAN), Op("return"),
+                End,
+                LocalGet(0), Op("f64.abs"), LocalSet(2),
+                LocalGet(1), Op("f64.abs"), LocalSet(3),
+                Block, Loop,
+                LocalGet(2), LocalGet(3), Op("f64.lt"), BrIf(1),
+                LocalGet(3), LocalSet(4),
+                Block, Loop,
+                LocalGet(4), F64Const(2.0), Op("f64.mul"), LocalGet(2), Op("f64.gt"), BrIf(1),
+                LocalGet(4), F64Const(2.0), Op("f64.mul"), LocalSet(4),
+                Br(0),
+                End, End,
+                LocalGet(2), LocalGet(4), Op("f64.sub"), LocalSet(2),
+                Br(0),
+                End, End,
+                LocalGet(2), LocalGet(0), Op("f64.copysign"),
+            ],
+        },
+        // ln(x) for a positive finite x = m * 2^k: k ln 2 + 2 atanh((m - 1) / (m + 1)),
+        // with m between sqrt(1/2) and sqrt(2)
+        Func {
+            name: "ln",
+            export: false,
+            params: vec![F64],
+            result: Some(F64),
+            locals: vec![I64, F64, F64, F64, F64, F64, F64],
+            body: vec![
+                I64Const(0), LocalSet(1),
+                LocalGet(0), F64Const(f64::MIN_POSITIVE), Op("f64.lt"), If,
+                LocalGet(0), F64Const(18_014_398_509_481_984.0), Op("f64.mul"), LocalSet(0),
+                I64Const(-54), LocalSet(1),
+                End,
+                LocalGet(1), LocalGet(0), Op("i64.reinterpret_f64"), I64Const(52), Op("i64.shr_u"), I64Const(1023), Op("i64.sub"),
+                Op("i64.add"), LocalSet(1),
+                LocalGet(0), Op("i64.reinterpret_f64"), I64Const(0x000F_FFFF_FFFF_FFFF), Op("i64.and"),
+                I64Const(0x3FF0_0000_0000_0000), Op("i64.or"), Op("f64.reinterpret_i64"), LocalSet(2),
+                LocalGet(2), F64Const(std::f64::consts::SQRT_2), Op("f64.gt"), If,
+                LocalGet(2), F64Const(0.5), Op("f64.mul"), LocalSet(2),
+                LocalGet(1), I64Const(1), Op("i64.add"), LocalSet(1),
+                End,
+                LocalGet(2), F64Const(1.0), Op("f64.sub"), LocalGet(2), F64Const(1.0), Op("f64.add"), Op("f64.div"), LocalTee(3),
+                LocalGet(3), Op("f64.mul"), LocalSet(4),
+                LocalGet(3), LocalSet(5),
+                F64Const(0.0), LocalSet(6),
+                F64Const(1.0), LocalSet(7),
+                Loop,
+                LocalGet(6), LocalGet(5), LocalGet(7), Op("f64.div"), Op("f64.add"), LocalSet(6),
+                LocalGet(5), LocalGet(4), Op("f64.mul"), LocalSet(5),
+                LocalGet(7), F64Const(2.0), Op("f64.add"), LocalTee(7), F64Const(41.0), Op("f64.lt"), BrIf(0),
+                End,
+                LocalGet(1), Op("f64.convert_i64_s"), F64Const(LN2_HI), Op("f64.mul"),
+                LocalGet(1), Op("f64.convert_i64_s"), F64Const(LN2_LO), Op("f64.mul"),
+                LocalGet(6), F64Const(2.0), Op("f64.mul"), Op("f64.add"), Op("f64.add"),
+            ],
+        },
+        // exp(x) = 2^k e^r with |r| at most ln(2) / 2; 2^k is applied in two halves,
+        // so that each factor is a normal float
+        Func {
+            name: "exp",
+            export: false,
+            params: vec![F64],
+            result: Some(F64),
+            locals: vec![F64, F64, F64, F64, F64, I64],
+            body: vec![
+                LocalGet(0), LocalGet(0), Op("f64.ne"), If, LocalGet(0), Op("return"), End,
+                LocalGet(0), F64Const(709.782_712_893_384), Op("f64.gt"), If, F64Const(f64::INFINITY), Op("return"), End,
+                LocalGet(0), F64Const(-745.133_219_101_941_2), Op("f64.lt"), If, F64Const(0.0), Op("return"), End,
+                LocalGet(0), F64Const(std::f64::consts::LN_2), Op("f64.div"), Op("f64.nearest"), LocalSet(1),
+                LocalGet(0), LocalGet(1), F64Const(LN2_HI), Op("f64.mul"), Op("f64.sub"),
+                LocalGet(1), F64Const(LN2_LO), Op("f64.mul"), Op("f64.sub"), LocalSet(2),
+                F64Const(1.0), LocalSet(3),
+                F64Const(1.0), LocalSet(4),
+                F64Const(1.0), LocalSet(5),
+                Loop,
+                LocalGet(3), LocalGet(2), Op("f64.mul"), LocalGet(5), Op("f64.div"), LocalTee(3),
+                LocalGet(4), Op("f64.add"), LocalSet(4),
+                LocalGet(5), F64Const(1.0), Op("f64.add"), LocalTee(5), F64Const(20.0), Op("f64.lt"), BrIf(0),
+                End,
+                LocalGet(1), F64Const(0.5), Op("f64.mul"), Op("f64.trunc"), Op("i64.trunc_f64_s"), LocalSet(6),
+                LocalGet(4),
+                LocalGet(6), I64Const(1023), Op("i64.add"), I64Const(52), Op("i64.shl"), Op("f64.reinterpret_i64"), Op("f64.mul"),
+                LocalGet(1), Op("i64.trunc_f64_s"), LocalGet(6), Op("i64.sub"),
+                I64Const(1023), Op("i64.add"), I64Const(52), Op("i64.shl"), Op("f64.reinterpret_i64"), Op("f64.mul"),
+            ],
+        },
+        // fpow(x, y) as C's pow: whole exponents below 2^53 multiply, squaring as
+        // they go, and the rest go through exp(y ln x)
+        Func {
+            name: "fpow",
+            export: false,
+            params: vec![F64, F64],
+            result: Some(F64),
+            locals: vec![F64, F64, I64],
+            body: vec![
+                LocalGet(1), F64Const(0.0), Op("f64.eq"), LocalGet(0), F64Const(1.0), Op("f64.eq"), Op("i32.or"), If,
+                F64Const(1.0), Op("return"),
+                End,
+                LocalGet(0), LocalGet(0), Op("f64.ne"), LocalGet(1), LocalGet(1), Op("f64.ne"), Op("i32.or"), If,
+                LocalGet(0), LocalGet(1), Op("f64.add"), Op("return"),
+                End,
+                // An infinite exponent: 1 for -1, otherwise infinity or 0
+                LocalGet(1), Op("f64.abs"), F64Const(f64::INFINITY), Op("f64.eq"), If,
+                LocalGet(0), Op("f64.abs"), F64Const(1.0), Op("f64.eq"), If, F64Const(1.0), Op("return"), End,
+                F64Const(f64::INFINITY), F64Const(0.0),
+                LocalGet(0), Op("f64.abs"), F64Const(1.0), Op("f64.gt"), LocalGet(1), F64Const(0.0), Op("f64.gt"), Op("i32.eq"),
+                Op("select"), Op("return"),
+                End,
+                // Exponents from 2^53 up are even whole numbers
+                LocalGet(1), Op("f64.abs"), F64Const(9_007_199_254_740_992.0), Op("f64.ge"), If,
+                LocalGet(0), Op("f64.abs"), LocalSet(0),
+                Else,
+                LocalGet(1), Op("f64.trunc"), LocalGet(1), Op("f64.eq"), If,
+                LocalGet(1), Op("f64.abs"), Op("i64.trunc_f64_s"), LocalSet(4),
+                F64Const(1.0), LocalSet(2),
+                LocalGet(0), LocalSet(3),
+                Loop,
+                LocalGet(4), I64Const(1), Op("i64.and"), Op("i32.wrap_i64"), If,
+                LocalGet(2), LocalGet(3), Op("f64.mul"), LocalSet(2),
+                End,
+                LocalGet(3), LocalGet(3), Op("f64.mul"), LocalSet(3),
+                LocalGet(4), I64Const(1), Op("i64.shr_u"), LocalTee(4), Op("i64.eqz"), Op("i32.eqz"), BrIf(0),
+                End,
+                LocalGet(1), F64Const(0.0), Op("f64.lt"), If,
+                // A power past the float range, or inexact below it, has a reciprocal
+                // that may still be a float
+                LocalGet(2), Op("f64.abs"), F64Const(f64::INFINITY), Op("f64.eq"),
+                LocalGet(2), Op("f64.abs"), F64Const(f64::MIN_POSITIVE), Op("f64.lt"),
+                LocalGet(2), F64Const(0.0), Op("f64.ne"), Op("i32.and"), Op("i32.or"), If,
+                LocalGet(1), LocalGet(0), Op("f64.abs"), Call("ln"), Op("f64.mul"), Call("exp"), LocalGet(2), Op("f64.copysign"),
+                Op("return"),
+                End,
+                F64Const(1.0), LocalGet(2), Op("f64.div"), Op("return"),
+                End,
+                LocalGet(2), Op("return"),
+                End,
+                End,
+                // A fractional exponent of a negative number has no real result,
+                // but -infinity behaves as infinity
+                LocalGet(0), F64Const(f64::NEG_INFINITY), Op("f64.eq"), If,
+                F64Const(f64::INFINITY), LocalSet(0),
+                End,
+                LocalGet(0), F64Const(0.0), Op("f64.lt"), If, F64Const(f64::NAN), Op("return"), End,
+                LocalGet(0), F64Const(0.0), Op("f64.eq"), LocalGet(0), F64Const(f64::INFINITY), Op("f64.eq"), Op("i32.or"), If,
+                F64Const(f64::INFINITY), F64Const(0.0),
+                LocalGet(0), F64Const(f64::INFINITY), Op("f64.eq"), LocalGet(1), F64Const(0.0), Op("f64.gt"), Op("i32.eq"),
+                Op("select"), Op("return"),
+                End,
+                LocalGet(1), LocalGet(0), Call("ln"), Op("f64.mul"), Call("exp"),
+            ],
+        },
+    ]
+}
+
+/// How a value is represented on the wasm stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// `i64`
+    Int,
+    /// `f64`
+    Float,
+    /// `i32` pointer to a NUL-terminated string
+    Str,
+}
+
+/// Where a Ven variable lives.
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    /// An `i64` global; a `u64` keeps its bits.
+    Int(IntType),
+    Float,
+    /// An `i64` global holding 0 or 1.
+    Bool,
+    Buffer { addr: u32, size: u32 },
+}
+
+struct Codegen {
+    overflow: Overflow,
+    slots: HashMap<String, Slot>,
+    globals: Vec<(String, ValType)>,
+    data: Vec<(u32, Vec<u8>)>,
+    literals: HashMap<Vec<u8>, u32>,
+    next_addr: u32,
+    body: Vec<Instr>,
+    var_types: HashMap<String, VarType>,
+    /// Whether the math helpers are called.
+    math: bool,
+    /// Whether a float is printed, which needs `float_text`.
+    floats: bool,
+    /// Whether the conversion helpers are called.
+    conversions: bool,
+    /// Whether the string operation helpers are called.
+    strings: bool,
+    /// Whether `/`, `%` and integer `**` check for division by zero.
+    checks: bool,
+    /// Whether the division by zero checks are called.
+    division: bool,
+    /// The source line of the statement being generated, named by runtime errors.
+    line: usize,
+}
+
+impl Codegen {
+    fn new(nodes: &[ASTNode], options: &CodegenOptions, var_types: HashMap<String, VarType>) -> Self {
+        let mut codegen = Codegen {
+            overflow: options.overflow,
+            slots: HashMap::new(),
+            globals: Vec::new(),
+            data: vec![(MINUS as u32, b"-\n ".to_vec()), (NAN_TEXT as u32, b"NaN\0-inf\0".to_vec())],
+            literals: HashMap::new(),
+            next_addr: HEAP_START,
+            body: Vec::new(),
+            var_types,
+            math: false,
+            floats: false,
+            conversions: false,
+            strings: false,
+            checks: options.runtime_checks,
+            division: false,
+            line: 0,
+        };
+        codegen.allocate(nodes);
+        codegen
+    }
+
+    /// Assigns a global or a memory buffer to every declared variable, in declaration order.
+    fn allocate(&mut self, nodes: &[ASTNode]) {
+        for node in nodes {
+            match node {
+                ASTNode::VarDeclaration { name, var_type, .. } => {
+                    if self.slots.contains_key(name) {
+                        continue;
+                    }
+                    let slot = match var_type {
+                        VarType::Int(int_type) => {
+                            self.globals.push((name.clone(), ValType::I64));
+                            Slot::Int(*int_type)
+                        }
+                        VarType::Float => {
+                            self.globals.push((name.clone(), ValType::F64));
+                            Slot::Float
+                        }
+                        VarType::Bool => {
+                            self.globals.push((name.clone(), ValType::I64));
+                            Slot::Bool
+                        }
+                        VarType::String => self.reserve(STR_SIZE),
+                        VarType::Char { size } => self.reserve(*size as u32 + 1),
+                        // Rejected by check_support
+                        VarType::Array(_) | VarType::Map(..) | VarType::Record(_) => continue,
+                    };
+                    self.slots.insert(name.clone(), slot);
+                }
+                ASTNode::If { body, .. } => self.allocate(body),
+                _ => {}
+            }
+        }
+    }
+
+    fn reserve(&mut self, size: u32) -> Slot {
+        let addr = self.next_addr;
+        self.next_addr += size;
+        Slot::Buffer { addr, size }
+    }
+
+    /// Places a NUL-terminated literal in the data section, returning its address and length.
+    fn literal(&mut self, text: &str) -> (i32, i32) {
+        let bytes = text.as_bytes().to_vec();
+        let len = bytes.len() as i32;
+        if let Some(addr) = self.literals.get(&bytes) {
+            return (*addr as i32, len);
+        }
+        let addr = self.next_addr;
+        let mut stored = bytes.clone();
+        stored.push(0);
+        self.next_addr += stored.len() as u32;
+        self.data.push((addr, stored));
+        self.literals.insert(bytes, addr);
+        (addr as i32, len)
+    }
+
+    fn emit(&mut self, instrs: impl IntoIterator<Item = Instr>) {
+        self.body.extend(instrs);
+    }
+
+    fn print_text(&mut self, fd: i32, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let (addr, len) = self.literal(text);
+        self.emit([Instr::I32Const(fd), Instr::I32Const(addr), Instr::I32Const(len), Instr::Call("write")]);
+    }
+
+    fn print_var(&mut self, fd: i32, name: &str) {
+        match self.slots.get(name).copied() {
+            Some(Slot::Int(IntType::U64)) => self.emit([
+                Instr::I32Const(fd),
+                Instr::GlobalGet(name.to_string()),
+                Instr::I32Const(1),
+                Instr::Call("print_digits"),
+            ]),
+            Some(Slot::Int(_)) => self.emit([Instr::I32Const(fd), Instr::GlobalGet(name.to_string()), Instr::Call("print_i64")]),
+            Some(Slot::Float) => {
+                self.floats = true;
+                self.emit([Instr::I32Const(fd), Instr::GlobalGet(name.to_string()), Instr::Call("print_f64")])
+            }
+            Some(Slot::Bool) => {
+                self.emit([Instr::GlobalGet(name.to_string()), Instr::Op("i64.eqz"), Instr::If]);
+                self.print_text(fd, "false");
+                self.emit([Instr::Else]);
+                self.print_text(fd, "true");
+                self.emit([Instr::End]);
+            }
+            Some(Slot::Buffer { addr, .. }) => {
+                self.emit([Instr::I32Const(fd), Instr::I32Const(addr as i32), Instr::Call("print_cstr")])
+            }
+            None => self.print_text(fd, "[undefined]"),
+        }
+    }
+
+    /// Prints the value `expr` evaluates to.
+    fn print_value(&mut self, fd: i32, expr: &Expression) {
+        if value_kind(expr, &self.var_types) == ValueKind::Bool {
+            self.emit([Instr::I32Const(fd)]);
+            self.gen_text(expr);
+            self.emit([Instr::Call("print_cstr")]);
+            return;
+        }
+        let kind = self.kind_of(expr);
+        self.emit([Instr::I32Const(fd)]);
+        self.gen_value(expr, kind);
+        self.floats |= kind == Kind::Float;
+        let print = match kind {
+            Kind::Int => "print_i64",
+            Kind::Float => "print_f64",
+            Kind::Str => "print_cstr",
+        };
+        self.emit([Instr::Call(print)]);
+    }
+
+    fn gen_node(&mut self, node: &ASTNode) {
+        if let Some(line) = node.line() {
+            self.line = line;
+        }
+        match node {
+            ASTNode::VarDeclaration { name, value, .. } => match self.slots.get(name).copied() {
+                Some(Slot::Int(int_type)) => {
+                    match value {
+                        Some(expr) => self.gen_int(name, int_type, expr),
+                        None => self.emit([Instr::I64Const(0)]),
+                    }
+                    self.emit([Instr::GlobalSet(name.clone())]);
+                }
+                Some(Slot::Bool) => {
+                    match value {
+                        Some(expr) => self.gen_value(expr, Kind::Int),
+                        None => self.emit([Instr::I64Const(0)]),
+                    }
+                    self.emit([Instr::GlobalSet(name.clone())]);
+                }
+                Some(Slot::Float) => {
+                    match value {
+                        Some(expr) => self.gen_value(expr, Kind::Float),
+                        None => self.emit([Instr::F64Const(0.0)]),
+                    }
+                    self.emit([Instr::GlobalSet(name.clone())]);
+                }
+                Some(Slot::Buffer { addr, size }) => match value {
+                    Some(expr) => {
+                        self.emit([Instr::I32Const(addr as i32)]);
+                        match expr {
+                            // Declaration literals arrive with their quotes already stripped
+                            Expression::Literal(lit) => {
+                                let text = literal_text(lit);
+                                let (lit_addr, _) = self.literal(&text);
+                                self.emit([Instr::I32Const(lit_addr)]);
+                            }
+                            _ => self.gen_value(expr, Kind::Str),
+                        }
+                        self.emit([Instr::I32Const(size as i32), Instr::Call("str_copy")]);
+                    }
+                    None => self.emit([Instr::I32Const(addr as i32), Instr::I32Const(0), Instr::Mem("i32.store8")]),
+                },
+                None => {}
+            },
+            ASTNode::Input { name, .. } => match self.slots.get(name).copied() {
+                // Cut to the buffer like any other text stored into it
+                Some(Slot::Buffer { addr, size }) => self.emit([
+                    Instr::I32Const(LINE_BUF),
+                    Instr::I32Const(LINE_BUF_SIZE - 1),
+                    Instr::Call("read_line"),
+                    Instr::Op("drop"),
+                    Instr::I32Const(addr as i32),
+                    Instr::I32Const(LINE_BUF),
+                    Instr::I32Const(size as i32),
+                    Instr::Call("str_copy"),
+                ]),
+                slot => {
+                    self.emit([
+                        Instr::I32Const(LINE_BUF),
+                        Instr::I32Const(LINE_BUF_SIZE - 1),
+                        Instr::Call("read_line"),
+                        Instr::Op("drop"),
+                    ]);
+                    match slot {
+                        Some(Slot::Int(int_type)) => {
+                            self.emit([
+                                Instr::I32Const(LINE_BUF),
+                                Instr::Call("parse_int"),
+                                Instr::I32Const(ABOVE_I64),
+                                Instr::Mem("i32.load"),
+                            ]);
+                            self.fit(name, int_type);
+                            self.emit([Instr::GlobalSet(name.clone())]);
+                        }
+                        Some(Slot::Float) => self.emit([
+                            Instr::I32Const(LINE_BUF),
+                            Instr::Call("parse_f64"),
+                            Instr::GlobalSet(name.clone()),
+                        ]),
+                        // True only for the line `true`
+                        Some(Slot::Bool) => {
+                            let (addr, _) = self.literal("true");
+                            self.emit([
+                                Instr::I32Const(LINE_BUF),
+                                Instr::I32Const(addr),
+                                Instr::Call("str_cmp"),
+                                Instr::Op("i32.eqz"),
+                                Instr::Op("i64.extend_i32_u"),
+                                Instr::GlobalSet(name.clone()),
+                            ]);
+                        }
+                        // Undeclared target: the line is consumed and discarded
+                        _ => {}
+                    }
+                }
+            },
+            ASTNode::Print { to_stderr, expr, .. } => {
+                let fd = if *to_stderr { 2 } else { 1 };
+                match expr {
+                    Some(Expression::Literal(text)) => {
+                        for segment in split_interpolation(text) {
+                            match segment {
+                                Segment::Text(text) => self.print_text(fd, &text),
+                                Segment::Var(name) if self.slots.contains_key(&name) => self.print_var(fd, &name),
+                                Segment::Var(name) => match placeholder(&name, &self.var_types) {
+                                    Some(expr) => self.print_value(fd, &expr),
+                                    None => self.print_text(fd, "[undefined]"),
+                                },
+                                Segment::Formatted(name, spec) => match formatted(&name, &self.var_types) {
+                                    Some(expr) => {
+                                        let kind = value_kind(&expr, &self.var_types);
+                                        self.conversions = true;
+                                        self.emit([Instr::I32Const(fd)]);
+                                        match (kind, spec.precision) {
+                                            (ValueKind::Float, Some(precision)) => {
+                                                self.gen_value(&expr, Kind::Float);
+                                                self.emit([Instr::I32Const(precision as i32), Instr::Call("fixed_text")]);
+                                            }
+                                            _ => self.gen_text(&expr),
+                                        }
+                                        let right = spec.right(matches!(kind, ValueKind::Int | ValueKind::Float));
+                                        self.emit([
+                                            Instr::I32Const(spec.width as i32),
+                                            Instr::I32Const(right as i32),
+                                            Instr::Call("print_padded"),
+                                        ]);
+                                    }
+                                    None => self.print_text(fd, "[undefined]"),
+                                },
+                            }
+                        }
+                    }
+                    Some(Expression::Identifier(name)) => {
+                        if self.slots.contains_key(name) {
+                            self.print_var(fd, name);
+                        } else {
+                            // Bare words are printed as written
+                            for segment in split_interpolation(name) {
+                                if let Segment::Text(text) = segment {
+                                    self.print_text(fd, &text);
+                                }
+                            }
+                        }
+                    }
+                    Some(
+                        expr @ (Expression::Math { .. }
+                        | Expression::Convert { .. }
+                        | Expression::Text { .. }
+                        | Expression::Len(_)),
+                    ) => self.print_value(fd, expr),
+                    Some(cond) => {
+                        self.gen_condition(cond);
+                        self.emit([Instr::If]);
+                        self.print_text(fd, "true");
+                        self.emit([Instr::Else]);
+                        self.print_text(fd, "false");
+                        self.emit([Instr::End]);
+                    }
+                    None => {}
+                }
+                self.emit([Instr::I32Const(fd), Instr::I32Const(NEWLINE), Instr::I32Const(1), Instr::Call("write")]);
+            }
+            ASTNode::MathOp { name, operator, operand, line, .. } => match self.slots.get(name).copied() {
+                Some(Slot::Int(int_type)) if self.kind_of(operand) == Kind::Int => {
+                    // The operand is stored into the variable's type first, then the result is
+                    self.emit([Instr::GlobalGet(name.clone())]);
+                    self.gen_int(name, int_type, operand);
+                    match operator {
+                        MathOperator::Divide | MathOperator::Modulo => self.check_divisor(name, "divisor", *line),
+                        MathOperator::Power if int_type.signed && self.checks => {
+                            self.emit([Instr::GlobalGet(name.clone())]);
+                            self.check_divisor(name, "exponent", *line);
+                        }
+                        _ => {}
+                    }
+                    if int_type.bits == 64 {
+                        let helper = match (operator, int_type.signed) {
+                            (MathOperator::Add, true) => "add_i64",
+                            (MathOperator::Subtract, true) => "sub_i64",
+                            (MathOperator::Multiply, true) => "mul_i64",
+                            (MathOperator::Divide, true) => "div_i64",
+                            (MathOperator::Add, false) => "add_u64",
+                            (MathOperator::Subtract, false) => "sub_u64",
+                            (MathOperator::Multiply, false) => "mul_u64",
+                            // A u64 quotient always fits
+                            (MathOperator::Divide, false) => {
+                                self.emit([Instr::Op("i64.div_u"), Instr::GlobalSet(name.clone())]);
+                                return;
+                            }
+                            // So does a remainder, and i64::MIN % -1 is 0
+                            (MathOperator::Modulo, signed) => {
+                                let op = if signed { "i64.rem_s" } else { "i64.rem_u" };
+                                self.emit([Instr::Op(op), Instr::GlobalSet(name.clone())]);
+                                return;
+                            }
+                            (MathOperator::Power, _) => {
+                                self.pow_int(name, int_type);
+                                return;
+                            }
+                        };
+                        let (name_addr, type_addr) = self.names(name, int_type);
+                        self.emit([Instr::I32Const(name_addr), Instr::I32Const(type_addr), Instr::Call(helper)]);
+                    } else {
+                        // Narrower types are exact in 64 bits, though a u32 product needs the u64 range
+                        let op = match operator {
+                            MathOperator::Add => "i64.add",
+                            MathOperator::Subtract => "i64.sub",
+                            MathOperator::Multiply => "i64.mul",
+                            MathOperator::Divide => "i64.div_s",
+                            MathOperator::Modulo => "i64.rem_s",
+                            MathOperator::Power => {
+                                self.pow_int(name, int_type);
+                                return;
+                            }
+                        };
+                        let unsigned = *operator == MathOperator::Multiply && !int_type.signed;
+                        self.emit([Instr::Op(op), Instr::I32Const(unsigned as i32)]);
+                        self.fit(name, int_type);
+                    }
+                    self.emit([Instr::GlobalSet(name.clone())]);
+                }
+                // A float or text operand on an integer is applied in floating point
+                Some(Slot::Int(int_type)) => {
+                    self.gen_value(&Expression::Identifier(name.clone()), Kind::Float);
+                    self.gen_value(operand, Kind::Float);
+                    if matches!(operator, MathOperator::Divide | MathOperator::Modulo) {
+                        self.check_divisor(name, "divisor_f", *line);
+                    }
+                    self.float_math(operator);
+                    self.fit_float(name, int_type);
+                    self.emit([Instr::GlobalSet(name.clone())]);
+                }
+                Some(Slot::Float) => {
+                    self.emit([Instr::GlobalGet(name.clone())]);
+                    self.gen_value(operand, Kind::Float);
+                    if matches!(operator, MathOperator::Divide | MathOperator::Modulo) {
+                        self.check_divisor(name, "divisor_f", *line);
+                    }
+                    self.float_math(operator);
+                    self.emit([Instr::GlobalSet(name.clone())]);
+                }
+                // Arithmetic on string buffers has no meaning
+                _ => {}
+            },
+            ASTNode::If { condition, body, .. } => {
+                self.gen_condition(condition);
+                self.emit([Instr::If]);
+                for node in body {
+                    self.gen_node(node);
+                }
+                self.emit([Instr::End]);
+            }
+            // Rejected by check_support
+            ASTNode::Call { .. }
+            | ASTNode::SetIndex { .. }
+            | ASTNode::Append { .. }
+            | ASTNode::ForEach { .. }
+            | ASTNode::RecordDeclaration(_) => {}
+        }
+    }
+
+    fn kind_of(&self, expr: &Expression) -> Kind {
+        match expr {
+            Expression::Literal(lit) => {
+                if lit.starts_with('"') || lit.starts_with('\'') {
+                    return Kind::Str;
+                }
+                let number = clean_number(lit);
+                if number == "true" || number == "false" || int_literal(&number).is_some() {
+                    Kind::Int
+                } else if number.parse::<f64>().is_ok() {
+                    Kind::Float
+                } else {
+                    Kind::Str
+                }
+            }
+            Expression::Identifier(name) => match self.slots.get(name) {
+                Some(Slot::Float) => Kind::Float,
+                Some(Slot::Buffer { .. }) => Kind::Str,
+                _ => Kind::Int,
+            },
+            Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) => Kind::Int,
+            Expression::Math { op, args } => {
+                if *op == MathFn::Sqrt || args.iter().any(|arg| self.kind_of(arg) == Kind::Float) {
+                    Kind::Float
+                } else {
+                    Kind::Int
+                }
+            }
+            Expression::Convert { to: VarType::Float, .. } => Kind::Float,
+            Expression::Convert { to: VarType::String, .. } => Kind::Str,
+            Expression::Convert { .. } => Kind::Int,
+            Expression::Text { op, .. } if op.result() == VarType::String => Kind::Str,
+            Expression::Text { .. } | Expression::Len(_) => Kind::Int,
+            // Arrays, maps and records are rejected by check_support
+            Expression::List(_)
+            | Expression::Index { .. }
+            | Expression::In { .. }
+            | Expression::Field { .. }
+            | Expression::Record(_) => Kind::Int,
+        }
+    }
+
+    /// Pushes the value of `expr`, converted to `want`.
+    fn gen_value(&mut self, expr: &Expression, want: Kind) {
+        let have = self.kind_of(expr);
+        match expr {
+            Expression::Literal(lit) => match have {
+                Kind::Int => {
+                    let value = match clean_number(lit).as_str() {
+                        "true" => 1,
+                        "false" => 0,
+                        // A u64 keeps its bits
+                        number => int_literal(number).unwrap_or(0) as i64,
+                    };
+                    self.emit([Instr::I64Const(value)]);
+                }
+                Kind::Float => {
+                    let value = clean_number(lit).parse::<f64>().unwrap_or(0.0);
+                    self.emit([Instr::F64Const(value)]);
+                }
+                Kind::Str => {
+                    let (addr, _) = self.literal(&literal_text(lit));
+                    self.emit([Instr::I32Const(addr)]);
+                }
+            },
+            Expression::Identifier(name) => match self.slots.get(name).copied() {
+                Some(Slot::Buffer { addr, .. }) => self.emit([Instr::I32Const(addr as i32)]),
+                Some(_) => self.emit([Instr::GlobalGet(name.clone())]),
+                None => self.emit([Instr::I64Const(0)]),
+            },
+            Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) => {
+                self.gen_condition(expr);
+                self.emit([Instr::Op("i64.extend_i32_u")]);
+            }
+            Expression::Math { op, args } => self.gen_math(*op, args, have),
+            Expression::Convert { to, value } => self.gen_convert(to, value),
+            Expression::Len(name) if let Some(Slot::Buffer { addr, .. }) = self.slots.get(name).copied() => {
+                self.strings = true;
+                self.emit([Instr::I32Const(addr as i32), Instr::Call("str_chars")]);
+            }
+            Expression::Text { op, args } => self.gen_text_op(*op, args),
+            // Arrays, maps and records are rejected by check_support
+            Expression::List(_)
+            | Expression::Index { .. }
+            | Expression::Len(_)
+            | Expression::In { .. }
+            | Expression::Field { .. }
+            | Expression::Record(_) => self.emit([Instr::I64Const(0)]),
+        }
+
+        match (have, want) {
+            (Kind::Int, Kind::Float) if self.is_unsigned(expr) => self.emit([Instr::Op("f64.convert_i64_u")]),
+            (Kind::Int, Kind::Float) => self.emit([Instr::Op("f64.convert_i64_s")]),
+            (Kind::Float, Kind::Int) => self.emit([Instr::Op("i64.trunc_f64_s")]),
+            (Kind::Str, Kind::Int) => self.emit([Instr::Call("parse_int")]),
+            (Kind::Str, Kind::Float) => self.emit([Instr::Call("parse_f64")]),
+            (Kind::Int | Kind::Float, Kind::Str) => {
+                let (addr, _) = self.literal("");
+                self.emit([Instr::Op("drop"), Instr::I32Const(addr)]);
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies `operator` to the two floats on the stack.
+    /// Calls the check `helper` on the operand on top of the stack, which it
+    /// leaves there, for math on `name` on `line`. `exponent` also takes the
+    /// variable's value, pushed after the operand.
+    /// Pushes `i(value)`, `f(value)` or `str(value)`; text that is not a
+    /// number stops the program.
+    fn gen_convert(&mut self, to: &VarType, value: &Expression) {
+        self.conversions = true;
+        let kind = value_kind(value, &self.var_types);
+        match (to, kind) {
+            (VarType::String, ValueKind::Str) => self.gen_value(value, Kind::Str),
+            // Into a buffer of its own, as the text of a number is only kept until the next one
+            (VarType::String, _) => {
+                let Slot::Buffer { addr, size } = self.reserve(FLOAT_TEXT_SIZE) else { unreachable!() };
+                self.emit([Instr::I32Const(addr as i32)]);
+                self.gen_text(value);
+                self.emit([Instr::I32Const(size as i32), Instr::Call("str_copy"), Instr::I32Const(addr as i32)]);
+            }
+            (VarType::Float, ValueKind::Str) => {
+                self.gen_value(value, Kind::Str);
+                self.mark_line();
+                self.emit([Instr::Call("convert_float")]);
+            }
+            (VarType::Float, _) => self.gen_value(value, Kind::Float),
+            (_, ValueKind::Str) => {
+                self.gen_value(value, Kind::Str);
+                self.mark_line();
+                self.emit([Instr::Call("convert_int")]);
+            }
+            (_, ValueKind::Float) => {
+                self.gen_value(value, Kind::Float);
+                self.emit([Instr::Call("float_to_int")]);
+            }
+            _ => self.gen_value(value, Kind::Int),
+        }
+    }
+
+    /// Pushes the result of the string operation `op`: the address of a buffer
+    /// of its own for text, the `i64` length or a 0 or 1 for the tests.
+    fn gen_text_op(&mut self, op: TextOp, args: &[Expression]) {
+        self.strings = true;
+        if op.result() == VarType::String {
+            let Slot::Buffer { addr, .. } = self.reserve(STR_SIZE) else { unreachable!() };
+            let addr = addr as i32;
+            match op {
+                // Each text is appended to the length so far
+                TextOp::Concat => {
+                    self.emit([Instr::I32Const(0)]);
+                    for arg in args {
+                        self.gen_value(arg, Kind::Str);
+                        self.emit([Instr::I32Const(addr), Instr::Call("str_cat")]);
+                    }
+                    self.emit([Instr::Op("drop"), Instr::I32Const(addr)]);
+                }
+                TextOp::Slice => {
+                    self.emit([Instr::I32Const(addr)]);
+                    self.gen_value(&args[0], Kind::Str);
+                    self.gen_value(&args[1], Kind::Int);
+                    self.gen_value(&args[2], Kind::Int);
+                    self.emit([Instr::Call("str_slice")]);
+                }
+                TextOp::Upper | TextOp::Lower => {
+                    let (first, delta) = if op == TextOp::Upper { (b'a', -32) } else { (b'A', 32) };
+                    self.emit([Instr::I32Const(addr)]);
+                    self.gen_value(&args[0], Kind::Str);
+                    self.emit([Instr::I32Const(first as i32), Instr::I32Const(delta), Instr::Call("str_case")]);
+                }
+                TextOp::Trim => {
+                    self.emit([Instr::I32Const(addr)]);
+                    self.gen_value(&args[0], Kind::Str);
+                    self.emit([Instr::Call("str_trim")]);
+                }
+                _ => {
+                    self.emit([Instr::I32Const(addr)]);
+                    for arg in args {
+                        self.gen_value(arg, Kind::Str);
+                    }
+                    self.emit([Instr::Call("str_replace")]);
+                }
+            }
+            return;
+        }
+        for arg in args {
+            self.gen_value(arg, Kind::Str);
+        }
+        match op {
+            TextOp::Len => self.emit([Instr::Call("str_chars")]),
+            TextOp::Contains => {
+                self.emit([Instr::Call("str_find"), Instr::I32Const(0), Instr::Op("i32.ne"), Instr::Op("i64.extend_i32_u")])
+            }
+            _ => self.emit([Instr::Call("str_prefix"), Instr::Op("i64.extend_i32_u")]),
+        }
+    }
+
+    /// Pushes the address of the text of `expr`, which for a number lasts
+    /// until the text of the next one is made.
+    fn gen_text(&mut self, expr: &Expression) {
+        match value_kind(expr, &self.var_types) {
+            ValueKind::Int => {
+                self.conversions = true;
+                let unsigned = self.is_unsigned(expr);
+                self.emit([Instr::I32Const(FLOAT_TEXT)]);
+                self.gen_value(expr, Kind::Int);
+                self.emit([Instr::I32Const(unsigned as i32), Instr::Call("int_text")]);
+            }
+            ValueKind::Float => {
+                self.floats = true;
+                self.gen_value(expr, Kind::Float);
+                self.emit([Instr::Call("float_text")]);
+            }
+            ValueKind::Str => self.gen_value(expr, Kind::Str),
+            ValueKind::Bool => {
+                let (no, _) = self.literal("false");
+                let (yes, _) = self.literal("true");
+                self.emit([Instr::I32Const(no), Instr::I32Const(yes)]);
+                self.gen_value(expr, Kind::Int);
+                self.emit([Instr::Op("i64.eqz"), Instr::Op("select")]);
+            }
+        }
+    }
+
+    fn check_divisor(&mut self, name: &str, helper: &'static str, line: usize) {
+        if !self.checks {
+            return;
+        }
+        self.division = true;
+        let name_addr = self.literal(name).0;
+        self.emit([Instr::I32Const(name_addr), Instr::I64Const(line as i64), Instr::Call(helper)]);
+    }
+
+    fn float_math(&mut self, operator: &MathOperator) {
+        let instr = match operator {
+            MathOperator::Add => Instr::Op("f64.add"),
+            MathOperator::Subtract => Instr::Op("f64.sub"),
+            MathOperator::Multiply => Instr::Op("f64.mul"),
+            MathOperator::Divide => Instr::Op("f64.div"),
+            MathOperator::Modulo => Instr::Call("fmod"),
+            MathOperator::Power => Instr::Call("fpow"),
+        };
+        self.math |= matches!(instr, Instr::Call(_));
+        self.emit([instr]);
+    }
+
+    /// Stores the integer variable `name` raised to the power on the stack, both
+    /// of its type, into it.
+    fn pow_int(&mut self, name: &str, int_type: IntType) {
+        let (name_addr, type_addr) = self.names(name, int_type);
+        self.math = true;
+        self.emit([
+            Instr::I32Const(!int_type.signed as i32),
+            Instr::I64Const(int_type.min() as i64),
+            Instr::I64Const(int_type.max() as i64),
+            Instr::I32Const(name_addr),
+            Instr::I32Const(type_addr),
+            Instr::Call("pow_int"),
+            Instr::GlobalSet(name.to_string()),
+        ]);
+    }
+
+    /// Pushes the result of the math function `op`, of kind `kind`. Integer
+    /// arguments are taken as `i64`s, like the result.
+    fn gen_math(&mut self, op: MathFn, args: &[Expression], kind: Kind) {
+        self.math = true;
+        if kind == Kind::Float {
+            for (i, arg) in args.iter().enumerate() {
+                self.gen_value(arg, Kind::Float);
+                match op {
+                    MathFn::Min if i > 0 => self.emit([Instr::Call("min_f64")]),
+                    MathFn::Max if i > 0 => self.emit([Instr::Call("max_f64")]),
+                    _ => {}
+                }
+            }
+            let instr = match op {
+                MathFn::Negate => Instr::Op("f64.neg"),
+                MathFn::Abs => Instr::Op("f64.abs"),
+                MathFn::Sqrt => Instr::Op("f64.sqrt"),
+                MathFn::Floor => Instr::Op("f64.floor"),
+                MathFn::Ceil => Instr::Op("f64.ceil"),
+                MathFn::Round => Instr::Call("round_f64"),
+                MathFn::Min | MathFn::Max => return,
+            };
+            self.emit([instr]);
+            return;
+        }
+
+        let called = op.called();
+        if op == MathFn::Negate {
+            self.emit([Instr::I64Const(0)]);
+        }
+        for (i, arg) in args.iter().enumerate() {
+            self.gen_value(arg, Kind::Int);
+            if self.is_unsigned(arg) {
+                self.emit([Instr::I32Const(1)]);
+                self.fit(called, IntType::I64);
+            }
+            match op {
+                MathFn::Min if i > 0 => self.emit([Instr::Call("min_i64")]),
+                MathFn::Max if i > 0 => self.emit([Instr::Call("max_i64")]),
+                _ => {}
+            }
+        }
+        // A whole number is its own floor, ceiling and rounding
+        let helper = match op {
+            MathFn::Negate => "sub_i64",
+            MathFn::Abs => "abs_i64",
+            _ => return,
+        };
+        let (name_addr, type_addr) = self.names(called, IntType::I64);
+        self.emit([Instr::I32Const(name_addr), Instr::I32Const(type_addr), Instr::Call(helper)]);
+    }
+
+    /// Whether `expr` is an integer whose bits are read as a `u64`.
+    fn is_unsigned(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::Identifier(name) => matches!(self.slots.get(name), Some(Slot::Int(IntType::U64))),
+            Expression::Literal(lit) => int_literal(&clean_number(lit)).is_some_and(|value| value > i64::MAX as i128),
+            _ => false,
+        }
+    }
+
+    /// Addresses of the name and type of the integer variable `name`, for the trap message.
+    fn names(&mut self, name: &str, int_type: IntType) -> (i32, i32) {
+        if self.overflow == Overflow::Trap {
+            self.mark_line();
+        }
+        (self.literal(name).0, self.literal(&int_type.to_string()).0)
+    }
+
+    /// Sets the `ven.line` global to the statement's line, for a runtime error
+    /// the next call may report.
+    fn mark_line(&mut self) {
+        self.emit([Instr::I64Const(self.line as i64), Instr::GlobalSet(LINE_GLOBAL.to_string())]);
+    }
+
+    /// Stores an `i64` and an `i32` that says whether it is read as a `u64` into
+    /// the integer variable `name`.
+    fn fit(&mut self, name: &str, int_type: IntType) {
+        let (name_addr, type_addr) = self.names(name, int_type);
+        self.emit([
+            Instr::I64Const(int_type.min() as i64),
+            Instr::I64Const(int_type.max() as i64),
+            Instr::I32Const(name_addr),
+            Instr::I32Const(type_addr),
+            Instr::Call("fit"),
+        ]);
+    }
+
+    fn fit_float(&mut self, name: &str, int_type: IntType) {
+        let (name_addr, type_addr) = self.names(name, int_type);
+        self.emit([
+            Instr::F64Const(int_type.min() as f64),
+            Instr::F64Const((int_type.max() + 1) as f64),
+            Instr::I64Const(int_type.min() as i64),
+            Instr::I64Const(int_type.max() as i64),
+            Instr::I32Const(name_addr),
+            Instr::I32Const(type_addr),
+            Instr::Call("fit_float"),
+        ]);
+    }
+
+    /// Pushes `expr` converted for a store into the integer variable `name`.
+    /// Checks are left out where the value always fits.
+    fn gen_int(&mut self, name: &str, int_type: IntType, expr: &Expression) {
+        match (expr, self.kind_of(expr)) {
+            (Expression::Literal(lit), Kind::Int) => {
+                let literal = match clean_number(lit).as_str() {
+                    "true" => 1,
+                    "false" => 0,
+                    number => int_literal(number).unwrap_or(0),
+                };
+                match fit(literal, int_type, self.overflow) {
+                    Some(fitted) => self.emit([Instr::I64Const(fitted as i64)]),
+                    None => {
+                        self.emit([Instr::I64Const(literal as i64), Instr::I32Const((literal > i64::MAX as i128) as i32)]);
+                        self.fit(name, int_type);
+                    }
+                }
+            }
+            (Expression::Identifier(other), Kind::Int) => match self.slots.get(other).copied() {
+                Some(Slot::Int(other_type)) if !int_type.holds(other_type) => {
+                    self.emit([Instr::GlobalGet(other.clone()), Instr::I32Const((other_type == IntType::U64) as i32)]);
+                    self.fit(name, int_type);
+                }
+                _ => self.gen_value(expr, Kind::Int),
+            },
+            // A length or a math result is an i64, and an integer converts to itself
+            (Expression::Len(_) | Expression::Text { .. } | Expression::Math { .. } | Expression::Convert { .. }, Kind::Int) => {
+                let from = int_type_of(expr, &self.var_types).unwrap_or(IntType::I64);
+                self.gen_value(expr, Kind::Int);
+                if !int_type.holds(from) {
+                    self.emit([Instr::I32Const((from == IntType::U64) as i32)]);
+                    self.fit(name, int_type);
+                }
+            }
+            (_, Kind::Float) => {
+                self.gen_value(expr, Kind::Float);
+                self.fit_float(name, int_type);
+            }
+            (_, Kind::Str) => {
+                self.gen_value(expr, Kind::Int);
+                self.emit([Instr::I32Const(ABOVE_I64), Instr::Mem("i32.load")]);
+                self.fit(name, int_type);
+            }
+            _ => self.gen_value(expr, Kind::Int),
+        }
+    }
+
+    /// Pushes an `i32` truth value for `expr`.
+    fn gen_condition(&mut self, expr: &Expression) {
+        match expr {
+            Expression::BinaryOp { left, operator, right } => {
+                let (lk, rk) = (self.kind_of(left), self.kind_of(right));
+                if lk == Kind::Str || rk == Kind::Str {
+                    if lk != rk {
+                        // A string never equals a number
+                        let differs = *operator == ComparisonOperator::NotEqual;
+                        self.emit([Instr::I32Const(differs as i32)]);
+                        return;
+                    }
+                    self.gen_value(left, Kind::Str);
+                    self.gen_value(right, Kind::Str);
+                    self.emit([Instr::Call("str_cmp"), Instr::I32Const(0)]);
+                    let op = match operator {
+                        ComparisonOperator::Equal => "i32.eq",
+                        ComparisonOperator::NotEqual => "i32.ne",
+                        ComparisonOperator::LessThan => "i32.lt_s",
+                        ComparisonOperator::LessThanEqual => "i32.le_s",
+                        ComparisonOperator::GreaterThan => "i32.gt_s",
+                        ComparisonOperator::GreaterThanEqual => "i32.ge_s",
+                    };
+                    self.emit([Instr::Op(op)]);
+                } else if lk == Kind::Float || rk == Kind::Float {
+                    self.gen_value(left, Kind::Float);
+                    self.gen_value(right, Kind::Float);
+                    let op = match operator {
+                        ComparisonOperator::Equal => "f64.eq",
+                        ComparisonOperator::NotEqual => "f64.ne",
+                        ComparisonOperator::LessThan => "f64.lt",
+                        ComparisonOperator::LessThanEqual => "f64.le",
+                        ComparisonOperator::GreaterThan => "f64.gt",
+                        ComparisonOperator::GreaterThanEqual => "f64.ge",
+                    };
+                    self.emit([Instr::Op(op)]);
+                } else if self.is_unsigned(left) || self.is_unsigned(right) {
+                    let (left_unsigned, right_unsigned) = (self.is_unsigned(left), self.is_unsigned(right));
+                    self.gen_value(left, Kind::Int);
+                    self.emit([Instr::I32Const(left_unsigned as i32)]);
+                    self.gen_value(right, Kind::Int);
+                    self.emit([Instr::I32Const(right_unsigned as i32), Instr::Call("compare"), Instr::I32Const(0)]);
+                    let op = match operator {
+                        ComparisonOperator::Equal => "i32.eq",
+                        ComparisonOperator::NotEqual => "i32.ne",
+                        ComparisonOperator::LessThan => "i32.lt_s",
+                        ComparisonOperator::LessThanEqual => "i32.le_s",
+                        ComparisonOperator::GreaterThan => "i32.gt_s",
+                        ComparisonOperator::GreaterThanEqual => "i32.ge_s",
+                    };
+                    self.emit([Instr::Op(op)]);
+                } else {
+                    self.gen_value(left, Kind::Int);
+                    self.gen_value(right, Kind::Int);
+                    let op = match operator {
+                        ComparisonOperator::Equal => "i64.eq",
+                        ComparisonOperator::NotEqual => "i64.ne",
+                        ComparisonOperator::LessThan => "i64.lt_s",
+                        ComparisonOperator::LessThanEqual => "i64.le_s",
+                        ComparisonOperator::GreaterThan => "i64.gt_s",
+                        ComparisonOperator::GreaterThanEqual => "i64.ge_s",
+                    };
+                    self.emit([Instr::Op(op)]);
+                }
+            }
+            Expression::LogicalOp { left, operator, right } => {
+                self.gen_condition(left);
+                self.gen_condition(right);
+                let op = match operator {
+                    LogicalOperator::And => "i32.and",
+                    LogicalOperator::Or => "i32.or",
+                };
+                self.emit([Instr::Op(op)]);
+            }
+            Expression::Not(operand) => {
+                self.gen_condition(operand);
+                self.emit([Instr::Op("i32.eqz")]);
+            }
+            _ => match self.kind_of(expr) {
+                Kind::Int => {
+                    self.gen_value(expr, Kind::Int);
+                    self.emit([Instr::Op("i64.eqz"), Instr::Op("i32.eqz")]);
+                }
+                Kind::Float => {
+                    self.gen_value(expr, Kind::Float);
+                    self.emit([Instr::F64Const(0.0), Instr::Op("f64.ne")]);
+                }
+                // Non-empty strings are truthy
+                Kind::Str => {
+                    self.gen_value(expr, Kind::Str);
+                    self.emit([Instr::Mem("i32.load8_u"), Instr::I32Const(0), Instr::Op("i32.ne")]);
+                }
+            },
+        }
+    }
+
+    fn finish(mut self) -> Module {
+        let mut funcs = runtime_funcs();
+        funcs.extend(overflow_funcs(self.overflow));
+        if self.math {
+            funcs.extend(math_funcs(self.overflow));
+        }
+        if self.conversions {
+            let message = [
+                self.literal("runtime error: cannot convert \""),
+                self.literal("\" to i on line "),
+                self.literal("\" to f on line "),
+            ];
+            funcs.extend(conversion_funcs(message));
+        }
+        if self.floats || self.conversions {
+            funcs.extend(float_funcs());
+        }
+        if self.strings {
+            funcs.extend(string_funcs());
+        }
+        if self.conversions || self.strings {
+            funcs.push(blank_func());
+        }
+        let mut imports = vec![
+            Import {
+                field: "fd_write",
+                params: vec![ValType::I32; 4],
+                result: Some(ValType::I32),
+            },
+            Import {
+                field: "fd_read",
+                params: vec![ValType::I32; 4],
+                result: Some(ValType::I32),
+            },
+        ];
+        if self.division {
+            let message = [self.literal("runtime error: division of '"), self.literal("' by zero on line ")];
+            funcs.extend(division_funcs(message));
+        }
+        if self.overflow == Overflow::Trap {
+            let message = [
+                self.literal("runtime error: integer overflow in '"),
+                self.literal("' ("),
+                self.literal(") on line "),
+            ];
+            funcs.push(overflow_report(message));
+        }
+        if self.overflow == Overflow::Trap || self.conversions {
+            self.globals.push((LINE_GLOBAL.to_string(), ValType::I64));
+        }
+        if self.overflow == Overflow::Trap || self.division || self.conversions {
+            imports.push(Import {
+                field: "proc_exit",
+                params: vec![ValType::I32],
+                result: None,
+            });
+        }
+        funcs.push(Func {
+            name: "_start",
+            export: true,
+            params: vec![],
+            result: None,
+            locals: vec![],
+            body: self.body,
+        });
+        Module {
+            imports,
+            globals: self.globals,
+            funcs,
+            data: self.data,
+            pages: self.next_addr.div_ceil(65536).max(1),
+        }
+    }
+}
+
+fn build_module(ast: &AST, options: &CodegenOptions) -> Module {
+    let AST::Program(nodes) = ast;
+    let var_types = collect_var_types(ast).into_iter().collect();
+    let mut codegen = Codegen::new(nodes, options, var_types);
+    for node in nodes {
+        codegen.gen_node(node);
+    }
+    codegen.finish()
+}
+
+/// Transpiles the AST to a WASI module in WebAssembly text format.
+pub fn transpile_wasm(ast: &AST, options: &CodegenOptions) -> String {
+    build_module(ast, options).to_wat()
+}
+
+/// Transpiles the AST to a binary WASI module, equivalent to [`transpile_wasm`].
+pub fn transpile_wasm_binary(ast: &AST, options: &CodegenOptions) -> Vec<u8> {
+    build_module(ast, options).encode()
+}
+
+pub struct WasmBackend;
+
+impl Backend for WasmBackend {
+    fn name(&self) -> &'static str {
+        "wasm"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["wat"]
+    }
+
+    fn description(&self) -> &'static str {
+        "WebAssembly text"
+    }
+
+    fn extension(&self) -> &'static str {
+        "wat"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        // Memory holds fixed-size variables only
+        Capabilities {
+            arrays: false,
+            maps: false,
+            records: false,
+            ..Capabilities::ALL
+        }
+    }
+
+    fn emit(&self, ast: &AST, options: &CodegenOptions) -> Result<String, BackendError> {
+        Ok(transpile_wasm(ast, options))
+    }
+
+    fn emit_binary(&self, ast: &AST, options: &CodegenOptions) -> Option<(&'static str, Vec<u8>)> {
+        Some(("wasm", transpile_wasm_binary(ast, options)))
+    }
+}
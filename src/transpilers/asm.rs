@@ -0,0 +1,1933 @@
+//! Shared code generator for the Linux assembly targets.
+//!
+//! The AST is lowered once into a tiny register-machine IR (`Inst`), together with
+//! the runtime routines every program needs: integer formatting, line reading,
+//! number parsing and string comparison, and the conversions, string
+//! operations and math functions when a program uses them. Each architecture only maps registers,
+//! instructions, syscall numbers and data directives onto its own assembler
+//! dialect through `AsmTarget`.
+//!
+//! Values are 64-bit integers, with booleans as 0 and 1, or pointers to
+//! NUL-terminated buffers; `f` is not supported on these targets. Every integer
+//! type is held in 64 bits, a `u64` keeping its bits, and math that can leave
+//! the type goes through the `ven_fit` and `ven_*_i64`/`ven_*_u64` routines.
+
+use crate::errs::BackendError;
+use crate::parse::{
+    AST, ASTNode, ComparisonOperator, Expression, IntType, LogicalOperator, MathFn, MathOperator, TextOp, VarType,
+};
+use crate::transpilers::{
+    CodegenOptions, Overflow, Segment, ValueKind, clean_number, collect_var_types, fit, fit_float, formatted,
+    int_literal, int_type_of, is_text_var, literal_text, placeholder, split_interpolation, value_kind,
+};
+use std::collections::HashMap;
+
+/// Size of every string buffer, including the terminating NUL.
+pub const STR_SIZE: usize = 256;
+/// Scratch space used by integer formatting.
+const NUM_BUF_SIZE: i64 = 32;
+
+/// Virtual registers. Syscall and routine arguments go in `R0`, `R1`, `R2`
+/// and results come back in `R0`; calls may clobber all of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reg {
+    R0,
+    R1,
+    R2,
+    R3,
+    R4,
+    R5,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    /// Signed division truncating toward zero.
+    Div,
+    /// Remainder of `Div`, with the sign of the dividend.
+    Rem,
+    /// Unsigned division.
+    UDiv,
+    /// Remainder of `UDiv`.
+    URem,
+}
+
+/// Branch conditions, tested against the last `Cmp`/`CmpImm`. `Below` to
+/// `AboveEq` compare unsigned, the others signed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cond {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Below,
+    BelowEq,
+    Above,
+    AboveEq,
+}
+
+impl Cond {
+    pub fn inverse(self) -> Cond {
+        match self {
+            Cond::Eq => Cond::Ne,
+            Cond::Ne => Cond::Eq,
+            Cond::Lt => Cond::Ge,
+            Cond::Le => Cond::Gt,
+            Cond::Gt => Cond::Le,
+            Cond::Ge => Cond::Lt,
+            Cond::Below => Cond::AboveEq,
+            Cond::BelowEq => Cond::Above,
+            Cond::Above => Cond::BelowEq,
+            Cond::AboveEq => Cond::Below,
+        }
+    }
+}
+
+/// The Linux syscalls the generated programs use.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Syscall {
+    /// `read(R0 = fd, R1 = buf, R2 = len)`, result in `R0`.
+    Read,
+    /// `write(R0 = fd, R1 = buf, R2 = len)`, result in `R0`.
+    Write,
+    /// `exit(R0 = status)`.
+    Exit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inst {
+    Label(String),
+    Comment(String),
+    /// Load an immediate.
+    Imm(Reg, i64),
+    /// Load the address of a label.
+    Addr(Reg, String),
+    /// Load the 64-bit value stored at a label.
+    Load(Reg, String),
+    /// Store a 64-bit value at a label.
+    Store(String, Reg),
+    /// `dst = *(u8 *)addr`.
+    LoadByte(Reg, Reg),
+    /// `*(u8 *)addr = src`.
+    StoreByte(Reg, Reg),
+    Mov(Reg, Reg),
+    Arith(ArithOp, Reg, Reg),
+    AddImm(Reg, i64),
+    Cmp(Reg, Reg),
+    CmpImm(Reg, i64),
+    Jump(String),
+    JumpIf(Cond, String),
+    Call(&'static str),
+    Ret,
+    Syscall(Syscall),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Section {
+    Data,
+    Bss,
+    Text,
+}
+
+/// One architecture and assembler dialect.
+pub trait AsmTarget {
+    /// Line comment marker.
+    fn comment(&self) -> &'static str;
+    /// Linux syscall number on this architecture.
+    fn syscall_number(&self, call: Syscall) -> u32;
+    /// Directives emitted before the first section.
+    fn prelude(&self) -> String;
+    fn section(&self, section: Section) -> String;
+    /// Declares `_start` as the global entry point.
+    fn entry(&self) -> String;
+    /// A labelled run of initialized bytes.
+    fn bytes(&self, label: &str, bytes: &[u8]) -> String;
+    /// A labelled, 8-byte aligned block of zeroed memory.
+    fn reserve(&self, label: &str, size: usize) -> String;
+    /// Appends the instructions for `inst` to `out`.
+    fn lower(&self, inst: &Inst, out: &mut String);
+}
+
+/// Lowers a whole program for `target`; `name` is the backend reported in errors.
+pub fn emit_program(
+    target: &dyn AsmTarget,
+    name: &'static str,
+    ast: &AST,
+    options: &CodegenOptions,
+) -> Result<String, BackendError> {
+    let mut codegen = Codegen::new(ast, options.overflow, options.runtime_checks);
+    let AST::Program(nodes) = ast;
+    codegen
+        .gen_nodes(nodes)
+        .map_err(|construct| BackendError::Unsupported { target: name, construct })?;
+    codegen.code.push(Inst::Comment("exit(0)".to_string()));
+    codegen.code.push(Inst::Imm(Reg::R0, 0));
+    codegen.code.push(Inst::Syscall(Syscall::Exit));
+
+    let c = target.comment();
+    let mut out = format!("{} Generated by the Ven compiler\n", c);
+    out.push_str(&target.prelude());
+
+    out.push('\n');
+    out.push_str(&target.section(Section::Data));
+    out.push_str(&target.bytes("newline", b"\n"));
+    out.push_str(&target.bytes("lit_true", b"true"));
+    out.push_str(&target.bytes("lit_false", b"false"));
+    out.push_str(&target.bytes("lit_undefined", b"[undefined]"));
+    if options.overflow == Overflow::Trap {
+        out.push_str(&target.bytes("lit_overflow", OVERFLOW_MESSAGE[0].as_bytes()));
+        out.push_str(&target.bytes("lit_overflow_type", OVERFLOW_MESSAGE[1].as_bytes()));
+        out.push_str(&target.bytes("lit_overflow_end", OVERFLOW_MESSAGE[2].as_bytes()));
+    }
+    if codegen.division {
+        out.push_str(&target.bytes("lit_div_zero", DIVISION_MESSAGE[0].as_bytes()));
+        out.push_str(&target.bytes("lit_div_zero_line", DIVISION_MESSAGE[1].as_bytes()));
+    }
+    if codegen.conversions {
+        out.push_str(&target.bytes("lit_space", b" "));
+        out.push_str(&target.bytes("lit_convert", CONVERSION_MESSAGE[0].as_bytes()));
+        out.push_str(&target.bytes("lit_convert_end", CONVERSION_MESSAGE[1].as_bytes()));
+    }
+    for (label, bytes) in &codegen.data {
+        out.push_str(&target.bytes(label, bytes));
+    }
+
+    out.push('\n');
+    out.push_str(&target.section(Section::Bss));
+    out.push_str(&target.reserve("num_buf", NUM_BUF_SIZE as usize));
+    out.push_str(&target.reserve("line_buf", STR_SIZE));
+    for (name, var_type) in &codegen.var_order {
+        let size = match var_type {
+            // Arrays, maps, records and floats are rejected by check_support
+            VarType::Int(_)
+            | VarType::Float
+            | VarType::Bool
+            | VarType::Array(_)
+            | VarType::Map(..)
+            | VarType::Record(_) => 8,
+            VarType::String => STR_SIZE,
+            VarType::Char { size } => size + 1,
+        };
+        out.push_str(&target.reserve(&var_label(name), size));
+    }
+    for label in &codegen.temps {
+        out.push_str(&target.reserve(label, 8));
+    }
+    for label in &codegen.buffers {
+        out.push_str(&target.reserve(label, STR_SIZE));
+    }
+    if codegen.strings {
+        for label in REPLACE_SCRATCH {
+            out.push_str(&target.reserve(label, 8));
+        }
+    }
+    if codegen.math {
+        out.push_str(&target.reserve(POW_SCRATCH, 8));
+    }
+    if codegen.conversions {
+        for label in CONVERSION_SCRATCH {
+            out.push_str(&target.reserve(label, 8));
+        }
+    }
+    if options.overflow == Overflow::Trap || codegen.conversions {
+        out.push_str(&target.reserve(LINE_SCRATCH, 8));
+    }
+
+    out.push('\n');
+    out.push_str(&target.section(Section::Text));
+    out.push_str(&target.entry());
+    out.push_str("_start:\n");
+    for inst in &codegen.code {
+        target.lower(inst, &mut out);
+    }
+    for inst in runtime() {
+        target.lower(&inst, &mut out);
+    }
+    if codegen.strings {
+        for inst in string_runtime() {
+            target.lower(&inst, &mut out);
+        }
+    }
+    if codegen.conversions {
+        for inst in conversion_runtime() {
+            target.lower(&inst, &mut out);
+        }
+    }
+    if codegen.math {
+        for inst in math_runtime(options.overflow) {
+            target.lower(&inst, &mut out);
+        }
+    }
+    for inst in overflow_runtime(options.overflow) {
+        target.lower(&inst, &mut out);
+    }
+    if codegen.division {
+        for inst in division_runtime() {
+            target.lower(&inst, &mut out);
+        }
+    }
+    Ok(out)
+}
+
+/// The parts of the `--overflow=trap` message around the variable's name and type,
+/// before its line.
+const OVERFLOW_MESSAGE: [&str; 3] = ["runtime error: integer overflow in '", "' (", ") on line "];
+
+/// The parts of the division by zero message before the variable's name and line.
+const DIVISION_MESSAGE: [&str; 2] = ["runtime error: division of '", "' by zero on line "];
+
+/// The parts of the message of a failed `i()` around the text, before its line.
+const CONVERSION_MESSAGE: [&str; 2] = ["runtime error: cannot convert \"", "\" to i on line "];
+
+/// Where the line of the statement that may stop the program is kept for its report.
+const LINE_SCRATCH: &str = "ven_line";
+
+/// Label of the storage for a Ven variable.
+fn var_label(name: &str) -> String {
+    format!("var_{}", name)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Int,
+    Str,
+}
+
+struct Codegen {
+    overflow: Overflow,
+    code: Vec<Inst>,
+    data: Vec<(String, Vec<u8>)>,
+    literals: HashMap<Vec<u8>, String>,
+    var_order: Vec<(String, VarType)>,
+    var_types: HashMap<String, VarType>,
+    temps: Vec<String>,
+    /// Buffers of `STR_SIZE` bytes that string operations write into, one per use.
+    buffers: Vec<String>,
+    /// Whether the string operation routines are called.
+    strings: bool,
+    /// Whether the math routines are called.
+    math: bool,
+    /// Whether the conversion routines are called.
+    conversions: bool,
+    /// Whether `/`, `%` and `**` check for division by zero.
+    checks: bool,
+    /// Whether `ven_div_zero` is called.
+    division: bool,
+    /// The source line of the statement being generated, named by runtime errors.
+    line: usize,
+    labels: usize,
+}
+
+impl Codegen {
+    fn new(ast: &AST, overflow: Overflow, checks: bool) -> Self {
+        let var_order = collect_var_types(ast);
+        let var_types = var_order.iter().cloned().collect();
+        Codegen {
+            overflow,
+            code: Vec::new(),
+            data: Vec::new(),
+            literals: HashMap::new(),
+            var_order,
+            var_types,
+            temps: Vec::new(),
+            buffers: Vec::new(),
+            strings: false,
+            math: false,
+            conversions: false,
+            checks,
+            division: false,
+            line: 0,
+            labels: 0,
+        }
+    }
+
+    fn emit(&mut self, inst: Inst) {
+        self.code.push(inst);
+    }
+
+    fn label(&mut self, hint: &str) -> String {
+        self.labels += 1;
+        format!("L{}_{}", self.labels, hint)
+    }
+
+    fn temp(&mut self) -> String {
+        let label = format!("tmp_{}", self.temps.len());
+        self.temps.push(label.clone());
+        label
+    }
+
+    /// A new buffer for the text a string operation builds.
+    fn buffer(&mut self) -> String {
+        let label = format!("text_{}", self.buffers.len());
+        self.buffers.push(label.clone());
+        label
+    }
+
+    /// Interns a NUL-terminated literal and returns its label.
+    fn literal(&mut self, text: &str) -> String {
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.push(0);
+        if let Some(label) = self.literals.get(&bytes) {
+            return label.clone();
+        }
+        let label = format!("str_{}", self.data.len());
+        self.literals.insert(bytes.clone(), label.clone());
+        self.data.push((label.clone(), bytes));
+        label
+    }
+
+    /// Generates code for `nodes`, or names the first construct that cannot be lowered.
+    fn gen_nodes(&mut self, nodes: &[ASTNode]) -> Result<(), String> {
+        for node in nodes {
+            if let Some(line) = node.line() {
+                self.line = line;
+            }
+            self.gen_node(node)?;
+        }
+        Ok(())
+    }
+
+    fn gen_node(&mut self, node: &ASTNode) -> Result<(), String> {
+        match node {
+            ASTNode::VarDeclaration { name, var_type, value, .. } => {
+                self.emit(Inst::Comment(format!("declare {}", name)));
+                match (var_type, value) {
+                    (VarType::String | VarType::Char { .. }, Some(Expression::Literal(lit))) => {
+                        // Declaration literals arrive with their quotes already stripped
+                        let label = self.literal(&literal_text(lit));
+                        self.copy_string(name, label);
+                    }
+                    (VarType::String | VarType::Char { .. }, Some(expr)) if self.kind_of(expr) == Kind::Str => {
+                        let src = self.gen_text(expr);
+                        self.copy_string(name, src);
+                    }
+                    (VarType::String | VarType::Char { .. }, _) => {
+                        self.emit(Inst::Addr(Reg::R0, var_label(name)));
+                        self.emit(Inst::Imm(Reg::R1, 0));
+                        self.emit(Inst::StoreByte(Reg::R0, Reg::R1));
+                    }
+                    (VarType::Int(int_type), Some(expr)) => {
+                        self.gen_fitted(name, *int_type, expr);
+                        self.emit(Inst::Store(var_label(name), Reg::R0));
+                    }
+                    (_, Some(expr)) => {
+                        self.gen_int(expr, Reg::R0);
+                        self.emit(Inst::Store(var_label(name), Reg::R0));
+                    }
+                    (_, None) => {
+                        self.emit(Inst::Imm(Reg::R0, 0));
+                        self.emit(Inst::Store(var_label(name), Reg::R0));
+                    }
+                }
+            }
+            ASTNode::Input { name, .. } => {
+                self.emit(Inst::Comment(format!("read a line into {}", name)));
+                match self.var_types.get(name) {
+                    Some(VarType::String) => {
+                        self.emit(Inst::Addr(Reg::R0, var_label(name)));
+                        self.emit(Inst::Imm(Reg::R1, STR_SIZE as i64 - 1));
+                        self.emit(Inst::Call("ven_read_line"));
+                    }
+                    Some(VarType::Char { .. }) => {
+                        self.read_line_buf();
+                        self.copy_string(name, "line_buf".to_string());
+                    }
+                    // True only for the line `true`
+                    Some(VarType::Bool) => {
+                        self.read_line_buf();
+                        let (false_label, end) = (self.label("false"), self.label("end"));
+                        let word = self.literal("true");
+                        self.emit(Inst::Addr(Reg::R0, "line_buf".to_string()));
+                        self.emit(Inst::Addr(Reg::R1, word));
+                        self.emit(Inst::Call("ven_str_cmp"));
+                        self.emit(Inst::CmpImm(Reg::R0, 0));
+                        self.emit(Inst::JumpIf(Cond::Ne, false_label.clone()));
+                        self.emit(Inst::Imm(Reg::R0, 1));
+                        self.emit(Inst::Jump(end.clone()));
+                        self.emit(Inst::Label(false_label));
+                        self.emit(Inst::Imm(Reg::R0, 0));
+                        self.emit(Inst::Label(end));
+                        self.emit(Inst::Store(var_label(name), Reg::R0));
+                    }
+                    Some(VarType::Int(int_type)) => {
+                        let int_type = *int_type;
+                        self.read_line_buf();
+                        self.emit(Inst::Addr(Reg::R0, "line_buf".to_string()));
+                        self.emit(Inst::Call("ven_parse_int"));
+                        self.fit(name, int_type);
+                        self.emit(Inst::Store(var_label(name), Reg::R0));
+                    }
+                    Some(_) => {
+                        self.read_line_buf();
+                        self.emit(Inst::Addr(Reg::R0, "line_buf".to_string()));
+                        self.emit(Inst::Call("ven_parse_int"));
+                        self.emit(Inst::Store(var_label(name), Reg::R0));
+                    }
+                    // Undeclared target: the line is consumed and discarded
+                    None => self.read_line_buf(),
+                }
+            }
+            ASTNode::Print { to_stderr, expr, .. } => {
+                let fd = if *to_stderr { 2 } else { 1 };
+                self.emit(Inst::Comment(format!("print to fd {}", fd)));
+                match expr {
+                    Some(Expression::Literal(text)) => self.print_literal(text, fd),
+                    Some(Expression::Identifier(name)) if self.var_types.contains_key(name) => {
+                        self.print_var(name, fd)
+                    }
+                    // Bare words are printed as written
+                    Some(Expression::Identifier(word)) => {
+                        let label = self.literal(word);
+                        self.write_label(fd, label, word.len());
+                    }
+                    Some(expr) => self.print_value(expr, fd),
+                    None => {}
+                }
+                self.write_label(fd, "newline".to_string(), 1);
+            }
+            ASTNode::MathOp { name, field: None, operator, operand, line } => {
+                self.emit(Inst::Comment(format!("math on {}", name)));
+                match self.var_types.get(name) {
+                    Some(VarType::Int(int_type)) => {
+                        // The operand is stored into the variable's type first, then the result is
+                        let int_type = *int_type;
+                        self.gen_fitted(name, int_type, operand);
+                        self.emit(Inst::Mov(Reg::R1, Reg::R0));
+                        self.emit(Inst::Load(Reg::R0, var_label(name)));
+                        if self.checks {
+                            self.check_divisor(name, operator, int_type, *line);
+                        }
+                        if *operator == MathOperator::Power {
+                            self.math = true;
+                            self.emit(Inst::Imm(Reg::R2, !int_type.signed as i64));
+                            self.emit(Inst::Call("ven_pow"));
+                            self.emit(Inst::Imm(Reg::R2, int_type.min() as i64));
+                            self.emit(Inst::Imm(Reg::R3, int_type.max() as i64));
+                            self.names(name, int_type);
+                            self.emit(Inst::Call("ven_pow_fit"));
+                        } else if int_type.bits == 64 {
+                            let routine = match (operator, int_type.signed) {
+                                (MathOperator::Add, true) => Some("ven_add_i64"),
+                                (MathOperator::Subtract, true) => Some("ven_sub_i64"),
+                                (MathOperator::Multiply, true) => Some("ven_mul_i64"),
+                                (MathOperator::Divide, true) => Some("ven_div_i64"),
+                                (MathOperator::Modulo, true) => {
+                                    self.math = true;
+                                    Some("ven_rem_i64")
+                                }
+                                (MathOperator::Add, false) => Some("ven_add_u64"),
+                                (MathOperator::Subtract, false) => Some("ven_sub_u64"),
+                                (MathOperator::Multiply, false) => Some("ven_mul_u64"),
+                                (MathOperator::Divide | MathOperator::Modulo, false) => None,
+                                (MathOperator::Power, _) => unreachable!("powers are stored above"),
+                            };
+                            match routine {
+                                Some(routine) => {
+                                    self.names(name, int_type);
+                                    self.emit(Inst::Call(routine));
+                                }
+                                // A u64 quotient or remainder always fits
+                                None if *operator == MathOperator::Modulo => {
+                                    self.emit(Inst::Arith(ArithOp::URem, Reg::R0, Reg::R1))
+                                }
+                                None => self.emit(Inst::Arith(ArithOp::UDiv, Reg::R0, Reg::R1)),
+                            }
+                        } else {
+                            // Narrower types are exact in 64 bits, though a u32 product needs the u64 range
+                            let op = match operator {
+                                MathOperator::Add => ArithOp::Add,
+                                MathOperator::Subtract => ArithOp::Sub,
+                                MathOperator::Multiply => ArithOp::Mul,
+                                MathOperator::Divide => ArithOp::Div,
+                                MathOperator::Modulo => ArithOp::Rem,
+                                MathOperator::Power => unreachable!("powers are stored above"),
+                            };
+                            self.emit(Inst::Arith(op, Reg::R0, Reg::R1));
+                            let unsigned = *operator == MathOperator::Multiply && !int_type.signed;
+                            self.emit(Inst::Imm(Reg::R1, unsigned as i64));
+                            self.fit(name, int_type);
+                        }
+                        self.emit(Inst::Store(var_label(name), Reg::R0));
+                    }
+                    Some(_) => return Err(format!("math on the non-numeric variable '{}'", name)),
+                    None => return Err(format!("math on the undeclared variable '{}'", name)),
+                }
+            }
+            ASTNode::If { condition, body, .. } => {
+                let end = self.label("endif");
+                self.emit(Inst::Comment("if".to_string()));
+                self.gen_branch(condition, &end, false);
+                self.gen_nodes(body)?;
+                self.emit(Inst::Label(end));
+            }
+            ASTNode::Call { name, .. } => return Err(format!("the native function call '{}'", name)),
+            ASTNode::SetIndex { name, .. } | ASTNode::Append { name, .. } | ASTNode::ForEach { array: name, .. } => {
+                return Err(format!("the array '{}'", name));
+            }
+            ASTNode::MathOp { name, field: Some(_), .. } => return Err(format!("the record '{}'", name)),
+            ASTNode::RecordDeclaration(record) => return Err(format!("the record type '{}'", record.name)),
+        }
+        Ok(())
+    }
+
+    /// Copies the text at `src` into `name`, cut to the variable's buffer.
+    fn copy_string(&mut self, name: &str, src: String) {
+        let size = match self.var_types.get(name) {
+            Some(VarType::Char { size }) => size + 1,
+            _ => STR_SIZE,
+        };
+        self.emit(Inst::Addr(Reg::R0, var_label(name)));
+        self.emit(Inst::Addr(Reg::R1, src));
+        self.emit(Inst::Imm(Reg::R2, size as i64));
+        self.emit(Inst::Call("ven_str_copy"));
+    }
+
+    fn read_line_buf(&mut self) {
+        self.emit(Inst::Addr(Reg::R0, "line_buf".to_string()));
+        self.emit(Inst::Imm(Reg::R1, STR_SIZE as i64 - 1));
+        self.emit(Inst::Call("ven_read_line"));
+    }
+
+    fn write_label(&mut self, fd: i64, label: String, len: usize) {
+        if len == 0 {
+            return;
+        }
+        self.emit(Inst::Imm(Reg::R0, fd));
+        self.emit(Inst::Addr(Reg::R1, label));
+        self.emit(Inst::Imm(Reg::R2, len as i64));
+        self.emit(Inst::Syscall(Syscall::Write));
+    }
+
+    fn print_literal(&mut self, text: &str, fd: i64) {
+        for segment in split_interpolation(text) {
+            match segment {
+                Segment::Text(text) => {
+                    let label = self.literal(&text);
+                    self.write_label(fd, label, text.len());
+                }
+                Segment::Var(name) => match placeholder(&name, &self.var_types) {
+                    Some(Expression::Identifier(name)) => self.print_var(&name, fd),
+                    Some(expr) => self.print_value(&expr, fd),
+                    None => self.write_label(fd, "lit_undefined".to_string(), 11),
+                },
+                // The precision only applies to floats, which are rejected by check_support
+                Segment::Formatted(name, spec) => match formatted(&name, &self.var_types) {
+                    Some(expr) => {
+                        let numeric = value_kind(&expr, &self.var_types) == ValueKind::Int;
+                        self.gen_text_addr(&expr);
+                        self.conversions = true;
+                        self.emit(Inst::Imm(Reg::R1, fd));
+                        self.emit(Inst::Imm(Reg::R2, spec.width as i64));
+                        self.emit(Inst::Imm(Reg::R3, spec.right(numeric) as i64));
+                        self.emit(Inst::Call("ven_pad"));
+                    }
+                    None => self.write_label(fd, "lit_undefined".to_string(), 11),
+                },
+            }
+        }
+    }
+
+    /// Prints text as it is, a length or a math result as a number and anything
+    /// else as `true` or `false`.
+    fn print_value(&mut self, expr: &Expression, fd: i64) {
+        match expr {
+            _ if self.kind_of(expr) == Kind::Str => {
+                let text = self.gen_text(expr);
+                self.emit(Inst::Addr(Reg::R0, text));
+                self.emit(Inst::Imm(Reg::R1, fd));
+                self.emit(Inst::Call("ven_print_cstr"));
+            }
+            Expression::Len(_) | Expression::Text { op: TextOp::Len, .. } | Expression::Math { .. } => {
+                self.gen_int(expr, Reg::R0);
+                self.emit(Inst::Imm(Reg::R1, fd));
+                self.emit(Inst::Call("ven_print_int"));
+            }
+            Expression::Convert { to: VarType::Int(_), .. } => {
+                let routine = if self.is_unsigned(expr) { "ven_print_uint" } else { "ven_print_int" };
+                self.gen_int(expr, Reg::R0);
+                self.emit(Inst::Imm(Reg::R1, fd));
+                self.emit(Inst::Call(routine));
+            }
+            _ => {
+                let (false_label, end) = (self.label("false"), self.label("end"));
+                self.gen_branch(expr, &false_label, false);
+                self.write_label(fd, "lit_true".to_string(), 4);
+                self.emit(Inst::Jump(end.clone()));
+                self.emit(Inst::Label(false_label));
+                self.write_label(fd, "lit_false".to_string(), 5);
+                self.emit(Inst::Label(end));
+            }
+        }
+    }
+
+    fn print_var(&mut self, name: &str, fd: i64) {
+        match self.var_types.get(name) {
+            Some(VarType::String | VarType::Char { .. }) => {
+                self.emit(Inst::Addr(Reg::R0, var_label(name)));
+                self.emit(Inst::Imm(Reg::R1, fd));
+                self.emit(Inst::Call("ven_print_cstr"));
+            }
+            Some(VarType::Bool) => {
+                let (false_label, end) = (self.label("false"), self.label("end"));
+                self.emit(Inst::Load(Reg::R0, var_label(name)));
+                self.emit(Inst::CmpImm(Reg::R0, 0));
+                self.emit(Inst::JumpIf(Cond::Eq, false_label.clone()));
+                self.write_label(fd, "lit_true".to_string(), 4);
+                self.emit(Inst::Jump(end.clone()));
+                self.emit(Inst::Label(false_label));
+                self.write_label(fd, "lit_false".to_string(), 5);
+                self.emit(Inst::Label(end));
+            }
+            Some(VarType::Int(IntType::U64)) => {
+                self.emit(Inst::Load(Reg::R0, var_label(name)));
+                self.emit(Inst::Imm(Reg::R1, fd));
+                self.emit(Inst::Call("ven_print_uint"));
+            }
+            _ => {
+                self.emit(Inst::Load(Reg::R0, var_label(name)));
+                self.emit(Inst::Imm(Reg::R1, fd));
+                self.emit(Inst::Call("ven_print_int"));
+            }
+        }
+    }
+
+    /// Stops with a division by zero in `name` on `line` when `R1` divides `R0`
+    /// by zero: `/` and `%` by 0, or a signed `**` of 0 to a negative power.
+    fn check_divisor(&mut self, name: &str, operator: &MathOperator, int_type: IntType, line: usize) {
+        let power = *operator == MathOperator::Power && int_type.signed;
+        if !power && !matches!(operator, MathOperator::Divide | MathOperator::Modulo) {
+            return;
+        }
+        let ok = self.label("divisor_ok");
+        match operator {
+            MathOperator::Divide | MathOperator::Modulo => {
+                self.emit(Inst::CmpImm(Reg::R1, 0));
+                self.emit(Inst::JumpIf(Cond::Ne, ok.clone()));
+            }
+            _ => {
+                self.emit(Inst::CmpImm(Reg::R0, 0));
+                self.emit(Inst::JumpIf(Cond::Ne, ok.clone()));
+                self.emit(Inst::CmpImm(Reg::R1, 0));
+                self.emit(Inst::JumpIf(Cond::Ge, ok.clone()));
+            }
+        }
+        self.division = true;
+        let name_label = self.literal(name);
+        self.emit(Inst::Addr(Reg::R4, name_label));
+        self.emit(Inst::Imm(Reg::R5, line as i64));
+        self.emit(Inst::Call("ven_div_zero"));
+        self.emit(Inst::Label(ok));
+    }
+
+    /// Loads the addresses of the name and type of the integer variable `name`
+    /// into `R4` and `R5`, for the trap message, which also names the line.
+    fn names(&mut self, name: &str, int_type: IntType) {
+        if self.overflow == Overflow::Trap {
+            self.emit(Inst::Imm(Reg::R4, self.line as i64));
+            self.emit(Inst::Store(LINE_SCRATCH.to_string(), Reg::R4));
+        }
+        let (name_label, type_label) = (self.literal(name), self.literal(&int_type.to_string()));
+        self.emit(Inst::Addr(Reg::R4, name_label));
+        self.emit(Inst::Addr(Reg::R5, type_label));
+    }
+
+    /// Stores `R0` into the integer variable `name`, with `R1` set when it is
+    /// read as a `u64`. The result is left in `R0`; every register is clobbered.
+    fn fit(&mut self, name: &str, int_type: IntType) {
+        self.emit(Inst::Imm(Reg::R2, int_type.min() as i64));
+        self.emit(Inst::Imm(Reg::R3, int_type.max() as i64));
+        self.names(name, int_type);
+        self.emit(Inst::Call("ven_fit"));
+    }
+
+    /// Loads `expr` into `R0`, converted for a store into the integer variable
+    /// `name`. Checks are left out where the value always fits.
+    fn gen_fitted(&mut self, name: &str, int_type: IntType, expr: &Expression) {
+        match expr {
+            Expression::Literal(lit) if self.kind_of(expr) == Kind::Int => {
+                let number = clean_number(lit);
+                let fitted = match number.as_str() {
+                    "true" => Some(1),
+                    "false" => Some(0),
+                    _ => match int_literal(&number) {
+                        Some(value) => fit(value, int_type, self.overflow),
+                        None => fit_float(number.parse::<f64>().unwrap_or(0.0), int_type, self.overflow),
+                    },
+                };
+                match fitted {
+                    // A u64 keeps its bits
+                    Some(value) => self.emit(Inst::Imm(Reg::R0, value as i64)),
+                    // Only a trap leaves a literal unfitted
+                    None => {
+                        self.names(name, int_type);
+                        self.emit(Inst::Call("ven_overflow"));
+                    }
+                }
+            }
+            Expression::Identifier(other) => match self.var_types.get(other) {
+                Some(VarType::Int(other_type)) if !int_type.holds(*other_type) => {
+                    let unsigned = *other_type == IntType::U64;
+                    self.emit(Inst::Load(Reg::R0, var_label(other)));
+                    self.emit(Inst::Imm(Reg::R1, unsigned as i64));
+                    self.fit(name, int_type);
+                }
+                Some(VarType::String | VarType::Char { .. }) => {
+                    self.emit(Inst::Addr(Reg::R0, var_label(other)));
+                    self.emit(Inst::Call("ven_parse_int"));
+                    self.fit(name, int_type);
+                }
+                _ => self.gen_int(expr, Reg::R0),
+            },
+            // A length or a math result is an i64
+            Expression::Len(_) | Expression::Text { .. } | Expression::Math { .. } if !int_type.holds(IntType::I64) => {
+                self.gen_int(expr, Reg::R0);
+                self.emit(Inst::Imm(Reg::R1, 0));
+                self.fit(name, int_type);
+            }
+            Expression::Convert { .. } => {
+                let from = int_type_of(expr, &self.var_types).unwrap_or(IntType::I64);
+                self.gen_int(expr, Reg::R0);
+                if !int_type.holds(from) {
+                    self.emit(Inst::Imm(Reg::R1, (from == IntType::U64) as i64));
+                    self.fit(name, int_type);
+                }
+            }
+            _ => self.gen_int(expr, Reg::R0),
+        }
+    }
+
+    /// Whether `expr` is an integer whose bits are read as a `u64`.
+    fn is_unsigned(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::Identifier(name) => self.var_types.get(name) == Some(&VarType::Int(IntType::U64)),
+            Expression::Literal(lit) => int_literal(&clean_number(lit)).is_some_and(|value| value > i64::MAX as i128),
+            // An integer converts to itself
+            Expression::Convert { to: VarType::Int(_), value } => self.is_unsigned(value),
+            _ => false,
+        }
+    }
+
+    fn kind_of(&self, expr: &Expression) -> Kind {
+        match expr {
+            Expression::Literal(lit) => {
+                let number = clean_number(lit);
+                if lit.starts_with('"') || lit.starts_with('\'') {
+                    Kind::Str
+                } else if number == "true" || number == "false" || number.parse::<f64>().is_ok() {
+                    Kind::Int
+                } else {
+                    Kind::Str
+                }
+            }
+            Expression::Identifier(name) => match self.var_types.get(name) {
+                Some(VarType::String | VarType::Char { .. }) => Kind::Str,
+                // Undeclared names evaluate to 0
+                _ => Kind::Int,
+            },
+            Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) => Kind::Int,
+            // Float results are rejected by check_support
+            Expression::Math { .. } => Kind::Int,
+            Expression::Text { op, .. } if op.result() == VarType::String => Kind::Str,
+            Expression::Text { .. } => Kind::Int,
+            Expression::Convert { to: VarType::String, .. } => Kind::Str,
+            // f() is rejected by check_support
+            Expression::Convert { .. } => Kind::Int,
+            // Arrays, maps and records are rejected by check_support
+            Expression::List(_)
+            | Expression::Index { .. }
+            | Expression::Len(_)
+            | Expression::In { .. }
+            | Expression::Field { .. }
+            | Expression::Record(_) => Kind::Int,
+        }
+    }
+
+    /// Loads an integer value into `reg`. May clobber every register when the
+    /// expression is itself a condition.
+    fn gen_int(&mut self, expr: &Expression, reg: Reg) {
+        match expr {
+            Expression::Literal(lit) => {
+                let number = clean_number(lit);
+                let value = match number.as_str() {
+                    "true" => 1,
+                    "false" => 0,
+                    // A u64 keeps its bits
+                    _ => int_literal(&number)
+                        .map(|value| value as i64)
+                        .unwrap_or_else(|| number.parse::<f64>().map(|f| f as i64).unwrap_or(0)),
+                };
+                self.emit(Inst::Imm(reg, value));
+            }
+            Expression::Identifier(name) if self.kind_of(expr) == Kind::Int && self.var_types.contains_key(name) => {
+                self.emit(Inst::Load(reg, var_label(name)));
+            }
+            Expression::Identifier(_) => self.emit(Inst::Imm(reg, 0)),
+            Expression::Len(name) if is_text_var(name, &self.var_types) => {
+                self.emit(Inst::Addr(Reg::R0, var_label(name)));
+                self.strings = true;
+                self.emit(Inst::Call("ven_str_len"));
+                self.emit_result(reg);
+            }
+            Expression::Text { op, args } => {
+                let texts: Vec<String> = args.iter().map(|arg| self.gen_text(arg)).collect();
+                self.strings = true;
+                self.emit(Inst::Addr(Reg::R0, texts[0].clone()));
+                let routine = match op {
+                    TextOp::Contains => "ven_str_contains",
+                    TextOp::StartsWith => "ven_str_prefix",
+                    _ => "ven_str_len",
+                };
+                if let Some(part) = texts.get(1) {
+                    self.emit(Inst::Addr(Reg::R1, part.clone()));
+                }
+                self.emit(Inst::Call(routine));
+                self.emit_result(reg);
+            }
+            Expression::Math { op, args } => {
+                self.gen_math(*op, args);
+                self.emit_result(reg);
+            }
+            // Booleans are 0 and 1 already, and an integer converts to itself
+            Expression::Convert { value, .. } if self.kind_of(value) == Kind::Int => self.gen_int(value, reg),
+            Expression::Convert { value, .. } => {
+                let text = self.gen_text(value);
+                self.conversions = true;
+                self.emit(Inst::Imm(Reg::R0, self.line as i64));
+                self.emit(Inst::Store(LINE_SCRATCH.to_string(), Reg::R0));
+                self.emit(Inst::Addr(Reg::R0, text));
+                self.emit(Inst::Call("ven_convert_int"));
+                self.emit_result(reg);
+            }
+            // Arrays, maps and records are rejected by check_support
+            Expression::List(_)
+            | Expression::Index { .. }
+            | Expression::Len(_)
+            | Expression::In { .. }
+            | Expression::Field { .. }
+            | Expression::Record(_) => self.emit(Inst::Imm(reg, 0)),
+            Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) => {
+                let (false_label, end) = (self.label("false"), self.label("end"));
+                self.gen_branch(expr, &false_label, false);
+                self.emit(Inst::Imm(reg, 1));
+                self.emit(Inst::Jump(end.clone()));
+                self.emit(Inst::Label(false_label));
+                self.emit(Inst::Imm(reg, 0));
+                self.emit(Inst::Label(end));
+            }
+        }
+    }
+
+    /// Loads the result of the math function `op` into `R0`. Arguments are taken
+    /// as `i64`s, like the result; every register is clobbered.
+    fn gen_math(&mut self, op: MathFn, args: &[Expression]) {
+        let called = op.called();
+        let arg = |codegen: &mut Self, arg: &Expression| {
+            codegen.gen_int(arg, Reg::R0);
+            if codegen.is_unsigned(arg) {
+                codegen.emit(Inst::Imm(Reg::R1, 1));
+                codegen.fit(called, IntType::I64);
+            }
+        };
+        self.emit(Inst::Comment(called.to_string()));
+        arg(self, &args[0]);
+        match op {
+            MathFn::Negate => {
+                self.emit(Inst::Mov(Reg::R1, Reg::R0));
+                self.emit(Inst::Imm(Reg::R0, 0));
+                self.names(called, IntType::I64);
+                self.emit(Inst::Call("ven_sub_i64"));
+            }
+            MathFn::Abs => {
+                self.math = true;
+                self.names(called, IntType::I64);
+                self.emit(Inst::Call("ven_abs_i64"));
+            }
+            // The value so far is kept aside; a later one only wins when it is
+            // smaller, or larger
+            MathFn::Min | MathFn::Max => {
+                let best = self.temp();
+                let wins = if op == MathFn::Min { Cond::Lt } else { Cond::Gt };
+                for next in &args[1..] {
+                    self.emit(Inst::Store(best.clone(), Reg::R0));
+                    arg(self, next);
+                    let keep = self.label("keep");
+                    self.emit(Inst::Load(Reg::R1, best.clone()));
+                    self.emit(Inst::Cmp(Reg::R0, Reg::R1));
+                    self.emit(Inst::JumpIf(wins, keep.clone()));
+                    self.emit(Inst::Mov(Reg::R0, Reg::R1));
+                    self.emit(Inst::Label(keep));
+                }
+            }
+            // A whole number is its own floor, ceiling and rounding
+            MathFn::Sqrt | MathFn::Floor | MathFn::Ceil | MathFn::Round => {}
+        }
+    }
+
+    /// Moves a routine's result from `R0` into `reg`.
+    fn emit_result(&mut self, reg: Reg) {
+        if reg != Reg::R0 {
+            self.emit(Inst::Mov(reg, Reg::R0));
+        }
+    }
+
+    /// The label of a string value, with the text of a string operation built
+    /// into a new buffer first. Every register is clobbered.
+    fn gen_text(&mut self, expr: &Expression) -> String {
+        let (op, args) = match expr {
+            Expression::Identifier(name) => return var_label(name),
+            Expression::Literal(lit) => return self.literal(&literal_text(lit)),
+            Expression::Text { op, args } => (*op, args),
+            Expression::Convert { value, .. } if self.kind_of(value) == Kind::Str => return self.gen_text(value),
+            // Into a buffer of its own, as the text of a number is only kept until the next one
+            Expression::Convert { value, .. } => {
+                let buf = self.buffer();
+                self.gen_text_addr(value);
+                self.emit(Inst::Mov(Reg::R1, Reg::R0));
+                self.emit(Inst::Addr(Reg::R0, buf.clone()));
+                self.emit(Inst::Imm(Reg::R2, STR_SIZE as i64));
+                self.emit(Inst::Call("ven_str_copy"));
+                return buf;
+            }
+            _ => return self.literal(""),
+        };
+        // The positions of slice() are kept aside while the text is built
+        let mut positions = Vec::new();
+        let texts: Vec<String> = match op {
+            TextOp::Slice => {
+                for arg in &args[1..] {
+                    let temp = self.temp();
+                    self.gen_int(arg, Reg::R0);
+                    self.emit(Inst::Store(temp.clone(), Reg::R0));
+                    positions.push(temp);
+                }
+                vec![self.gen_text(&args[0])]
+            }
+            _ => args.iter().map(|arg| self.gen_text(arg)).collect(),
+        };
+        let buf = self.buffer();
+        self.strings = true;
+        self.emit(Inst::Comment(format!("{}()", op.name())));
+        match op {
+            TextOp::Slice => {
+                let start = self.temp();
+                self.emit(Inst::Addr(Reg::R0, texts[0].clone()));
+                self.emit(Inst::Load(Reg::R1, positions[0].clone()));
+                self.emit(Inst::Call("ven_str_skip"));
+                self.emit(Inst::Store(start.clone(), Reg::R0));
+                self.emit(Inst::Addr(Reg::R0, texts[0].clone()));
+                self.emit(Inst::Load(Reg::R1, positions[1].clone()));
+                self.emit(Inst::Call("ven_str_skip"));
+                self.emit(Inst::Mov(Reg::R2, Reg::R0));
+                self.emit(Inst::Load(Reg::R1, start));
+                self.start_put(&buf);
+                self.emit(Inst::Call("ven_str_put"));
+            }
+            TextOp::Trim => {
+                self.emit(Inst::Addr(Reg::R0, texts[0].clone()));
+                self.emit(Inst::Call("ven_str_trim"));
+                self.emit(Inst::Mov(Reg::R2, Reg::R1));
+                self.emit(Inst::Mov(Reg::R1, Reg::R0));
+                self.start_put(&buf);
+                self.emit(Inst::Call("ven_str_put"));
+            }
+            TextOp::Replace => {
+                self.emit(Inst::Addr(Reg::R0, buf.clone()));
+                self.emit(Inst::Addr(Reg::R1, texts[0].clone()));
+                self.emit(Inst::Addr(Reg::R2, texts[1].clone()));
+                self.emit(Inst::Addr(Reg::R3, texts[2].clone()));
+                self.emit(Inst::Call("ven_str_replace"));
+            }
+            // concat(), upper() and lower() put their texts one after another
+            _ => {
+                self.start_put(&buf);
+                for text in texts {
+                    self.emit(Inst::Addr(Reg::R1, text));
+                    self.emit(Inst::Imm(Reg::R2, -1));
+                    self.emit(Inst::Call("ven_str_put"));
+                }
+                if matches!(op, TextOp::Upper | TextOp::Lower) {
+                    let (first, delta) = if op == TextOp::Upper { (b'a', -32) } else { (b'A', 32) };
+                    self.emit(Inst::Addr(Reg::R0, buf.clone()));
+                    self.emit(Inst::Imm(Reg::R1, first as i64));
+                    self.emit(Inst::Imm(Reg::R2, delta));
+                    self.emit(Inst::Call("ven_str_case"));
+                }
+            }
+        }
+        buf
+    }
+
+    /// Loads the address of the text `expr` prints as into `R0`. The text of a
+    /// number is only kept until the next one is made; every register is clobbered.
+    fn gen_text_addr(&mut self, expr: &Expression) {
+        match value_kind(expr, &self.var_types) {
+            ValueKind::Str => {
+                let text = self.gen_text(expr);
+                self.emit(Inst::Addr(Reg::R0, text));
+            }
+            ValueKind::Bool => {
+                let (false_label, end) = (self.label("false"), self.label("end"));
+                let (yes, no) = (self.literal("true"), self.literal("false"));
+                self.gen_branch(expr, &false_label, false);
+                self.emit(Inst::Addr(Reg::R0, yes));
+                self.emit(Inst::Jump(end.clone()));
+                self.emit(Inst::Label(false_label));
+                self.emit(Inst::Addr(Reg::R0, no));
+                self.emit(Inst::Label(end));
+            }
+            // Floats are rejected by check_support
+            ValueKind::Int | ValueKind::Float => {
+                let unsigned = self.is_unsigned(expr);
+                self.gen_int(expr, Reg::R0);
+                self.conversions = true;
+                self.emit(Inst::Imm(Reg::R1, unsigned as i64));
+                self.emit(Inst::Call("ven_int_text"));
+            }
+        }
+    }
+
+    /// Points `R0` at the start of `buf` and `R3` at its last byte, for `ven_str_put`.
+    fn start_put(&mut self, buf: &str) {
+        self.emit(Inst::Addr(Reg::R0, buf.to_string()));
+        self.emit(Inst::Mov(Reg::R3, Reg::R0));
+        self.emit(Inst::AddImm(Reg::R3, STR_SIZE as i64 - 1));
+    }
+
+    /// Jumps to `target` when `expr` evaluates to `when`, falls through otherwise.
+    fn gen_branch(&mut self, expr: &Expression, target: &str, when: bool) {
+        match expr {
+            Expression::Not(operand) => self.gen_branch(operand, target, !when),
+            Expression::LogicalOp { left, operator, right } => {
+                // `a && b` fails as soon as one side fails, `a || b` succeeds as soon as one side does
+                let short_circuit = matches!(operator, LogicalOperator::Or);
+                if short_circuit == when {
+                    self.gen_branch(left, target, when);
+                    self.gen_branch(right, target, when);
+                } else {
+                    let skip = self.label("skip");
+                    self.gen_branch(left, &skip, !when);
+                    self.gen_branch(right, target, when);
+                    self.emit(Inst::Label(skip));
+                }
+            }
+            Expression::BinaryOp { left, operator, right } => {
+                let cond = match operator {
+                    ComparisonOperator::Equal => Cond::Eq,
+                    ComparisonOperator::NotEqual => Cond::Ne,
+                    ComparisonOperator::LessThan => Cond::Lt,
+                    ComparisonOperator::LessThanEqual => Cond::Le,
+                    ComparisonOperator::GreaterThan => Cond::Gt,
+                    ComparisonOperator::GreaterThanEqual => Cond::Ge,
+                };
+                let cond = if when { cond } else { cond.inverse() };
+                match (self.kind_of(left), self.kind_of(right)) {
+                    (Kind::Str, Kind::Str) => {
+                        let (left, right) = (self.gen_text(left), self.gen_text(right));
+                        self.emit(Inst::Addr(Reg::R0, left));
+                        self.emit(Inst::Addr(Reg::R1, right));
+                        self.emit(Inst::Call("ven_str_cmp"));
+                        self.emit(Inst::CmpImm(Reg::R0, 0));
+                        self.emit(Inst::JumpIf(cond, target.to_string()));
+                    }
+                    (Kind::Int, Kind::Int) => {
+                        self.gen_int(right, Reg::R1);
+                        if clobbers(left) {
+                            // Evaluating the left side clobbers R1
+                            let temp = self.temp();
+                            self.emit(Inst::Store(temp.clone(), Reg::R1));
+                            self.gen_int(left, Reg::R0);
+                            self.emit(Inst::Load(Reg::R1, temp));
+                        } else {
+                            self.gen_int(left, Reg::R0);
+                        }
+                        if self.is_unsigned(left) || self.is_unsigned(right) {
+                            let (left_unsigned, right_unsigned) = (self.is_unsigned(left), self.is_unsigned(right));
+                            self.emit(Inst::Imm(Reg::R2, left_unsigned as i64));
+                            self.emit(Inst::Imm(Reg::R3, right_unsigned as i64));
+                            self.emit(Inst::Call("ven_compare"));
+                            self.emit(Inst::CmpImm(Reg::R0, 0));
+                        } else {
+                            self.emit(Inst::Cmp(Reg::R0, Reg::R1));
+                        }
+                        self.emit(Inst::JumpIf(cond, target.to_string()));
+                    }
+                    // A string never equals a number
+                    _ => {
+                        let holds = *operator == ComparisonOperator::NotEqual;
+                        if holds == when {
+                            self.emit(Inst::Jump(target.to_string()));
+                        }
+                    }
+                }
+            }
+            _ => {
+                match self.kind_of(expr) {
+                    // Strings are true when non-empty
+                    Kind::Str => {
+                        let text = self.gen_text(expr);
+                        self.emit(Inst::Addr(Reg::R1, text));
+                        self.emit(Inst::LoadByte(Reg::R0, Reg::R1));
+                    }
+                    Kind::Int => self.gen_int(expr, Reg::R0),
+                }
+                self.emit(Inst::CmpImm(Reg::R0, 0));
+                self.emit(Inst::JumpIf(if when { Cond::Ne } else { Cond::Eq }, target.to_string()));
+            }
+        }
+    }
+}
+
+/// Whether evaluating `expr` may clobber every register.
+fn clobbers(expr: &Expression) -> bool {
+    matches!(
+        expr,
+        Expression::BinaryOp { .. }
+            | Expression::LogicalOp { .. }
+            | Expression::Not(_)
+            | Expression::Len(_)
+            | Expression::Text { .. }
+            | Expression::Math { .. }
+            | Expression::Convert { .. }
+    )
+}
+
+/// Runtime routines shared by every assembly target.
+#[rustfmt::skip]
+fn runtime() -> Vec<Inst> {
+    use ArithOp::*;
+    use Inst::*;
+    use Reg::*;
+    use self::Syscall as Sys;
+    let l = |name: &str| name.to_string();
+    vec![
+        Comment(l("---- runtime ----")),
+        // ven_print_int(R0 = value, R1 = fd)
+        // Digits are produced from the negated value so that i64::MIN formats correctly.
+        Label(l("ven_print_int")),
+        Addr(R2, l("num_buf")), AddImm(R2, NUM_BUF_SIZE),
+        Imm(R3, 0),
+        CmpImm(R0, 0), JumpIf(Cond::Lt, l("ven_print_int_digits")),
+        Imm(R4, 0), Arith(Sub, R4, R0), Mov(R0, R4),
+        Imm(R3, 1),
+        Label(l("ven_print_int_digits")),
+        // value is <= 0 here and R3 == 1 when it was positive
+        Mov(R4, R0), Imm(R5, 10), Arith(Rem, R4, R5),
+        Imm(R5, 48), Arith(Sub, R5, R4),
+        AddImm(R2, -1), StoreByte(R2, R5),
+        Imm(R5, 10), Arith(Div, R0, R5),
+        CmpImm(R0, 0), JumpIf(Cond::Ne, l("ven_print_int_digits")),
+        CmpImm(R3, 0), JumpIf(Cond::Ne, l("ven_print_int_write")),
+        AddImm(R2, -1), Imm(R5, 45), StoreByte(R2, R5),
+        Label(l("ven_print_int_write")),
+        Addr(R4, l("num_buf")), AddImm(R4, NUM_BUF_SIZE), Arith(Sub, R4, R2),
+        Mov(R0, R1), Mov(R1, R2), Mov(R2, R4),
+        Syscall(Sys::Write),
+        Ret,
+
+        // ven_print_uint(R0 = value, R1 = fd): the value read as a u64
+        Label(l("ven_print_uint")),
+        Addr(R2, l("num_buf")), AddImm(R2, NUM_BUF_SIZE),
+        Label(l("ven_print_uint_digits")),
+        Mov(R3, R0), Imm(R5, 10), Arith(UDiv, R3, R5),
+        Mov(R4, R3), Arith(Mul, R4, R5), Mov(R5, R0), Arith(Sub, R5, R4),
+        AddImm(R5, 48), AddImm(R2, -1), StoreByte(R2, R5),
+        Mov(R0, R3),
+        CmpImm(R0, 0), JumpIf(Cond::Ne, l("ven_print_uint_digits")),
+        Jump(l("ven_print_int_write")),
+
+        // ven_print_cstr(R0 = address, R1 = fd)
+        Label(l("ven_print_cstr")),
+        Mov(R2, R0),
+        Label(l("ven_print_cstr_len")),
+        LoadByte(R3, R2), CmpImm(R3, 0), JumpIf(Cond::Eq, l("ven_print_cstr_write")),
+        AddImm(R2, 1), Jump(l("ven_print_cstr_len")),
+        Label(l("ven_print_cstr_write")),
+        Arith(Sub, R2, R0), CmpImm(R2, 0), JumpIf(Cond::Eq, l("ven_print_cstr_done")),
+        Mov(R3, R0), Mov(R0, R1), Mov(R1, R3),
+        Syscall(Sys::Write),
+        Label(l("ven_print_cstr_done")),
+        Ret,
+
+        // ven_read_line(R0 = buffer, R1 = capacity without the NUL)
+        // Reads byte by byte up to a newline or EOF; carriage returns and overflow are dropped.
+        Label(l("ven_read_line")),
+        Mov(R4, R0), Mov(R5, R0), Arith(Add, R5, R1),
+        Label(l("ven_read_line_next")),
+        Imm(R0, 0), Mov(R1, R4), Imm(R2, 1),
+        Syscall(Sys::Read),
+        CmpImm(R0, 1), JumpIf(Cond::Lt, l("ven_read_line_done")),
+        LoadByte(R3, R4),
+        CmpImm(R3, 10), JumpIf(Cond::Eq, l("ven_read_line_done")),
+        CmpImm(R3, 13), JumpIf(Cond::Eq, l("ven_read_line_next")),
+        Cmp(R4, R5), JumpIf(Cond::Ge, l("ven_read_line_next")),
+        AddImm(R4, 1), Jump(l("ven_read_line_next")),
+        Label(l("ven_read_line_done")),
+        Imm(R3, 0), StoreByte(R4, R3),
+        Ret,
+
+        // ven_parse_int(R0 = address) -> R0, with R1 = 1 when only a u64 holds the value
+        // Leading spaces and one sign are accepted; parsing stops at the first non-digit.
+        // Numbers outside the i64 minimum to the u64 maximum read as 0.
+        Label(l("ven_parse_int")),
+        Mov(R1, R0), Imm(R0, 0), Imm(R2, 0),
+        Label(l("ven_parse_int_space")),
+        LoadByte(R3, R1), CmpImm(R3, 32), JumpIf(Cond::Ne, l("ven_parse_int_sign")),
+        AddImm(R1, 1), Jump(l("ven_parse_int_space")),
+        Label(l("ven_parse_int_sign")),
+        CmpImm(R3, 45), JumpIf(Cond::Ne, l("ven_parse_int_plus")),
+        Imm(R2, 1), AddImm(R1, 1), Jump(l("ven_parse_int_digit")),
+        Label(l("ven_parse_int_plus")),
+        CmpImm(R3, 43), JumpIf(Cond::Ne, l("ven_parse_int_digit")),
+        AddImm(R1, 1),
+        Label(l("ven_parse_int_digit")),
+        LoadByte(R3, R1),
+        CmpImm(R3, 48), JumpIf(Cond::Lt, l("ven_parse_int_end")),
+        CmpImm(R3, 57), JumpIf(Cond::Gt, l("ven_parse_int_end")),
+        AddImm(R3, -48),
+        // Past the u64 maximum once value > (u64::MAX - digit) / 10
+        Imm(R4, -1), Arith(Sub, R4, R3), Imm(R5, 10), Arith(UDiv, R4, R5),
+        Cmp(R0, R4), JumpIf(Cond::Above, l("ven_parse_int_range")),
+        Imm(R4, 10), Arith(Mul, R0, R4), Arith(Add, R0, R3),
+        AddImm(R1, 1), Jump(l("ven_parse_int_digit")),
+        Label(l("ven_parse_int_end")),
+        Imm(R1, 0),
+        CmpImm(R2, 0), JumpIf(Cond::Eq, l("ven_parse_int_positive")),
+        Imm(R4, i64::MIN), Cmp(R0, R4), JumpIf(Cond::Above, l("ven_parse_int_range")),
+        Imm(R4, 0), Arith(Sub, R4, R0), Mov(R0, R4),
+        Ret,
+        Label(l("ven_parse_int_positive")),
+        CmpImm(R0, 0), JumpIf(Cond::Ge, l("ven_parse_int_done")),
+        Imm(R1, 1),
+        Label(l("ven_parse_int_done")),
+        Ret,
+        Label(l("ven_parse_int_range")),
+        Imm(R0, 0), Imm(R1, 0),
+        Ret,
+
+        // ven_compare(R0 = a, R1 = b, R2 = 1 when a is a u64, R3 = 1 when b is) -> R0 in {-1, 0, 1}
+        // A negative signed side is the smaller one; otherwise both compare unsigned.
+        Label(l("ven_compare")),
+        CmpImm(R2, 0), JumpIf(Cond::Ne, l("ven_compare_b")),
+        CmpImm(R0, 0), JumpIf(Cond::Lt, l("ven_compare_negative")),
+        Label(l("ven_compare_b")),
+        CmpImm(R3, 0), JumpIf(Cond::Ne, l("ven_compare_unsigned")),
+        CmpImm(R1, 0), JumpIf(Cond::Lt, l("ven_compare_greater")),
+        Label(l("ven_compare_unsigned")),
+        Cmp(R0, R1),
+        JumpIf(Cond::Below, l("ven_compare_less")),
+        JumpIf(Cond::Above, l("ven_compare_greater")),
+        Imm(R0, 0), Ret,
+        Label(l("ven_compare_negative")),
+        CmpImm(R3, 0), JumpIf(Cond::Ne, l("ven_compare_less")),
+        Cmp(R0, R1),
+        JumpIf(Cond::Lt, l("ven_compare_less")),
+        JumpIf(Cond::Gt, l("ven_compare_greater")),
+        Imm(R0, 0), Ret,
+        Label(l("ven_compare_less")),
+        Imm(R0, -1), Ret,
+        Label(l("ven_compare_greater")),
+        Imm(R0, 1), Ret,
+
+        // ven_str_cmp(R0 = a, R1 = b) -> R0 in {-1, 0, 1}
+        Label(l("ven_str_cmp")),
+        LoadByte(R2, R0), LoadByte(R3, R1),
+        Cmp(R2, R3),
+        JumpIf(Cond::Lt, l("ven_str_cmp_less")),
+        JumpIf(Cond::Gt, l("ven_str_cmp_greater")),
+        CmpImm(R2, 0), JumpIf(Cond::Eq, l("ven_str_cmp_equal")),
+        AddImm(R0, 1), AddImm(R1, 1), Jump(l("ven_str_cmp")),
+        Label(l("ven_str_cmp_less")),
+        Imm(R0, -1), Ret,
+        Label(l("ven_str_cmp_greater")),
+        Imm(R0, 1), Ret,
+        Label(l("ven_str_cmp_equal")),
+        Imm(R0, 0), Ret,
+
+        // ven_str_copy(R0 = dest, R1 = src, R2 = dest size)
+        // A cut never splits a UTF-8 character: it backs off continuation bytes.
+        Label(l("ven_str_copy")),
+        Mov(R3, R0), Arith(Add, R3, R2), AddImm(R3, -1), Mov(R2, R0),
+        Label(l("ven_str_copy_next")),
+        Cmp(R0, R3), JumpIf(Cond::Ge, l("ven_str_copy_cut")),
+        LoadByte(R4, R1), CmpImm(R4, 0), JumpIf(Cond::Eq, l("ven_str_copy_done")),
+        StoreByte(R0, R4),
+        AddImm(R0, 1), AddImm(R1, 1), Jump(l("ven_str_copy_next")),
+        Label(l("ven_str_copy_cut")),
+        Cmp(R0, R2), JumpIf(Cond::BelowEq, l("ven_str_copy_done")),
+        LoadByte(R4, R1),
+        CmpImm(R4, 0x80), JumpIf(Cond::Below, l("ven_str_copy_done")),
+        CmpImm(R4, 0xBF), JumpIf(Cond::Above, l("ven_str_copy_done")),
+        AddImm(R0, -1), AddImm(R1, -1), Jump(l("ven_str_copy_cut")),
+        Label(l("ven_str_copy_done")),
+        Imm(R4, 0), StoreByte(R0, R4),
+        Ret,
+    ]
+}
+
+/// Where `ven_str_replace` keeps its state between the routines it calls.
+const REPLACE_SCRATCH: [&str; 6] =
+    ["ven_rep_at", "ven_rep_from", "ven_rep_to", "ven_rep_pos", "ven_rep_limit", "ven_rep_next"];
+
+/// The string operation routines, for programs that use them. Text is built by
+/// `ven_str_put`, which cuts it at the last whole character that fits.
+#[rustfmt::skip]
+fn string_runtime() -> Vec<Inst> {
+    use ArithOp::*;
+    use Inst::*;
+    use Reg::*;
+    let l = |name: &str| name.to_string();
+    let blank = |byte: Reg, target: &str| {
+        [32, 9, 10, 13].into_iter().flat_map(|c| [CmpImm(byte, c), JumpIf(Cond::Eq, l(target))]).collect::<Vec<_>>()
+    };
+    let mut code = vec![
+        Comment(l("---- string operations ----")),
+        // ven_str_put(R0 = dest, R1 = src, R2 = src end or -1, R3 = last byte of dest) -> R0 at the NUL
+        // Copies up to the end or the NUL of src. A cut backs off to the start of the
+        // character that did not fit and sets R3 to R0, so later puts add nothing.
+        Label(l("ven_str_put")),
+        Cmp(R1, R2), JumpIf(Cond::AboveEq, l("ven_str_put_done")),
+        LoadByte(R4, R1), CmpImm(R4, 0), JumpIf(Cond::Eq, l("ven_str_put_done")),
+        Cmp(R0, R3), JumpIf(Cond::AboveEq, l("ven_str_put_cut")),
+        StoreByte(R0, R4),
+        AddImm(R0, 1), AddImm(R1, 1), Jump(l("ven_str_put")),
+        Label(l("ven_str_put_cut")),
+        CmpImm(R4, 0x80), JumpIf(Cond::Below, l("ven_str_put_full")),
+        CmpImm(R4, 0xBF), JumpIf(Cond::Above, l("ven_str_put_full")),
+        AddImm(R0, -1), AddImm(R1, -1), LoadByte(R4, R1), Jump(l("ven_str_put_cut")),
+        Label(l("ven_str_put_full")),
+        Mov(R3, R0),
+        Label(l("ven_str_put_done")),
+        Imm(R4, 0), StoreByte(R0, R4),
+        Ret,
+
+        // ven_str_len(R0 = text) -> R0, the number of characters: bytes that are not 0x80..=0xBF
+        Label(l("ven_str_len")),
+        Mov(R1, R0), Imm(R0, 0),
+        Label(l("ven_str_len_next")),
+        LoadByte(R2, R1), CmpImm(R2, 0), JumpIf(Cond::Eq, l("ven_str_len_done")),
+        AddImm(R1, 1), AddImm(R2, -0x80), CmpImm(R2, 0x40), JumpIf(Cond::Below, l("ven_str_len_next")),
+        AddImm(R0, 1), Jump(l("ven_str_len_next")),
+        Label(l("ven_str_len_done")),
+        Ret,
+
+        // ven_str_skip(R0 = text, R1 = n) -> R0 at character n, or at the NUL when there are fewer
+        Label(l("ven_str_skip")),
+        CmpImm(R1, 0), JumpIf(Cond::Le, l("ven_str_skip_done")),
+        LoadByte(R2, R0), CmpImm(R2, 0), JumpIf(Cond::Eq, l("ven_str_skip_done")),
+        AddImm(R0, 1),
+        Label(l("ven_str_skip_rest")),
+        LoadByte(R2, R0), AddImm(R2, -0x80), CmpImm(R2, 0x40), JumpIf(Cond::AboveEq, l("ven_str_skip_next")),
+        AddImm(R0, 1), Jump(l("ven_str_skip_rest")),
+        Label(l("ven_str_skip_next")),
+        AddImm(R1, -1), Jump(l("ven_str_skip")),
+        Label(l("ven_str_skip_done")),
+        Ret,
+
+        // ven_str_case(R0 = text, R1 = first letter, R2 = delta): adds the delta to the
+        // 26 ASCII letters from the first, in place
+        Label(l("ven_str_case")),
+        LoadByte(R3, R0), CmpImm(R3, 0), JumpIf(Cond::Eq, l("ven_str_case_done")),
+        Mov(R4, R3), Arith(Sub, R4, R1), CmpImm(R4, 26), JumpIf(Cond::AboveEq, l("ven_str_case_next")),
+        Arith(Add, R3, R2), StoreByte(R0, R3),
+        Label(l("ven_str_case_next")),
+        AddImm(R0, 1), Jump(l("ven_str_case")),
+        Label(l("ven_str_case_done")),
+        Ret,
+
+        // ven_str_trim(R0 = text) -> R0 at the first byte kept, R1 after the last
+        // Spaces, tabs and line breaks are trimmed.
+        Label(l("ven_str_trim")),
+        LoadByte(R2, R0),
+    ];
+    code.extend(blank(R2, "ven_str_trim_front"));
+    code.extend([
+        Mov(R1, R0),
+        Label(l("ven_str_trim_end")),
+        LoadByte(R2, R1), CmpImm(R2, 0), JumpIf(Cond::Eq, l("ven_str_trim_back")),
+        AddImm(R1, 1), Jump(l("ven_str_trim_end")),
+        Label(l("ven_str_trim_back")),
+        Cmp(R1, R0), JumpIf(Cond::BelowEq, l("ven_str_trim_done")),
+        Mov(R3, R1), AddImm(R3, -1), LoadByte(R2, R3),
+    ]);
+    code.extend(blank(R2, "ven_str_trim_drop"));
+    code.extend([
+        Label(l("ven_str_trim_done")),
+        Ret,
+        Label(l("ven_str_trim_front")),
+        AddImm(R0, 1), Jump(l("ven_str_trim")),
+        Label(l("ven_str_trim_drop")),
+        Mov(R1, R3), Jump(l("ven_str_trim_back")),
+
+        // ven_str_find(R0 = text, R1 = part) -> R0 at the first match or 0, R2 after the match
+        Label(l("ven_str_find")),
+        Mov(R2, R0), Mov(R3, R1),
+        Label(l("ven_str_find_next")),
+        LoadByte(R5, R3), CmpImm(R5, 0), JumpIf(Cond::Eq, l("ven_str_find_done")),
+        LoadByte(R4, R2), Cmp(R4, R5), JumpIf(Cond::Ne, l("ven_str_find_miss")),
+        AddImm(R2, 1), AddImm(R3, 1), Jump(l("ven_str_find_next")),
+        Label(l("ven_str_find_miss")),
+        LoadByte(R4, R0), CmpImm(R4, 0), JumpIf(Cond::Eq, l("ven_str_find_none")),
+        AddImm(R0, 1), Jump(l("ven_str_find")),
+        Label(l("ven_str_find_none")),
+        Imm(R0, 0),
+        Label(l("ven_str_find_done")),
+        Ret,
+
+        // ven_str_contains(R0 = text, R1 = part) -> R0 in {0, 1}
+        Label(l("ven_str_contains")),
+        Call("ven_str_find"),
+        CmpImm(R0, 0), JumpIf(Cond::Eq, l("ven_str_contains_done")),
+        Imm(R0, 1),
+        Label(l("ven_str_contains_done")),
+        Ret,
+
+        // ven_str_prefix(R0 = text, R1 = prefix) -> R0 in {0, 1}
+        Label(l("ven_str_prefix")),
+        LoadByte(R3, R1), CmpImm(R3, 0), JumpIf(Cond::Eq, l("ven_str_prefix_yes")),
+        LoadByte(R2, R0), Cmp(R2, R3), JumpIf(Cond::Ne, l("ven_str_prefix_no")),
+        AddImm(R0, 1), AddImm(R1, 1), Jump(l("ven_str_prefix")),
+        Label(l("ven_str_prefix_yes")),
+        Imm(R0, 1), Ret,
+        Label(l("ven_str_prefix_no")),
+        Imm(R0, 0), Ret,
+
+        // ven_str_replace(R0 = dest, R1 = text, R2 = from, R3 = to)
+        // Every from, left to right; an empty from leaves the text as it is.
+        Label(l("ven_str_replace")),
+        Store(l("ven_rep_at"), R1), Store(l("ven_rep_from"), R2), Store(l("ven_rep_to"), R3),
+        Mov(R3, R0), AddImm(R3, STR_SIZE as i64 - 1),
+        Store(l("ven_rep_pos"), R0), Store(l("ven_rep_limit"), R3),
+        LoadByte(R4, R2), CmpImm(R4, 0), JumpIf(Cond::Eq, l("ven_str_replace_rest")),
+        Label(l("ven_str_replace_next")),
+        Load(R0, l("ven_rep_at")), Load(R1, l("ven_rep_from")),
+        Call("ven_str_find"),
+        CmpImm(R0, 0), JumpIf(Cond::Eq, l("ven_str_replace_rest")),
+        Store(l("ven_rep_next"), R2),
+        Mov(R2, R0), Load(R1, l("ven_rep_at")), Load(R0, l("ven_rep_pos")), Load(R3, l("ven_rep_limit")),
+        Call("ven_str_put"),
+        Load(R1, l("ven_rep_to")), Imm(R2, -1),
+        Call("ven_str_put"),
+        Store(l("ven_rep_pos"), R0), Store(l("ven_rep_limit"), R3),
+        Load(R0, l("ven_rep_next")), Store(l("ven_rep_at"), R0),
+        Jump(l("ven_str_replace_next")),
+        Label(l("ven_str_replace_rest")),
+        Load(R0, l("ven_rep_pos")), Load(R1, l("ven_rep_at")), Imm(R2, -1), Load(R3, l("ven_rep_limit")),
+        Call("ven_str_put"),
+        Ret,
+    ]);
+    code
+}
+
+/// Where `ven_convert_int` keeps the text it reports and `ven_pad` its arguments
+/// across the writes.
+const CONVERSION_SCRATCH: [&str; 5] = ["ven_conv_text", "ven_pad_text", "ven_pad_fd", "ven_pad_left", "ven_pad_right"];
+
+/// The conversion and format spec routines, for programs that use them.
+#[rustfmt::skip]
+fn conversion_runtime() -> Vec<Inst> {
+    use ArithOp::*;
+    use Inst::*;
+    use Reg::*;
+    use self::Syscall as Sys;
+    let l = |name: &str| name.to_string();
+    let blank = |byte: Reg, target: &str| {
+        [32, 9, 10, 13].into_iter().flat_map(|c| [CmpImm(byte, c), JumpIf(Cond::Eq, l(target))]).collect::<Vec<_>>()
+    };
+    let mut code = vec![
+        Comment(l("---- conversions ----")),
+        // ven_convert_int(R0 = text) -> R0: one sign and decimal digits within i64, with spaces,
+        // tabs or line breaks around them. Anything else stops the program.
+        Label(l("ven_convert_int")),
+        Store(l("ven_conv_text"), R0), Mov(R1, R0),
+        Label(l("ven_convert_int_front")),
+        LoadByte(R2, R1),
+    ];
+    code.extend(blank(R2, "ven_convert_int_skip"));
+    code.extend([
+        Imm(R3, 0),
+        CmpImm(R2, 45), JumpIf(Cond::Ne, l("ven_convert_int_plus")),
+        Imm(R3, 1), AddImm(R1, 1), Jump(l("ven_convert_int_start")),
+        Label(l("ven_convert_int_plus")),
+        CmpImm(R2, 43), JumpIf(Cond::Ne, l("ven_convert_int_start")),
+        AddImm(R1, 1),
+        Label(l("ven_convert_int_start")),
+        Imm(R0, 0),
+        LoadByte(R2, R1), AddImm(R2, -48), CmpImm(R2, 10), JumpIf(Cond::AboveEq, l("ven_convert_int_failed")),
+        Label(l("ven_convert_int_digit")),
+        LoadByte(R2, R1), AddImm(R2, -48), CmpImm(R2, 10), JumpIf(Cond::AboveEq, l("ven_convert_int_back")),
+        // Past the i64 range, whose negative side goes one further, once
+        // value > (i64::MAX + minus - digit) / 10
+        Imm(R4, i64::MAX), Arith(Add, R4, R3), Arith(Sub, R4, R2), Imm(R5, 10), Arith(UDiv, R4, R5),
+        Cmp(R0, R4), JumpIf(Cond::Above, l("ven_convert_int_failed")),
+        Arith(Mul, R0, R5), Arith(Add, R0, R2),
+        AddImm(R1, 1), Jump(l("ven_convert_int_digit")),
+        Label(l("ven_convert_int_back")),
+        LoadByte(R2, R1),
+    ]);
+    code.extend(blank(R2, "ven_convert_int_next"));
+    code.extend([
+        CmpImm(R2, 0), JumpIf(Cond::Ne, l("ven_convert_int_failed")),
+        CmpImm(R3, 0), JumpIf(Cond::Eq, l("ven_convert_int_done")),
+        Imm(R4, 0), Arith(Sub, R4, R0), Mov(R0, R4),
+        Label(l("ven_convert_int_done")),
+        Ret,
+        Label(l("ven_convert_int_skip")),
+        AddImm(R1, 1), Jump(l("ven_convert_int_front")),
+        Label(l("ven_convert_int_next")),
+        AddImm(R1, 1), Jump(l("ven_convert_int_back")),
+        Label(l("ven_convert_int_failed")),
+        Imm(R0, 2), Addr(R1, l("lit_convert")), Imm(R2, CONVERSION_MESSAGE[0].len() as i64),
+        Syscall(Sys::Write),
+        Load(R0, l("ven_conv_text")), Imm(R1, 2),
+        Call("ven_print_cstr"),
+        Imm(R0, 2), Addr(R1, l("lit_convert_end")), Imm(R2, CONVERSION_MESSAGE[1].len() as i64),
+        Syscall(Sys::Write),
+        Load(R0, l(LINE_SCRATCH)), Imm(R1, 2), Call("ven_print_int"),
+        Imm(R0, 2), Addr(R1, l("newline")), Imm(R2, 1), Syscall(Sys::Write),
+        Imm(R0, 1),
+        Syscall(Sys::Exit),
+
+        // ven_int_text(R0 = value, R1 = 1 when it is read as a u64) -> R0, its text in num_buf
+        // Digits are produced from the negated value so that i64::MIN formats correctly.
+        Label(l("ven_int_text")),
+        Addr(R2, l("num_buf")), AddImm(R2, NUM_BUF_SIZE - 1),
+        Imm(R3, 0), StoreByte(R2, R3),
+        CmpImm(R1, 0), JumpIf(Cond::Ne, l("ven_int_text_unsigned")),
+        CmpImm(R0, 0), JumpIf(Cond::Lt, l("ven_int_text_digits")),
+        Imm(R4, 0), Arith(Sub, R4, R0), Mov(R0, R4),
+        Imm(R3, 1),
+        Label(l("ven_int_text_digits")),
+        Mov(R4, R0), Imm(R5, 10), Arith(Rem, R4, R5),
+        Imm(R5, 48), Arith(Sub, R5, R4),
+        AddImm(R2, -1), StoreByte(R2, R5),
+        Imm(R5, 10), Arith(Div, R0, R5),
+        CmpImm(R0, 0), JumpIf(Cond::Ne, l("ven_int_text_digits")),
+        CmpImm(R3, 0), JumpIf(Cond::Ne, l("ven_int_text_done")),
+        AddImm(R2, -1), Imm(R5, 45), StoreByte(R2, R5),
+        Jump(l("ven_int_text_done")),
+        Label(l("ven_int_text_unsigned")),
+        Mov(R3, R0), Imm(R5, 10), Arith(UDiv, R3, R5),
+        Mov(R4, R3), Arith(Mul, R4, R5), Mov(R5, R0), Arith(Sub, R5, R4),
+        AddImm(R5, 48), AddImm(R2, -1), StoreByte(R2, R5),
+        Mov(R0, R3),
+        CmpImm(R0, 0), JumpIf(Cond::Ne, l("ven_int_text_unsigned")),
+        Label(l("ven_int_text_done")),
+        Mov(R0, R2),
+        Ret,
+
+        // ven_pad(R0 = text, R1 = fd, R2 = width, R3 = 1 when the spaces go before the text)
+        // Spaces are added up to width characters: bytes that are not 0x80..=0xBF.
+        Label(l("ven_pad")),
+        Store(l("ven_pad_text"), R0), Store(l("ven_pad_fd"), R1), Store(l("ven_pad_right"), R3),
+        Mov(R1, R0),
+        Label(l("ven_pad_count")),
+        LoadByte(R4, R1), CmpImm(R4, 0), JumpIf(Cond::Eq, l("ven_pad_counted")),
+        AddImm(R1, 1), AddImm(R4, -0x80), CmpImm(R4, 0x40), JumpIf(Cond::Below, l("ven_pad_count")),
+        AddImm(R2, -1), Jump(l("ven_pad_count")),
+        Label(l("ven_pad_counted")),
+        Store(l("ven_pad_left"), R2),
+        CmpImm(R3, 0), JumpIf(Cond::Ne, l("ven_pad_spaces")),
+        Load(R0, l("ven_pad_text")), Load(R1, l("ven_pad_fd")),
+        Call("ven_print_cstr"),
+        Label(l("ven_pad_spaces")),
+        Load(R3, l("ven_pad_left")), CmpImm(R3, 0), JumpIf(Cond::Le, l("ven_pad_after")),
+        AddImm(R3, -1), Store(l("ven_pad_left"), R3),
+        Load(R0, l("ven_pad_fd")), Addr(R1, l("lit_space")), Imm(R2, 1),
+        Syscall(Sys::Write),
+        Jump(l("ven_pad_spaces")),
+        Label(l("ven_pad_after")),
+        Load(R3, l("ven_pad_right")), CmpImm(R3, 0), JumpIf(Cond::Eq, l("ven_pad_done")),
+        Load(R0, l("ven_pad_text")), Load(R1, l("ven_pad_fd")),
+        Call("ven_print_cstr"),
+        Label(l("ven_pad_done")),
+        Ret,
+    ]);
+    code
+}
+
+/// Where `ven_pow` keeps the exponent while it multiplies.
+const POW_SCRATCH: &str = "ven_pow_y";
+
+/// The math routines, for programs that use them.
+#[rustfmt::skip]
+fn math_runtime(overflow: Overflow) -> Vec<Inst> {
+    use ArithOp::*;
+    use Inst::*;
+    use Reg::*;
+    let l = |name: &str| name.to_string();
+    let mut code = vec![
+        Comment(l("---- math ----")),
+        // ven_rem_i64(R0 = x, R1 = y) -> R0; i64::MIN % -1 faults, and is 0
+        Label(l("ven_rem_i64")),
+        CmpImm(R1, -1), JumpIf(Cond::Eq, l("ven_rem_i64_zero")),
+        Arith(Rem, R0, R1),
+        Ret,
+        Label(l("ven_rem_i64_zero")),
+        Imm(R0, 0), Ret,
+
+        // ven_abs_i64(R0 = x, R4 = name, R5 = type) -> R0
+        Label(l("ven_abs_i64")),
+        CmpImm(R0, 0), JumpIf(Cond::Ge, l("ven_abs_i64_done")),
+        Mov(R1, R0), Imm(R0, 0), Jump(l("ven_sub_i64")),
+        Label(l("ven_abs_i64_done")),
+        Ret,
+
+        // ven_pow(R0 = x, R1 = y, R2 = 1 when both are read as u64s) -> R0, with R1
+        // saying what it holds: 0 an i64, 1 a u64, or the low 64 bits of a power
+        // past every integer type, 2 when it is positive and 3 when negative.
+        // The magnitude is multiplied up y times, until it leaves the u64 range.
+        Label(l("ven_pow")),
+        CmpImm(R1, 0), JumpIf(Cond::Eq, l("ven_pow_one")),
+        CmpImm(R0, 1), JumpIf(Cond::BelowEq, l("ven_pow_same")),
+        Store(l(POW_SCRATCH), R1),
+        Imm(R3, 0),
+        CmpImm(R2, 0), JumpIf(Cond::Ne, l("ven_pow_unsigned")),
+        CmpImm(R0, -1), JumpIf(Cond::Eq, l("ven_pow_minus_one")),
+        // A negative exponent gives 1 / x ** -y, which truncates to 0
+        CmpImm(R1, 0), JumpIf(Cond::Lt, l("ven_pow_zero")),
+        CmpImm(R0, 0), JumpIf(Cond::Ge, l("ven_pow_unsigned")),
+        Mov(R3, R1), Imm(R4, 2), Arith(URem, R3, R4),
+        Imm(R4, 0), Arith(Sub, R4, R0), Mov(R0, R4),
+        Label(l("ven_pow_unsigned")),
+        Mov(R2, R0), Imm(R0, 1),
+        Label(l("ven_pow_next")),
+        Mov(R4, R0), Arith(Mul, R4, R2),
+        Mov(R5, R4), Arith(UDiv, R5, R2),
+        Cmp(R5, R0), JumpIf(Cond::Ne, l("ven_pow_beyond")),
+        Mov(R0, R4),
+        AddImm(R1, -1), CmpImm(R1, 0), JumpIf(Cond::Ne, l("ven_pow_next")),
+        CmpImm(R3, 0), JumpIf(Cond::Ne, l("ven_pow_negative")),
+        Imm(R1, 1), Ret,
+        Label(l("ven_pow_negative")),
+        Imm(R4, i64::MIN), Cmp(R0, R4), JumpIf(Cond::Above, l("ven_pow_beyond")),
+        Imm(R4, 0), Arith(Sub, R4, R0), Mov(R0, R4),
+        Imm(R1, 0), Ret,
+        // The low bits, squaring as the exponent is halved
+        Label(l("ven_pow_beyond")),
+        Imm(R0, 1), Load(R1, l(POW_SCRATCH)),
+        Label(l("ven_pow_square")),
+        CmpImm(R1, 0), JumpIf(Cond::Eq, l("ven_pow_low")),
+        Mov(R4, R1), Imm(R5, 2), Arith(URem, R4, R5),
+        CmpImm(R4, 0), JumpIf(Cond::Eq, l("ven_pow_even")),
+        Arith(Mul, R0, R2),
+        Label(l("ven_pow_even")),
+        Arith(Mul, R2, R2), Arith(UDiv, R1, R5),
+        Jump(l("ven_pow_square")),
+        Label(l("ven_pow_low")),
+        Imm(R1, 2),
+        CmpImm(R3, 0), JumpIf(Cond::Eq, l("ven_pow_done")),
+        Imm(R4, 0), Arith(Sub, R4, R0), Mov(R0, R4),
+        Imm(R1, 3),
+        Label(l("ven_pow_done")),
+        Ret,
+        Label(l("ven_pow_minus_one")),
+        Mov(R3, R1), Imm(R4, 2), Arith(URem, R3, R4),
+        CmpImm(R3, 0), JumpIf(Cond::Eq, l("ven_pow_one")),
+        Imm(R1, 0), Ret,
+        Label(l("ven_pow_one")),
+        Imm(R0, 1),
+        Label(l("ven_pow_same")),
+        Imm(R1, 0), Ret,
+        Label(l("ven_pow_zero")),
+        Imm(R0, 0), Imm(R1, 0), Ret,
+
+        // ven_pow_fit(R0 = power, R1 = what it holds, R2 = min, R3 = max, R4 = name,
+        //             R5 = type) -> R0, the result of ven_pow stored into a type
+        Label(l("ven_pow_fit")),
+        CmpImm(R1, 2), JumpIf(Cond::Below, l("ven_fit")),
+    ];
+    code.extend(on_overflow(overflow, vec![Imm(R1, 0), Jump(l("ven_fit"))], vec![
+        CmpImm(R1, 3), JumpIf(Cond::Eq, l("ven_pow_fit_min")),
+        Mov(R0, R3), Ret,
+        Label(l("ven_pow_fit_min")),
+        Mov(R0, R2), Ret,
+    ]));
+    code
+}
+
+/// What an out-of-range integer becomes under `overflow`: `wrapped` and
+/// `saturated` leave the result in `R0` and return. A trap reports the variable
+/// whose name and type are in `R4` and `R5`.
+fn on_overflow(overflow: Overflow, wrapped: Vec<Inst>, saturated: Vec<Inst>) -> Vec<Inst> {
+    match overflow {
+        Overflow::Wrap => wrapped,
+        Overflow::Saturate => saturated,
+        Overflow::Trap => vec![Inst::Call("ven_overflow")],
+    }
+}
+
+/// A 64-bit routine: `body` computes `R0 op R1` into `R0` and jumps to
+/// `<name>_overflow` when the result left the type, where a wrapped result is
+/// already in `R0`.
+fn checked(name: &str, body: Vec<Inst>, saturated: Vec<Inst>, overflow: Overflow) -> Vec<Inst> {
+    let mut code = vec![Inst::Label(name.to_string())];
+    code.extend(body);
+    code.push(Inst::Ret);
+    code.push(Inst::Label(format!("{}_overflow", name)));
+    code.extend(on_overflow(overflow, vec![Inst::Ret], saturated));
+    code
+}
+
+/// Routines that store integers into a type under `overflow`.
+#[rustfmt::skip]
+fn overflow_runtime(overflow: Overflow) -> Vec<Inst> {
+    use ArithOp::*;
+    use Inst::*;
+    use Reg::*;
+    use self::Syscall as Sys;
+    let l = |name: &str| name.to_string();
+    let limit = |value: i64| vec![Imm(R0, value), Ret];
+    // R2 holds x: the signed limit on its side
+    let signed_limit = |routine: &str| vec![
+        CmpImm(R2, 0), JumpIf(Cond::Lt, format!("{}_min", routine)),
+        Imm(R0, i64::MAX), Ret,
+        Label(format!("{}_min", routine)),
+        Imm(R0, i64::MIN), Ret,
+    ];
+
+    let mut code = vec![
+        Comment(l("---- integer overflow ----")),
+        // ven_fit(R0 = value, R1 = 1 when value is read as a u64, R2 = min, R3 = max,
+        //         R4 = name, R5 = type) -> R0
+        // A u64 has min 0 and max -1.
+        Label(l("ven_fit")),
+        CmpImm(R2, 0), JumpIf(Cond::Ne, l("ven_fit_signed")),
+        CmpImm(R3, -1), JumpIf(Cond::Ne, l("ven_fit_signed")),
+        CmpImm(R1, 0), JumpIf(Cond::Ne, l("ven_fit_ok")),
+        CmpImm(R0, 0), JumpIf(Cond::Ge, l("ven_fit_ok")),
+    ];
+    code.extend(on_overflow(overflow, vec![Ret], limit(0)));
+    code.extend([
+        Label(l("ven_fit_signed")),
+        CmpImm(R1, 0), JumpIf(Cond::Eq, l("ven_fit_range")),
+        CmpImm(R0, 0), JumpIf(Cond::Lt, l("ven_fit_above")),
+        Label(l("ven_fit_range")),
+        Cmp(R0, R2), JumpIf(Cond::Lt, l("ven_fit_below")),
+        Cmp(R0, R3), JumpIf(Cond::Gt, l("ven_fit_above")),
+        Label(l("ven_fit_ok")),
+        Ret,
+        Label(l("ven_fit_below")),
+    ]);
+    code.extend(on_overflow(overflow, vec![Jump(l("ven_fit_wrap"))], vec![Mov(R0, R2), Ret]));
+    code.push(Label(l("ven_fit_above")));
+    code.extend(on_overflow(overflow, vec![Jump(l("ven_fit_wrap"))], vec![Mov(R0, R3), Ret]));
+    if overflow == Overflow::Wrap {
+        code.extend([
+            // Keep the low bits: the remainder by the type's span, moved into the range
+            Label(l("ven_fit_wrap")),
+            Mov(R4, R3), Arith(Sub, R4, R2), AddImm(R4, 1),
+            CmpImm(R4, 0), JumpIf(Cond::Eq, l("ven_fit_ok")),
+            Arith(Rem, R0, R4),
+            Cmp(R0, R2), JumpIf(Cond::Ge, l("ven_fit_wrap_high")),
+            Arith(Add, R0, R4),
+            Label(l("ven_fit_wrap_high")),
+            Cmp(R0, R3), JumpIf(Cond::Le, l("ven_fit_ok")),
+            Arith(Sub, R0, R4),
+            Ret,
+        ]);
+    }
+
+    // Math on i64 and u64: R0 = x, R1 = y, R4 = name, R5 = type -> R0; x is kept in R2
+    code.extend(checked("ven_add_i64", vec![
+        Mov(R2, R0), Arith(Add, R0, R1),
+        CmpImm(R1, 0), JumpIf(Cond::Lt, l("ven_add_i64_negative")),
+        Cmp(R0, R2), JumpIf(Cond::Lt, l("ven_add_i64_overflow")),
+        Ret,
+        Label(l("ven_add_i64_negative")),
+        Cmp(R0, R2), JumpIf(Cond::Gt, l("ven_add_i64_overflow")),
+    ], signed_limit("ven_add_i64"), overflow));
+    code.extend(checked("ven_sub_i64", vec![
+        Mov(R2, R0), Arith(Sub, R0, R1),
+        CmpImm(R1, 0), JumpIf(Cond::Lt, l("ven_sub_i64_negative")),
+        Cmp(R0, R2), JumpIf(Cond::Gt, l("ven_sub_i64_overflow")),
+        Ret,
+        Label(l("ven_sub_i64_negative")),
+        Cmp(R0, R2), JumpIf(Cond::Lt, l("ven_sub_i64_overflow")),
+    ], signed_limit("ven_sub_i64"), overflow));
+    code.extend(checked("ven_mul_i64", vec![
+        // The check divides by x, so x = -1 is handled apart
+        Mov(R2, R0), Arith(Mul, R0, R1),
+        CmpImm(R2, -1), JumpIf(Cond::Ne, l("ven_mul_i64_divide")),
+        Imm(R3, i64::MIN), Cmp(R1, R3), JumpIf(Cond::Eq, l("ven_mul_i64_overflow")),
+        Ret,
+        Label(l("ven_mul_i64_divide")),
+        CmpImm(R2, 0), JumpIf(Cond::Eq, l("ven_mul_i64_done")),
+        Mov(R3, R0), Arith(Div, R3, R2),
+        Cmp(R3, R1), JumpIf(Cond::Ne, l("ven_mul_i64_overflow")),
+        Label(l("ven_mul_i64_done")),
+    ], vec![
+        // The product is negative when exactly one side is
+        CmpImm(R2, 0), JumpIf(Cond::Lt, l("ven_mul_i64_x_negative")),
+        CmpImm(R1, 0), JumpIf(Cond::Lt, l("ven_mul_i64_min")),
+        Imm(R0, i64::MAX), Ret,
+        Label(l("ven_mul_i64_x_negative")),
+        CmpImm(R1, 0), JumpIf(Cond::Ge, l("ven_mul_i64_min")),
+        Imm(R0, i64::MAX), Ret,
+        Label(l("ven_mul_i64_min")),
+        Imm(R0, i64::MIN), Ret,
+    ], overflow));
+    code.extend(checked("ven_div_i64", vec![
+        // i64::MIN / -1 faults, and wraps to i64::MIN, which is x
+        CmpImm(R1, -1), JumpIf(Cond::Ne, l("ven_div_i64_divide")),
+        Imm(R3, i64::MIN), Cmp(R0, R3), JumpIf(Cond::Eq, l("ven_div_i64_overflow")),
+        Label(l("ven_div_i64_divide")),
+        Arith(Div, R0, R1),
+    ], limit(i64::MAX), overflow));
+    code.extend(checked("ven_add_u64", vec![
+        Mov(R2, R0), Arith(Add, R0, R1),
+        Cmp(R0, R2), JumpIf(Cond::Below, l("ven_add_u64_overflow")),
+    ], limit(-1), overflow));
+    code.extend(checked("ven_sub_u64", vec![
+        Mov(R2, R0), Arith(Sub, R0, R1),
+        Cmp(R2, R1), JumpIf(Cond::Below, l("ven_sub_u64_overflow")),
+    ], limit(0), overflow));
+    code.extend(checked("ven_mul_u64", vec![
+        Mov(R2, R0), Arith(Mul, R0, R1),
+        CmpImm(R2, 0), JumpIf(Cond::Eq, l("ven_mul_u64_done")),
+        Mov(R3, R0), Arith(UDiv, R3, R2),
+        Cmp(R3, R1), JumpIf(Cond::Ne, l("ven_mul_u64_overflow")),
+        Label(l("ven_mul_u64_done")),
+    ], limit(-1), overflow));
+
+    if overflow == Overflow::Trap {
+        let [before, between, after] = OVERFLOW_MESSAGE.map(str::len);
+        code.extend([
+            // ven_overflow(R4 = name, R5 = type): reports the variable and the line in ven_line
+            // and exits with status 1
+            Label(l("ven_overflow")),
+            Imm(R0, 2), Addr(R1, l("lit_overflow")), Imm(R2, before as i64), Syscall(Sys::Write),
+            Mov(R0, R4), Imm(R1, 2), Call("ven_print_cstr"),
+            Imm(R0, 2), Addr(R1, l("lit_overflow_type")), Imm(R2, between as i64), Syscall(Sys::Write),
+            Mov(R0, R5), Imm(R1, 2), Call("ven_print_cstr"),
+            Imm(R0, 2), Addr(R1, l("lit_overflow_end")), Imm(R2, after as i64), Syscall(Sys::Write),
+            Load(R0, l(LINE_SCRATCH)), Imm(R1, 2), Call("ven_print_int"),
+            Imm(R0, 2), Addr(R1, l("newline")), Imm(R2, 1), Syscall(Sys::Write),
+            Imm(R0, 1), Syscall(Sys::Exit),
+        ]);
+    }
+    code
+}
+
+/// The division by zero report, for programs that check for it.
+#[rustfmt::skip]
+fn division_runtime() -> Vec<Inst> {
+    use Inst::*;
+    use Reg::*;
+    use self::Syscall as Sys;
+    let l = |name: &str| name.to_string();
+    let [before, between] = DIVISION_MESSAGE.map(str::len);
+    vec![
+        // ven_div_zero(R4 = name, R5 = line): reports the variable and line and exits with status 1
+        Label(l("ven_div_zero")),
+        Imm(R0, 2), Addr(R1, l("lit_div_zero")), Imm(R2, before as i64), Syscall(Sys::Write),
+        Mov(R0, R4), Imm(R1, 2), Call("ven_print_cstr"),
+        Imm(R0, 2), Addr(R1, l("lit_div_zero_line")), Imm(R2, between as i64), Syscall(Sys::Write),
+        Mov(R0, R5), Imm(R1, 2), Call("ven_print_int"),
+        Imm(R0, 2), Addr(R1, l("newline")), Imm(R2, 1), Syscall(Sys::Write),
+        Imm(R0, 1), Syscall(Sys::Exit),
+    ]
+}
@@ -1,8 +1,1138 @@
 #[allow(non_snake_case)]
 pub mod C;
 #[allow(non_snake_case)]
+pub mod JS;
+#[allow(non_snake_case)]
+pub mod LA64;
+#[allow(non_snake_case)]
 pub mod LLVM;
 #[allow(non_snake_case)]
 pub mod LX8664;
 #[allow(non_snake_case)]
+pub mod PY;
+#[allow(non_snake_case)]
 pub mod RST;
+#[allow(non_snake_case)]
+pub mod WASM;
+pub mod asm;
+
+use crate::errs::BackendError;
+use crate::parse::{AST, ASTNode, Expression, IntType, MathFn, MathOperator, RecordType, TextOp, VarType};
+use crate::parse2::parse_access;
+use crate::token::Tokenizer;
+use std::collections::HashMap;
+
+/// Language features a backend can translate. Programs using anything else are
+/// rejected with a `BackendError` before any code is emitted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capabilities {
+    /// `f` variables.
+    pub floats: bool,
+    /// `?()` blocks.
+    pub conditionals: bool,
+    /// Statements other than `>>`/`>>>` inside `?()` blocks, nested blocks included.
+    pub block_statements: bool,
+    /// `..` into `i` or `f` variables.
+    pub numeric_input: bool,
+    /// `..` into `str` or `c[size]` variables.
+    pub string_input: bool,
+    /// `T[]` variables, their elements and `~()` loops.
+    pub arrays: bool,
+    /// `map[K]V` variables, their keys and `in` tests.
+    pub maps: bool,
+    /// `#` record types, their variables and fields.
+    pub records: bool,
+    /// `i()`, `f()` and `str()` conversions, and `{name:spec}` format specs.
+    pub conversions: bool,
+    /// `upper()`, `slice()` and the other string operations, and `len()` of text.
+    pub strings: bool,
+}
+
+impl Capabilities {
+    /// Everything the language currently has.
+    pub const ALL: Capabilities = Capabilities {
+        floats: true,
+        conditionals: true,
+        block_statements: true,
+        numeric_input: true,
+        string_input: true,
+        arrays: true,
+        maps: true,
+        records: true,
+        conversions: true,
+        strings: true,
+    };
+
+    /// Human-readable list of the missing features, for `--list-targets`.
+    pub fn missing(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if !self.floats {
+            missing.push("floats");
+        }
+        if !self.conditionals {
+            missing.push("conditionals");
+        }
+        if !self.block_statements {
+            missing.push("statements in blocks");
+        }
+        if !self.numeric_input {
+            missing.push("numeric input");
+        }
+        if !self.string_input {
+            missing.push("string input");
+        }
+        if !self.arrays {
+            missing.push("arrays");
+        }
+        if !self.maps {
+            missing.push("maps");
+        }
+        if !self.records {
+            missing.push("records");
+        }
+        if !self.conversions {
+            missing.push("conversions");
+        }
+        if !self.strings {
+            missing.push("string operations");
+        }
+        missing
+    }
+}
+
+/// What integer math does with a result outside the variable's type. The same
+/// rule applies when a value is stored into a narrower integer variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Keep the low bits, as two's complement hardware does.
+    #[default]
+    Wrap,
+    /// Clamp to the type's minimum or maximum.
+    Saturate,
+    /// Stop the program with a runtime error.
+    Trap,
+}
+
+impl Overflow {
+    /// Parses the value of `--overflow=`.
+    pub fn from_name(name: &str) -> Option<Overflow> {
+        match name {
+            "wrap" => Some(Overflow::Wrap),
+            "saturate" => Some(Overflow::Saturate),
+            "trap" => Some(Overflow::Trap),
+            _ => None,
+        }
+    }
+}
+
+/// Settings that change what the generated program does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CodegenOptions {
+    pub overflow: Overflow,
+    /// Check for division by zero before `/`, `%` and integer `**`, and stop
+    /// with a runtime error naming the source line. Without the checks the
+    /// target does whatever its own division does (`--no-runtime-checks`).
+    pub runtime_checks: bool,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        CodegenOptions {
+            overflow: Overflow::default(),
+            runtime_checks: true,
+        }
+    }
+}
+
+/// `value` stored into `int_type`, or `None` when it does not fit and `overflow`
+/// traps. Every backend implements exactly this.
+pub fn fit(value: i128, int_type: IntType, overflow: Overflow) -> Option<i128> {
+    if (int_type.min()..=int_type.max()).contains(&value) {
+        return Some(value);
+    }
+    match overflow {
+        Overflow::Wrap => {
+            let low = value.rem_euclid(1i128 << int_type.bits);
+            Some(if low > int_type.max() { low - (1i128 << int_type.bits) } else { low })
+        }
+        Overflow::Saturate => Some(value.clamp(int_type.min(), int_type.max())),
+        Overflow::Trap => None,
+    }
+}
+
+/// Text stored into a `c[size]` buffer: the longest start of `text` that fits
+/// in `size` bytes without splitting a character. Every backend cuts text
+/// exactly this way.
+pub fn fit_text(text: &str, size: usize) -> &str {
+    let mut end = size.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Bytes a `str` holds, the size of its buffer on the native targets less the
+/// terminator. A line read, a literal stored and the text `concat()` and
+/// `replace()` build are cut to it on every target.
+pub const STR_CAPACITY: usize = 255;
+
+/// The text of the literal `lit` stored into a variable of `var_type`, cut to
+/// fit a `str`.
+pub fn stored_text(lit: &str, var_type: &VarType) -> String {
+    let text = literal_text(lit);
+    match var_type {
+        VarType::String => fit_text(&text, STR_CAPACITY).to_string(),
+        _ => text,
+    }
+}
+
+/// A float stored into `int_type`: truncated toward zero, with NaN as 0. Floats
+/// never wrap; out of range they clamp to the type's limits, or trap.
+pub fn fit_float(value: f64, int_type: IntType, overflow: Overflow) -> Option<i128> {
+    if value.is_nan() {
+        return Some(0);
+    }
+    let value = value.trunc();
+    // Both bounds are powers of two (or zero), so they convert exactly
+    if value >= int_type.min() as f64 && value < (int_type.max() + 1) as f64 {
+        return Some(value as i128);
+    }
+    match overflow {
+        Overflow::Trap => None,
+        _ if value < 0.0 => Some(int_type.min()),
+        _ => Some(int_type.max()),
+    }
+}
+
+/// `x op y` for two values of `int_type`, fitted back into it. Division
+/// truncates toward zero and the remainder takes the sign of `x`; callers
+/// rule out division by zero, and `0 ** y` for a negative `y`.
+pub fn int_math(x: i128, operator: &MathOperator, y: i128, int_type: IntType, overflow: Overflow) -> Option<i128> {
+    let exact = match operator {
+        MathOperator::Add => x + y,
+        MathOperator::Subtract => x - y,
+        MathOperator::Divide => x / y,
+        MathOperator::Modulo => x % y,
+        MathOperator::Power => int_power(x, y),
+        MathOperator::Multiply => match x.checked_mul(y) {
+            Some(product) => product,
+            // Only the product of two large `u64` values gets here
+            None => {
+                return match overflow {
+                    Overflow::Wrap => Some((x as u64).wrapping_mul(y as u64) as i128),
+                    Overflow::Saturate => Some(int_type.max()),
+                    Overflow::Trap => None,
+                };
+            }
+        },
+    };
+    fit(exact, int_type, overflow)
+}
+
+/// `x ** y`, with a negative `y` giving `1 / x ** -y` truncated toward zero.
+/// A power too large for an `i128` comes back as a stand-in outside every
+/// integer type, with the power's sign and the same low 64 bits, which `fit`
+/// wraps, saturates or traps exactly as it would the power itself.
+pub fn int_power(x: i128, y: i128) -> i128 {
+    match x {
+        _ if y == 0 => return 1,
+        0 | 1 => return x,
+        -1 if y % 2 == 0 => return 1,
+        -1 => return -1,
+        _ if y < 0 => return 0,
+        _ => {}
+    }
+    if let Some(power) = u32::try_from(y).ok().and_then(|y| x.checked_pow(y)) {
+        return power;
+    }
+    let (mut base, mut exponent, mut low) = (x as u64, y as u64, 1u64);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            low = low.wrapping_mul(base);
+        }
+        base = base.wrapping_mul(base);
+        exponent >>= 1;
+    }
+    if x < 0 && y % 2 == 1 { low as i128 - (1i128 << 65) } else { low as i128 + (1i128 << 64) }
+}
+
+/// `op` of integer values, each first stored into an `i64`, with the result
+/// fitted into an `i64` too. `sqrt()` is never an integer.
+pub fn int_math_fn(op: MathFn, args: &[i128], overflow: Overflow) -> Option<i128> {
+    let exact = match op {
+        MathFn::Negate => -args[0],
+        MathFn::Abs => args[0].abs(),
+        MathFn::Min => args.iter().copied().min()?,
+        MathFn::Max => args.iter().copied().max()?,
+        MathFn::Sqrt | MathFn::Floor | MathFn::Ceil | MathFn::Round => args[0],
+    };
+    fit(exact, IntType::I64, overflow)
+}
+
+/// `op` of float values. `min()` and `max()` compare with `<` and `>` from
+/// the left, so a later value only wins when it is smaller or larger.
+pub fn float_math_fn(op: MathFn, args: &[f64]) -> f64 {
+    match op {
+        MathFn::Negate => -args[0],
+        MathFn::Abs => args[0].abs(),
+        MathFn::Min => args[1..].iter().fold(args[0], |min, &x| if x < min { x } else { min }),
+        MathFn::Max => args[1..].iter().fold(args[0], |max, &x| if x > max { x } else { max }),
+        MathFn::Sqrt => args[0].sqrt(),
+        MathFn::Floor => args[0].floor(),
+        MathFn::Ceil => args[0].ceil(),
+        MathFn::Round => args[0].round(),
+    }
+}
+
+/// The value of an integer literal. Literals run from `i64::MIN` to `u64::MAX`.
+pub fn int_literal(lit: &str) -> Option<i128> {
+    clean_number(lit)
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+}
+
+/// A compilation target.
+pub trait Backend: Sync {
+    /// Canonical name, as accepted by `-t=`.
+    fn name(&self) -> &'static str;
+    /// Other names accepted by `-t=`.
+    fn aliases(&self) -> &'static [&'static str];
+    /// Human-readable name of the output language.
+    fn description(&self) -> &'static str;
+    /// Extension of the written file, without the dot.
+    fn extension(&self) -> &'static str;
+    fn capabilities(&self) -> Capabilities;
+    /// Emits the program. Only called for programs that passed `check_support`.
+    fn emit(&self, ast: &AST, options: &CodegenOptions) -> Result<String, BackendError>;
+
+    /// An optional binary artifact and its extension (`--wasm-binary`).
+    fn emit_binary(&self, _ast: &AST, _options: &CodegenOptions) -> Option<(&'static str, Vec<u8>)> {
+        None
+    }
+
+    /// Checks the program against the capabilities, then emits it.
+    fn transpile(&self, ast: &AST, options: &CodegenOptions) -> Result<String, BackendError> {
+        check_support(self.name(), self.capabilities(), ast)?;
+        self.emit(ast, options)
+    }
+}
+
+/// Every available backend, in `--list-targets` order.
+pub static BACKENDS: &[&dyn Backend] = &[
+    &RST::RustBackend,
+    &C::CBackend,
+    &LLVM::LlvmBackend,
+    &LX8664::Lx8664Backend,
+    &LA64::La64Backend,
+    &WASM::WasmBackend,
+    &JS::JsBackend,
+    &PY::PyBackend,
+];
+
+/// Looks a backend up by name or alias, ignoring case.
+pub fn find_backend(name: &str) -> Option<&'static dyn Backend> {
+    BACKENDS.iter().copied().find(|backend| {
+        backend.name().eq_ignore_ascii_case(name)
+            || backend.aliases().iter().any(|alias| alias.eq_ignore_ascii_case(name))
+    })
+}
+
+/// Rejects the first construct in `ast` that `caps` does not cover.
+pub fn check_support(target: &'static str, caps: Capabilities, ast: &AST) -> Result<(), BackendError> {
+    fn walk(
+        nodes: &[ASTNode],
+        in_block: bool,
+        caps: Capabilities,
+        var_types: &HashMap<String, VarType>,
+    ) -> Result<(), String> {
+        for node in nodes {
+            if in_block && !caps.block_statements && !matches!(node, ASTNode::Print { .. }) {
+                return Err("statements other than prints inside `?()` blocks".to_string());
+            }
+            match node {
+                ASTNode::VarDeclaration { name, var_type: VarType::Float, .. } if !caps.floats => {
+                    return Err(format!("float variable '{}'", name));
+                }
+                ASTNode::VarDeclaration { name, var_type: VarType::Array(_), .. } if !caps.arrays => {
+                    return Err(format!("the array '{}'", name));
+                }
+                ASTNode::VarDeclaration { name, var_type: VarType::Map(..), .. } if !caps.maps => {
+                    return Err(format!("the map '{}'", name));
+                }
+                ASTNode::VarDeclaration { name, var_type: VarType::Record(_), .. } if !caps.records => {
+                    return Err(format!("the record '{}'", name));
+                }
+                ASTNode::RecordDeclaration(record) if !caps.records => {
+                    return Err(format!("the record type '{}'", record.name));
+                }
+                ASTNode::Input { name, .. } => match var_types.get(name) {
+                    Some(VarType::Int(_) | VarType::Float) if !caps.numeric_input => {
+                        return Err(format!("reading a number into '{}'", name));
+                    }
+                    Some(VarType::String | VarType::Char { .. }) if !caps.string_input => {
+                        return Err(format!("reading a string into '{}'", name));
+                    }
+                    _ => {}
+                },
+                _ if let Some(construct) = unsupported_in(node, caps, var_types) => {
+                    return Err(construct);
+                }
+                ASTNode::If { body, .. } => {
+                    if !caps.conditionals {
+                        return Err("`?()` conditionals".to_string());
+                    }
+                    walk(body, true, caps, var_types)?;
+                }
+                ASTNode::ForEach { body, .. } => walk(body, true, caps, var_types)?,
+                // Native functions only exist inside an embedding host (`ven::vm`)
+                ASTNode::Call { name, .. } => {
+                    return Err(format!("the native function call '{}'", name));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    let var_types: HashMap<String, VarType> = collect_var_types(ast).into_iter().collect();
+    let AST::Program(nodes) = ast;
+    walk(nodes, false, caps, &var_types).map_err(|construct| BackendError::Unsupported { target, construct })
+}
+
+/// Whether any of `nodes` converts a value or formats a placeholder, for the
+/// targets that emit their conversion helpers only when they are used.
+pub fn uses_conversions(nodes: &[ASTNode], var_types: &HashMap<String, VarType>) -> bool {
+    uses(nodes, Capabilities { conversions: false, ..Capabilities::ALL }, var_types)
+}
+
+/// Whether any of `nodes` uses a string operation, for the targets that emit
+/// their string helpers only when they are used.
+pub fn uses_strings(nodes: &[ASTNode], var_types: &HashMap<String, VarType>) -> bool {
+    uses(nodes, Capabilities { strings: false, ..Capabilities::ALL }, var_types)
+}
+
+/// Whether any of `nodes` uses `%`, `**` or a math function, for the targets
+/// that emit their math helpers only when they are used.
+pub fn uses_math(nodes: &[ASTNode], var_types: &HashMap<String, VarType>) -> bool {
+    fn math_in(expr: &Expression) -> bool {
+        match expr {
+            Expression::Math { .. } => true,
+            Expression::Convert { value: operand, .. }
+            | Expression::Not(operand)
+            | Expression::Index { index: operand, .. }
+            | Expression::In { key: operand, .. } => math_in(operand),
+            Expression::Text { args, .. } | Expression::List(args) => args.iter().any(math_in),
+            Expression::BinaryOp { left, right, .. } | Expression::LogicalOp { left, right, .. } => {
+                math_in(left) || math_in(right)
+            }
+            Expression::Record(values) => values.iter().any(|(_, value)| math_in(value)),
+            Expression::Literal(_) | Expression::Identifier(_) | Expression::Len(_) | Expression::Field { .. } => false,
+        }
+    }
+
+    nodes.iter().any(|node| match node {
+        ASTNode::MathOp { operator: MathOperator::Modulo | MathOperator::Power, .. } => true,
+        // Only printed text has placeholders
+        ASTNode::Print { expr: Some(Expression::Literal(literal)), .. } => {
+            split_interpolation(literal).into_iter().any(|segment| match segment {
+                Segment::Var(text) | Segment::Formatted(text, _) => {
+                    placeholder(&text, var_types).is_some_and(|expr| math_in(&expr))
+                }
+                Segment::Text(_) => false,
+            })
+        }
+        ASTNode::Print { expr, .. } | ASTNode::VarDeclaration { value: expr, .. } => expr.iter().any(math_in),
+        ASTNode::MathOp { operand, .. } | ASTNode::Append { value: operand, .. } => math_in(operand),
+        ASTNode::If { condition, body, .. } => math_in(condition) || uses_math(body, var_types),
+        ASTNode::ForEach { body, .. } => uses_math(body, var_types),
+        ASTNode::SetIndex { index, value, .. } => math_in(index) || math_in(value),
+        ASTNode::Call { args, .. } => args.iter().any(math_in),
+        ASTNode::Input { .. } | ASTNode::RecordDeclaration(_) => false,
+    })
+}
+
+/// Whether any of `nodes` uses `/`, `%` or `**`, for the targets that emit
+/// their division by zero checks only when they are used.
+pub fn uses_division(nodes: &[ASTNode]) -> bool {
+    nodes.iter().any(|node| match node {
+        ASTNode::MathOp { operator, .. } => {
+            matches!(operator, MathOperator::Divide | MathOperator::Modulo | MathOperator::Power)
+        }
+        ASTNode::If { body, .. } | ASTNode::ForEach { body, .. } => uses_division(body),
+        _ => false,
+    })
+}
+
+/// Whether any of `nodes` has something `caps` does not cover.
+fn uses(nodes: &[ASTNode], caps: Capabilities, var_types: &HashMap<String, VarType>) -> bool {
+    nodes.iter().any(|node| {
+        unsupported_in(node, caps, var_types).is_some()
+            || matches!(node, ASTNode::If { body, .. } | ASTNode::ForEach { body, .. }
+                if uses(body, caps, var_types))
+    })
+}
+
+/// The first conversion, format spec or string operation in `node` itself that
+/// `caps` does not cover, not counting the statements of its block.
+fn unsupported_in(node: &ASTNode, caps: Capabilities, var_types: &HashMap<String, VarType>) -> Option<String> {
+    fn find(expr: &Expression, caps: Capabilities, var_types: &HashMap<String, VarType>) -> Option<String> {
+        let find_in = |expr: &Expression| find(expr, caps, var_types);
+        match expr {
+            Expression::Convert { to, .. } if !caps.conversions => {
+                Some(format!("the conversion `{}()`", conversion_name(to)))
+            }
+            Expression::Convert { to: VarType::Float, .. } if !caps.floats => Some("the conversion `f()`".to_string()),
+            Expression::Text { op, .. } if !caps.strings => Some(format!("the string operation `{}()`", op.name())),
+            Expression::Math { op, args } if !caps.floats && math_kind(*op, args, var_types) == ValueKind::Float => {
+                Some(format!("the float result of `{}`", op.called()))
+            }
+            Expression::Len(name) if !caps.strings && is_text_var(name, var_types) => {
+                Some("the string operation `len()`".to_string())
+            }
+            Expression::Convert { value, .. } => find_in(value),
+            Expression::Text { args, .. } | Expression::Math { args, .. } | Expression::List(args) => {
+                args.iter().find_map(find_in)
+            }
+            Expression::BinaryOp { left, right, .. } | Expression::LogicalOp { left, right, .. } => {
+                find_in(left).or_else(|| find_in(right))
+            }
+            Expression::Not(operand) => find_in(operand),
+            Expression::Index { index, .. } => find_in(index),
+            Expression::In { key, .. } => find_in(key),
+            Expression::Record(values) => values.iter().find_map(|(_, value)| find_in(value)),
+            Expression::Literal(_) | Expression::Identifier(_) | Expression::Len(_) | Expression::Field { .. } => None,
+        }
+    }
+
+    let exprs: Vec<&Expression> = match node {
+        // Only printed text has placeholders
+        ASTNode::Print { expr: Some(Expression::Literal(literal)), .. } => {
+            for segment in split_interpolation(literal) {
+                let text = match segment {
+                    Segment::Formatted(text, spec) if !caps.conversions => {
+                        return Some(format!("the format spec in `{{{}:{}}}`", text, spec));
+                    }
+                    Segment::Var(text) | Segment::Formatted(text, _) => text,
+                    Segment::Text(_) => continue,
+                };
+                if let Some(construct) = placeholder(&text, var_types).and_then(|expr| find(&expr, caps, var_types)) {
+                    return Some(construct);
+                }
+            }
+            return None;
+        }
+        ASTNode::Print { expr, .. } | ASTNode::VarDeclaration { value: expr, .. } => expr.iter().collect(),
+        ASTNode::MathOp { operand, .. } => vec![operand],
+        ASTNode::If { condition, .. } => vec![condition],
+        ASTNode::SetIndex { index, value, .. } => vec![index, value],
+        ASTNode::Append { value, .. } => vec![value],
+        ASTNode::Call { args, .. } => args.iter().collect(),
+        ASTNode::Input { .. } | ASTNode::ForEach { .. } | ASTNode::RecordDeclaration(_) => Vec::new(),
+    };
+    exprs.into_iter().find_map(|expr| find(expr, caps, var_types))
+}
+
+/// How `i()`, `f()` or `str()` is written for a conversion to `to`.
+pub fn conversion_name(to: &VarType) -> &'static str {
+    match to {
+        VarType::Float => "f",
+        VarType::String => "str",
+        _ => "i",
+    }
+}
+
+/// The value of `i(text)`: optional spaces, tabs or line breaks around an
+/// optional sign and decimal digits, within `i64`. `None` for anything else,
+/// which every target reports as `cannot convert "text" to i`.
+pub fn convert_int(text: &str) -> Option<i64> {
+    let digits = trim_blank(text);
+    let unsigned = digits.strip_prefix(['+', '-']).unwrap_or(digits);
+    if unsigned.is_empty() || !unsigned.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// The value of `f(text)`: optional spaces, tabs or line breaks around a
+/// decimal number such as `-1.5`, `.5`, `2.` or `6.02e23`, that is finite as
+/// an `f`. `None` for anything else, `inf` and `nan` included.
+pub fn convert_float(text: &str) -> Option<f64> {
+    let number = trim_blank(text);
+    let unsigned = number.strip_prefix(['+', '-']).unwrap_or(number);
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(at) => (&unsigned[..at], Some(&unsigned[at + 1..])),
+        None => (unsigned, None),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    let exponent_ok = exponent.is_none_or(|exponent| {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        !exponent.is_empty() && digits(exponent)
+    });
+    if whole.len() + fraction.len() == 0 || !digits(whole) || !digits(fraction) || !exponent_ok {
+        return None;
+    }
+    number.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// `text` without the spaces, tabs and line breaks around it, as `trim()`
+/// gives it.
+pub fn trim_blank(text: &str) -> &str {
+    text.trim_matches([' ', '\t', '\n', '\r'])
+}
+
+/// The text of `slice(text, start, end)`: the characters from `start` up to,
+/// but not including, `end`. Both count characters from 0 and are clamped to
+/// the text, so a negative `start` is 0 and an `end` before `start` gives "".
+/// Every backend slices exactly this way.
+pub fn slice_text(text: &str, start: i128, end: i128) -> &str {
+    let offset = |index: i128| {
+        let index = index.max(0).min(usize::MAX as i128) as usize;
+        text.char_indices().nth(index).map_or(text.len(), |(offset, _)| offset)
+    };
+    let (start, end) = (offset(start), offset(end));
+    if end <= start { "" } else { &text[start..end] }
+}
+
+/// The text of `replace(text, from, to)`: every `from`, left to right and not
+/// overlapping, replaced with `to`. An empty `from` leaves the text as it is.
+pub fn replace_text(text: &str, from: &str, to: &str) -> String {
+    if from.is_empty() { text.to_string() } else { text.replace(from, to) }
+}
+
+/// Why `args` do not fit the string operation `op`, or `None` when they do.
+/// Names that are not declared are reported on their own and pass here.
+pub fn text_op_error(op: TextOp, args: &[Expression], var_types: &HashMap<String, VarType>) -> Option<String> {
+    let params: &[&str] = match op {
+        TextOp::Concat => &["text", "text", "..."],
+        TextOp::Len | TextOp::Upper | TextOp::Lower | TextOp::Trim => &["text"],
+        TextOp::Slice => &["text", "start", "end"],
+        TextOp::Contains => &["text", "part"],
+        TextOp::StartsWith => &["text", "prefix"],
+        TextOp::Replace => &["text", "from", "to"],
+    };
+    let fits = match op {
+        TextOp::Concat => args.len() >= 2,
+        _ => args.len() == params.len(),
+    };
+    if !fits {
+        return Some(format!(
+            "{}() takes ({}), found {} value{}",
+            op.name(),
+            params.join(", "),
+            args.len(),
+            if args.len() == 1 { "" } else { "s" }
+        ));
+    }
+    for (i, arg) in args.iter().enumerate() {
+        let declared = match arg {
+            Expression::Identifier(name)
+            | Expression::Index { name, .. }
+            | Expression::Len(name)
+            | Expression::Field { name, .. } => var_types.contains_key(name),
+            _ => true,
+        };
+        let found = match value_kind(arg, var_types) {
+            _ if !declared => continue,
+            ValueKind::Int => "an integer",
+            ValueKind::Float => "a float",
+            ValueKind::Str => "text",
+            ValueKind::Bool => "a bool",
+        };
+        let index = op == TextOp::Slice && i > 0;
+        if index && found != "an integer" {
+            return Some(format!("the {} of slice() must be an integer, found {}", params[i], found));
+        }
+        if !index && found != "text" {
+            return Some(format!("{}() works on text, found {}; convert it with str()", op.name(), found));
+        }
+    }
+    None
+}
+
+/// Why `args` do not fit the math function `op`, or `None` when they do.
+/// Names that are not declared are reported on their own and pass here.
+pub fn math_fn_error(op: MathFn, args: &[Expression], var_types: &HashMap<String, VarType>) -> Option<String> {
+    let (params, fits) = match op {
+        MathFn::Min | MathFn::Max => ("a, b, ...", args.len() >= 2),
+        _ => ("x", args.len() == 1),
+    };
+    if !fits {
+        return Some(format!(
+            "{}() takes ({}), found {} value{}",
+            op.name(),
+            params,
+            args.len(),
+            if args.len() == 1 { "" } else { "s" }
+        ));
+    }
+    for arg in args {
+        let declared = match arg {
+            Expression::Identifier(name)
+            | Expression::Index { name, .. }
+            | Expression::Len(name)
+            | Expression::Field { name, .. } => var_types.contains_key(name),
+            _ => true,
+        };
+        let found = match value_kind(arg, var_types) {
+            _ if !declared => continue,
+            ValueKind::Int | ValueKind::Float => continue,
+            ValueKind::Str => "text",
+            ValueKind::Bool => "a bool",
+        };
+        return Some(format!("{} works on numbers, found {}; convert it with i() or f()", op.called(), found));
+    }
+    None
+}
+
+/// The kind of value `op` of `args` gives: `sqrt()` is always a float, and the
+/// rest give a float when any of their values is one.
+pub fn math_kind(op: MathFn, args: &[Expression], var_types: &HashMap<String, VarType>) -> ValueKind {
+    let float = args.iter().any(|arg| value_kind(arg, var_types) == ValueKind::Float);
+    if op == MathFn::Sqrt || float { ValueKind::Float } else { ValueKind::Int }
+}
+
+/// Whether `name` is a `str` or `c[size]` variable.
+pub fn is_text_var(name: &str, var_types: &HashMap<String, VarType>) -> bool {
+    matches!(var_types.get(name), Some(VarType::String | VarType::Char { .. }))
+}
+
+/// How a `{name:spec}` placeholder lays out its value: `<` or `>` alignment,
+/// a width of up to three digits and a `.precision` of at most 20, each
+/// optional, as in `{price:.2}`, `{count:>5}` or `{total:<10.1}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatSpec {
+    /// `Some(true)` for `<`, `Some(false)` for `>`; numbers align right and
+    /// everything else left when it is left out.
+    pub left: Option<bool>,
+    /// The value is padded with spaces to this many characters.
+    pub width: usize,
+    /// Digits after the point for an `f` value, rounded half to even; ignored
+    /// for other values.
+    pub precision: Option<usize>,
+}
+
+impl FormatSpec {
+    pub fn parse(spec: &str) -> Option<FormatSpec> {
+        let (left, rest) = match spec.as_bytes().first() {
+            Some(b'<') => (Some(true), &spec[1..]),
+            Some(b'>') => (Some(false), &spec[1..]),
+            _ => (None, spec),
+        };
+        let (width, precision) = match rest.split_once('.') {
+            Some((width, precision)) => (width, Some(precision)),
+            None => (rest, None),
+        };
+        let number = |digits: &str, most: usize| {
+            (!digits.is_empty() && digits.len() <= 3 && digits.bytes().all(|b| b.is_ascii_digit()))
+                .then(|| digits.parse::<usize>().ok())
+                .flatten()
+                .filter(|&value| value <= most)
+        };
+        let width = if width.is_empty() { 0 } else { number(width, 999)? };
+        let precision = match precision {
+            Some(precision) => Some(number(precision, 20)?),
+            None => None,
+        };
+        (left.is_some() || width > 0 || precision.is_some()).then_some(FormatSpec { left, width, precision })
+    }
+
+    /// Whether the value is padded on the left, for a number when `numeric`.
+    pub fn right(&self, numeric: bool) -> bool {
+        self.left.map_or(numeric, |left| !left)
+    }
+}
+
+/// Writes the spec as it appears after the `:`.
+impl std::fmt::Display for FormatSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.left {
+            Some(true) => f.write_str("<")?,
+            Some(false) => f.write_str(">")?,
+            None => {}
+        }
+        if self.width > 0 {
+            write!(f, "{}", self.width)?;
+        }
+        match self.precision {
+            Some(precision) => write!(f, ".{}", precision),
+            None => Ok(()),
+        }
+    }
+}
+
+/// `text` padded with spaces to `width` characters, on the left when `right`.
+pub fn pad(text: &str, width: usize, right: bool) -> String {
+    let padding = " ".repeat(width.saturating_sub(text.chars().count()));
+    if right { padding + text } else { text.to_string() + &padding }
+}
+
+/// The placeholder text and spec of `{text:spec}`. A placeholder without a
+/// valid spec is returned whole.
+pub fn split_spec(placeholder: &str) -> (&str, Option<FormatSpec>) {
+    match placeholder.split_once(':') {
+        Some((text, spec)) if let Some(spec) = FormatSpec::parse(spec.trim()) => (text.trim(), Some(spec)),
+        _ => (placeholder, None),
+    }
+}
+
+/// A piece of a print literal after placeholder splitting.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// Plain text with escape sequences already resolved.
+    Text(String),
+    /// A `{name}` placeholder.
+    Var(String),
+    /// A `{name:spec}` placeholder, with the text before the `:`.
+    Formatted(String, FormatSpec),
+}
+
+/// Splits a print literal into text and `{placeholder}` segments.
+///
+/// The quotes delimiting (possibly merged) string chunks are dropped, `\n`, `\t`,
+/// `\r`, `\"` and `\\` are resolved, and backslashes in front of a brace turn it
+/// into literal text (`\{name\}` prints `{name}`).
+pub fn split_interpolation(literal: &str) -> Vec<Segment> {
+    let chars: Vec<char> = literal.chars().collect();
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => {
+                // A run of backslashes directly before a brace escapes the brace
+                let mut run_end = i;
+                while run_end < chars.len() && chars[run_end] == '\\' {
+                    run_end += 1;
+                }
+                if run_end < chars.len() && (chars[run_end] == '{' || chars[run_end] == '}') {
+                    text.push(chars[run_end]);
+                    i = run_end + 1;
+                    continue;
+                }
+                match chars.get(i + 1) {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some('"') => text.push('"'),
+                    Some('\\') => text.push('\\'),
+                    Some(other) => {
+                        text.push('\\');
+                        text.push(*other);
+                    }
+                    None => text.push('\\'),
+                }
+                i += 2;
+            }
+            '"' => i += 1,
+            '{' => {
+                let close = chars[i + 1..].iter().position(|&c| c == '}');
+                match close {
+                    Some(rel) => {
+                        let name: String = chars[i + 1..i + 1 + rel].iter().collect();
+                        let name = name.trim();
+                        if name.is_empty() {
+                            text.push_str("{}");
+                        } else {
+                            if !text.is_empty() {
+                                segments.push(Segment::Text(std::mem::take(&mut text)));
+                            }
+                            segments.push(match split_spec(name) {
+                                (name, Some(spec)) => Segment::Formatted(name.to_string(), spec),
+                                (name, None) => Segment::Var(name.to_string()),
+                            });
+                        }
+                        i += rel + 2;
+                    }
+                    None => {
+                        text.push('{');
+                        i += 1;
+                    }
+                }
+            }
+            c => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    segments
+}
+
+/// What a `{placeholder}` prints: a declared variable, `name[index]` or
+/// `len(name)` of a declared array with a literal or integer variable index,
+/// `name[key]` or `len(name)` of a declared map with a key variable of the
+/// key type, or a literal key of an `i` map, `name.field` of a declared
+/// record, `len(name)` of a declared text variable, or a conversion, string
+/// operation or math function of any of these but a whole array, map or
+/// record, and `-` in front of any of these. `None` prints as `[undefined]`.
+pub fn placeholder(text: &str, var_types: &HashMap<String, VarType>) -> Option<Expression> {
+    if var_types.contains_key(text) {
+        return Some(Expression::Identifier(text.to_string()));
+    }
+    if let Some(operand) = text.strip_prefix('-') {
+        let negate = Expression::Math { op: MathFn::Negate, args: vec![placeholder(operand.trim(), var_types)?] };
+        return access_placeholder(negate, var_types);
+    }
+    let tokens = Tokenizer::new(text).tokenize().to_vec();
+    let expr = parse_access(&tokens, 0, tokens.len(), text)?;
+    access_placeholder(expr, var_types)
+}
+
+/// What a `{name:spec}` placeholder prints: any placeholder but a whole array,
+/// map or record.
+pub fn formatted(text: &str, var_types: &HashMap<String, VarType>) -> Option<Expression> {
+    placeholder(text, var_types).filter(|expr| is_single(expr, var_types))
+}
+
+/// Whether `expr` is a single value rather than a whole array, map or record.
+fn is_single(expr: &Expression, var_types: &HashMap<String, VarType>) -> bool {
+    !matches!(
+        expr,
+        Expression::Identifier(name)
+            if matches!(var_types.get(name), Some(VarType::Array(_) | VarType::Map(..) | VarType::Record(_)))
+    )
+}
+
+fn access_placeholder(expr: Expression, var_types: &HashMap<String, VarType>) -> Option<Expression> {
+    let valid = |value: &Expression| {
+        let defined = match value {
+            Expression::Literal(_) => true,
+            Expression::Identifier(name) => var_types.contains_key(name),
+            value => access_placeholder(value.clone(), var_types).is_some(),
+        };
+        defined && is_single(value, var_types)
+    };
+    if let Expression::Convert { value, .. } = &expr {
+        return valid(value).then_some(expr);
+    }
+    if let Expression::Text { op, args } = &expr {
+        return (args.iter().all(valid) && text_op_error(*op, args, var_types).is_none()).then_some(expr);
+    }
+    if let Expression::Math { op, args } = &expr {
+        return (args.iter().all(valid) && math_fn_error(*op, args, var_types).is_none()).then_some(expr);
+    }
+    if let Expression::Field { name, field } = &expr {
+        return field_type(name, field, var_types).is_some().then_some(expr);
+    }
+    let (Expression::Index { name, .. } | Expression::Len(name)) = &expr else {
+        return None;
+    };
+    let key_kind = match var_types.get(name) {
+        Some(VarType::String | VarType::Char { .. }) if matches!(expr, Expression::Len(_)) => return Some(expr),
+        Some(VarType::Array(_)) => ValueKind::Int,
+        Some(VarType::Map(key, _)) => type_kind(key),
+        _ => return None,
+    };
+    match &expr {
+        Expression::Index { index, .. } => match index.as_ref() {
+            Expression::Literal(lit) if key_kind == ValueKind::Int && int_literal(lit).is_some() => Some(expr),
+            Expression::Identifier(index) if var_types.get(index).map(type_kind) == Some(key_kind) => Some(expr),
+            _ => None,
+        },
+        _ => Some(expr),
+    }
+}
+
+/// The type of `name[...]`: the element type of the array `name`, or the value
+/// type of the map `name`.
+pub fn element_type<'a>(name: &str, var_types: &'a HashMap<String, VarType>) -> Option<&'a VarType> {
+    match var_types.get(name) {
+        Some(VarType::Array(element) | VarType::Map(_, element)) => Some(element),
+        _ => None,
+    }
+}
+
+/// The type of `name.field`, when `name` is a record with that field.
+pub fn field_type<'a>(name: &str, field: &str, var_types: &'a HashMap<String, VarType>) -> Option<&'a VarType> {
+    match var_types.get(name) {
+        Some(VarType::Record(record)) => record.field(field),
+        _ => None,
+    }
+}
+
+/// The type of what `* name` or `* name.field` changes.
+pub fn target_type<'a>(
+    name: &str,
+    field: Option<&str>,
+    var_types: &'a HashMap<String, VarType>,
+) -> Option<&'a VarType> {
+    match field {
+        Some(field) => field_type(name, field, var_types),
+        None => var_types.get(name),
+    }
+}
+
+/// Whether `var_type` is a `c[size]` buffer or a record with one.
+pub fn has_buffer(var_type: &VarType) -> bool {
+    match var_type {
+        VarType::Char { .. } => true,
+        VarType::Record(record) => record.fields.iter().any(|(_, field_type)| has_buffer(field_type)),
+        _ => false,
+    }
+}
+
+/// The key and value types of the map `name`.
+pub fn map_types<'a>(name: &str, var_types: &'a HashMap<String, VarType>) -> Option<(&'a VarType, &'a VarType)> {
+    match var_types.get(name) {
+        Some(VarType::Map(key, value)) => Some((key, value)),
+        _ => None,
+    }
+}
+
+/// The integer type `expr` has when it is an integer variable or element.
+/// Lengths and integer math functions are `i64`.
+pub fn int_type_of(expr: &Expression, var_types: &HashMap<String, VarType>) -> Option<IntType> {
+    let var_type = match expr {
+        Expression::Identifier(name) => var_types.get(name),
+        Expression::Index { name, .. } => element_type(name, var_types),
+        Expression::Field { name, field } => field_type(name, field, var_types),
+        Expression::Len(_) | Expression::Text { op: TextOp::Len, .. } => return Some(IntType::I64),
+        Expression::Math { op, args } if math_kind(*op, args, var_types) == ValueKind::Int => {
+            return Some(IntType::I64);
+        }
+        // An integer converts to itself
+        Expression::Convert { to: VarType::Int(_), value } => {
+            return Some(int_type_of(value, var_types).unwrap_or(IntType::I64));
+        }
+        _ => None,
+    };
+    match var_type {
+        Some(VarType::Int(int_type)) => Some(*int_type),
+        _ => None,
+    }
+}
+
+/// Resolves escapes in a literal and keeps any `{placeholder}` text as written.
+pub fn literal_text(lit: &str) -> String {
+    split_interpolation(lit)
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text,
+            Segment::Var(name) => format!("{{{}}}", name),
+            Segment::Formatted(name, spec) => format!("{{{}:{}}}", name, spec),
+        })
+        .collect()
+}
+
+/// Numeric literals from the math parser may contain spaces between tokens ("1 . 5").
+pub fn clean_number(lit: &str) -> String {
+    lit.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// What an expression evaluates to, for the targets that need static types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Int,
+    Float,
+    /// `str` and `c[size]` values, and words that are not numbers.
+    Str,
+    /// Conditions and `b` values.
+    Bool,
+}
+
+/// Stands in for the code of a name that no declaration matches. Session
+/// rejects such a program with `V0001` before any backend sees it.
+pub(crate) fn undeclared(name: &str) -> ! {
+    unreachable!("'{}' is undeclared, and the name check rejects it before code generation", name)
+}
+
+/// The kind of value `expr` evaluates to.
+pub fn value_kind(expr: &Expression, var_types: &HashMap<String, VarType>) -> ValueKind {
+    match expr {
+        Expression::Literal(lit) => {
+            let number = clean_number(lit);
+            if lit.starts_with('"') || lit.starts_with('\'') {
+                ValueKind::Str
+            } else if number == "true" || number == "false" {
+                ValueKind::Bool
+            } else if int_literal(&number).is_some() {
+                ValueKind::Int
+            } else if number.parse::<f64>().is_ok() {
+                ValueKind::Float
+            } else {
+                ValueKind::Str
+            }
+        }
+        Expression::Identifier(name) => var_types.get(name).map_or(ValueKind::Int, type_kind),
+        Expression::Index { name, .. } => element_type(name, var_types).map_or(ValueKind::Int, type_kind),
+        Expression::Field { name, field } => field_type(name, field, var_types).map_or(ValueKind::Int, type_kind),
+        // Whole arrays, maps and records are only ever printed, which every target checks for first
+        Expression::Len(_) | Expression::List(_) | Expression::Record(_) => ValueKind::Int,
+        Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) | Expression::In { .. } => {
+            ValueKind::Bool
+        }
+        Expression::Convert { to, .. } => type_kind(to),
+        Expression::Text { op, .. } => type_kind(&op.result()),
+        Expression::Math { op, args } => math_kind(*op, args, var_types),
+    }
+}
+
+/// The kind of value a variable of `var_type` holds.
+pub fn type_kind(var_type: &VarType) -> ValueKind {
+    match var_type {
+        VarType::Float => ValueKind::Float,
+        VarType::String | VarType::Char { .. } => ValueKind::Str,
+        VarType::Bool => ValueKind::Bool,
+        VarType::Int(_) | VarType::Array(_) | VarType::Map(..) | VarType::Record(_) => ValueKind::Int,
+    }
+}
+
+/// Collects every declared record type, including declarations nested in blocks.
+pub fn record_types(ast: &AST) -> Vec<RecordType> {
+    fn walk(nodes: &[ASTNode], records: &mut Vec<RecordType>) {
+        for node in nodes {
+            match node {
+                ASTNode::RecordDeclaration(record) => records.push(record.clone()),
+                ASTNode::If { body, .. } | ASTNode::ForEach { body, .. } => walk(body, records),
+                _ => {}
+            }
+        }
+    }
+
+    let mut records = Vec::new();
+    let AST::Program(nodes) = ast;
+    walk(nodes, &mut records);
+    records
+}
+
+/// Collects every declared variable with its type, including declarations nested in
+/// blocks and `~()` loop items, in order of first declaration.
+pub fn collect_var_types(ast: &AST) -> Vec<(String, VarType)> {
+    fn walk(nodes: &[ASTNode], vars: &mut Vec<(String, VarType)>) {
+        for node in nodes {
+            match node {
+                ASTNode::VarDeclaration { name, var_type, .. }
+                    if !vars.iter().any(|(existing, _)| existing == name) =>
+                {
+                    vars.push((name.clone(), var_type.clone()));
+                }
+                ASTNode::If { body, .. } => walk(body, vars),
+                ASTNode::ForEach { item, array, body } => {
+                    let element = vars.iter().find_map(|(name, var_type)| match var_type {
+                        VarType::Array(element) if name == array => Some((**element).clone()),
+                        _ => None,
+                    });
+                    if let Some(element) = element
+                        && !vars.iter().any(|(existing, _)| existing == item)
+                    {
+                        vars.push((item.clone(), element));
+                    }
+                    walk(body, vars);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut vars = Vec::new();
+    let AST::Program(nodes) = ast;
+    walk(nodes, &mut vars);
+    vars
+}
@@ -1,94 +1,466 @@
 use crate::errs::VarError;
-use crate::parse::{AST, ASTNode, Expression, VarType};
+use crate::parse::{AST, ASTNode, Expression, MathFn, RecordType, TextOp, VarType};
+use crate::token::{Token, TokenKind, Tokenizer};
+use crate::transpilers::{Segment, clean_number, conversion_name, literal_text, split_interpolation};
 use std::borrow::Cow;
 use std::collections::HashMap;
 
 pub type VarMap = HashMap<String, (VarType, usize)>;
 
+/// Record types by name, as declared with `#`.
+pub type TypeTable = HashMap<String, RecordType>;
+
+/// Checks declaration initializers against their declared types, including
+/// declarations nested in blocks. `var_map` comes from the parser, which records
+/// each variable's declaration line.
 #[inline(always)]
 pub fn check_variables(ast: &AST, var_map: &mut VarMap) -> Vec<VarError> {
     let mut errors = Vec::new();
+    let mut types = TypeTable::new();
     let AST::Program(ref nodes) = *ast;
+    check_nodes(nodes, var_map, &mut types, &mut errors);
+    errors
+}
 
+fn check_nodes(nodes: &[ASTNode], var_map: &mut VarMap, types: &mut TypeTable, errors: &mut Vec<VarError>) {
     for node in nodes {
-        if let ASTNode::VarDeclaration {
-            ref name,
-            ref var_type,
-            ref value,
-            ..
-        } = *node
-        {
-            let line = 0; // Placeholder for line number
-            let var_type = var_type; // Copy instead of cloning
-            var_map.insert(name.clone(), (var_type.clone(), line));
-
-            if let Some(ref expr) = *value {
-                let result = match (var_type, expr) {
-                    (VarType::Int, Expression::Literal(lit)) => {
-                        lit.parse::<i32>()
-                            .is_err()
-                            .then(|| VarError::TypeMismatch {
-                                expected: "int".into(),
-                                found: lit.clone(),
-                                line,
-                            })
-                    }
+        match *node {
+            ASTNode::RecordDeclaration(ref record) => {
+                types.insert(record.name.clone(), record.clone());
+            }
+            ASTNode::VarDeclaration {
+                ref name,
+                ref var_type,
+                ref value,
+                ..
+            } => {
+                let line = var_map.get(name).map(|(_, line)| *line).unwrap_or(0);
+                var_map
+                    .entry(name.clone())
+                    .or_insert_with(|| (var_type.clone(), line));
 
-                    (VarType::Float, Expression::Literal(lit)) => {
-                        lit.parse::<f64>()
-                            .is_err()
-                            .then(|| VarError::TypeMismatch {
-                                expected: "float".into(),
-                                found: lit.clone(),
-                                line,
-                            })
+                if let Some(ref expr) = *value
+                    && let Some(err) = check_initializer(var_type, expr, var_map, types, line)
+                {
+                    errors.push(err);
+                }
+            }
+            ASTNode::If { ref body, .. } | ASTNode::ForEach { ref body, .. } => {
+                check_nodes(body, var_map, types, errors)
+            }
+            // A valid spec splits off its placeholder, so a `:` left in one starts a spec that is not
+            ASTNode::Print { expr: Some(Expression::Literal(ref text)), line, .. } if text.starts_with('"') => {
+                for segment in split_interpolation(text) {
+                    if let Segment::Var(inner) = segment
+                        && let Some((_, spec)) = inner.split_once(':')
+                    {
+                        errors.push(VarError::InvalidFormatSpec { spec: spec.trim().to_string(), line });
                     }
+                }
+            }
+            _ => {}
+        }
+    }
+}
 
-                    (VarType::String, Expression::Literal(lit)) => (!lit.starts_with('\"')
-                        || !lit.ends_with('\"'))
-                    .then(|| VarError::TypeMismatch {
-                        expected: "string literal enclosed in double quotes".into(),
-                        found: lit.clone(),
-                        line,
-                    }),
-
-                    (VarType::Char { size }, Expression::Literal(lit)) => {
-                        if lit.starts_with('\'') && lit.ends_with('\'') {
-                            let stripped = strip_char_quotes(lit);
-                            (stripped.chars().count() != *size).then(|| VarError::TypeMismatch {
-                                expected: format!("char literal of size {}", size),
-                                found: format!("char literal of size {}", stripped.chars().count()),
-                                line,
-                            })
-                        } else {
-                            Some(VarError::TypeMismatch {
-                                expected: "char literal enclosed in single quotes".into(),
-                                found: lit.clone(),
-                                line,
-                            })
-                        }
-                    }
+/// A declaration or a use of a name, in source order.
+enum Event {
+    Declare { name: String, mutable: bool },
+    Use { name: String, line: usize, write: bool },
+}
 
-                    _ => Some(VarError::TypeMismatch {
-                        expected: format!("{:?}", var_type),
-                        found: format!("{:?}", expr),
-                        line,
-                    }),
-                };
+/// Checks every name the program uses: each must match a declaration, and an
+/// `@` variable cannot be changed by math, a store or a call's `-> name`.
+/// Variables are program-wide, so a name refers to its latest declaration
+/// before the use, otherwise to the first one after it. Errors already in
+/// `reported` are not reported again, and a name whose declaration the parser
+/// dropped with one of them is not reported as undeclared.
+pub fn check_names(ast: &AST, tokens: &[Token], source: &str, reported: &[VarError]) -> Vec<VarError> {
+    let mut events = Vec::new();
+    let AST::Program(ref nodes) = *ast;
+    name_events(nodes, &mut events);
+    let dropped = dropped_declarations(tokens, source, reported);
 
-                if let Some(err) = result {
-                    errors.push(err);
-                    var_map.insert(name.clone(), (VarType::Int, line));
-                }
+    let mut errors: Vec<VarError> = Vec::new();
+    for (at, event) in events.iter().enumerate() {
+        let Event::Use { name, line, write } = event else { continue };
+        let declared = |event: &Event| matches!(event, Event::Declare { name: declared, .. } if declared == name);
+        let declaration = events[..at].iter().rev().find(|event| declared(event)).or_else(|| events.iter().find(|event| declared(event)));
+        let error = match declaration {
+            None if dropped.contains(name) => continue,
+            None => VarError::UndeclaredVariable { name: name.clone(), line: *line },
+            Some(Event::Declare { mutable: false, .. }) if *write => {
+                VarError::ImmutableAssignment { name: name.clone(), line: *line }
             }
+            Some(_) => continue,
+        };
+        let same = |other: &VarError| other.code() == error.code() && other.line() == error.line() && other.to_string() == error.to_string();
+        if !reported.iter().chain(&errors).any(same) {
+            errors.push(error);
         }
     }
     errors
 }
 
+/// Names declared by `@`/`@@` statements on lines that already have an error.
+fn dropped_declarations(tokens: &[Token], source: &str, reported: &[VarError]) -> Vec<String> {
+    let text = |token: &Token| source.get(token.start..token.end).unwrap_or("");
+    let tokens: Vec<&Token> = tokens.iter().filter(|t| t.kind == TokenKind::Newline || !text(t).trim().is_empty()).collect();
+    let mut names = Vec::new();
+    let mut line = 1;
+    let mut statement_start = true;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.kind {
+            TokenKind::Newline => {
+                line += 1;
+                statement_start = true;
+                continue;
+            }
+            TokenKind::At if statement_start => {
+                let at = if tokens.get(i + 1).map(|t| t.kind) == Some(TokenKind::At) { i + 2 } else { i + 1 };
+                if let Some(name) = tokens.get(at).filter(|t| t.kind == TokenKind::Identifier)
+                    && reported.iter().any(|error| error.line() == line)
+                {
+                    names.push(text(name).to_string());
+                }
+            }
+            _ => {}
+        }
+        statement_start = false;
+    }
+    names
+}
+
+fn name_events(nodes: &[ASTNode], events: &mut Vec<Event>) {
+    let uses = |names: Vec<String>, line: usize, events: &mut Vec<Event>| {
+        events.extend(names.into_iter().map(|name| Event::Use { name, line, write: false }));
+    };
+    let write = |name: &str, line: usize, events: &mut Vec<Event>| {
+        events.push(Event::Use { name: name.to_string(), line, write: true });
+    };
+    for node in nodes {
+        match node {
+            ASTNode::VarDeclaration { mutable, name, value, line, .. } => {
+                events.push(Event::Declare { name: name.clone(), mutable: *mutable });
+                // An initializer from another variable is checked with the initializer
+                if let Some(value) = value
+                    && !matches!(value, Expression::Identifier(_))
+                {
+                    uses(expression_names(value), *line, events);
+                }
+            }
+            ASTNode::Input { name, line } => uses(vec![name.clone()], *line, events),
+            // A bare word prints itself
+            ASTNode::Print { expr: Some(Expression::Identifier(_)), .. } | ASTNode::Print { expr: None, .. } => {}
+            ASTNode::Print { expr: Some(Expression::Literal(text)), line, .. } if text.starts_with('"') => {
+                for segment in split_interpolation(text) {
+                    if let Segment::Var(inner) | Segment::Formatted(inner, _) = segment {
+                        uses(placeholder_names(&inner), *line, events);
+                    }
+                }
+            }
+            ASTNode::Print { expr: Some(expr), line, .. } => uses(expression_names(expr), *line, events),
+            ASTNode::MathOp { name, operand, line, .. } => {
+                write(name, *line, events);
+                uses(expression_names(operand), *line, events);
+            }
+            ASTNode::If { condition, body, line } => {
+                uses(expression_names(condition), *line, events);
+                name_events(body, events);
+            }
+            ASTNode::Call { args, into, line, .. } => {
+                for arg in args {
+                    uses(expression_names(arg), *line, events);
+                }
+                if let Some(into) = into {
+                    write(into, *line, events);
+                }
+            }
+            ASTNode::SetIndex { name, index, value, line } => {
+                write(name, *line, events);
+                uses(expression_names(index), *line, events);
+                uses(expression_names(value), *line, events);
+            }
+            ASTNode::Append { name, value, line } => {
+                write(name, *line, events);
+                uses(expression_names(value), *line, events);
+            }
+            // The parser has already resolved the array
+            ASTNode::ForEach { item, body, .. } => {
+                events.push(Event::Declare { name: item.clone(), mutable: false });
+                name_events(body, events);
+            }
+            ASTNode::RecordDeclaration(_) => {}
+        }
+    }
+}
+
+/// The variables `expr` reads, in order.
+fn expression_names(expr: &Expression) -> Vec<String> {
+    let mut names = Vec::new();
+    collect_names(expr, &mut names);
+    names
+}
+
+fn collect_names(expr: &Expression, names: &mut Vec<String>) {
+    match expr {
+        Expression::Literal(_) => {}
+        Expression::Identifier(name) | Expression::Len(name) | Expression::Field { name, .. } => names.push(name.clone()),
+        Expression::BinaryOp { left, right, .. } | Expression::LogicalOp { left, right, .. } => {
+            collect_names(left, names);
+            collect_names(right, names);
+        }
+        Expression::Not(value) | Expression::Convert { value, .. } => collect_names(value, names),
+        Expression::List(items) | Expression::Text { args: items, .. } | Expression::Math { args: items, .. } => {
+            items.iter().for_each(|item| collect_names(item, names))
+        }
+        Expression::Index { name, index } => {
+            names.push(name.clone());
+            collect_names(index, names);
+        }
+        Expression::In { key, name } => {
+            collect_names(key, names);
+            names.push(name.clone());
+        }
+        Expression::Record(values) => values.iter().for_each(|(_, value)| collect_names(value, names)),
+    }
+}
+
+/// Whether `word` names a conversion, a string operation or a math function
+/// when a `(` follows.
+pub(crate) fn is_builtin(word: &str) -> bool {
+    matches!(word, "i" | "f" | "str") || TextOp::from_name(word).is_some() || MathFn::from_name(word).is_some()
+}
+
+/// The variables a `{placeholder}` reads: every name in an element
+/// `{name[index]}`, a call such as `{len(name)}` or a field `{name.field}`,
+/// otherwise the whole text, which names one variable. `{-name}` reads what
+/// it negates.
+fn placeholder_names(inner: &str) -> Vec<String> {
+    // An invalid spec is reported on its own
+    let inner = inner.split_once(':').map_or(inner, |(name, _)| name).trim();
+    let inner = inner.strip_prefix('-').map_or(inner, str::trim);
+    let tokens: Vec<Token> = Tokenizer::new(inner).tokenize().to_vec();
+    let lexeme = |token: &Token| inner.get(token.start..token.end).unwrap_or("");
+    let access = match tokens.as_slice() {
+        [name, open, ..] if name.kind == TokenKind::Identifier => {
+            open.kind == TokenKind::LBigB
+                || (is_builtin(lexeme(name)) && open.kind == TokenKind::LSmallB)
+                || lexeme(open) == "."
+        }
+        _ => false,
+    };
+    if !access {
+        return if inner.is_empty() { Vec::new() } else { vec![inner.to_string()] };
+    }
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|&(i, token)| {
+            let word = lexeme(token);
+            let call = is_builtin(word) && tokens.get(i + 1).map(|t| t.kind) == Some(TokenKind::LSmallB);
+            let field = i > 0 && lexeme(&tokens[i - 1]) == ".";
+            token.kind == TokenKind::Identifier && is_name(word) && !call && !field
+        })
+        .map(|(_, token)| lexeme(token).to_string())
+        .collect()
+}
+
+/// Whether `word` can be a variable's name.
+pub(crate) fn is_name(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') && !matches!(word, "true" | "false" | "in")
+}
+
+fn check_initializer(
+    var_type: &VarType,
+    expr: &Expression,
+    var_map: &VarMap,
+    types: &TypeTable,
+    line: usize,
+) -> Option<VarError> {
+    match (var_type, expr) {
+        (VarType::Int(int_type), Expression::Literal(lit)) => match clean_number(lit).parse::<i128>() {
+            Err(_) => Some(VarError::TypeMismatch {
+                expected: "int".into(),
+                found: lit.clone(),
+                line,
+            }),
+            Ok(value) => (!(int_type.min()..=int_type.max()).contains(&value)).then(|| VarError::OutOfRange {
+                value: clean_number(lit),
+                var_type: *int_type,
+                line,
+            }),
+        },
+
+        (VarType::Float, Expression::Literal(lit)) => {
+            clean_number(lit)
+                .parse::<f64>()
+                .is_err()
+                .then(|| VarError::TypeMismatch {
+                    expected: "float".into(),
+                    found: lit.clone(),
+                    line,
+                })
+        }
+
+        // The parser has already stripped the quotes
+        (VarType::String, Expression::Literal(_)) => None,
+
+        (VarType::Char { size }, Expression::Literal(lit)) => {
+            // A `c[size]` holds `size` bytes, so a multi-byte character counts for each byte
+            let length = literal_text(&strip_char_quotes(lit)).len();
+            (length > *size).then(|| VarError::TypeMismatch {
+                expected: format!("char literal of at most {} bytes", size),
+                found: format!("char literal of {} bytes", length),
+                line,
+            })
+        }
+
+        (VarType::Bool, Expression::Literal(lit)) => {
+            (lit != "true" && lit != "false").then(|| VarError::TypeMismatch {
+                expected: "bool".into(),
+                found: lit.clone(),
+                line,
+            })
+        }
+
+        // Conditions are checked when they are parsed
+        (
+            VarType::Bool,
+            Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) | Expression::In { .. },
+        ) => None,
+
+        // Each element is checked like a declaration of the element type
+        (VarType::Array(element), Expression::List(items)) => items
+            .iter()
+            .find_map(|item| check_initializer(element, &strip_item_quotes(element, item), var_map, types, line)),
+
+        // Each field is checked like a declaration of the field type, against the
+        // fields the type was declared with
+        (VarType::Record(record), Expression::Record(values)) => {
+            let fields = types.get(&record.name).map_or(&record.fields, |declared| &declared.fields);
+            values.iter().find_map(|(field, value)| {
+                let field_type = fields.iter().find(|(name, _)| name == field).map(|(_, field_type)| field_type)?;
+                check_initializer(field_type, &strip_item_quotes(field_type, value), var_map, types, line)
+            })
+        }
+
+        // The parser has already checked that the record has the field
+        (_, Expression::Field { name, field }) => match var_map.get(name) {
+            Some((VarType::Record(record), _)) => {
+                let field_type = types.get(&record.name).unwrap_or(record).field(field)?;
+                (family(var_type) != family(field_type)).then(|| VarError::TypeMismatch {
+                    expected: var_type.to_string(),
+                    found: format!("{} field '{}.{}'", field_type, name, field),
+                    line,
+                })
+            }
+            _ => None,
+        },
+
+        // `{}`, the only map initializer
+        (VarType::Map(..), Expression::List(items)) if items.is_empty() => None,
+
+        // The parser has already checked that `name` is an array or a map
+        (_, Expression::Index { name, .. }) => match var_map.get(name) {
+            Some((VarType::Array(element), _)) if family(var_type) != family(element) => {
+                Some(VarError::TypeMismatch {
+                    expected: var_type.to_string(),
+                    found: format!("{} element of '{}'", element, name),
+                    line,
+                })
+            }
+            Some((VarType::Map(_, value), _)) if family(var_type) != family(value) => Some(VarError::TypeMismatch {
+                expected: var_type.to_string(),
+                found: format!("{} value of '{}'", value, name),
+                line,
+            }),
+            _ => None,
+        },
+        // A length is a number, of an array, a map or text alike
+        (_, Expression::Len(name)) => (family(var_type) != "number").then(|| VarError::TypeMismatch {
+            expected: var_type.to_string(),
+            found: format!("len({})", name),
+            line,
+        }),
+
+        // `i()` and `f()` give a number and `str()` text, whatever they convert
+        (_, Expression::Convert { to, .. }) => (family(var_type) != family(to)).then(|| VarError::TypeMismatch {
+            expected: var_type.to_string(),
+            found: format!("the conversion {}()", conversion_name(to)),
+            line,
+        }),
+
+        // `len()` gives a number, `contains()` and `starts_with()` a boolean and the
+        // other string operations text
+        (_, Expression::Text { op, .. }) => {
+            (family(var_type) != family(&op.result())).then(|| VarError::TypeMismatch {
+                expected: var_type.to_string(),
+                found: format!("the string operation {}()", op.name()),
+                line,
+            })
+        }
+
+        // `-x` and the math functions give a number
+        (_, Expression::Math { op, .. }) => (family(var_type) != "number").then(|| VarError::TypeMismatch {
+            expected: var_type.to_string(),
+            found: match op {
+                MathFn::Negate => "a negated value".to_string(),
+                _ => format!("the math function {}", op.called()),
+            },
+            line,
+        }),
+
+        // Initialized from another variable: numbers from numbers, text from text,
+        // booleans from booleans
+        (_, Expression::Identifier(other)) => match var_map.get(other) {
+            None => Some(VarError::UndeclaredVariable {
+                name: other.clone(),
+                line,
+            }),
+            Some((other_type, _)) if family(var_type) != family(other_type) => {
+                Some(VarError::TypeMismatch {
+                    expected: var_type.to_string(),
+                    found: format!("{} variable '{}'", other_type, other),
+                    line,
+                })
+            }
+            Some(_) => None,
+        },
+
+        _ => Some(VarError::TypeMismatch {
+            expected: var_type.to_string(),
+            found: format!("{:?}", expr),
+            line,
+        }),
+    }
+}
+
+/// Variables of the same family can initialize each other.
+fn family(var_type: &VarType) -> &'static str {
+    match var_type {
+        VarType::Int(_) | VarType::Float => "number",
+        VarType::String | VarType::Char { .. } => "text",
+        VarType::Bool => "bool",
+        VarType::Array(_) => "array",
+        VarType::Map(..) => "map",
+        VarType::Record(_) => "record",
+    }
+}
+
+/// List elements keep their quotes; declarations of `str` do not.
+fn strip_item_quotes(element: &VarType, item: &Expression) -> Expression {
+    match (element, item) {
+        (VarType::String, Expression::Literal(lit)) if lit.len() >= 2 && lit.starts_with('"') && lit.ends_with('"') => {
+            Expression::Literal(lit[1..lit.len() - 1].to_string())
+        }
+        _ => item.clone(),
+    }
+}
+
 #[inline(always)]
 #[allow(dead_code)]
-fn strip_quotes(s: &str) -> Cow<str> {
+fn strip_quotes(s: &str) -> Cow<'_, str> {
     if s.len() >= 2 && s.starts_with('\"') && s.ends_with('\"') {
         Cow::Borrowed(&s[1..s.len() - 1])
     } else {
@@ -97,7 +469,7 @@ fn strip_quotes(s: &str) -> Cow<str> {
 }
 
 #[inline(always)]
-fn strip_char_quotes(s: &str) -> Cow<str> {
+fn strip_char_quotes(s: &str) -> Cow<'_, str> {
     if s.len() >= 2 && s.starts_with('\'') && s.ends_with('\'') {
         Cow::Borrowed(&s[1..s.len() - 1])
     } else {
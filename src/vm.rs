@@ -0,0 +1,949 @@
+//! Runs Ven programs in-process, for hosts that embed the language.
+//!
+//! The host picks the streams the program reads and prints to, binds variables
+//! before the run, registers native functions the script can call with
+//! `name(args)` or `name(args) -> var`, and reads variables back afterwards.
+//!
+//! ```
+//! use ven::vm::{Value, Vm};
+//!
+//! let mut out = Vec::new();
+//! let mut vm = Vm::new().with_stdout(&mut out);
+//! vm.set("name", Value::Str("Alice".into()));
+//! vm.register("shout", |args| Ok(Some(Value::Str(args[0].to_string().to_uppercase()))));
+//! vm.eval("@@ loud str\nshout(name) -> loud\n>> \"Hi {loud}\"\n").unwrap();
+//! assert_eq!(vm.get("loud"), Some(&Value::Str("ALICE".into())));
+//! drop(vm);
+//! assert_eq!(out, b"Hi ALICE\n");
+//! ```
+//!
+//! Programs run as parsed, without the optimisation passes, so variables the
+//! script never reads are still there for the host afterwards. Prints, input,
+//! comparisons and math follow the transpiled targets.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use crate::errs::RuntimeError;
+use crate::parse::{
+    AST, ASTNode, ComparisonOperator, Expression, IntType, LogicalOperator, MathFn, MathOperator, RecordType,
+    TextOp, VarType,
+};
+use crate::token::Tokenizer;
+use crate::transpilers::{
+    FormatSpec, Overflow, STR_CAPACITY, Segment, clean_number, convert_float, convert_int, fit, fit_float, fit_text,
+    float_math_fn, formatted, int_literal, int_math, int_math_fn, literal_text, pad, placeholder, replace_text,
+    slice_text, split_interpolation, trim_blank,
+};
+
+/// A value held by a variable or passed to a native function.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    /// Used for the unsigned types, `u8` to `u64`.
+    UInt(u64),
+    Float(f64),
+    /// Used for both `str` and `c[size]` variables.
+    Str(String),
+    Bool(bool),
+    /// A `T[]` variable; every element has the type `T`.
+    Array(Vec<Value>),
+    /// A `map[K]V` variable as its entries, in insertion order.
+    Map(Vec<(Value, Value)>),
+    /// A variable of the record type `name`, with its fields in declaration order.
+    Record { name: String, fields: Vec<(String, Value)> },
+}
+
+/// The entries of a map, in insertion order.
+type Entries = [(Value, Value)];
+
+impl Value {
+    fn var_type(&self) -> VarType {
+        match self {
+            Value::Int(_) => VarType::Int(IntType::I64),
+            Value::UInt(_) => VarType::Int(IntType::U64),
+            Value::Float(_) => VarType::Float,
+            Value::Str(_) => VarType::String,
+            Value::Bool(_) => VarType::Bool,
+            Value::Array(items) => VarType::Array(Box::new(
+                items.first().map_or(VarType::Int(IntType::I64), Value::var_type),
+            )),
+            Value::Map(entries) => {
+                let (key, value) = entries.first().map_or(
+                    (VarType::String, VarType::Int(IntType::I64)),
+                    |(key, value)| (key.var_type(), value.var_type()),
+                );
+                VarType::Map(Box::new(key), Box::new(value))
+            }
+            Value::Record { name, fields } => VarType::Record(RecordType {
+                name: name.clone(),
+                fields: fields.iter().map(|(field, value)| (field.clone(), value.var_type())).collect(),
+            }),
+        }
+    }
+
+    /// Converts to the representation of `var_type`. Text that is not a number
+    /// becomes 0 and text other than `true` becomes false, as with `..` on the
+    /// other targets, and text is cut to fit a `c[size]`. `None` when an integer
+    /// does not fit and `overflow` traps.
+    fn convert(self, var_type: &VarType, overflow: Overflow) -> Option<Value> {
+        Some(match (var_type, self) {
+            (VarType::Int(int_type), Value::Float(x)) => int_value(fit_float(x, *int_type, overflow)?, *int_type),
+            (VarType::Int(int_type), Value::Str(s)) => int_value(fit(parse_int(&s), *int_type, overflow)?, *int_type),
+            (VarType::Int(int_type), value) => {
+                int_value(fit(value.as_int().unwrap_or(0), *int_type, overflow)?, *int_type)
+            }
+            (VarType::Float, Value::Str(s)) => Value::Float(s.trim().parse().unwrap_or(0.0)),
+            (VarType::Float, value) => Value::Float(as_f64(&value)),
+            (VarType::Char { size }, value) => Value::Str(fit_text(&value.to_string(), *size).to_string()),
+            (VarType::String, value) => Value::Str(fit_text(&value.to_string(), STR_CAPACITY).to_string()),
+            (VarType::Bool, Value::Str(s)) => Value::Bool(s.trim() == "true"),
+            (VarType::Bool, value) => Value::Bool(value.truthy()),
+            (VarType::Array(element), Value::Array(items)) => Value::Array(
+                items
+                    .into_iter()
+                    .map(|item| item.convert(element, overflow))
+                    .collect::<Option<_>>()?,
+            ),
+            (VarType::Array(_), _) => Value::Array(Vec::new()),
+            (VarType::Map(key_type, value_type), Value::Map(entries)) => Value::Map(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| Some((key.convert(key_type, overflow)?, value.convert(value_type, overflow)?)))
+                    .collect::<Option<_>>()?,
+            ),
+            (VarType::Map(..), _) => Value::Map(Vec::new()),
+            (VarType::Record(record), Value::Record { mut fields, .. }) => Value::Record {
+                name: record.name.clone(),
+                fields: record
+                    .fields
+                    .iter()
+                    .map(|(field, field_type)| {
+                        let value = match fields.iter().position(|(given, _)| given == field) {
+                            Some(i) => fields.swap_remove(i).1.convert(field_type, overflow)?,
+                            None => default_value(field_type),
+                        };
+                        Some((field.clone(), value))
+                    })
+                    .collect::<Option<_>>()?,
+            },
+            (VarType::Record(_), _) => default_value(var_type),
+        })
+    }
+
+    /// The exact value of an integer or boolean.
+    fn as_int(&self) -> Option<i128> {
+        match self {
+            Value::Int(n) => Some(*n as i128),
+            Value::UInt(n) => Some(*n as i128),
+            Value::Bool(b) => Some(*b as i128),
+            Value::Float(_) | Value::Str(_) | Value::Array(_) | Value::Map(_) | Value::Record { .. } => None,
+        }
+    }
+
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Int(n) => *n != 0,
+            Value::UInt(n) => *n != 0,
+            Value::Float(x) => *x != 0.0,
+            Value::Str(s) => !s.is_empty(),
+            Value::Bool(b) => *b,
+            Value::Array(items) => !items.is_empty(),
+            Value::Map(entries) => !entries.is_empty(),
+            Value::Record { .. } => true,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::UInt(n) => write!(f, "{}", n),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Str(s) => f.write_str(s),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Array(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            }
+            Value::Map(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", key, value)?;
+                }
+                f.write_str("}")
+            }
+            Value::Record { fields, .. } => {
+                f.write_str("{")?;
+                for (i, (field, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", field, value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+impl From<i64> for Value {
+    fn from(n: i64) -> Self {
+        Value::Int(n)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(n: u64) -> Self {
+        Value::UInt(n)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(x: f64) -> Self {
+        Value::Float(x)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Str(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Str(s)
+    }
+}
+
+/// A host function callable from scripts. `Ok(None)` means it returns nothing.
+pub type NativeFn<'a> = Box<dyn FnMut(&[Value]) -> Result<Option<Value>, String> + 'a>;
+
+/// A variable as the VM holds it.
+#[derive(Debug, Clone)]
+pub struct Variable {
+    pub value: Value,
+    /// Declared type, or the type of the value for host-bound variables.
+    pub var_type: VarType,
+    pub mutable: bool,
+}
+
+/// An in-process interpreter. Variables persist across `run` calls.
+pub struct Vm<'a> {
+    vars: HashMap<String, Variable>,
+    natives: HashMap<String, NativeFn<'a>>,
+    /// `None` reads from the process's stdin, sharing its buffer with the host.
+    stdin: Option<Box<dyn BufRead + 'a>>,
+    stdout: Box<dyn Write + 'a>,
+    stderr: Box<dyn Write + 'a>,
+    overflow: Overflow,
+}
+
+impl Default for Vm<'_> {
+    fn default() -> Self {
+        Vm::new()
+    }
+}
+
+impl<'a> Vm<'a> {
+    /// A VM on the process's stdin, stdout and stderr.
+    pub fn new() -> Vm<'a> {
+        Vm {
+            vars: HashMap::new(),
+            natives: HashMap::new(),
+            stdin: None,
+            stdout: Box::new(io::stdout()),
+            stderr: Box::new(io::stderr()),
+            overflow: Overflow::default(),
+        }
+    }
+
+    /// Where `..` reads lines from.
+    pub fn with_stdin(mut self, stdin: impl Read + 'a) -> Vm<'a> {
+        self.stdin = Some(Box::new(BufReader::new(stdin)));
+        self
+    }
+
+    /// Where `>>` prints to.
+    pub fn with_stdout(mut self, stdout: impl Write + 'a) -> Vm<'a> {
+        self.stdout = Box::new(stdout);
+        self
+    }
+
+    /// Where `>>>` prints to.
+    pub fn with_stderr(mut self, stderr: impl Write + 'a) -> Vm<'a> {
+        self.stderr = Box::new(stderr);
+        self
+    }
+
+    /// What integer math does when a result does not fit, as `--overflow=`.
+    pub fn with_overflow(mut self, overflow: Overflow) -> Vm<'a> {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Binds a mutable variable. Its type follows the value until the script
+    /// declares it; a declaration without an initializer keeps the bound value.
+    pub fn set(&mut self, name: &str, value: impl Into<Value>) {
+        let value = value.into();
+        self.vars.insert(
+            name.to_string(),
+            Variable {
+                var_type: value.var_type(),
+                value,
+                mutable: true,
+            },
+        );
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.vars.get(name).map(|variable| &variable.value)
+    }
+
+    /// Every variable that is currently set, in no particular order.
+    pub fn vars(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.vars.iter().map(|(name, variable)| (name.as_str(), &variable.value))
+    }
+
+    /// Like `vars`, with each variable's type and mutability.
+    pub fn variables(&self) -> impl Iterator<Item = (&str, &Variable)> {
+        self.vars.iter().map(|(name, variable)| (name.as_str(), variable))
+    }
+
+    /// Makes `function` callable from scripts as `name(...)`.
+    pub fn register(
+        &mut self,
+        name: &str,
+        function: impl FnMut(&[Value]) -> Result<Option<Value>, String> + 'a,
+    ) {
+        self.natives.insert(name.to_string(), Box::new(function));
+    }
+
+    /// Tokenizes, parses and runs `source`.
+    pub fn eval(&mut self, source: &str) -> Result<(), RuntimeError> {
+        let mut tokenizer = Tokenizer::new(source);
+        tokenizer.tokenize();
+        let (ast, _, errors) = AST::parse(&tokenizer.tokens, source);
+        if !errors.is_empty() {
+            return Err(RuntimeError::Parse(errors));
+        }
+        self.run(&ast)
+    }
+
+    pub fn run(&mut self, ast: &AST) -> Result<(), RuntimeError> {
+        let AST::Program(nodes) = ast;
+        let result = self.exec_nodes(nodes);
+        self.stdout.flush()?;
+        self.stderr.flush()?;
+        result
+    }
+
+    fn exec_nodes(&mut self, nodes: &[ASTNode]) -> Result<(), RuntimeError> {
+        for node in nodes {
+            self.exec_node(node).map_err(|mut e| {
+                // Errors are raised without a line; the innermost statement names it
+                if let (Some(line), Some(at)) = (node.line(), e.line_mut())
+                    && *at == 0
+                {
+                    *at = line;
+                }
+                e
+            })?;
+        }
+        Ok(())
+    }
+
+    fn exec_node(&mut self, node: &ASTNode) -> Result<(), RuntimeError> {
+        match node {
+            ASTNode::VarDeclaration { mutable, name, var_type, value, .. } => {
+                let value = match value {
+                    // Declaration literals arrive with their quotes already stripped
+                    Some(Expression::Literal(lit)) if matches!(var_type, VarType::String | VarType::Char { .. }) => {
+                        Value::Str(literal_text(lit))
+                    }
+                    Some(expr) => self.eval_expr(expr)?,
+                    None => match self.vars.remove(name) {
+                        Some(bound) => bound.value,
+                        None => default_value(var_type),
+                    },
+                };
+                let value = self.fit(name, value, var_type)?;
+                self.vars.insert(
+                    name.clone(),
+                    Variable {
+                        value,
+                        var_type: var_type.clone(),
+                        mutable: *mutable,
+                    },
+                );
+            }
+            ASTNode::Input { name, .. } => {
+                let mut line = String::new();
+                match &mut self.stdin {
+                    Some(stdin) => stdin.read_line(&mut line)?,
+                    None => io::stdin().read_line(&mut line)?,
+                };
+                let line = line.strip_suffix('\n').unwrap_or(&line);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                let line = fit_text(line, STR_CAPACITY);
+                // Undeclared target: the line is consumed and discarded
+                if let Some(variable) = self.vars.get(name) {
+                    let value = self.fit(name, Value::Str(line.to_string()), &variable.var_type)?;
+                    self.vars.get_mut(name).unwrap().value = value;
+                }
+            }
+            ASTNode::Print { to_stderr, expr, .. } => {
+                let mut text = match expr {
+                    Some(Expression::Literal(text)) => self.interpolate(text)?,
+                    Some(Expression::Identifier(name)) if self.vars.contains_key(name) => {
+                        self.vars[name].value.to_string()
+                    }
+                    // Bare words are printed as written
+                    Some(Expression::Identifier(word)) => self.interpolate(word)?,
+                    Some(
+                        expr @ (Expression::Index { .. }
+                        | Expression::Len(_)
+                        | Expression::Field { .. }
+                        | Expression::Convert { .. }
+                        | Expression::Text { .. }
+                        | Expression::Math { .. }),
+                    ) => {
+                        self.eval_expr(expr)?.to_string()
+                    }
+                    Some(cond) => self.is_true(cond)?.to_string(),
+                    None => String::new(),
+                };
+                text.push('\n');
+                let stream = if *to_stderr { &mut self.stderr } else { &mut self.stdout };
+                stream.write_all(text.as_bytes())?;
+            }
+            ASTNode::MathOp { name, field: None, operator, operand, line } => {
+                let operand = self.eval_expr(operand)?;
+                let overflow = self.overflow;
+                let variable = self.assignable(name)?;
+                variable.value = apply_math(name, variable, operator, &operand, overflow, *line)?;
+            }
+            ASTNode::MathOp { name, field: Some(field), operator, operand, line } => {
+                let operand = self.eval_expr(operand)?;
+                let overflow = self.overflow;
+                let target = format!("{}.{}", name, field);
+                let variable = self.assignable(name)?;
+                let (VarType::Record(record), Value::Record { fields, .. }) = (&variable.var_type, &mut variable.value)
+                else {
+                    return Err(RuntimeError::UndeclaredVariable { name: target });
+                };
+                let (Some(field_type), Some((_, value))) =
+                    (record.field(field), fields.iter_mut().find(|(given, _)| given == field))
+                else {
+                    return Err(RuntimeError::UndeclaredVariable { name: target });
+                };
+                // The field is changed like a variable of the field type
+                let current = Variable {
+                    value: value.clone(),
+                    var_type: field_type.clone(),
+                    mutable: true,
+                };
+                *value = apply_math(&target, &current, operator, &operand, overflow, *line)?;
+            }
+            ASTNode::If { condition, body, .. } => {
+                if self.is_true(condition)? {
+                    self.exec_nodes(body)?;
+                }
+            }
+            ASTNode::SetIndex { name, index, value, .. } if self.map(name).is_ok() => {
+                let key = self.key(name, index)?;
+                let value = self.eval_expr(value)?;
+                let value = self.fit(name, value, self.map(name)?.1)?;
+                if let Value::Map(entries) = &mut self.assignable(name)?.value {
+                    // A new key goes last; an existing one keeps its place
+                    match entries.iter_mut().find(|(existing, _)| *existing == key) {
+                        Some(entry) => entry.1 = value,
+                        None => entries.push((key, value)),
+                    }
+                }
+            }
+            ASTNode::SetIndex { name, index, value, .. } => {
+                // The bounds are checked before the value is evaluated
+                let index = self.eval_expr(index)?;
+                let index = self.index(name, &index, self.array(name)?.1.len())?;
+                let value = self.eval_expr(value)?;
+                let value = self.fit(name, value, self.array(name)?.0)?;
+                if let Value::Array(items) = &mut self.assignable(name)?.value {
+                    items[index] = value;
+                }
+            }
+            ASTNode::Append { name, value, .. } => {
+                let value = self.eval_expr(value)?;
+                let value = self.fit(name, value, self.array(name)?.0)?;
+                if let Value::Array(items) = &mut self.assignable(name)?.value {
+                    items.push(value);
+                }
+            }
+            ASTNode::ForEach { item, array, body } => {
+                // The length is read again before each pass, so appended elements are visited
+                let mut i = 0;
+                loop {
+                    let (element, items) = self.array(array)?;
+                    let Some(value) = items.get(i) else { break };
+                    let variable = Variable {
+                        value: value.clone(),
+                        var_type: element.clone(),
+                        mutable: false,
+                    };
+                    self.vars.insert(item.clone(), variable);
+                    self.exec_nodes(body)?;
+                    i += 1;
+                }
+            }
+            // Types are resolved by the parser
+            ASTNode::RecordDeclaration(_) => {}
+            ASTNode::Call { name, args, into, .. } => {
+                let args = args.iter().map(|arg| self.eval_expr(arg)).collect::<Result<Vec<Value>, _>>()?;
+                let function = self
+                    .natives
+                    .get_mut(name)
+                    .ok_or_else(|| RuntimeError::UnknownFunction { name: name.clone() })?;
+                let result = function(&args).map_err(|message| RuntimeError::Native {
+                    name: name.clone(),
+                    message,
+                })?;
+                if let Some(target) = into {
+                    let result = result.ok_or_else(|| RuntimeError::NoValue { name: name.clone() })?;
+                    let var_type = self.assignable(target)?.var_type.clone();
+                    let result = self.fit(target, result, &var_type)?;
+                    self.assignable(target)?.value = result;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The variable behind a variable that math or a call result may change.
+    fn assignable(&mut self, name: &str) -> Result<&mut Variable, RuntimeError> {
+        match self.vars.get_mut(name) {
+            Some(variable) if variable.mutable => Ok(variable),
+            Some(_) => Err(RuntimeError::ImmutableAssignment { name: name.to_string() }),
+            None => Err(RuntimeError::UndeclaredVariable { name: name.to_string() }),
+        }
+    }
+
+    /// `value` converted for a store into `name`. An overflowing array element
+    /// is reported with the element type, and a map key or value with its type.
+    fn fit(&self, name: &str, value: Value, var_type: &VarType) -> Result<Value, RuntimeError> {
+        if let (VarType::Array(element), Value::Array(items)) = (var_type, &value) {
+            return items
+                .iter()
+                .map(|item| self.fit(name, item.clone(), element))
+                .collect::<Result<_, _>>()
+                .map(Value::Array);
+        }
+        // An overflowing field is reported as `name.field`
+        if let (VarType::Record(record), Value::Record { fields, .. }) = (var_type, &value) {
+            return record
+                .fields
+                .iter()
+                .map(|(field, field_type)| {
+                    let value = match fields.iter().find(|(given, _)| given == field) {
+                        Some((_, value)) => self.fit(&format!("{}.{}", name, field), value.clone(), field_type)?,
+                        None => default_value(field_type),
+                    };
+                    Ok((field.clone(), value))
+                })
+                .collect::<Result<_, _>>()
+                .map(|fields| Value::Record { name: record.name.clone(), fields });
+        }
+        value.convert(var_type, self.overflow).ok_or_else(|| overflow_error(name, var_type))
+    }
+
+    /// The element type and elements of the array `name`.
+    fn array(&self, name: &str) -> Result<(&VarType, &[Value]), RuntimeError> {
+        match self.vars.get(name) {
+            Some(Variable { value: Value::Array(items), var_type: VarType::Array(element), .. }) => {
+                Ok((element, items))
+            }
+            _ => Err(RuntimeError::UndeclaredVariable { name: name.to_string() }),
+        }
+    }
+
+    /// The key type, value type and entries of the map `name`.
+    fn map(&self, name: &str) -> Result<(&VarType, &VarType, &Entries), RuntimeError> {
+        match self.vars.get(name) {
+            Some(Variable { value: Value::Map(entries), var_type: VarType::Map(key, value), .. }) => {
+                Ok((key, value, entries))
+            }
+            _ => Err(RuntimeError::UndeclaredVariable { name: name.to_string() }),
+        }
+    }
+
+    /// `key` evaluated and converted to the key type of the map `name`.
+    fn key(&self, name: &str, key: &Expression) -> Result<Value, RuntimeError> {
+        let key = self.eval_expr(key)?;
+        self.fit(name, key, self.map(name)?.0)
+    }
+
+    /// `index` as a position in the array `name` of `len` elements.
+    fn index(&self, name: &str, index: &Value, len: usize) -> Result<usize, RuntimeError> {
+        let index = index.as_int().unwrap_or(0);
+        usize::try_from(index)
+            .ok()
+            .filter(|&i| i < len)
+            .ok_or_else(|| RuntimeError::IndexOutOfBounds {
+                name: name.to_string(),
+                index,
+                len,
+                line: 0,
+            })
+    }
+
+    fn interpolate(&self, text: &str) -> Result<String, RuntimeError> {
+        let var_types = std::cell::OnceCell::new();
+        let var_types = || {
+            var_types.get_or_init(|| {
+                self.vars
+                    .iter()
+                    .map(|(name, variable)| (name.clone(), variable.var_type.clone()))
+                    .collect::<HashMap<_, _>>()
+            })
+        };
+        let mut out = String::new();
+        for segment in split_interpolation(text) {
+            match segment {
+                Segment::Text(text) => out.push_str(&text),
+                Segment::Var(name) => match self.vars.get(&name) {
+                    Some(variable) => out.push_str(&variable.value.to_string()),
+                    // `{name[index]}`, `{len(name)}` and `{i(name)}`
+                    None => match placeholder(&name, var_types()) {
+                        Some(expr) => out.push_str(&self.eval_expr(&expr)?.to_string()),
+                        None => out.push_str("[undefined]"),
+                    },
+                },
+                Segment::Formatted(name, spec) => match formatted(&name, var_types()) {
+                    Some(expr) => out.push_str(&format_value(&self.eval_expr(&expr)?, spec)),
+                    None => out.push_str("[undefined]"),
+                },
+            }
+        }
+        Ok(out)
+    }
+
+    fn eval_expr(&self, expr: &Expression) -> Result<Value, RuntimeError> {
+        Ok(match expr {
+            Expression::Literal(lit) if lit.starts_with('"') || lit.starts_with('\'') => Value::Str(literal_text(lit)),
+            Expression::Literal(lit) => {
+                let number = clean_number(lit);
+                if let Some(n) = int_literal(&number) {
+                    match i64::try_from(n) {
+                        Ok(n) => Value::Int(n),
+                        Err(_) => Value::UInt(n as u64),
+                    }
+                } else if let Ok(x) = number.parse::<f64>() {
+                    Value::Float(x)
+                } else {
+                    match number.as_str() {
+                        "true" => Value::Bool(true),
+                        "false" => Value::Bool(false),
+                        _ => Value::Str(lit.clone()),
+                    }
+                }
+            }
+            // Undeclared names evaluate to 0, as on the transpiled targets
+            Expression::Identifier(name) => match self.vars.get(name) {
+                Some(variable) => variable.value.clone(),
+                None => Value::Int(0),
+            },
+            Expression::BinaryOp { .. } | Expression::LogicalOp { .. } | Expression::Not(_) => {
+                Value::Bool(self.is_true(expr)?)
+            }
+            Expression::List(items) => {
+                Value::Array(items.iter().map(|item| self.eval_expr(item)).collect::<Result<_, _>>()?)
+            }
+            Expression::Index { name, index } if self.map(name).is_ok() => {
+                let key = self.key(name, index)?;
+                match self.map(name)?.2.iter().find(|(existing, _)| *existing == key) {
+                    Some((_, value)) => value.clone(),
+                    None => return Err(missing_key(name, &key)),
+                }
+            }
+            Expression::Index { name, index } => {
+                let index = self.eval_expr(index)?;
+                let (_, items) = self.array(name)?;
+                items[self.index(name, &index, items.len())?].clone()
+            }
+            Expression::Len(name) if let Some(Variable { value: Value::Str(text), .. }) = self.vars.get(name) => {
+                Value::Int(text.chars().count() as i64)
+            }
+            Expression::Len(name) => match self.map(name) {
+                Ok((_, _, entries)) => Value::Int(entries.len() as i64),
+                Err(_) => Value::Int(self.array(name)?.1.len() as i64),
+            },
+            Expression::In { key, name } => {
+                let key = self.key(name, key)?;
+                Value::Bool(self.map(name)?.2.iter().any(|(existing, _)| *existing == key))
+            }
+            Expression::Field { name, field } => match self.vars.get(name) {
+                Some(Variable { value: Value::Record { fields, .. }, .. }) => {
+                    match fields.iter().find(|(given, _)| given == field) {
+                        Some((_, value)) => value.clone(),
+                        None => Value::Int(0),
+                    }
+                }
+                _ => Value::Int(0),
+            },
+            // Named and completed by the declaration it initializes
+            Expression::Record(values) => Value::Record {
+                name: String::new(),
+                fields: values
+                    .iter()
+                    .map(|(field, value)| Ok((field.clone(), self.eval_expr(value)?)))
+                    .collect::<Result<_, RuntimeError>>()?,
+            },
+            Expression::Convert { to, value } => convert(to, self.eval_expr(value)?)?,
+            Expression::Text { op, args } => {
+                text_op(*op, &args.iter().map(|arg| self.eval_expr(arg)).collect::<Result<Vec<_>, _>>()?)
+            }
+            Expression::Math { op, args } => math_fn(
+                *op,
+                &args.iter().map(|arg| self.eval_expr(arg)).collect::<Result<Vec<_>, _>>()?,
+                self.overflow,
+            )?,
+        })
+    }
+
+    fn is_true(&self, expr: &Expression) -> Result<bool, RuntimeError> {
+        Ok(match expr {
+            Expression::LogicalOp { left, operator, right } => match operator {
+                LogicalOperator::And => self.is_true(left)? && self.is_true(right)?,
+                LogicalOperator::Or => self.is_true(left)? || self.is_true(right)?,
+            },
+            Expression::BinaryOp { left, operator, right } => {
+                compare(&self.eval_expr(left)?, operator, &self.eval_expr(right)?)
+            }
+            Expression::Not(operand) => !self.is_true(operand)?,
+            _ => self.eval_expr(expr)?.truthy(),
+        })
+    }
+}
+
+fn default_value(var_type: &VarType) -> Value {
+    match var_type {
+        VarType::Int(int_type) => int_value(0, *int_type),
+        VarType::Float => Value::Float(0.0),
+        VarType::String | VarType::Char { .. } => Value::Str(String::new()),
+        VarType::Bool => Value::Bool(false),
+        VarType::Array(_) => Value::Array(Vec::new()),
+        VarType::Map(..) => Value::Map(Vec::new()),
+        VarType::Record(record) => Value::Record {
+            name: record.name.clone(),
+            fields: record
+                .fields
+                .iter()
+                .map(|(field, field_type)| (field.clone(), default_value(field_type)))
+                .collect(),
+        },
+    }
+}
+
+/// `i(value)`, `f(value)` or `str(value)`. Text converts to a number only when
+/// it is one; a float converts to an integer as it is stored into an `i64`.
+fn convert(to: &VarType, value: Value) -> Result<Value, RuntimeError> {
+    let failed = |text: String| RuntimeError::Conversion {
+        text,
+        to: crate::transpilers::conversion_name(to),
+        line: 0,
+    };
+    Ok(match (to, value) {
+        (VarType::String, value) => Value::Str(value.to_string()),
+        (VarType::Float, Value::Str(text)) => Value::Float(convert_float(&text).ok_or_else(|| failed(text))?),
+        (VarType::Float, value) => Value::Float(as_f64(&value)),
+        (_, Value::Str(text)) => Value::Int(convert_int(&text).ok_or_else(|| failed(text))?),
+        (_, value @ (Value::Int(_) | Value::UInt(_))) => value,
+        (_, Value::Float(x)) => Value::Int(fit_float(x, IntType::I64, Overflow::Saturate).unwrap_or(0) as i64),
+        (_, Value::Bool(b)) => Value::Int(b as i64),
+        // Whole arrays, maps and records are rejected by the parser
+        (_, Value::Array(_) | Value::Map(_) | Value::Record { .. }) => Value::Int(0),
+    })
+}
+
+/// The string operation `op` of `args`, which the parser has checked are text
+/// apart from the integer start and end of `slice()`.
+fn text_op(op: TextOp, args: &[Value]) -> Value {
+    let text = |i: usize| args[i].to_string();
+    match op {
+        TextOp::Concat => {
+            let joined: String = args.iter().map(Value::to_string).collect();
+            Value::Str(fit_text(&joined, STR_CAPACITY).to_string())
+        }
+        TextOp::Len => Value::Int(text(0).chars().count() as i64),
+        TextOp::Slice => {
+            let (start, end) = (args[1].as_int().unwrap_or(0), args[2].as_int().unwrap_or(0));
+            Value::Str(slice_text(&text(0), start, end).to_string())
+        }
+        TextOp::Upper => Value::Str(text(0).to_ascii_uppercase()),
+        TextOp::Lower => Value::Str(text(0).to_ascii_lowercase()),
+        TextOp::Trim => Value::Str(trim_blank(&text(0)).to_string()),
+        TextOp::Contains => Value::Bool(text(0).contains(&text(1))),
+        TextOp::StartsWith => Value::Bool(text(0).starts_with(&text(1))),
+        TextOp::Replace => Value::Str(fit_text(&replace_text(&text(0), &text(1), &text(2)), STR_CAPACITY).to_string()),
+    }
+}
+
+/// The math function `op` of `args`, which the parser has checked are numbers.
+/// Integers are stored into an `i64` first, and so is an integer result.
+fn math_fn(op: MathFn, args: &[Value], overflow: Overflow) -> Result<Value, RuntimeError> {
+    let ints = args.iter().map(Value::as_int).collect::<Option<Vec<_>>>();
+    match ints {
+        Some(ints) if op != MathFn::Sqrt => ints
+            .into_iter()
+            .map(|n| fit(n, IntType::I64, overflow))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|ints| int_math_fn(op, &ints, overflow))
+            .map(|n| Value::Int(n as i64))
+            .ok_or_else(|| overflow_error(op.called(), &VarType::Int(IntType::I64))),
+        _ => Ok(Value::Float(float_math_fn(op, &args.iter().map(as_f64).collect::<Vec<_>>()))),
+    }
+}
+
+/// `value` as a `{name:spec}` placeholder prints it.
+fn format_value(value: &Value, spec: FormatSpec) -> String {
+    let text = match (value, spec.precision) {
+        (Value::Float(x), Some(precision)) if x.is_finite() => format!("{:.*}", precision, x),
+        _ => value.to_string(),
+    };
+    let numeric = matches!(value, Value::Int(_) | Value::UInt(_) | Value::Float(_));
+    pad(&text, spec.width, spec.right(numeric))
+}
+
+/// The error for a lookup of `key` in the map `name`, with text keys quoted.
+fn missing_key(name: &str, key: &Value) -> RuntimeError {
+    let key = match key {
+        Value::Str(text) => format!("\"{}\"", text),
+        other => other.to_string(),
+    };
+    RuntimeError::MissingKey {
+        name: name.to_string(),
+        key,
+        line: 0,
+    }
+}
+
+/// Strings compare by bytes, numbers numerically with booleans as 1 and 0, and
+/// a string never equals a number.
+fn compare(left: &Value, operator: &ComparisonOperator, right: &Value) -> bool {
+    let ordering = match (left, right) {
+        (Value::Str(a), Value::Str(b)) => Some(a.cmp(b)),
+        (Value::Int(_) | Value::UInt(_), Value::Int(_) | Value::UInt(_)) => left.as_int().partial_cmp(&right.as_int()),
+        (Value::Str(_), _) | (_, Value::Str(_)) => return *operator == ComparisonOperator::NotEqual,
+        (a, b) => as_f64(a).partial_cmp(&as_f64(b)),
+    };
+    match ordering {
+        Some(ordering) => match operator {
+            ComparisonOperator::Equal => ordering.is_eq(),
+            ComparisonOperator::NotEqual => ordering.is_ne(),
+            ComparisonOperator::LessThan => ordering.is_lt(),
+            ComparisonOperator::LessThanEqual => ordering.is_le(),
+            ComparisonOperator::GreaterThan => ordering.is_gt(),
+            ComparisonOperator::GreaterThanEqual => ordering.is_ge(),
+        },
+        // NaN is unordered
+        None => *operator == ComparisonOperator::NotEqual,
+    }
+}
+
+fn as_f64(value: &Value) -> f64 {
+    match value {
+        Value::Int(n) => *n as f64,
+        Value::UInt(n) => *n as f64,
+        Value::Float(x) => *x,
+        Value::Str(s) => s.trim().parse().unwrap_or(0.0),
+        Value::Bool(b) => *b as i64 as f64,
+        Value::Array(_) | Value::Map(_) | Value::Record { .. } => 0.0,
+    }
+}
+
+/// `* name op operand`. The variable keeps its type: an integer operand is
+/// stored into the variable's type first, integer division truncates toward
+/// zero, and a result that does not fit is handled by `overflow`. `0 ** y`
+/// with a negative integer `y` divides by zero; the error names `line`.
+fn apply_math(
+    name: &str,
+    variable: &Variable,
+    operator: &MathOperator,
+    operand: &Value,
+    overflow: Overflow,
+    line: usize,
+) -> Result<Value, RuntimeError> {
+    let divide_by_zero = |y: f64| matches!(operator, MathOperator::Divide | MathOperator::Modulo) && y == 0.0;
+    if divide_by_zero(as_f64(operand)) && matches!(variable.value, Value::Int(_) | Value::UInt(_) | Value::Float(_)) {
+        return Err(RuntimeError::DivisionByZero { name: name.to_string(), line });
+    }
+    let result = match (&variable.var_type, variable.value.as_int(), operand.as_int()) {
+        (VarType::Int(int_type), Some(x), Some(y)) => match fit(y, *int_type, overflow) {
+            // The operand may only become 0 once stored into the variable's type
+            Some(y) if divide_by_zero(y as f64) || (*operator == MathOperator::Power && x == 0 && y < 0) => {
+                return Err(RuntimeError::DivisionByZero { name: name.to_string(), line });
+            }
+            y => y.and_then(|y| int_math(x, operator, y, *int_type, overflow)).map(|n| int_value(n, *int_type)),
+        },
+        (VarType::Int(int_type), Some(x), None) => {
+            fit_float(float_math(x as f64, operator, as_f64(operand)), *int_type, overflow)
+                .map(|n| int_value(n, *int_type))
+        }
+        _ => match &variable.value {
+            Value::Float(a) => Some(Value::Float(float_math(*a, operator, as_f64(operand)))),
+            _ => return Err(RuntimeError::NonNumeric { name: name.to_string() }),
+        },
+    };
+    result.ok_or_else(|| overflow_error(name, &variable.var_type))
+}
+
+fn overflow_error(name: &str, var_type: &VarType) -> RuntimeError {
+    RuntimeError::Overflow {
+        name: name.to_string(),
+        var_type: var_type.to_string(),
+        line: 0,
+    }
+}
+
+/// An integer of `int_type` as the VM holds it.
+fn int_value(n: i128, int_type: IntType) -> Value {
+    if int_type.signed { Value::Int(n as i64) } else { Value::UInt(n as u64) }
+}
+
+/// `..` input and text stored into an integer: not a number, or outside
+/// `i64::MIN..=u64::MAX`, reads as 0.
+fn parse_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|n| (i64::MIN as i128..=u64::MAX as i128).contains(n))
+        .unwrap_or(0)
+}
+
+fn float_math(a: f64, operator: &MathOperator, b: f64) -> f64 {
+    match operator {
+        MathOperator::Add => a + b,
+        MathOperator::Subtract => a - b,
+        MathOperator::Multiply => a * b,
+        MathOperator::Divide => a / b,
+        MathOperator::Modulo => a % b,
+        MathOperator::Power => a.powf(b),
+    }
+}
@@ -0,0 +1,60 @@
+//! Targets backed by a backend the embedder provides rather than a built-in one.
+
+use ven::errs::BackendError;
+use ven::parse::AST;
+use ven::{Backend, Capabilities, CodegenOptions, Options, Target, compile};
+
+/// Emits the number of top-level statements, and cannot translate floats.
+struct CountBackend;
+
+impl Backend for CountBackend {
+    fn name(&self) -> &'static str {
+        "count"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn description(&self) -> &'static str {
+        "Statement count"
+    }
+
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities { floats: false, ..Capabilities::ALL }
+    }
+
+    fn emit(&self, ast: &AST, _options: &CodegenOptions) -> Result<String, BackendError> {
+        let AST::Program(nodes) = ast;
+        Ok(format!("{} statements\n", nodes.len()))
+    }
+}
+
+#[test]
+fn custom_backends_compile_programs() {
+    let target = Target::from_backend(&CountBackend);
+    assert_eq!(target.name(), "count");
+    let mut options = Options::default();
+    options.optimize = false;
+    let output = compile("@ a i 1\n>> a\n>> \"done\"\n", target, options).unwrap();
+    assert_eq!(output.code, "3 statements\n");
+    assert_eq!(output.extension, "txt");
+}
+
+#[test]
+fn custom_backends_are_checked_against_their_capabilities() {
+    let target = Target::from_backend(&CountBackend);
+    let diagnostics = compile("@@ a f 0\n.. a\n>> a\n", target, Options::default()).unwrap_err();
+    assert!(diagnostics.errors.is_empty());
+    assert_eq!(diagnostics.backend.map(|error| error.code()), Some("V0010"));
+}
+
+#[test]
+fn custom_backends_are_not_built_in() {
+    assert!(Target::from_name("count").is_none());
+    assert!(Target::all().all(|target| target.name() != "count"));
+}
@@ -0,0 +1,9 @@
+//! The examples of `ven explain` against what the compiler reports.
+
+use ven::explain::{EXPLANATIONS, check};
+
+#[test]
+fn examples_report_their_code() {
+    let failures: Vec<String> = EXPLANATIONS.iter().filter_map(|explanation| check(explanation).err()).collect();
+    assert!(failures.is_empty(), "{} explanation(s) out of date:\n{}", failures.len(), failures.join("\n"));
+}
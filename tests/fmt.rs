@@ -0,0 +1,93 @@
+//! `ven fmt` on files on disk: `--check`, rewriting, and that formatting
+//! twice changes nothing.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use common::scratch;
+
+fn fmt(flags: &[&str], path: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ven"))
+        .arg("fmt")
+        .args(flags)
+        .arg(path)
+        .output()
+        .unwrap()
+}
+
+const MESSY: &str = "@   a   i 1\n?(a>0){\n>> \"{a}\"\n}\n";
+const TIDY: &str = "@ a i 1\n?(a > 0) {\n    >> \"{a}\"\n}\n";
+
+#[test]
+fn check_lists_unformatted_files_without_touching_them() {
+    let dir = scratch("check");
+    let messy = dir.join("messy.ven");
+    let tidy = dir.join("tidy.ven");
+    fs::write(&messy, MESSY).unwrap();
+    fs::write(&tidy, TIDY).unwrap();
+
+    let output = fmt(&["--check"], &messy);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), messy.display().to_string());
+    assert_eq!(fs::read_to_string(&messy).unwrap(), MESSY);
+
+    let output = fmt(&["--check"], &tidy);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn formatting_rewrites_the_file() {
+    let dir = scratch("rewrite");
+    let path = dir.join("main.ven");
+    fs::write(&path, MESSY).unwrap();
+    assert_eq!(fmt(&[], &path).status.code(), Some(0));
+    assert_eq!(fs::read_to_string(&path).unwrap(), TIDY);
+}
+
+#[test]
+fn moved_lines_keep_the_meaning() {
+    let dir = scratch("moved");
+    let cases = [
+        ("@ a i 1\n?(a > 0)\n{\n>> \"{a}\"\n}\n", "@ a i 1\n?(a > 0) {\n    >> \"{a}\"\n}\n"),
+        ("@ a i 1\n\n\n\n>> a\n", "@ a i 1\n\n>> a\n"),
+        ("\n\n@ a i 1\n>> a\n", "@ a i 1\n>> a\n"),
+    ];
+    for (source, formatted) in cases {
+        let path = dir.join("main.ven");
+        fs::write(&path, source).unwrap();
+        assert_eq!(fmt(&[], &path).status.code(), Some(0), "{:?}", source);
+        assert_eq!(fs::read_to_string(&path).unwrap(), formatted);
+    }
+}
+
+#[test]
+fn unparsable_files_are_left_alone() {
+    let dir = scratch("unparsable");
+    let path = dir.join("broken.ven");
+    fs::write(&path, "?(a > 0) {\n").unwrap();
+    assert_eq!(fmt(&[], &path).status.code(), Some(1));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "?(a > 0) {\n");
+}
+
+#[test]
+fn formatting_is_idempotent() {
+    let dir = scratch("idempotent");
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    for entry in fs::read_dir(golden).unwrap() {
+        let source = entry.unwrap().path();
+        if source.extension().is_none_or(|extension| extension != "ven") {
+            continue;
+        }
+        let path = dir.join(source.file_name().unwrap());
+        fs::copy(&source, &path).unwrap();
+        assert_eq!(fmt(&[], &path).status.code(), Some(0), "{}", source.display());
+        let once = fs::read_to_string(&path).unwrap();
+        let output = fmt(&["--check"], &path);
+        assert_eq!(output.status.code(), Some(0), "{} changes when formatted again", source.display());
+        assert_eq!(fs::read_to_string(&path).unwrap(), once);
+    }
+}
@@ -0,0 +1,339 @@
+//! Golden tests for the transpilers.
+//!
+//! Every `tests/golden/<name>.ven` is compiled once per expected output that
+//! sits next to it (`<name>.py`, `<name>.mjs`, ...) and the result is compared
+//! with it. Set `VEN_BLESS=1` to rewrite the expected files after an intended
+//! change. The outputs are then built and run with the usual toolchain for each
+//! target, and must print what the VM prints for the same program: asm with
+//! nasm and ld, AArch64 assembly with the aarch64-linux-gnu binutils under
+//! qemu-aarch64. A target whose tools are not installed is skipped with a note
+//! on stderr.
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+use ven::Overflow;
+use ven::errs::RuntimeError;
+use ven::vm::Vm;
+
+use common::scratch;
+
+/// Expected-file extension and the `-t=` target it is compiled with.
+const TARGETS: [(&str, &str); 8] = [
+    ("py", "py"),
+    ("mjs", "js"),
+    ("wat", "wasm"),
+    ("c", "c"),
+    ("rs", "rs"),
+    ("ll", "llvm"),
+    ("asm", "lx8664"),
+    ("s", "la64"),
+];
+
+/// Runs a `.wasm` module that imports WASI under node.
+const WASI_RUNNER: &str = "\
+import { WASI } from 'node:wasi';
+import fs from 'node:fs';
+const wasi = new WASI({ version: 'preview1', args: [], env: {} });
+const module = await WebAssembly.compile(fs.readFileSync(process.argv[2]));
+const instance = await WebAssembly.instantiate(module, wasi.getImportObject());
+process.exitCode = wasi.start(instance) ?? 0;
+";
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+/// Each golden program's name with the extensions of its expected outputs.
+fn programs() -> Vec<(String, Vec<&'static str>)> {
+    let dir = golden_dir();
+    let mut names: Vec<String> = fs::read_dir(&dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.unwrap().path();
+            let name = path.file_stem()?.to_str()?.to_string();
+            (path.extension()? == "ven").then_some(name)
+        })
+        .collect();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| {
+            let extensions = TARGETS
+                .iter()
+                .map(|(extension, _)| *extension)
+                .filter(|extension| dir.join(format!("{}.{}", name, extension)).exists())
+                .collect();
+            (name, extensions)
+        })
+        .collect()
+}
+
+fn golden_source(name: &str) -> String {
+    fs::read_to_string(golden_dir().join(format!("{}.ven", name))).unwrap()
+}
+
+/// Compiles `program` as `<name>.ven` for the target of `extension` into
+/// `work`. Returns the path of the output, or the compiler's stderr.
+fn compile(work: &Path, name: &str, program: &str, extension: &str, flags: &[&str]) -> Result<PathBuf, String> {
+    let target = TARGETS.iter().find(|(ext, _)| *ext == extension).unwrap().1;
+    let source = work.join(format!("{}.ven", name));
+    fs::write(&source, program).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_ven"))
+        .arg(format!("--in={}", source.display()))
+        .arg(format!("-t={}", target))
+        .args(flags)
+        .output()
+        .unwrap();
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    Ok(work.join(format!("{}.{}", name, extension)))
+}
+
+/// The first line where `expected` and `actual` differ, for failure messages.
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => break,
+            (left, right) if left != right => {
+                return format!("line {}: expected {:?}, got {:?}", line, left.unwrap_or(""), right.unwrap_or(""));
+            }
+            _ => {}
+        }
+    }
+    "outputs differ only in their final newline".to_string()
+}
+
+#[test]
+fn outputs_match_the_expected_files() {
+    let bless = env::var_os("VEN_BLESS").is_some();
+    let work = scratch("compare");
+    let mut failures = Vec::new();
+    for (name, extensions) in programs() {
+        for extension in extensions {
+            let expected_path = golden_dir().join(format!("{}.{}", name, extension));
+            let actual = match compile(&work, &name, &golden_source(&name), extension, &[]) {
+                Ok(path) => fs::read_to_string(path).unwrap(),
+                Err(stderr) => {
+                    failures.push(format!("{}.{}: compiler error\n{}", name, extension, stderr));
+                    continue;
+                }
+            };
+            if bless {
+                fs::write(&expected_path, &actual).unwrap();
+                continue;
+            }
+            let expected = fs::read_to_string(&expected_path).unwrap();
+            if expected != actual {
+                failures.push(format!("{}.{}: {}", name, extension, first_difference(&expected, &actual)));
+            }
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} golden output(s) differ (VEN_BLESS=1 accepts them):\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/// What a program printed on stdout and on stderr.
+type Printed = (String, String);
+
+/// What the VM prints for `program` with nothing on stdin, and the error it
+/// stops with.
+fn vm_run(program: &str, overflow: Overflow) -> (Printed, Option<RuntimeError>) {
+    let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+    let mut vm = Vm::new()
+        .with_stdin(io::empty())
+        .with_stdout(&mut stdout)
+        .with_stderr(&mut stderr)
+        .with_overflow(overflow);
+    let error = vm.eval(program).err();
+    drop(vm);
+    ((String::from_utf8(stdout).unwrap(), String::from_utf8(stderr).unwrap()), error)
+}
+
+/// What the VM prints for `<name>.ven`.
+fn vm_output(name: &str) -> Printed {
+    match vm_run(&golden_source(name), Overflow::Wrap) {
+        (printed, None) => printed,
+        (_, Some(e)) => panic!("{}.ven fails on the VM: {}", name, e),
+    }
+}
+
+fn installed(tool: &str) -> bool {
+    Command::new(tool).arg("--version").output().is_ok_and(|output| output.status.success())
+}
+
+/// Runs `command` with nothing on stdin: its exit code and what it printed.
+fn run(command: &mut Command) -> (Option<i32>, Printed) {
+    let Output { status, stdout, stderr } = command.stdin(Stdio::null()).output().unwrap();
+    let printed = (String::from_utf8_lossy(&stdout).into_owned(), String::from_utf8_lossy(&stderr).into_owned());
+    (status.code(), printed)
+}
+
+/// Builds `program` for `extension` with `flags` and runs it. `None` if one
+/// of the target's tools is missing.
+fn execute(
+    work: &Path,
+    name: &str,
+    program: &str,
+    extension: &str,
+    flags: &[&str],
+) -> Option<Result<(Option<i32>, Printed), String>> {
+    let tools: &[&str] = match extension {
+        "c" => &["gcc"],
+        "rs" => &["rustc"],
+        "ll" => &["lli"],
+        "mjs" | "wat" => &["node"],
+        "py" => &["python3"],
+        "asm" => &["nasm", "ld"],
+        "s" => &["aarch64-linux-gnu-as", "aarch64-linux-gnu-ld", "qemu-aarch64"],
+        _ => return None,
+    };
+    if let Some(tool) = tools.iter().find(|tool| !installed(tool)) {
+        eprintln!("skipping {}.{}: {} is not installed", name, extension, tool);
+        return None;
+    }
+    let mut flags = flags.to_vec();
+    if extension == "wat" {
+        flags.push("--wasm-binary");
+    }
+    let path = match compile(work, name, program, extension, &flags) {
+        Ok(path) => path,
+        Err(stderr) => return Some(Err(format!("compiler error\n{}", stderr))),
+    };
+    let binary = work.join(format!("{}-{}", name, extension));
+    let object = binary.with_extension("o");
+    let link = |linker: &str, assembled: (Option<i32>, Printed)| match assembled {
+        (Some(0), _) => run(Command::new(linker).arg(&object).arg("-o").arg(&binary)),
+        failed => failed,
+    };
+    let built = match extension {
+        "c" => run(Command::new("gcc").arg("-w").arg("-o").arg(&binary).arg(&path).arg("-lm")),
+        "rs" => run(Command::new("rustc").arg("-o").arg(&binary).arg(&path)),
+        "asm" => link("ld", run(Command::new("nasm").arg("-f").arg("elf64").arg("-o").arg(&object).arg(&path))),
+        "s" => link(
+            "aarch64-linux-gnu-ld",
+            run(Command::new("aarch64-linux-gnu-as").arg("-o").arg(&object).arg(&path)),
+        ),
+        _ => (Some(0), Printed::default()),
+    };
+    if built.0 != Some(0) {
+        return Some(Err(format!("does not build: {}", built.1.1)));
+    }
+    Some(Ok(match extension {
+        "c" | "rs" | "asm" => run(&mut Command::new(&binary)),
+        "s" => run(Command::new("qemu-aarch64").arg(&binary)),
+        "ll" => run(Command::new("lli").arg(&path)),
+        "mjs" => run(Command::new("node").arg(&path)),
+        "py" => run(Command::new("python3").arg(&path)),
+        _ => {
+            let runner = work.join("run_wasi.mjs");
+            fs::write(&runner, WASI_RUNNER).unwrap();
+            run(Command::new("node")
+                .arg("--no-warnings")
+                .arg(&runner)
+                .arg(path.with_extension("wasm")))
+        }
+    }))
+}
+
+#[test]
+fn outputs_print_what_the_vm_prints() {
+    let work = scratch("run");
+    let mut failures = Vec::new();
+    for (name, extensions) in programs() {
+        let expected = vm_output(&name);
+        for extension in extensions {
+            match execute(&work, &name, &golden_source(&name), extension, &[]) {
+                None => {}
+                Some(Err(e)) => failures.push(format!("{}.{}: {}", name, extension, e)),
+                Some(Ok((code, (_, stderr)))) if code != Some(0) => {
+                    failures.push(format!("{}.{}: exits with {:?}: {}", name, extension, code, stderr))
+                }
+                Some(Ok((_, (stdout, stderr)))) => {
+                    if stdout != expected.0 {
+                        let difference = first_difference(&expected.0, &stdout);
+                        failures.push(format!("{}.{}: stdout {}", name, extension, difference));
+                    }
+                    if stderr != expected.1 {
+                        let difference = first_difference(&expected.1, &stderr);
+                        failures.push(format!("{}.{}: stderr {}", name, extension, difference));
+                    }
+                }
+            }
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} output(s) do not print what the VM prints:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/// Programs that stop with a runtime error under `--overflow=trap`, with the
+/// targets that support what they use.
+const TRAPS: [(&str, &str, &[&str]); 5] = [
+    ("overflow", "@@ b u8 250\n>> \"start\"\n* b + 10\n>> b\n", &["py", "mjs", "wat", "c", "rs", "ll"]),
+    (
+        "abs",
+        "@@ m i -9223372036854775807\n>> \"start\"\n?(m < 0) {\n    * m - 1\n    >> \"{abs(m)}\"\n}\n",
+        &["py", "mjs", "wat", "c", "rs", "ll"],
+    ),
+    ("conversion", "@ s str \"4 2\"\n>> \"start\"\n@ n i i(s)\n>> n\n", &["py", "mjs", "wat", "c", "rs", "ll"]),
+    ("index", "@@ xs i[] [1, 2]\n@ at i 5\n>> \"start\"\n>> xs[at]\n", &["py", "mjs", "c", "rs", "ll"]),
+    (
+        "key",
+        "@@ ages map[str]i {}\n* ages[\"ann\"] = 1\n>> \"start\"\n@ k str \"zed\"\n>> ages[k]\n",
+        &["py", "mjs", "c", "rs"],
+    ),
+];
+
+#[test]
+fn runtime_errors_match_the_vm() {
+    let work = scratch("traps");
+    let mut failures = Vec::new();
+    for (name, program, extensions) in TRAPS {
+        let ((stdout, _), error) = vm_run(program, Overflow::Trap);
+        let error = error.unwrap_or_else(|| panic!("{} does not stop on the VM", name));
+        let stderr = format!("runtime error: {}\n", error);
+        assert!(stderr.contains(" on line "), "{} names no line: {}", name, stderr);
+        for extension in extensions {
+            match execute(&work, name, program, extension, &["--overflow=trap"]) {
+                Some(Err(e)) => failures.push(format!("{}.{}: {}", name, extension, e)),
+                Some(Ok((code, printed))) if code != Some(1) || printed != (stdout.clone(), stderr.clone()) => {
+                    failures.push(format!("{}.{}: exits with {:?}, printing {:?}", name, extension, code, printed))
+                }
+                None | Some(Ok(_)) => {}
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{} output(s) do not stop like the VM:\n{}", failures.len(), failures.join("\n"));
+}
+
+#[test]
+fn misspelt_names_get_a_suggestion() {
+    let work = scratch("suggest");
+    let cases = [
+        ("name", "@@ name i 0\n@@ count i 1\n>> \"{nmae} {cont}\"\n", ["did you mean `name`?", "did you mean `count`?"]),
+        ("map", "@@ ages map[string]i {}\n>> \"ages\"\n", ["did you mean `str`?", "unknown type 'string'"]),
+    ];
+    for (name, program, expected) in cases {
+        let stderr = compile(&work, name, program, "c", &[]).unwrap_err();
+        for text in expected {
+            assert!(stderr.contains(text), "{} has no {:?}:\n{}", name, text, stderr);
+        }
+        assert!(!stderr.contains("declare it first"), "{} suggests a declaration:\n{}", name, stderr);
+    }
+}
@@ -0,0 +1,10 @@
+#!/usr/bin/env bash
+# Golden tests for the transpilers; the checks themselves live in tests/golden.rs.
+#
+# Run with --bless to rewrite the expected files after an intended change.
+set -u
+
+root="$(cd "$(dirname "$0")/.." && pwd)"
+[ "${1:-}" = "--bless" ] && export VEN_BLESS=1
+
+exec cargo test -q --manifest-path "$root/Cargo.toml" --test golden
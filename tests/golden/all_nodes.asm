@@ -0,0 +1,604 @@
+;; Generated by the Ven compiler
+bits 64
+default rel   ;; RIP-relative addressing for every label
+
+section .data
+    newline: db 10
+    lit_true: db "true"
+    lit_false: db "false"
+    lit_undefined: db "[undefined]"
+    str_0: db "Paris", 0
+    str_1: db "Hello, Alice!", 10, "Age ", 0
+    str_2: db "age", 0
+    str_3: db "i64", 0
+    str_4: db "adult ", 0
+    str_5: db "nested", 0
+    str_6: db "city ", 0
+    str_7: db "err ", 0
+
+section .bss
+    alignb 8
+    num_buf: resb 32
+    alignb 8
+    line_buf: resb 256
+    alignb 8
+    var_age: resb 8
+    alignb 8
+    var_city: resb 256
+
+section .text
+global _start
+_start:
+    ;; declare age
+    mov rdi, 30
+    mov [var_age], rdi
+    ;; declare city
+    lea rdi, [var_city]
+    lea rsi, [str_0]
+    mov rdx, 256
+    call ven_str_copy
+    ;; print to fd 1
+    mov rdi, 1
+    lea rsi, [str_1]
+    mov rdx, 18
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    mov rdi, [var_age]
+    mov rsi, 1
+    call ven_print_int
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ;; read a line into age
+    lea rdi, [line_buf]
+    mov rsi, 255
+    call ven_read_line
+    lea rdi, [line_buf]
+    call ven_parse_int
+    mov rdx, -9223372036854775808
+    mov r8, 9223372036854775807
+    lea r9, [str_2]
+    lea r10, [str_3]
+    call ven_fit
+    mov [var_age], rdi
+    ;; math on age
+    mov rdi, 10
+    mov rsi, rdi
+    mov rdi, [var_age]
+    lea r9, [str_2]
+    lea r10, [str_3]
+    call ven_add_i64
+    mov [var_age], rdi
+    ;; math on age
+    mov rdi, 2
+    mov rsi, rdi
+    mov rdi, [var_age]
+    lea r9, [str_2]
+    lea r10, [str_3]
+    call ven_mul_i64
+    mov [var_age], rdi
+    ;; if
+    mov rsi, 18
+    mov rdi, [var_age]
+    cmp rdi, rsi
+    jle L1_endif
+    ;; print to fd 1
+    mov rdi, 1
+    lea rsi, [str_4]
+    mov rdx, 6
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    mov rdi, [var_age]
+    mov rsi, 1
+    call ven_print_int
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ;; math on age
+    mov rdi, 1
+    mov rsi, rdi
+    mov rdi, [var_age]
+    lea r9, [str_2]
+    lea r10, [str_3]
+    call ven_sub_i64
+    mov [var_age], rdi
+    ;; if
+    mov rsi, 59
+    mov rdi, [var_age]
+    cmp rdi, rsi
+    jne L2_endif
+    ;; print to fd 1
+    mov rdi, 1
+    lea rsi, [str_5]
+    mov rdx, 6
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+L2_endif:
+L1_endif:
+    ;; if
+    lea rdi, [var_city]
+    lea rsi, [str_0]
+    call ven_str_cmp
+    cmp rdi, 0
+    jne L3_endif
+    mov rsi, 1
+    mov rdi, [var_age]
+    cmp rdi, rsi
+    jl L3_endif
+    ;; print to fd 1
+    mov rdi, 1
+    lea rsi, [str_6]
+    mov rdx, 5
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    lea rdi, [var_city]
+    mov rsi, 1
+    call ven_print_cstr
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+L3_endif:
+    ;; print to fd 2
+    mov rdi, 2
+    lea rsi, [str_7]
+    mov rdx, 4
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    mov rdi, [var_age]
+    mov rsi, 2
+    call ven_print_int
+    mov rdi, 2
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ;; exit(0)
+    mov rdi, 0
+    mov eax, 60   ;; syscall: Exit
+    syscall
+    ;; ---- runtime ----
+ven_print_int:
+    lea rdx, [num_buf]
+    add rdx, 32
+    mov r8, 0
+    cmp rdi, 0
+    jl ven_print_int_digits
+    mov r9, 0
+    sub r9, rdi
+    mov rdi, r9
+    mov r8, 1
+ven_print_int_digits:
+    mov r9, rdi
+    mov r10, 10
+    mov rax, r9
+    mov rcx, r10
+    mov r11, rdx   ;; save rdx around idiv
+    cqo   ;; sign-extend rax into rdx:rax
+    idiv rcx
+    mov rcx, rdx
+    mov rdx, r11
+    mov r9, rcx
+    mov r10, 48
+    sub r10, r9
+    add rdx, -1
+    mov [rdx], r10b
+    mov r10, 10
+    mov rax, rdi
+    mov rcx, r10
+    mov r11, rdx   ;; save rdx around idiv
+    cqo   ;; sign-extend rax into rdx:rax
+    idiv rcx
+    mov rcx, rax
+    mov rdx, r11
+    mov rdi, rcx
+    cmp rdi, 0
+    jne ven_print_int_digits
+    cmp r8, 0
+    jne ven_print_int_write
+    add rdx, -1
+    mov r10, 45
+    mov [rdx], r10b
+ven_print_int_write:
+    lea r9, [num_buf]
+    add r9, 32
+    sub r9, rdx
+    mov rdi, rsi
+    mov rsi, rdx
+    mov rdx, r9
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ret
+ven_print_uint:
+    lea rdx, [num_buf]
+    add rdx, 32
+ven_print_uint_digits:
+    mov r8, rdi
+    mov r10, 10
+    mov rax, r8
+    mov rcx, r10
+    mov r11, rdx   ;; save rdx around div
+    xor edx, edx   ;; zero-extend rax into rdx:rax
+    div rcx
+    mov rdx, r11
+    mov r8, rax
+    mov r9, r8
+    imul r9, r10
+    mov r10, rdi
+    sub r10, r9
+    add r10, 48
+    add rdx, -1
+    mov [rdx], r10b
+    mov rdi, r8
+    cmp rdi, 0
+    jne ven_print_uint_digits
+    jmp ven_print_int_write
+ven_print_cstr:
+    mov rdx, rdi
+ven_print_cstr_len:
+    movzx r8, byte [rdx]
+    cmp r8, 0
+    je ven_print_cstr_write
+    add rdx, 1
+    jmp ven_print_cstr_len
+ven_print_cstr_write:
+    sub rdx, rdi
+    cmp rdx, 0
+    je ven_print_cstr_done
+    mov r8, rdi
+    mov rdi, rsi
+    mov rsi, r8
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+ven_print_cstr_done:
+    ret
+ven_read_line:
+    mov r9, rdi
+    mov r10, rdi
+    add r10, rsi
+ven_read_line_next:
+    mov rdi, 0
+    mov rsi, r9
+    mov rdx, 1
+    mov eax, 0   ;; syscall: Read
+    syscall
+    mov rdi, rax   ;; result into R0
+    cmp rdi, 1
+    jl ven_read_line_done
+    movzx r8, byte [r9]
+    cmp r8, 10
+    je ven_read_line_done
+    cmp r8, 13
+    je ven_read_line_next
+    cmp r9, r10
+    jge ven_read_line_next
+    add r9, 1
+    jmp ven_read_line_next
+ven_read_line_done:
+    mov r8, 0
+    mov [r9], r8b
+    ret
+ven_parse_int:
+    mov rsi, rdi
+    mov rdi, 0
+    mov rdx, 0
+ven_parse_int_space:
+    movzx r8, byte [rsi]
+    cmp r8, 32
+    jne ven_parse_int_sign
+    add rsi, 1
+    jmp ven_parse_int_space
+ven_parse_int_sign:
+    cmp r8, 45
+    jne ven_parse_int_plus
+    mov rdx, 1
+    add rsi, 1
+    jmp ven_parse_int_digit
+ven_parse_int_plus:
+    cmp r8, 43
+    jne ven_parse_int_digit
+    add rsi, 1
+ven_parse_int_digit:
+    movzx r8, byte [rsi]
+    cmp r8, 48
+    jl ven_parse_int_end
+    cmp r8, 57
+    jg ven_parse_int_end
+    add r8, -48
+    mov r9, -1
+    sub r9, r8
+    mov r10, 10
+    mov rax, r9
+    mov rcx, r10
+    mov r11, rdx   ;; save rdx around div
+    xor edx, edx   ;; zero-extend rax into rdx:rax
+    div rcx
+    mov rdx, r11
+    mov r9, rax
+    cmp rdi, r9
+    ja ven_parse_int_range
+    mov r9, 10
+    imul rdi, r9
+    add rdi, r8
+    add rsi, 1
+    jmp ven_parse_int_digit
+ven_parse_int_end:
+    mov rsi, 0
+    cmp rdx, 0
+    je ven_parse_int_positive
+    mov r9, -9223372036854775808
+    cmp rdi, r9
+    ja ven_parse_int_range
+    mov r9, 0
+    sub r9, rdi
+    mov rdi, r9
+    ret
+ven_parse_int_positive:
+    cmp rdi, 0
+    jge ven_parse_int_done
+    mov rsi, 1
+ven_parse_int_done:
+    ret
+ven_parse_int_range:
+    mov rdi, 0
+    mov rsi, 0
+    ret
+ven_compare:
+    cmp rdx, 0
+    jne ven_compare_b
+    cmp rdi, 0
+    jl ven_compare_negative
+ven_compare_b:
+    cmp r8, 0
+    jne ven_compare_unsigned
+    cmp rsi, 0
+    jl ven_compare_greater
+ven_compare_unsigned:
+    cmp rdi, rsi
+    jb ven_compare_less
+    ja ven_compare_greater
+    mov rdi, 0
+    ret
+ven_compare_negative:
+    cmp r8, 0
+    jne ven_compare_less
+    cmp rdi, rsi
+    jl ven_compare_less
+    jg ven_compare_greater
+    mov rdi, 0
+    ret
+ven_compare_less:
+    mov rdi, -1
+    ret
+ven_compare_greater:
+    mov rdi, 1
+    ret
+ven_str_cmp:
+    movzx rdx, byte [rdi]
+    movzx r8, byte [rsi]
+    cmp rdx, r8
+    jl ven_str_cmp_less
+    jg ven_str_cmp_greater
+    cmp rdx, 0
+    je ven_str_cmp_equal
+    add rdi, 1
+    add rsi, 1
+    jmp ven_str_cmp
+ven_str_cmp_less:
+    mov rdi, -1
+    ret
+ven_str_cmp_greater:
+    mov rdi, 1
+    ret
+ven_str_cmp_equal:
+    mov rdi, 0
+    ret
+ven_str_copy:
+    mov r8, rdi
+    add r8, rdx
+    add r8, -1
+    mov rdx, rdi
+ven_str_copy_next:
+    cmp rdi, r8
+    jge ven_str_copy_cut
+    movzx r9, byte [rsi]
+    cmp r9, 0
+    je ven_str_copy_done
+    mov [rdi], r9b
+    add rdi, 1
+    add rsi, 1
+    jmp ven_str_copy_next
+ven_str_copy_cut:
+    cmp rdi, rdx
+    jbe ven_str_copy_done
+    movzx r9, byte [rsi]
+    cmp r9, 128
+    jb ven_str_copy_done
+    cmp r9, 191
+    ja ven_str_copy_done
+    add rdi, -1
+    add rsi, -1
+    jmp ven_str_copy_cut
+ven_str_copy_done:
+    mov r9, 0
+    mov [rdi], r9b
+    ret
+    ;; ---- integer overflow ----
+ven_fit:
+    cmp rdx, 0
+    jne ven_fit_signed
+    cmp r8, -1
+    jne ven_fit_signed
+    cmp rsi, 0
+    jne ven_fit_ok
+    cmp rdi, 0
+    jge ven_fit_ok
+    ret
+ven_fit_signed:
+    cmp rsi, 0
+    je ven_fit_range
+    cmp rdi, 0
+    jl ven_fit_above
+ven_fit_range:
+    cmp rdi, rdx
+    jl ven_fit_below
+    cmp rdi, r8
+    jg ven_fit_above
+ven_fit_ok:
+    ret
+ven_fit_below:
+    jmp ven_fit_wrap
+ven_fit_above:
+    jmp ven_fit_wrap
+ven_fit_wrap:
+    mov r9, r8
+    sub r9, rdx
+    add r9, 1
+    cmp r9, 0
+    je ven_fit_ok
+    mov rax, rdi
+    mov rcx, r9
+    mov r11, rdx   ;; save rdx around idiv
+    cqo   ;; sign-extend rax into rdx:rax
+    idiv rcx
+    mov rcx, rdx
+    mov rdx, r11
+    mov rdi, rcx
+    cmp rdi, rdx
+    jge ven_fit_wrap_high
+    add rdi, r9
+ven_fit_wrap_high:
+    cmp rdi, r8
+    jle ven_fit_ok
+    sub rdi, r9
+    ret
+ven_add_i64:
+    mov rdx, rdi
+    add rdi, rsi
+    cmp rsi, 0
+    jl ven_add_i64_negative
+    cmp rdi, rdx
+    jl ven_add_i64_overflow
+    ret
+ven_add_i64_negative:
+    cmp rdi, rdx
+    jg ven_add_i64_overflow
+    ret
+ven_add_i64_overflow:
+    ret
+ven_sub_i64:
+    mov rdx, rdi
+    sub rdi, rsi
+    cmp rsi, 0
+    jl ven_sub_i64_negative
+    cmp rdi, rdx
+    jg ven_sub_i64_overflow
+    ret
+ven_sub_i64_negative:
+    cmp rdi, rdx
+    jl ven_sub_i64_overflow
+    ret
+ven_sub_i64_overflow:
+    ret
+ven_mul_i64:
+    mov rdx, rdi
+    imul rdi, rsi
+    cmp rdx, -1
+    jne ven_mul_i64_divide
+    mov r8, -9223372036854775808
+    cmp rsi, r8
+    je ven_mul_i64_overflow
+    ret
+ven_mul_i64_divide:
+    cmp rdx, 0
+    je ven_mul_i64_done
+    mov r8, rdi
+    mov rax, r8
+    mov rcx, rdx
+    mov r11, rdx   ;; save rdx around idiv
+    cqo   ;; sign-extend rax into rdx:rax
+    idiv rcx
+    mov rcx, rax
+    mov rdx, r11
+    mov r8, rcx
+    cmp r8, rsi
+    jne ven_mul_i64_overflow
+ven_mul_i64_done:
+    ret
+ven_mul_i64_overflow:
+    ret
+ven_div_i64:
+    cmp rsi, -1
+    jne ven_div_i64_divide
+    mov r8, -9223372036854775808
+    cmp rdi, r8
+    je ven_div_i64_overflow
+ven_div_i64_divide:
+    mov rax, rdi
+    mov rcx, rsi
+    mov r11, rdx   ;; save rdx around idiv
+    cqo   ;; sign-extend rax into rdx:rax
+    idiv rcx
+    mov rcx, rax
+    mov rdx, r11
+    mov rdi, rcx
+    ret
+ven_div_i64_overflow:
+    ret
+ven_add_u64:
+    mov rdx, rdi
+    add rdi, rsi
+    cmp rdi, rdx
+    jb ven_add_u64_overflow
+    ret
+ven_add_u64_overflow:
+    ret
+ven_sub_u64:
+    mov rdx, rdi
+    sub rdi, rsi
+    cmp rdx, rsi
+    jb ven_sub_u64_overflow
+    ret
+ven_sub_u64_overflow:
+    ret
+ven_mul_u64:
+    mov rdx, rdi
+    imul rdi, rsi
+    cmp rdx, 0
+    je ven_mul_u64_done
+    mov r8, rdi
+    mov rax, r8
+    mov rcx, rdx
+    mov r11, rdx   ;; save rdx around div
+    xor edx, edx   ;; zero-extend rax into rdx:rax
+    div rcx
+    mov rdx, r11
+    mov r8, rax
+    cmp r8, rsi
+    jne ven_mul_u64_overflow
+ven_mul_u64_done:
+    ret
+ven_mul_u64_overflow:
+    ret
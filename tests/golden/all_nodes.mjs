@@ -0,0 +1,67 @@
+// Generated by the Ven compiler
+import fs from "node:fs";
+
+const __byte = Buffer.alloc(1);
+function __readLine() {
+  const bytes = [];
+  for (;;) {
+    let read = 0;
+    try {
+      read = fs.readSync(0, __byte, 0, 1, null);
+    } catch (e) {
+      if (e.code === "EAGAIN") continue;
+      if (e.code === "EOF") break;
+      throw e;
+    }
+    if (read === 0 || __byte[0] === 10) break;
+    bytes.push(__byte[0]);
+  }
+  if (bytes[bytes.length - 1] === 13) bytes.pop();
+  // A line holds at most 255 bytes, as on the native targets, and a character
+  // cut in two is dropped whole
+  let end = Math.min(bytes.length, 255);
+  while (end > 0 && end < bytes.length && (bytes[end] & 0xc0) === 0x80) end--;
+  return Buffer.from(bytes.slice(0, end)).toString("utf8");
+}
+
+// Integers are BigInts. Text that is not a whole number from the i64 minimum to
+// the u64 maximum reads as 0.
+function __toInt(text) {
+  const trimmed = text.trim();
+  if (!/^[+-]?[0-9]+$/.test(trimmed)) return 0n;
+  const value = BigInt(trimmed);
+  return value < -(2n ** 63n) || value >= 2n ** 64n ? 0n : value;
+}
+
+function __fit(value, min, max, name, type, line) {
+  if (value >= min && value <= max) return value;
+  const span = max - min + 1n;
+  return (((value - min) % span) + span) % span + min;
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+function __fitFloat(value, min, max, name, type, line) {
+  if (Number.isNaN(value)) return 0n;
+  const whole = Math.trunc(value);
+  return whole < Number(min) ? min : whole >= Number(max) + 1 ? max : BigInt(whole);
+}
+
+let age, city;
+
+age = 30n;
+city = "Paris";
+process.stdout.write(`Hello, Alice!\nAge ${age}\n`);
+age = __fit(__toInt(__readLine()), -9223372036854775808n, 9223372036854775807n, "age", "i64", 8);
+age = __fit(age + 10n, -9223372036854775808n, 9223372036854775807n, "age", "i64", 9);
+age = __fit(age * 2n, -9223372036854775808n, 9223372036854775807n, "age", "i64", 10);
+if (age > 18n) {
+  process.stdout.write(`adult ${age}\n`);
+  age = __fit(age - 1n, -9223372036854775808n, 9223372036854775807n, "age", "i64", 13);
+  if (age === 59n) {
+    process.stdout.write(`nested\n`);
+  }
+}
+if ((city === "Paris") && (age >= 1n)) {
+  process.stdout.write(`city ${city}\n`);
+}
+process.stderr.write(`err ${age}\n`);
@@ -0,0 +1,63 @@
+# Generated by the Ven compiler
+import sys
+
+
+def _read_line():
+    # A line holds at most 255 bytes, as on the native targets
+    line = sys.stdin.readline().rstrip("\r\n").encode()
+    return line[:255].decode(errors="ignore")
+
+
+# Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+def _to_int(text):
+    try:
+        value = int(text.strip())
+    except ValueError:
+        return 0
+    return value if -(2**63) <= value < 2**64 else 0
+
+
+def _to_float(text):
+    try:
+        return float(text.strip())
+    except ValueError:
+        return 0.0
+
+
+def _int_div(a, b):
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+
+
+def _fit(value, lo, hi, name, type, line):
+    return (value - lo) % (hi - lo + 1) + lo
+
+
+# Floats never wrap: they are truncated and clamp at the limits
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+
+
+age = 0
+city = ""
+
+age = 30
+city = "Paris"
+print(f"Hello, Alice!\nAge {age}")
+age = _fit(_to_int(_read_line()), -9223372036854775808, 9223372036854775807, "age", "i64", 8)
+age = _fit(age + 10, -9223372036854775808, 9223372036854775807, "age", "i64", 9)
+age = _fit(age * 2, -9223372036854775808, 9223372036854775807, "age", "i64", 10)
+if age > 18:
+    print(f"adult {age}")
+    age = _fit(age - 1, -9223372036854775808, 9223372036854775807, "age", "i64", 13)
+    if age == 59:
+        print("nested")
+if (city == "Paris") and (age >= 1):
+    print(f"city {city}")
+print(f"err {age}", file=sys.stderr)
@@ -0,0 +1,637 @@
+// Generated by the Ven compiler
+
+.data
+newline:
+    .byte 10
+lit_true:
+    .ascii "true"
+lit_false:
+    .ascii "false"
+lit_undefined:
+    .ascii "[undefined]"
+str_0:
+    .ascii "Paris"
+    .byte 0
+str_1:
+    .ascii "Hello, Alice!"
+    .byte 10
+    .ascii "Age "
+    .byte 0
+str_2:
+    .ascii "age"
+    .byte 0
+str_3:
+    .ascii "i64"
+    .byte 0
+str_4:
+    .ascii "adult "
+    .byte 0
+str_5:
+    .ascii "nested"
+    .byte 0
+str_6:
+    .ascii "city "
+    .byte 0
+str_7:
+    .ascii "err "
+    .byte 0
+
+.bss
+    .balign 8
+num_buf:
+    .skip 32
+    .balign 8
+line_buf:
+    .skip 256
+    .balign 8
+var_age:
+    .skip 8
+    .balign 8
+var_city:
+    .skip 256
+
+.text
+.global _start
+_start:
+    // declare age
+    mov x0, #30
+    adrp x9, var_age
+    str x0, [x9, :lo12:var_age]
+    // declare city
+    adrp x0, var_city
+    add x0, x0, :lo12:var_city
+    adrp x1, str_0
+    add x1, x1, :lo12:str_0
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    // print to fd 1
+    mov x0, #1
+    adrp x1, str_1
+    add x1, x1, :lo12:str_1
+    mov x2, #18
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_age
+    ldr x0, [x9, :lo12:var_age]
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // read a line into age
+    adrp x0, line_buf
+    add x0, x0, :lo12:line_buf
+    mov x1, #255
+    str x30, [sp, #-16]!
+    bl ven_read_line
+    ldr x30, [sp], #16
+    adrp x0, line_buf
+    add x0, x0, :lo12:line_buf
+    str x30, [sp, #-16]!
+    bl ven_parse_int
+    ldr x30, [sp], #16
+    movz x2, #0
+    movk x2, #32768, lsl #48
+    movz x3, #65535
+    movk x3, #65535, lsl #16
+    movk x3, #65535, lsl #32
+    movk x3, #32767, lsl #48
+    adrp x4, str_2
+    add x4, x4, :lo12:str_2
+    adrp x5, str_3
+    add x5, x5, :lo12:str_3
+    str x30, [sp, #-16]!
+    bl ven_fit
+    ldr x30, [sp], #16
+    adrp x9, var_age
+    str x0, [x9, :lo12:var_age]
+    // math on age
+    mov x0, #10
+    mov x1, x0
+    adrp x9, var_age
+    ldr x0, [x9, :lo12:var_age]
+    adrp x4, str_2
+    add x4, x4, :lo12:str_2
+    adrp x5, str_3
+    add x5, x5, :lo12:str_3
+    str x30, [sp, #-16]!
+    bl ven_add_i64
+    ldr x30, [sp], #16
+    adrp x9, var_age
+    str x0, [x9, :lo12:var_age]
+    // math on age
+    mov x0, #2
+    mov x1, x0
+    adrp x9, var_age
+    ldr x0, [x9, :lo12:var_age]
+    adrp x4, str_2
+    add x4, x4, :lo12:str_2
+    adrp x5, str_3
+    add x5, x5, :lo12:str_3
+    str x30, [sp, #-16]!
+    bl ven_mul_i64
+    ldr x30, [sp], #16
+    adrp x9, var_age
+    str x0, [x9, :lo12:var_age]
+    // if
+    mov x1, #18
+    adrp x9, var_age
+    ldr x0, [x9, :lo12:var_age]
+    cmp x0, x1
+    b.le L1_endif
+    // print to fd 1
+    mov x0, #1
+    adrp x1, str_4
+    add x1, x1, :lo12:str_4
+    mov x2, #6
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_age
+    ldr x0, [x9, :lo12:var_age]
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // math on age
+    mov x0, #1
+    mov x1, x0
+    adrp x9, var_age
+    ldr x0, [x9, :lo12:var_age]
+    adrp x4, str_2
+    add x4, x4, :lo12:str_2
+    adrp x5, str_3
+    add x5, x5, :lo12:str_3
+    str x30, [sp, #-16]!
+    bl ven_sub_i64
+    ldr x30, [sp], #16
+    adrp x9, var_age
+    str x0, [x9, :lo12:var_age]
+    // if
+    mov x1, #59
+    adrp x9, var_age
+    ldr x0, [x9, :lo12:var_age]
+    cmp x0, x1
+    b.ne L2_endif
+    // print to fd 1
+    mov x0, #1
+    adrp x1, str_5
+    add x1, x1, :lo12:str_5
+    mov x2, #6
+    mov x8, #64   // syscall: Write
+    svc #0
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+L2_endif:
+L1_endif:
+    // if
+    adrp x0, var_city
+    add x0, x0, :lo12:var_city
+    adrp x1, str_0
+    add x1, x1, :lo12:str_0
+    str x30, [sp, #-16]!
+    bl ven_str_cmp
+    ldr x30, [sp], #16
+    cmp x0, #0
+    b.ne L3_endif
+    mov x1, #1
+    adrp x9, var_age
+    ldr x0, [x9, :lo12:var_age]
+    cmp x0, x1
+    b.lt L3_endif
+    // print to fd 1
+    mov x0, #1
+    adrp x1, str_6
+    add x1, x1, :lo12:str_6
+    mov x2, #5
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x0, var_city
+    add x0, x0, :lo12:var_city
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_cstr
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+L3_endif:
+    // print to fd 2
+    mov x0, #2
+    adrp x1, str_7
+    add x1, x1, :lo12:str_7
+    mov x2, #4
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_age
+    ldr x0, [x9, :lo12:var_age]
+    mov x1, #2
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #2
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // exit(0)
+    mov x0, #0
+    mov x8, #93   // syscall: Exit
+    svc #0
+    // ---- runtime ----
+ven_print_int:
+    adrp x2, num_buf
+    add x2, x2, :lo12:num_buf
+    add x2, x2, #32
+    mov x3, #0
+    cmp x0, #0
+    b.lt ven_print_int_digits
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    mov x3, #1
+ven_print_int_digits:
+    mov x4, x0
+    mov x5, #10
+    sdiv x9, x4, x5
+    msub x4, x9, x5, x4
+    mov x5, #48
+    sub x5, x5, x4
+    sub x2, x2, #1
+    strb w5, [x2]
+    mov x5, #10
+    sdiv x0, x0, x5
+    cmp x0, #0
+    b.ne ven_print_int_digits
+    cmp x3, #0
+    b.ne ven_print_int_write
+    sub x2, x2, #1
+    mov x5, #45
+    strb w5, [x2]
+ven_print_int_write:
+    adrp x4, num_buf
+    add x4, x4, :lo12:num_buf
+    add x4, x4, #32
+    sub x4, x4, x2
+    mov x0, x1
+    mov x1, x2
+    mov x2, x4
+    mov x8, #64   // syscall: Write
+    svc #0
+    ret
+ven_print_uint:
+    adrp x2, num_buf
+    add x2, x2, :lo12:num_buf
+    add x2, x2, #32
+ven_print_uint_digits:
+    mov x3, x0
+    mov x5, #10
+    udiv x3, x3, x5
+    mov x4, x3
+    mul x4, x4, x5
+    mov x5, x0
+    sub x5, x5, x4
+    add x5, x5, #48
+    sub x2, x2, #1
+    strb w5, [x2]
+    mov x0, x3
+    cmp x0, #0
+    b.ne ven_print_uint_digits
+    b ven_print_int_write
+ven_print_cstr:
+    mov x2, x0
+ven_print_cstr_len:
+    ldrb w3, [x2]
+    cmp x3, #0
+    b.eq ven_print_cstr_write
+    add x2, x2, #1
+    b ven_print_cstr_len
+ven_print_cstr_write:
+    sub x2, x2, x0
+    cmp x2, #0
+    b.eq ven_print_cstr_done
+    mov x3, x0
+    mov x0, x1
+    mov x1, x3
+    mov x8, #64   // syscall: Write
+    svc #0
+ven_print_cstr_done:
+    ret
+ven_read_line:
+    mov x4, x0
+    mov x5, x0
+    add x5, x5, x1
+ven_read_line_next:
+    mov x0, #0
+    mov x1, x4
+    mov x2, #1
+    mov x8, #63   // syscall: Read
+    svc #0
+    cmp x0, #1
+    b.lt ven_read_line_done
+    ldrb w3, [x4]
+    cmp x3, #10
+    b.eq ven_read_line_done
+    cmp x3, #13
+    b.eq ven_read_line_next
+    cmp x4, x5
+    b.ge ven_read_line_next
+    add x4, x4, #1
+    b ven_read_line_next
+ven_read_line_done:
+    mov x3, #0
+    strb w3, [x4]
+    ret
+ven_parse_int:
+    mov x1, x0
+    mov x0, #0
+    mov x2, #0
+ven_parse_int_space:
+    ldrb w3, [x1]
+    cmp x3, #32
+    b.ne ven_parse_int_sign
+    add x1, x1, #1
+    b ven_parse_int_space
+ven_parse_int_sign:
+    cmp x3, #45
+    b.ne ven_parse_int_plus
+    mov x2, #1
+    add x1, x1, #1
+    b ven_parse_int_digit
+ven_parse_int_plus:
+    cmp x3, #43
+    b.ne ven_parse_int_digit
+    add x1, x1, #1
+ven_parse_int_digit:
+    ldrb w3, [x1]
+    cmp x3, #48
+    b.lt ven_parse_int_end
+    cmp x3, #57
+    b.gt ven_parse_int_end
+    sub x3, x3, #48
+    mov x4, #-1
+    sub x4, x4, x3
+    mov x5, #10
+    udiv x4, x4, x5
+    cmp x0, x4
+    b.hi ven_parse_int_range
+    mov x4, #10
+    mul x0, x0, x4
+    add x0, x0, x3
+    add x1, x1, #1
+    b ven_parse_int_digit
+ven_parse_int_end:
+    mov x1, #0
+    cmp x2, #0
+    b.eq ven_parse_int_positive
+    movz x4, #0
+    movk x4, #32768, lsl #48
+    cmp x0, x4
+    b.hi ven_parse_int_range
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    ret
+ven_parse_int_positive:
+    cmp x0, #0
+    b.ge ven_parse_int_done
+    mov x1, #1
+ven_parse_int_done:
+    ret
+ven_parse_int_range:
+    mov x0, #0
+    mov x1, #0
+    ret
+ven_compare:
+    cmp x2, #0
+    b.ne ven_compare_b
+    cmp x0, #0
+    b.lt ven_compare_negative
+ven_compare_b:
+    cmp x3, #0
+    b.ne ven_compare_unsigned
+    cmp x1, #0
+    b.lt ven_compare_greater
+ven_compare_unsigned:
+    cmp x0, x1
+    b.lo ven_compare_less
+    b.hi ven_compare_greater
+    mov x0, #0
+    ret
+ven_compare_negative:
+    cmp x3, #0
+    b.ne ven_compare_less
+    cmp x0, x1
+    b.lt ven_compare_less
+    b.gt ven_compare_greater
+    mov x0, #0
+    ret
+ven_compare_less:
+    mov x0, #-1
+    ret
+ven_compare_greater:
+    mov x0, #1
+    ret
+ven_str_cmp:
+    ldrb w2, [x0]
+    ldrb w3, [x1]
+    cmp x2, x3
+    b.lt ven_str_cmp_less
+    b.gt ven_str_cmp_greater
+    cmp x2, #0
+    b.eq ven_str_cmp_equal
+    add x0, x0, #1
+    add x1, x1, #1
+    b ven_str_cmp
+ven_str_cmp_less:
+    mov x0, #-1
+    ret
+ven_str_cmp_greater:
+    mov x0, #1
+    ret
+ven_str_cmp_equal:
+    mov x0, #0
+    ret
+ven_str_copy:
+    mov x3, x0
+    add x3, x3, x2
+    sub x3, x3, #1
+    mov x2, x0
+ven_str_copy_next:
+    cmp x0, x3
+    b.ge ven_str_copy_cut
+    ldrb w4, [x1]
+    cmp x4, #0
+    b.eq ven_str_copy_done
+    strb w4, [x0]
+    add x0, x0, #1
+    add x1, x1, #1
+    b ven_str_copy_next
+ven_str_copy_cut:
+    cmp x0, x2
+    b.ls ven_str_copy_done
+    ldrb w4, [x1]
+    cmp x4, #128
+    b.lo ven_str_copy_done
+    cmp x4, #191
+    b.hi ven_str_copy_done
+    sub x0, x0, #1
+    sub x1, x1, #1
+    b ven_str_copy_cut
+ven_str_copy_done:
+    mov x4, #0
+    strb w4, [x0]
+    ret
+    // ---- integer overflow ----
+ven_fit:
+    cmp x2, #0
+    b.ne ven_fit_signed
+    cmn x3, #1
+    b.ne ven_fit_signed
+    cmp x1, #0
+    b.ne ven_fit_ok
+    cmp x0, #0
+    b.ge ven_fit_ok
+    ret
+ven_fit_signed:
+    cmp x1, #0
+    b.eq ven_fit_range
+    cmp x0, #0
+    b.lt ven_fit_above
+ven_fit_range:
+    cmp x0, x2
+    b.lt ven_fit_below
+    cmp x0, x3
+    b.gt ven_fit_above
+ven_fit_ok:
+    ret
+ven_fit_below:
+    b ven_fit_wrap
+ven_fit_above:
+    b ven_fit_wrap
+ven_fit_wrap:
+    mov x4, x3
+    sub x4, x4, x2
+    add x4, x4, #1
+    cmp x4, #0
+    b.eq ven_fit_ok
+    sdiv x9, x0, x4
+    msub x0, x9, x4, x0
+    cmp x0, x2
+    b.ge ven_fit_wrap_high
+    add x0, x0, x4
+ven_fit_wrap_high:
+    cmp x0, x3
+    b.le ven_fit_ok
+    sub x0, x0, x4
+    ret
+ven_add_i64:
+    mov x2, x0
+    add x0, x0, x1
+    cmp x1, #0
+    b.lt ven_add_i64_negative
+    cmp x0, x2
+    b.lt ven_add_i64_overflow
+    ret
+ven_add_i64_negative:
+    cmp x0, x2
+    b.gt ven_add_i64_overflow
+    ret
+ven_add_i64_overflow:
+    ret
+ven_sub_i64:
+    mov x2, x0
+    sub x0, x0, x1
+    cmp x1, #0
+    b.lt ven_sub_i64_negative
+    cmp x0, x2
+    b.gt ven_sub_i64_overflow
+    ret
+ven_sub_i64_negative:
+    cmp x0, x2
+    b.lt ven_sub_i64_overflow
+    ret
+ven_sub_i64_overflow:
+    ret
+ven_mul_i64:
+    mov x2, x0
+    mul x0, x0, x1
+    cmn x2, #1
+    b.ne ven_mul_i64_divide
+    movz x3, #0
+    movk x3, #32768, lsl #48
+    cmp x1, x3
+    b.eq ven_mul_i64_overflow
+    ret
+ven_mul_i64_divide:
+    cmp x2, #0
+    b.eq ven_mul_i64_done
+    mov x3, x0
+    sdiv x3, x3, x2
+    cmp x3, x1
+    b.ne ven_mul_i64_overflow
+ven_mul_i64_done:
+    ret
+ven_mul_i64_overflow:
+    ret
+ven_div_i64:
+    cmn x1, #1
+    b.ne ven_div_i64_divide
+    movz x3, #0
+    movk x3, #32768, lsl #48
+    cmp x0, x3
+    b.eq ven_div_i64_overflow
+ven_div_i64_divide:
+    sdiv x0, x0, x1
+    ret
+ven_div_i64_overflow:
+    ret
+ven_add_u64:
+    mov x2, x0
+    add x0, x0, x1
+    cmp x0, x2
+    b.lo ven_add_u64_overflow
+    ret
+ven_add_u64_overflow:
+    ret
+ven_sub_u64:
+    mov x2, x0
+    sub x0, x0, x1
+    cmp x2, x1
+    b.lo ven_sub_u64_overflow
+    ret
+ven_sub_u64_overflow:
+    ret
+ven_mul_u64:
+    mov x2, x0
+    mul x0, x0, x1
+    cmp x2, #0
+    b.eq ven_mul_u64_done
+    mov x3, x0
+    udiv x3, x3, x2
+    cmp x3, x1
+    b.ne ven_mul_u64_overflow
+ven_mul_u64_done:
+    ret
+ven_mul_u64_overflow:
+    ret
@@ -0,0 +1,257 @@
+// Generated by the Ven compiler
+#include <ctype.h>
+#include <errno.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static char ven_line[256];
+
+/* Wide enough for every value of every Ven integer type. */
+typedef __int128 ven_wide;
+
+/* Reads one line without its newline; carriage returns and overflow are dropped. */
+static void ven_read_line(char *buf, size_t size) {
+    size_t len = 0;
+    int c;
+    while ((c = getchar()) != EOF && c != '\n') {
+        if (c != '\r' && len + 1 < size) {
+            buf[len++] = (char)c;
+        }
+    }
+    buf[len] = '\0';
+}
+
+/* Copies as much of src as fits without splitting a character, always terminating dest. */
+static void ven_copy(char *dest, const char *src, size_t size) {
+    size_t len = strlen(src);
+    if (len >= size) {
+        len = size - 1;
+        while (len > 0 && ((unsigned char)src[len] & 0xC0) == 0x80) {
+            len--;
+        }
+    }
+    memmove(dest, src, len);
+    dest[len] = '\0';
+}
+
+static bool ven_blank(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return *text == '\0';
+}
+
+/* Text that is not a whole number from INT64_MIN to UINT64_MAX reads as 0. */
+static ven_wide ven_to_int(const char *text) {
+    char *end;
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    errno = 0;
+    if (*text == '-') {
+        long long value = strtoll(text, &end, 10);
+        return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+    }
+    unsigned long long value = strtoull(text, &end, 10);
+    return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+}
+
+static double ven_to_float(const char *text) {
+    char *end;
+    double value = strtod(text, &end);
+    return end == text || !ven_blank(end) ? 0.0 : value;
+}
+
+/* Only the word true reads as true. */
+static bool ven_to_bool(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return strncmp(text, "true", 4) == 0 && ven_blank(text + 4);
+}
+
+/* The shortest digits that read back as the same double, written out without
+   an exponent, like 0.001 or 1000000, into buf of 400 bytes. */
+static const char *ven_float_text(char *buf, double x) {
+    if (x != x) {
+        return "NaN";
+    }
+    /* Only an infinity is not finite after x - x */
+    if (x - x != 0) {
+        return x < 0 ? "-inf" : "inf";
+    }
+    char shortest[32];
+    for (int precision = 1; precision <= 17; precision++) {
+        snprintf(shortest, sizeof shortest, "%.*e", precision - 1, x);
+        if (strtod(shortest, NULL) == x) {
+            break;
+        }
+    }
+    char digits[24];
+    int len = 0;
+    const char *at = shortest;
+    char *out = buf;
+    if (*at == '-') {
+        *out++ = *at++;
+    }
+    for (; *at != 'e'; at++) {
+        if (*at != '.') {
+            digits[len++] = *at;
+        }
+    }
+    int exponent = atoi(at + 1);
+    if (exponent < 0) {
+        *out++ = '0';
+        *out++ = '.';
+        for (int i = -1; i > exponent; i--) {
+            *out++ = '0';
+        }
+        memcpy(out, digits, len);
+        out += len;
+    } else if (exponent + 1 >= len) {
+        memcpy(out, digits, len);
+        out += len;
+        for (int i = len; i <= exponent; i++) {
+            *out++ = '0';
+        }
+    } else {
+        memcpy(out, digits, exponent + 1);
+        out += exponent + 1;
+        *out++ = '.';
+        memcpy(out, digits + exponent + 1, len - exponent - 1);
+        out += len - exponent - 1;
+    }
+    *out = '\0';
+    return buf;
+}
+
+static void ven_print_float(FILE *out, double x) {
+    char buf[400];
+    fputs(ven_float_text(buf, x), out);
+}
+
+static ven_wide ven_fit(ven_wide value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value < min || value > max) {
+        ven_wide span = max - min + 1;
+        value %= span;
+        if (value < min) {
+            value += span;
+        } else if (value > max) {
+            value -= span;
+        }
+    }
+    return value;
+}
+
+/* Floats never wrap: they are truncated and clamp at the limits. */
+static ven_wide ven_fit_float(double value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value != value) {
+        return 0;
+    }
+    /* Truncate toward zero; doubles past 2^63 are whole already */
+    if (value > -9223372036854775808.0 && value < 9223372036854775808.0) {
+        value = (double)(long long)value;
+    }
+    return value < (double)min ? min : value >= (double)max + 1.0 ? max : (ven_wide)value;
+}
+
+/* A u64 product, which can exceed ven_wide. */
+static uint64_t ven_mul_u64(uint64_t a, uint64_t b, const char *name, int line) {
+    (void)name;
+    (void)line;
+    return a * b;
+}
+
+/* Makes room for one more element; running out of memory ends the program. */
+static void *ven_grow(void *items, size_t *cap, size_t len, size_t size) {
+    if (len < *cap) {
+        return items;
+    }
+    *cap = *cap ? *cap * 2 : 8;
+    items = realloc(items, *cap * size);
+    if (items == NULL) {
+        fflush(stdout);
+        fprintf(stderr, "runtime error: out of memory\n");
+        exit(1);
+    }
+    return items;
+}
+
+/* The position of index in the array name of len elements. */
+static size_t ven_index(ven_wide index, size_t len, const char *name, int line) {
+    if (index < 0 || index >= (ven_wide)len) {
+        fflush(stdout);
+        if (index < 0) {
+            fprintf(stderr, "runtime error: index %lld is out of bounds for '%s' (length %zu) on line %d\n", (long long)index, name, len, line);
+        } else {
+            fprintf(stderr, "runtime error: index %llu is out of bounds for '%s' (length %zu) on line %d\n", (unsigned long long)index, name, len, line);
+        }
+        exit(1);
+    }
+    return (size_t)index;
+}
+
+struct { uint8_t *items; size_t len, cap; } scores = {0};
+struct { char (*items)[256]; size_t len, cap; } names = {0};
+int64_t i = 0;
+uint8_t score = 0;
+
+int main(void) {
+    scores.len = 0;
+    scores.items = ven_grow(scores.items, &scores.cap, scores.len, sizeof *scores.items);
+    scores.items[scores.len] = 1;
+    scores.len++;
+    scores.items = ven_grow(scores.items, &scores.cap, scores.len, sizeof *scores.items);
+    scores.items[scores.len] = 2;
+    scores.len++;
+    scores.items = ven_grow(scores.items, &scores.cap, scores.len, sizeof *scores.items);
+    scores.items[scores.len] = 3;
+    scores.len++;
+    names.len = 0;
+    names.items = ven_grow(names.items, &names.cap, names.len, sizeof *names.items);
+    ven_copy(names.items[names.len], "ann", sizeof names.items[names.len]);
+    names.len++;
+    names.items = ven_grow(names.items, &names.cap, names.len, sizeof *names.items);
+    ven_copy(names.items[names.len], "bob", sizeof names.items[names.len]);
+    names.len++;
+    i = 1;
+    fputs("[", stdout);
+    for (size_t ven_i = 0; ven_i < scores.len; ven_i++) {
+        if (ven_i > 0) {
+            fputs(", ", stdout);
+        }
+        fprintf(stdout, "%lld", (long long)scores.items[ven_i]);
+    }
+    fputs("]\n", stdout);
+    {
+        size_t ven_at = ven_index(i, scores.len, "scores", 6);
+        scores.items[ven_at] = 44;
+    }
+    scores.items = ven_grow(scores.items, &scores.cap, scores.len, sizeof *scores.items);
+    scores.items[scores.len] = 4;
+    scores.len++;
+    for (size_t ven_loop1 = 0; ven_loop1 < scores.len; ven_loop1++) {
+        score = scores.items[ven_loop1];
+        if (score > (int64_t)names.len) {
+            fprintf(stdout, "%lld", (long long)score);
+            fputs(" of ", stdout);
+            fprintf(stdout, "%lld", (long long)(int64_t)scores.len);
+            fputs("\n", stdout);
+        }
+    }
+    fputs("first name ", stdout);
+    fputs(names.items[ven_index(0, names.len, "names", 13)], stdout);
+    fputs(", last score ", stdout);
+    fprintf(stdout, "%lld", (long long)scores.items[ven_index(3, scores.len, "scores", 13)]);
+    fputs("\n", stdout);
+    return 0;
+}
@@ -0,0 +1,83 @@
+# Generated by the Ven compiler
+import sys
+
+
+def _read_line():
+    # A line holds at most 255 bytes, as on the native targets
+    line = sys.stdin.readline().rstrip("\r\n").encode()
+    return line[:255].decode(errors="ignore")
+
+
+# Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+def _to_int(text):
+    try:
+        value = int(text.strip())
+    except ValueError:
+        return 0
+    return value if -(2**63) <= value < 2**64 else 0
+
+
+def _to_float(text):
+    try:
+        return float(text.strip())
+    except ValueError:
+        return 0.0
+
+
+def _int_div(a, b):
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+
+
+def _fit(value, lo, hi, name, type, line):
+    return (value - lo) % (hi - lo + 1) + lo
+
+
+# Floats never wrap: they are truncated and clamp at the limits
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+
+
+def _shown(value):
+    if isinstance(value, bool):
+        return str(value).lower()
+    return _float_text(value) if isinstance(value, float) else str(value)
+
+
+def _index(index, length, name, line):
+    if not 0 <= index < length:
+        sys.stdout.flush()
+        print(f"runtime error: index {index} is out of bounds for '{name}' (length {length}) on line {line}", file=sys.stderr)
+        sys.exit(1)
+    return index
+
+
+def _show(items):
+    return "[" + ", ".join(_shown(item) for item in items) + "]"
+
+
+scores = []
+names = []
+i = 0
+score = 0
+
+scores = [1, 2, 3]
+names = ["ann", "bob"]
+i = 1
+print(_show(scores))
+_at = _index(i, len(scores), "scores", 6)
+scores[_at] = 44
+scores.append(4)
+_loop0 = 0
+while _loop0 < len(scores):
+    score = scores[_loop0]
+    if score > len(names):
+        print(f"{score} of {len(scores)}")
+    _loop0 += 1
+print(f"first name {names[_index(0, len(names), 'names', 13)]}, last score {scores[_index(3, len(scores), 'scores', 13)]}")
@@ -0,0 +1,88 @@
+// Generated by the Ven compiler
+#![allow(dead_code, unused_mut, unused_assignments, unused_variables, unused_parens)]
+use std::io::BufRead;
+
+fn ven_read_line() -> String {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).unwrap_or(0);
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+    // A line holds at most 255 bytes, as on the native targets
+    let mut end = line.len().min(255);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+    line
+}
+
+// Text that is not a whole number from i64::MIN to u64::MAX reads as 0
+fn ven_to_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+        .unwrap_or(0)
+}
+
+fn ven_to_float(text: &str) -> f64 {
+    text.trim().parse().unwrap_or(0.0)
+}
+
+fn ven_to_bool(text: &str) -> bool {
+    text.trim() == "true"
+}
+
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        let span = max - min + 1;
+        let low = value.rem_euclid(span);
+        return if low > max { low - span } else { low };
+    }
+    value
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => (a as u64).wrapping_mul(b as u64) as i128,
+    }
+}
+
+fn ven_index(index: i128, len: usize, name: &str, line: u32) -> usize {
+    if index < 0 || index >= len as i128 {
+        eprintln!("runtime error: index {} is out of bounds for '{}' (length {}) on line {}", index, name, len, line);
+        std::process::exit(1);
+    }
+    index as usize
+}
+
+fn main() {
+    let mut scores: Vec<u8> = Vec::new();
+    let mut names: Vec<String> = Vec::new();
+    let mut i: i64 = 0;
+    let mut score: u8 = 0;
+    scores = vec![1, 2, 3];
+    names = vec!["ann".to_string(), "bob".to_string()];
+    i = 1;
+    println!("{}", format!("[{}]", scores.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(", ")));
+    let ven_at = ven_index(i as i128, scores.len(), "scores", 6);
+    scores[ven_at] = 44;
+    scores.push(4);
+    let mut ven_loop1 = 0;
+    while ven_loop1 < scores.len() {
+        score = scores[ven_loop1].clone();
+        if ((score as i128) > ((names.len() as i64) as i128)) {
+            println!("{} of {}", score, (scores.len() as i64));
+        }
+        ven_loop1 += 1;
+    }
+    println!("first name {}, last score {}", names[ven_index(0 as i128, names.len(), "names", 13)], scores[ven_index(3 as i128, scores.len(), "scores", 13)]);
+}
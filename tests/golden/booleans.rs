@@ -0,0 +1,74 @@
+// Generated by the Ven compiler
+#![allow(dead_code, unused_mut, unused_assignments, unused_variables, unused_parens)]
+use std::io::BufRead;
+
+fn ven_read_line() -> String {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).unwrap_or(0);
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+    // A line holds at most 255 bytes, as on the native targets
+    let mut end = line.len().min(255);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+    line
+}
+
+// Text that is not a whole number from i64::MIN to u64::MAX reads as 0
+fn ven_to_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+        .unwrap_or(0)
+}
+
+fn ven_to_float(text: &str) -> f64 {
+    text.trim().parse().unwrap_or(0.0)
+}
+
+fn ven_to_bool(text: &str) -> bool {
+    text.trim() == "true"
+}
+
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        let span = max - min + 1;
+        let low = value.rem_euclid(span);
+        return if low > max { low - span } else { low };
+    }
+    value
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => (a as u64).wrapping_mul(b as u64) as i128,
+    }
+}
+
+fn main() {
+    let mut age: i64 = 0;
+    let mut adult = false;
+    let mut member = false;
+    age = 20;
+    adult = (age >= 18);
+    member = false;
+    member = ven_to_bool(&ven_read_line());
+    if (adult && !(member)) {
+        println!("adult {}, member={}", age, member);
+    }
+    if !((age < 18) || member) {
+        age = ven_fit(age as i128 + (1) as i128, -9223372036854775808, 9223372036854775807, "age", "i64", 10) as i64;
+    }
+    println!("age {}", age);
+}
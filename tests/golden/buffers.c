@@ -0,0 +1,194 @@
+// Generated by the Ven compiler
+#include <ctype.h>
+#include <errno.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static char ven_line[256];
+
+/* Wide enough for every value of every Ven integer type. */
+typedef __int128 ven_wide;
+
+/* Reads one line without its newline; carriage returns and overflow are dropped. */
+static void ven_read_line(char *buf, size_t size) {
+    size_t len = 0;
+    int c;
+    while ((c = getchar()) != EOF && c != '\n') {
+        if (c != '\r' && len + 1 < size) {
+            buf[len++] = (char)c;
+        }
+    }
+    buf[len] = '\0';
+}
+
+/* Copies as much of src as fits without splitting a character, always terminating dest. */
+static void ven_copy(char *dest, const char *src, size_t size) {
+    size_t len = strlen(src);
+    if (len >= size) {
+        len = size - 1;
+        while (len > 0 && ((unsigned char)src[len] & 0xC0) == 0x80) {
+            len--;
+        }
+    }
+    memmove(dest, src, len);
+    dest[len] = '\0';
+}
+
+static bool ven_blank(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return *text == '\0';
+}
+
+/* Text that is not a whole number from INT64_MIN to UINT64_MAX reads as 0. */
+static ven_wide ven_to_int(const char *text) {
+    char *end;
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    errno = 0;
+    if (*text == '-') {
+        long long value = strtoll(text, &end, 10);
+        return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+    }
+    unsigned long long value = strtoull(text, &end, 10);
+    return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+}
+
+static double ven_to_float(const char *text) {
+    char *end;
+    double value = strtod(text, &end);
+    return end == text || !ven_blank(end) ? 0.0 : value;
+}
+
+/* Only the word true reads as true. */
+static bool ven_to_bool(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return strncmp(text, "true", 4) == 0 && ven_blank(text + 4);
+}
+
+/* The shortest digits that read back as the same double, written out without
+   an exponent, like 0.001 or 1000000, into buf of 400 bytes. */
+static const char *ven_float_text(char *buf, double x) {
+    if (x != x) {
+        return "NaN";
+    }
+    /* Only an infinity is not finite after x - x */
+    if (x - x != 0) {
+        return x < 0 ? "-inf" : "inf";
+    }
+    char shortest[32];
+    for (int precision = 1; precision <= 17; precision++) {
+        snprintf(shortest, sizeof shortest, "%.*e", precision - 1, x);
+        if (strtod(shortest, NULL) == x) {
+            break;
+        }
+    }
+    char digits[24];
+    int len = 0;
+    const char *at = shortest;
+    char *out = buf;
+    if (*at == '-') {
+        *out++ = *at++;
+    }
+    for (; *at != 'e'; at++) {
+        if (*at != '.') {
+            digits[len++] = *at;
+        }
+    }
+    int exponent = atoi(at + 1);
+    if (exponent < 0) {
+        *out++ = '0';
+        *out++ = '.';
+        for (int i = -1; i > exponent; i--) {
+            *out++ = '0';
+        }
+        memcpy(out, digits, len);
+        out += len;
+    } else if (exponent + 1 >= len) {
+        memcpy(out, digits, len);
+        out += len;
+        for (int i = len; i <= exponent; i++) {
+            *out++ = '0';
+        }
+    } else {
+        memcpy(out, digits, exponent + 1);
+        out += exponent + 1;
+        *out++ = '.';
+        memcpy(out, digits + exponent + 1, len - exponent - 1);
+        out += len - exponent - 1;
+    }
+    *out = '\0';
+    return buf;
+}
+
+static void ven_print_float(FILE *out, double x) {
+    char buf[400];
+    fputs(ven_float_text(buf, x), out);
+}
+
+static ven_wide ven_fit(ven_wide value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value < min || value > max) {
+        ven_wide span = max - min + 1;
+        value %= span;
+        if (value < min) {
+            value += span;
+        } else if (value > max) {
+            value -= span;
+        }
+    }
+    return value;
+}
+
+/* Floats never wrap: they are truncated and clamp at the limits. */
+static ven_wide ven_fit_float(double value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value != value) {
+        return 0;
+    }
+    /* Truncate toward zero; doubles past 2^63 are whole already */
+    if (value > -9223372036854775808.0 && value < 9223372036854775808.0) {
+        value = (double)(long long)value;
+    }
+    return value < (double)min ? min : value >= (double)max + 1.0 ? max : (ven_wide)value;
+}
+
+/* A u64 product, which can exceed ven_wide. */
+static uint64_t ven_mul_u64(uint64_t a, uint64_t b, const char *name, int line) {
+    (void)name;
+    (void)line;
+    return a * b;
+}
+
+char code[4] = "";
+char name[256] = "";
+char head[4] = "";
+
+int main(void) {
+    ven_copy(code, "ab", sizeof code);
+    ven_copy(name, "longer than three", sizeof name);
+    ven_read_line(name, sizeof name);
+    ven_copy(head, name, sizeof head);
+    fputs("BUFFER A [", stdout);
+    fputs(code, stdout);
+    fputs("] [", stdout);
+    fputs(head, stdout);
+    fputs("]\n", stdout);
+    ven_read_line(ven_line, sizeof ven_line);
+    ven_copy(code, ven_line, sizeof code);
+    fputs("[", stdout);
+    fputs(code, stdout);
+    fputs("]\n", stdout);
+    return 0;
+}
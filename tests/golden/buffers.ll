@@ -0,0 +1,327 @@
+; Generated by the Ven compiler
+declare i32 @dprintf(i32, i8*, ...)
+declare i32 @snprintf(i8*, i64, i8*, ...)
+declare i32 @strcmp(i8*, i8*)
+declare i32 @strncmp(i8*, i8*, i64)
+declare i64 @strlen(i8*)
+declare i64 @strtoll(i8*, i8**, i32)
+declare i64 @strtoull(i8*, i8**, i32)
+declare i32* @__errno_location()
+declare void @exit(i32)
+declare double @llvm.trunc.f64(double)
+declare {i64, i1} @llvm.umul.with.overflow.i64(i64, i64)
+declare double @strtod(i8*, i8**)
+declare i64 @read(i32, i8*, i64)
+declare i8* @memmove(i8*, i8*, i64)
+declare i8* @memset(i8*, i32, i64)
+declare i8* @strchr(i8*, i32)
+
+@ven.line = internal global [256 x i8] zeroinitializer
+@ven.fmt.s = private unnamed_addr constant [3 x i8] c"%s\00"
+@ven.fmt.lld = private unnamed_addr constant [5 x i8] c"%lld\00"
+@ven.fmt.llu = private unnamed_addr constant [5 x i8] c"%llu\00"
+@ven.fmt.e = private unnamed_addr constant [5 x i8] c"%.*e\00"
+@ven.fmt.f = private unnamed_addr constant [5 x i8] c"%.*f\00"
+@ven.nan = private unnamed_addr constant [4 x i8] c"NaN\00"
+@ven.inf = private unnamed_addr constant [4 x i8] c"inf\00"
+@ven.minus.inf = private unnamed_addr constant [5 x i8] c"-inf\00"
+@ven.true = private unnamed_addr constant [5 x i8] c"true\00"
+@ven.false = private unnamed_addr constant [6 x i8] c"false\00"
+
+; Reads one line without its newline; carriage returns and overflow are dropped
+define internal void @ven_read_line(i8* %buf, i64 %size) {
+entry:
+  %c = alloca i8
+  %max = sub i64 %size, 1
+  br label %loop
+loop:
+  %len = phi i64 [0, %entry], [%len, %skip], [%next, %keep]
+  %n = call i64 @read(i32 0, i8* %c, i64 1)
+  %eof = icmp slt i64 %n, 1
+  br i1 %eof, label %done, label %got
+got:
+  %byte = load i8, i8* %c
+  %newline = icmp eq i8 %byte, 10
+  br i1 %newline, label %done, label %check
+check:
+  %cr = icmp eq i8 %byte, 13
+  %full = icmp uge i64 %len, %max
+  %drop = or i1 %cr, %full
+  br i1 %drop, label %skip, label %keep
+skip:
+  br label %loop
+keep:
+  %slot = getelementptr inbounds i8, i8* %buf, i64 %len
+  store i8 %byte, i8* %slot
+  %next = add i64 %len, 1
+  br label %loop
+done:
+  %end = getelementptr inbounds i8, i8* %buf, i64 %len
+  store i8 0, i8* %end
+  ret void
+}
+
+; Copies as much of src as fits without splitting a character, always terminating dest
+define internal void @ven_copy(i8* %dest, i8* %src, i64 %size) {
+entry:
+  %len = call i64 @strlen(i8* %src)
+  %max = sub i64 %size, 1
+  %long = icmp ugt i64 %len, %max
+  br i1 %long, label %cut, label %copy
+cut:
+  %cut.len = phi i64 [%max, %entry], [%back, %inside]
+  %at = getelementptr inbounds i8, i8* %src, i64 %cut.len
+  %byte = load i8, i8* %at
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %nonzero = icmp ugt i64 %cut.len, 0
+  %split = and i1 %continuation, %nonzero
+  br i1 %split, label %inside, label %copy
+inside:
+  %back = sub i64 %cut.len, 1
+  br label %cut
+copy:
+  %n = phi i64 [%len, %entry], [%cut.len, %cut]
+  call i8* @memmove(i8* %dest, i8* %src, i64 %n)
+  %end = getelementptr inbounds i8, i8* %dest, i64 %n
+  store i8 0, i8* %end
+  ret void
+}
+
+define internal i8* @ven_skip_space(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %space]
+  %byte = load i8, i8* %p
+  %sp = icmp eq i8 %byte, 32
+  %low = icmp uge i8 %byte, 9
+  %high = icmp ule i8 %byte, 13
+  %ctl = and i1 %low, %high
+  %ws = or i1 %sp, %ctl
+  br i1 %ws, label %space, label %done
+space:
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %p
+}
+
+define internal i1 @ven_blank(i8* %text) {
+  %p = call i8* @ven_skip_space(i8* %text)
+  %byte = load i8, i8* %p
+  %blank = icmp eq i8 %byte, 0
+  ret i1 %blank
+}
+
+; Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+define internal i128 @ven_to_int(i8* %text) {
+entry:
+  %end = alloca i8*
+  %start = call i8* @ven_skip_space(i8* %text)
+  %errno = call i32* @__errno_location()
+  store i32 0, i32* %errno
+  %first = load i8, i8* %start
+  %minus = icmp eq i8 %first, 45
+  br i1 %minus, label %negative, label %positive
+negative:
+  %signed = call i64 @strtoll(i8* %start, i8** %end, i32 10)
+  %wide.signed = sext i64 %signed to i128
+  br label %check
+positive:
+  %unsigned = call i64 @strtoull(i8* %start, i8** %end, i32 10)
+  %wide.unsigned = zext i64 %unsigned to i128
+  br label %check
+check:
+  %value = phi i128 [%wide.signed, %negative], [%wide.unsigned, %positive]
+  %stop = load i8*, i8** %end
+  %moved = icmp ne i8* %stop, %start
+  %rest = call i1 @ven_blank(i8* %stop)
+  %err = load i32, i32* %errno
+  %in.range = icmp ne i32 %err, 34
+  %number = and i1 %moved, %rest
+  %ok = and i1 %number, %in.range
+  %result = select i1 %ok, i128 %value, i128 0
+  ret i128 %result
+}
+
+define internal double @ven_to_float(i8* %text) {
+  %end = alloca i8*
+  %value = call double @strtod(i8* %text, i8** %end)
+  %stop = load i8*, i8** %end
+  %moved = icmp ne i8* %stop, %text
+  %rest = call i1 @ven_blank(i8* %stop)
+  %ok = and i1 %moved, %rest
+  %result = select i1 %ok, double %value, double 0.0
+  ret double %result
+}
+
+; Only the word true reads as true
+define internal i1 @ven_to_bool(i8* %text) {
+entry:
+  %p = call i8* @ven_skip_space(i8* %text)
+  %cmp = call i32 @strncmp(i8* %p, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.true, i64 0, i64 0), i64 4)
+  %word = icmp eq i32 %cmp, 0
+  br i1 %word, label %rest, label %no
+rest:
+  %after = getelementptr inbounds i8, i8* %p, i64 4
+  %blank = call i1 @ven_blank(i8* %after)
+  ret i1 %blank
+no:
+  ret i1 false
+}
+
+; The shortest text that reads back as %x, without an exponent, in %buf of 400 bytes
+define internal i8* @ven_float_text(i8* %buf, double %x) {
+entry:
+  %short = alloca [32 x i8]
+  %text = getelementptr inbounds [32 x i8], [32 x i8]* %short, i64 0, i64 0
+  %nan = fcmp uno double %x, %x
+  br i1 %nan, label %not.a.number, label %check
+not.a.number:
+  ret i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.nan, i64 0, i64 0)
+check:
+  ; Only an infinity is not finite after x - x
+  %zero = fsub double %x, %x
+  %finite = fcmp oeq double %zero, 0.0
+  br i1 %finite, label %loop, label %infinite
+infinite:
+  %negative = fcmp olt double %x, 0.0
+  %word = select i1 %negative, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.minus.inf, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.inf, i64 0, i64 0)
+  ret i8* %word
+loop:
+  %precision = phi i32 [1, %check], [%next, %retry]
+  %decimals = sub i32 %precision, 1
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %text, i64 32, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.e, i64 0, i64 0), i32 %decimals, double %x)
+  %back = call double @strtod(i8* %text, i8** null)
+  %same = fcmp oeq double %back, %x
+  %last = icmp sge i32 %precision, 17
+  %stop = or i1 %same, %last
+  br i1 %stop, label %found, label %retry
+retry:
+  %next = add i32 %precision, 1
+  br label %loop
+found:
+  %e = call i8* @strchr(i8* %text, i32 101)
+  %after.e = getelementptr inbounds i8, i8* %e, i64 1
+  %exponent.wide = call i64 @strtoll(i8* %after.e, i8** null, i32 10)
+  %exponent = trunc i64 %exponent.wide to i32
+  %fraction = icmp slt i32 %exponent, %decimals
+  br i1 %fraction, label %fixed, label %whole
+fixed:
+  ; Rounding at the last shortest digit gives the same digits written out
+  %places = sub i32 %decimals, %exponent
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %buf, i64 400, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.f, i64 0, i64 0), i32 %places, double %x)
+  ret i8* %buf
+whole:
+  ; The digits without their point, then zeros up to the point
+  store i8 0, i8* %e
+  %first = load i8, i8* %text
+  %minus = icmp eq i8 %first, 45
+  %lead = select i1 %minus, i64 2, i64 1
+  %point = getelementptr inbounds i8, i8* %text, i64 %lead
+  %rest = call i64 @strlen(i8* %point)
+  %after.point = getelementptr inbounds i8, i8* %point, i64 1
+  call i8* @memmove(i8* %point, i8* %after.point, i64 %rest)
+  %len = call i64 @strlen(i8* %text)
+  call i8* @memmove(i8* %buf, i8* %text, i64 %len)
+  %end = getelementptr inbounds i8, i8* %buf, i64 %len
+  %zeros.narrow = sub i32 %exponent, %decimals
+  %zeros = sext i32 %zeros.narrow to i64
+  call i8* @memset(i8* %end, i32 48, i64 %zeros)
+  %stop.at = getelementptr inbounds i8, i8* %end, i64 %zeros
+  store i8 0, i8* %stop.at
+  ret i8* %buf
+}
+
+define internal void @ven_print_float(i32 %fd, double %x) {
+  %buf = alloca [400 x i8]
+  %start = getelementptr inbounds [400 x i8], [400 x i8]* %buf, i64 0, i64 0
+  %text = call i8* @ven_float_text(i8* %start, double %x)
+  call i32 (i32, i8*, ...) @dprintf(i32 %fd, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* %text)
+  ret void
+}
+
+define internal i128 @ven_fit(i128 %value, i128 %min, i128 %max, i8* %name, i8* %type, i64 %line) {
+entry:
+  %below = icmp slt i128 %value, %min
+  %above = icmp sgt i128 %value, %max
+  %out = or i1 %below, %above
+  br i1 %out, label %wrap, label %ok
+ok:
+  ret i128 %value
+wrap:
+  %mask = sub i128 %max, %min
+  %span = add i128 %mask, 1
+  %low = and i128 %value, %mask
+  %high = icmp sgt i128 %low, %max
+  %back = sub i128 %low, %span
+  %result = select i1 %high, i128 %back, i128 %low
+  ret i128 %result
+}
+
+; A u64 product, which can exceed i128
+define internal i64 @ven_mul_u64(i64 %a, i64 %b, i8* %name, i64 %line) {
+  %pair = call {i64, i1} @llvm.umul.with.overflow.i64(i64 %a, i64 %b)
+  %product = extractvalue {i64, i1} %pair, 0
+  ret i64 %product
+}
+
+define internal i128 @ven_fit_float(double %value, i128 %min, i128 %max, double %low, double %high, i8* %name, i8* %type, i64 %line) {
+entry:
+  %nan = fcmp uno double %value, %value
+  br i1 %nan, label %zero, label %number
+zero:
+  ret i128 0
+number:
+  %whole = call double @llvm.trunc.f64(double %value)
+  %below = fcmp olt double %whole, %low
+  %above = fcmp oge double %whole, %high
+  br i1 %below, label %clamp.min, label %check
+clamp.min:
+  ret i128 %min
+check:
+  br i1 %above, label %clamp.max, label %convert
+clamp.max:
+  ret i128 %max
+convert:
+  %negative = fcmp olt double %whole, 0.0
+  br i1 %negative, label %signed, label %unsigned
+signed:
+  %s = fptosi double %whole to i64
+  %wide.s = sext i64 %s to i128
+  ret i128 %wide.s
+unsigned:
+  %u = fptoui double %whole to i64
+  %wide.u = zext i64 %u to i128
+  ret i128 %wide.u
+}
+
+@v.code = internal global [4 x i8] zeroinitializer
+@v.name = internal global [256 x i8] zeroinitializer
+@v.head = internal global [4 x i8] zeroinitializer
+@.str.0 = private unnamed_addr constant [3 x i8] c"ab\00"
+@.str.1 = private unnamed_addr constant [18 x i8] c"longer than three\00"
+@.str.2 = private unnamed_addr constant [11 x i8] c"BUFFER A [\00"
+@.str.3 = private unnamed_addr constant [4 x i8] c"] [\00"
+@.str.4 = private unnamed_addr constant [3 x i8] c"]\0A\00"
+@.str.5 = private unnamed_addr constant [2 x i8] c"[\00"
+
+define i32 @main() {
+entry:
+  call void @ven_copy(i8* getelementptr inbounds ([4 x i8], [4 x i8]* @v.code, i64 0, i64 0), i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str.0, i64 0, i64 0), i64 4)
+  call void @ven_copy(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.name, i64 0, i64 0), i8* getelementptr inbounds ([18 x i8], [18 x i8]* @.str.1, i64 0, i64 0), i64 256)
+  call void @ven_read_line(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.name, i64 0, i64 0), i64 256)
+  call void @ven_copy(i8* getelementptr inbounds ([4 x i8], [4 x i8]* @v.head, i64 0, i64 0), i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.name, i64 0, i64 0), i64 4)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([11 x i8], [11 x i8]* @.str.2, i64 0, i64 0))
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @v.code, i64 0, i64 0))
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.3, i64 0, i64 0))
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @v.head, i64 0, i64 0))
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str.4, i64 0, i64 0))
+  call void @ven_read_line(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.line, i64 0, i64 0), i64 256)
+  call void @ven_copy(i8* getelementptr inbounds ([4 x i8], [4 x i8]* @v.code, i64 0, i64 0), i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.line, i64 0, i64 0), i64 4)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.5, i64 0, i64 0))
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @v.code, i64 0, i64 0))
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str.4, i64 0, i64 0))
+  ret i32 0
+}
@@ -0,0 +1,360 @@
+// Generated by the Ven compiler
+#include <ctype.h>
+#include <errno.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static char ven_line[256];
+
+/* Wide enough for every value of every Ven integer type. */
+typedef __int128 ven_wide;
+
+/* Reads one line without its newline; carriage returns and overflow are dropped. */
+static void ven_read_line(char *buf, size_t size) {
+    size_t len = 0;
+    int c;
+    while ((c = getchar()) != EOF && c != '\n') {
+        if (c != '\r' && len + 1 < size) {
+            buf[len++] = (char)c;
+        }
+    }
+    buf[len] = '\0';
+}
+
+/* Copies as much of src as fits without splitting a character, always terminating dest. */
+static void ven_copy(char *dest, const char *src, size_t size) {
+    size_t len = strlen(src);
+    if (len >= size) {
+        len = size - 1;
+        while (len > 0 && ((unsigned char)src[len] & 0xC0) == 0x80) {
+            len--;
+        }
+    }
+    memmove(dest, src, len);
+    dest[len] = '\0';
+}
+
+static bool ven_blank(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return *text == '\0';
+}
+
+/* Text that is not a whole number from INT64_MIN to UINT64_MAX reads as 0. */
+static ven_wide ven_to_int(const char *text) {
+    char *end;
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    errno = 0;
+    if (*text == '-') {
+        long long value = strtoll(text, &end, 10);
+        return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+    }
+    unsigned long long value = strtoull(text, &end, 10);
+    return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+}
+
+static double ven_to_float(const char *text) {
+    char *end;
+    double value = strtod(text, &end);
+    return end == text || !ven_blank(end) ? 0.0 : value;
+}
+
+/* Only the word true reads as true. */
+static bool ven_to_bool(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return strncmp(text, "true", 4) == 0 && ven_blank(text + 4);
+}
+
+/* The shortest digits that read back as the same double, written out without
+   an exponent, like 0.001 or 1000000, into buf of 400 bytes. */
+static const char *ven_float_text(char *buf, double x) {
+    if (x != x) {
+        return "NaN";
+    }
+    /* Only an infinity is not finite after x - x */
+    if (x - x != 0) {
+        return x < 0 ? "-inf" : "inf";
+    }
+    char shortest[32];
+    for (int precision = 1; precision <= 17; precision++) {
+        snprintf(shortest, sizeof shortest, "%.*e", precision - 1, x);
+        if (strtod(shortest, NULL) == x) {
+            break;
+        }
+    }
+    char digits[24];
+    int len = 0;
+    const char *at = shortest;
+    char *out = buf;
+    if (*at == '-') {
+        *out++ = *at++;
+    }
+    for (; *at != 'e'; at++) {
+        if (*at != '.') {
+            digits[len++] = *at;
+        }
+    }
+    int exponent = atoi(at + 1);
+    if (exponent < 0) {
+        *out++ = '0';
+        *out++ = '.';
+        for (int i = -1; i > exponent; i--) {
+            *out++ = '0';
+        }
+        memcpy(out, digits, len);
+        out += len;
+    } else if (exponent + 1 >= len) {
+        memcpy(out, digits, len);
+        out += len;
+        for (int i = len; i <= exponent; i++) {
+            *out++ = '0';
+        }
+    } else {
+        memcpy(out, digits, exponent + 1);
+        out += exponent + 1;
+        *out++ = '.';
+        memcpy(out, digits + exponent + 1, len - exponent - 1);
+        out += len - exponent - 1;
+    }
+    *out = '\0';
+    return buf;
+}
+
+static void ven_print_float(FILE *out, double x) {
+    char buf[400];
+    fputs(ven_float_text(buf, x), out);
+}
+
+static ven_wide ven_fit(ven_wide value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value < min || value > max) {
+        ven_wide span = max - min + 1;
+        value %= span;
+        if (value < min) {
+            value += span;
+        } else if (value > max) {
+            value -= span;
+        }
+    }
+    return value;
+}
+
+/* Floats never wrap: they are truncated and clamp at the limits. */
+static ven_wide ven_fit_float(double value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value != value) {
+        return 0;
+    }
+    /* Truncate toward zero; doubles past 2^63 are whole already */
+    if (value > -9223372036854775808.0 && value < 9223372036854775808.0) {
+        value = (double)(long long)value;
+    }
+    return value < (double)min ? min : value >= (double)max + 1.0 ? max : (ven_wide)value;
+}
+
+/* A u64 product, which can exceed ven_wide. */
+static uint64_t ven_mul_u64(uint64_t a, uint64_t b, const char *name, int line) {
+    (void)name;
+    (void)line;
+    return a * b;
+}
+
+/* Makes room for one more element; running out of memory ends the program. */
+static void *ven_grow(void *items, size_t *cap, size_t len, size_t size) {
+    if (len < *cap) {
+        return items;
+    }
+    *cap = *cap ? *cap * 2 : 8;
+    items = realloc(items, *cap * size);
+    if (items == NULL) {
+        fflush(stdout);
+        fprintf(stderr, "runtime error: out of memory\n");
+        exit(1);
+    }
+    return items;
+}
+
+/* The position of index in the array name of len elements. */
+static size_t ven_index(ven_wide index, size_t len, const char *name, int line) {
+    if (index < 0 || index >= (ven_wide)len) {
+        fflush(stdout);
+        if (index < 0) {
+            fprintf(stderr, "runtime error: index %lld is out of bounds for '%s' (length %zu) on line %d\n", (long long)index, name, len, line);
+        } else {
+            fprintf(stderr, "runtime error: index %llu is out of bounds for '%s' (length %zu) on line %d\n", (unsigned long long)index, name, len, line);
+        }
+        exit(1);
+    }
+    return (size_t)index;
+}
+
+static void ven_convert_failed(const char *text, const char *to, int line) {
+    fflush(stdout);
+    fprintf(stderr, "runtime error: cannot convert \"%s\" to %s on line %d\n", text, to, line);
+    exit(1);
+}
+
+static bool ven_space(char c) {
+    return c == ' ' || c == '\t' || c == '\n' || c == '\r';
+}
+
+/* A sign and decimal digits within int64_t, with spaces around them. */
+static int64_t ven_convert_int(const char *text, int line) {
+    const char *start = text;
+    while (ven_space(*start)) {
+        start++;
+    }
+    const char *at = start + (*start == '+' || *start == '-');
+    const char *digits = at;
+    while (isdigit((unsigned char)*at)) {
+        at++;
+    }
+    bool whole = at > digits;
+    while (ven_space(*at)) {
+        at++;
+    }
+    errno = 0;
+    long long value = whole && *at == '\0' ? strtoll(start, NULL, 10) : 0;
+    if (!whole || *at != '\0' || errno == ERANGE) {
+        ven_convert_failed(text, "i", line);
+    }
+    return value;
+}
+
+/* A finite decimal number like -1.5, .5, 2. or 6.02e23, with spaces around it. */
+static double ven_convert_float(const char *text, int line) {
+    const char *start = text;
+    while (ven_space(*start)) {
+        start++;
+    }
+    const char *at = start + (*start == '+' || *start == '-');
+    int digits = 0;
+    for (; isdigit((unsigned char)*at); at++) {
+        digits++;
+    }
+    if (*at == '.') {
+        for (at++; isdigit((unsigned char)*at); at++) {
+            digits++;
+        }
+    }
+    if (digits > 0 && (*at == 'e' || *at == 'E')) {
+        at += 1 + (at[1] == '+' || at[1] == '-');
+        digits = isdigit((unsigned char)*at) ? digits : 0;
+        while (isdigit((unsigned char)*at)) {
+            at++;
+        }
+    }
+    while (ven_space(*at)) {
+        at++;
+    }
+    double value = digits > 0 && *at == '\0' ? strtod(start, NULL) : 0.0;
+    if (digits == 0 || *at != '\0' || value - value != 0) {
+        ven_convert_failed(text, "f", line);
+    }
+    return value;
+}
+
+/* Truncated toward zero, with NaN as 0 and clamped at the int64_t limits. */
+static int64_t ven_float_to_int(double x) {
+    if (x != x) {
+        return 0;
+    }
+    return x <= -9223372036854775808.0 ? INT64_MIN : x >= 9223372036854775808.0 ? INT64_MAX : (int64_t)x;
+}
+
+static const char *ven_int_text(char *buf, long long value) {
+    snprintf(buf, 24, "%lld", value);
+    return buf;
+}
+
+static const char *ven_uint_text(char *buf, unsigned long long value) {
+    snprintf(buf, 24, "%llu", value);
+    return buf;
+}
+
+/* precision digits after the point, rounded half to even, into buf of 400 bytes. */
+static const char *ven_fixed_text(char *buf, double x, int precision) {
+    if (x != x || x - x != 0) {
+        return ven_float_text(buf, x);
+    }
+    snprintf(buf, 400, "%.*f", precision, x);
+    return buf;
+}
+
+/* Prints text padded with spaces to width characters, before it when right. */
+static void ven_pad(FILE *out, const char *text, size_t width, bool right) {
+    size_t chars = 0;
+    for (const char *at = text; *at; at++) {
+        chars += ((unsigned char)*at & 0xC0) != 0x80;
+    }
+    if (!right) {
+        fputs(text, out);
+    }
+    for (; chars < width; chars++) {
+        fputc(' ', out);
+    }
+    if (right) {
+        fputs(text, out);
+    }
+}
+
+char line[256] = "";
+int64_t n = 0;
+double ratio = 0.0;
+char label[256] = "";
+uint8_t small = 0;
+double price = 0.0;
+char item[256] = "";
+struct { double *items; size_t len, cap; } prices = {0};
+
+int main(void) {
+    ven_copy(line, " 42 ", sizeof line);
+    n = ven_convert_int(line, 3);
+    ratio = ven_convert_float("2.5", 4);
+    ven_copy(label, ven_int_text((char[24]){0}, n), sizeof label);
+    small = (uint8_t)ven_fit(ven_convert_int("200", 6), 0, 255, "small", "u8", 6);
+    small = (uint8_t)ven_fit((ven_wide)small + (uint8_t)ven_fit(ven_float_to_int(ratio), 0, 255, "small", "u8", 7), 0, 255, "small", "u8", 7);
+    if ((ven_float_to_int(ratio) == 2) && (strcmp(ven_int_text((char[24]){0}, n), "42") == 0)) {
+        fputs(ven_float_text((char[400]){0}, ratio), stdout);
+        fputs(" rounds down to ", stdout);
+        fprintf(stdout, "%lld", (long long)ven_float_to_int(ratio));
+        fputs("\n", stdout);
+    }
+    price = 2.675;
+    ven_copy(item, "tea", sizeof item);
+    prices.len = 0;
+    prices.items = ven_grow(prices.items, &prices.cap, prices.len, sizeof *prices.items);
+    prices.items[prices.len] = 1.5;
+    prices.len++;
+    prices.items = ven_grow(prices.items, &prices.cap, prices.len, sizeof *prices.items);
+    prices.items[prices.len] = 10.25;
+    prices.len++;
+    fputs("[", stdout);
+    ven_pad(stdout, ven_fixed_text((char[400]){0}, price, 2), 0, true);
+    fputs("] [", stdout);
+    ven_pad(stdout, ven_int_text((char[24]){0}, n), 5, true);
+    fputs("] [", stdout);
+    ven_pad(stdout, item, 6, false);
+    fputs("] [", stdout);
+    ven_pad(stdout, ven_fixed_text((char[400]){0}, price, 1), 8, true);
+    fputs("] [", stdout);
+    ven_pad(stdout, ven_fixed_text((char[400]){0}, prices.items[ven_index(1, prices.len, "prices", 14)], 1), 0, true);
+    fputs("] [", stdout);
+    ven_pad(stdout, ven_int_text((char[24]){0}, small), 4, false);
+    fputs("]\n", stdout);
+    fputs(label, stdout);
+    fputs("\n", stdout);
+    return 0;
+}
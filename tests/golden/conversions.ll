@@ -0,0 +1,687 @@
+; Generated by the Ven compiler
+declare i32 @dprintf(i32, i8*, ...)
+declare i32 @snprintf(i8*, i64, i8*, ...)
+declare i32 @strcmp(i8*, i8*)
+declare i32 @strncmp(i8*, i8*, i64)
+declare i64 @strlen(i8*)
+declare i64 @strtoll(i8*, i8**, i32)
+declare i64 @strtoull(i8*, i8**, i32)
+declare i32* @__errno_location()
+declare void @exit(i32)
+declare double @llvm.trunc.f64(double)
+declare {i64, i1} @llvm.umul.with.overflow.i64(i64, i64)
+declare double @strtod(i8*, i8**)
+declare i64 @read(i32, i8*, i64)
+declare i8* @memmove(i8*, i8*, i64)
+declare i8* @memset(i8*, i32, i64)
+declare i8* @strchr(i8*, i32)
+
+@ven.line = internal global [256 x i8] zeroinitializer
+@ven.fmt.s = private unnamed_addr constant [3 x i8] c"%s\00"
+@ven.fmt.lld = private unnamed_addr constant [5 x i8] c"%lld\00"
+@ven.fmt.llu = private unnamed_addr constant [5 x i8] c"%llu\00"
+@ven.fmt.e = private unnamed_addr constant [5 x i8] c"%.*e\00"
+@ven.fmt.f = private unnamed_addr constant [5 x i8] c"%.*f\00"
+@ven.nan = private unnamed_addr constant [4 x i8] c"NaN\00"
+@ven.inf = private unnamed_addr constant [4 x i8] c"inf\00"
+@ven.minus.inf = private unnamed_addr constant [5 x i8] c"-inf\00"
+@ven.true = private unnamed_addr constant [5 x i8] c"true\00"
+@ven.false = private unnamed_addr constant [6 x i8] c"false\00"
+
+; Reads one line without its newline; carriage returns and overflow are dropped
+define internal void @ven_read_line(i8* %buf, i64 %size) {
+entry:
+  %c = alloca i8
+  %max = sub i64 %size, 1
+  br label %loop
+loop:
+  %len = phi i64 [0, %entry], [%len, %skip], [%next, %keep]
+  %n = call i64 @read(i32 0, i8* %c, i64 1)
+  %eof = icmp slt i64 %n, 1
+  br i1 %eof, label %done, label %got
+got:
+  %byte = load i8, i8* %c
+  %newline = icmp eq i8 %byte, 10
+  br i1 %newline, label %done, label %check
+check:
+  %cr = icmp eq i8 %byte, 13
+  %full = icmp uge i64 %len, %max
+  %drop = or i1 %cr, %full
+  br i1 %drop, label %skip, label %keep
+skip:
+  br label %loop
+keep:
+  %slot = getelementptr inbounds i8, i8* %buf, i64 %len
+  store i8 %byte, i8* %slot
+  %next = add i64 %len, 1
+  br label %loop
+done:
+  %end = getelementptr inbounds i8, i8* %buf, i64 %len
+  store i8 0, i8* %end
+  ret void
+}
+
+; Copies as much of src as fits without splitting a character, always terminating dest
+define internal void @ven_copy(i8* %dest, i8* %src, i64 %size) {
+entry:
+  %len = call i64 @strlen(i8* %src)
+  %max = sub i64 %size, 1
+  %long = icmp ugt i64 %len, %max
+  br i1 %long, label %cut, label %copy
+cut:
+  %cut.len = phi i64 [%max, %entry], [%back, %inside]
+  %at = getelementptr inbounds i8, i8* %src, i64 %cut.len
+  %byte = load i8, i8* %at
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %nonzero = icmp ugt i64 %cut.len, 0
+  %split = and i1 %continuation, %nonzero
+  br i1 %split, label %inside, label %copy
+inside:
+  %back = sub i64 %cut.len, 1
+  br label %cut
+copy:
+  %n = phi i64 [%len, %entry], [%cut.len, %cut]
+  call i8* @memmove(i8* %dest, i8* %src, i64 %n)
+  %end = getelementptr inbounds i8, i8* %dest, i64 %n
+  store i8 0, i8* %end
+  ret void
+}
+
+define internal i8* @ven_skip_space(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %space]
+  %byte = load i8, i8* %p
+  %sp = icmp eq i8 %byte, 32
+  %low = icmp uge i8 %byte, 9
+  %high = icmp ule i8 %byte, 13
+  %ctl = and i1 %low, %high
+  %ws = or i1 %sp, %ctl
+  br i1 %ws, label %space, label %done
+space:
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %p
+}
+
+define internal i1 @ven_blank(i8* %text) {
+  %p = call i8* @ven_skip_space(i8* %text)
+  %byte = load i8, i8* %p
+  %blank = icmp eq i8 %byte, 0
+  ret i1 %blank
+}
+
+; Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+define internal i128 @ven_to_int(i8* %text) {
+entry:
+  %end = alloca i8*
+  %start = call i8* @ven_skip_space(i8* %text)
+  %errno = call i32* @__errno_location()
+  store i32 0, i32* %errno
+  %first = load i8, i8* %start
+  %minus = icmp eq i8 %first, 45
+  br i1 %minus, label %negative, label %positive
+negative:
+  %signed = call i64 @strtoll(i8* %start, i8** %end, i32 10)
+  %wide.signed = sext i64 %signed to i128
+  br label %check
+positive:
+  %unsigned = call i64 @strtoull(i8* %start, i8** %end, i32 10)
+  %wide.unsigned = zext i64 %unsigned to i128
+  br label %check
+check:
+  %value = phi i128 [%wide.signed, %negative], [%wide.unsigned, %positive]
+  %stop = load i8*, i8** %end
+  %moved = icmp ne i8* %stop, %start
+  %rest = call i1 @ven_blank(i8* %stop)
+  %err = load i32, i32* %errno
+  %in.range = icmp ne i32 %err, 34
+  %number = and i1 %moved, %rest
+  %ok = and i1 %number, %in.range
+  %result = select i1 %ok, i128 %value, i128 0
+  ret i128 %result
+}
+
+define internal double @ven_to_float(i8* %text) {
+  %end = alloca i8*
+  %value = call double @strtod(i8* %text, i8** %end)
+  %stop = load i8*, i8** %end
+  %moved = icmp ne i8* %stop, %text
+  %rest = call i1 @ven_blank(i8* %stop)
+  %ok = and i1 %moved, %rest
+  %result = select i1 %ok, double %value, double 0.0
+  ret double %result
+}
+
+; Only the word true reads as true
+define internal i1 @ven_to_bool(i8* %text) {
+entry:
+  %p = call i8* @ven_skip_space(i8* %text)
+  %cmp = call i32 @strncmp(i8* %p, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.true, i64 0, i64 0), i64 4)
+  %word = icmp eq i32 %cmp, 0
+  br i1 %word, label %rest, label %no
+rest:
+  %after = getelementptr inbounds i8, i8* %p, i64 4
+  %blank = call i1 @ven_blank(i8* %after)
+  ret i1 %blank
+no:
+  ret i1 false
+}
+
+; The shortest text that reads back as %x, without an exponent, in %buf of 400 bytes
+define internal i8* @ven_float_text(i8* %buf, double %x) {
+entry:
+  %short = alloca [32 x i8]
+  %text = getelementptr inbounds [32 x i8], [32 x i8]* %short, i64 0, i64 0
+  %nan = fcmp uno double %x, %x
+  br i1 %nan, label %not.a.number, label %check
+not.a.number:
+  ret i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.nan, i64 0, i64 0)
+check:
+  ; Only an infinity is not finite after x - x
+  %zero = fsub double %x, %x
+  %finite = fcmp oeq double %zero, 0.0
+  br i1 %finite, label %loop, label %infinite
+infinite:
+  %negative = fcmp olt double %x, 0.0
+  %word = select i1 %negative, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.minus.inf, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.inf, i64 0, i64 0)
+  ret i8* %word
+loop:
+  %precision = phi i32 [1, %check], [%next, %retry]
+  %decimals = sub i32 %precision, 1
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %text, i64 32, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.e, i64 0, i64 0), i32 %decimals, double %x)
+  %back = call double @strtod(i8* %text, i8** null)
+  %same = fcmp oeq double %back, %x
+  %last = icmp sge i32 %precision, 17
+  %stop = or i1 %same, %last
+  br i1 %stop, label %found, label %retry
+retry:
+  %next = add i32 %precision, 1
+  br label %loop
+found:
+  %e = call i8* @strchr(i8* %text, i32 101)
+  %after.e = getelementptr inbounds i8, i8* %e, i64 1
+  %exponent.wide = call i64 @strtoll(i8* %after.e, i8** null, i32 10)
+  %exponent = trunc i64 %exponent.wide to i32
+  %fraction = icmp slt i32 %exponent, %decimals
+  br i1 %fraction, label %fixed, label %whole
+fixed:
+  ; Rounding at the last shortest digit gives the same digits written out
+  %places = sub i32 %decimals, %exponent
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %buf, i64 400, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.f, i64 0, i64 0), i32 %places, double %x)
+  ret i8* %buf
+whole:
+  ; The digits without their point, then zeros up to the point
+  store i8 0, i8* %e
+  %first = load i8, i8* %text
+  %minus = icmp eq i8 %first, 45
+  %lead = select i1 %minus, i64 2, i64 1
+  %point = getelementptr inbounds i8, i8* %text, i64 %lead
+  %rest = call i64 @strlen(i8* %point)
+  %after.point = getelementptr inbounds i8, i8* %point, i64 1
+  call i8* @memmove(i8* %point, i8* %after.point, i64 %rest)
+  %len = call i64 @strlen(i8* %text)
+  call i8* @memmove(i8* %buf, i8* %text, i64 %len)
+  %end = getelementptr inbounds i8, i8* %buf, i64 %len
+  %zeros.narrow = sub i32 %exponent, %decimals
+  %zeros = sext i32 %zeros.narrow to i64
+  call i8* @memset(i8* %end, i32 48, i64 %zeros)
+  %stop.at = getelementptr inbounds i8, i8* %end, i64 %zeros
+  store i8 0, i8* %stop.at
+  ret i8* %buf
+}
+
+define internal void @ven_print_float(i32 %fd, double %x) {
+  %buf = alloca [400 x i8]
+  %start = getelementptr inbounds [400 x i8], [400 x i8]* %buf, i64 0, i64 0
+  %text = call i8* @ven_float_text(i8* %start, double %x)
+  call i32 (i32, i8*, ...) @dprintf(i32 %fd, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* %text)
+  ret void
+}
+
+define internal i128 @ven_fit(i128 %value, i128 %min, i128 %max, i8* %name, i8* %type, i64 %line) {
+entry:
+  %below = icmp slt i128 %value, %min
+  %above = icmp sgt i128 %value, %max
+  %out = or i1 %below, %above
+  br i1 %out, label %wrap, label %ok
+ok:
+  ret i128 %value
+wrap:
+  %mask = sub i128 %max, %min
+  %span = add i128 %mask, 1
+  %low = and i128 %value, %mask
+  %high = icmp sgt i128 %low, %max
+  %back = sub i128 %low, %span
+  %result = select i1 %high, i128 %back, i128 %low
+  ret i128 %result
+}
+
+; A u64 product, which can exceed i128
+define internal i64 @ven_mul_u64(i64 %a, i64 %b, i8* %name, i64 %line) {
+  %pair = call {i64, i1} @llvm.umul.with.overflow.i64(i64 %a, i64 %b)
+  %product = extractvalue {i64, i1} %pair, 0
+  ret i64 %product
+}
+
+define internal i128 @ven_fit_float(double %value, i128 %min, i128 %max, double %low, double %high, i8* %name, i8* %type, i64 %line) {
+entry:
+  %nan = fcmp uno double %value, %value
+  br i1 %nan, label %zero, label %number
+zero:
+  ret i128 0
+number:
+  %whole = call double @llvm.trunc.f64(double %value)
+  %below = fcmp olt double %whole, %low
+  %above = fcmp oge double %whole, %high
+  br i1 %below, label %clamp.min, label %check
+clamp.min:
+  ret i128 %min
+check:
+  br i1 %above, label %clamp.max, label %convert
+clamp.max:
+  ret i128 %max
+convert:
+  %negative = fcmp olt double %whole, 0.0
+  br i1 %negative, label %signed, label %unsigned
+signed:
+  %s = fptosi double %whole to i64
+  %wide.s = sext i64 %s to i128
+  ret i128 %wide.s
+unsigned:
+  %u = fptoui double %whole to i64
+  %wide.u = zext i64 %u to i128
+  ret i128 %wide.u
+}
+
+declare i8* @realloc(i8*, i64)
+
+@ven.fmt.oom = private unnamed_addr constant [30 x i8] c"runtime error: out of memory\0A\00"
+@ven.fmt.index = private unnamed_addr constant [22 x i8] c"runtime error: index \00"
+@ven.fmt.bounds = private unnamed_addr constant [55 x i8] c" is out of bounds for '%s' (length %llu) on line %lld\0A\00"
+@ven.fmt.sep = private unnamed_addr constant [3 x i8] c", \00"
+@ven.fmt.none = private unnamed_addr constant [1 x i8] zeroinitializer
+
+; Makes room for one more element of %size bytes
+define internal void @ven_grow({i8*, i64, i64}* %array, i64 %size) {
+entry:
+  %items.ptr = getelementptr inbounds {i8*, i64, i64}, {i8*, i64, i64}* %array, i32 0, i32 0
+  %len.ptr = getelementptr inbounds {i8*, i64, i64}, {i8*, i64, i64}* %array, i32 0, i32 1
+  %cap.ptr = getelementptr inbounds {i8*, i64, i64}, {i8*, i64, i64}* %array, i32 0, i32 2
+  %len = load i64, i64* %len.ptr
+  %cap = load i64, i64* %cap.ptr
+  %full = icmp uge i64 %len, %cap
+  br i1 %full, label %grow, label %done
+grow:
+  %empty = icmp eq i64 %cap, 0
+  %doubled = shl i64 %cap, 1
+  %new.cap = select i1 %empty, i64 8, i64 %doubled
+  %bytes = mul i64 %new.cap, %size
+  %items = load i8*, i8** %items.ptr
+  %new.items = call i8* @realloc(i8* %items, i64 %bytes)
+  %failed = icmp eq i8* %new.items, null
+  br i1 %failed, label %oom, label %store
+oom:
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([30 x i8], [30 x i8]* @ven.fmt.oom, i64 0, i64 0))
+  call void @exit(i32 1)
+  unreachable
+store:
+  store i8* %new.items, i8** %items.ptr
+  store i64 %new.cap, i64* %cap.ptr
+  br label %done
+done:
+  ret void
+}
+
+; The position of %index in the array %name of %len elements
+define internal i64 @ven_index(i128 %index, i64 %len, i8* %name, i64 %line) {
+entry:
+  %negative = icmp slt i128 %index, 0
+  %wide.len = zext i64 %len to i128
+  %past = icmp sge i128 %index, %wide.len
+  %out = or i1 %negative, %past
+  br i1 %out, label %fail, label %ok
+ok:
+  %position = trunc i128 %index to i64
+  ret i64 %position
+fail:
+  %narrow = trunc i128 %index to i64
+  %fmt = select i1 %negative, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.llu, i64 0, i64 0)
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([22 x i8], [22 x i8]* @ven.fmt.index, i64 0, i64 0))
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* %fmt, i64 %narrow)
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([55 x i8], [55 x i8]* @ven.fmt.bounds, i64 0, i64 0), i8* %name, i64 %len, i64 %line)
+  call void @exit(i32 1)
+  unreachable
+}
+
+@ven.fmt.convert = private unnamed_addr constant [55 x i8] c"runtime error: cannot convert \22%s\22 to %s on line %lld\0A\00"
+@ven.fmt.pad.right = private unnamed_addr constant [6 x i8] c"%*s%s\00"
+@ven.fmt.pad.left = private unnamed_addr constant [6 x i8] c"%s%*s\00"
+@ven.to.i = private unnamed_addr constant [2 x i8] c"i\00"
+@ven.to.f = private unnamed_addr constant [2 x i8] c"f\00"
+@ven.empty = private unnamed_addr constant [1 x i8] zeroinitializer
+
+define internal void @ven_convert_failed(i8* %text, i8* %to, i64 %line) {
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([55 x i8], [55 x i8]* @ven.fmt.convert, i64 0, i64 0), i8* %text, i8* %to, i64 %line)
+  call void @exit(i32 1)
+  unreachable
+}
+
+; Skips spaces, tabs and line breaks
+define internal i8* @ven_skip_blank(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %blank]
+  %byte = load i8, i8* %p
+  %space = icmp eq i8 %byte, 32
+  %tab = icmp eq i8 %byte, 9
+  %newline = icmp eq i8 %byte, 10
+  %return = icmp eq i8 %byte, 13
+  %gap = or i1 %space, %tab
+  %line = or i1 %newline, %return
+  %is.blank = or i1 %gap, %line
+  br i1 %is.blank, label %blank, label %done
+blank:
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %p
+}
+
+define internal i8* @ven_skip_digits(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %digit]
+  %byte = load i8, i8* %p
+  %offset = sub i8 %byte, 48
+  %is.digit = icmp ult i8 %offset, 10
+  br i1 %is.digit, label %digit, label %done
+digit:
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %p
+}
+
+define internal i8* @ven_skip_sign(i8* %text) {
+  %byte = load i8, i8* %text
+  %plus = icmp eq i8 %byte, 43
+  %minus = icmp eq i8 %byte, 45
+  %sign = or i1 %plus, %minus
+  %step = zext i1 %sign to i64
+  %after = getelementptr inbounds i8, i8* %text, i64 %step
+  ret i8* %after
+}
+
+; A sign and decimal digits within i64, with spaces around them
+define internal i128 @ven_convert_int(i8* %text, i64 %line) {
+entry:
+  %start = call i8* @ven_skip_blank(i8* %text)
+  %digits = call i8* @ven_skip_sign(i8* %start)
+  %end = call i8* @ven_skip_digits(i8* %digits)
+  %some = icmp ne i8* %end, %digits
+  %rest = call i8* @ven_skip_blank(i8* %end)
+  %byte = load i8, i8* %rest
+  %over = icmp eq i8 %byte, 0
+  %whole = and i1 %some, %over
+  br i1 %whole, label %parse, label %fail
+parse:
+  %errno = call i32* @__errno_location()
+  store i32 0, i32* %errno
+  %value = call i64 @strtoll(i8* %start, i8** null, i32 10)
+  %err = load i32, i32* %errno
+  %range = icmp eq i32 %err, 34
+  br i1 %range, label %fail, label %ok
+ok:
+  %wide = sext i64 %value to i128
+  ret i128 %wide
+fail:
+  call void @ven_convert_failed(i8* %text, i8* getelementptr inbounds ([2 x i8], [2 x i8]* @ven.to.i, i64 0, i64 0), i64 %line)
+  unreachable
+}
+
+; A finite decimal number like -1.5, .5, 2. or 6.02e23, with spaces around it
+define internal double @ven_convert_float(i8* %text, i64 %line) {
+entry:
+  %start = call i8* @ven_skip_blank(i8* %text)
+  %whole = call i8* @ven_skip_sign(i8* %start)
+  %point = call i8* @ven_skip_digits(i8* %whole)
+  %byte = load i8, i8* %point
+  %dot = icmp eq i8 %byte, 46
+  %step = zext i1 %dot to i64
+  %fraction = getelementptr inbounds i8, i8* %point, i64 %step
+  %mantissa = call i8* @ven_skip_digits(i8* %fraction)
+  %whole.digits = icmp ne i8* %point, %whole
+  %fraction.digits = icmp ne i8* %mantissa, %fraction
+  %digits = or i1 %whole.digits, %fraction.digits
+  %mark = load i8, i8* %mantissa
+  %lower = icmp eq i8 %mark, 101
+  %upper = icmp eq i8 %mark, 69
+  %has.exponent = or i1 %lower, %upper
+  br i1 %has.exponent, label %exponent, label %tail
+exponent:
+  %after.e = getelementptr inbounds i8, i8* %mantissa, i64 1
+  %exponent.digits = call i8* @ven_skip_sign(i8* %after.e)
+  %exponent.end = call i8* @ven_skip_digits(i8* %exponent.digits)
+  %exponent.some = icmp ne i8* %exponent.end, %exponent.digits
+  br label %tail
+tail:
+  %end = phi i8* [%mantissa, %entry], [%exponent.end, %exponent]
+  %exponent.ok = phi i1 [true, %entry], [%exponent.some, %exponent]
+  %rest = call i8* @ven_skip_blank(i8* %end)
+  %last = load i8, i8* %rest
+  %over = icmp eq i8 %last, 0
+  %number = and i1 %digits, %exponent.ok
+  %form = and i1 %number, %over
+  br i1 %form, label %parse, label %fail
+parse:
+  %value = call double @strtod(i8* %start, i8** null)
+  %zero = fsub double %value, %value
+  %finite = fcmp oeq double %zero, 0.0
+  br i1 %finite, label %ok, label %fail
+ok:
+  ret double %value
+fail:
+  call void @ven_convert_failed(i8* %text, i8* getelementptr inbounds ([2 x i8], [2 x i8]* @ven.to.f, i64 0, i64 0), i64 %line)
+  unreachable
+}
+
+; Truncated toward zero, with NaN as 0 and clamped at the i64 limits
+define internal i128 @ven_float_to_int(double %x) {
+  %nan = fcmp uno double %x, %x
+  %low = fcmp ole double %x, 0xC3E0000000000000
+  %high = fcmp oge double %x, 0x43E0000000000000
+  %int = fptosi double %x to i64
+  %wide = sext i64 %int to i128
+  %raised = select i1 %low, i128 -9223372036854775808, i128 %wide
+  %capped = select i1 %high, i128 9223372036854775807, i128 %raised
+  %result = select i1 %nan, i128 0, i128 %capped
+  ret i128 %result
+}
+
+; %precision digits after the point, rounded half to even, in %buf of 400 bytes
+define internal i8* @ven_fixed_text(i8* %buf, double %x, i32 %precision) {
+entry:
+  %zero = fsub double %x, %x
+  %finite = fcmp oeq double %zero, 0.0
+  br i1 %finite, label %fixed, label %plain
+fixed:
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %buf, i64 400, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.f, i64 0, i64 0), i32 %precision, double %x)
+  ret i8* %buf
+plain:
+  %text = call i8* @ven_float_text(i8* %buf, double %x)
+  ret i8* %text
+}
+
+; Prints %text padded with spaces to %width characters, before it when %right
+define internal void @ven_pad(i32 %fd, i8* %text, i64 %width, i1 %right) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %count]
+  %chars = phi i64 [0, %entry], [%more, %count]
+  %byte = load i8, i8* %p
+  %end = icmp eq i8 %byte, 0
+  br i1 %end, label %done, label %count
+count:
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %first = xor i1 %continuation, true
+  %add = zext i1 %first to i64
+  %more = add i64 %chars, %add
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  %narrow = icmp ult i64 %chars, %width
+  %gap = sub i64 %width, %chars
+  %spaces.wide = select i1 %narrow, i64 %gap, i64 0
+  %spaces = trunc i64 %spaces.wide to i32
+  %empty = getelementptr inbounds [1 x i8], [1 x i8]* @ven.empty, i64 0, i64 0
+  br i1 %right, label %before, label %after
+before:
+  call i32 (i32, i8*, ...) @dprintf(i32 %fd, i8* getelementptr inbounds ([6 x i8], [6 x i8]* @ven.fmt.pad.right, i64 0, i64 0), i32 %spaces, i8* %empty, i8* %text)
+  ret void
+after:
+  call i32 (i32, i8*, ...) @dprintf(i32 %fd, i8* getelementptr inbounds ([6 x i8], [6 x i8]* @ven.fmt.pad.left, i64 0, i64 0), i8* %text, i32 %spaces, i8* %empty)
+  ret void
+}
+
+@v.line = internal global [256 x i8] zeroinitializer
+@v.n = internal global i64 0
+@v.ratio = internal global double 0.0
+@v.label = internal global [256 x i8] zeroinitializer
+@v.small = internal global i8 0
+@v.price = internal global double 0.0
+@v.item = internal global [256 x i8] zeroinitializer
+@v.prices = internal global {i8*, i64, i64} zeroinitializer
+@ven.text.1 = internal global [24 x i8] zeroinitializer
+@ven.text.2 = internal global [24 x i8] zeroinitializer
+@ven.text.3 = internal global [400 x i8] zeroinitializer
+@ven.text.4 = internal global [400 x i8] zeroinitializer
+@ven.text.5 = internal global [24 x i8] zeroinitializer
+@ven.text.6 = internal global [400 x i8] zeroinitializer
+@ven.text.7 = internal global [400 x i8] zeroinitializer
+@ven.text.8 = internal global [24 x i8] zeroinitializer
+@.str.0 = private unnamed_addr constant [5 x i8] c" 42 \00"
+@.str.1 = private unnamed_addr constant [4 x i8] c"2.5\00"
+@.str.2 = private unnamed_addr constant [4 x i8] c"200\00"
+@.str.3 = private unnamed_addr constant [6 x i8] c"small\00"
+@.str.4 = private unnamed_addr constant [3 x i8] c"u8\00"
+@.str.5 = private unnamed_addr constant [3 x i8] c"42\00"
+@.str.6 = private unnamed_addr constant [17 x i8] c" rounds down to \00"
+@.str.7 = private unnamed_addr constant [2 x i8] c"\0A\00"
+@.str.8 = private unnamed_addr constant [4 x i8] c"tea\00"
+@.str.9 = private unnamed_addr constant [2 x i8] c"[\00"
+@.str.10 = private unnamed_addr constant [4 x i8] c"] [\00"
+@.str.11 = private unnamed_addr constant [7 x i8] c"prices\00"
+@.str.12 = private unnamed_addr constant [3 x i8] c"]\0A\00"
+
+define i32 @main() {
+entry:
+  call void @ven_copy(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.line, i64 0, i64 0), i8* getelementptr inbounds ([5 x i8], [5 x i8]* @.str.0, i64 0, i64 0), i64 256)
+  %t1 = call i128 @ven_convert_int(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.line, i64 0, i64 0), i64 3)
+  %t2 = trunc i128 %t1 to i64
+  store i64 %t2, i64* @v.n
+  %t3 = call double @ven_convert_float(i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.1, i64 0, i64 0), i64 4)
+  store double %t3, double* @v.ratio
+  %t4 = load i64, i64* @v.n
+  %t5 = sext i64 %t4 to i128
+  %t6 = trunc i128 %t5 to i64
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* getelementptr inbounds ([24 x i8], [24 x i8]* @ven.text.1, i64 0, i64 0), i64 24, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t6)
+  call void @ven_copy(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.label, i64 0, i64 0), i8* getelementptr inbounds ([24 x i8], [24 x i8]* @ven.text.1, i64 0, i64 0), i64 256)
+  %t7 = call i128 @ven_convert_int(i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.2, i64 0, i64 0), i64 6)
+  %t8 = call i128 @ven_fit(i128 %t7, i128 0, i128 255, i8* getelementptr inbounds ([6 x i8], [6 x i8]* @.str.3, i64 0, i64 0), i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str.4, i64 0, i64 0), i64 6)
+  %t9 = trunc i128 %t8 to i8
+  store i8 %t9, i8* @v.small
+  %t10 = load i8, i8* @v.small
+  %t11 = zext i8 %t10 to i128
+  %t12 = load double, double* @v.ratio
+  %t13 = call i128 @ven_float_to_int(double %t12)
+  %t14 = call i128 @ven_fit(i128 %t13, i128 0, i128 255, i8* getelementptr inbounds ([6 x i8], [6 x i8]* @.str.3, i64 0, i64 0), i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str.4, i64 0, i64 0), i64 7)
+  %t15 = add i128 %t11, %t14
+  %t16 = call i128 @ven_fit(i128 %t15, i128 0, i128 255, i8* getelementptr inbounds ([6 x i8], [6 x i8]* @.str.3, i64 0, i64 0), i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str.4, i64 0, i64 0), i64 7)
+  %t17 = trunc i128 %t16 to i8
+  store i8 %t17, i8* @v.small
+  %t18 = load double, double* @v.ratio
+  %t19 = call i128 @ven_float_to_int(double %t18)
+  %t20 = icmp eq i128 %t19, 2
+  %t21 = load i64, i64* @v.n
+  %t22 = sext i64 %t21 to i128
+  %t23 = trunc i128 %t22 to i64
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* getelementptr inbounds ([24 x i8], [24 x i8]* @ven.text.2, i64 0, i64 0), i64 24, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t23)
+  %t24 = call i32 @strcmp(i8* getelementptr inbounds ([24 x i8], [24 x i8]* @ven.text.2, i64 0, i64 0), i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str.5, i64 0, i64 0))
+  %t25 = icmp eq i32 %t24, 0
+  %t26 = and i1 %t20, %t25
+  br i1 %t26, label %then.1, label %endif.1
+then.1:
+  %t27 = load double, double* @v.ratio
+  %t28 = call i8* @ven_float_text(i8* getelementptr inbounds ([400 x i8], [400 x i8]* @ven.text.3, i64 0, i64 0), double %t27)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* %t28)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([17 x i8], [17 x i8]* @.str.6, i64 0, i64 0))
+  %t29 = load double, double* @v.ratio
+  %t30 = call i128 @ven_float_to_int(double %t29)
+  %t31 = trunc i128 %t30 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t31)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.7, i64 0, i64 0))
+  br label %endif.1
+endif.1:
+  store double 0x4005666666666666, double* @v.price
+  call void @ven_copy(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.item, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.8, i64 0, i64 0), i64 256)
+  store i64 0, i64* getelementptr inbounds ({i8*, i64, i64}, {i8*, i64, i64}* @v.prices, i32 0, i32 1)
+  call void @ven_grow({i8*, i64, i64}* @v.prices, i64 8)
+  %t32 = load i64, i64* getelementptr inbounds ({i8*, i64, i64}, {i8*, i64, i64}* @v.prices, i32 0, i32 1)
+  %t33 = load i8*, i8** getelementptr inbounds ({i8*, i64, i64}, {i8*, i64, i64}* @v.prices, i32 0, i32 0)
+  %t34 = bitcast i8* %t33 to double*
+  %t35 = getelementptr inbounds double, double* %t34, i64 %t32
+  store double 0x3FF8000000000000, double* %t35
+  %t36 = add i64 %t32, 1
+  store i64 %t36, i64* getelementptr inbounds ({i8*, i64, i64}, {i8*, i64, i64}* @v.prices, i32 0, i32 1)
+  call void @ven_grow({i8*, i64, i64}* @v.prices, i64 8)
+  %t37 = load i64, i64* getelementptr inbounds ({i8*, i64, i64}, {i8*, i64, i64}* @v.prices, i32 0, i32 1)
+  %t38 = load i8*, i8** getelementptr inbounds ({i8*, i64, i64}, {i8*, i64, i64}* @v.prices, i32 0, i32 0)
+  %t39 = bitcast i8* %t38 to double*
+  %t40 = getelementptr inbounds double, double* %t39, i64 %t37
+  store double 0x4024800000000000, double* %t40
+  %t41 = add i64 %t37, 1
+  store i64 %t41, i64* getelementptr inbounds ({i8*, i64, i64}, {i8*, i64, i64}* @v.prices, i32 0, i32 1)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.9, i64 0, i64 0))
+  %t42 = load double, double* @v.price
+  %t43 = call i8* @ven_fixed_text(i8* getelementptr inbounds ([400 x i8], [400 x i8]* @ven.text.4, i64 0, i64 0), double %t42, i32 2)
+  call void @ven_pad(i32 1, i8* %t43, i64 0, i1 true)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.10, i64 0, i64 0))
+  %t44 = load i64, i64* @v.n
+  %t45 = sext i64 %t44 to i128
+  %t46 = trunc i128 %t45 to i64
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* getelementptr inbounds ([24 x i8], [24 x i8]* @ven.text.5, i64 0, i64 0), i64 24, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t46)
+  call void @ven_pad(i32 1, i8* getelementptr inbounds ([24 x i8], [24 x i8]* @ven.text.5, i64 0, i64 0), i64 5, i1 true)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.10, i64 0, i64 0))
+  call void @ven_pad(i32 1, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.item, i64 0, i64 0), i64 6, i1 false)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.10, i64 0, i64 0))
+  %t47 = load double, double* @v.price
+  %t48 = call i8* @ven_fixed_text(i8* getelementptr inbounds ([400 x i8], [400 x i8]* @ven.text.6, i64 0, i64 0), double %t47, i32 1)
+  call void @ven_pad(i32 1, i8* %t48, i64 8, i1 true)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.10, i64 0, i64 0))
+  %t49 = load i64, i64* getelementptr inbounds ({i8*, i64, i64}, {i8*, i64, i64}* @v.prices, i32 0, i32 1)
+  %t50 = call i64 @ven_index(i128 1, i64 %t49, i8* getelementptr inbounds ([7 x i8], [7 x i8]* @.str.11, i64 0, i64 0), i64 14)
+  %t51 = load i8*, i8** getelementptr inbounds ({i8*, i64, i64}, {i8*, i64, i64}* @v.prices, i32 0, i32 0)
+  %t52 = bitcast i8* %t51 to double*
+  %t53 = getelementptr inbounds double, double* %t52, i64 %t50
+  %t54 = load double, double* %t53
+  %t55 = call i8* @ven_fixed_text(i8* getelementptr inbounds ([400 x i8], [400 x i8]* @ven.text.7, i64 0, i64 0), double %t54, i32 1)
+  call void @ven_pad(i32 1, i8* %t55, i64 0, i1 true)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.10, i64 0, i64 0))
+  %t56 = load i8, i8* @v.small
+  %t57 = zext i8 %t56 to i128
+  %t58 = trunc i128 %t57 to i64
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* getelementptr inbounds ([24 x i8], [24 x i8]* @ven.text.8, i64 0, i64 0), i64 24, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t58)
+  call void @ven_pad(i32 1, i8* getelementptr inbounds ([24 x i8], [24 x i8]* @ven.text.8, i64 0, i64 0), i64 4, i1 false)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str.12, i64 0, i64 0))
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.label, i64 0, i64 0))
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.7, i64 0, i64 0))
+  ret i32 0
+}
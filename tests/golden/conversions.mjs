@@ -0,0 +1,139 @@
+// Generated by the Ven compiler
+import fs from "node:fs";
+
+const __byte = Buffer.alloc(1);
+function __readLine() {
+  const bytes = [];
+  for (;;) {
+    let read = 0;
+    try {
+      read = fs.readSync(0, __byte, 0, 1, null);
+    } catch (e) {
+      if (e.code === "EAGAIN") continue;
+      if (e.code === "EOF") break;
+      throw e;
+    }
+    if (read === 0 || __byte[0] === 10) break;
+    bytes.push(__byte[0]);
+  }
+  if (bytes[bytes.length - 1] === 13) bytes.pop();
+  // A line holds at most 255 bytes, as on the native targets, and a character
+  // cut in two is dropped whole
+  let end = Math.min(bytes.length, 255);
+  while (end > 0 && end < bytes.length && (bytes[end] & 0xc0) === 0x80) end--;
+  return Buffer.from(bytes.slice(0, end)).toString("utf8");
+}
+
+// Integers are BigInts. Text that is not a whole number from the i64 minimum to
+// the u64 maximum reads as 0.
+function __toInt(text) {
+  const trimmed = text.trim();
+  if (!/^[+-]?[0-9]+$/.test(trimmed)) return 0n;
+  const value = BigInt(trimmed);
+  return value < -(2n ** 63n) || value >= 2n ** 64n ? 0n : value;
+}
+
+function __fit(value, min, max, name, type, line) {
+  if (value >= min && value <= max) return value;
+  const span = max - min + 1n;
+  return (((value - min) % span) + span) % span + min;
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+function __fitFloat(value, min, max, name, type, line) {
+  if (Number.isNaN(value)) return 0n;
+  const whole = Math.trunc(value);
+  return whole < Number(min) ? min : whole >= Number(max) + 1 ? max : BigInt(whole);
+}
+
+function __index(index, length, name, line) {
+  const i = BigInt(index);
+  if (i < 0n || i >= BigInt(length)) {
+    process.stderr.write(`runtime error: index ${i} is out of bounds for '${name}' (length ${length}) on line ${line}\n`);
+    process.exit(1);
+  }
+  return Number(i);
+}
+
+function __floatText(x) {
+  if (!Number.isFinite(x)) return Number.isNaN(x) ? "NaN" : x < 0 ? "-inf" : "inf";
+  if (Object.is(x, -0)) return "-0";
+  // The shortest digits, written out without an exponent like the native targets
+  const text = String(Math.abs(x));
+  const e = text.indexOf("e");
+  if (e < 0) return x < 0 ? `-${text}` : text;
+  const digits = text.slice(0, e).replace(".", "");
+  const point = 1 + Number(text.slice(e + 1));
+  const sign = x < 0 ? "-" : "";
+  if (point <= 0) return `${sign}0.${"0".repeat(-point)}${digits}`;
+  if (point >= digits.length) return sign + digits + "0".repeat(point - digits.length);
+  return `${sign}${digits.slice(0, point)}.${digits.slice(point)}`;
+}
+
+function __convertFailed(text, to, line) {
+  process.stderr.write(`runtime error: cannot convert "${text}" to ${to} on line ${line}\n`);
+  process.exit(1);
+}
+
+// A sign and decimal digits within i64, with spaces around them
+function __convertInt(text, line) {
+  const trimmed = text.replace(/^[ \t\n\r]+|[ \t\n\r]+$/g, "");
+  if (!/^[+-]?[0-9]+$/.test(trimmed)) __convertFailed(text, "i", line);
+  const value = BigInt(trimmed);
+  if (value < -(2n ** 63n) || value >= 2n ** 63n) __convertFailed(text, "i", line);
+  return value;
+}
+
+// A finite decimal number like -1.5, .5, 2. or 6.02e23, with spaces around it
+function __convertFloat(text, line) {
+  const trimmed = text.replace(/^[ \t\n\r]+|[ \t\n\r]+$/g, "");
+  const value = Number(trimmed);
+  if (!/^[+-]?([0-9]+(\.[0-9]*)?|\.[0-9]+)([eE][+-]?[0-9]+)?$/.test(trimmed) || !Number.isFinite(value)) {
+    __convertFailed(text, "f", line);
+  }
+  return value;
+}
+
+// Truncated toward zero, with NaN as 0 and clamped at the i64 limits
+function __floatToInt(x) {
+  if (Number.isNaN(x)) return 0n;
+  return x <= -(2 ** 63) ? -(2n ** 63n) : x >= 2 ** 63 ? 2n ** 63n - 1n : BigInt(Math.trunc(x));
+}
+
+// p digits after the point. toFixed rounds ties up, so an exact tie is
+// rounded half to even here, like the native targets.
+function __fixed(x, p) {
+  if (!Number.isFinite(x)) return __floatText(x);
+  const sign = x < 0 || Object.is(x, -0) ? "-" : "";
+  const abs = Math.abs(x);
+  if (abs >= 1e21) return sign + BigInt(abs).toString() + (p > 0 ? `.${"0".repeat(p)}` : "");
+  const longer = abs.toFixed(p + 1);
+  if (longer.endsWith("5") && Number.isInteger(abs * 2 ** (p + 1))) {
+    const down = longer.slice(0, p === 0 ? -2 : -1);
+    if (Number(down[down.length - 1]) % 2 === 0) return sign + down;
+  }
+  return sign + abs.toFixed(p);
+}
+
+// Padded with spaces to width characters, before the text when right
+function __pad(text, width, right) {
+  const fill = " ".repeat(Math.max(0, width - [...text].length));
+  return right ? fill + text : text + fill;
+}
+
+let line, n, ratio, label, small, price, item, prices;
+
+line = " 42 ";
+n = __convertInt(line, 3);
+ratio = __convertFloat("2.5", 4);
+label = String(n);
+small = __fit(__convertInt("200", 6), 0n, 255n, "small", "u8", 6);
+small = __fit(small + __fit(__floatToInt(ratio), 0n, 255n, "small", "u8", 7), 0n, 255n, "small", "u8", 7);
+if ((__floatToInt(ratio) === 2n) && (String(n) === "42")) {
+  process.stdout.write(`${__floatText(ratio)} rounds down to ${__floatToInt(ratio)}\n`);
+}
+price = 2.675;
+item = "tea";
+prices = [1.5, 10.25];
+process.stdout.write(`[${__pad(__fixed(price, 2), 0, true)}] [${__pad(String(n), 5, true)}] [${__pad(String(item), 6, false)}] [${__pad(__fixed(price, 1), 8, true)}] [${__pad(__fixed(prices[__index(1, prices.length, "prices", 14)], 1), 0, true)}] [${__pad(String(small), 4, false)}]\n`);
+process.stdout.write(`${label}\n`);
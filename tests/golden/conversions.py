@@ -0,0 +1,159 @@
+# Generated by the Ven compiler
+import sys
+
+
+def _read_line():
+    # A line holds at most 255 bytes, as on the native targets
+    line = sys.stdin.readline().rstrip("\r\n").encode()
+    return line[:255].decode(errors="ignore")
+
+
+# Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+def _to_int(text):
+    try:
+        value = int(text.strip())
+    except ValueError:
+        return 0
+    return value if -(2**63) <= value < 2**64 else 0
+
+
+def _to_float(text):
+    try:
+        return float(text.strip())
+    except ValueError:
+        return 0.0
+
+
+def _int_div(a, b):
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+
+
+def _fit(value, lo, hi, name, type, line):
+    return (value - lo) % (hi - lo + 1) + lo
+
+
+# Floats never wrap: they are truncated and clamp at the limits
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+
+
+def _shown(value):
+    if isinstance(value, bool):
+        return str(value).lower()
+    return _float_text(value) if isinstance(value, float) else str(value)
+
+
+def _index(index, length, name, line):
+    if not 0 <= index < length:
+        sys.stdout.flush()
+        print(f"runtime error: index {index} is out of bounds for '{name}' (length {length}) on line {line}", file=sys.stderr)
+        sys.exit(1)
+    return index
+
+
+def _show(items):
+    return "[" + ", ".join(_shown(item) for item in items) + "]"
+
+
+# The shortest digits, written out without an exponent like the native targets
+def _float_text(x):
+    x = float(x)
+    if x != x:
+        return "NaN"
+    if x - x != 0:
+        return "inf" if x > 0 else "-inf"
+    sign = "-" if str(x).startswith("-") else ""
+    mantissa, _, exponent = repr(abs(x)).partition("e")
+    whole, _, fraction = mantissa.partition(".")
+    digits = (whole + fraction).rstrip("0")
+    point = len(whole) + int(exponent or 0) - (len(digits) - len(digits.lstrip("0")))
+    digits = digits.lstrip("0")
+    if not digits:
+        return sign + "0"
+    if point <= 0:
+        return sign + "0." + "0" * -point + digits
+    if point >= len(digits):
+        return sign + digits + "0" * (point - len(digits))
+    return sign + digits[:point] + "." + digits[point:]
+
+
+def _convert_failed(text, to, line):
+    sys.stdout.flush()
+    print(f'runtime error: cannot convert "{text}" to {to} on line {line}', file=sys.stderr)
+    sys.exit(1)
+
+
+def _digits(text):
+    return all("0" <= c <= "9" for c in text)
+
+
+# A sign and decimal digits within i64, with spaces around them
+def _convert_int(text, line):
+    trimmed = text.strip(" \t\n\r")
+    unsigned = trimmed[1:] if trimmed[:1] in ("+", "-") else trimmed
+    if not unsigned or not _digits(unsigned) or not -(2**63) <= int(trimmed) < 2**63:
+        _convert_failed(text, "i", line)
+    return int(trimmed)
+
+
+# A finite decimal number like -1.5, .5, 2. or 6.02e23, with spaces around it
+def _convert_float(text, line):
+    number = text.strip(" \t\n\r")
+    unsigned = number[1:] if number[:1] in ("+", "-") else number
+    mantissa, e, exponent = unsigned.replace("E", "e").partition("e")
+    whole, _, fraction = mantissa.partition(".")
+    exponent = exponent[1:] if exponent[:1] in ("+", "-") else exponent
+    valid = (whole or fraction) and _digits(whole) and _digits(fraction) and _digits(exponent)
+    if not valid or (e and not exponent) or float(number) - float(number) != 0:
+        _convert_failed(text, "f", line)
+    return float(number)
+
+
+# Truncated toward zero, with NaN as 0 and clamped at the i64 limits
+def _float_to_int(x):
+    if x != x:
+        return 0
+    if x <= -(2**63):
+        return -(2**63)
+    return 2**63 - 1 if x >= 2**63 else int(x)
+
+
+# p digits after the point, rounded half to even
+def _fixed(x, p):
+    return "%.*f" % (p, x) if x - x == 0 else _float_text(x)
+
+
+# Padded with spaces to width characters, before the text when right
+def _pad(text, width, right):
+    return text.rjust(width) if right else text.ljust(width)
+
+
+line = ""
+n = 0
+ratio = 0.0
+label = ""
+small = 0
+price = 0.0
+item = ""
+prices = []
+
+line = " 42 "
+n = _convert_int(line, 3)
+ratio = _convert_float("2.5", 4)
+label = str(n)
+small = _fit(_convert_int("200", 6), 0, 255, "small", "u8", 6)
+small = _fit(small + _fit(_float_to_int(ratio), 0, 255, "small", "u8", 7), 0, 255, "small", "u8", 7)
+if (_float_to_int(ratio) == 2) and (str(n) == "42"):
+    print(f"{_float_text(ratio)} rounds down to {_float_to_int(ratio)}")
+price = 2.675
+item = "tea"
+prices = [1.5, 10.25]
+print(f"[{_pad(_fixed(price, 2), 0, True)}] [{_pad(str(n), 5, True)}] [{_pad(item, 6, False)}] [{_pad(_fixed(price, 1), 8, True)}] [{_pad(_fixed(prices[_index(1, len(prices), 'prices', 14)], 1), 0, True)}] [{_pad(str(small), 4, False)}]")
+print(label)
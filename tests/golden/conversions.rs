@@ -0,0 +1,126 @@
+// Generated by the Ven compiler
+#![allow(dead_code, unused_mut, unused_assignments, unused_variables, unused_parens)]
+use std::io::BufRead;
+
+fn ven_read_line() -> String {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).unwrap_or(0);
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+    // A line holds at most 255 bytes, as on the native targets
+    let mut end = line.len().min(255);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+    line
+}
+
+// Text that is not a whole number from i64::MIN to u64::MAX reads as 0
+fn ven_to_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+        .unwrap_or(0)
+}
+
+fn ven_to_float(text: &str) -> f64 {
+    text.trim().parse().unwrap_or(0.0)
+}
+
+fn ven_to_bool(text: &str) -> bool {
+    text.trim() == "true"
+}
+
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        let span = max - min + 1;
+        let low = value.rem_euclid(span);
+        return if low > max { low - span } else { low };
+    }
+    value
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => (a as u64).wrapping_mul(b as u64) as i128,
+    }
+}
+
+fn ven_index(index: i128, len: usize, name: &str, line: u32) -> usize {
+    if index < 0 || index >= len as i128 {
+        eprintln!("runtime error: index {} is out of bounds for '{}' (length {}) on line {}", index, name, len, line);
+        std::process::exit(1);
+    }
+    index as usize
+}
+
+fn ven_convert_failed(text: &str, to: &str, line: u32) -> ! {
+    eprintln!("runtime error: cannot convert \"{}\" to {} on line {}", text, to, line);
+    std::process::exit(1);
+}
+
+// A sign and decimal digits within i64, with spaces around them
+fn ven_convert_int(text: &str, line: u32) -> i64 {
+    let digits = text.trim_matches([' ', '\t', '\n', '\r']);
+    let unsigned = digits.strip_prefix(['+', '-']).unwrap_or(digits);
+    match digits.parse() {
+        Ok(value) if unsigned.bytes().all(|b| b.is_ascii_digit()) => value,
+        _ => ven_convert_failed(text, "i", line),
+    }
+}
+
+// A finite decimal number like -1.5, .5, 2. or 6.02e23, with spaces around it
+fn ven_convert_float(text: &str, line: u32) -> f64 {
+    let number = text.trim_matches([' ', '\t', '\n', '\r']);
+    let unsigned = number.strip_prefix(['+', '-']).unwrap_or(number);
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(at) => (&unsigned[..at], Some(&unsigned[at + 1..])),
+        None => (unsigned, None),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    let exponent_ok = exponent.map_or(true, |exponent| {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        !exponent.is_empty() && digits(exponent)
+    });
+    match number.parse::<f64>() {
+        Ok(value) if whole.len() + fraction.len() > 0 && digits(whole) && digits(fraction) && exponent_ok
+            && value.is_finite() => value,
+        _ => ven_convert_failed(text, "f", line),
+    }
+}
+
+fn main() {
+    let mut line = String::new();
+    let mut n: i64 = 0;
+    let mut ratio: f64 = 0.0;
+    let mut label = String::new();
+    let mut small: u8 = 0;
+    let mut price: f64 = 0.0;
+    let mut item = String::new();
+    let mut prices: Vec<f64> = Vec::new();
+    line = " 42 ".to_string();
+    n = ven_convert_int(&line, 3);
+    ratio = ven_convert_float(&"2.5", 4);
+    label = (n).to_string().to_string();
+    small = ven_fit(ven_convert_int(&"200", 6) as i128, 0, 255, "small", "u8", 6) as u8;
+    small = ven_fit(small as i128 + (ven_fit((ratio as i64) as i128, 0, 255, "small", "u8", 7) as u8) as i128, 0, 255, "small", "u8", 7) as u8;
+    if (((ratio as i64) == 2) && ((n).to_string().as_str() == "42")) {
+        println!("{} rounds down to {}", (ratio).to_string(), (ratio as i64));
+    }
+    price = 2.675;
+    item = "tea".to_string();
+    prices = vec![1.5, 10.25];
+    println!("[{:.2}] [{:>5}] [{:<6}] [{:8.1}] [{:.1}] [{:<4}]", price, n, item, price, prices[ven_index(1 as i128, prices.len(), "prices", 14)], small);
+    println!("{}", label);
+}
@@ -0,0 +1,134 @@
+// Generated by the Ven compiler
+import fs from "node:fs";
+
+const __byte = Buffer.alloc(1);
+function __readLine() {
+  const bytes = [];
+  for (;;) {
+    let read = 0;
+    try {
+      read = fs.readSync(0, __byte, 0, 1, null);
+    } catch (e) {
+      if (e.code === "EAGAIN") continue;
+      if (e.code === "EOF") break;
+      throw e;
+    }
+    if (read === 0 || __byte[0] === 10) break;
+    bytes.push(__byte[0]);
+  }
+  if (bytes[bytes.length - 1] === 13) bytes.pop();
+  // A line holds at most 255 bytes, as on the native targets, and a character
+  // cut in two is dropped whole
+  let end = Math.min(bytes.length, 255);
+  while (end > 0 && end < bytes.length && (bytes[end] & 0xc0) === 0x80) end--;
+  return Buffer.from(bytes.slice(0, end)).toString("utf8");
+}
+
+// Integers are BigInts. Text that is not a whole number from the i64 minimum to
+// the u64 maximum reads as 0.
+function __toInt(text) {
+  const trimmed = text.trim();
+  if (!/^[+-]?[0-9]+$/.test(trimmed)) return 0n;
+  const value = BigInt(trimmed);
+  return value < -(2n ** 63n) || value >= 2n ** 64n ? 0n : value;
+}
+
+function __fit(value, min, max, name, type, line) {
+  if (value >= min && value <= max) return value;
+  const span = max - min + 1n;
+  return (((value - min) % span) + span) % span + min;
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+function __fitFloat(value, min, max, name, type, line) {
+  if (Number.isNaN(value)) return 0n;
+  const whole = Math.trunc(value);
+  return whole < Number(min) ? min : whole >= Number(max) + 1 ? max : BigInt(whole);
+}
+
+function __floatText(x) {
+  if (!Number.isFinite(x)) return Number.isNaN(x) ? "NaN" : x < 0 ? "-inf" : "inf";
+  if (Object.is(x, -0)) return "-0";
+  // The shortest digits, written out without an exponent like the native targets
+  const text = String(Math.abs(x));
+  const e = text.indexOf("e");
+  if (e < 0) return x < 0 ? `-${text}` : text;
+  const digits = text.slice(0, e).replace(".", "");
+  const point = 1 + Number(text.slice(e + 1));
+  const sign = x < 0 ? "-" : "";
+  if (point <= 0) return `${sign}0.${"0".repeat(-point)}${digits}`;
+  if (point >= digits.length) return sign + digits + "0".repeat(point - digits.length);
+  return `${sign}${digits.slice(0, point)}.${digits.slice(point)}`;
+}
+
+// A negative y gives 1 / x ** -y truncated toward zero. A power past 128 bits
+// is cut to its sign and low 64 bits, still past every integer type, which
+// `__fit` handles as it would the power itself
+function __pow(x, y) {
+  if (y === 0n) return 1n;
+  if (x === 0n || x === 1n) return x;
+  if (x === -1n) return y % 2n === 0n ? 1n : -1n;
+  if (y < 0n) return 0n;
+  if (y < 128n) return x ** y;
+  let low = 1n;
+  let base = BigInt.asUintN(64, x);
+  for (let e = y; e > 0n; e >>= 1n) {
+    if (e & 1n) low = BigInt.asUintN(64, low * base);
+    base = BigInt.asUintN(64, base * base);
+  }
+  return x < 0n && y % 2n !== 0n ? low - (1n << 65n) : low + (1n << 64n);
+}
+
+// As on the native targets, 1 to any power and -1 to an infinite one are 1
+function __fpow(x, y) {
+  if (x === 1 || (x === -1 && !Number.isFinite(y) && !Number.isNaN(y))) return 1;
+  return x ** y;
+}
+
+function __abs(x) {
+  return x < 0n ? -x : x;
+}
+
+// A later value only wins when it is smaller, or larger
+function __min(...values) {
+  return values.reduce((min, x) => (x < min ? x : min));
+}
+
+function __max(...values) {
+  return values.reduce((max, x) => (x > max ? x : max));
+}
+
+// Halves round away from zero
+function __round(x) {
+  return x < 0 ? -Math.round(-x) : Math.round(x);
+}
+
+function __divZero(name, line) {
+  process.stderr.write(`runtime error: division of '${name}' by zero on line ${line}\n`);
+  process.exit(1);
+}
+
+function __divisor(y, name, line) {
+  if (y == 0) __divZero(name, line);
+  return y;
+}
+
+function __exponent(x, y, name, line) {
+  if (x == 0 && y < 0) __divZero(name, line);
+  return y;
+}
+
+let angle, growth, side, low;
+
+angle = 370.5;
+angle = angle % __divisor(360.0, "angle", 3);
+growth = 1.5;
+growth = __fpow(growth, 2.5);
+side = Math.sqrt(2.0);
+low = -2.5;
+process.stdout.write(`angle=${__floatText(angle)} growth=${__floatText(growth)}\n`);
+process.stdout.write(`${__floatText(__round(low))} ${__floatText(Math.floor(low))} ${__floatText(Math.ceil(low))} ${__floatText(Math.abs(low))}\n`);
+process.stdout.write(`${__floatText(__min(side, 1.0))} ${__floatText(__max(low, 3))}\n`);
+if (__round(side) === 1.0) {
+  process.stdout.write(`sqrt(2) rounds to 1\n`);
+}
@@ -0,0 +1,165 @@
+# Generated by the Ven compiler
+import math
+import sys
+
+
+def _read_line():
+    # A line holds at most 255 bytes, as on the native targets
+    line = sys.stdin.readline().rstrip("\r\n").encode()
+    return line[:255].decode(errors="ignore")
+
+
+# Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+def _to_int(text):
+    try:
+        value = int(text.strip())
+    except ValueError:
+        return 0
+    return value if -(2**63) <= value < 2**64 else 0
+
+
+def _to_float(text):
+    try:
+        return float(text.strip())
+    except ValueError:
+        return 0.0
+
+
+def _int_div(a, b):
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+
+
+def _fit(value, lo, hi, name, type, line):
+    return (value - lo) % (hi - lo + 1) + lo
+
+
+# Floats never wrap: they are truncated and clamp at the limits
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+
+
+# The shortest digits, written out without an exponent like the native targets
+def _float_text(x):
+    x = float(x)
+    if x != x:
+        return "NaN"
+    if x - x != 0:
+        return "inf" if x > 0 else "-inf"
+    sign = "-" if str(x).startswith("-") else ""
+    mantissa, _, exponent = repr(abs(x)).partition("e")
+    whole, _, fraction = mantissa.partition(".")
+    digits = (whole + fraction).rstrip("0")
+    point = len(whole) + int(exponent or 0) - (len(digits) - len(digits.lstrip("0")))
+    digits = digits.lstrip("0")
+    if not digits:
+        return sign + "0"
+    if point <= 0:
+        return sign + "0." + "0" * -point + digits
+    if point >= len(digits):
+        return sign + digits + "0" * (point - len(digits))
+    return sign + digits[:point] + "." + digits[point:]
+
+
+def _int_rem(a, b):
+    r = abs(a) % abs(b)
+    return -r if a < 0 else r
+
+
+# A negative y gives 1 / x ** -y truncated toward zero. A power past 128 bits is
+# cut to its sign and low 64 bits, still past every integer type, which _fit
+# handles as it would the power itself
+def _pow(x, y):
+    if y == 0:
+        return 1
+    if x in (0, 1):
+        return x
+    if x == -1:
+        return 1 if y % 2 == 0 else -1
+    if y < 0:
+        return 0
+    if y < 128:
+        return x**y
+    low = pow(x % 2**64, y, 2**64)
+    return low - 2**65 if x < 0 and y % 2 == 1 else low + 2**64
+
+
+def _fmod(x, y):
+    if x - x != 0 or y != y or y == 0:
+        return math.nan
+    return math.fmod(x, y)
+
+
+def _fpow(x, y):
+    try:
+        return math.pow(x, y)
+    except OverflowError:
+        return math.copysign(math.inf, x) if y % 2 == 1 else math.inf
+    except ValueError:
+        if x == 0:
+            return math.copysign(math.inf, x) if y % 2 == 1 else math.inf
+        return math.nan
+
+
+def _sqrt(x):
+    return math.sqrt(x) if x >= 0 else math.nan
+
+
+# Infinities and NaN stay as they are; whole results keep the sign of x, so
+# ceil(-0.5) is -0 as on the native targets
+def _floor(x):
+    return math.copysign(math.floor(x), x) if x - x == 0 else x
+
+
+def _ceil(x):
+    return math.copysign(math.ceil(x), x) if x - x == 0 else x
+
+
+# Halves round away from zero
+def _round(x):
+    if x - x != 0:
+        return x
+    whole = math.floor(abs(x))
+    return math.copysign(whole + (abs(x) - whole >= 0.5), x)
+
+
+def _div_zero(name, line):
+    sys.stdout.flush()
+    print(f"runtime error: division of '{name}' by zero on line {line}", file=sys.stderr)
+    sys.exit(1)
+
+
+def _divisor(y, name, line):
+    if y == 0:
+        _div_zero(name, line)
+    return y
+
+
+def _exponent(x, y, name, line):
+    if x == 0 and y < 0:
+        _div_zero(name, line)
+    return y
+
+
+angle = 0.0
+growth = 0.0
+side = 0.0
+low = 0.0
+
+angle = 370.5
+angle = _fmod(angle, _divisor(360.0, "angle", 3))
+growth = 1.5
+growth = _fpow(growth, 2.5)
+side = _sqrt(2.0)
+low = -2.5
+print(f"angle={_float_text(angle)} growth={_float_text(growth)}")
+print(f"{_float_text(_round(low))} {_float_text(_floor(low))} {_float_text(_ceil(low))} {_float_text(abs(low))}")
+print(f"{_float_text(min(side, 1.0))} {_float_text(max(low, 3))}")
+if _round(side) == 1.0:
+    print("sqrt(2) rounds to 1")
@@ -0,0 +1,137 @@
+// Generated by the Ven compiler
+#![allow(dead_code, unused_mut, unused_assignments, unused_variables, unused_parens)]
+use std::io::BufRead;
+
+fn ven_read_line() -> String {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).unwrap_or(0);
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+    // A line holds at most 255 bytes, as on the native targets
+    let mut end = line.len().min(255);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+    line
+}
+
+// Text that is not a whole number from i64::MIN to u64::MAX reads as 0
+fn ven_to_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+        .unwrap_or(0)
+}
+
+fn ven_to_float(text: &str) -> f64 {
+    text.trim().parse().unwrap_or(0.0)
+}
+
+fn ven_to_bool(text: &str) -> bool {
+    text.trim() == "true"
+}
+
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        let span = max - min + 1;
+        let low = value.rem_euclid(span);
+        return if low > max { low - span } else { low };
+    }
+    value
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => (a as u64).wrapping_mul(b as u64) as i128,
+    }
+}
+
+// A negative `y` gives `1 / x ** -y` truncated toward zero. A power too large
+// for i128 comes back as a stand-in outside every integer type, with its sign
+// and low 64 bits, which `ven_fit` handles as it would the power itself
+fn ven_pow(x: i128, y: i128) -> i128 {
+    match x {
+        _ if y == 0 => return 1,
+        0 | 1 => return x,
+        -1 => return if y % 2 == 0 { 1 } else { -1 },
+        _ if y < 0 => return 0,
+        _ => {}
+    }
+    if let Some(power) = if y <= u32::MAX as i128 { x.checked_pow(y as u32) } else { None } {
+        return power;
+    }
+    let (mut base, mut exponent, mut low) = (x as u64, y as u64, 1u64);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            low = low.wrapping_mul(base);
+        }
+        base = base.wrapping_mul(base);
+        exponent >>= 1;
+    }
+    if x < 0 && y % 2 == 1 { low as i128 - (1i128 << 65) } else { low as i128 + (1i128 << 64) }
+}
+
+// A later value only wins when it is smaller, or larger
+fn ven_min<T: PartialOrd + Copy>(values: &[T]) -> T {
+    values[1..].iter().fold(values[0], |min, &x| if x < min { x } else { min })
+}
+
+fn ven_max<T: PartialOrd + Copy>(values: &[T]) -> T {
+    values[1..].iter().fold(values[0], |max, &x| if x > max { x } else { max })
+}
+
+fn ven_div_zero(name: &str, line: u32) -> ! {
+    eprintln!("runtime error: division of '{}' by zero on line {}", name, line);
+    std::process::exit(1);
+}
+
+fn ven_divisor(y: i128, name: &str, line: u32) -> i128 {
+    if y == 0 {
+        ven_div_zero(name, line);
+    }
+    y
+}
+
+fn ven_divisor_f(y: f64, name: &str, line: u32) -> f64 {
+    if y == 0.0 {
+        ven_div_zero(name, line);
+    }
+    y
+}
+
+// A negative power of zero divides by zero too
+fn ven_exponent(x: i128, y: i128, name: &str, line: u32) -> i128 {
+    if x == 0 && y < 0 {
+        ven_div_zero(name, line);
+    }
+    y
+}
+
+fn main() {
+    let mut angle: f64 = 0.0;
+    let mut growth: f64 = 0.0;
+    let mut side: f64 = 0.0;
+    let mut low: f64 = 0.0;
+    angle = 370.5;
+    angle = angle % ven_divisor_f(360.0, "angle", 3);
+    growth = 1.5;
+    growth = growth.powf(2.5);
+    side = f64::sqrt(2.0);
+    low = -2.5;
+    println!("angle={} growth={}", angle, growth);
+    println!("{} {} {} {}", f64::round(low), f64::floor(low), f64::ceil(low), f64::abs(low));
+    println!("{} {}", ven_min(&[side, 1.0]), ven_max(&[low, (3 as f64)]));
+    if (f64::round(side) == 1.0) {
+        println!("sqrt(2) rounds to 1");
+    }
+}
@@ -0,0 +1,2352 @@
+(module
+  (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_read" (func $fd_read (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+  (memory (export "memory") 1)
+  (global $angle (mut f64) (f64.const 0))
+  (global $growth (mut f64) (f64.const 0))
+  (global $side (mut f64) (f64.const 0))
+  (global $low (mut f64) (f64.const 0))
+  (data (i32.const 60) "-\0a ")
+  (data (i32.const 320) "NaN\00-inf\00")
+  (data (i32.const 3072) "angle\00")
+  (data (i32.const 3078) "angle=\00")
+  (data (i32.const 3085) " growth=\00")
+  (data (i32.const 3094) " \00")
+  (data (i32.const 3096) "sqrt(2) rounds to 1\00")
+  (data (i32.const 3116) "runtime error: division of '\00")
+  (data (i32.const 3145) "' by zero on line \00")
+  (func $write (param i32 i32 i32)
+    i32.const 0
+    local.get 1
+    i32.store
+    i32.const 4
+    local.get 2
+    i32.store
+    local.get 0
+    i32.const 0
+    i32.const 1
+    i32.const 8
+    call $fd_write
+    drop
+  )
+  (func $strlen (param i32) (result i32)
+    (local i32)
+    block
+      loop
+        local.get 0
+        local.get 1
+        i32.add
+        i32.load8_u
+        i32.eqz
+        br_if 1
+        local.get 1
+        i32.const 1
+        i32.add
+        local.set 1
+        br 0
+      end
+    end
+    local.get 1
+  )
+  (func $print_cstr (param i32 i32)
+    local.get 0
+    local.get 1
+    local.get 1
+    call $strlen
+    call $write
+  )
+  (func $print_digits (param i32 i64 i32)
+    (local i32)
+    i32.const 48
+    local.set 3
+    loop
+      local.get 3
+      i32.const 1
+      i32.sub
+      local.set 3
+      local.get 3
+      local.get 1
+      i64.const 10
+      i64.rem_u
+      i32.wrap_i64
+      i32.const 48
+      i32.add
+      i32.store8
+      local.get 1
+      i64.const 10
+      i64.div_u
+      local.set 1
+      local.get 2
+      i32.const 1
+      i32.sub
+      local.set 2
+      local.get 1
+      i64.eqz
+      i32.eqz
+      local.get 2
+      i32.const 0
+      i32.gt_s
+      i32.or
+      br_if 0
+    end
+    local.get 0
+    local.get 3
+    i32.const 48
+    local.get 3
+    i32.sub
+    call $write
+  )
+  (func $print_i64 (param i32 i64)
+    local.get 1
+    i64.const 0
+    i64.lt_s
+    if
+      local.get 0
+      i32.const 60
+      i32.const 1
+      call $write
+      i64.const 0
+      local.get 1
+      i64.sub
+      local.set 1
+    end
+    local.get 0
+    local.get 1
+    i32.const 1
+    call $print_digits
+  )
+  (func $read_line (param i32 i32) (result i32)
+    (local i32 i32)
+    block
+      loop
+        local.get 0
+        local.get 2
+        local.get 1
+        local.get 2
+        local.get 1
+        i32.lt_s
+        select
+        i32.add
+        local.set 3
+        i32.const 0
+        local.get 3
+        i32.store
+        i32.const 4
+        i32.const 1
+        i32.store
+        i32.const 0
+        i32.const 0
+        i32.const 1
+        i32.const 8
+        call $fd_read
+        br_if 1
+        i32.const 8
+        i32.load
+        i32.eqz
+        br_if 1
+        local.get 3
+        i32.load8_u
+        i32.const 10
+        i32.eq
+        br_if 1
+        local.get 2
+        local.get 1
+        i32.lt_s
+        if
+          local.get 2
+          i32.const 1
+          i32.add
+          local.set 2
+        end
+        br 0
+      end
+    end
+    local.get 2
+    i32.const 0
+    i32.gt_s
+    if
+      local.get 0
+      local.get 2
+      i32.add
+      i32.const 1
+      i32.sub
+      i32.load8_u
+      i32.const 13
+      i32.eq
+      if
+        local.get 2
+        i32.const 1
+        i32.sub
+        local.set 2
+      end
+    end
+    local.get 0
+    local.get 2
+    i32.add
+    i32.const 0
+    i32.store8
+    local.get 2
+  )
+  (func $parse_int (param i32) (result i64)
+    (local i64 i32 i32)
+    i32.const 12
+    i32.const 0
+    i32.store
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        i32.const 32
+        i32.ne
+        br_if 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        br 0
+      end
+    end
+    local.get 0
+    i32.load8_u
+    i32.const 45
+    i32.eq
+    if
+      i32.const 1
+      local.set 2
+      local.get 0
+      i32.const 1
+      i32.add
+      local.set 0
+    end
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        i32.const 48
+        i32.sub
+        local.tee 3
+        i32.const 9
+        i32.gt_u
+        br_if 1
+        local.get 1
+        i64.const -1
+        local.get 3
+        i64.extend_i32_u
+        i64.sub
+        i64.const 10
+        i64.div_u
+        i64.gt_u
+        if
+          i64.const 0
+          return
+        end
+        local.get 1
+        i64.const 10
+        i64.mul
+        local.get 3
+        i64.extend_i32_u
+        i64.add
+        local.set 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        br 0
+      end
+    end
+    local.get 2
+    if
+      local.get 1
+      i64.const -9223372036854775808
+      i64.gt_u
+      if
+        i64.const 0
+        return
+      end
+      i64.const 0
+      local.get 1
+      i64.sub
+      return
+    end
+    i32.const 12
+    local.get 1
+    i64.const 0
+    i64.lt_s
+    i32.store
+    local.get 1
+  )
+  (func $parse_f64 (param i32) (result f64)
+    (local f64 i32 i32 f64)
+    f64.const 1.0
+    local.set 4
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        i32.const 32
+        i32.ne
+        br_if 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        br 0
+      end
+    end
+    local.get 0
+    i32.load8_u
+    i32.const 45
+    i32.eq
+    if
+      i32.const 1
+      local.set 2
+      local.get 0
+      i32.const 1
+      i32.add
+      local.set 0
+    end
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        i32.const 48
+        i32.sub
+        local.tee 3
+        i32.const 9
+        i32.gt_u
+        br_if 1
+        local.get 1
+        f64.const 10.0
+        f64.mul
+        local.get 3
+        f64.convert_i32_u
+        f64.add
+        local.set 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        br 0
+      end
+    end
+    local.get 0
+    i32.load8_u
+    i32.const 46
+    i32.eq
+    if
+      local.get 0
+      i32.const 1
+      i32.add
+      local.set 0
+      block
+        loop
+          local.get 0
+          i32.load8_u
+          i32.const 48
+          i32.sub
+          local.tee 3
+          i32.const 9
+          i32.gt_u
+          br_if 1
+          local.get 1
+          f64.const 10.0
+          f64.mul
+          local.get 3
+          f64.convert_i32_u
+          f64.add
+          local.set 1
+          local.get 4
+          f64.const 10.0
+          f64.mul
+          local.set 4
+          local.get 0
+          i32.const 1
+          i32.add
+          local.set 0
+          br 0
+        end
+      end
+    end
+    local.get 1
+    local.get 4
+    f64.div
+    local.set 1
+    local.get 1
+    f64.neg
+    local.get 1
+    local.get 2
+    select
+  )
+  (func $str_cmp (param i32 i32) (result i32)
+    (local i32 i32)
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        local.set 2
+        local.get 1
+        i32.load8_u
+        local.set 3
+        local.get 2
+        local.get 3
+        i32.ne
+        br_if 1
+        local.get 2
+        i32.eqz
+        br_if 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        local.get 1
+        i32.const 1
+        i32.add
+        local.set 1
+        br 0
+      end
+    end
+    local.get 2
+    local.get 3
+    i32.sub
+  )
+  (func $str_copy (param i32 i32 i32)
+    (local i32 i32)
+    local.get 0
+    local.set 4
+    block
+      loop
+        local.get 1
+        i32.load8_u
+        local.tee 3
+        i32.eqz
+        br_if 1
+        local.get 2
+        i32.const 1
+        i32.le_s
+        if
+          block
+            loop
+              local.get 0
+              local.get 4
+              i32.le_u
+              br_if 1
+              local.get 1
+              i32.load8_u
+              i32.const 192
+              i32.and
+              i32.const 128
+              i32.ne
+              br_if 1
+              local.get 0
+              i32.const 1
+              i32.sub
+              local.set 0
+              local.get 1
+              i32.const 1
+              i32.sub
+              local.set 1
+              br 0
+            end
+          end
+          br 2
+        end
+        local.get 0
+        local.get 3
+        i32.store8
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        local.get 1
+        i32.const 1
+        i32.add
+        local.set 1
+        local.get 2
+        i32.const 1
+        i32.sub
+        local.set 2
+        br 0
+      end
+    end
+    local.get 0
+    i32.const 0
+    i32.store8
+  )
+  (func $fit (param i64 i32 i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 2
+    i64.eqz
+    local.get 3
+    i64.const -1
+    i64.eq
+    i32.and
+    if
+      local.get 1
+      i32.eqz
+      local.get 0
+      i64.const 0
+      i64.lt_s
+      i32.and
+      if
+      end
+      local.get 0
+      return
+    end
+    local.get 1
+    local.get 0
+    i64.const 0
+    i64.lt_s
+    i32.and
+    if
+      local.get 0
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.and
+      local.set 6
+      local.get 6
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.sub
+      i64.const 1
+      i64.sub
+      local.get 6
+      local.get 6
+      local.get 3
+      i64.gt_s
+      select
+      return
+    end
+    local.get 0
+    local.get 2
+    i64.lt_s
+    if
+      local.get 0
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.and
+      local.set 6
+      local.get 6
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.sub
+      i64.const 1
+      i64.sub
+      local.get 6
+      local.get 6
+      local.get 3
+      i64.gt_s
+      select
+      return
+    end
+    local.get 0
+    local.get 3
+    i64.gt_s
+    if
+      local.get 0
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.and
+      local.set 6
+      local.get 6
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.sub
+      i64.const 1
+      i64.sub
+      local.get 6
+      local.get 6
+      local.get 3
+      i64.gt_s
+      select
+      return
+    end
+    local.get 0
+  )
+  (func $fit_float (param f64 f64 f64 i64 i64 i32 i32) (result i64)
+    local.get 0
+    local.get 0
+    f64.ne
+    if
+      i64.const 0
+      return
+    end
+    local.get 0
+    f64.trunc
+    local.set 0
+    local.get 0
+    local.get 1
+    f64.lt
+    if
+      local.get 3
+      return
+    end
+    local.get 0
+    local.get 2
+    f64.ge
+    if
+      local.get 4
+      return
+    end
+    local.get 0
+    f64.const 0.0
+    f64.lt
+    if
+      local.get 0
+      i64.trunc_f64_s
+      return
+    end
+    local.get 0
+    i64.trunc_f64_u
+  )
+  (func $add_i64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.add
+    local.set 4
+    local.get 0
+    local.get 4
+    i64.xor
+    local.get 1
+    local.get 4
+    i64.xor
+    i64.and
+    i64.const 0
+    i64.lt_s
+    if
+    end
+    local.get 4
+  )
+  (func $sub_i64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.sub
+    local.set 4
+    local.get 0
+    local.get 1
+    i64.xor
+    local.get 0
+    local.get 4
+    i64.xor
+    i64.and
+    i64.const 0
+    i64.lt_s
+    if
+    end
+    local.get 4
+  )
+  (func $mul_i64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.mul
+    local.set 4
+    local.get 0
+    i64.const -1
+    i64.eq
+    if
+      local.get 1
+      i64.const -9223372036854775808
+      i64.eq
+      i64.extend_i32_u
+      local.set 4
+    else
+      local.get 0
+      i64.eqz
+      if
+        i64.const 0
+        local.set 4
+      else
+        local.get 4
+        local.get 0
+        i64.div_s
+        local.get 1
+        i64.ne
+        i64.extend_i32_u
+        local.set 4
+      end
+    end
+    local.get 4
+    i64.eqz
+    i32.eqz
+    local.get 0
+    local.get 1
+    i64.mul
+    local.set 4
+    if
+    end
+    local.get 4
+  )
+  (func $div_i64 (param i64 i64 i32 i32) (result i64)
+    local.get 0
+    i64.const -9223372036854775808
+    i64.eq
+    local.get 1
+    i64.const -1
+    i64.eq
+    i32.and
+    if
+      i64.const -9223372036854775808
+      return
+    end
+    local.get 0
+    local.get 1
+    i64.div_s
+  )
+  (func $add_u64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.add
+    local.set 4
+    local.get 4
+    local.get 0
+    i64.lt_u
+    if
+    end
+    local.get 4
+  )
+  (func $sub_u64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.sub
+    local.set 4
+    local.get 0
+    local.get 1
+    i64.lt_u
+    if
+    end
+    local.get 4
+  )
+  (func $mul_u64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.mul
+    local.set 4
+    local.get 0
+    i64.eqz
+    if
+      i64.const 0
+      local.set 4
+    else
+      local.get 4
+      local.get 0
+      i64.div_u
+      local.get 1
+      i64.ne
+      i64.extend_i32_u
+      local.set 4
+    end
+    local.get 4
+    i64.eqz
+    i32.eqz
+    local.get 0
+    local.get 1
+    i64.mul
+    local.set 4
+    if
+    end
+    local.get 4
+  )
+  (func $compare (param i64 i32 i64 i32) (result i32)
+    local.get 1
+    i32.eqz
+    local.get 0
+    i64.const 0
+    i64.lt_s
+    i32.and
+    local.get 3
+    i32.eqz
+    local.get 2
+    i64.const 0
+    i64.lt_s
+    i32.and
+    i32.ne
+    if
+      i32.const -1
+      i32.const 1
+      local.get 1
+      i32.eqz
+      local.get 0
+      i64.const 0
+      i64.lt_s
+      i32.and
+      select
+      return
+    end
+    local.get 1
+    local.get 3
+    i32.or
+    if
+      local.get 0
+      local.get 2
+      i64.gt_u
+      local.get 0
+      local.get 2
+      i64.lt_u
+      i32.sub
+      return
+    end
+    local.get 0
+    local.get 2
+    i64.gt_s
+    local.get 0
+    local.get 2
+    i64.lt_s
+    i32.sub
+  )
+  (func $pow_int (param i64 i64 i32 i64 i64 i32 i32) (result i64)
+    (local i64 i64 i32 i64 i64)
+    local.get 1
+    i64.eqz
+    if
+      i64.const 1
+      return
+    end
+    local.get 0
+    i64.const 1
+    i64.le_u
+    if
+      local.get 0
+      return
+    end
+    local.get 2
+    if
+      local.get 0
+      local.set 8
+    else
+      local.get 0
+      i64.const -1
+      i64.eq
+      if
+        i64.const -1
+        i64.const 1
+        local.get 1
+        i64.const 1
+        i64.and
+        i32.wrap_i64
+        select
+        return
+      end
+      local.get 1
+      i64.const 0
+      i64.lt_s
+      if
+        i64.const 0
+        return
+      end
+      local.get 0
+      i64.const 0
+      i64.lt_s
+      local.get 1
+      i32.wrap_i64
+      i32.const 1
+      i32.and
+      i32.and
+      local.set 9
+      i64.const 0
+      local.get 0
+      i64.sub
+      local.get 0
+      local.get 0
+      i64.const 0
+      i64.lt_s
+      select
+      local.set 8
+    end
+    i64.const 1
+    local.set 7
+    i64.const 0
+    local.set 10
+    block
+      loop
+        local.get 7
+        local.get 8
+        i64.mul
+        local.set 11
+        local.get 11
+        local.get 8
+        i64.div_u
+        local.get 7
+        i64.ne
+        if
+          i64.const 1
+          local.set 7
+          local.get 0
+          local.set 8
+          local.get 1
+          local.set 10
+          block
+            loop
+              local.get 10
+              i64.eqz
+              br_if 1
+              local.get 10
+              i64.const 1
+              i64.and
+              i32.wrap_i64
+              if
+                local.get 7
+                local.get 8
+                i64.mul
+                local.set 7
+              end
+              local.get 8
+              local.get 8
+              i64.mul
+              local.set 8
+              local.get 10
+              i64.const 1
+              i64.shr_u
+              local.set 10
+              br 0
+            end
+          end
+          local.get 7
+          i32.const 0
+          local.get 3
+          local.get 4
+          local.get 5
+          local.get 6
+          call $fit
+          return
+        end
+        local.get 11
+        local.set 7
+        local.get 10
+        i64.const 1
+        i64.add
+        local.tee 10
+        local.get 1
+        i64.ne
+        br_if 0
+      end
+    end
+    local.get 9
+    if
+      local.get 7
+      i64.const -9223372036854775808
+      i64.gt_u
+      if
+        i64.const 1
+        local.set 7
+        local.get 0
+        local.set 8
+        local.get 1
+        local.set 10
+        block
+          loop
+            local.get 10
+            i64.eqz
+            br_if 1
+            local.get 10
+            i64.const 1
+            i64.and
+            i32.wrap_i64
+            if
+              local.get 7
+              local.get 8
+              i64.mul
+              local.set 7
+            end
+            local.get 8
+            local.get 8
+            i64.mul
+            local.set 8
+            local.get 10
+            i64.const 1
+            i64.shr_u
+            local.set 10
+            br 0
+          end
+        end
+        local.get 7
+        i32.const 0
+        local.get 3
+        local.get 4
+        local.get 5
+        local.get 6
+        call $fit
+        return
+      end
+      i64.const 0
+      local.get 7
+      i64.sub
+      i32.const 0
+      local.get 3
+      local.get 4
+      local.get 5
+      local.get 6
+      call $fit
+      return
+    end
+    local.get 7
+    i32.const 1
+    local.get 3
+    local.get 4
+    local.get 5
+    local.get 6
+    call $fit
+  )
+  (func $abs_i64 (param i64 i32 i32) (result i64)
+    local.get 0
+    i64.const 0
+    i64.lt_s
+    if
+      i64.const 0
+      local.get 0
+      local.get 1
+      local.get 2
+      call $sub_i64
+      return
+    end
+    local.get 0
+  )
+  (func $min_i64 (param i64 i64) (result i64)
+    local.get 1
+    local.get 0
+    local.get 1
+    local.get 0
+    i64.lt_s
+    select
+  )
+  (func $max_i64 (param i64 i64) (result i64)
+    local.get 1
+    local.get 0
+    local.get 1
+    local.get 0
+    i64.gt_s
+    select
+  )
+  (func $min_f64 (param f64 f64) (result f64)
+    local.get 1
+    local.get 0
+    local.get 1
+    local.get 0
+    f64.lt
+    select
+  )
+  (func $max_f64 (param f64 f64) (result f64)
+    local.get 1
+    local.get 0
+    local.get 1
+    local.get 0
+    f64.gt
+    select
+  )
+  (func $round_f64 (param f64) (result f64)
+    (local f64)
+    local.get 0
+    f64.trunc
+    local.set 1
+    local.get 0
+    local.get 1
+    f64.sub
+    f64.abs
+    f64.const 0.5
+    f64.ge
+    if
+      local.get 1
+      f64.const 1.0
+      local.get 0
+      f64.copysign
+      f64.add
+      local.set 1
+    end
+    local.get 1
+    local.get 0
+    f64.copysign
+  )
+  (func $fmod (param f64 f64) (result f64)
+    (local f64 f64 f64)
+    local.get 0
+    local.get 0
+    f64.sub
+    f64.const 0.0
+    f64.ne
+    local.get 1
+    local.get 1
+    f64.ne
+    i32.or
+    local.get 1
+    f64.const 0.0
+    f64.eq
+    i32.or
+    if
+      f64.const nan
+      return
+    end
+    local.get 0
+    f64.abs
+    local.set 2
+    local.get 1
+    f64.abs
+    local.set 3
+    block
+      loop
+        local.get 2
+        local.get 3
+        f64.lt
+        br_if 1
+        local.get 3
+        local.set 4
+        block
+          loop
+            local.get 4
+            f64.const 2.0
+            f64.mul
+            local.get 2
+            f64.gt
+            br_if 1
+            local.get 4
+            f64.const 2.0
+            f64.mul
+            local.set 4
+            br 0
+          end
+        end
+        local.get 2
+        local.get 4
+        f64.sub
+        local.set 2
+        br 0
+      end
+    end
+    local.get 2
+    local.get 0
+    f64.copysign
+  )
+  (func $ln (param f64) (result f64)
+    (local i64 f64 f64 f64 f64 f64 f64)
+    i64.const 0
+    local.set 1
+    local.get 0
+    f64.const 2.2250738585072014e-308
+    f64.lt
+    if
+      local.get 0
+      f64.const 1.8014398509481984e16
+      f64.mul
+      local.set 0
+      i64.const -54
+      local.set 1
+    end
+    local.get 1
+    local.get 0
+    i64.reinterpret_f64
+    i64.const 52
+    i64.shr_u
+    i64.const 1023
+    i64.sub
+    i64.add
+    local.set 1
+    local.get 0
+    i64.reinterpret_f64
+    i64.const 4503599627370495
+    i64.and
+    i64.const 4607182418800017408
+    i64.or
+    f64.reinterpret_i64
+    local.set 2
+    local.get 2
+    f64.const 1.4142135623730951
+    f64.gt
+    if
+      local.get 2
+      f64.const 0.5
+      f64.mul
+      local.set 2
+      local.get 1
+      i64.const 1
+      i64.add
+      local.set 1
+    end
+    local.get 2
+    f64.const 1.0
+    f64.sub
+    local.get 2
+    f64.const 1.0
+    f64.add
+    f64.div
+    local.tee 3
+    local.get 3
+    f64.mul
+    local.set 4
+    local.get 3
+    local.set 5
+    f64.const 0.0
+    local.set 6
+    f64.const 1.0
+    local.set 7
+    loop
+      local.get 6
+      local.get 5
+      local.get 7
+      f64.div
+      f64.add
+      local.set 6
+      local.get 5
+      local.get 4
+      f64.mul
+      local.set 5
+      local.get 7
+      f64.const 2.0
+      f64.add
+      local.tee 7
+      f64.const 41.0
+      f64.lt
+      br_if 0
+    end
+    local.get 1
+    f64.convert_i64_s
+    f64.const 0.6931471803691238
+    f64.mul
+    local.get 1
+    f64.convert_i64_s
+    f64.const 1.9082149292705877e-10
+    f64.mul
+    local.get 6
+    f64.const 2.0
+    f64.mul
+    f64.add
+    f64.add
+  )
+  (func $exp (param f64) (result f64)
+    (local f64 f64 f64 f64 f64 i64)
+    local.get 0
+    local.get 0
+    f64.ne
+    if
+      local.get 0
+      return
+    end
+    local.get 0
+    f64.const 709.782712893384
+    f64.gt
+    if
+      f64.const inf
+      return
+    end
+    local.get 0
+    f64.const -745.1332191019412
+    f64.lt
+    if
+      f64.const 0.0
+      return
+    end
+    local.get 0
+    f64.const 0.6931471805599453
+    f64.div
+    f64.nearest
+    local.set 1
+    local.get 0
+    local.get 1
+    f64.const 0.6931471803691238
+    f64.mul
+    f64.sub
+    local.get 1
+    f64.const 1.9082149292705877e-10
+    f64.mul
+    f64.sub
+    local.set 2
+    f64.const 1.0
+    local.set 3
+    f64.const 1.0
+    local.set 4
+    f64.const 1.0
+    local.set 5
+    loop
+      local.get 3
+      local.get 2
+      f64.mul
+      local.get 5
+      f64.div
+      local.tee 3
+      local.get 4
+      f64.add
+      local.set 4
+      local.get 5
+      f64.const 1.0
+      f64.add
+      local.tee 5
+      f64.const 20.0
+      f64.lt
+      br_if 0
+    end
+    local.get 1
+    f64.const 0.5
+    f64.mul
+    f64.trunc
+    i64.trunc_f64_s
+    local.set 6
+    local.get 4
+    local.get 6
+    i64.const 1023
+    i64.add
+    i64.const 52
+    i64.shl
+    f64.reinterpret_i64
+    f64.mul
+    local.get 1
+    i64.trunc_f64_s
+    local.get 6
+    i64.sub
+    i64.const 1023
+    i64.add
+    i64.const 52
+    i64.shl
+    f64.reinterpret_i64
+    f64.mul
+  )
+  (func $fpow (param f64 f64) (result f64)
+    (local f64 f64 i64)
+    local.get 1
+    f64.const 0.0
+    f64.eq
+    local.get 0
+    f64.const 1.0
+    f64.eq
+    i32.or
+    if
+      f64.const 1.0
+      return
+    end
+    local.get 0
+    local.get 0
+    f64.ne
+    local.get 1
+    local.get 1
+    f64.ne
+    i32.or
+    if
+      local.get 0
+      local.get 1
+      f64.add
+      return
+    end
+    local.get 1
+    f64.abs
+    f64.const inf
+    f64.eq
+    if
+      local.get 0
+      f64.abs
+      f64.const 1.0
+      f64.eq
+      if
+        f64.const 1.0
+        return
+      end
+      f64.const inf
+      f64.const 0.0
+      local.get 0
+      f64.abs
+      f64.const 1.0
+      f64.gt
+      local.get 1
+      f64.const 0.0
+      f64.gt
+      i32.eq
+      select
+      return
+    end
+    local.get 1
+    f64.abs
+    f64.const 9007199254740992.0
+    f64.ge
+    if
+      local.get 0
+      f64.abs
+      local.set 0
+    else
+      local.get 1
+      f64.trunc
+      local.get 1
+      f64.eq
+      if
+        local.get 1
+        f64.abs
+        i64.trunc_f64_s
+        local.set 4
+        f64.const 1.0
+        local.set 2
+        local.get 0
+        local.set 3
+        loop
+          local.get 4
+          i64.const 1
+          i64.and
+          i32.wrap_i64
+          if
+            local.get 2
+            local.get 3
+            f64.mul
+            local.set 2
+          end
+          local.get 3
+          local.get 3
+          f64.mul
+          local.set 3
+          local.get 4
+          i64.const 1
+          i64.shr_u
+          local.tee 4
+          i64.eqz
+          i32.eqz
+          br_if 0
+        end
+        local.get 1
+        f64.const 0.0
+        f64.lt
+        if
+          local.get 2
+          f64.abs
+          f64.const inf
+          f64.eq
+          local.get 2
+          f64.abs
+          f64.const 2.2250738585072014e-308
+          f64.lt
+          local.get 2
+          f64.const 0.0
+          f64.ne
+          i32.and
+          i32.or
+          if
+            local.get 1
+            local.get 0
+            f64.abs
+            call $ln
+            f64.mul
+            call $exp
+            local.get 2
+            f64.copysign
+            return
+          end
+          f64.const 1.0
+          local.get 2
+          f64.div
+          return
+        end
+        local.get 2
+        return
+      end
+    end
+    local.get 0
+    f64.const -inf
+    f64.eq
+    if
+      f64.const inf
+      local.set 0
+    end
+    local.get 0
+    f64.const 0.0
+    f64.lt
+    if
+      f64.const nan
+      return
+    end
+    local.get 0
+    f64.const 0.0
+    f64.eq
+    local.get 0
+    f64.const inf
+    f64.eq
+    i32.or
+    if
+      f64.const inf
+      f64.const 0.0
+      local.get 0
+      f64.const inf
+      f64.eq
+      local.get 1
+      f64.const 0.0
+      f64.gt
+      i32.eq
+      select
+      return
+    end
+    local.get 1
+    local.get 0
+    call $ln
+    f64.mul
+    call $exp
+  )
+  (func $big_set (param i32 i64)
+    (local i32)
+    block
+      loop
+        local.get 2
+        i32.const 288
+        i32.ge_u
+        br_if 1
+        local.get 0
+        local.get 2
+        i32.add
+        i32.const 0
+        i32.store
+        local.get 2
+        i32.const 4
+        i32.add
+        local.set 2
+        br 0
+      end
+    end
+    local.get 0
+    local.get 1
+    i64.store
+  )
+  (func $big_mul (param i32 i64)
+    (local i32 i64 i64)
+    block
+      loop
+        local.get 2
+        i32.const 288
+        i32.ge_u
+        br_if 1
+        local.get 0
+        local.get 2
+        i32.add
+        i64.load32_u
+        local.get 1
+        i64.mul
+        local.get 3
+        i64.add
+        local.set 4
+        local.get 0
+        local.get 2
+        i32.add
+        local.get 4
+        i64.store32
+        local.get 4
+        i64.const 32
+        i64.shr_u
+        local.set 3
+        local.get 2
+        i32.const 4
+        i32.add
+        local.set 2
+        br 0
+      end
+    end
+  )
+  (func $big_shl (param i32 i32)
+    block
+      loop
+        local.get 1
+        i32.const 16
+        i32.lt_s
+        br_if 1
+        local.get 0
+        i64.const 65536
+        call $big_mul
+        local.get 1
+        i32.const 16
+        i32.sub
+        local.set 1
+        br 0
+      end
+    end
+    local.get 0
+    i64.const 1
+    local.get 1
+    i64.extend_i32_u
+    i64.shl
+    call $big_mul
+  )
+  (func $big_pow10 (param i32 i32)
+    block
+      loop
+        local.get 1
+        i32.const 0
+        i32.le_s
+        br_if 1
+        local.get 0
+        i64.const 10
+        call $big_mul
+        local.get 1
+        i32.const 1
+        i32.sub
+        local.set 1
+        br 0
+      end
+    end
+  )
+  (func $big_div (param i32 i64) (result i64)
+    (local i32 i64 i64)
+    i32.const 288
+    local.set 2
+    block
+      loop
+        local.get 2
+        i32.eqz
+        br_if 1
+        local.get 2
+        i32.const 4
+        i32.sub
+        local.set 2
+        local.get 3
+        i64.const 32
+        i64.shl
+        local.get 0
+        local.get 2
+        i32.add
+        i64.load32_u
+        i64.or
+        local.set 4
+        local.get 0
+        local.get 2
+        i32.add
+        local.get 4
+        local.get 1
+        i64.div_u
+        i64.store32
+        local.get 4
+        local.get 1
+        i64.rem_u
+        local.set 3
+        br 0
+      end
+    end
+    local.get 3
+  )
+  (func $big_bits (param i32) (result i32)
+    (local i32 i32)
+    i32.const 288
+    local.set 1
+    block
+      loop
+        local.get 1
+        i32.eqz
+        br_if 1
+        local.get 1
+        i32.const 4
+        i32.sub
+        local.set 1
+        local.get 0
+        local.get 1
+        i32.add
+        i32.load
+        local.tee 2
+        if
+          local.get 1
+          i32.const 8
+          i32.mul
+          i32.const 32
+          i32.add
+          local.get 2
+          i32.clz
+          i32.sub
+          return
+        end
+        br 0
+      end
+    end
+    i32.const 0
+  )
+  (func $big_cmp (param i32 i32) (result i32)
+    (local i32 i32 i32)
+    i32.const 284
+    local.set 2
+    block
+      loop
+        local.get 0
+        local.get 2
+        i32.add
+        i32.load
+        local.set 3
+        local.get 1
+        local.get 2
+        i32.add
+        i32.load
+        local.set 4
+        local.get 3
+        local.get 4
+        i32.ne
+        if
+          i32.const 1
+          i32.const -1
+          local.get 3
+          local.get 4
+          i32.gt_u
+          select
+          return
+        end
+        local.get 2
+        i32.eqz
+        br_if 1
+        local.get 2
+        i32.const 4
+        i32.sub
+        local.set 2
+        br 0
+      end
+    end
+    i32.const 0
+  )
+  (func $big_add (param i32 i32 i32)
+    (local i32 i64 i64)
+    block
+      loop
+        local.get 3
+        i32.const 288
+        i32.ge_u
+        br_if 1
+        local.get 1
+        local.get 3
+        i32.add
+        i64.load32_u
+        local.get 2
+        local.get 3
+        i32.add
+        i64.load32_u
+        i64.add
+        local.get 5
+        i64.add
+        local.set 4
+        local.get 0
+        local.get 3
+        i32.add
+        local.get 4
+        i64.store32
+        local.get 4
+        i64.const 32
+        i64.shr_u
+        local.set 5
+        local.get 3
+        i32.const 4
+        i32.add
+        local.set 3
+        br 0
+      end
+    end
+  )
+  (func $big_sub (param i32 i32)
+    (local i32 i64 i64)
+    block
+      loop
+        local.get 2
+        i32.const 288
+        i32.ge_u
+        br_if 1
+        local.get 0
+        local.get 2
+        i32.add
+        i64.load32_u
+        local.get 1
+        local.get 2
+        i32.add
+        i64.load32_u
+        i64.sub
+        local.get 4
+        i64.sub
+        local.set 3
+        local.get 0
+        local.get 2
+        i32.add
+        local.get 3
+        i64.store32
+        local.get 3
+        i64.const 63
+        i64.shr_u
+        local.set 4
+        local.get 2
+        i32.const 4
+        i32.add
+        local.set 2
+        br 0
+      end
+    end
+  )
+  (func $float_text (param f64) (result i32)
+    (local i32 i64 i64 i32 i32 i32 i32 i32 i32 i32 i32 i32)
+    local.get 0
+    local.get 0
+    f64.ne
+    if
+      i32.const 320
+      return
+    end
+    local.get 0
+    local.get 0
+    f64.sub
+    f64.const 0.0
+    f64.ne
+    if
+      i32.const 325
+      i32.const 324
+      local.get 0
+      f64.const 0.0
+      f64.gt
+      select
+      return
+    end
+    i32.const 336
+    local.set 1
+    local.get 0
+    i64.reinterpret_f64
+    local.tee 2
+    i64.const 0
+    i64.lt_s
+    if
+      local.get 1
+      i32.const 45
+      i32.store8
+      local.get 1
+      i32.const 1
+      i32.add
+      local.set 1
+    end
+    local.get 0
+    f64.const 0.0
+    f64.eq
+    if
+      local.get 1
+      i32.const 48
+      i32.store8
+      local.get 1
+      i32.const 1
+      i32.add
+      local.set 1
+      local.get 1
+      i32.const 0
+      i32.store8
+      i32.const 336
+      return
+    end
+    local.get 2
+    i64.const 4503599627370495
+    i64.and
+    local.set 3
+    local.get 2
+    i64.const 52
+    i64.shr_u
+    i32.wrap_i64
+    i32.const 2047
+    i32.and
+    local.tee 4
+    if
+      local.get 3
+      i64.const 4503599627370496
+      i64.or
+      local.set 3
+      local.get 4
+      i32.const 1075
+      i32.sub
+      local.set 4
+    else
+      i32.const -1074
+      local.set 4
+    end
+    local.get 3
+    i32.wrap_i64
+    i32.const 1
+    i32.and
+    i32.eqz
+    local.set 6
+    local.get 3
+    i64.const 4503599627370496
+    i64.eq
+    local.get 4
+    i32.const -1074
+    i32.ne
+    i32.and
+    local.set 11
+    i32.const 1024
+    local.get 3
+    i64.const 1
+    local.get 11
+    i64.extend_i32_u
+    i64.add
+    i64.shl
+    call $big_set
+    i32.const 1312
+    i64.const 2
+    local.get 11
+    i64.extend_i32_u
+    i64.shl
+    call $big_set
+    i32.const 1600
+    i64.const 1
+    local.get 11
+    i64.extend_i32_u
+    i64.shl
+    call $big_set
+    i32.const 1888
+    i64.const 1
+    call $big_set
+    local.get 4
+    i32.const 0
+    i32.ge_s
+    if
+      i32.const 1024
+      local.get 4
+      call $big_shl
+      i32.const 1600
+      local.get 4
+      call $big_shl
+      i32.const 1888
+      local.get 4
+      call $big_shl
+    else
+      i32.const 1312
+      i32.const 0
+      local.get 4
+      i32.sub
+      call $big_shl
+    end
+    local.get 4
+    i32.const 63
+    local.get 3
+    i64.clz
+    i32.wrap_i64
+    i32.sub
+    i32.add
+    f64.convert_i32_s
+    f64.const 0.3010299956639812
+    f64.mul
+    f64.const 1e-10
+    f64.sub
+    f64.ceil
+    i32.trunc_f64_s
+    local.set 5
+    local.get 5
+    i32.const 0
+    i32.ge_s
+    if
+      i32.const 1312
+      local.get 5
+      call $big_pow10
+    else
+      i32.const 1024
+      i32.const 0
+      local.get 5
+      i32.sub
+      call $big_pow10
+      i32.const 1600
+      i32.const 0
+      local.get 5
+      i32.sub
+      call $big_pow10
+      i32.const 1888
+      i32.const 0
+      local.get 5
+      i32.sub
+      call $big_pow10
+    end
+    i32.const 2176
+    i32.const 1024
+    i32.const 1600
+    call $big_add
+    i32.const 2176
+    i32.const 1312
+    call $big_cmp
+    local.get 6
+    i32.add
+    i32.const 0
+    i32.gt_s
+    if
+      local.get 5
+      i32.const 1
+      i32.add
+      local.set 5
+    else
+      i32.const 1024
+      i64.const 10
+      call $big_mul
+      i32.const 1600
+      i64.const 10
+      call $big_mul
+      i32.const 1888
+      i64.const 10
+      call $big_mul
+    end
+    i32.const 0
+    local.set 7
+    block
+      loop
+        i32.const 0
+        local.set 8
+        block
+          loop
+            i32.const 1024
+            i32.const 1312
+            call $big_cmp
+            i32.const 0
+            i32.lt_s
+            br_if 1
+            i32.const 1024
+            i32.const 1312
+            call $big_sub
+            local.get 8
+            i32.const 1
+            i32.add
+            local.set 8
+            br 0
+          end
+        end
+        i32.const 1024
+        i32.const 1888
+        call $big_cmp
+        local.get 6
+        i32.sub
+        i32.const 0
+        i32.lt_s
+        local.set 9
+        i32.const 2176
+        i32.const 1024
+        i32.const 1600
+        call $big_add
+        i32.const 2176
+        i32.const 1312
+        call $big_cmp
+        local.get 6
+        i32.add
+        i32.const 0
+        i32.gt_s
+        local.set 10
+        local.get 9
+        local.get 10
+        i32.or
+        br_if 1
+        i32.const 672
+        local.get 7
+        i32.add
+        local.get 8
+        i32.const 48
+        i32.add
+        i32.store8
+        local.get 7
+        i32.const 1
+        i32.add
+        local.set 7
+        i32.const 1024
+        i64.const 10
+        call $big_mul
+        i32.const 1600
+        i64.const 10
+        call $big_mul
+        i32.const 1888
+        i64.const 10
+        call $big_mul
+        br 0
+      end
+    end
+    local.get 9
+    local.get 10
+    i32.and
+    if
+      i32.const 2176
+      i32.const 1024
+      i32.const 1024
+      call $big_add
+      i32.const 2176
+      i32.const 1312
+      call $big_cmp
+      local.get 8
+      i32.const 1
+      i32.and
+      i32.add
+      i32.const 0
+      i32.gt_s
+      local.set 10
+    end
+    i32.const 672
+    local.get 7
+    i32.add
+    local.get 8
+    local.get 10
+    i32.add
+    i32.const 48
+    i32.add
+    i32.store8
+    local.get 7
+    i32.const 1
+    i32.add
+    local.set 7
+    local.get 5
+    i32.const 0
+    i32.le_s
+    if
+      local.get 1
+      i32.const 48
+      i32.store8
+      local.get 1
+      i32.const 1
+      i32.add
+      local.set 1
+      local.get 1
+      i32.const 46
+      i32.store8
+      local.get 1
+      i32.const 1
+      i32.add
+      local.set 1
+      local.get 5
+      local.set 12
+      block
+        loop
+          local.get 12
+          i32.const 0
+          i32.ge_s
+          br_if 1
+          local.get 1
+          i32.const 48
+          i32.store8
+          local.get 1
+          i32.const 1
+          i32.add
+          local.set 1
+          local.get 12
+          i32.const 1
+          i32.add
+          local.set 12
+          br 0
+        end
+      end
+    end
+    i32.const 0
+    local.set 12
+    block
+      loop
+        local.get 12
+        local.get 7
+        i32.ge_s
+        br_if 1
+        local.get 12
+        local.get 5
+        i32.eq
+        local.get 5
+        i32.const 0
+        i32.gt_s
+        i32.and
+        if
+          local.get 1
+          i32.const 46
+          i32.store8
+          local.get 1
+          i32.const 1
+          i32.add
+          local.set 1
+        end
+        local.get 1
+        i32.const 672
+        local.get 12
+        i32.add
+        i32.load8_u
+        i32.store8
+        local.get 1
+        i32.const 1
+        i32.add
+        local.set 1
+        local.get 12
+        i32.const 1
+        i32.add
+        local.set 12
+        br 0
+      end
+    end
+    block
+      loop
+        local.get 12
+        local.get 5
+        i32.ge_s
+        br_if 1
+        local.get 1
+        i32.const 48
+        i32.store8
+        local.get 1
+        i32.const 1
+        i32.add
+        local.set 1
+        local.get 12
+        i32.const 1
+        i32.add
+        local.set 12
+        br 0
+      end
+    end
+    local.get 1
+    i32.const 0
+    i32.store8
+    i32.const 336
+  )
+  (func $print_f64 (param i32 f64)
+    local.get 0
+    local.get 1
+    call $float_text
+    call $print_cstr
+  )
+  (func $div_zero (param i32 i64)
+    i32.const 2
+    i32.const 3116
+    i32.const 28
+    call $write
+    i32.const 2
+    local.get 0
+    call $print_cstr
+    i32.const 2
+    i32.const 3145
+    i32.const 18
+    call $write
+    i32.const 2
+    local.get 1
+    call $print_i64
+    i32.const 2
+    i32.const 61
+    i32.const 1
+    call $write
+    i32.const 1
+    call $proc_exit
+  )
+  (func $divisor (param i64 i32 i64) (result i64)
+    local.get 0
+    i64.eqz
+    if
+      local.get 1
+      local.get 2
+      call $div_zero
+    end
+    local.get 0
+  )
+  (func $divisor_f (param f64 i32 i64) (result f64)
+    local.get 0
+    f64.const 0.0
+    f64.eq
+    if
+      local.get 1
+      local.get 2
+      call $div_zero
+    end
+    local.get 0
+  )
+  (func $exponent (param i64 i64 i32 i64) (result i64)
+    local.get 1
+    i64.eqz
+    local.get 0
+    i64.const 0
+    i64.lt_s
+    i32.and
+    if
+      local.get 2
+      local.get 3
+      call $div_zero
+    end
+    local.get 0
+  )
+  (func $_start (export "_start")
+    f64.const 370.5
+    global.set $angle
+    global.get $angle
+    f64.const 360.0
+    i32.const 3072
+    i64.const 3
+    call $divisor_f
+    call $fmod
+    global.set $angle
+    f64.const 1.5
+    global.set $growth
+    global.get $growth
+    f64.const 2.5
+    call $fpow
+    global.set $growth
+    f64.const 2.0
+    f64.sqrt
+    global.set $side
+    f64.const -2.5
+    global.set $low
+    i32.const 1
+    i32.const 3078
+    i32.const 6
+    call $write
+    i32.const 1
+    global.get $angle
+    call $print_f64
+    i32.const 1
+    i32.const 3085
+    i32.const 8
+    call $write
+    i32.const 1
+    global.get $growth
+    call $print_f64
+    i32.const 1
+    i32.const 61
+    i32.const 1
+    call $write
+    i32.const 1
+    global.get $low
+    call $round_f64
+    call $print_f64
+    i32.const 1
+    i32.const 3094
+    i32.const 1
+    call $write
+    i32.const 1
+    global.get $low
+    f64.floor
+    call $print_f64
+    i32.const 1
+    i32.const 3094
+    i32.const 1
+    call $write
+    i32.const 1
+    global.get $low
+    f64.ceil
+    call $print_f64
+    i32.const 1
+    i32.const 3094
+    i32.const 1
+    call $write
+    i32.const 1
+    global.get $low
+    f64.abs
+    call $print_f64
+    i32.const 1
+    i32.const 61
+    i32.const 1
+    call $write
+    i32.const 1
+    global.get $side
+    f64.const 1.0
+    call $min_f64
+    call $print_f64
+    i32.const 1
+    i32.const 3094
+    i32.const 1
+    call $write
+    i32.const 1
+    global.get $low
+    i64.const 3
+    f64.convert_i64_s
+    call $max_f64
+    call $print_f64
+    i32.const 1
+    i32.const 61
+    i32.const 1
+    call $write
+    global.get $side
+    call $round_f64
+    f64.const 1.0
+    f64.eq
+    if
+      i32.const 1
+      i32.const 3096
+      i32.const 19
+      call $write
+      i32.const 1
+      i32.const 61
+      i32.const 1
+      call $write
+    end
+  )
+)
@@ -0,0 +1,998 @@
+// Generated by the Ven compiler
+
+.data
+newline:
+    .byte 10
+lit_true:
+    .ascii "true"
+lit_false:
+    .ascii "false"
+lit_undefined:
+    .ascii "[undefined]"
+lit_space:
+    .ascii " "
+lit_convert:
+    .ascii "runtime error: cannot convert "
+    .byte 34
+lit_convert_end:
+    .byte 34
+    .ascii " to i on line "
+str_0:
+    .ascii " -42 "
+    .byte 0
+str_1:
+    .ascii "200"
+    .byte 0
+str_2:
+    .ascii "small"
+    .byte 0
+str_3:
+    .ascii "u8"
+    .byte 0
+str_4:
+    .ascii "true"
+    .byte 0
+str_5:
+    .ascii "false"
+    .byte 0
+str_6:
+    .ascii " and "
+    .byte 0
+str_7:
+    .ascii "tea"
+    .byte 0
+str_8:
+    .ascii "["
+    .byte 0
+str_9:
+    .ascii "] ["
+    .byte 0
+str_10:
+    .ascii "]"
+    .byte 0
+
+.bss
+    .balign 8
+num_buf:
+    .skip 32
+    .balign 8
+line_buf:
+    .skip 256
+    .balign 8
+var_line:
+    .skip 256
+    .balign 8
+var_n:
+    .skip 8
+    .balign 8
+var_ready:
+    .skip 8
+    .balign 8
+var_label:
+    .skip 256
+    .balign 8
+var_small:
+    .skip 8
+    .balign 8
+var_item:
+    .skip 256
+    .balign 8
+tmp_0:
+    .skip 8
+    .balign 8
+text_0:
+    .skip 256
+    .balign 8
+text_1:
+    .skip 256
+    .balign 8
+text_2:
+    .skip 256
+    .balign 8
+ven_conv_text:
+    .skip 8
+    .balign 8
+ven_pad_text:
+    .skip 8
+    .balign 8
+ven_pad_fd:
+    .skip 8
+    .balign 8
+ven_pad_left:
+    .skip 8
+    .balign 8
+ven_pad_right:
+    .skip 8
+    .balign 8
+ven_line:
+    .skip 8
+
+.text
+.global _start
+_start:
+    // declare line
+    adrp x0, var_line
+    add x0, x0, :lo12:var_line
+    adrp x1, str_0
+    add x1, x1, :lo12:str_0
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    // declare n
+    mov x0, #3
+    adrp x9, ven_line
+    str x0, [x9, :lo12:ven_line]
+    adrp x0, var_line
+    add x0, x0, :lo12:var_line
+    str x30, [sp, #-16]!
+    bl ven_convert_int
+    ldr x30, [sp], #16
+    adrp x9, var_n
+    str x0, [x9, :lo12:var_n]
+    // declare ready
+    mov x0, #1
+    adrp x9, var_ready
+    str x0, [x9, :lo12:var_ready]
+    // declare label
+    adrp x9, var_n
+    ldr x0, [x9, :lo12:var_n]
+    mov x1, #0
+    str x30, [sp, #-16]!
+    bl ven_int_text
+    ldr x30, [sp], #16
+    mov x1, x0
+    adrp x0, text_0
+    add x0, x0, :lo12:text_0
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    adrp x0, var_label
+    add x0, x0, :lo12:var_label
+    adrp x1, text_0
+    add x1, x1, :lo12:text_0
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    // declare small
+    mov x0, #6
+    adrp x9, ven_line
+    str x0, [x9, :lo12:ven_line]
+    adrp x0, str_1
+    add x0, x0, :lo12:str_1
+    str x30, [sp, #-16]!
+    bl ven_convert_int
+    ldr x30, [sp], #16
+    mov x1, #0
+    mov x2, #0
+    mov x3, #255
+    adrp x4, str_2
+    add x4, x4, :lo12:str_2
+    adrp x5, str_3
+    add x5, x5, :lo12:str_3
+    str x30, [sp, #-16]!
+    bl ven_fit
+    ldr x30, [sp], #16
+    adrp x9, var_small
+    str x0, [x9, :lo12:var_small]
+    // math on small
+    adrp x9, var_ready
+    ldr x0, [x9, :lo12:var_ready]
+    mov x1, #0
+    mov x2, #0
+    mov x3, #255
+    adrp x4, str_2
+    add x4, x4, :lo12:str_2
+    adrp x5, str_3
+    add x5, x5, :lo12:str_3
+    str x30, [sp, #-16]!
+    bl ven_fit
+    ldr x30, [sp], #16
+    mov x1, x0
+    adrp x9, var_small
+    ldr x0, [x9, :lo12:var_small]
+    add x0, x0, x1
+    mov x1, #0
+    mov x2, #0
+    mov x3, #255
+    adrp x4, str_2
+    add x4, x4, :lo12:str_2
+    adrp x5, str_3
+    add x5, x5, :lo12:str_3
+    str x30, [sp, #-16]!
+    bl ven_fit
+    ldr x30, [sp], #16
+    adrp x9, var_small
+    str x0, [x9, :lo12:var_small]
+    // if
+    mov x1, #-42
+    adrp x9, tmp_0
+    str x1, [x9, :lo12:tmp_0]
+    mov x0, #8
+    adrp x9, ven_line
+    str x0, [x9, :lo12:ven_line]
+    adrp x0, var_label
+    add x0, x0, :lo12:var_label
+    str x30, [sp, #-16]!
+    bl ven_convert_int
+    ldr x30, [sp], #16
+    adrp x9, tmp_0
+    ldr x1, [x9, :lo12:tmp_0]
+    cmp x0, x1
+    b.ne L1_endif
+    adrp x9, var_ready
+    ldr x0, [x9, :lo12:var_ready]
+    cmp x0, #0
+    b.eq L2_false
+    adrp x0, str_4
+    add x0, x0, :lo12:str_4
+    b L3_end
+L2_false:
+    adrp x0, str_5
+    add x0, x0, :lo12:str_5
+L3_end:
+    mov x1, x0
+    adrp x0, text_1
+    add x0, x0, :lo12:text_1
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    adrp x0, text_1
+    add x0, x0, :lo12:text_1
+    adrp x1, str_4
+    add x1, x1, :lo12:str_4
+    str x30, [sp, #-16]!
+    bl ven_str_cmp
+    ldr x30, [sp], #16
+    cmp x0, #0
+    b.ne L1_endif
+    // print to fd 1
+    adrp x9, var_small
+    ldr x0, [x9, :lo12:var_small]
+    mov x1, #0
+    str x30, [sp, #-16]!
+    bl ven_int_text
+    ldr x30, [sp], #16
+    mov x1, x0
+    adrp x0, text_2
+    add x0, x0, :lo12:text_2
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    adrp x0, text_2
+    add x0, x0, :lo12:text_2
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_cstr
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, str_6
+    add x1, x1, :lo12:str_6
+    mov x2, #5
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_ready
+    ldr x0, [x9, :lo12:var_ready]
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+L1_endif:
+    // declare item
+    adrp x0, var_item
+    add x0, x0, :lo12:var_item
+    adrp x1, str_7
+    add x1, x1, :lo12:str_7
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    // print to fd 1
+    mov x0, #1
+    adrp x1, str_8
+    add x1, x1, :lo12:str_8
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_n
+    ldr x0, [x9, :lo12:var_n]
+    mov x1, #0
+    str x30, [sp, #-16]!
+    bl ven_int_text
+    ldr x30, [sp], #16
+    mov x1, #1
+    mov x2, #5
+    mov x3, #1
+    str x30, [sp, #-16]!
+    bl ven_pad
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, str_9
+    add x1, x1, :lo12:str_9
+    mov x2, #3
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x0, var_item
+    add x0, x0, :lo12:var_item
+    mov x1, #1
+    mov x2, #6
+    mov x3, #0
+    str x30, [sp, #-16]!
+    bl ven_pad
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, str_9
+    add x1, x1, :lo12:str_9
+    mov x2, #3
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_small
+    ldr x0, [x9, :lo12:var_small]
+    mov x1, #0
+    str x30, [sp, #-16]!
+    bl ven_int_text
+    ldr x30, [sp], #16
+    mov x1, #1
+    mov x2, #4
+    mov x3, #0
+    str x30, [sp, #-16]!
+    bl ven_pad
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, str_9
+    add x1, x1, :lo12:str_9
+    mov x2, #3
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_ready
+    ldr x0, [x9, :lo12:var_ready]
+    cmp x0, #0
+    b.eq L4_false
+    adrp x0, str_4
+    add x0, x0, :lo12:str_4
+    b L5_end
+L4_false:
+    adrp x0, str_5
+    add x0, x0, :lo12:str_5
+L5_end:
+    mov x1, #1
+    mov x2, #6
+    mov x3, #0
+    str x30, [sp, #-16]!
+    bl ven_pad
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, str_10
+    add x1, x1, :lo12:str_10
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // print to fd 1
+    adrp x0, var_label
+    add x0, x0, :lo12:var_label
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_cstr
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // exit(0)
+    mov x0, #0
+    mov x8, #93   // syscall: Exit
+    svc #0
+    // ---- runtime ----
+ven_print_int:
+    adrp x2, num_buf
+    add x2, x2, :lo12:num_buf
+    add x2, x2, #32
+    mov x3, #0
+    cmp x0, #0
+    b.lt ven_print_int_digits
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    mov x3, #1
+ven_print_int_digits:
+    mov x4, x0
+    mov x5, #10
+    sdiv x9, x4, x5
+    msub x4, x9, x5, x4
+    mov x5, #48
+    sub x5, x5, x4
+    sub x2, x2, #1
+    strb w5, [x2]
+    mov x5, #10
+    sdiv x0, x0, x5
+    cmp x0, #0
+    b.ne ven_print_int_digits
+    cmp x3, #0
+    b.ne ven_print_int_write
+    sub x2, x2, #1
+    mov x5, #45
+    strb w5, [x2]
+ven_print_int_write:
+    adrp x4, num_buf
+    add x4, x4, :lo12:num_buf
+    add x4, x4, #32
+    sub x4, x4, x2
+    mov x0, x1
+    mov x1, x2
+    mov x2, x4
+    mov x8, #64   // syscall: Write
+    svc #0
+    ret
+ven_print_uint:
+    adrp x2, num_buf
+    add x2, x2, :lo12:num_buf
+    add x2, x2, #32
+ven_print_uint_digits:
+    mov x3, x0
+    mov x5, #10
+    udiv x3, x3, x5
+    mov x4, x3
+    mul x4, x4, x5
+    mov x5, x0
+    sub x5, x5, x4
+    add x5, x5, #48
+    sub x2, x2, #1
+    strb w5, [x2]
+    mov x0, x3
+    cmp x0, #0
+    b.ne ven_print_uint_digits
+    b ven_print_int_write
+ven_print_cstr:
+    mov x2, x0
+ven_print_cstr_len:
+    ldrb w3, [x2]
+    cmp x3, #0
+    b.eq ven_print_cstr_write
+    add x2, x2, #1
+    b ven_print_cstr_len
+ven_print_cstr_write:
+    sub x2, x2, x0
+    cmp x2, #0
+    b.eq ven_print_cstr_done
+    mov x3, x0
+    mov x0, x1
+    mov x1, x3
+    mov x8, #64   // syscall: Write
+    svc #0
+ven_print_cstr_done:
+    ret
+ven_read_line:
+    mov x4, x0
+    mov x5, x0
+    add x5, x5, x1
+ven_read_line_next:
+    mov x0, #0
+    mov x1, x4
+    mov x2, #1
+    mov x8, #63   // syscall: Read
+    svc #0
+    cmp x0, #1
+    b.lt ven_read_line_done
+    ldrb w3, [x4]
+    cmp x3, #10
+    b.eq ven_read_line_done
+    cmp x3, #13
+    b.eq ven_read_line_next
+    cmp x4, x5
+    b.ge ven_read_line_next
+    add x4, x4, #1
+    b ven_read_line_next
+ven_read_line_done:
+    mov x3, #0
+    strb w3, [x4]
+    ret
+ven_parse_int:
+    mov x1, x0
+    mov x0, #0
+    mov x2, #0
+ven_parse_int_space:
+    ldrb w3, [x1]
+    cmp x3, #32
+    b.ne ven_parse_int_sign
+    add x1, x1, #1
+    b ven_parse_int_space
+ven_parse_int_sign:
+    cmp x3, #45
+    b.ne ven_parse_int_plus
+    mov x2, #1
+    add x1, x1, #1
+    b ven_parse_int_digit
+ven_parse_int_plus:
+    cmp x3, #43
+    b.ne ven_parse_int_digit
+    add x1, x1, #1
+ven_parse_int_digit:
+    ldrb w3, [x1]
+    cmp x3, #48
+    b.lt ven_parse_int_end
+    cmp x3, #57
+    b.gt ven_parse_int_end
+    sub x3, x3, #48
+    mov x4, #-1
+    sub x4, x4, x3
+    mov x5, #10
+    udiv x4, x4, x5
+    cmp x0, x4
+    b.hi ven_parse_int_range
+    mov x4, #10
+    mul x0, x0, x4
+    add x0, x0, x3
+    add x1, x1, #1
+    b ven_parse_int_digit
+ven_parse_int_end:
+    mov x1, #0
+    cmp x2, #0
+    b.eq ven_parse_int_positive
+    movz x4, #0
+    movk x4, #32768, lsl #48
+    cmp x0, x4
+    b.hi ven_parse_int_range
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    ret
+ven_parse_int_positive:
+    cmp x0, #0
+    b.ge ven_parse_int_done
+    mov x1, #1
+ven_parse_int_done:
+    ret
+ven_parse_int_range:
+    mov x0, #0
+    mov x1, #0
+    ret
+ven_compare:
+    cmp x2, #0
+    b.ne ven_compare_b
+    cmp x0, #0
+    b.lt ven_compare_negative
+ven_compare_b:
+    cmp x3, #0
+    b.ne ven_compare_unsigned
+    cmp x1, #0
+    b.lt ven_compare_greater
+ven_compare_unsigned:
+    cmp x0, x1
+    b.lo ven_compare_less
+    b.hi ven_compare_greater
+    mov x0, #0
+    ret
+ven_compare_negative:
+    cmp x3, #0
+    b.ne ven_compare_less
+    cmp x0, x1
+    b.lt ven_compare_less
+    b.gt ven_compare_greater
+    mov x0, #0
+    ret
+ven_compare_less:
+    mov x0, #-1
+    ret
+ven_compare_greater:
+    mov x0, #1
+    ret
+ven_str_cmp:
+    ldrb w2, [x0]
+    ldrb w3, [x1]
+    cmp x2, x3
+    b.lt ven_str_cmp_less
+    b.gt ven_str_cmp_greater
+    cmp x2, #0
+    b.eq ven_str_cmp_equal
+    add x0, x0, #1
+    add x1, x1, #1
+    b ven_str_cmp
+ven_str_cmp_less:
+    mov x0, #-1
+    ret
+ven_str_cmp_greater:
+    mov x0, #1
+    ret
+ven_str_cmp_equal:
+    mov x0, #0
+    ret
+ven_str_copy:
+    mov x3, x0
+    add x3, x3, x2
+    sub x3, x3, #1
+    mov x2, x0
+ven_str_copy_next:
+    cmp x0, x3
+    b.ge ven_str_copy_cut
+    ldrb w4, [x1]
+    cmp x4, #0
+    b.eq ven_str_copy_done
+    strb w4, [x0]
+    add x0, x0, #1
+    add x1, x1, #1
+    b ven_str_copy_next
+ven_str_copy_cut:
+    cmp x0, x2
+    b.ls ven_str_copy_done
+    ldrb w4, [x1]
+    cmp x4, #128
+    b.lo ven_str_copy_done
+    cmp x4, #191
+    b.hi ven_str_copy_done
+    sub x0, x0, #1
+    sub x1, x1, #1
+    b ven_str_copy_cut
+ven_str_copy_done:
+    mov x4, #0
+    strb w4, [x0]
+    ret
+    // ---- conversions ----
+ven_convert_int:
+    adrp x9, ven_conv_text
+    str x0, [x9, :lo12:ven_conv_text]
+    mov x1, x0
+ven_convert_int_front:
+    ldrb w2, [x1]
+    cmp x2, #32
+    b.eq ven_convert_int_skip
+    cmp x2, #9
+    b.eq ven_convert_int_skip
+    cmp x2, #10
+    b.eq ven_convert_int_skip
+    cmp x2, #13
+    b.eq ven_convert_int_skip
+    mov x3, #0
+    cmp x2, #45
+    b.ne ven_convert_int_plus
+    mov x3, #1
+    add x1, x1, #1
+    b ven_convert_int_start
+ven_convert_int_plus:
+    cmp x2, #43
+    b.ne ven_convert_int_start
+    add x1, x1, #1
+ven_convert_int_start:
+    mov x0, #0
+    ldrb w2, [x1]
+    sub x2, x2, #48
+    cmp x2, #10
+    b.hs ven_convert_int_failed
+ven_convert_int_digit:
+    ldrb w2, [x1]
+    sub x2, x2, #48
+    cmp x2, #10
+    b.hs ven_convert_int_back
+    movz x4, #65535
+    movk x4, #65535, lsl #16
+    movk x4, #65535, lsl #32
+    movk x4, #32767, lsl #48
+    add x4, x4, x3
+    sub x4, x4, x2
+    mov x5, #10
+    udiv x4, x4, x5
+    cmp x0, x4
+    b.hi ven_convert_int_failed
+    mul x0, x0, x5
+    add x0, x0, x2
+    add x1, x1, #1
+    b ven_convert_int_digit
+ven_convert_int_back:
+    ldrb w2, [x1]
+    cmp x2, #32
+    b.eq ven_convert_int_next
+    cmp x2, #9
+    b.eq ven_convert_int_next
+    cmp x2, #10
+    b.eq ven_convert_int_next
+    cmp x2, #13
+    b.eq ven_convert_int_next
+    cmp x2, #0
+    b.ne ven_convert_int_failed
+    cmp x3, #0
+    b.eq ven_convert_int_done
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+ven_convert_int_done:
+    ret
+ven_convert_int_skip:
+    add x1, x1, #1
+    b ven_convert_int_front
+ven_convert_int_next:
+    add x1, x1, #1
+    b ven_convert_int_back
+ven_convert_int_failed:
+    mov x0, #2
+    adrp x1, lit_convert
+    add x1, x1, :lo12:lit_convert
+    mov x2, #31
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, ven_conv_text
+    ldr x0, [x9, :lo12:ven_conv_text]
+    mov x1, #2
+    str x30, [sp, #-16]!
+    bl ven_print_cstr
+    ldr x30, [sp], #16
+    mov x0, #2
+    adrp x1, lit_convert_end
+    add x1, x1, :lo12:lit_convert_end
+    mov x2, #15
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, ven_line
+    ldr x0, [x9, :lo12:ven_line]
+    mov x1, #2
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #2
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    mov x0, #1
+    mov x8, #93   // syscall: Exit
+    svc #0
+ven_int_text:
+    adrp x2, num_buf
+    add x2, x2, :lo12:num_buf
+    add x2, x2, #31
+    mov x3, #0
+    strb w3, [x2]
+    cmp x1, #0
+    b.ne ven_int_text_unsigned
+    cmp x0, #0
+    b.lt ven_int_text_digits
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    mov x3, #1
+ven_int_text_digits:
+    mov x4, x0
+    mov x5, #10
+    sdiv x9, x4, x5
+    msub x4, x9, x5, x4
+    mov x5, #48
+    sub x5, x5, x4
+    sub x2, x2, #1
+    strb w5, [x2]
+    mov x5, #10
+    sdiv x0, x0, x5
+    cmp x0, #0
+    b.ne ven_int_text_digits
+    cmp x3, #0
+    b.ne ven_int_text_done
+    sub x2, x2, #1
+    mov x5, #45
+    strb w5, [x2]
+    b ven_int_text_done
+ven_int_text_unsigned:
+    mov x3, x0
+    mov x5, #10
+    udiv x3, x3, x5
+    mov x4, x3
+    mul x4, x4, x5
+    mov x5, x0
+    sub x5, x5, x4
+    add x5, x5, #48
+    sub x2, x2, #1
+    strb w5, [x2]
+    mov x0, x3
+    cmp x0, #0
+    b.ne ven_int_text_unsigned
+ven_int_text_done:
+    mov x0, x2
+    ret
+ven_pad:
+    adrp x9, ven_pad_text
+    str x0, [x9, :lo12:ven_pad_text]
+    adrp x9, ven_pad_fd
+    str x1, [x9, :lo12:ven_pad_fd]
+    adrp x9, ven_pad_right
+    str x3, [x9, :lo12:ven_pad_right]
+    mov x1, x0
+ven_pad_count:
+    ldrb w4, [x1]
+    cmp x4, #0
+    b.eq ven_pad_counted
+    add x1, x1, #1
+    sub x4, x4, #128
+    cmp x4, #64
+    b.lo ven_pad_count
+    sub x2, x2, #1
+    b ven_pad_count
+ven_pad_counted:
+    adrp x9, ven_pad_left
+    str x2, [x9, :lo12:ven_pad_left]
+    cmp x3, #0
+    b.ne ven_pad_spaces
+    adrp x9, ven_pad_text
+    ldr x0, [x9, :lo12:ven_pad_text]
+    adrp x9, ven_pad_fd
+    ldr x1, [x9, :lo12:ven_pad_fd]
+    str x30, [sp, #-16]!
+    bl ven_print_cstr
+    ldr x30, [sp], #16
+ven_pad_spaces:
+    adrp x9, ven_pad_left
+    ldr x3, [x9, :lo12:ven_pad_left]
+    cmp x3, #0
+    b.le ven_pad_after
+    sub x3, x3, #1
+    adrp x9, ven_pad_left
+    str x3, [x9, :lo12:ven_pad_left]
+    adrp x9, ven_pad_fd
+    ldr x0, [x9, :lo12:ven_pad_fd]
+    adrp x1, lit_space
+    add x1, x1, :lo12:lit_space
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    b ven_pad_spaces
+ven_pad_after:
+    adrp x9, ven_pad_right
+    ldr x3, [x9, :lo12:ven_pad_right]
+    cmp x3, #0
+    b.eq ven_pad_done
+    adrp x9, ven_pad_text
+    ldr x0, [x9, :lo12:ven_pad_text]
+    adrp x9, ven_pad_fd
+    ldr x1, [x9, :lo12:ven_pad_fd]
+    str x30, [sp, #-16]!
+    bl ven_print_cstr
+    ldr x30, [sp], #16
+ven_pad_done:
+    ret
+    // ---- integer overflow ----
+ven_fit:
+    cmp x2, #0
+    b.ne ven_fit_signed
+    cmn x3, #1
+    b.ne ven_fit_signed
+    cmp x1, #0
+    b.ne ven_fit_ok
+    cmp x0, #0
+    b.ge ven_fit_ok
+    ret
+ven_fit_signed:
+    cmp x1, #0
+    b.eq ven_fit_range
+    cmp x0, #0
+    b.lt ven_fit_above
+ven_fit_range:
+    cmp x0, x2
+    b.lt ven_fit_below
+    cmp x0, x3
+    b.gt ven_fit_above
+ven_fit_ok:
+    ret
+ven_fit_below:
+    b ven_fit_wrap
+ven_fit_above:
+    b ven_fit_wrap
+ven_fit_wrap:
+    mov x4, x3
+    sub x4, x4, x2
+    add x4, x4, #1
+    cmp x4, #0
+    b.eq ven_fit_ok
+    sdiv x9, x0, x4
+    msub x0, x9, x4, x0
+    cmp x0, x2
+    b.ge ven_fit_wrap_high
+    add x0, x0, x4
+ven_fit_wrap_high:
+    cmp x0, x3
+    b.le ven_fit_ok
+    sub x0, x0, x4
+    ret
+ven_add_i64:
+    mov x2, x0
+    add x0, x0, x1
+    cmp x1, #0
+    b.lt ven_add_i64_negative
+    cmp x0, x2
+    b.lt ven_add_i64_overflow
+    ret
+ven_add_i64_negative:
+    cmp x0, x2
+    b.gt ven_add_i64_overflow
+    ret
+ven_add_i64_overflow:
+    ret
+ven_sub_i64:
+    mov x2, x0
+    sub x0, x0, x1
+    cmp x1, #0
+    b.lt ven_sub_i64_negative
+    cmp x0, x2
+    b.gt ven_sub_i64_overflow
+    ret
+ven_sub_i64_negative:
+    cmp x0, x2
+    b.lt ven_sub_i64_overflow
+    ret
+ven_sub_i64_overflow:
+    ret
+ven_mul_i64:
+    mov x2, x0
+    mul x0, x0, x1
+    cmn x2, #1
+    b.ne ven_mul_i64_divide
+    movz x3, #0
+    movk x3, #32768, lsl #48
+    cmp x1, x3
+    b.eq ven_mul_i64_overflow
+    ret
+ven_mul_i64_divide:
+    cmp x2, #0
+    b.eq ven_mul_i64_done
+    mov x3, x0
+    sdiv x3, x3, x2
+    cmp x3, x1
+    b.ne ven_mul_i64_overflow
+ven_mul_i64_done:
+    ret
+ven_mul_i64_overflow:
+    ret
+ven_div_i64:
+    cmn x1, #1
+    b.ne ven_div_i64_divide
+    movz x3, #0
+    movk x3, #32768, lsl #48
+    cmp x0, x3
+    b.eq ven_div_i64_overflow
+ven_div_i64_divide:
+    sdiv x0, x0, x1
+    ret
+ven_div_i64_overflow:
+    ret
+ven_add_u64:
+    mov x2, x0
+    add x0, x0, x1
+    cmp x0, x2
+    b.lo ven_add_u64_overflow
+    ret
+ven_add_u64_overflow:
+    ret
+ven_sub_u64:
+    mov x2, x0
+    sub x0, x0, x1
+    cmp x2, x1
+    b.lo ven_sub_u64_overflow
+    ret
+ven_sub_u64_overflow:
+    ret
+ven_mul_u64:
+    mov x2, x0
+    mul x0, x0, x1
+    cmp x2, #0
+    b.eq ven_mul_u64_done
+    mov x3, x0
+    udiv x3, x3, x2
+    cmp x3, x1
+    b.ne ven_mul_u64_overflow
+ven_mul_u64_done:
+    ret
+ven_mul_u64_overflow:
+    ret
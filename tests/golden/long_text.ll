@@ -0,0 +1,591 @@
+; Generated by the Ven compiler
+declare i32 @dprintf(i32, i8*, ...)
+declare i32 @snprintf(i8*, i64, i8*, ...)
+declare i32 @strcmp(i8*, i8*)
+declare i32 @strncmp(i8*, i8*, i64)
+declare i64 @strlen(i8*)
+declare i64 @strtoll(i8*, i8**, i32)
+declare i64 @strtoull(i8*, i8**, i32)
+declare i32* @__errno_location()
+declare void @exit(i32)
+declare double @llvm.trunc.f64(double)
+declare {i64, i1} @llvm.umul.with.overflow.i64(i64, i64)
+declare double @strtod(i8*, i8**)
+declare i64 @read(i32, i8*, i64)
+declare i8* @memmove(i8*, i8*, i64)
+declare i8* @memset(i8*, i32, i64)
+declare i8* @strchr(i8*, i32)
+
+@ven.line = internal global [256 x i8] zeroinitializer
+@ven.fmt.s = private unnamed_addr constant [3 x i8] c"%s\00"
+@ven.fmt.lld = private unnamed_addr constant [5 x i8] c"%lld\00"
+@ven.fmt.llu = private unnamed_addr constant [5 x i8] c"%llu\00"
+@ven.fmt.e = private unnamed_addr constant [5 x i8] c"%.*e\00"
+@ven.fmt.f = private unnamed_addr constant [5 x i8] c"%.*f\00"
+@ven.nan = private unnamed_addr constant [4 x i8] c"NaN\00"
+@ven.inf = private unnamed_addr constant [4 x i8] c"inf\00"
+@ven.minus.inf = private unnamed_addr constant [5 x i8] c"-inf\00"
+@ven.true = private unnamed_addr constant [5 x i8] c"true\00"
+@ven.false = private unnamed_addr constant [6 x i8] c"false\00"
+
+; Reads one line without its newline; carriage returns and overflow are dropped
+define internal void @ven_read_line(i8* %buf, i64 %size) {
+entry:
+  %c = alloca i8
+  %max = sub i64 %size, 1
+  br label %loop
+loop:
+  %len = phi i64 [0, %entry], [%len, %skip], [%next, %keep]
+  %n = call i64 @read(i32 0, i8* %c, i64 1)
+  %eof = icmp slt i64 %n, 1
+  br i1 %eof, label %done, label %got
+got:
+  %byte = load i8, i8* %c
+  %newline = icmp eq i8 %byte, 10
+  br i1 %newline, label %done, label %check
+check:
+  %cr = icmp eq i8 %byte, 13
+  %full = icmp uge i64 %len, %max
+  %drop = or i1 %cr, %full
+  br i1 %drop, label %skip, label %keep
+skip:
+  br label %loop
+keep:
+  %slot = getelementptr inbounds i8, i8* %buf, i64 %len
+  store i8 %byte, i8* %slot
+  %next = add i64 %len, 1
+  br label %loop
+done:
+  %end = getelementptr inbounds i8, i8* %buf, i64 %len
+  store i8 0, i8* %end
+  ret void
+}
+
+; Copies as much of src as fits without splitting a character, always terminating dest
+define internal void @ven_copy(i8* %dest, i8* %src, i64 %size) {
+entry:
+  %len = call i64 @strlen(i8* %src)
+  %max = sub i64 %size, 1
+  %long = icmp ugt i64 %len, %max
+  br i1 %long, label %cut, label %copy
+cut:
+  %cut.len = phi i64 [%max, %entry], [%back, %inside]
+  %at = getelementptr inbounds i8, i8* %src, i64 %cut.len
+  %byte = load i8, i8* %at
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %nonzero = icmp ugt i64 %cut.len, 0
+  %split = and i1 %continuation, %nonzero
+  br i1 %split, label %inside, label %copy
+inside:
+  %back = sub i64 %cut.len, 1
+  br label %cut
+copy:
+  %n = phi i64 [%len, %entry], [%cut.len, %cut]
+  call i8* @memmove(i8* %dest, i8* %src, i64 %n)
+  %end = getelementptr inbounds i8, i8* %dest, i64 %n
+  store i8 0, i8* %end
+  ret void
+}
+
+define internal i8* @ven_skip_space(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %space]
+  %byte = load i8, i8* %p
+  %sp = icmp eq i8 %byte, 32
+  %low = icmp uge i8 %byte, 9
+  %high = icmp ule i8 %byte, 13
+  %ctl = and i1 %low, %high
+  %ws = or i1 %sp, %ctl
+  br i1 %ws, label %space, label %done
+space:
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %p
+}
+
+define internal i1 @ven_blank(i8* %text) {
+  %p = call i8* @ven_skip_space(i8* %text)
+  %byte = load i8, i8* %p
+  %blank = icmp eq i8 %byte, 0
+  ret i1 %blank
+}
+
+; Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+define internal i128 @ven_to_int(i8* %text) {
+entry:
+  %end = alloca i8*
+  %start = call i8* @ven_skip_space(i8* %text)
+  %errno = call i32* @__errno_location()
+  store i32 0, i32* %errno
+  %first = load i8, i8* %start
+  %minus = icmp eq i8 %first, 45
+  br i1 %minus, label %negative, label %positive
+negative:
+  %signed = call i64 @strtoll(i8* %start, i8** %end, i32 10)
+  %wide.signed = sext i64 %signed to i128
+  br label %check
+positive:
+  %unsigned = call i64 @strtoull(i8* %start, i8** %end, i32 10)
+  %wide.unsigned = zext i64 %unsigned to i128
+  br label %check
+check:
+  %value = phi i128 [%wide.signed, %negative], [%wide.unsigned, %positive]
+  %stop = load i8*, i8** %end
+  %moved = icmp ne i8* %stop, %start
+  %rest = call i1 @ven_blank(i8* %stop)
+  %err = load i32, i32* %errno
+  %in.range = icmp ne i32 %err, 34
+  %number = and i1 %moved, %rest
+  %ok = and i1 %number, %in.range
+  %result = select i1 %ok, i128 %value, i128 0
+  ret i128 %result
+}
+
+define internal double @ven_to_float(i8* %text) {
+  %end = alloca i8*
+  %value = call double @strtod(i8* %text, i8** %end)
+  %stop = load i8*, i8** %end
+  %moved = icmp ne i8* %stop, %text
+  %rest = call i1 @ven_blank(i8* %stop)
+  %ok = and i1 %moved, %rest
+  %result = select i1 %ok, double %value, double 0.0
+  ret double %result
+}
+
+; Only the word true reads as true
+define internal i1 @ven_to_bool(i8* %text) {
+entry:
+  %p = call i8* @ven_skip_space(i8* %text)
+  %cmp = call i32 @strncmp(i8* %p, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.true, i64 0, i64 0), i64 4)
+  %word = icmp eq i32 %cmp, 0
+  br i1 %word, label %rest, label %no
+rest:
+  %after = getelementptr inbounds i8, i8* %p, i64 4
+  %blank = call i1 @ven_blank(i8* %after)
+  ret i1 %blank
+no:
+  ret i1 false
+}
+
+; The shortest text that reads back as %x, without an exponent, in %buf of 400 bytes
+define internal i8* @ven_float_text(i8* %buf, double %x) {
+entry:
+  %short = alloca [32 x i8]
+  %text = getelementptr inbounds [32 x i8], [32 x i8]* %short, i64 0, i64 0
+  %nan = fcmp uno double %x, %x
+  br i1 %nan, label %not.a.number, label %check
+not.a.number:
+  ret i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.nan, i64 0, i64 0)
+check:
+  ; Only an infinity is not finite after x - x
+  %zero = fsub double %x, %x
+  %finite = fcmp oeq double %zero, 0.0
+  br i1 %finite, label %loop, label %infinite
+infinite:
+  %negative = fcmp olt double %x, 0.0
+  %word = select i1 %negative, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.minus.inf, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.inf, i64 0, i64 0)
+  ret i8* %word
+loop:
+  %precision = phi i32 [1, %check], [%next, %retry]
+  %decimals = sub i32 %precision, 1
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %text, i64 32, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.e, i64 0, i64 0), i32 %decimals, double %x)
+  %back = call double @strtod(i8* %text, i8** null)
+  %same = fcmp oeq double %back, %x
+  %last = icmp sge i32 %precision, 17
+  %stop = or i1 %same, %last
+  br i1 %stop, label %found, label %retry
+retry:
+  %next = add i32 %precision, 1
+  br label %loop
+found:
+  %e = call i8* @strchr(i8* %text, i32 101)
+  %after.e = getelementptr inbounds i8, i8* %e, i64 1
+  %exponent.wide = call i64 @strtoll(i8* %after.e, i8** null, i32 10)
+  %exponent = trunc i64 %exponent.wide to i32
+  %fraction = icmp slt i32 %exponent, %decimals
+  br i1 %fraction, label %fixed, label %whole
+fixed:
+  ; Rounding at the last shortest digit gives the same digits written out
+  %places = sub i32 %decimals, %exponent
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %buf, i64 400, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.f, i64 0, i64 0), i32 %places, double %x)
+  ret i8* %buf
+whole:
+  ; The digits without their point, then zeros up to the point
+  store i8 0, i8* %e
+  %first = load i8, i8* %text
+  %minus = icmp eq i8 %first, 45
+  %lead = select i1 %minus, i64 2, i64 1
+  %point = getelementptr inbounds i8, i8* %text, i64 %lead
+  %rest = call i64 @strlen(i8* %point)
+  %after.point = getelementptr inbounds i8, i8* %point, i64 1
+  call i8* @memmove(i8* %point, i8* %after.point, i64 %rest)
+  %len = call i64 @strlen(i8* %text)
+  call i8* @memmove(i8* %buf, i8* %text, i64 %len)
+  %end = getelementptr inbounds i8, i8* %buf, i64 %len
+  %zeros.narrow = sub i32 %exponent, %decimals
+  %zeros = sext i32 %zeros.narrow to i64
+  call i8* @memset(i8* %end, i32 48, i64 %zeros)
+  %stop.at = getelementptr inbounds i8, i8* %end, i64 %zeros
+  store i8 0, i8* %stop.at
+  ret i8* %buf
+}
+
+define internal void @ven_print_float(i32 %fd, double %x) {
+  %buf = alloca [400 x i8]
+  %start = getelementptr inbounds [400 x i8], [400 x i8]* %buf, i64 0, i64 0
+  %text = call i8* @ven_float_text(i8* %start, double %x)
+  call i32 (i32, i8*, ...) @dprintf(i32 %fd, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* %text)
+  ret void
+}
+
+define internal i128 @ven_fit(i128 %value, i128 %min, i128 %max, i8* %name, i8* %type, i64 %line) {
+entry:
+  %below = icmp slt i128 %value, %min
+  %above = icmp sgt i128 %value, %max
+  %out = or i1 %below, %above
+  br i1 %out, label %wrap, label %ok
+ok:
+  ret i128 %value
+wrap:
+  %mask = sub i128 %max, %min
+  %span = add i128 %mask, 1
+  %low = and i128 %value, %mask
+  %high = icmp sgt i128 %low, %max
+  %back = sub i128 %low, %span
+  %result = select i1 %high, i128 %back, i128 %low
+  ret i128 %result
+}
+
+; A u64 product, which can exceed i128
+define internal i64 @ven_mul_u64(i64 %a, i64 %b, i8* %name, i64 %line) {
+  %pair = call {i64, i1} @llvm.umul.with.overflow.i64(i64 %a, i64 %b)
+  %product = extractvalue {i64, i1} %pair, 0
+  ret i64 %product
+}
+
+define internal i128 @ven_fit_float(double %value, i128 %min, i128 %max, double %low, double %high, i8* %name, i8* %type, i64 %line) {
+entry:
+  %nan = fcmp uno double %value, %value
+  br i1 %nan, label %zero, label %number
+zero:
+  ret i128 0
+number:
+  %whole = call double @llvm.trunc.f64(double %value)
+  %below = fcmp olt double %whole, %low
+  %above = fcmp oge double %whole, %high
+  br i1 %below, label %clamp.min, label %check
+clamp.min:
+  ret i128 %min
+check:
+  br i1 %above, label %clamp.max, label %convert
+clamp.max:
+  ret i128 %max
+convert:
+  %negative = fcmp olt double %whole, 0.0
+  br i1 %negative, label %signed, label %unsigned
+signed:
+  %s = fptosi double %whole to i64
+  %wide.s = sext i64 %s to i128
+  ret i128 %wide.s
+unsigned:
+  %u = fptoui double %whole to i64
+  %wide.u = zext i64 %u to i128
+  ret i128 %wide.u
+}
+
+declare i8* @strstr(i8*, i8*)
+
+; Appends %n bytes of %src at %len in %buf, cutting at the last whole character
+; that fits, and returns the new length; once text is cut nothing more is added
+define internal i64 @ven_put(i8* %buf, i64 %len, i8* %src, i64 %n) {
+entry:
+  %total = add i64 %len, %n
+  %fits = icmp ult i64 %total, 256
+  br i1 %fits, label %copy, label %cut
+cut:
+  %room = sub i64 255, %len
+  br label %back
+back:
+  %keep = phi i64 [%room, %cut], [%less, %inside]
+  %at = getelementptr inbounds i8, i8* %src, i64 %keep
+  %byte = load i8, i8* %at
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %nonzero = icmp ugt i64 %keep, 0
+  %split = and i1 %continuation, %nonzero
+  br i1 %split, label %inside, label %copy
+inside:
+  %less = sub i64 %keep, 1
+  br label %back
+copy:
+  %count = phi i64 [%n, %entry], [%keep, %back]
+  %next = phi i64 [%total, %entry], [255, %back]
+  %dest = getelementptr inbounds i8, i8* %buf, i64 %len
+  call i8* @memmove(i8* %dest, i8* %src, i64 %count)
+  %end = getelementptr inbounds i8, i8* %dest, i64 %count
+  store i8 0, i8* %end
+  ret i64 %next
+}
+
+define internal i64 @ven_append(i8* %buf, i64 %len, i8* %src) {
+  %n = call i64 @strlen(i8* %src)
+  %next = call i64 @ven_put(i8* %buf, i64 %len, i8* %src, i64 %n)
+  ret i64 %next
+}
+
+; The number of characters in %text
+define internal i64 @ven_len(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %count]
+  %chars = phi i64 [0, %entry], [%more, %count]
+  %byte = load i8, i8* %p
+  %end = icmp eq i8 %byte, 0
+  br i1 %end, label %done, label %count
+count:
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %first = xor i1 %continuation, true
+  %add = zext i1 %first to i64
+  %more = add i64 %chars, %add
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i64 %chars
+}
+
+; The byte offset of character %index, or the length when there are fewer
+define internal i64 @ven_offset(i8* %text, i64 %index) {
+entry:
+  br label %loop
+loop:
+  %at = phi i64 [0, %entry], [%next, %check]
+  %chars = phi i64 [0, %entry], [%more, %check]
+  %p = getelementptr inbounds i8, i8* %text, i64 %at
+  %byte = load i8, i8* %p
+  %end = icmp eq i8 %byte, 0
+  br i1 %end, label %done, label %look
+look:
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %first = xor i1 %continuation, true
+  %reached = icmp eq i64 %chars, %index
+  %found = and i1 %first, %reached
+  br i1 %found, label %done, label %check
+check:
+  %add = zext i1 %first to i64
+  %more = add i64 %chars, %add
+  %next = add i64 %at, 1
+  br label %loop
+done:
+  ret i64 %at
+}
+
+; A character index from 0 up to the i64 maximum
+define internal i64 @ven_char_index(i128 %index) {
+  %negative = icmp slt i128 %index, 0
+  %large = icmp sgt i128 %index, 9223372036854775807
+  %low = select i1 %negative, i128 0, i128 %index
+  %clamped = select i1 %large, i128 9223372036854775807, i128 %low
+  %narrow = trunc i128 %clamped to i64
+  ret i64 %narrow
+}
+
+define internal i8* @ven_slice(i8* %buf, i8* %text, i128 %start, i128 %end) {
+entry:
+  %first = call i64 @ven_char_index(i128 %start)
+  %last = call i64 @ven_char_index(i128 %end)
+  %from = call i64 @ven_offset(i8* %text, i64 %first)
+  %to = call i64 @ven_offset(i8* %text, i64 %last)
+  store i8 0, i8* %buf
+  %some = icmp ugt i64 %to, %from
+  br i1 %some, label %put, label %done
+put:
+  %src = getelementptr inbounds i8, i8* %text, i64 %from
+  %n = sub i64 %to, %from
+  call i64 @ven_put(i8* %buf, i64 0, i8* %src, i64 %n)
+  br label %done
+done:
+  ret i8* %buf
+}
+
+; Adds %delta to the ASCII letters from %first, so only ASCII letters change case
+define internal i8* @ven_case(i8* %buf, i8* %text, i8 %first, i8 %delta) {
+entry:
+  call i64 @ven_append(i8* %buf, i64 0, i8* %text)
+  br label %loop
+loop:
+  %p = phi i8* [%buf, %entry], [%next, %letter]
+  %byte = load i8, i8* %p
+  %end = icmp eq i8 %byte, 0
+  br i1 %end, label %done, label %letter
+letter:
+  %offset = sub i8 %byte, %first
+  %is.letter = icmp ult i8 %offset, 26
+  %changed = add i8 %byte, %delta
+  %new = select i1 %is.letter, i8 %changed, i8 %byte
+  store i8 %new, i8* %p
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %buf
+}
+
+define internal i1 @ven_trimmed(i8 %byte) {
+  %space = icmp eq i8 %byte, 32
+  %tab = icmp eq i8 %byte, 9
+  %newline = icmp eq i8 %byte, 10
+  %return = icmp eq i8 %byte, 13
+  %gap = or i1 %space, %tab
+  %line = or i1 %newline, %return
+  %trimmed = or i1 %gap, %line
+  ret i1 %trimmed
+}
+
+; %text without spaces, tabs and line breaks at either end
+define internal i8* @ven_trim(i8* %buf, i8* %text) {
+entry:
+  %len = call i64 @strlen(i8* %text)
+  br label %front
+front:
+  %start = phi i64 [0, %entry], [%after, %skip]
+  %left = icmp ult i64 %start, %len
+  br i1 %left, label %look, label %tail
+look:
+  %p = getelementptr inbounds i8, i8* %text, i64 %start
+  %byte = load i8, i8* %p
+  %blank = call i1 @ven_trimmed(i8 %byte)
+  br i1 %blank, label %skip, label %tail
+skip:
+  %after = add i64 %start, 1
+  br label %front
+tail:
+  %stop = phi i64 [%len, %front], [%len, %look], [%before, %peek]
+  %more = icmp ugt i64 %stop, %start
+  br i1 %more, label %peek, label %copy
+peek:
+  %before = sub i64 %stop, 1
+  %q = getelementptr inbounds i8, i8* %text, i64 %before
+  %last = load i8, i8* %q
+  %end.blank = call i1 @ven_trimmed(i8 %last)
+  br i1 %end.blank, label %tail, label %copy
+copy:
+  %src = getelementptr inbounds i8, i8* %text, i64 %start
+  %n = sub i64 %stop, %start
+  store i8 0, i8* %buf
+  call i64 @ven_put(i8* %buf, i64 0, i8* %src, i64 %n)
+  ret i8* %buf
+}
+
+define internal i1 @ven_contains(i8* %text, i8* %part) {
+  %found = call i8* @strstr(i8* %text, i8* %part)
+  %some = icmp ne i8* %found, null
+  ret i1 %some
+}
+
+define internal i1 @ven_starts_with(i8* %text, i8* %prefix) {
+  %n = call i64 @strlen(i8* %prefix)
+  %cmp = call i32 @strncmp(i8* %text, i8* %prefix, i64 %n)
+  %same = icmp eq i32 %cmp, 0
+  ret i1 %same
+}
+
+; Every %from, left to right; an empty %from leaves the text as it is
+define internal i8* @ven_replace(i8* %buf, i8* %text, i8* %from, i8* %to) {
+entry:
+  %from.len = call i64 @strlen(i8* %from)
+  %to.len = call i64 @strlen(i8* %to)
+  store i8 0, i8* %buf
+  %empty = icmp eq i64 %from.len, 0
+  br i1 %empty, label %rest, label %loop
+loop:
+  %at = phi i8* [%text, %entry], [%skip, %hit]
+  %len = phi i64 [0, %entry], [%after.to, %hit]
+  %found = call i8* @strstr(i8* %at, i8* %from)
+  %none = icmp eq i8* %found, null
+  br i1 %none, label %rest, label %hit
+hit:
+  %at.int = ptrtoint i8* %at to i64
+  %found.int = ptrtoint i8* %found to i64
+  %gap = sub i64 %found.int, %at.int
+  %after.gap = call i64 @ven_put(i8* %buf, i64 %len, i8* %at, i64 %gap)
+  %after.to = call i64 @ven_put(i8* %buf, i64 %after.gap, i8* %to, i64 %to.len)
+  %skip = getelementptr inbounds i8, i8* %found, i64 %from.len
+  br label %loop
+rest:
+  %tail = phi i8* [%text, %entry], [%at, %loop]
+  %tail.len = phi i64 [0, %entry], [%len, %loop]
+  call i64 @ven_append(i8* %buf, i64 %tail.len, i8* %tail)
+  ret i8* %buf
+}
+
+@v.ten = internal global [256 x i8] zeroinitializer
+@v.fifty = internal global [256 x i8] zeroinitializer
+@v.wide = internal global [256 x i8] zeroinitializer
+@v.doubled = internal global [256 x i8] zeroinitializer
+@v.literal = internal global [256 x i8] zeroinitializer
+@ven.text.1 = internal global [256 x i8] zeroinitializer
+@ven.text.2 = internal global [256 x i8] zeroinitializer
+@ven.text.3 = internal global [256 x i8] zeroinitializer
+@ven.text.4 = internal global [256 x i8] zeroinitializer
+@ven.text.5 = internal global [256 x i8] zeroinitializer
+@ven.text.6 = internal global [256 x i8] zeroinitializer
+@.str.0 = private unnamed_addr constant [11 x i8] c"abcdefghij\00"
+@.str.1 = private unnamed_addr constant [7 x i8] c"abcd\C3\A9\00"
+@.str.2 = private unnamed_addr constant [2 x i8] c"\0A\00"
+@.str.3 = private unnamed_addr constant [2 x i8] c"a\00"
+@.str.4 = private unnamed_addr constant [69 x i8] c"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\00"
+@.str.5 = private unnamed_addr constant [281 x i8] c"0123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789\00"
+
+define i32 @main() {
+entry:
+  call void @ven_copy(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.ten, i64 0, i64 0), i8* getelementptr inbounds ([11 x i8], [11 x i8]* @.str.0, i64 0, i64 0), i64 256)
+  %t1 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.1, i64 0, i64 0), i64 0, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.ten, i64 0, i64 0))
+  %t2 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.1, i64 0, i64 0), i64 %t1, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.ten, i64 0, i64 0))
+  %t3 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.1, i64 0, i64 0), i64 %t2, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.ten, i64 0, i64 0))
+  %t4 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.1, i64 0, i64 0), i64 %t3, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.ten, i64 0, i64 0))
+  %t5 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.1, i64 0, i64 0), i64 %t4, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.ten, i64 0, i64 0))
+  call void @ven_copy(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.fifty, i64 0, i64 0), i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.1, i64 0, i64 0), i64 256)
+  %t6 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.2, i64 0, i64 0), i64 0, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.fifty, i64 0, i64 0))
+  %t7 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.2, i64 0, i64 0), i64 %t6, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.fifty, i64 0, i64 0))
+  %t8 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.2, i64 0, i64 0), i64 %t7, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.fifty, i64 0, i64 0))
+  %t9 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.2, i64 0, i64 0), i64 %t8, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.fifty, i64 0, i64 0))
+  %t10 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.2, i64 0, i64 0), i64 %t9, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.fifty, i64 0, i64 0))
+  %t11 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.2, i64 0, i64 0), i64 %t10, i8* getelementptr inbounds ([7 x i8], [7 x i8]* @.str.1, i64 0, i64 0))
+  call void @ven_copy(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.wide, i64 0, i64 0), i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.2, i64 0, i64 0), i64 256)
+  %t12 = call i64 @ven_len(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.wide, i64 0, i64 0))
+  %t13 = zext i64 %t12 to i128
+  %t14 = trunc i128 %t13 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t14)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.2, i64 0, i64 0))
+  %t15 = call i8* @ven_slice(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.3, i64 0, i64 0), i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.wide, i64 0, i64 0), i128 250, i128 260)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* %t15)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.2, i64 0, i64 0))
+  %t16 = call i8* @ven_replace(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.4, i64 0, i64 0), i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.fifty, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.3, i64 0, i64 0), i8* getelementptr inbounds ([69 x i8], [69 x i8]* @.str.4, i64 0, i64 0))
+  call void @ven_copy(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.doubled, i64 0, i64 0), i8* %t16, i64 256)
+  %t17 = call i64 @ven_len(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.doubled, i64 0, i64 0))
+  %t18 = zext i64 %t17 to i128
+  %t19 = trunc i128 %t18 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t19)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.2, i64 0, i64 0))
+  call void @ven_copy(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.literal, i64 0, i64 0), i8* getelementptr inbounds ([281 x i8], [281 x i8]* @.str.5, i64 0, i64 0), i64 256)
+  %t20 = call i64 @ven_len(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.literal, i64 0, i64 0))
+  %t21 = zext i64 %t20 to i128
+  %t22 = trunc i128 %t21 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t22)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.2, i64 0, i64 0))
+  %t23 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.5, i64 0, i64 0), i64 0, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.literal, i64 0, i64 0))
+  %t24 = call i64 @ven_append(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.5, i64 0, i64 0), i64 %t23, i8* getelementptr inbounds ([256 x i8], [256 x i8]* @v.wide, i64 0, i64 0))
+  %t25 = call i8* @ven_case(i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.6, i64 0, i64 0), i8* getelementptr inbounds ([256 x i8], [256 x i8]* @ven.text.5, i64 0, i64 0), i8 97, i8 -32)
+  %t26 = call i64 @ven_len(i8* %t25)
+  %t27 = zext i64 %t26 to i128
+  %t28 = trunc i128 %t27 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t28)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.2, i64 0, i64 0))
+  ret i32 0
+}
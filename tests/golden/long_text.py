@@ -0,0 +1,87 @@
+# Generated by the Ven compiler
+import sys
+
+
+def _read_line():
+    # A line holds at most 255 bytes, as on the native targets
+    line = sys.stdin.readline().rstrip("\r\n").encode()
+    return line[:255].decode(errors="ignore")
+
+
+# Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+def _to_int(text):
+    try:
+        value = int(text.strip())
+    except ValueError:
+        return 0
+    return value if -(2**63) <= value < 2**64 else 0
+
+
+def _to_float(text):
+    try:
+        return float(text.strip())
+    except ValueError:
+        return 0.0
+
+
+def _int_div(a, b):
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+
+
+def _fit(value, lo, hi, name, type, line):
+    return (value - lo) % (hi - lo + 1) + lo
+
+
+# Floats never wrap: they are truncated and clamp at the limits
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+
+
+def _fit_text(text, size):
+    data = str(text).encode()
+    if len(data) <= size:
+        return text
+    # A character cut in two is dropped whole
+    return data[:size].decode(errors="ignore")
+
+
+_UPPER = {c: c - 32 for c in range(97, 123)}
+_LOWER = {c: c + 32 for c in range(65, 91)}
+
+
+def _slice(text, start, end):
+    return text[max(start, 0):max(end, 0)]
+
+
+def _trim(text):
+    return text.strip(" \t\n\r")
+
+
+# An empty `old` leaves the text as it is
+def _replace(text, old, new):
+    return text.replace(old, new) if old else text
+
+
+ten = ""
+fifty = ""
+wide = ""
+doubled = ""
+literal = ""
+
+ten = "abcdefghij"
+fifty = _fit_text(ten + ten + ten + ten + ten, 255)
+wide = _fit_text(fifty + fifty + fifty + fifty + fifty + "abcdé", 255)
+print(len(wide))
+print(_slice(wide, 250, 260))
+doubled = _fit_text(_replace(fifty, "a", "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"), 255)
+print(len(doubled))
+literal = "012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234"
+print(len(literal))
+print(len(_fit_text(literal + wide, 255).translate(_UPPER)))
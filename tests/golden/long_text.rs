@@ -0,0 +1,101 @@
+// Generated by the Ven compiler
+#![allow(dead_code, unused_mut, unused_assignments, unused_variables, unused_parens)]
+use std::io::BufRead;
+
+fn ven_read_line() -> String {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).unwrap_or(0);
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+    // A line holds at most 255 bytes, as on the native targets
+    let mut end = line.len().min(255);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+    line
+}
+
+// Text that is not a whole number from i64::MIN to u64::MAX reads as 0
+fn ven_to_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+        .unwrap_or(0)
+}
+
+fn ven_to_float(text: &str) -> f64 {
+    text.trim().parse().unwrap_or(0.0)
+}
+
+fn ven_to_bool(text: &str) -> bool {
+    text.trim() == "true"
+}
+
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        let span = max - min + 1;
+        let low = value.rem_euclid(span);
+        return if low > max { low - span } else { low };
+    }
+    value
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => (a as u64).wrapping_mul(b as u64) as i128,
+    }
+}
+
+fn ven_fit_text(mut text: String, size: usize) -> String {
+    if text.len() > size {
+        let mut end = size;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+    text
+}
+
+// The characters from `start` up to `end`, both clamped to the text
+fn ven_slice(text: &str, start: i128, end: i128) -> String {
+    let offset = |index: i128| {
+        let index = index.clamp(0, usize::MAX as i128) as usize;
+        text.char_indices().nth(index).map_or(text.len(), |(offset, _)| offset)
+    };
+    let (start, end) = (offset(start), offset(end));
+    if end <= start { String::new() } else { text[start..end].to_string() }
+}
+
+// An empty `from` leaves the text as it is
+fn ven_replace(text: &str, from: &str, to: &str) -> String {
+    if from.is_empty() { text.to_string() } else { text.replace(from, to) }
+}
+
+fn main() {
+    let mut ten = String::new();
+    let mut fifty = String::new();
+    let mut wide = String::new();
+    let mut doubled = String::new();
+    let mut literal = String::new();
+    ten = "abcdefghij".to_string();
+    fifty = ven_fit_text([ten.as_str(), ten.as_str(), ten.as_str(), ten.as_str(), ten.as_str()].concat(), 255).to_string();
+    wide = ven_fit_text([fifty.as_str(), fifty.as_str(), fifty.as_str(), fifty.as_str(), fifty.as_str(), "abcdé"].concat(), 255).to_string();
+    println!("{}", (wide.chars().count() as i64));
+    println!("{}", ven_slice(wide.as_str(), 250 as i128, 260 as i128));
+    doubled = ven_fit_text(ven_replace(fifty.as_str(), "a", "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"), 255).to_string();
+    println!("{}", (doubled.chars().count() as i64));
+    literal = "012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234".to_string();
+    println!("{}", (literal.chars().count() as i64));
+    println!("{}", (ven_fit_text([literal.as_str(), wide.as_str()].concat(), 255).as_str().to_ascii_uppercase().as_str().chars().count() as i64));
+}
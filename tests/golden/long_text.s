@@ -0,0 +1,986 @@
+// Generated by the Ven compiler
+
+.data
+newline:
+    .byte 10
+lit_true:
+    .ascii "true"
+lit_false:
+    .ascii "false"
+lit_undefined:
+    .ascii "[undefined]"
+str_0:
+    .ascii "abcdefghij"
+    .byte 0
+str_1:
+    .ascii "abcd"
+    .byte 195, 169, 0
+str_2:
+    .ascii "a"
+    .byte 0
+str_3:
+    .ascii "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+    .byte 0
+str_4:
+    .ascii "0123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789"
+    .byte 0
+
+.bss
+    .balign 8
+num_buf:
+    .skip 32
+    .balign 8
+line_buf:
+    .skip 256
+    .balign 8
+var_ten:
+    .skip 256
+    .balign 8
+var_fifty:
+    .skip 256
+    .balign 8
+var_wide:
+    .skip 256
+    .balign 8
+var_doubled:
+    .skip 256
+    .balign 8
+var_literal:
+    .skip 256
+    .balign 8
+tmp_0:
+    .skip 8
+    .balign 8
+tmp_1:
+    .skip 8
+    .balign 8
+tmp_2:
+    .skip 8
+    .balign 8
+text_0:
+    .skip 256
+    .balign 8
+text_1:
+    .skip 256
+    .balign 8
+text_2:
+    .skip 256
+    .balign 8
+text_3:
+    .skip 256
+    .balign 8
+text_4:
+    .skip 256
+    .balign 8
+text_5:
+    .skip 256
+    .balign 8
+ven_rep_at:
+    .skip 8
+    .balign 8
+ven_rep_from:
+    .skip 8
+    .balign 8
+ven_rep_to:
+    .skip 8
+    .balign 8
+ven_rep_pos:
+    .skip 8
+    .balign 8
+ven_rep_limit:
+    .skip 8
+    .balign 8
+ven_rep_next:
+    .skip 8
+
+.text
+.global _start
+_start:
+    // declare ten
+    adrp x0, var_ten
+    add x0, x0, :lo12:var_ten
+    adrp x1, str_0
+    add x1, x1, :lo12:str_0
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    // declare fifty
+    // concat()
+    adrp x0, text_0
+    add x0, x0, :lo12:text_0
+    mov x3, x0
+    add x3, x3, #255
+    adrp x1, var_ten
+    add x1, x1, :lo12:var_ten
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x1, var_ten
+    add x1, x1, :lo12:var_ten
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x1, var_ten
+    add x1, x1, :lo12:var_ten
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x1, var_ten
+    add x1, x1, :lo12:var_ten
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x1, var_ten
+    add x1, x1, :lo12:var_ten
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x0, var_fifty
+    add x0, x0, :lo12:var_fifty
+    adrp x1, text_0
+    add x1, x1, :lo12:text_0
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    // declare wide
+    // concat()
+    adrp x0, text_1
+    add x0, x0, :lo12:text_1
+    mov x3, x0
+    add x3, x3, #255
+    adrp x1, var_fifty
+    add x1, x1, :lo12:var_fifty
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x1, var_fifty
+    add x1, x1, :lo12:var_fifty
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x1, var_fifty
+    add x1, x1, :lo12:var_fifty
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x1, var_fifty
+    add x1, x1, :lo12:var_fifty
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x1, var_fifty
+    add x1, x1, :lo12:var_fifty
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x1, str_1
+    add x1, x1, :lo12:str_1
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x0, var_wide
+    add x0, x0, :lo12:var_wide
+    adrp x1, text_1
+    add x1, x1, :lo12:text_1
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    // print to fd 1
+    adrp x0, var_wide
+    add x0, x0, :lo12:var_wide
+    str x30, [sp, #-16]!
+    bl ven_str_len
+    ldr x30, [sp], #16
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // print to fd 1
+    mov x0, #250
+    adrp x9, tmp_0
+    str x0, [x9, :lo12:tmp_0]
+    mov x0, #260
+    adrp x9, tmp_1
+    str x0, [x9, :lo12:tmp_1]
+    // slice()
+    adrp x0, var_wide
+    add x0, x0, :lo12:var_wide
+    adrp x9, tmp_0
+    ldr x1, [x9, :lo12:tmp_0]
+    str x30, [sp, #-16]!
+    bl ven_str_skip
+    ldr x30, [sp], #16
+    adrp x9, tmp_2
+    str x0, [x9, :lo12:tmp_2]
+    adrp x0, var_wide
+    add x0, x0, :lo12:var_wide
+    adrp x9, tmp_1
+    ldr x1, [x9, :lo12:tmp_1]
+    str x30, [sp, #-16]!
+    bl ven_str_skip
+    ldr x30, [sp], #16
+    mov x2, x0
+    adrp x9, tmp_2
+    ldr x1, [x9, :lo12:tmp_2]
+    adrp x0, text_2
+    add x0, x0, :lo12:text_2
+    mov x3, x0
+    add x3, x3, #255
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x0, text_2
+    add x0, x0, :lo12:text_2
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_cstr
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // declare doubled
+    // replace()
+    adrp x0, text_3
+    add x0, x0, :lo12:text_3
+    adrp x1, var_fifty
+    add x1, x1, :lo12:var_fifty
+    adrp x2, str_2
+    add x2, x2, :lo12:str_2
+    adrp x3, str_3
+    add x3, x3, :lo12:str_3
+    str x30, [sp, #-16]!
+    bl ven_str_replace
+    ldr x30, [sp], #16
+    adrp x0, var_doubled
+    add x0, x0, :lo12:var_doubled
+    adrp x1, text_3
+    add x1, x1, :lo12:text_3
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    // print to fd 1
+    adrp x0, var_doubled
+    add x0, x0, :lo12:var_doubled
+    str x30, [sp, #-16]!
+    bl ven_str_len
+    ldr x30, [sp], #16
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // declare literal
+    adrp x0, var_literal
+    add x0, x0, :lo12:var_literal
+    adrp x1, str_4
+    add x1, x1, :lo12:str_4
+    mov x2, #256
+    str x30, [sp, #-16]!
+    bl ven_str_copy
+    ldr x30, [sp], #16
+    // print to fd 1
+    adrp x0, var_literal
+    add x0, x0, :lo12:var_literal
+    str x30, [sp, #-16]!
+    bl ven_str_len
+    ldr x30, [sp], #16
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // print to fd 1
+    // concat()
+    adrp x0, text_4
+    add x0, x0, :lo12:text_4
+    mov x3, x0
+    add x3, x3, #255
+    adrp x1, var_literal
+    add x1, x1, :lo12:var_literal
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x1, var_wide
+    add x1, x1, :lo12:var_wide
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    // upper()
+    adrp x0, text_5
+    add x0, x0, :lo12:text_5
+    mov x3, x0
+    add x3, x3, #255
+    adrp x1, text_4
+    add x1, x1, :lo12:text_4
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x0, text_5
+    add x0, x0, :lo12:text_5
+    mov x1, #97
+    mov x2, #-32
+    str x30, [sp, #-16]!
+    bl ven_str_case
+    ldr x30, [sp], #16
+    adrp x0, text_5
+    add x0, x0, :lo12:text_5
+    str x30, [sp, #-16]!
+    bl ven_str_len
+    ldr x30, [sp], #16
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // exit(0)
+    mov x0, #0
+    mov x8, #93   // syscall: Exit
+    svc #0
+    // ---- runtime ----
+ven_print_int:
+    adrp x2, num_buf
+    add x2, x2, :lo12:num_buf
+    add x2, x2, #32
+    mov x3, #0
+    cmp x0, #0
+    b.lt ven_print_int_digits
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    mov x3, #1
+ven_print_int_digits:
+    mov x4, x0
+    mov x5, #10
+    sdiv x9, x4, x5
+    msub x4, x9, x5, x4
+    mov x5, #48
+    sub x5, x5, x4
+    sub x2, x2, #1
+    strb w5, [x2]
+    mov x5, #10
+    sdiv x0, x0, x5
+    cmp x0, #0
+    b.ne ven_print_int_digits
+    cmp x3, #0
+    b.ne ven_print_int_write
+    sub x2, x2, #1
+    mov x5, #45
+    strb w5, [x2]
+ven_print_int_write:
+    adrp x4, num_buf
+    add x4, x4, :lo12:num_buf
+    add x4, x4, #32
+    sub x4, x4, x2
+    mov x0, x1
+    mov x1, x2
+    mov x2, x4
+    mov x8, #64   // syscall: Write
+    svc #0
+    ret
+ven_print_uint:
+    adrp x2, num_buf
+    add x2, x2, :lo12:num_buf
+    add x2, x2, #32
+ven_print_uint_digits:
+    mov x3, x0
+    mov x5, #10
+    udiv x3, x3, x5
+    mov x4, x3
+    mul x4, x4, x5
+    mov x5, x0
+    sub x5, x5, x4
+    add x5, x5, #48
+    sub x2, x2, #1
+    strb w5, [x2]
+    mov x0, x3
+    cmp x0, #0
+    b.ne ven_print_uint_digits
+    b ven_print_int_write
+ven_print_cstr:
+    mov x2, x0
+ven_print_cstr_len:
+    ldrb w3, [x2]
+    cmp x3, #0
+    b.eq ven_print_cstr_write
+    add x2, x2, #1
+    b ven_print_cstr_len
+ven_print_cstr_write:
+    sub x2, x2, x0
+    cmp x2, #0
+    b.eq ven_print_cstr_done
+    mov x3, x0
+    mov x0, x1
+    mov x1, x3
+    mov x8, #64   // syscall: Write
+    svc #0
+ven_print_cstr_done:
+    ret
+ven_read_line:
+    mov x4, x0
+    mov x5, x0
+    add x5, x5, x1
+ven_read_line_next:
+    mov x0, #0
+    mov x1, x4
+    mov x2, #1
+    mov x8, #63   // syscall: Read
+    svc #0
+    cmp x0, #1
+    b.lt ven_read_line_done
+    ldrb w3, [x4]
+    cmp x3, #10
+    b.eq ven_read_line_done
+    cmp x3, #13
+    b.eq ven_read_line_next
+    cmp x4, x5
+    b.ge ven_read_line_next
+    add x4, x4, #1
+    b ven_read_line_next
+ven_read_line_done:
+    mov x3, #0
+    strb w3, [x4]
+    ret
+ven_parse_int:
+    mov x1, x0
+    mov x0, #0
+    mov x2, #0
+ven_parse_int_space:
+    ldrb w3, [x1]
+    cmp x3, #32
+    b.ne ven_parse_int_sign
+    add x1, x1, #1
+    b ven_parse_int_space
+ven_parse_int_sign:
+    cmp x3, #45
+    b.ne ven_parse_int_plus
+    mov x2, #1
+    add x1, x1, #1
+    b ven_parse_int_digit
+ven_parse_int_plus:
+    cmp x3, #43
+    b.ne ven_parse_int_digit
+    add x1, x1, #1
+ven_parse_int_digit:
+    ldrb w3, [x1]
+    cmp x3, #48
+    b.lt ven_parse_int_end
+    cmp x3, #57
+    b.gt ven_parse_int_end
+    sub x3, x3, #48
+    mov x4, #-1
+    sub x4, x4, x3
+    mov x5, #10
+    udiv x4, x4, x5
+    cmp x0, x4
+    b.hi ven_parse_int_range
+    mov x4, #10
+    mul x0, x0, x4
+    add x0, x0, x3
+    add x1, x1, #1
+    b ven_parse_int_digit
+ven_parse_int_end:
+    mov x1, #0
+    cmp x2, #0
+    b.eq ven_parse_int_positive
+    movz x4, #0
+    movk x4, #32768, lsl #48
+    cmp x0, x4
+    b.hi ven_parse_int_range
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    ret
+ven_parse_int_positive:
+    cmp x0, #0
+    b.ge ven_parse_int_done
+    mov x1, #1
+ven_parse_int_done:
+    ret
+ven_parse_int_range:
+    mov x0, #0
+    mov x1, #0
+    ret
+ven_compare:
+    cmp x2, #0
+    b.ne ven_compare_b
+    cmp x0, #0
+    b.lt ven_compare_negative
+ven_compare_b:
+    cmp x3, #0
+    b.ne ven_compare_unsigned
+    cmp x1, #0
+    b.lt ven_compare_greater
+ven_compare_unsigned:
+    cmp x0, x1
+    b.lo ven_compare_less
+    b.hi ven_compare_greater
+    mov x0, #0
+    ret
+ven_compare_negative:
+    cmp x3, #0
+    b.ne ven_compare_less
+    cmp x0, x1
+    b.lt ven_compare_less
+    b.gt ven_compare_greater
+    mov x0, #0
+    ret
+ven_compare_less:
+    mov x0, #-1
+    ret
+ven_compare_greater:
+    mov x0, #1
+    ret
+ven_str_cmp:
+    ldrb w2, [x0]
+    ldrb w3, [x1]
+    cmp x2, x3
+    b.lt ven_str_cmp_less
+    b.gt ven_str_cmp_greater
+    cmp x2, #0
+    b.eq ven_str_cmp_equal
+    add x0, x0, #1
+    add x1, x1, #1
+    b ven_str_cmp
+ven_str_cmp_less:
+    mov x0, #-1
+    ret
+ven_str_cmp_greater:
+    mov x0, #1
+    ret
+ven_str_cmp_equal:
+    mov x0, #0
+    ret
+ven_str_copy:
+    mov x3, x0
+    add x3, x3, x2
+    sub x3, x3, #1
+    mov x2, x0
+ven_str_copy_next:
+    cmp x0, x3
+    b.ge ven_str_copy_cut
+    ldrb w4, [x1]
+    cmp x4, #0
+    b.eq ven_str_copy_done
+    strb w4, [x0]
+    add x0, x0, #1
+    add x1, x1, #1
+    b ven_str_copy_next
+ven_str_copy_cut:
+    cmp x0, x2
+    b.ls ven_str_copy_done
+    ldrb w4, [x1]
+    cmp x4, #128
+    b.lo ven_str_copy_done
+    cmp x4, #191
+    b.hi ven_str_copy_done
+    sub x0, x0, #1
+    sub x1, x1, #1
+    b ven_str_copy_cut
+ven_str_copy_done:
+    mov x4, #0
+    strb w4, [x0]
+    ret
+    // ---- string operations ----
+ven_str_put:
+    cmp x1, x2
+    b.hs ven_str_put_done
+    ldrb w4, [x1]
+    cmp x4, #0
+    b.eq ven_str_put_done
+    cmp x0, x3
+    b.hs ven_str_put_cut
+    strb w4, [x0]
+    add x0, x0, #1
+    add x1, x1, #1
+    b ven_str_put
+ven_str_put_cut:
+    cmp x4, #128
+    b.lo ven_str_put_full
+    cmp x4, #191
+    b.hi ven_str_put_full
+    sub x0, x0, #1
+    sub x1, x1, #1
+    ldrb w4, [x1]
+    b ven_str_put_cut
+ven_str_put_full:
+    mov x3, x0
+ven_str_put_done:
+    mov x4, #0
+    strb w4, [x0]
+    ret
+ven_str_len:
+    mov x1, x0
+    mov x0, #0
+ven_str_len_next:
+    ldrb w2, [x1]
+    cmp x2, #0
+    b.eq ven_str_len_done
+    add x1, x1, #1
+    sub x2, x2, #128
+    cmp x2, #64
+    b.lo ven_str_len_next
+    add x0, x0, #1
+    b ven_str_len_next
+ven_str_len_done:
+    ret
+ven_str_skip:
+    cmp x1, #0
+    b.le ven_str_skip_done
+    ldrb w2, [x0]
+    cmp x2, #0
+    b.eq ven_str_skip_done
+    add x0, x0, #1
+ven_str_skip_rest:
+    ldrb w2, [x0]
+    sub x2, x2, #128
+    cmp x2, #64
+    b.hs ven_str_skip_next
+    add x0, x0, #1
+    b ven_str_skip_rest
+ven_str_skip_next:
+    sub x1, x1, #1
+    b ven_str_skip
+ven_str_skip_done:
+    ret
+ven_str_case:
+    ldrb w3, [x0]
+    cmp x3, #0
+    b.eq ven_str_case_done
+    mov x4, x3
+    sub x4, x4, x1
+    cmp x4, #26
+    b.hs ven_str_case_next
+    add x3, x3, x2
+    strb w3, [x0]
+ven_str_case_next:
+    add x0, x0, #1
+    b ven_str_case
+ven_str_case_done:
+    ret
+ven_str_trim:
+    ldrb w2, [x0]
+    cmp x2, #32
+    b.eq ven_str_trim_front
+    cmp x2, #9
+    b.eq ven_str_trim_front
+    cmp x2, #10
+    b.eq ven_str_trim_front
+    cmp x2, #13
+    b.eq ven_str_trim_front
+    mov x1, x0
+ven_str_trim_end:
+    ldrb w2, [x1]
+    cmp x2, #0
+    b.eq ven_str_trim_back
+    add x1, x1, #1
+    b ven_str_trim_end
+ven_str_trim_back:
+    cmp x1, x0
+    b.ls ven_str_trim_done
+    mov x3, x1
+    sub x3, x3, #1
+    ldrb w2, [x3]
+    cmp x2, #32
+    b.eq ven_str_trim_drop
+    cmp x2, #9
+    b.eq ven_str_trim_drop
+    cmp x2, #10
+    b.eq ven_str_trim_drop
+    cmp x2, #13
+    b.eq ven_str_trim_drop
+ven_str_trim_done:
+    ret
+ven_str_trim_front:
+    add x0, x0, #1
+    b ven_str_trim
+ven_str_trim_drop:
+    mov x1, x3
+    b ven_str_trim_back
+ven_str_find:
+    mov x2, x0
+    mov x3, x1
+ven_str_find_next:
+    ldrb w5, [x3]
+    cmp x5, #0
+    b.eq ven_str_find_done
+    ldrb w4, [x2]
+    cmp x4, x5
+    b.ne ven_str_find_miss
+    add x2, x2, #1
+    add x3, x3, #1
+    b ven_str_find_next
+ven_str_find_miss:
+    ldrb w4, [x0]
+    cmp x4, #0
+    b.eq ven_str_find_none
+    add x0, x0, #1
+    b ven_str_find
+ven_str_find_none:
+    mov x0, #0
+ven_str_find_done:
+    ret
+ven_str_contains:
+    str x30, [sp, #-16]!
+    bl ven_str_find
+    ldr x30, [sp], #16
+    cmp x0, #0
+    b.eq ven_str_contains_done
+    mov x0, #1
+ven_str_contains_done:
+    ret
+ven_str_prefix:
+    ldrb w3, [x1]
+    cmp x3, #0
+    b.eq ven_str_prefix_yes
+    ldrb w2, [x0]
+    cmp x2, x3
+    b.ne ven_str_prefix_no
+    add x0, x0, #1
+    add x1, x1, #1
+    b ven_str_prefix
+ven_str_prefix_yes:
+    mov x0, #1
+    ret
+ven_str_prefix_no:
+    mov x0, #0
+    ret
+ven_str_replace:
+    adrp x9, ven_rep_at
+    str x1, [x9, :lo12:ven_rep_at]
+    adrp x9, ven_rep_from
+    str x2, [x9, :lo12:ven_rep_from]
+    adrp x9, ven_rep_to
+    str x3, [x9, :lo12:ven_rep_to]
+    mov x3, x0
+    add x3, x3, #255
+    adrp x9, ven_rep_pos
+    str x0, [x9, :lo12:ven_rep_pos]
+    adrp x9, ven_rep_limit
+    str x3, [x9, :lo12:ven_rep_limit]
+    ldrb w4, [x2]
+    cmp x4, #0
+    b.eq ven_str_replace_rest
+ven_str_replace_next:
+    adrp x9, ven_rep_at
+    ldr x0, [x9, :lo12:ven_rep_at]
+    adrp x9, ven_rep_from
+    ldr x1, [x9, :lo12:ven_rep_from]
+    str x30, [sp, #-16]!
+    bl ven_str_find
+    ldr x30, [sp], #16
+    cmp x0, #0
+    b.eq ven_str_replace_rest
+    adrp x9, ven_rep_next
+    str x2, [x9, :lo12:ven_rep_next]
+    mov x2, x0
+    adrp x9, ven_rep_at
+    ldr x1, [x9, :lo12:ven_rep_at]
+    adrp x9, ven_rep_pos
+    ldr x0, [x9, :lo12:ven_rep_pos]
+    adrp x9, ven_rep_limit
+    ldr x3, [x9, :lo12:ven_rep_limit]
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x9, ven_rep_to
+    ldr x1, [x9, :lo12:ven_rep_to]
+    mov x2, #-1
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    adrp x9, ven_rep_pos
+    str x0, [x9, :lo12:ven_rep_pos]
+    adrp x9, ven_rep_limit
+    str x3, [x9, :lo12:ven_rep_limit]
+    adrp x9, ven_rep_next
+    ldr x0, [x9, :lo12:ven_rep_next]
+    adrp x9, ven_rep_at
+    str x0, [x9, :lo12:ven_rep_at]
+    b ven_str_replace_next
+ven_str_replace_rest:
+    adrp x9, ven_rep_pos
+    ldr x0, [x9, :lo12:ven_rep_pos]
+    adrp x9, ven_rep_at
+    ldr x1, [x9, :lo12:ven_rep_at]
+    mov x2, #-1
+    adrp x9, ven_rep_limit
+    ldr x3, [x9, :lo12:ven_rep_limit]
+    str x30, [sp, #-16]!
+    bl ven_str_put
+    ldr x30, [sp], #16
+    ret
+    // ---- integer overflow ----
+ven_fit:
+    cmp x2, #0
+    b.ne ven_fit_signed
+    cmn x3, #1
+    b.ne ven_fit_signed
+    cmp x1, #0
+    b.ne ven_fit_ok
+    cmp x0, #0
+    b.ge ven_fit_ok
+    ret
+ven_fit_signed:
+    cmp x1, #0
+    b.eq ven_fit_range
+    cmp x0, #0
+    b.lt ven_fit_above
+ven_fit_range:
+    cmp x0, x2
+    b.lt ven_fit_below
+    cmp x0, x3
+    b.gt ven_fit_above
+ven_fit_ok:
+    ret
+ven_fit_below:
+    b ven_fit_wrap
+ven_fit_above:
+    b ven_fit_wrap
+ven_fit_wrap:
+    mov x4, x3
+    sub x4, x4, x2
+    add x4, x4, #1
+    cmp x4, #0
+    b.eq ven_fit_ok
+    sdiv x9, x0, x4
+    msub x0, x9, x4, x0
+    cmp x0, x2
+    b.ge ven_fit_wrap_high
+    add x0, x0, x4
+ven_fit_wrap_high:
+    cmp x0, x3
+    b.le ven_fit_ok
+    sub x0, x0, x4
+    ret
+ven_add_i64:
+    mov x2, x0
+    add x0, x0, x1
+    cmp x1, #0
+    b.lt ven_add_i64_negative
+    cmp x0, x2
+    b.lt ven_add_i64_overflow
+    ret
+ven_add_i64_negative:
+    cmp x0, x2
+    b.gt ven_add_i64_overflow
+    ret
+ven_add_i64_overflow:
+    ret
+ven_sub_i64:
+    mov x2, x0
+    sub x0, x0, x1
+    cmp x1, #0
+    b.lt ven_sub_i64_negative
+    cmp x0, x2
+    b.gt ven_sub_i64_overflow
+    ret
+ven_sub_i64_negative:
+    cmp x0, x2
+    b.lt ven_sub_i64_overflow
+    ret
+ven_sub_i64_overflow:
+    ret
+ven_mul_i64:
+    mov x2, x0
+    mul x0, x0, x1
+    cmn x2, #1
+    b.ne ven_mul_i64_divide
+    movz x3, #0
+    movk x3, #32768, lsl #48
+    cmp x1, x3
+    b.eq ven_mul_i64_overflow
+    ret
+ven_mul_i64_divide:
+    cmp x2, #0
+    b.eq ven_mul_i64_done
+    mov x3, x0
+    sdiv x3, x3, x2
+    cmp x3, x1
+    b.ne ven_mul_i64_overflow
+ven_mul_i64_done:
+    ret
+ven_mul_i64_overflow:
+    ret
+ven_div_i64:
+    cmn x1, #1
+    b.ne ven_div_i64_divide
+    movz x3, #0
+    movk x3, #32768, lsl #48
+    cmp x0, x3
+    b.eq ven_div_i64_overflow
+ven_div_i64_divide:
+    sdiv x0, x0, x1
+    ret
+ven_div_i64_overflow:
+    ret
+ven_add_u64:
+    mov x2, x0
+    add x0, x0, x1
+    cmp x0, x2
+    b.lo ven_add_u64_overflow
+    ret
+ven_add_u64_overflow:
+    ret
+ven_sub_u64:
+    mov x2, x0
+    sub x0, x0, x1
+    cmp x2, x1
+    b.lo ven_sub_u64_overflow
+    ret
+ven_sub_u64_overflow:
+    ret
+ven_mul_u64:
+    mov x2, x0
+    mul x0, x0, x1
+    cmp x2, #0
+    b.eq ven_mul_u64_done
+    mov x3, x0
+    udiv x3, x3, x2
+    cmp x3, x1
+    b.ne ven_mul_u64_overflow
+ven_mul_u64_done:
+    ret
+ven_mul_u64_overflow:
+    ret
@@ -0,0 +1,373 @@
+// Generated by the Ven compiler
+#include <ctype.h>
+#include <errno.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static char ven_line[256];
+
+/* Wide enough for every value of every Ven integer type. */
+typedef __int128 ven_wide;
+
+/* Reads one line without its newline; carriage returns and overflow are dropped. */
+static void ven_read_line(char *buf, size_t size) {
+    size_t len = 0;
+    int c;
+    while ((c = getchar()) != EOF && c != '\n') {
+        if (c != '\r' && len + 1 < size) {
+            buf[len++] = (char)c;
+        }
+    }
+    buf[len] = '\0';
+}
+
+/* Copies as much of src as fits without splitting a character, always terminating dest. */
+static void ven_copy(char *dest, const char *src, size_t size) {
+    size_t len = strlen(src);
+    if (len >= size) {
+        len = size - 1;
+        while (len > 0 && ((unsigned char)src[len] & 0xC0) == 0x80) {
+            len--;
+        }
+    }
+    memmove(dest, src, len);
+    dest[len] = '\0';
+}
+
+static bool ven_blank(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return *text == '\0';
+}
+
+/* Text that is not a whole number from INT64_MIN to UINT64_MAX reads as 0. */
+static ven_wide ven_to_int(const char *text) {
+    char *end;
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    errno = 0;
+    if (*text == '-') {
+        long long value = strtoll(text, &end, 10);
+        return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+    }
+    unsigned long long value = strtoull(text, &end, 10);
+    return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+}
+
+static double ven_to_float(const char *text) {
+    char *end;
+    double value = strtod(text, &end);
+    return end == text || !ven_blank(end) ? 0.0 : value;
+}
+
+/* Only the word true reads as true. */
+static bool ven_to_bool(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return strncmp(text, "true", 4) == 0 && ven_blank(text + 4);
+}
+
+/* The shortest digits that read back as the same double, written out without
+   an exponent, like 0.001 or 1000000, into buf of 400 bytes. */
+static const char *ven_float_text(char *buf, double x) {
+    if (x != x) {
+        return "NaN";
+    }
+    /* Only an infinity is not finite after x - x */
+    if (x - x != 0) {
+        return x < 0 ? "-inf" : "inf";
+    }
+    char shortest[32];
+    for (int precision = 1; precision <= 17; precision++) {
+        snprintf(shortest, sizeof shortest, "%.*e", precision - 1, x);
+        if (strtod(shortest, NULL) == x) {
+            break;
+        }
+    }
+    char digits[24];
+    int len = 0;
+    const char *at = shortest;
+    char *out = buf;
+    if (*at == '-') {
+        *out++ = *at++;
+    }
+    for (; *at != 'e'; at++) {
+        if (*at != '.') {
+            digits[len++] = *at;
+        }
+    }
+    int exponent = atoi(at + 1);
+    if (exponent < 0) {
+        *out++ = '0';
+        *out++ = '.';
+        for (int i = -1; i > exponent; i--) {
+            *out++ = '0';
+        }
+        memcpy(out, digits, len);
+        out += len;
+    } else if (exponent + 1 >= len) {
+        memcpy(out, digits, len);
+        out += len;
+        for (int i = len; i <= exponent; i++) {
+            *out++ = '0';
+        }
+    } else {
+        memcpy(out, digits, exponent + 1);
+        out += exponent + 1;
+        *out++ = '.';
+        memcpy(out, digits + exponent + 1, len - exponent - 1);
+        out += len - exponent - 1;
+    }
+    *out = '\0';
+    return buf;
+}
+
+static void ven_print_float(FILE *out, double x) {
+    char buf[400];
+    fputs(ven_float_text(buf, x), out);
+}
+
+static ven_wide ven_fit(ven_wide value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value < min || value > max) {
+        ven_wide span = max - min + 1;
+        value %= span;
+        if (value < min) {
+            value += span;
+        } else if (value > max) {
+            value -= span;
+        }
+    }
+    return value;
+}
+
+/* Floats never wrap: they are truncated and clamp at the limits. */
+static ven_wide ven_fit_float(double value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value != value) {
+        return 0;
+    }
+    /* Truncate toward zero; doubles past 2^63 are whole already */
+    if (value > -9223372036854775808.0 && value < 9223372036854775808.0) {
+        value = (double)(long long)value;
+    }
+    return value < (double)min ? min : value >= (double)max + 1.0 ? max : (ven_wide)value;
+}
+
+/* A u64 product, which can exceed ven_wide. */
+static uint64_t ven_mul_u64(uint64_t a, uint64_t b, const char *name, int line) {
+    (void)name;
+    (void)line;
+    return a * b;
+}
+
+/* Makes room for one more element; running out of memory ends the program. */
+static void *ven_grow(void *items, size_t *cap, size_t len, size_t size) {
+    if (len < *cap) {
+        return items;
+    }
+    *cap = *cap ? *cap * 2 : 8;
+    items = realloc(items, *cap * size);
+    if (items == NULL) {
+        fflush(stdout);
+        fprintf(stderr, "runtime error: out of memory\n");
+        exit(1);
+    }
+    return items;
+}
+
+/* The position of index in the array name of len elements. */
+static size_t ven_index(ven_wide index, size_t len, const char *name, int line) {
+    if (index < 0 || index >= (ven_wide)len) {
+        fflush(stdout);
+        if (index < 0) {
+            fprintf(stderr, "runtime error: index %lld is out of bounds for '%s' (length %zu) on line %d\n", (long long)index, name, len, line);
+        } else {
+            fprintf(stderr, "runtime error: index %llu is out of bounds for '%s' (length %zu) on line %d\n", (unsigned long long)index, name, len, line);
+        }
+        exit(1);
+    }
+    return (size_t)index;
+}
+
+typedef struct {
+    size_t *slots;
+    size_t cap;
+} ven_table;
+
+/* FNV-1a over the bytes of a text key or a number key. */
+static size_t ven_hash(const char *text, int64_t number) {
+    const unsigned char *bytes = text ? (const unsigned char *)text : (const unsigned char *)&number;
+    size_t len = text ? strlen(text) : sizeof number;
+    uint64_t hash = 14695981039346656037ULL;
+    for (size_t i = 0; i < len; i++) {
+        hash = (hash ^ bytes[i]) * 1099511628211ULL;
+    }
+    return (size_t)hash;
+}
+
+/* The slot holding the key, or the empty slot where it would go. */
+static size_t ven_slot(const ven_table *table, const void *items, size_t stride, const char *text, int64_t number) {
+    size_t mask = table->cap - 1;
+    for (size_t at = ven_hash(text, number) & mask;; at = (at + 1) & mask) {
+        size_t entry = table->slots[at];
+        if (entry == 0) {
+            return at;
+        }
+        const char *key = (const char *)items + (entry - 1) * stride;
+        if (text ? strcmp(key, text) == 0 : memcmp(key, &number, sizeof number) == 0) {
+            return at;
+        }
+    }
+}
+
+/* The position of the key among len entries, or len when it is missing. */
+static size_t ven_find(const ven_table *table, const void *items, size_t len, size_t stride, const char *text, int64_t number) {
+    if (table->cap == 0) {
+        return len;
+    }
+    size_t entry = table->slots[ven_slot(table, items, stride, text, number)];
+    return entry == 0 ? len : entry - 1;
+}
+
+/* The position of the key, which must be in the map name. */
+static size_t ven_lookup(const ven_table *table, const void *items, size_t len, size_t stride, const char *text, int64_t number, const char *name, int line) {
+    size_t at = ven_find(table, items, len, stride, text, number);
+    if (at == len) {
+        fflush(stdout);
+        if (text) {
+            fprintf(stderr, "runtime error: key \"%s\" is not in '%s' on line %d\n", text, name, line);
+        } else {
+            fprintf(stderr, "runtime error: key %lld is not in '%s' on line %d\n", (long long)number, name, line);
+        }
+        exit(1);
+    }
+    return at;
+}
+
+/* Indexes the last of len entries, rebuilding the table when it is half full. */
+static void ven_table_add(ven_table *table, const void *items, size_t len, size_t stride, bool text) {
+    size_t first = len - 1;
+    if (len * 2 > table->cap) {
+        free(table->slots);
+        table->cap = table->cap ? table->cap * 2 : 16;
+        table->slots = calloc(table->cap, sizeof *table->slots);
+        if (table->slots == NULL) {
+            fflush(stdout);
+            fprintf(stderr, "runtime error: out of memory\n");
+            exit(1);
+        }
+        first = 0;
+    }
+    for (size_t i = first; i < len; i++) {
+        const char *key = (const char *)items + i * stride;
+        int64_t number = 0;
+        if (!text) {
+            memcpy(&number, key, sizeof number);
+        }
+        table->slots[ven_slot(table, items, stride, text ? key : NULL, number)] = i + 1;
+    }
+}
+
+struct { struct { char key[256]; int64_t value; } *items; size_t len, cap; ven_table table; } ages = {0};
+struct { struct { int64_t key; bool value; } *items; size_t len, cap; ven_table table; } seen = {0};
+char who[256] = "";
+int64_t n = 0;
+bool known = false;
+
+int main(void) {
+    ages.len = 0;
+    ages.table.cap = 0;
+    seen.len = 0;
+    seen.table.cap = 0;
+    ven_copy(who, "bob", sizeof who);
+    n = 7;
+    {
+        char ven_key[256];
+        ven_copy(ven_key, "ann", sizeof ven_key);
+        size_t ven_at = ven_find(&ages.table, ages.items, ages.len, sizeof *ages.items, ven_key, 0);
+        if (ven_at == ages.len) {
+            ages.items = ven_grow(ages.items, &ages.cap, ages.len, sizeof *ages.items);
+        }
+        ages.items[ven_at].value = 31;
+        if (ven_at == ages.len) {
+            memcpy(ages.items[ven_at].key, ven_key, sizeof ven_key);
+            ages.len++;
+            ven_table_add(&ages.table, ages.items, ages.len, sizeof *ages.items, true);
+        }
+    }
+    {
+        char ven_key[256];
+        ven_copy(ven_key, "bob", sizeof ven_key);
+        size_t ven_at = ven_find(&ages.table, ages.items, ages.len, sizeof *ages.items, ven_key, 0);
+        if (ven_at == ages.len) {
+            ages.items = ven_grow(ages.items, &ages.cap, ages.len, sizeof *ages.items);
+        }
+        ages.items[ven_at].value = 40;
+        if (ven_at == ages.len) {
+            memcpy(ages.items[ven_at].key, ven_key, sizeof ven_key);
+            ages.len++;
+            ven_table_add(&ages.table, ages.items, ages.len, sizeof *ages.items, true);
+        }
+    }
+    {
+        char ven_key[256];
+        ven_copy(ven_key, "ann", sizeof ven_key);
+        size_t ven_at = ven_find(&ages.table, ages.items, ages.len, sizeof *ages.items, ven_key, 0);
+        if (ven_at == ages.len) {
+            ages.items = ven_grow(ages.items, &ages.cap, ages.len, sizeof *ages.items);
+        }
+        ages.items[ven_at].value = 32;
+        if (ven_at == ages.len) {
+            memcpy(ages.items[ven_at].key, ven_key, sizeof ven_key);
+            ages.len++;
+            ven_table_add(&ages.table, ages.items, ages.len, sizeof *ages.items, true);
+        }
+    }
+    {
+        int64_t ven_key = 7;
+        size_t ven_at = ven_find(&seen.table, seen.items, seen.len, sizeof *seen.items, NULL, ven_key);
+        if (ven_at == seen.len) {
+            seen.items = ven_grow(seen.items, &seen.cap, seen.len, sizeof *seen.items);
+        }
+        seen.items[ven_at].value = true;
+        if (ven_at == seen.len) {
+            seen.items[ven_at].key = ven_key;
+            seen.len++;
+            ven_table_add(&seen.table, seen.items, seen.len, sizeof *seen.items, false);
+        }
+    }
+    if ((ven_find(&ages.table, ages.items, ages.len, sizeof *ages.items, "ann", 0) < ages.len) && !(ven_find(&seen.table, seen.items, seen.len, sizeof *seen.items, NULL, 3) < seen.len)) {
+        fputs("bob is ", stdout);
+        fprintf(stdout, "%lld", (long long)ages.items[ven_lookup(&ages.table, ages.items, ages.len, sizeof *ages.items, who, 0, "ages", 11)].value);
+        fputs(", ", stdout);
+        fprintf(stdout, "%lld", (long long)(int64_t)ages.len);
+        fputs(" people, seen ", stdout);
+        fputs(seen.items[ven_lookup(&seen.table, seen.items, seen.len, sizeof *seen.items, NULL, n, "seen", 11)].value ? "true" : "false", stdout);
+        fputs("\n", stdout);
+    }
+    known = (ven_find(&ages.table, ages.items, ages.len, sizeof *ages.items, "cy", 0) < ages.len);
+    fputs(known ? "true" : "false", stdout);
+    fputs("\n", stdout);
+    fputs("{", stdout);
+    for (size_t ven_i = 0; ven_i < ages.len; ven_i++) {
+        if (ven_i > 0) {
+            fputs(", ", stdout);
+        }
+        fputs(ages.items[ven_i].key, stdout);
+        fputs(": ", stdout);
+        fprintf(stdout, "%lld", (long long)ages.items[ven_i].value);
+    }
+    fputs("}\n", stdout);
+    return 0;
+}
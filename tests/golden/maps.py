@@ -0,0 +1,88 @@
+# Generated by the Ven compiler
+import sys
+
+
+def _read_line():
+    # A line holds at most 255 bytes, as on the native targets
+    line = sys.stdin.readline().rstrip("\r\n").encode()
+    return line[:255].decode(errors="ignore")
+
+
+# Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+def _to_int(text):
+    try:
+        value = int(text.strip())
+    except ValueError:
+        return 0
+    return value if -(2**63) <= value < 2**64 else 0
+
+
+def _to_float(text):
+    try:
+        return float(text.strip())
+    except ValueError:
+        return 0.0
+
+
+def _int_div(a, b):
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+
+
+def _fit(value, lo, hi, name, type, line):
+    return (value - lo) % (hi - lo + 1) + lo
+
+
+# Floats never wrap: they are truncated and clamp at the limits
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+
+
+def _shown(value):
+    if isinstance(value, bool):
+        return str(value).lower()
+    return _float_text(value) if isinstance(value, float) else str(value)
+
+
+def _get(entries, key, name, line):
+    if key not in entries:
+        sys.stdout.flush()
+        shown = f'"{key}"' if isinstance(key, str) else key
+        print(f"runtime error: key {shown} is not in '{name}' on line {line}", file=sys.stderr)
+        sys.exit(1)
+    return entries[key]
+
+
+def _show_map(entries):
+    return "{" + ", ".join(f"{key}: {_shown(value)}" for key, value in entries.items()) + "}"
+
+
+ages = {}
+seen = {}
+who = ""
+n = 0
+known = False
+
+ages = {}
+seen = {}
+who = "bob"
+n = 7
+_at = "ann"
+ages[_at] = 31
+_at = "bob"
+ages[_at] = 40
+_at = "ann"
+ages[_at] = 32
+_at = 7
+seen[_at] = True
+if (("ann" in ages)) and (not ((3 in seen))):
+    print(f"bob is {_get(ages, who, 'ages', 11)}, {len(ages)} people, seen {str(_get(seen, n, 'seen', 11)).lower()}")
+known = ("cy" in ages)
+print(str(known).lower())
+print(_show_map(ages))
@@ -0,0 +1,153 @@
+// Generated by the Ven compiler
+#![allow(dead_code, unused_mut, unused_assignments, unused_variables, unused_parens)]
+use std::io::BufRead;
+
+fn ven_read_line() -> String {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).unwrap_or(0);
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+    // A line holds at most 255 bytes, as on the native targets
+    let mut end = line.len().min(255);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+    line
+}
+
+// Text that is not a whole number from i64::MIN to u64::MAX reads as 0
+fn ven_to_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+        .unwrap_or(0)
+}
+
+fn ven_to_float(text: &str) -> f64 {
+    text.trim().parse().unwrap_or(0.0)
+}
+
+fn ven_to_bool(text: &str) -> bool {
+    text.trim() == "true"
+}
+
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        let span = max - min + 1;
+        let low = value.rem_euclid(span);
+        return if low > max { low - span } else { low };
+    }
+    value
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => (a as u64).wrapping_mul(b as u64) as i128,
+    }
+}
+
+struct VenMap<K, V> {
+    index: std::collections::HashMap<K, usize>,
+    entries: Vec<(K, V)>,
+}
+
+// How a missing key is shown: text in double quotes
+trait VenKey: std::hash::Hash + Eq + Clone + std::fmt::Display {
+    fn shown(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl VenKey for String {
+    fn shown(&self) -> String {
+        format!("\"{}\"", self)
+    }
+}
+
+impl VenKey for i64 {}
+
+impl<K: VenKey, V: Clone + std::fmt::Display> VenMap<K, V> {
+    fn new() -> Self {
+        VenMap { index: std::collections::HashMap::new(), entries: Vec::new() }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn has<Q: std::hash::Hash + Eq + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: std::borrow::Borrow<Q>,
+    {
+        self.index.contains_key(key)
+    }
+
+    fn get<Q: std::hash::Hash + Eq + ToOwned<Owned = K> + ?Sized>(&self, key: &Q, name: &str, line: u32) -> V
+    where
+        K: std::borrow::Borrow<Q>,
+    {
+        match self.index.get(key) {
+            Some(&at) => self.entries[at].1.clone(),
+            None => {
+                eprintln!("runtime error: key {} is not in '{}' on line {}", key.to_owned().shown(), name, line);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // A new key goes last; an existing one keeps its place
+    fn set(&mut self, key: K, value: V) {
+        match self.index.get(&key) {
+            Some(&at) => self.entries[at].1 = value,
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+}
+
+impl<K: std::fmt::Display, V: std::fmt::Display> std::fmt::Display for VenMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("{")?;
+        for (i, (key, value)) in self.entries.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", key, value)?;
+        }
+        f.write_str("}")
+    }
+}
+
+fn main() {
+    let mut ages: VenMap<String, i64> = VenMap::new();
+    let mut seen: VenMap<i64, bool> = VenMap::new();
+    let mut who = String::new();
+    let mut n: i64 = 0;
+    let mut known = false;
+    ages = VenMap::new();
+    seen = VenMap::new();
+    who = "bob".to_string();
+    n = 7;
+    ages.set("ann".to_string(), 31);
+    ages.set("bob".to_string(), 40);
+    ages.set("ann".to_string(), 32);
+    seen.set(7, true);
+    if (ages.has("ann") && !(seen.has(&3i64))) {
+        println!("bob is {}, {} people, seen {}", ages.get(who.as_str(), "ages", 11), (ages.len() as i64), seen.get(&n, "seen", 11));
+    }
+    known = ages.has("cy");
+    println!("{}", known);
+    println!("{}", ages);
+}
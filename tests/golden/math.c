@@ -0,0 +1,312 @@
+// Generated by the Ven compiler
+#include <ctype.h>
+#include <errno.h>
+#include <math.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static char ven_line[256];
+
+/* Wide enough for every value of every Ven integer type. */
+typedef __int128 ven_wide;
+
+/* Reads one line without its newline; carriage returns and overflow are dropped. */
+static void ven_read_line(char *buf, size_t size) {
+    size_t len = 0;
+    int c;
+    while ((c = getchar()) != EOF && c != '\n') {
+        if (c != '\r' && len + 1 < size) {
+            buf[len++] = (char)c;
+        }
+    }
+    buf[len] = '\0';
+}
+
+/* Copies as much of src as fits without splitting a character, always terminating dest. */
+static void ven_copy(char *dest, const char *src, size_t size) {
+    size_t len = strlen(src);
+    if (len >= size) {
+        len = size - 1;
+        while (len > 0 && ((unsigned char)src[len] & 0xC0) == 0x80) {
+            len--;
+        }
+    }
+    memmove(dest, src, len);
+    dest[len] = '\0';
+}
+
+static bool ven_blank(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return *text == '\0';
+}
+
+/* Text that is not a whole number from INT64_MIN to UINT64_MAX reads as 0. */
+static ven_wide ven_to_int(const char *text) {
+    char *end;
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    errno = 0;
+    if (*text == '-') {
+        long long value = strtoll(text, &end, 10);
+        return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+    }
+    unsigned long long value = strtoull(text, &end, 10);
+    return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+}
+
+static double ven_to_float(const char *text) {
+    char *end;
+    double value = strtod(text, &end);
+    return end == text || !ven_blank(end) ? 0.0 : value;
+}
+
+/* Only the word true reads as true. */
+static bool ven_to_bool(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return strncmp(text, "true", 4) == 0 && ven_blank(text + 4);
+}
+
+/* The shortest digits that read back as the same double, written out without
+   an exponent, like 0.001 or 1000000, into buf of 400 bytes. */
+static const char *ven_float_text(char *buf, double x) {
+    if (x != x) {
+        return "NaN";
+    }
+    /* Only an infinity is not finite after x - x */
+    if (x - x != 0) {
+        return x < 0 ? "-inf" : "inf";
+    }
+    char shortest[32];
+    for (int precision = 1; precision <= 17; precision++) {
+        snprintf(shortest, sizeof shortest, "%.*e", precision - 1, x);
+        if (strtod(shortest, NULL) == x) {
+            break;
+        }
+    }
+    char digits[24];
+    int len = 0;
+    const char *at = shortest;
+    char *out = buf;
+    if (*at == '-') {
+        *out++ = *at++;
+    }
+    for (; *at != 'e'; at++) {
+        if (*at != '.') {
+            digits[len++] = *at;
+        }
+    }
+    int exponent = atoi(at + 1);
+    if (exponent < 0) {
+        *out++ = '0';
+        *out++ = '.';
+        for (int i = -1; i > exponent; i--) {
+            *out++ = '0';
+        }
+        memcpy(out, digits, len);
+        out += len;
+    } else if (exponent + 1 >= len) {
+        memcpy(out, digits, len);
+        out += len;
+        for (int i = len; i <= exponent; i++) {
+            *out++ = '0';
+        }
+    } else {
+        memcpy(out, digits, exponent + 1);
+        out += exponent + 1;
+        *out++ = '.';
+        memcpy(out, digits + exponent + 1, len - exponent - 1);
+        out += len - exponent - 1;
+    }
+    *out = '\0';
+    return buf;
+}
+
+static void ven_print_float(FILE *out, double x) {
+    char buf[400];
+    fputs(ven_float_text(buf, x), out);
+}
+
+static ven_wide ven_fit(ven_wide value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value < min || value > max) {
+        ven_wide span = max - min + 1;
+        value %= span;
+        if (value < min) {
+            value += span;
+        } else if (value > max) {
+            value -= span;
+        }
+    }
+    return value;
+}
+
+/* Floats never wrap: they are truncated and clamp at the limits. */
+static ven_wide ven_fit_float(double value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value != value) {
+        return 0;
+    }
+    /* Truncate toward zero; doubles past 2^63 are whole already */
+    if (value > -9223372036854775808.0 && value < 9223372036854775808.0) {
+        value = (double)(long long)value;
+    }
+    return value < (double)min ? min : value >= (double)max + 1.0 ? max : (ven_wide)value;
+}
+
+/* A u64 product, which can exceed ven_wide. */
+static uint64_t ven_mul_u64(uint64_t a, uint64_t b, const char *name, int line) {
+    (void)name;
+    (void)line;
+    return a * b;
+}
+
+/* x ** y; a negative y gives 1 / x ** -y truncated toward zero. A power too
+   large for ven_wide comes back as a stand-in past every integer type, with
+   its sign and low 64 bits, so ven_fit handles it as it would the power. */
+static ven_wide ven_pow(ven_wide x, ven_wide y) {
+    if (y == 0) {
+        return 1;
+    }
+    if (x == 0 || x == 1) {
+        return x;
+    }
+    if (x == -1) {
+        return y % 2 == 0 ? 1 : -1;
+    }
+    if (y < 0) {
+        return 0;
+    }
+    ven_wide power = 1;
+    for (ven_wide i = 0; i < y; i++) {
+        if (__builtin_mul_overflow(power, x, &power)) {
+            uint64_t low = 1, base = (uint64_t)x;
+            for (uint64_t e = (uint64_t)y; e > 0; e >>= 1) {
+                if (e & 1) {
+                    low *= base;
+                }
+                base *= base;
+            }
+            ven_wide past = (ven_wide)1 << 64;
+            return x < 0 && y % 2 != 0 ? (ven_wide)low - 2 * past : (ven_wide)low + past;
+        }
+    }
+    return power;
+}
+
+static ven_wide ven_abs(int64_t x) {
+    return x < 0 ? -(ven_wide)x : x;
+}
+
+/* The smallest or largest of count values; a later value only wins when it
+   is smaller or larger. */
+static int64_t ven_min(const int64_t *values, size_t count) {
+    int64_t min = values[0];
+    for (size_t i = 1; i < count; i++) {
+        min = values[i] < min ? values[i] : min;
+    }
+    return min;
+}
+
+static int64_t ven_max(const int64_t *values, size_t count) {
+    int64_t max = values[0];
+    for (size_t i = 1; i < count; i++) {
+        max = values[i] > max ? values[i] : max;
+    }
+    return max;
+}
+
+static double ven_fmin(const double *values, size_t count) {
+    double min = values[0];
+    for (size_t i = 1; i < count; i++) {
+        min = values[i] < min ? values[i] : min;
+    }
+    return min;
+}
+
+static double ven_fmax(const double *values, size_t count) {
+    double max = values[0];
+    for (size_t i = 1; i < count; i++) {
+        max = values[i] > max ? values[i] : max;
+    }
+    return max;
+}
+
+static void ven_div_zero(const char *name, int line) {
+    fflush(stdout);
+    fprintf(stderr, "runtime error: division of '%s' by zero on line %d\n", name, line);
+    exit(1);
+}
+
+static ven_wide ven_divisor(ven_wide y, const char *name, int line) {
+    if (y == 0) {
+        ven_div_zero(name, line);
+    }
+    return y;
+}
+
+static double ven_divisor_f(double y, const char *name, int line) {
+    if (y == 0.0) {
+        ven_div_zero(name, line);
+    }
+    return y;
+}
+
+/* A negative power of zero divides by zero too. */
+static ven_wide ven_exponent(ven_wide x, ven_wide y, const char *name, int line) {
+    if (x == 0 && y < 0) {
+        ven_div_zero(name, line);
+    }
+    return y;
+}
+
+int64_t minute = 0;
+uint8_t side = 0;
+int64_t half = 0;
+int64_t low = 0;
+int64_t high = 0;
+int64_t span = 0;
+int64_t most = 0;
+
+int main(void) {
+    minute = -7;
+    minute = (int64_t)ven_fit((ven_wide)minute % ven_divisor(2, "minute", 3), INT64_MIN, 9223372036854775807, "minute", "i64", 3);
+    fputs("minute=", stdout);
+    fprintf(stdout, "%lld", (long long)minute);
+    fputs("\n", stdout);
+    side = 3;
+    side = (uint8_t)ven_fit(ven_pow(side, ven_exponent(side, 5, "side", 6)), 0, 255, "side", "u8", 6);
+    fputs("side=", stdout);
+    fprintf(stdout, "%lld", (long long)side);
+    fputs("\n", stdout);
+    half = 2;
+    half = (int64_t)ven_fit(ven_pow(half, ven_exponent(half, -1, "half", 9)), INT64_MIN, 9223372036854775807, "half", "i64", 9);
+    low = -12;
+    high = 40;
+    span = (int64_t)ven_fit(ven_abs(low), INT64_MIN, 9223372036854775807, "abs()", "i64", 12);
+    most = ven_max((int64_t[]){low, high, 7}, 3);
+    fprintf(stdout, "%lld", (long long)span);
+    fputs(" ", stdout);
+    fprintf(stdout, "%lld", (long long)most);
+    fputs(" ", stdout);
+    fprintf(stdout, "%lld", (long long)ven_min((int64_t[]){low, (int64_t)ven_fit(-(ven_wide)high, INT64_MIN, 9223372036854775807, "-", "i64", 14)}, 2));
+    fputs(" ", stdout);
+    fprintf(stdout, "%lld", (long long)half);
+    fputs("\n", stdout);
+    if (((int64_t)ven_fit(ven_abs(low), INT64_MIN, 9223372036854775807, "abs()", "i64", 15) > 10) && ((int64_t)ven_fit(-(ven_wide)low, INT64_MIN, 9223372036854775807, "-", "i64", 15) < 40)) {
+        fprintf(stdout, "%lld", (long long)span);
+        fputs(" is between\n", stdout);
+    }
+    return 0;
+}
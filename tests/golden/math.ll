@@ -0,0 +1,540 @@
+; Generated by the Ven compiler
+declare i32 @dprintf(i32, i8*, ...)
+declare i32 @snprintf(i8*, i64, i8*, ...)
+declare i32 @strcmp(i8*, i8*)
+declare i32 @strncmp(i8*, i8*, i64)
+declare i64 @strlen(i8*)
+declare i64 @strtoll(i8*, i8**, i32)
+declare i64 @strtoull(i8*, i8**, i32)
+declare i32* @__errno_location()
+declare void @exit(i32)
+declare double @llvm.trunc.f64(double)
+declare {i64, i1} @llvm.umul.with.overflow.i64(i64, i64)
+declare double @strtod(i8*, i8**)
+declare i64 @read(i32, i8*, i64)
+declare i8* @memmove(i8*, i8*, i64)
+declare i8* @memset(i8*, i32, i64)
+declare i8* @strchr(i8*, i32)
+
+@ven.line = internal global [256 x i8] zeroinitializer
+@ven.fmt.s = private unnamed_addr constant [3 x i8] c"%s\00"
+@ven.fmt.lld = private unnamed_addr constant [5 x i8] c"%lld\00"
+@ven.fmt.llu = private unnamed_addr constant [5 x i8] c"%llu\00"
+@ven.fmt.e = private unnamed_addr constant [5 x i8] c"%.*e\00"
+@ven.fmt.f = private unnamed_addr constant [5 x i8] c"%.*f\00"
+@ven.nan = private unnamed_addr constant [4 x i8] c"NaN\00"
+@ven.inf = private unnamed_addr constant [4 x i8] c"inf\00"
+@ven.minus.inf = private unnamed_addr constant [5 x i8] c"-inf\00"
+@ven.true = private unnamed_addr constant [5 x i8] c"true\00"
+@ven.false = private unnamed_addr constant [6 x i8] c"false\00"
+
+; Reads one line without its newline; carriage returns and overflow are dropped
+define internal void @ven_read_line(i8* %buf, i64 %size) {
+entry:
+  %c = alloca i8
+  %max = sub i64 %size, 1
+  br label %loop
+loop:
+  %len = phi i64 [0, %entry], [%len, %skip], [%next, %keep]
+  %n = call i64 @read(i32 0, i8* %c, i64 1)
+  %eof = icmp slt i64 %n, 1
+  br i1 %eof, label %done, label %got
+got:
+  %byte = load i8, i8* %c
+  %newline = icmp eq i8 %byte, 10
+  br i1 %newline, label %done, label %check
+check:
+  %cr = icmp eq i8 %byte, 13
+  %full = icmp uge i64 %len, %max
+  %drop = or i1 %cr, %full
+  br i1 %drop, label %skip, label %keep
+skip:
+  br label %loop
+keep:
+  %slot = getelementptr inbounds i8, i8* %buf, i64 %len
+  store i8 %byte, i8* %slot
+  %next = add i64 %len, 1
+  br label %loop
+done:
+  %end = getelementptr inbounds i8, i8* %buf, i64 %len
+  store i8 0, i8* %end
+  ret void
+}
+
+; Copies as much of src as fits without splitting a character, always terminating dest
+define internal void @ven_copy(i8* %dest, i8* %src, i64 %size) {
+entry:
+  %len = call i64 @strlen(i8* %src)
+  %max = sub i64 %size, 1
+  %long = icmp ugt i64 %len, %max
+  br i1 %long, label %cut, label %copy
+cut:
+  %cut.len = phi i64 [%max, %entry], [%back, %inside]
+  %at = getelementptr inbounds i8, i8* %src, i64 %cut.len
+  %byte = load i8, i8* %at
+  %top = and i8 %byte, -64
+  %continuation = icmp eq i8 %top, -128
+  %nonzero = icmp ugt i64 %cut.len, 0
+  %split = and i1 %continuation, %nonzero
+  br i1 %split, label %inside, label %copy
+inside:
+  %back = sub i64 %cut.len, 1
+  br label %cut
+copy:
+  %n = phi i64 [%len, %entry], [%cut.len, %cut]
+  call i8* @memmove(i8* %dest, i8* %src, i64 %n)
+  %end = getelementptr inbounds i8, i8* %dest, i64 %n
+  store i8 0, i8* %end
+  ret void
+}
+
+define internal i8* @ven_skip_space(i8* %text) {
+entry:
+  br label %loop
+loop:
+  %p = phi i8* [%text, %entry], [%next, %space]
+  %byte = load i8, i8* %p
+  %sp = icmp eq i8 %byte, 32
+  %low = icmp uge i8 %byte, 9
+  %high = icmp ule i8 %byte, 13
+  %ctl = and i1 %low, %high
+  %ws = or i1 %sp, %ctl
+  br i1 %ws, label %space, label %done
+space:
+  %next = getelementptr inbounds i8, i8* %p, i64 1
+  br label %loop
+done:
+  ret i8* %p
+}
+
+define internal i1 @ven_blank(i8* %text) {
+  %p = call i8* @ven_skip_space(i8* %text)
+  %byte = load i8, i8* %p
+  %blank = icmp eq i8 %byte, 0
+  ret i1 %blank
+}
+
+; Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+define internal i128 @ven_to_int(i8* %text) {
+entry:
+  %end = alloca i8*
+  %start = call i8* @ven_skip_space(i8* %text)
+  %errno = call i32* @__errno_location()
+  store i32 0, i32* %errno
+  %first = load i8, i8* %start
+  %minus = icmp eq i8 %first, 45
+  br i1 %minus, label %negative, label %positive
+negative:
+  %signed = call i64 @strtoll(i8* %start, i8** %end, i32 10)
+  %wide.signed = sext i64 %signed to i128
+  br label %check
+positive:
+  %unsigned = call i64 @strtoull(i8* %start, i8** %end, i32 10)
+  %wide.unsigned = zext i64 %unsigned to i128
+  br label %check
+check:
+  %value = phi i128 [%wide.signed, %negative], [%wide.unsigned, %positive]
+  %stop = load i8*, i8** %end
+  %moved = icmp ne i8* %stop, %start
+  %rest = call i1 @ven_blank(i8* %stop)
+  %err = load i32, i32* %errno
+  %in.range = icmp ne i32 %err, 34
+  %number = and i1 %moved, %rest
+  %ok = and i1 %number, %in.range
+  %result = select i1 %ok, i128 %value, i128 0
+  ret i128 %result
+}
+
+define internal double @ven_to_float(i8* %text) {
+  %end = alloca i8*
+  %value = call double @strtod(i8* %text, i8** %end)
+  %stop = load i8*, i8** %end
+  %moved = icmp ne i8* %stop, %text
+  %rest = call i1 @ven_blank(i8* %stop)
+  %ok = and i1 %moved, %rest
+  %result = select i1 %ok, double %value, double 0.0
+  ret double %result
+}
+
+; Only the word true reads as true
+define internal i1 @ven_to_bool(i8* %text) {
+entry:
+  %p = call i8* @ven_skip_space(i8* %text)
+  %cmp = call i32 @strncmp(i8* %p, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.true, i64 0, i64 0), i64 4)
+  %word = icmp eq i32 %cmp, 0
+  br i1 %word, label %rest, label %no
+rest:
+  %after = getelementptr inbounds i8, i8* %p, i64 4
+  %blank = call i1 @ven_blank(i8* %after)
+  ret i1 %blank
+no:
+  ret i1 false
+}
+
+; The shortest text that reads back as %x, without an exponent, in %buf of 400 bytes
+define internal i8* @ven_float_text(i8* %buf, double %x) {
+entry:
+  %short = alloca [32 x i8]
+  %text = getelementptr inbounds [32 x i8], [32 x i8]* %short, i64 0, i64 0
+  %nan = fcmp uno double %x, %x
+  br i1 %nan, label %not.a.number, label %check
+not.a.number:
+  ret i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.nan, i64 0, i64 0)
+check:
+  ; Only an infinity is not finite after x - x
+  %zero = fsub double %x, %x
+  %finite = fcmp oeq double %zero, 0.0
+  br i1 %finite, label %loop, label %infinite
+infinite:
+  %negative = fcmp olt double %x, 0.0
+  %word = select i1 %negative, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.minus.inf, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @ven.inf, i64 0, i64 0)
+  ret i8* %word
+loop:
+  %precision = phi i32 [1, %check], [%next, %retry]
+  %decimals = sub i32 %precision, 1
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %text, i64 32, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.e, i64 0, i64 0), i32 %decimals, double %x)
+  %back = call double @strtod(i8* %text, i8** null)
+  %same = fcmp oeq double %back, %x
+  %last = icmp sge i32 %precision, 17
+  %stop = or i1 %same, %last
+  br i1 %stop, label %found, label %retry
+retry:
+  %next = add i32 %precision, 1
+  br label %loop
+found:
+  %e = call i8* @strchr(i8* %text, i32 101)
+  %after.e = getelementptr inbounds i8, i8* %e, i64 1
+  %exponent.wide = call i64 @strtoll(i8* %after.e, i8** null, i32 10)
+  %exponent = trunc i64 %exponent.wide to i32
+  %fraction = icmp slt i32 %exponent, %decimals
+  br i1 %fraction, label %fixed, label %whole
+fixed:
+  ; Rounding at the last shortest digit gives the same digits written out
+  %places = sub i32 %decimals, %exponent
+  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %buf, i64 400, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.f, i64 0, i64 0), i32 %places, double %x)
+  ret i8* %buf
+whole:
+  ; The digits without their point, then zeros up to the point
+  store i8 0, i8* %e
+  %first = load i8, i8* %text
+  %minus = icmp eq i8 %first, 45
+  %lead = select i1 %minus, i64 2, i64 1
+  %point = getelementptr inbounds i8, i8* %text, i64 %lead
+  %rest = call i64 @strlen(i8* %point)
+  %after.point = getelementptr inbounds i8, i8* %point, i64 1
+  call i8* @memmove(i8* %point, i8* %after.point, i64 %rest)
+  %len = call i64 @strlen(i8* %text)
+  call i8* @memmove(i8* %buf, i8* %text, i64 %len)
+  %end = getelementptr inbounds i8, i8* %buf, i64 %len
+  %zeros.narrow = sub i32 %exponent, %decimals
+  %zeros = sext i32 %zeros.narrow to i64
+  call i8* @memset(i8* %end, i32 48, i64 %zeros)
+  %stop.at = getelementptr inbounds i8, i8* %end, i64 %zeros
+  store i8 0, i8* %stop.at
+  ret i8* %buf
+}
+
+define internal void @ven_print_float(i32 %fd, double %x) {
+  %buf = alloca [400 x i8]
+  %start = getelementptr inbounds [400 x i8], [400 x i8]* %buf, i64 0, i64 0
+  %text = call i8* @ven_float_text(i8* %start, double %x)
+  call i32 (i32, i8*, ...) @dprintf(i32 %fd, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* %text)
+  ret void
+}
+
+define internal i128 @ven_fit(i128 %value, i128 %min, i128 %max, i8* %name, i8* %type, i64 %line) {
+entry:
+  %below = icmp slt i128 %value, %min
+  %above = icmp sgt i128 %value, %max
+  %out = or i1 %below, %above
+  br i1 %out, label %wrap, label %ok
+ok:
+  ret i128 %value
+wrap:
+  %mask = sub i128 %max, %min
+  %span = add i128 %mask, 1
+  %low = and i128 %value, %mask
+  %high = icmp sgt i128 %low, %max
+  %back = sub i128 %low, %span
+  %result = select i1 %high, i128 %back, i128 %low
+  ret i128 %result
+}
+
+; A u64 product, which can exceed i128
+define internal i64 @ven_mul_u64(i64 %a, i64 %b, i8* %name, i64 %line) {
+  %pair = call {i64, i1} @llvm.umul.with.overflow.i64(i64 %a, i64 %b)
+  %product = extractvalue {i64, i1} %pair, 0
+  ret i64 %product
+}
+
+define internal i128 @ven_fit_float(double %value, i128 %min, i128 %max, double %low, double %high, i8* %name, i8* %type, i64 %line) {
+entry:
+  %nan = fcmp uno double %value, %value
+  br i1 %nan, label %zero, label %number
+zero:
+  ret i128 0
+number:
+  %whole = call double @llvm.trunc.f64(double %value)
+  %below = fcmp olt double %whole, %low
+  %above = fcmp oge double %whole, %high
+  br i1 %below, label %clamp.min, label %check
+clamp.min:
+  ret i128 %min
+check:
+  br i1 %above, label %clamp.max, label %convert
+clamp.max:
+  ret i128 %max
+convert:
+  %negative = fcmp olt double %whole, 0.0
+  br i1 %negative, label %signed, label %unsigned
+signed:
+  %s = fptosi double %whole to i64
+  %wide.s = sext i64 %s to i128
+  ret i128 %wide.s
+unsigned:
+  %u = fptoui double %whole to i64
+  %wide.u = zext i64 %u to i128
+  ret i128 %wide.u
+}
+
+declare double @llvm.pow.f64(double, double)
+declare double @llvm.fabs.f64(double)
+declare double @llvm.sqrt.f64(double)
+declare double @llvm.floor.f64(double)
+declare double @llvm.ceil.f64(double)
+declare double @llvm.round.f64(double)
+
+; x ** y, with a negative y giving 1 / x ** -y truncated toward zero. A power
+; of 2^64 or more in size comes back as a stand-in outside every integer type,
+; with its sign and low 64 bits, which @ven_fit handles as it would the power
+define internal i128 @ven_pow(i128 %x, i128 %y) {
+entry:
+  %zero = icmp eq i128 %y, 0
+  br i1 %zero, label %one, label %small
+one:
+  ret i128 1
+small:
+  %odd = trunc i128 %y to i1
+  %same = icmp ule i128 %x, 1
+  br i1 %same, label %itself, label %minus.one
+itself:
+  ret i128 %x
+minus.one:
+  %is.minus.one = icmp eq i128 %x, -1
+  br i1 %is.minus.one, label %sign, label %below.one
+sign:
+  %sign.value = select i1 %odd, i128 -1, i128 1
+  ret i128 %sign.value
+below.one:
+  %fraction = icmp slt i128 %y, 0
+  br i1 %fraction, label %none, label %start
+none:
+  ret i128 0
+start:
+  %negative.x = icmp slt i128 %x, 0
+  %minus.x = sub i128 0, %x
+  %magnitude = select i1 %negative.x, i128 %minus.x, i128 %x
+  %negative = and i1 %negative.x, %odd
+  br label %loop
+loop:
+  %power = phi i128 [1, %start], [%next, %more]
+  %count = phi i128 [0, %start], [%counted, %more]
+  %next = mul i128 %power, %magnitude
+  %counted = add i128 %count, 1
+  %big = icmp uge i128 %next, 18446744073709551616
+  br i1 %big, label %low, label %more
+more:
+  %done = icmp eq i128 %counted, %y
+  br i1 %done, label %exact, label %loop
+exact:
+  %minus.power = sub i128 0, %next
+  %result = select i1 %negative, i128 %minus.power, i128 %next
+  ret i128 %result
+low:
+  %base.start = trunc i128 %x to i64
+  %exponent.start = trunc i128 %y to i64
+  br label %square
+square:
+  %bits = phi i64 [1, %low], [%bits.next, %square]
+  %base = phi i64 [%base.start, %low], [%base.next, %square]
+  %exponent = phi i64 [%exponent.start, %low], [%exponent.next, %square]
+  %bit = trunc i64 %exponent to i1
+  %product = mul i64 %bits, %base
+  %bits.next = select i1 %bit, i64 %product, i64 %bits
+  %base.next = mul i64 %base, %base
+  %exponent.next = lshr i64 %exponent, 1
+  %left = icmp ne i64 %exponent.next, 0
+  br i1 %left, label %square, label %stand.in
+stand.in:
+  %wide = zext i64 %bits.next to i128
+  %under = sub i128 %wide, 36893488147419103232
+  %over = add i128 %wide, 18446744073709551616
+  %stand = select i1 %negative, i128 %under, i128 %over
+  ret i128 %stand
+}
+
+@ven.fmt.div_zero = private unnamed_addr constant [54 x i8] c"runtime error: division of '%s' by zero on line %lld\0A\00"
+
+define internal void @ven_div_zero(i8* %name, i64 %line) {
+  call i32 (i32, i8*, ...) @dprintf(i32 2, i8* getelementptr inbounds ([54 x i8], [54 x i8]* @ven.fmt.div_zero, i64 0, i64 0), i8* %name, i64 %line)
+  call void @exit(i32 1)
+  unreachable
+}
+
+@v.minute = internal global i64 0
+@v.side = internal global i8 0
+@v.half = internal global i64 0
+@v.low = internal global i64 0
+@v.high = internal global i64 0
+@v.span = internal global i64 0
+@v.most = internal global i64 0
+@.str.0 = private unnamed_addr constant [7 x i8] c"minute\00"
+@.str.1 = private unnamed_addr constant [4 x i8] c"i64\00"
+@.str.2 = private unnamed_addr constant [8 x i8] c"minute=\00"
+@.str.3 = private unnamed_addr constant [2 x i8] c"\0A\00"
+@.str.4 = private unnamed_addr constant [5 x i8] c"side\00"
+@.str.5 = private unnamed_addr constant [3 x i8] c"u8\00"
+@.str.6 = private unnamed_addr constant [6 x i8] c"side=\00"
+@.str.7 = private unnamed_addr constant [5 x i8] c"half\00"
+@.str.8 = private unnamed_addr constant [6 x i8] c"abs()\00"
+@.str.9 = private unnamed_addr constant [2 x i8] c" \00"
+@.str.10 = private unnamed_addr constant [2 x i8] c"-\00"
+@.str.11 = private unnamed_addr constant [13 x i8] c" is between\0A\00"
+
+define i32 @main() {
+entry:
+  %t1 = trunc i128 -7 to i64
+  store i64 %t1, i64* @v.minute
+  %t2 = load i64, i64* @v.minute
+  %t3 = sext i64 %t2 to i128
+  %t4 = icmp eq i128 2, 0
+  br i1 %t4, label %divzero.1, label %divok.1
+divzero.1:
+  call void @ven_div_zero(i8* getelementptr inbounds ([7 x i8], [7 x i8]* @.str.0, i64 0, i64 0), i64 3)
+  unreachable
+divok.1:
+  %t5 = trunc i128 %t3 to i64
+  %t6 = trunc i128 2 to i64
+  %t9 = icmp eq i128 2, -1
+  %t10 = select i1 %t9, i64 1, i64 %t6
+  %t7 = srem i64 %t5, %t10
+  %t8 = sext i64 %t7 to i128
+  %t11 = call i128 @ven_fit(i128 %t8, i128 -9223372036854775808, i128 9223372036854775807, i8* getelementptr inbounds ([7 x i8], [7 x i8]* @.str.0, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.1, i64 0, i64 0), i64 3)
+  %t12 = trunc i128 %t11 to i64
+  store i64 %t12, i64* @v.minute
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([8 x i8], [8 x i8]* @.str.2, i64 0, i64 0))
+  %t13 = load i64, i64* @v.minute
+  %t14 = sext i64 %t13 to i128
+  %t15 = trunc i128 %t14 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t15)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.3, i64 0, i64 0))
+  %t16 = trunc i128 3 to i8
+  store i8 %t16, i8* @v.side
+  %t17 = load i8, i8* @v.side
+  %t18 = zext i8 %t17 to i128
+  %t19 = icmp eq i128 %t18, 0
+  %t20 = icmp slt i128 5, 0
+  %t21 = and i1 %t19, %t20
+  br i1 %t21, label %divzero.2, label %divok.2
+divzero.2:
+  call void @ven_div_zero(i8* getelementptr inbounds ([5 x i8], [5 x i8]* @.str.4, i64 0, i64 0), i64 6)
+  unreachable
+divok.2:
+  %t22 = call i128 @ven_pow(i128 %t18, i128 5)
+  %t23 = call i128 @ven_fit(i128 %t22, i128 0, i128 255, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @.str.4, i64 0, i64 0), i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str.5, i64 0, i64 0), i64 6)
+  %t24 = trunc i128 %t23 to i8
+  store i8 %t24, i8* @v.side
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([6 x i8], [6 x i8]* @.str.6, i64 0, i64 0))
+  %t25 = load i8, i8* @v.side
+  %t26 = zext i8 %t25 to i128
+  %t27 = trunc i128 %t26 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t27)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.3, i64 0, i64 0))
+  %t28 = trunc i128 2 to i64
+  store i64 %t28, i64* @v.half
+  %t29 = load i64, i64* @v.half
+  %t30 = sext i64 %t29 to i128
+  %t31 = icmp eq i128 %t30, 0
+  %t32 = icmp slt i128 -1, 0
+  %t33 = and i1 %t31, %t32
+  br i1 %t33, label %divzero.3, label %divok.3
+divzero.3:
+  call void @ven_div_zero(i8* getelementptr inbounds ([5 x i8], [5 x i8]* @.str.7, i64 0, i64 0), i64 9)
+  unreachable
+divok.3:
+  %t34 = call i128 @ven_pow(i128 %t30, i128 -1)
+  %t35 = call i128 @ven_fit(i128 %t34, i128 -9223372036854775808, i128 9223372036854775807, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @.str.7, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.1, i64 0, i64 0), i64 9)
+  %t36 = trunc i128 %t35 to i64
+  store i64 %t36, i64* @v.half
+  %t37 = trunc i128 -12 to i64
+  store i64 %t37, i64* @v.low
+  %t38 = trunc i128 40 to i64
+  store i64 %t38, i64* @v.high
+  %t39 = load i64, i64* @v.low
+  %t40 = sext i64 %t39 to i128
+  %t41 = sub i128 0, %t40
+  %t42 = icmp slt i128 %t40, 0
+  %t43 = select i1 %t42, i128 %t41, i128 %t40
+  %t44 = call i128 @ven_fit(i128 %t43, i128 -9223372036854775808, i128 9223372036854775807, i8* getelementptr inbounds ([6 x i8], [6 x i8]* @.str.8, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.1, i64 0, i64 0), i64 12)
+  %t45 = trunc i128 %t44 to i64
+  store i64 %t45, i64* @v.span
+  %t46 = load i64, i64* @v.low
+  %t47 = sext i64 %t46 to i128
+  %t48 = load i64, i64* @v.high
+  %t49 = sext i64 %t48 to i128
+  %t50 = icmp sgt i128 %t49, %t47
+  %t51 = select i1 %t50, i128 %t49, i128 %t47
+  %t52 = icmp sgt i128 7, %t51
+  %t53 = select i1 %t52, i128 7, i128 %t51
+  %t54 = trunc i128 %t53 to i64
+  store i64 %t54, i64* @v.most
+  %t55 = load i64, i64* @v.span
+  %t56 = sext i64 %t55 to i128
+  %t57 = trunc i128 %t56 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t57)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.9, i64 0, i64 0))
+  %t58 = load i64, i64* @v.most
+  %t59 = sext i64 %t58 to i128
+  %t60 = trunc i128 %t59 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t60)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.9, i64 0, i64 0))
+  %t61 = load i64, i64* @v.low
+  %t62 = sext i64 %t61 to i128
+  %t63 = load i64, i64* @v.high
+  %t64 = sext i64 %t63 to i128
+  %t65 = sub i128 0, %t64
+  %t66 = call i128 @ven_fit(i128 %t65, i128 -9223372036854775808, i128 9223372036854775807, i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.10, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.1, i64 0, i64 0), i64 14)
+  %t67 = icmp slt i128 %t66, %t62
+  %t68 = select i1 %t67, i128 %t66, i128 %t62
+  %t69 = trunc i128 %t68 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t69)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.9, i64 0, i64 0))
+  %t70 = load i64, i64* @v.half
+  %t71 = sext i64 %t70 to i128
+  %t72 = trunc i128 %t71 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t72)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.3, i64 0, i64 0))
+  %t73 = load i64, i64* @v.low
+  %t74 = sext i64 %t73 to i128
+  %t75 = sub i128 0, %t74
+  %t76 = icmp slt i128 %t74, 0
+  %t77 = select i1 %t76, i128 %t75, i128 %t74
+  %t78 = call i128 @ven_fit(i128 %t77, i128 -9223372036854775808, i128 9223372036854775807, i8* getelementptr inbounds ([6 x i8], [6 x i8]* @.str.8, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.1, i64 0, i64 0), i64 15)
+  %t79 = icmp sgt i128 %t78, 10
+  %t80 = load i64, i64* @v.low
+  %t81 = sext i64 %t80 to i128
+  %t82 = sub i128 0, %t81
+  %t83 = call i128 @ven_fit(i128 %t82, i128 -9223372036854775808, i128 9223372036854775807, i8* getelementptr inbounds ([2 x i8], [2 x i8]* @.str.10, i64 0, i64 0), i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str.1, i64 0, i64 0), i64 15)
+  %t84 = icmp slt i128 %t83, 40
+  %t85 = and i1 %t79, %t84
+  br i1 %t85, label %then.4, label %endif.4
+then.4:
+  %t86 = load i64, i64* @v.span
+  %t87 = sext i64 %t86 to i128
+  %t88 = trunc i128 %t87 to i64
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([5 x i8], [5 x i8]* @ven.fmt.lld, i64 0, i64 0), i64 %t88)
+  call i32 (i32, i8*, ...) @dprintf(i32 1, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @ven.fmt.s, i64 0, i64 0), i8* getelementptr inbounds ([13 x i8], [13 x i8]* @.str.11, i64 0, i64 0))
+  br label %endif.4
+endif.4:
+  ret i32 0
+}
@@ -0,0 +1,138 @@
+// Generated by the Ven compiler
+import fs from "node:fs";
+
+const __byte = Buffer.alloc(1);
+function __readLine() {
+  const bytes = [];
+  for (;;) {
+    let read = 0;
+    try {
+      read = fs.readSync(0, __byte, 0, 1, null);
+    } catch (e) {
+      if (e.code === "EAGAIN") continue;
+      if (e.code === "EOF") break;
+      throw e;
+    }
+    if (read === 0 || __byte[0] === 10) break;
+    bytes.push(__byte[0]);
+  }
+  if (bytes[bytes.length - 1] === 13) bytes.pop();
+  // A line holds at most 255 bytes, as on the native targets, and a character
+  // cut in two is dropped whole
+  let end = Math.min(bytes.length, 255);
+  while (end > 0 && end < bytes.length && (bytes[end] & 0xc0) === 0x80) end--;
+  return Buffer.from(bytes.slice(0, end)).toString("utf8");
+}
+
+// Integers are BigInts. Text that is not a whole number from the i64 minimum to
+// the u64 maximum reads as 0.
+function __toInt(text) {
+  const trimmed = text.trim();
+  if (!/^[+-]?[0-9]+$/.test(trimmed)) return 0n;
+  const value = BigInt(trimmed);
+  return value < -(2n ** 63n) || value >= 2n ** 64n ? 0n : value;
+}
+
+function __fit(value, min, max, name, type, line) {
+  if (value >= min && value <= max) return value;
+  const span = max - min + 1n;
+  return (((value - min) % span) + span) % span + min;
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+function __fitFloat(value, min, max, name, type, line) {
+  if (Number.isNaN(value)) return 0n;
+  const whole = Math.trunc(value);
+  return whole < Number(min) ? min : whole >= Number(max) + 1 ? max : BigInt(whole);
+}
+
+function __floatText(x) {
+  if (!Number.isFinite(x)) return Number.isNaN(x) ? "NaN" : x < 0 ? "-inf" : "inf";
+  if (Object.is(x, -0)) return "-0";
+  // The shortest digits, written out without an exponent like the native targets
+  const text = String(Math.abs(x));
+  const e = text.indexOf("e");
+  if (e < 0) return x < 0 ? `-${text}` : text;
+  const digits = text.slice(0, e).replace(".", "");
+  const point = 1 + Number(text.slice(e + 1));
+  const sign = x < 0 ? "-" : "";
+  if (point <= 0) return `${sign}0.${"0".repeat(-point)}${digits}`;
+  if (point >= digits.length) return sign + digits + "0".repeat(point - digits.length);
+  return `${sign}${digits.slice(0, point)}.${digits.slice(point)}`;
+}
+
+// A negative y gives 1 / x ** -y truncated toward zero. A power past 128 bits
+// is cut to its sign and low 64 bits, still past every integer type, which
+// `__fit` handles as it would the power itself
+function __pow(x, y) {
+  if (y === 0n) return 1n;
+  if (x === 0n || x === 1n) return x;
+  if (x === -1n) return y % 2n === 0n ? 1n : -1n;
+  if (y < 0n) return 0n;
+  if (y < 128n) return x ** y;
+  let low = 1n;
+  let base = BigInt.asUintN(64, x);
+  for (let e = y; e > 0n; e >>= 1n) {
+    if (e & 1n) low = BigInt.asUintN(64, low * base);
+    base = BigInt.asUintN(64, base * base);
+  }
+  return x < 0n && y % 2n !== 0n ? low - (1n << 65n) : low + (1n << 64n);
+}
+
+// As on the native targets, 1 to any power and -1 to an infinite one are 1
+function __fpow(x, y) {
+  if (x === 1 || (x === -1 && !Number.isFinite(y) && !Number.isNaN(y))) return 1;
+  return x ** y;
+}
+
+function __abs(x) {
+  return x < 0n ? -x : x;
+}
+
+// A later value only wins when it is smaller, or larger
+function __min(...values) {
+  return values.reduce((min, x) => (x < min ? x : min));
+}
+
+function __max(...values) {
+  return values.reduce((max, x) => (x > max ? x : max));
+}
+
+// Halves round away from zero
+function __round(x) {
+  return x < 0 ? -Math.round(-x) : Math.round(x);
+}
+
+function __divZero(name, line) {
+  process.stderr.write(`runtime error: division of '${name}' by zero on line ${line}\n`);
+  process.exit(1);
+}
+
+function __divisor(y, name, line) {
+  if (y == 0) __divZero(name, line);
+  return y;
+}
+
+function __exponent(x, y, name, line) {
+  if (x == 0 && y < 0) __divZero(name, line);
+  return y;
+}
+
+let minute, side, half, low, high, span, most;
+
+minute = -7n;
+minute = __fit(minute % __divisor(2n, "minute", 3), -9223372036854775808n, 9223372036854775807n, "minute", "i64", 3);
+process.stdout.write(`minute=${minute}\n`);
+side = 3n;
+side = __fit(__pow(side, __exponent(side, 5n, "side", 6)), 0n, 255n, "side", "u8", 6);
+process.stdout.write(`side=${side}\n`);
+half = 2n;
+half = __fit(__pow(half, __exponent(half, -1n, "half", 9)), -9223372036854775808n, 9223372036854775807n, "half", "i64", 9);
+low = -12n;
+high = 40n;
+span = __fit(__abs(low), -9223372036854775808n, 9223372036854775807n, "abs()", "i64", 12);
+most = __max(low, high, 7n);
+process.stdout.write(`${span} ${most} ${__min(low, __fit(-(high), -9223372036854775808n, 9223372036854775807n, "-", "i64", 14))} ${half}\n`);
+if ((__fit(__abs(low), -9223372036854775808n, 9223372036854775807n, "abs()", "i64", 15) > 10n) && (__fit(-(low), -9223372036854775808n, 9223372036854775807n, "-", "i64", 15) < 40n)) {
+  process.stdout.write(`${span} is between\n`);
+}
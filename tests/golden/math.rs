@@ -0,0 +1,144 @@
+// Generated by the Ven compiler
+#![allow(dead_code, unused_mut, unused_assignments, unused_variables, unused_parens)]
+use std::io::BufRead;
+
+fn ven_read_line() -> String {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).unwrap_or(0);
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+    // A line holds at most 255 bytes, as on the native targets
+    let mut end = line.len().min(255);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+    line
+}
+
+// Text that is not a whole number from i64::MIN to u64::MAX reads as 0
+fn ven_to_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+        .unwrap_or(0)
+}
+
+fn ven_to_float(text: &str) -> f64 {
+    text.trim().parse().unwrap_or(0.0)
+}
+
+fn ven_to_bool(text: &str) -> bool {
+    text.trim() == "true"
+}
+
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        let span = max - min + 1;
+        let low = value.rem_euclid(span);
+        return if low > max { low - span } else { low };
+    }
+    value
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => (a as u64).wrapping_mul(b as u64) as i128,
+    }
+}
+
+// A negative `y` gives `1 / x ** -y` truncated toward zero. A power too large
+// for i128 comes back as a stand-in outside every integer type, with its sign
+// and low 64 bits, which `ven_fit` handles as it would the power itself
+fn ven_pow(x: i128, y: i128) -> i128 {
+    match x {
+        _ if y == 0 => return 1,
+        0 | 1 => return x,
+        -1 => return if y % 2 == 0 { 1 } else { -1 },
+        _ if y < 0 => return 0,
+        _ => {}
+    }
+    if let Some(power) = if y <= u32::MAX as i128 { x.checked_pow(y as u32) } else { None } {
+        return power;
+    }
+    let (mut base, mut exponent, mut low) = (x as u64, y as u64, 1u64);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            low = low.wrapping_mul(base);
+        }
+        base = base.wrapping_mul(base);
+        exponent >>= 1;
+    }
+    if x < 0 && y % 2 == 1 { low as i128 - (1i128 << 65) } else { low as i128 + (1i128 << 64) }
+}
+
+// A later value only wins when it is smaller, or larger
+fn ven_min<T: PartialOrd + Copy>(values: &[T]) -> T {
+    values[1..].iter().fold(values[0], |min, &x| if x < min { x } else { min })
+}
+
+fn ven_max<T: PartialOrd + Copy>(values: &[T]) -> T {
+    values[1..].iter().fold(values[0], |max, &x| if x > max { x } else { max })
+}
+
+fn ven_div_zero(name: &str, line: u32) -> ! {
+    eprintln!("runtime error: division of '{}' by zero on line {}", name, line);
+    std::process::exit(1);
+}
+
+fn ven_divisor(y: i128, name: &str, line: u32) -> i128 {
+    if y == 0 {
+        ven_div_zero(name, line);
+    }
+    y
+}
+
+fn ven_divisor_f(y: f64, name: &str, line: u32) -> f64 {
+    if y == 0.0 {
+        ven_div_zero(name, line);
+    }
+    y
+}
+
+// A negative power of zero divides by zero too
+fn ven_exponent(x: i128, y: i128, name: &str, line: u32) -> i128 {
+    if x == 0 && y < 0 {
+        ven_div_zero(name, line);
+    }
+    y
+}
+
+fn main() {
+    let mut minute: i64 = 0;
+    let mut side: u8 = 0;
+    let mut half: i64 = 0;
+    let mut low: i64 = 0;
+    let mut high: i64 = 0;
+    let mut span: i64 = 0;
+    let mut most: i64 = 0;
+    minute = -7;
+    minute = ven_fit(minute as i128 % ven_divisor((2) as i128, "minute", 3), -9223372036854775808, 9223372036854775807, "minute", "i64", 3) as i64;
+    println!("minute={}", minute);
+    side = 3;
+    side = ven_fit(ven_pow(side as i128, ven_exponent(side as i128, (5) as i128, "side", 6)), 0, 255, "side", "u8", 6) as u8;
+    println!("side={}", side);
+    half = 2;
+    half = ven_fit(ven_pow(half as i128, ven_exponent(half as i128, (-1) as i128, "half", 9)), -9223372036854775808, 9223372036854775807, "half", "i64", 9) as i64;
+    low = -12;
+    high = 40;
+    span = (ven_fit((low as i128).abs(), -9223372036854775808, 9223372036854775807, "abs()", "i64", 12) as i64);
+    most = ven_max(&[low, high, (7 as i64)]);
+    println!("{} {} {} {}", span, most, ven_min(&[low, (ven_fit(-(high as i128), -9223372036854775808, 9223372036854775807, "-", "i64", 14) as i64)]), half);
+    if (((ven_fit((low as i128).abs(), -9223372036854775808, 9223372036854775807, "abs()", "i64", 15) as i64) > 10) && ((ven_fit(-(low as i128), -9223372036854775808, 9223372036854775807, "-", "i64", 15) as i64) < 40)) {
+        println!("{} is between", (span));
+    }
+}
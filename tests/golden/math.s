@@ -0,0 +1,931 @@
+// Generated by the Ven compiler
+
+.data
+newline:
+    .byte 10
+lit_true:
+    .ascii "true"
+lit_false:
+    .ascii "false"
+lit_undefined:
+    .ascii "[undefined]"
+lit_div_zero:
+    .ascii "runtime error: division of '"
+lit_div_zero_line:
+    .ascii "' by zero on line "
+str_0:
+    .ascii "minute"
+    .byte 0
+str_1:
+    .ascii "i64"
+    .byte 0
+str_2:
+    .ascii "minute="
+    .byte 0
+str_3:
+    .ascii "side"
+    .byte 0
+str_4:
+    .ascii "u8"
+    .byte 0
+str_5:
+    .ascii "side="
+    .byte 0
+str_6:
+    .ascii "half"
+    .byte 0
+str_7:
+    .ascii "abs()"
+    .byte 0
+str_8:
+    .ascii " "
+    .byte 0
+str_9:
+    .ascii "-"
+    .byte 0
+str_10:
+    .ascii " is between"
+    .byte 0
+
+.bss
+    .balign 8
+num_buf:
+    .skip 32
+    .balign 8
+line_buf:
+    .skip 256
+    .balign 8
+var_minute:
+    .skip 8
+    .balign 8
+var_side:
+    .skip 8
+    .balign 8
+var_half:
+    .skip 8
+    .balign 8
+var_low:
+    .skip 8
+    .balign 8
+var_high:
+    .skip 8
+    .balign 8
+var_span:
+    .skip 8
+    .balign 8
+var_most:
+    .skip 8
+    .balign 8
+tmp_0:
+    .skip 8
+    .balign 8
+tmp_1:
+    .skip 8
+    .balign 8
+tmp_2:
+    .skip 8
+    .balign 8
+tmp_3:
+    .skip 8
+    .balign 8
+ven_pow_y:
+    .skip 8
+
+.text
+.global _start
+_start:
+    // declare minute
+    mov x0, #-7
+    adrp x9, var_minute
+    str x0, [x9, :lo12:var_minute]
+    // math on minute
+    mov x0, #2
+    mov x1, x0
+    adrp x9, var_minute
+    ldr x0, [x9, :lo12:var_minute]
+    cmp x1, #0
+    b.ne L1_divisor_ok
+    adrp x4, str_0
+    add x4, x4, :lo12:str_0
+    mov x5, #3
+    str x30, [sp, #-16]!
+    bl ven_div_zero
+    ldr x30, [sp], #16
+L1_divisor_ok:
+    adrp x4, str_0
+    add x4, x4, :lo12:str_0
+    adrp x5, str_1
+    add x5, x5, :lo12:str_1
+    str x30, [sp, #-16]!
+    bl ven_rem_i64
+    ldr x30, [sp], #16
+    adrp x9, var_minute
+    str x0, [x9, :lo12:var_minute]
+    // print to fd 1
+    mov x0, #1
+    adrp x1, str_2
+    add x1, x1, :lo12:str_2
+    mov x2, #7
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_minute
+    ldr x0, [x9, :lo12:var_minute]
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // declare side
+    mov x0, #3
+    adrp x9, var_side
+    str x0, [x9, :lo12:var_side]
+    // math on side
+    mov x0, #5
+    mov x1, x0
+    adrp x9, var_side
+    ldr x0, [x9, :lo12:var_side]
+    mov x2, #1
+    str x30, [sp, #-16]!
+    bl ven_pow
+    ldr x30, [sp], #16
+    mov x2, #0
+    mov x3, #255
+    adrp x4, str_3
+    add x4, x4, :lo12:str_3
+    adrp x5, str_4
+    add x5, x5, :lo12:str_4
+    str x30, [sp, #-16]!
+    bl ven_pow_fit
+    ldr x30, [sp], #16
+    adrp x9, var_side
+    str x0, [x9, :lo12:var_side]
+    // print to fd 1
+    mov x0, #1
+    adrp x1, str_5
+    add x1, x1, :lo12:str_5
+    mov x2, #5
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_side
+    ldr x0, [x9, :lo12:var_side]
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // declare half
+    mov x0, #2
+    adrp x9, var_half
+    str x0, [x9, :lo12:var_half]
+    // math on half
+    mov x0, #-1
+    mov x1, x0
+    adrp x9, var_half
+    ldr x0, [x9, :lo12:var_half]
+    cmp x0, #0
+    b.ne L2_divisor_ok
+    cmp x1, #0
+    b.ge L2_divisor_ok
+    adrp x4, str_6
+    add x4, x4, :lo12:str_6
+    mov x5, #9
+    str x30, [sp, #-16]!
+    bl ven_div_zero
+    ldr x30, [sp], #16
+L2_divisor_ok:
+    mov x2, #0
+    str x30, [sp, #-16]!
+    bl ven_pow
+    ldr x30, [sp], #16
+    movz x2, #0
+    movk x2, #32768, lsl #48
+    movz x3, #65535
+    movk x3, #65535, lsl #16
+    movk x3, #65535, lsl #32
+    movk x3, #32767, lsl #48
+    adrp x4, str_6
+    add x4, x4, :lo12:str_6
+    adrp x5, str_1
+    add x5, x5, :lo12:str_1
+    str x30, [sp, #-16]!
+    bl ven_pow_fit
+    ldr x30, [sp], #16
+    adrp x9, var_half
+    str x0, [x9, :lo12:var_half]
+    // declare low
+    mov x0, #-12
+    adrp x9, var_low
+    str x0, [x9, :lo12:var_low]
+    // declare high
+    mov x0, #40
+    adrp x9, var_high
+    str x0, [x9, :lo12:var_high]
+    // declare span
+    // abs()
+    adrp x9, var_low
+    ldr x0, [x9, :lo12:var_low]
+    adrp x4, str_7
+    add x4, x4, :lo12:str_7
+    adrp x5, str_1
+    add x5, x5, :lo12:str_1
+    str x30, [sp, #-16]!
+    bl ven_abs_i64
+    ldr x30, [sp], #16
+    adrp x9, var_span
+    str x0, [x9, :lo12:var_span]
+    // declare most
+    // max()
+    adrp x9, var_low
+    ldr x0, [x9, :lo12:var_low]
+    adrp x9, tmp_0
+    str x0, [x9, :lo12:tmp_0]
+    adrp x9, var_high
+    ldr x0, [x9, :lo12:var_high]
+    adrp x9, tmp_0
+    ldr x1, [x9, :lo12:tmp_0]
+    cmp x0, x1
+    b.gt L3_keep
+    mov x0, x1
+L3_keep:
+    adrp x9, tmp_0
+    str x0, [x9, :lo12:tmp_0]
+    mov x0, #7
+    adrp x9, tmp_0
+    ldr x1, [x9, :lo12:tmp_0]
+    cmp x0, x1
+    b.gt L4_keep
+    mov x0, x1
+L4_keep:
+    adrp x9, var_most
+    str x0, [x9, :lo12:var_most]
+    // print to fd 1
+    adrp x9, var_span
+    ldr x0, [x9, :lo12:var_span]
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, str_8
+    add x1, x1, :lo12:str_8
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_most
+    ldr x0, [x9, :lo12:var_most]
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, str_8
+    add x1, x1, :lo12:str_8
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // min()
+    adrp x9, var_low
+    ldr x0, [x9, :lo12:var_low]
+    adrp x9, tmp_1
+    str x0, [x9, :lo12:tmp_1]
+    // -
+    adrp x9, var_high
+    ldr x0, [x9, :lo12:var_high]
+    mov x1, x0
+    mov x0, #0
+    adrp x4, str_9
+    add x4, x4, :lo12:str_9
+    adrp x5, str_1
+    add x5, x5, :lo12:str_1
+    str x30, [sp, #-16]!
+    bl ven_sub_i64
+    ldr x30, [sp], #16
+    adrp x9, tmp_1
+    ldr x1, [x9, :lo12:tmp_1]
+    cmp x0, x1
+    b.lt L5_keep
+    mov x0, x1
+L5_keep:
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, str_8
+    add x1, x1, :lo12:str_8
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    adrp x9, var_half
+    ldr x0, [x9, :lo12:var_half]
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    // if
+    mov x1, #10
+    adrp x9, tmp_2
+    str x1, [x9, :lo12:tmp_2]
+    // abs()
+    adrp x9, var_low
+    ldr x0, [x9, :lo12:var_low]
+    adrp x4, str_7
+    add x4, x4, :lo12:str_7
+    adrp x5, str_1
+    add x5, x5, :lo12:str_1
+    str x30, [sp, #-16]!
+    bl ven_abs_i64
+    ldr x30, [sp], #16
+    adrp x9, tmp_2
+    ldr x1, [x9, :lo12:tmp_2]
+    cmp x0, x1
+    b.le L6_endif
+    mov x1, #40
+    adrp x9, tmp_3
+    str x1, [x9, :lo12:tmp_3]
+    // -
+    adrp x9, var_low
+    ldr x0, [x9, :lo12:var_low]
+    mov x1, x0
+    mov x0, #0
+    adrp x4, str_9
+    add x4, x4, :lo12:str_9
+    adrp x5, str_1
+    add x5, x5, :lo12:str_1
+    str x30, [sp, #-16]!
+    bl ven_sub_i64
+    ldr x30, [sp], #16
+    adrp x9, tmp_3
+    ldr x1, [x9, :lo12:tmp_3]
+    cmp x0, x1
+    b.ge L6_endif
+    // print to fd 1
+    // floor()
+    adrp x9, var_span
+    ldr x0, [x9, :lo12:var_span]
+    mov x1, #1
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #1
+    adrp x1, str_10
+    add x1, x1, :lo12:str_10
+    mov x2, #11
+    mov x8, #64   // syscall: Write
+    svc #0
+    mov x0, #1
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+L6_endif:
+    // exit(0)
+    mov x0, #0
+    mov x8, #93   // syscall: Exit
+    svc #0
+    // ---- runtime ----
+ven_print_int:
+    adrp x2, num_buf
+    add x2, x2, :lo12:num_buf
+    add x2, x2, #32
+    mov x3, #0
+    cmp x0, #0
+    b.lt ven_print_int_digits
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    mov x3, #1
+ven_print_int_digits:
+    mov x4, x0
+    mov x5, #10
+    sdiv x9, x4, x5
+    msub x4, x9, x5, x4
+    mov x5, #48
+    sub x5, x5, x4
+    sub x2, x2, #1
+    strb w5, [x2]
+    mov x5, #10
+    sdiv x0, x0, x5
+    cmp x0, #0
+    b.ne ven_print_int_digits
+    cmp x3, #0
+    b.ne ven_print_int_write
+    sub x2, x2, #1
+    mov x5, #45
+    strb w5, [x2]
+ven_print_int_write:
+    adrp x4, num_buf
+    add x4, x4, :lo12:num_buf
+    add x4, x4, #32
+    sub x4, x4, x2
+    mov x0, x1
+    mov x1, x2
+    mov x2, x4
+    mov x8, #64   // syscall: Write
+    svc #0
+    ret
+ven_print_uint:
+    adrp x2, num_buf
+    add x2, x2, :lo12:num_buf
+    add x2, x2, #32
+ven_print_uint_digits:
+    mov x3, x0
+    mov x5, #10
+    udiv x3, x3, x5
+    mov x4, x3
+    mul x4, x4, x5
+    mov x5, x0
+    sub x5, x5, x4
+    add x5, x5, #48
+    sub x2, x2, #1
+    strb w5, [x2]
+    mov x0, x3
+    cmp x0, #0
+    b.ne ven_print_uint_digits
+    b ven_print_int_write
+ven_print_cstr:
+    mov x2, x0
+ven_print_cstr_len:
+    ldrb w3, [x2]
+    cmp x3, #0
+    b.eq ven_print_cstr_write
+    add x2, x2, #1
+    b ven_print_cstr_len
+ven_print_cstr_write:
+    sub x2, x2, x0
+    cmp x2, #0
+    b.eq ven_print_cstr_done
+    mov x3, x0
+    mov x0, x1
+    mov x1, x3
+    mov x8, #64   // syscall: Write
+    svc #0
+ven_print_cstr_done:
+    ret
+ven_read_line:
+    mov x4, x0
+    mov x5, x0
+    add x5, x5, x1
+ven_read_line_next:
+    mov x0, #0
+    mov x1, x4
+    mov x2, #1
+    mov x8, #63   // syscall: Read
+    svc #0
+    cmp x0, #1
+    b.lt ven_read_line_done
+    ldrb w3, [x4]
+    cmp x3, #10
+    b.eq ven_read_line_done
+    cmp x3, #13
+    b.eq ven_read_line_next
+    cmp x4, x5
+    b.ge ven_read_line_next
+    add x4, x4, #1
+    b ven_read_line_next
+ven_read_line_done:
+    mov x3, #0
+    strb w3, [x4]
+    ret
+ven_parse_int:
+    mov x1, x0
+    mov x0, #0
+    mov x2, #0
+ven_parse_int_space:
+    ldrb w3, [x1]
+    cmp x3, #32
+    b.ne ven_parse_int_sign
+    add x1, x1, #1
+    b ven_parse_int_space
+ven_parse_int_sign:
+    cmp x3, #45
+    b.ne ven_parse_int_plus
+    mov x2, #1
+    add x1, x1, #1
+    b ven_parse_int_digit
+ven_parse_int_plus:
+    cmp x3, #43
+    b.ne ven_parse_int_digit
+    add x1, x1, #1
+ven_parse_int_digit:
+    ldrb w3, [x1]
+    cmp x3, #48
+    b.lt ven_parse_int_end
+    cmp x3, #57
+    b.gt ven_parse_int_end
+    sub x3, x3, #48
+    mov x4, #-1
+    sub x4, x4, x3
+    mov x5, #10
+    udiv x4, x4, x5
+    cmp x0, x4
+    b.hi ven_parse_int_range
+    mov x4, #10
+    mul x0, x0, x4
+    add x0, x0, x3
+    add x1, x1, #1
+    b ven_parse_int_digit
+ven_parse_int_end:
+    mov x1, #0
+    cmp x2, #0
+    b.eq ven_parse_int_positive
+    movz x4, #0
+    movk x4, #32768, lsl #48
+    cmp x0, x4
+    b.hi ven_parse_int_range
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    ret
+ven_parse_int_positive:
+    cmp x0, #0
+    b.ge ven_parse_int_done
+    mov x1, #1
+ven_parse_int_done:
+    ret
+ven_parse_int_range:
+    mov x0, #0
+    mov x1, #0
+    ret
+ven_compare:
+    cmp x2, #0
+    b.ne ven_compare_b
+    cmp x0, #0
+    b.lt ven_compare_negative
+ven_compare_b:
+    cmp x3, #0
+    b.ne ven_compare_unsigned
+    cmp x1, #0
+    b.lt ven_compare_greater
+ven_compare_unsigned:
+    cmp x0, x1
+    b.lo ven_compare_less
+    b.hi ven_compare_greater
+    mov x0, #0
+    ret
+ven_compare_negative:
+    cmp x3, #0
+    b.ne ven_compare_less
+    cmp x0, x1
+    b.lt ven_compare_less
+    b.gt ven_compare_greater
+    mov x0, #0
+    ret
+ven_compare_less:
+    mov x0, #-1
+    ret
+ven_compare_greater:
+    mov x0, #1
+    ret
+ven_str_cmp:
+    ldrb w2, [x0]
+    ldrb w3, [x1]
+    cmp x2, x3
+    b.lt ven_str_cmp_less
+    b.gt ven_str_cmp_greater
+    cmp x2, #0
+    b.eq ven_str_cmp_equal
+    add x0, x0, #1
+    add x1, x1, #1
+    b ven_str_cmp
+ven_str_cmp_less:
+    mov x0, #-1
+    ret
+ven_str_cmp_greater:
+    mov x0, #1
+    ret
+ven_str_cmp_equal:
+    mov x0, #0
+    ret
+ven_str_copy:
+    mov x3, x0
+    add x3, x3, x2
+    sub x3, x3, #1
+    mov x2, x0
+ven_str_copy_next:
+    cmp x0, x3
+    b.ge ven_str_copy_cut
+    ldrb w4, [x1]
+    cmp x4, #0
+    b.eq ven_str_copy_done
+    strb w4, [x0]
+    add x0, x0, #1
+    add x1, x1, #1
+    b ven_str_copy_next
+ven_str_copy_cut:
+    cmp x0, x2
+    b.ls ven_str_copy_done
+    ldrb w4, [x1]
+    cmp x4, #128
+    b.lo ven_str_copy_done
+    cmp x4, #191
+    b.hi ven_str_copy_done
+    sub x0, x0, #1
+    sub x1, x1, #1
+    b ven_str_copy_cut
+ven_str_copy_done:
+    mov x4, #0
+    strb w4, [x0]
+    ret
+    // ---- math ----
+ven_rem_i64:
+    cmn x1, #1
+    b.eq ven_rem_i64_zero
+    sdiv x9, x0, x1
+    msub x0, x9, x1, x0
+    ret
+ven_rem_i64_zero:
+    mov x0, #0
+    ret
+ven_abs_i64:
+    cmp x0, #0
+    b.ge ven_abs_i64_done
+    mov x1, x0
+    mov x0, #0
+    b ven_sub_i64
+ven_abs_i64_done:
+    ret
+ven_pow:
+    cmp x1, #0
+    b.eq ven_pow_one
+    cmp x0, #1
+    b.ls ven_pow_same
+    adrp x9, ven_pow_y
+    str x1, [x9, :lo12:ven_pow_y]
+    mov x3, #0
+    cmp x2, #0
+    b.ne ven_pow_unsigned
+    cmn x0, #1
+    b.eq ven_pow_minus_one
+    cmp x1, #0
+    b.lt ven_pow_zero
+    cmp x0, #0
+    b.ge ven_pow_unsigned
+    mov x3, x1
+    mov x4, #2
+    udiv x9, x3, x4
+    msub x3, x9, x4, x3
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+ven_pow_unsigned:
+    mov x2, x0
+    mov x0, #1
+ven_pow_next:
+    mov x4, x0
+    mul x4, x4, x2
+    mov x5, x4
+    udiv x5, x5, x2
+    cmp x5, x0
+    b.ne ven_pow_beyond
+    mov x0, x4
+    sub x1, x1, #1
+    cmp x1, #0
+    b.ne ven_pow_next
+    cmp x3, #0
+    b.ne ven_pow_negative
+    mov x1, #1
+    ret
+ven_pow_negative:
+    movz x4, #0
+    movk x4, #32768, lsl #48
+    cmp x0, x4
+    b.hi ven_pow_beyond
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    mov x1, #0
+    ret
+ven_pow_beyond:
+    mov x0, #1
+    adrp x9, ven_pow_y
+    ldr x1, [x9, :lo12:ven_pow_y]
+ven_pow_square:
+    cmp x1, #0
+    b.eq ven_pow_low
+    mov x4, x1
+    mov x5, #2
+    udiv x9, x4, x5
+    msub x4, x9, x5, x4
+    cmp x4, #0
+    b.eq ven_pow_even
+    mul x0, x0, x2
+ven_pow_even:
+    mul x2, x2, x2
+    udiv x1, x1, x5
+    b ven_pow_square
+ven_pow_low:
+    mov x1, #2
+    cmp x3, #0
+    b.eq ven_pow_done
+    mov x4, #0
+    sub x4, x4, x0
+    mov x0, x4
+    mov x1, #3
+ven_pow_done:
+    ret
+ven_pow_minus_one:
+    mov x3, x1
+    mov x4, #2
+    udiv x9, x3, x4
+    msub x3, x9, x4, x3
+    cmp x3, #0
+    b.eq ven_pow_one
+    mov x1, #0
+    ret
+ven_pow_one:
+    mov x0, #1
+ven_pow_same:
+    mov x1, #0
+    ret
+ven_pow_zero:
+    mov x0, #0
+    mov x1, #0
+    ret
+ven_pow_fit:
+    cmp x1, #2
+    b.lo ven_fit
+    mov x1, #0
+    b ven_fit
+    // ---- integer overflow ----
+ven_fit:
+    cmp x2, #0
+    b.ne ven_fit_signed
+    cmn x3, #1
+    b.ne ven_fit_signed
+    cmp x1, #0
+    b.ne ven_fit_ok
+    cmp x0, #0
+    b.ge ven_fit_ok
+    ret
+ven_fit_signed:
+    cmp x1, #0
+    b.eq ven_fit_range
+    cmp x0, #0
+    b.lt ven_fit_above
+ven_fit_range:
+    cmp x0, x2
+    b.lt ven_fit_below
+    cmp x0, x3
+    b.gt ven_fit_above
+ven_fit_ok:
+    ret
+ven_fit_below:
+    b ven_fit_wrap
+ven_fit_above:
+    b ven_fit_wrap
+ven_fit_wrap:
+    mov x4, x3
+    sub x4, x4, x2
+    add x4, x4, #1
+    cmp x4, #0
+    b.eq ven_fit_ok
+    sdiv x9, x0, x4
+    msub x0, x9, x4, x0
+    cmp x0, x2
+    b.ge ven_fit_wrap_high
+    add x0, x0, x4
+ven_fit_wrap_high:
+    cmp x0, x3
+    b.le ven_fit_ok
+    sub x0, x0, x4
+    ret
+ven_add_i64:
+    mov x2, x0
+    add x0, x0, x1
+    cmp x1, #0
+    b.lt ven_add_i64_negative
+    cmp x0, x2
+    b.lt ven_add_i64_overflow
+    ret
+ven_add_i64_negative:
+    cmp x0, x2
+    b.gt ven_add_i64_overflow
+    ret
+ven_add_i64_overflow:
+    ret
+ven_sub_i64:
+    mov x2, x0
+    sub x0, x0, x1
+    cmp x1, #0
+    b.lt ven_sub_i64_negative
+    cmp x0, x2
+    b.gt ven_sub_i64_overflow
+    ret
+ven_sub_i64_negative:
+    cmp x0, x2
+    b.lt ven_sub_i64_overflow
+    ret
+ven_sub_i64_overflow:
+    ret
+ven_mul_i64:
+    mov x2, x0
+    mul x0, x0, x1
+    cmn x2, #1
+    b.ne ven_mul_i64_divide
+    movz x3, #0
+    movk x3, #32768, lsl #48
+    cmp x1, x3
+    b.eq ven_mul_i64_overflow
+    ret
+ven_mul_i64_divide:
+    cmp x2, #0
+    b.eq ven_mul_i64_done
+    mov x3, x0
+    sdiv x3, x3, x2
+    cmp x3, x1
+    b.ne ven_mul_i64_overflow
+ven_mul_i64_done:
+    ret
+ven_mul_i64_overflow:
+    ret
+ven_div_i64:
+    cmn x1, #1
+    b.ne ven_div_i64_divide
+    movz x3, #0
+    movk x3, #32768, lsl #48
+    cmp x0, x3
+    b.eq ven_div_i64_overflow
+ven_div_i64_divide:
+    sdiv x0, x0, x1
+    ret
+ven_div_i64_overflow:
+    ret
+ven_add_u64:
+    mov x2, x0
+    add x0, x0, x1
+    cmp x0, x2
+    b.lo ven_add_u64_overflow
+    ret
+ven_add_u64_overflow:
+    ret
+ven_sub_u64:
+    mov x2, x0
+    sub x0, x0, x1
+    cmp x2, x1
+    b.lo ven_sub_u64_overflow
+    ret
+ven_sub_u64_overflow:
+    ret
+ven_mul_u64:
+    mov x2, x0
+    mul x0, x0, x1
+    cmp x2, #0
+    b.eq ven_mul_u64_done
+    mov x3, x0
+    udiv x3, x3, x2
+    cmp x3, x1
+    b.ne ven_mul_u64_overflow
+ven_mul_u64_done:
+    ret
+ven_mul_u64_overflow:
+    ret
+ven_div_zero:
+    mov x0, #2
+    adrp x1, lit_div_zero
+    add x1, x1, :lo12:lit_div_zero
+    mov x2, #28
+    mov x8, #64   // syscall: Write
+    svc #0
+    mov x0, x4
+    mov x1, #2
+    str x30, [sp, #-16]!
+    bl ven_print_cstr
+    ldr x30, [sp], #16
+    mov x0, #2
+    adrp x1, lit_div_zero_line
+    add x1, x1, :lo12:lit_div_zero_line
+    mov x2, #18
+    mov x8, #64   // syscall: Write
+    svc #0
+    mov x0, x5
+    mov x1, #2
+    str x30, [sp, #-16]!
+    bl ven_print_int
+    ldr x30, [sp], #16
+    mov x0, #2
+    adrp x1, newline
+    add x1, x1, :lo12:newline
+    mov x2, #1
+    mov x8, #64   // syscall: Write
+    svc #0
+    mov x0, #1
+    mov x8, #93   // syscall: Exit
+    svc #0
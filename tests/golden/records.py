@@ -0,0 +1,109 @@
+# Generated by the Ven compiler
+import sys
+
+
+def _read_line():
+    # A line holds at most 255 bytes, as on the native targets
+    line = sys.stdin.readline().rstrip("\r\n").encode()
+    return line[:255].decode(errors="ignore")
+
+
+# Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+def _to_int(text):
+    try:
+        value = int(text.strip())
+    except ValueError:
+        return 0
+    return value if -(2**63) <= value < 2**64 else 0
+
+
+def _to_float(text):
+    try:
+        return float(text.strip())
+    except ValueError:
+        return 0.0
+
+
+def _int_div(a, b):
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+
+
+def _fit(value, lo, hi, name, type, line):
+    return (value - lo) % (hi - lo + 1) + lo
+
+
+# Floats never wrap: they are truncated and clamp at the limits
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+
+
+def _shown(value):
+    if isinstance(value, bool):
+        return str(value).lower()
+    return _float_text(value) if isinstance(value, float) else str(value)
+
+
+def _get(entries, key, name, line):
+    if key not in entries:
+        sys.stdout.flush()
+        shown = f'"{key}"' if isinstance(key, str) else key
+        print(f"runtime error: key {shown} is not in '{name}' on line {line}", file=sys.stderr)
+        sys.exit(1)
+    return entries[key]
+
+
+def _show_map(entries):
+    return "{" + ", ".join(f"{key}: {_shown(value)}" for key, value in entries.items()) + "}"
+
+
+def _fit_text(text, size):
+    data = str(text).encode()
+    if len(data) <= size:
+        return text
+    # A character cut in two is dropped whole
+    return data[:size].decode(errors="ignore")
+
+
+# The shortest digits, written out without an exponent like the native targets
+def _float_text(x):
+    x = float(x)
+    if x != x:
+        return "NaN"
+    if x - x != 0:
+        return "inf" if x > 0 else "-inf"
+    sign = "-" if str(x).startswith("-") else ""
+    mantissa, _, exponent = repr(abs(x)).partition("e")
+    whole, _, fraction = mantissa.partition(".")
+    digits = (whole + fraction).rstrip("0")
+    point = len(whole) + int(exponent or 0) - (len(digits) - len(digits.lstrip("0")))
+    digits = digits.lstrip("0")
+    if not digits:
+        return sign + "0"
+    if point <= 0:
+        return sign + "0." + "0" * -point + digits
+    if point >= len(digits):
+        return sign + digits + "0" * (point - len(digits))
+    return sign + digits[:point] + "." + digits[point:]
+
+
+p = {'name': "", 'age': 0, 'height': 0.0, 'member': False, 'tag': ""}
+nobody = {'name': "", 'age': 0, 'height': 0.0, 'member': False, 'tag': ""}
+years = 0
+
+p = {'name': "Ann", 'age': 30, 'height': 1.5, 'member': False, 'tag': _fit_text("xyz", 3)}
+nobody = {'name': "", 'age': 0, 'height': 0.0, 'member': False, 'tag': _fit_text("", 3)}
+p['age'] = _fit(p['age'] + 1, 0, 255, "p.age", "u8", 5)
+p['height'] *= 2
+if (p['age'] > 30) and (not (p['member'])):
+    print(f"{p['name']} ({p['tag']}) is {p['age']} and {_float_text(p['height'])} tall")
+years = p['age']
+print(years)
+print(_show_map(p))
+print(_show_map(nobody))
@@ -0,0 +1,100 @@
+// Generated by the Ven compiler
+#![allow(dead_code, unused_mut, unused_assignments, unused_variables, unused_parens)]
+use std::io::BufRead;
+
+fn ven_read_line() -> String {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).unwrap_or(0);
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+    // A line holds at most 255 bytes, as on the native targets
+    let mut end = line.len().min(255);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+    line
+}
+
+// Text that is not a whole number from i64::MIN to u64::MAX reads as 0
+fn ven_to_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+        .unwrap_or(0)
+}
+
+fn ven_to_float(text: &str) -> f64 {
+    text.trim().parse().unwrap_or(0.0)
+}
+
+fn ven_to_bool(text: &str) -> bool {
+    text.trim() == "true"
+}
+
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        let span = max - min + 1;
+        let low = value.rem_euclid(span);
+        return if low > max { low - span } else { low };
+    }
+    value
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => (a as u64).wrapping_mul(b as u64) as i128,
+    }
+}
+
+fn ven_fit_text(mut text: String, size: usize) -> String {
+    if text.len() > size {
+        let mut end = size;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+    text
+}
+
+#[derive(Clone, Default)]
+struct Person {
+    name: String,
+    age: u8,
+    height: f64,
+    member: bool,
+    tag: String,
+}
+
+impl std::fmt::Display for Person {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{{name: {}, age: {}, height: {}, member: {}, tag: {}}}", self.name, self.age, self.height, self.member, self.tag)
+    }
+}
+
+fn main() {
+    let mut p = Person::default();
+    let mut nobody = Person::default();
+    let mut years: i64 = 0;
+    p = Person { name: "Ann".to_string(), age: 30, height: 1.5, member: false, tag: ven_fit_text("xyz".to_string(), 3) };
+    nobody = Person { name: "".to_string(), age: 0, height: 0.0, member: false, tag: ven_fit_text("".to_string(), 3) };
+    p.age = ven_fit(p.age as i128 + (1) as i128, 0, 255, "p.age", "u8", 5) as u8;
+    p.height = p.height * (2 as f64);
+    if ((p.age > 30) && !(p.member)) {
+        println!("{} ({}) is {} and {} tall", p.name, p.tag, p.age, p.height);
+    }
+    years = p.age as i64;
+    println!("{}", years);
+    println!("{}", p);
+    println!("{}", nobody);
+}
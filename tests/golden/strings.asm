@@ -0,0 +1,1027 @@
+;; Generated by the Ven compiler
+bits 64
+default rel   ;; RIP-relative addressing for every label
+
+section .data
+    newline: db 10
+    lit_true: db "true"
+    lit_false: db "false"
+    lit_undefined: db "[undefined]"
+    str_0: db "  h", 195, 169, "llo w", 195, 182, "rld  ", 0
+    str_1: db ", ", 0
+    str_2: db "!", 0
+    str_3: db "o", 0
+    str_4: db "0", 0
+    str_5: db "ABCDEF", 0
+    str_6: db "n", 0
+    str_7: db "i8", 0
+    str_8: db "w", 195, 182, "r", 0
+    str_9: db " has ", 0
+    str_10: db " characters", 0
+    str_11: db "H", 195, 137, 0
+    str_12: db "h", 195, 169, 0
+    str_13: db " was trimmed", 0
+
+section .bss
+    alignb 8
+    num_buf: resb 32
+    alignb 8
+    line_buf: resb 256
+    alignb 8
+    var_raw: resb 256
+    alignb 8
+    var_text: resb 256
+    alignb 8
+    var_loud: resb 256
+    alignb 8
+    var_first: resb 256
+    alignb 8
+    var_rest: resb 256
+    alignb 8
+    var_joined: resb 256
+    alignb 8
+    var_dashed: resb 256
+    alignb 8
+    var_code: resb 5
+    alignb 8
+    var_n: resb 8
+    alignb 8
+    var_found: resb 8
+    alignb 8
+    tmp_0: resb 8
+    alignb 8
+    tmp_1: resb 8
+    alignb 8
+    tmp_2: resb 8
+    alignb 8
+    tmp_3: resb 8
+    alignb 8
+    tmp_4: resb 8
+    alignb 8
+    tmp_5: resb 8
+    alignb 8
+    tmp_6: resb 8
+    alignb 8
+    text_0: resb 256
+    alignb 8
+    text_1: resb 256
+    alignb 8
+    text_2: resb 256
+    alignb 8
+    text_3: resb 256
+    alignb 8
+    text_4: resb 256
+    alignb 8
+    text_5: resb 256
+    alignb 8
+    text_6: resb 256
+    alignb 8
+    text_7: resb 256
+    alignb 8
+    ven_rep_at: resb 8
+    alignb 8
+    ven_rep_from: resb 8
+    alignb 8
+    ven_rep_to: resb 8
+    alignb 8
+    ven_rep_pos: resb 8
+    alignb 8
+    ven_rep_limit: resb 8
+    alignb 8
+    ven_rep_next: resb 8
+
+section .text
+global _start
+_start:
+    ;; declare raw
+    lea rdi, [var_raw]
+    lea rsi, [str_0]
+    mov rdx, 256
+    call ven_str_copy
+    ;; declare text
+    ;; trim()
+    lea rdi, [var_raw]
+    call ven_str_trim
+    mov rdx, rsi
+    mov rsi, rdi
+    lea rdi, [text_0]
+    mov r8, rdi
+    add r8, 255
+    call ven_str_put
+    lea rdi, [var_text]
+    lea rsi, [text_0]
+    mov rdx, 256
+    call ven_str_copy
+    ;; declare loud
+    ;; upper()
+    lea rdi, [text_1]
+    mov r8, rdi
+    add r8, 255
+    lea rsi, [var_text]
+    mov rdx, -1
+    call ven_str_put
+    lea rdi, [text_1]
+    mov rsi, 97
+    mov rdx, -32
+    call ven_str_case
+    lea rdi, [var_loud]
+    lea rsi, [text_1]
+    mov rdx, 256
+    call ven_str_copy
+    ;; declare first
+    mov rdi, 0
+    mov [tmp_0], rdi
+    mov rdi, 5
+    mov [tmp_1], rdi
+    ;; slice()
+    lea rdi, [var_text]
+    mov rsi, [tmp_0]
+    call ven_str_skip
+    mov [tmp_2], rdi
+    lea rdi, [var_text]
+    mov rsi, [tmp_1]
+    call ven_str_skip
+    mov rdx, rdi
+    mov rsi, [tmp_2]
+    lea rdi, [text_2]
+    mov r8, rdi
+    add r8, 255
+    call ven_str_put
+    lea rdi, [var_first]
+    lea rsi, [text_2]
+    mov rdx, 256
+    call ven_str_copy
+    ;; declare rest
+    mov rdi, -2
+    mov [tmp_3], rdi
+    mov rdi, 100
+    mov [tmp_4], rdi
+    ;; slice()
+    lea rdi, [var_text]
+    mov rsi, [tmp_3]
+    call ven_str_skip
+    mov [tmp_5], rdi
+    lea rdi, [var_text]
+    mov rsi, [tmp_4]
+    call ven_str_skip
+    mov rdx, rdi
+    mov rsi, [tmp_5]
+    lea rdi, [text_3]
+    mov r8, rdi
+    add r8, 255
+    call ven_str_put
+    lea rdi, [var_rest]
+    lea rsi, [text_3]
+    mov rdx, 256
+    call ven_str_copy
+    ;; declare joined
+    ;; concat()
+    lea rdi, [text_4]
+    mov r8, rdi
+    add r8, 255
+    lea rsi, [var_first]
+    mov rdx, -1
+    call ven_str_put
+    lea rsi, [str_1]
+    mov rdx, -1
+    call ven_str_put
+    lea rsi, [var_loud]
+    mov rdx, -1
+    call ven_str_put
+    lea rsi, [str_2]
+    mov rdx, -1
+    call ven_str_put
+    lea rdi, [var_joined]
+    lea rsi, [text_4]
+    mov rdx, 256
+    call ven_str_copy
+    ;; declare dashed
+    ;; replace()
+    lea rdi, [text_5]
+    lea rsi, [var_text]
+    lea rdx, [str_3]
+    lea r8, [str_4]
+    call ven_str_replace
+    lea rdi, [var_dashed]
+    lea rsi, [text_5]
+    mov rdx, 256
+    call ven_str_copy
+    ;; declare code
+    ;; lower()
+    lea rdi, [text_6]
+    mov r8, rdi
+    add r8, 255
+    lea rsi, [str_5]
+    mov rdx, -1
+    call ven_str_put
+    lea rdi, [text_6]
+    mov rsi, 65
+    mov rdx, 32
+    call ven_str_case
+    lea rdi, [var_code]
+    lea rsi, [text_6]
+    mov rdx, 5
+    call ven_str_copy
+    ;; declare n
+    lea rdi, [var_text]
+    call ven_str_len
+    mov rsi, 0
+    mov rdx, -128
+    mov r8, 127
+    lea r9, [str_6]
+    lea r10, [str_7]
+    call ven_fit
+    mov [var_n], rdi
+    ;; declare found
+    lea rdi, [var_text]
+    lea rsi, [str_8]
+    call ven_str_contains
+    mov [var_found], rdi
+    ;; print to fd 1
+    lea rdi, [var_joined]
+    mov rsi, 1
+    call ven_print_cstr
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ;; print to fd 1
+    lea rdi, [var_rest]
+    mov rsi, 1
+    call ven_print_cstr
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ;; print to fd 1
+    lea rdi, [var_dashed]
+    mov rsi, 1
+    call ven_print_cstr
+    mov rdi, 1
+    lea rsi, [str_9]
+    mov rdx, 5
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    lea rdi, [var_dashed]
+    call ven_str_len
+    mov rsi, 1
+    call ven_print_int
+    mov rdi, 1
+    lea rsi, [str_10]
+    mov rdx, 11
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ;; print to fd 1
+    lea rdi, [var_code]
+    mov rsi, 1
+    call ven_print_cstr
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ;; print to fd 1
+    mov rdi, [var_n]
+    mov rsi, 1
+    call ven_print_int
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ;; print to fd 1
+    mov rdi, [var_found]
+    cmp rdi, 0
+    je L1_false
+    mov rdi, 1
+    lea rsi, [lit_true]
+    mov rdx, 4
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    jmp L2_end
+L1_false:
+    mov rdi, 1
+    lea rsi, [lit_false]
+    mov rdx, 5
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+L2_end:
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ;; print to fd 1
+    lea rdi, [var_loud]
+    lea rsi, [str_11]
+    call ven_str_prefix
+    cmp rdi, 0
+    je L3_false
+    mov rdi, 1
+    lea rsi, [lit_true]
+    mov rdx, 4
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    jmp L4_end
+L3_false:
+    mov rdi, 1
+    lea rsi, [lit_false]
+    mov rdx, 5
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+L4_end:
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ;; if
+    mov rsi, [var_n]
+    mov [tmp_6], rsi
+    lea rdi, [var_raw]
+    call ven_str_len
+    mov rsi, [tmp_6]
+    cmp rdi, rsi
+    jle L5_endif
+    lea rdi, [var_text]
+    lea rsi, [str_12]
+    call ven_str_prefix
+    cmp rdi, 0
+    je L5_endif
+    ;; print to fd 1
+    ;; upper()
+    lea rdi, [text_7]
+    mov r8, rdi
+    add r8, 255
+    lea rsi, [var_first]
+    mov rdx, -1
+    call ven_str_put
+    lea rdi, [text_7]
+    mov rsi, 97
+    mov rdx, -32
+    call ven_str_case
+    lea rdi, [text_7]
+    mov rsi, 1
+    call ven_print_cstr
+    mov rdi, 1
+    lea rsi, [str_13]
+    mov rdx, 12
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    mov rdi, 1
+    lea rsi, [newline]
+    mov rdx, 1
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+L5_endif:
+    ;; exit(0)
+    mov rdi, 0
+    mov eax, 60   ;; syscall: Exit
+    syscall
+    ;; ---- runtime ----
+ven_print_int:
+    lea rdx, [num_buf]
+    add rdx, 32
+    mov r8, 0
+    cmp rdi, 0
+    jl ven_print_int_digits
+    mov r9, 0
+    sub r9, rdi
+    mov rdi, r9
+    mov r8, 1
+ven_print_int_digits:
+    mov r9, rdi
+    mov r10, 10
+    mov rax, r9
+    mov rcx, r10
+    mov r11, rdx   ;; save rdx around idiv
+    cqo   ;; sign-extend rax into rdx:rax
+    idiv rcx
+    mov rcx, rdx
+    mov rdx, r11
+    mov r9, rcx
+    mov r10, 48
+    sub r10, r9
+    add rdx, -1
+    mov [rdx], r10b
+    mov r10, 10
+    mov rax, rdi
+    mov rcx, r10
+    mov r11, rdx   ;; save rdx around idiv
+    cqo   ;; sign-extend rax into rdx:rax
+    idiv rcx
+    mov rcx, rax
+    mov rdx, r11
+    mov rdi, rcx
+    cmp rdi, 0
+    jne ven_print_int_digits
+    cmp r8, 0
+    jne ven_print_int_write
+    add rdx, -1
+    mov r10, 45
+    mov [rdx], r10b
+ven_print_int_write:
+    lea r9, [num_buf]
+    add r9, 32
+    sub r9, rdx
+    mov rdi, rsi
+    mov rsi, rdx
+    mov rdx, r9
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+    ret
+ven_print_uint:
+    lea rdx, [num_buf]
+    add rdx, 32
+ven_print_uint_digits:
+    mov r8, rdi
+    mov r10, 10
+    mov rax, r8
+    mov rcx, r10
+    mov r11, rdx   ;; save rdx around div
+    xor edx, edx   ;; zero-extend rax into rdx:rax
+    div rcx
+    mov rdx, r11
+    mov r8, rax
+    mov r9, r8
+    imul r9, r10
+    mov r10, rdi
+    sub r10, r9
+    add r10, 48
+    add rdx, -1
+    mov [rdx], r10b
+    mov rdi, r8
+    cmp rdi, 0
+    jne ven_print_uint_digits
+    jmp ven_print_int_write
+ven_print_cstr:
+    mov rdx, rdi
+ven_print_cstr_len:
+    movzx r8, byte [rdx]
+    cmp r8, 0
+    je ven_print_cstr_write
+    add rdx, 1
+    jmp ven_print_cstr_len
+ven_print_cstr_write:
+    sub rdx, rdi
+    cmp rdx, 0
+    je ven_print_cstr_done
+    mov r8, rdi
+    mov rdi, rsi
+    mov rsi, r8
+    mov eax, 1   ;; syscall: Write
+    syscall
+    mov rdi, rax   ;; result into R0
+ven_print_cstr_done:
+    ret
+ven_read_line:
+    mov r9, rdi
+    mov r10, rdi
+    add r10, rsi
+ven_read_line_next:
+    mov rdi, 0
+    mov rsi, r9
+    mov rdx, 1
+    mov eax, 0   ;; syscall: Read
+    syscall
+    mov rdi, rax   ;; result into R0
+    cmp rdi, 1
+    jl ven_read_line_done
+    movzx r8, byte [r9]
+    cmp r8, 10
+    je ven_read_line_done
+    cmp r8, 13
+    je ven_read_line_next
+    cmp r9, r10
+    jge ven_read_line_next
+    add r9, 1
+    jmp ven_read_line_next
+ven_read_line_done:
+    mov r8, 0
+    mov [r9], r8b
+    ret
+ven_parse_int:
+    mov rsi, rdi
+    mov rdi, 0
+    mov rdx, 0
+ven_parse_int_space:
+    movzx r8, byte [rsi]
+    cmp r8, 32
+    jne ven_parse_int_sign
+    add rsi, 1
+    jmp ven_parse_int_space
+ven_parse_int_sign:
+    cmp r8, 45
+    jne ven_parse_int_plus
+    mov rdx, 1
+    add rsi, 1
+    jmp ven_parse_int_digit
+ven_parse_int_plus:
+    cmp r8, 43
+    jne ven_parse_int_digit
+    add rsi, 1
+ven_parse_int_digit:
+    movzx r8, byte [rsi]
+    cmp r8, 48
+    jl ven_parse_int_end
+    cmp r8, 57
+    jg ven_parse_int_end
+    add r8, -48
+    mov r9, -1
+    sub r9, r8
+    mov r10, 10
+    mov rax, r9
+    mov rcx, r10
+    mov r11, rdx   ;; save rdx around div
+    xor edx, edx   ;; zero-extend rax into rdx:rax
+    div rcx
+    mov rdx, r11
+    mov r9, rax
+    cmp rdi, r9
+    ja ven_parse_int_range
+    mov r9, 10
+    imul rdi, r9
+    add rdi, r8
+    add rsi, 1
+    jmp ven_parse_int_digit
+ven_parse_int_end:
+    mov rsi, 0
+    cmp rdx, 0
+    je ven_parse_int_positive
+    mov r9, -9223372036854775808
+    cmp rdi, r9
+    ja ven_parse_int_range
+    mov r9, 0
+    sub r9, rdi
+    mov rdi, r9
+    ret
+ven_parse_int_positive:
+    cmp rdi, 0
+    jge ven_parse_int_done
+    mov rsi, 1
+ven_parse_int_done:
+    ret
+ven_parse_int_range:
+    mov rdi, 0
+    mov rsi, 0
+    ret
+ven_compare:
+    cmp rdx, 0
+    jne ven_compare_b
+    cmp rdi, 0
+    jl ven_compare_negative
+ven_compare_b:
+    cmp r8, 0
+    jne ven_compare_unsigned
+    cmp rsi, 0
+    jl ven_compare_greater
+ven_compare_unsigned:
+    cmp rdi, rsi
+    jb ven_compare_less
+    ja ven_compare_greater
+    mov rdi, 0
+    ret
+ven_compare_negative:
+    cmp r8, 0
+    jne ven_compare_less
+    cmp rdi, rsi
+    jl ven_compare_less
+    jg ven_compare_greater
+    mov rdi, 0
+    ret
+ven_compare_less:
+    mov rdi, -1
+    ret
+ven_compare_greater:
+    mov rdi, 1
+    ret
+ven_str_cmp:
+    movzx rdx, byte [rdi]
+    movzx r8, byte [rsi]
+    cmp rdx, r8
+    jl ven_str_cmp_less
+    jg ven_str_cmp_greater
+    cmp rdx, 0
+    je ven_str_cmp_equal
+    add rdi, 1
+    add rsi, 1
+    jmp ven_str_cmp
+ven_str_cmp_less:
+    mov rdi, -1
+    ret
+ven_str_cmp_greater:
+    mov rdi, 1
+    ret
+ven_str_cmp_equal:
+    mov rdi, 0
+    ret
+ven_str_copy:
+    mov r8, rdi
+    add r8, rdx
+    add r8, -1
+    mov rdx, rdi
+ven_str_copy_next:
+    cmp rdi, r8
+    jge ven_str_copy_cut
+    movzx r9, byte [rsi]
+    cmp r9, 0
+    je ven_str_copy_done
+    mov [rdi], r9b
+    add rdi, 1
+    add rsi, 1
+    jmp ven_str_copy_next
+ven_str_copy_cut:
+    cmp rdi, rdx
+    jbe ven_str_copy_done
+    movzx r9, byte [rsi]
+    cmp r9, 128
+    jb ven_str_copy_done
+    cmp r9, 191
+    ja ven_str_copy_done
+    add rdi, -1
+    add rsi, -1
+    jmp ven_str_copy_cut
+ven_str_copy_done:
+    mov r9, 0
+    mov [rdi], r9b
+    ret
+    ;; ---- string operations ----
+ven_str_put:
+    cmp rsi, rdx
+    jae ven_str_put_done
+    movzx r9, byte [rsi]
+    cmp r9, 0
+    je ven_str_put_done
+    cmp rdi, r8
+    jae ven_str_put_cut
+    mov [rdi], r9b
+    add rdi, 1
+    add rsi, 1
+    jmp ven_str_put
+ven_str_put_cut:
+    cmp r9, 128
+    jb ven_str_put_full
+    cmp r9, 191
+    ja ven_str_put_full
+    add rdi, -1
+    add rsi, -1
+    movzx r9, byte [rsi]
+    jmp ven_str_put_cut
+ven_str_put_full:
+    mov r8, rdi
+ven_str_put_done:
+    mov r9, 0
+    mov [rdi], r9b
+    ret
+ven_str_len:
+    mov rsi, rdi
+    mov rdi, 0
+ven_str_len_next:
+    movzx rdx, byte [rsi]
+    cmp rdx, 0
+    je ven_str_len_done
+    add rsi, 1
+    add rdx, -128
+    cmp rdx, 64
+    jb ven_str_len_next
+    add rdi, 1
+    jmp ven_str_len_next
+ven_str_len_done:
+    ret
+ven_str_skip:
+    cmp rsi, 0
+    jle ven_str_skip_done
+    movzx rdx, byte [rdi]
+    cmp rdx, 0
+    je ven_str_skip_done
+    add rdi, 1
+ven_str_skip_rest:
+    movzx rdx, byte [rdi]
+    add rdx, -128
+    cmp rdx, 64
+    jae ven_str_skip_next
+    add rdi, 1
+    jmp ven_str_skip_rest
+ven_str_skip_next:
+    add rsi, -1
+    jmp ven_str_skip
+ven_str_skip_done:
+    ret
+ven_str_case:
+    movzx r8, byte [rdi]
+    cmp r8, 0
+    je ven_str_case_done
+    mov r9, r8
+    sub r9, rsi
+    cmp r9, 26
+    jae ven_str_case_next
+    add r8, rdx
+    mov [rdi], r8b
+ven_str_case_next:
+    add rdi, 1
+    jmp ven_str_case
+ven_str_case_done:
+    ret
+ven_str_trim:
+    movzx rdx, byte [rdi]
+    cmp rdx, 32
+    je ven_str_trim_front
+    cmp rdx, 9
+    je ven_str_trim_front
+    cmp rdx, 10
+    je ven_str_trim_front
+    cmp rdx, 13
+    je ven_str_trim_front
+    mov rsi, rdi
+ven_str_trim_end:
+    movzx rdx, byte [rsi]
+    cmp rdx, 0
+    je ven_str_trim_back
+    add rsi, 1
+    jmp ven_str_trim_end
+ven_str_trim_back:
+    cmp rsi, rdi
+    jbe ven_str_trim_done
+    mov r8, rsi
+    add r8, -1
+    movzx rdx, byte [r8]
+    cmp rdx, 32
+    je ven_str_trim_drop
+    cmp rdx, 9
+    je ven_str_trim_drop
+    cmp rdx, 10
+    je ven_str_trim_drop
+    cmp rdx, 13
+    je ven_str_trim_drop
+ven_str_trim_done:
+    ret
+ven_str_trim_front:
+    add rdi, 1
+    jmp ven_str_trim
+ven_str_trim_drop:
+    mov rsi, r8
+    jmp ven_str_trim_back
+ven_str_find:
+    mov rdx, rdi
+    mov r8, rsi
+ven_str_find_next:
+    movzx r10, byte [r8]
+    cmp r10, 0
+    je ven_str_find_done
+    movzx r9, byte [rdx]
+    cmp r9, r10
+    jne ven_str_find_miss
+    add rdx, 1
+    add r8, 1
+    jmp ven_str_find_next
+ven_str_find_miss:
+    movzx r9, byte [rdi]
+    cmp r9, 0
+    je ven_str_find_none
+    add rdi, 1
+    jmp ven_str_find
+ven_str_find_none:
+    mov rdi, 0
+ven_str_find_done:
+    ret
+ven_str_contains:
+    call ven_str_find
+    cmp rdi, 0
+    je ven_str_contains_done
+    mov rdi, 1
+ven_str_contains_done:
+    ret
+ven_str_prefix:
+    movzx r8, byte [rsi]
+    cmp r8, 0
+    je ven_str_prefix_yes
+    movzx rdx, byte [rdi]
+    cmp rdx, r8
+    jne ven_str_prefix_no
+    add rdi, 1
+    add rsi, 1
+    jmp ven_str_prefix
+ven_str_prefix_yes:
+    mov rdi, 1
+    ret
+ven_str_prefix_no:
+    mov rdi, 0
+    ret
+ven_str_replace:
+    mov [ven_rep_at], rsi
+    mov [ven_rep_from], rdx
+    mov [ven_rep_to], r8
+    mov r8, rdi
+    add r8, 255
+    mov [ven_rep_pos], rdi
+    mov [ven_rep_limit], r8
+    movzx r9, byte [rdx]
+    cmp r9, 0
+    je ven_str_replace_rest
+ven_str_replace_next:
+    mov rdi, [ven_rep_at]
+    mov rsi, [ven_rep_from]
+    call ven_str_find
+    cmp rdi, 0
+    je ven_str_replace_rest
+    mov [ven_rep_next], rdx
+    mov rdx, rdi
+    mov rsi, [ven_rep_at]
+    mov rdi, [ven_rep_pos]
+    mov r8, [ven_rep_limit]
+    call ven_str_put
+    mov rsi, [ven_rep_to]
+    mov rdx, -1
+    call ven_str_put
+    mov [ven_rep_pos], rdi
+    mov [ven_rep_limit], r8
+    mov rdi, [ven_rep_next]
+    mov [ven_rep_at], rdi
+    jmp ven_str_replace_next
+ven_str_replace_rest:
+    mov rdi, [ven_rep_pos]
+    mov rsi, [ven_rep_at]
+    mov rdx, -1
+    mov r8, [ven_rep_limit]
+    call ven_str_put
+    ret
+    ;; ---- integer overflow ----
+ven_fit:
+    cmp rdx, 0
+    jne ven_fit_signed
+    cmp r8, -1
+    jne ven_fit_signed
+    cmp rsi, 0
+    jne ven_fit_ok
+    cmp rdi, 0
+    jge ven_fit_ok
+    ret
+ven_fit_signed:
+    cmp rsi, 0
+    je ven_fit_range
+    cmp rdi, 0
+    jl ven_fit_above
+ven_fit_range:
+    cmp rdi, rdx
+    jl ven_fit_below
+    cmp rdi, r8
+    jg ven_fit_above
+ven_fit_ok:
+    ret
+ven_fit_below:
+    jmp ven_fit_wrap
+ven_fit_above:
+    jmp ven_fit_wrap
+ven_fit_wrap:
+    mov r9, r8
+    sub r9, rdx
+    add r9, 1
+    cmp r9, 0
+    je ven_fit_ok
+    mov rax, rdi
+    mov rcx, r9
+    mov r11, rdx   ;; save rdx around idiv
+    cqo   ;; sign-extend rax into rdx:rax
+    idiv rcx
+    mov rcx, rdx
+    mov rdx, r11
+    mov rdi, rcx
+    cmp rdi, rdx
+    jge ven_fit_wrap_high
+    add rdi, r9
+ven_fit_wrap_high:
+    cmp rdi, r8
+    jle ven_fit_ok
+    sub rdi, r9
+    ret
+ven_add_i64:
+    mov rdx, rdi
+    add rdi, rsi
+    cmp rsi, 0
+    jl ven_add_i64_negative
+    cmp rdi, rdx
+    jl ven_add_i64_overflow
+    ret
+ven_add_i64_negative:
+    cmp rdi, rdx
+    jg ven_add_i64_overflow
+    ret
+ven_add_i64_overflow:
+    ret
+ven_sub_i64:
+    mov rdx, rdi
+    sub rdi, rsi
+    cmp rsi, 0
+    jl ven_sub_i64_negative
+    cmp rdi, rdx
+    jg ven_sub_i64_overflow
+    ret
+ven_sub_i64_negative:
+    cmp rdi, rdx
+    jl ven_sub_i64_overflow
+    ret
+ven_sub_i64_overflow:
+    ret
+ven_mul_i64:
+    mov rdx, rdi
+    imul rdi, rsi
+    cmp rdx, -1
+    jne ven_mul_i64_divide
+    mov r8, -9223372036854775808
+    cmp rsi, r8
+    je ven_mul_i64_overflow
+    ret
+ven_mul_i64_divide:
+    cmp rdx, 0
+    je ven_mul_i64_done
+    mov r8, rdi
+    mov rax, r8
+    mov rcx, rdx
+    mov r11, rdx   ;; save rdx around idiv
+    cqo   ;; sign-extend rax into rdx:rax
+    idiv rcx
+    mov rcx, rax
+    mov rdx, r11
+    mov r8, rcx
+    cmp r8, rsi
+    jne ven_mul_i64_overflow
+ven_mul_i64_done:
+    ret
+ven_mul_i64_overflow:
+    ret
+ven_div_i64:
+    cmp rsi, -1
+    jne ven_div_i64_divide
+    mov r8, -9223372036854775808
+    cmp rdi, r8
+    je ven_div_i64_overflow
+ven_div_i64_divide:
+    mov rax, rdi
+    mov rcx, rsi
+    mov r11, rdx   ;; save rdx around idiv
+    cqo   ;; sign-extend rax into rdx:rax
+    idiv rcx
+    mov rcx, rax
+    mov rdx, r11
+    mov rdi, rcx
+    ret
+ven_div_i64_overflow:
+    ret
+ven_add_u64:
+    mov rdx, rdi
+    add rdi, rsi
+    cmp rdi, rdx
+    jb ven_add_u64_overflow
+    ret
+ven_add_u64_overflow:
+    ret
+ven_sub_u64:
+    mov rdx, rdi
+    sub rdi, rsi
+    cmp rdx, rsi
+    jb ven_sub_u64_overflow
+    ret
+ven_sub_u64_overflow:
+    ret
+ven_mul_u64:
+    mov rdx, rdi
+    imul rdi, rsi
+    cmp rdx, 0
+    je ven_mul_u64_done
+    mov r8, rdi
+    mov rax, r8
+    mov rcx, rdx
+    mov r11, rdx   ;; save rdx around div
+    xor edx, edx   ;; zero-extend rax into rdx:rax
+    div rcx
+    mov rdx, r11
+    mov r8, rax
+    cmp r8, rsi
+    jne ven_mul_u64_overflow
+ven_mul_u64_done:
+    ret
+ven_mul_u64_overflow:
+    ret
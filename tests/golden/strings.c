@@ -0,0 +1,327 @@
+// Generated by the Ven compiler
+#include <ctype.h>
+#include <errno.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static char ven_line[256];
+
+/* Wide enough for every value of every Ven integer type. */
+typedef __int128 ven_wide;
+
+/* Reads one line without its newline; carriage returns and overflow are dropped. */
+static void ven_read_line(char *buf, size_t size) {
+    size_t len = 0;
+    int c;
+    while ((c = getchar()) != EOF && c != '\n') {
+        if (c != '\r' && len + 1 < size) {
+            buf[len++] = (char)c;
+        }
+    }
+    buf[len] = '\0';
+}
+
+/* Copies as much of src as fits without splitting a character, always terminating dest. */
+static void ven_copy(char *dest, const char *src, size_t size) {
+    size_t len = strlen(src);
+    if (len >= size) {
+        len = size - 1;
+        while (len > 0 && ((unsigned char)src[len] & 0xC0) == 0x80) {
+            len--;
+        }
+    }
+    memmove(dest, src, len);
+    dest[len] = '\0';
+}
+
+static bool ven_blank(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return *text == '\0';
+}
+
+/* Text that is not a whole number from INT64_MIN to UINT64_MAX reads as 0. */
+static ven_wide ven_to_int(const char *text) {
+    char *end;
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    errno = 0;
+    if (*text == '-') {
+        long long value = strtoll(text, &end, 10);
+        return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+    }
+    unsigned long long value = strtoull(text, &end, 10);
+    return end == text || errno == ERANGE || !ven_blank(end) ? 0 : value;
+}
+
+static double ven_to_float(const char *text) {
+    char *end;
+    double value = strtod(text, &end);
+    return end == text || !ven_blank(end) ? 0.0 : value;
+}
+
+/* Only the word true reads as true. */
+static bool ven_to_bool(const char *text) {
+    while (isspace((unsigned char)*text)) {
+        text++;
+    }
+    return strncmp(text, "true", 4) == 0 && ven_blank(text + 4);
+}
+
+/* The shortest digits that read back as the same double, written out without
+   an exponent, like 0.001 or 1000000, into buf of 400 bytes. */
+static const char *ven_float_text(char *buf, double x) {
+    if (x != x) {
+        return "NaN";
+    }
+    /* Only an infinity is not finite after x - x */
+    if (x - x != 0) {
+        return x < 0 ? "-inf" : "inf";
+    }
+    char shortest[32];
+    for (int precision = 1; precision <= 17; precision++) {
+        snprintf(shortest, sizeof shortest, "%.*e", precision - 1, x);
+        if (strtod(shortest, NULL) == x) {
+            break;
+        }
+    }
+    char digits[24];
+    int len = 0;
+    const char *at = shortest;
+    char *out = buf;
+    if (*at == '-') {
+        *out++ = *at++;
+    }
+    for (; *at != 'e'; at++) {
+        if (*at != '.') {
+            digits[len++] = *at;
+        }
+    }
+    int exponent = atoi(at + 1);
+    if (exponent < 0) {
+        *out++ = '0';
+        *out++ = '.';
+        for (int i = -1; i > exponent; i--) {
+            *out++ = '0';
+        }
+        memcpy(out, digits, len);
+        out += len;
+    } else if (exponent + 1 >= len) {
+        memcpy(out, digits, len);
+        out += len;
+        for (int i = len; i <= exponent; i++) {
+            *out++ = '0';
+        }
+    } else {
+        memcpy(out, digits, exponent + 1);
+        out += exponent + 1;
+        *out++ = '.';
+        memcpy(out, digits + exponent + 1, len - exponent - 1);
+        out += len - exponent - 1;
+    }
+    *out = '\0';
+    return buf;
+}
+
+static void ven_print_float(FILE *out, double x) {
+    char buf[400];
+    fputs(ven_float_text(buf, x), out);
+}
+
+static ven_wide ven_fit(ven_wide value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value < min || value > max) {
+        ven_wide span = max - min + 1;
+        value %= span;
+        if (value < min) {
+            value += span;
+        } else if (value > max) {
+            value -= span;
+        }
+    }
+    return value;
+}
+
+/* Floats never wrap: they are truncated and clamp at the limits. */
+static ven_wide ven_fit_float(double value, ven_wide min, ven_wide max, const char *name, const char *type, int line) {
+    (void)name;
+    (void)type;
+    (void)line;
+    if (value != value) {
+        return 0;
+    }
+    /* Truncate toward zero; doubles past 2^63 are whole already */
+    if (value > -9223372036854775808.0 && value < 9223372036854775808.0) {
+        value = (double)(long long)value;
+    }
+    return value < (double)min ? min : value >= (double)max + 1.0 ? max : (ven_wide)value;
+}
+
+/* A u64 product, which can exceed ven_wide. */
+static uint64_t ven_mul_u64(uint64_t a, uint64_t b, const char *name, int line) {
+    (void)name;
+    (void)line;
+    return a * b;
+}
+
+/* Appends n bytes of src to buf, cutting at the last whole character that
+   fits; once text is cut nothing more is added. */
+static void ven_put(char *buf, size_t *len, const char *src, size_t n) {
+    if (*len + n >= 256) {
+        n = 255 - *len;
+        while (n > 0 && ((unsigned char)src[n] & 0xC0) == 0x80) {
+            n--;
+        }
+        memcpy(buf + *len, src, n);
+        buf[*len + n] = '\0';
+        *len = 255;
+        return;
+    }
+    memcpy(buf + *len, src, n);
+    *len += n;
+    buf[*len] = '\0';
+}
+
+static char *ven_concat(char *buf, const char **parts, size_t count) {
+    size_t len = 0;
+    buf[0] = '\0';
+    for (size_t i = 0; i < count; i++) {
+        ven_put(buf, &len, parts[i], strlen(parts[i]));
+    }
+    return buf;
+}
+
+static int64_t ven_len(const char *text) {
+    int64_t chars = 0;
+    for (; *text; text++) {
+        chars += ((unsigned char)*text & 0xC0) != 0x80;
+    }
+    return chars;
+}
+
+/* The byte offset of character index, or the length when there are fewer. */
+static size_t ven_offset(const char *text, int64_t index) {
+    size_t at = 0;
+    for (int64_t chars = 0; text[at]; at++) {
+        if (((unsigned char)text[at] & 0xC0) != 0x80 && chars++ == index) {
+            break;
+        }
+    }
+    return at;
+}
+
+static char *ven_slice(char *buf, const char *text, int64_t start, int64_t end) {
+    size_t from = ven_offset(text, start < 0 ? 0 : start);
+    size_t to = ven_offset(text, end < 0 ? 0 : end);
+    size_t len = 0;
+    buf[0] = '\0';
+    if (to > from) {
+        ven_put(buf, &len, text + from, to - from);
+    }
+    return buf;
+}
+
+/* Only ASCII letters change case. */
+static char *ven_case(char *buf, const char *text, bool upper) {
+    size_t len = 0;
+    buf[0] = '\0';
+    ven_put(buf, &len, text, strlen(text));
+    for (char *at = buf; *at; at++) {
+        if (upper && *at >= 'a' && *at <= 'z') {
+            *at -= 32;
+        } else if (!upper && *at >= 'A' && *at <= 'Z') {
+            *at += 32;
+        }
+    }
+    return buf;
+}
+
+static bool ven_trimmed(char c) {
+    return c == ' ' || c == '\t' || c == '\n' || c == '\r';
+}
+
+static char *ven_trim(char *buf, const char *text) {
+    size_t n = strlen(text);
+    while (n > 0 && ven_trimmed(*text)) {
+        text++;
+        n--;
+    }
+    while (n > 0 && ven_trimmed(text[n - 1])) {
+        n--;
+    }
+    size_t len = 0;
+    buf[0] = '\0';
+    ven_put(buf, &len, text, n);
+    return buf;
+}
+
+static bool ven_starts_with(const char *text, const char *prefix) {
+    return strncmp(text, prefix, strlen(prefix)) == 0;
+}
+
+/* Every from, left to right; an empty from leaves the text as it is. */
+static char *ven_replace(char *buf, const char *text, const char *from, const char *to) {
+    size_t len = 0;
+    size_t from_len = strlen(from);
+    buf[0] = '\0';
+    const char *found;
+    while (from_len > 0 && (found = strstr(text, from)) != NULL) {
+        ven_put(buf, &len, text, (size_t)(found - text));
+        ven_put(buf, &len, to, strlen(to));
+        text = found + from_len;
+    }
+    ven_put(buf, &len, text, strlen(text));
+    return buf;
+}
+
+char raw[256] = "";
+char text[256] = "";
+char loud[256] = "";
+char first[256] = "";
+char rest[256] = "";
+char joined[256] = "";
+char dashed[256] = "";
+char code[5] = "";
+int8_t n = 0;
+bool found = false;
+
+int main(void) {
+    ven_copy(raw, "  héllo wörld  ", sizeof raw);
+    ven_copy(text, ven_trim((char[256]){0}, raw), sizeof text);
+    ven_copy(loud, ven_case((char[256]){0}, text, true), sizeof loud);
+    ven_copy(first, ven_slice((char[256]){0}, text, 0, 5), sizeof first);
+    ven_copy(rest, ven_slice((char[256]){0}, text, -2, 100), sizeof rest);
+    ven_copy(joined, ven_concat((char[256]){0}, (const char *[]){first, ", ", loud, "!"}, 4), sizeof joined);
+    ven_copy(dashed, ven_replace((char[256]){0}, text, "o", "0"), sizeof dashed);
+    ven_copy(code, ven_case((char[256]){0}, "ABCDEF", false), sizeof code);
+    n = (int8_t)ven_fit(ven_len(text), -128, 127, "n", "i8", 10);
+    found = (strstr(text, "wör") != NULL);
+    fputs(joined, stdout);
+    fputs("\n", stdout);
+    fputs(rest, stdout);
+    fputs("\n", stdout);
+    fputs(dashed, stdout);
+    fputs(" has ", stdout);
+    fprintf(stdout, "%lld", (long long)ven_len(dashed));
+    fputs(" characters\n", stdout);
+    fputs(code, stdout);
+    fputs("\n", stdout);
+    fprintf(stdout, "%lld", (long long)n);
+    fputs("\n", stdout);
+    fputs(found ? "true" : "false", stdout);
+    fputs("\n", stdout);
+    fputs(ven_starts_with(loud, "HÉ") ? "true" : "false", stdout);
+    fputs("\n", stdout);
+    if ((ven_len(raw) > n) && ven_starts_with(text, "hé")) {
+        fputs(ven_case((char[256]){0}, first, true), stdout);
+        fputs(" was trimmed\n", stdout);
+    }
+    return 0;
+}
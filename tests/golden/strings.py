@@ -0,0 +1,101 @@
+# Generated by the Ven compiler
+import sys
+
+
+def _read_line():
+    # A line holds at most 255 bytes, as on the native targets
+    line = sys.stdin.readline().rstrip("\r\n").encode()
+    return line[:255].decode(errors="ignore")
+
+
+# Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+def _to_int(text):
+    try:
+        value = int(text.strip())
+    except ValueError:
+        return 0
+    return value if -(2**63) <= value < 2**64 else 0
+
+
+def _to_float(text):
+    try:
+        return float(text.strip())
+    except ValueError:
+        return 0.0
+
+
+def _int_div(a, b):
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+
+
+def _fit(value, lo, hi, name, type, line):
+    return (value - lo) % (hi - lo + 1) + lo
+
+
+# Floats never wrap: they are truncated and clamp at the limits
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+
+
+def _fit_text(text, size):
+    data = str(text).encode()
+    if len(data) <= size:
+        return text
+    # A character cut in two is dropped whole
+    return data[:size].decode(errors="ignore")
+
+
+_UPPER = {c: c - 32 for c in range(97, 123)}
+_LOWER = {c: c + 32 for c in range(65, 91)}
+
+
+def _slice(text, start, end):
+    return text[max(start, 0):max(end, 0)]
+
+
+def _trim(text):
+    return text.strip(" \t\n\r")
+
+
+# An empty `old` leaves the text as it is
+def _replace(text, old, new):
+    return text.replace(old, new) if old else text
+
+
+raw = ""
+text = ""
+loud = ""
+first = ""
+rest = ""
+joined = ""
+dashed = ""
+code = ""
+n = 0
+found = False
+
+raw = "  héllo wörld  "
+text = _trim(raw)
+loud = text.translate(_UPPER)
+first = _slice(text, 0, 5)
+rest = _slice(text, -2, 100)
+joined = _fit_text(first + ", " + loud + "!", 255)
+dashed = _fit_text(_replace(text, "o", "0"), 255)
+code = _fit_text("ABCDEF".translate(_LOWER), 4)
+n = _fit(len(text), -128, 127, "n", "i8", 10)
+found = ("wör" in text)
+print(joined)
+print(rest)
+print(f"{dashed} has {len(dashed)} characters")
+print(code)
+print(n)
+print(str(found).lower())
+print(str(loud.startswith("HÉ")).lower())
+if (len(raw) > n) and (text.startswith("hé")):
+    print(f"{first.translate(_UPPER)} was trimmed")
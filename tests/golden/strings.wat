@@ -0,0 +1,1416 @@
+(module
+  (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_read" (func $fd_read (param i32 i32 i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (global $n (mut i64) (i64.const 0))
+  (global $found (mut i64) (i64.const 0))
+  (data (i32.const 60) "-\0a ")
+  (data (i32.const 320) "NaN\00-inf\00")
+  (data (i32.const 4869) "  h\c3\a9llo w\c3\b6rld  \00")
+  (data (i32.const 6167) ", \00")
+  (data (i32.const 6170) "!\00")
+  (data (i32.const 6428) "o\00")
+  (data (i32.const 6430) "0\00")
+  (data (i32.const 6688) "ABCDEF\00")
+  (data (i32.const 6695) "n\00")
+  (data (i32.const 6697) "i8\00")
+  (data (i32.const 6700) "w\c3\b6r\00")
+  (data (i32.const 6705) " has \00")
+  (data (i32.const 6711) " characters\00")
+  (data (i32.const 6723) "false\00")
+  (data (i32.const 6729) "true\00")
+  (data (i32.const 6734) "H\c3\89\00")
+  (data (i32.const 6738) "h\c3\a9\00")
+  (data (i32.const 6998) " was trimmed\00")
+  (func $write (param i32 i32 i32)
+    i32.const 0
+    local.get 1
+    i32.store
+    i32.const 4
+    local.get 2
+    i32.store
+    local.get 0
+    i32.const 0
+    i32.const 1
+    i32.const 8
+    call $fd_write
+    drop
+  )
+  (func $strlen (param i32) (result i32)
+    (local i32)
+    block
+      loop
+        local.get 0
+        local.get 1
+        i32.add
+        i32.load8_u
+        i32.eqz
+        br_if 1
+        local.get 1
+        i32.const 1
+        i32.add
+        local.set 1
+        br 0
+      end
+    end
+    local.get 1
+  )
+  (func $print_cstr (param i32 i32)
+    local.get 0
+    local.get 1
+    local.get 1
+    call $strlen
+    call $write
+  )
+  (func $print_digits (param i32 i64 i32)
+    (local i32)
+    i32.const 48
+    local.set 3
+    loop
+      local.get 3
+      i32.const 1
+      i32.sub
+      local.set 3
+      local.get 3
+      local.get 1
+      i64.const 10
+      i64.rem_u
+      i32.wrap_i64
+      i32.const 48
+      i32.add
+      i32.store8
+      local.get 1
+      i64.const 10
+      i64.div_u
+      local.set 1
+      local.get 2
+      i32.const 1
+      i32.sub
+      local.set 2
+      local.get 1
+      i64.eqz
+      i32.eqz
+      local.get 2
+      i32.const 0
+      i32.gt_s
+      i32.or
+      br_if 0
+    end
+    local.get 0
+    local.get 3
+    i32.const 48
+    local.get 3
+    i32.sub
+    call $write
+  )
+  (func $print_i64 (param i32 i64)
+    local.get 1
+    i64.const 0
+    i64.lt_s
+    if
+      local.get 0
+      i32.const 60
+      i32.const 1
+      call $write
+      i64.const 0
+      local.get 1
+      i64.sub
+      local.set 1
+    end
+    local.get 0
+    local.get 1
+    i32.const 1
+    call $print_digits
+  )
+  (func $read_line (param i32 i32) (result i32)
+    (local i32 i32)
+    block
+      loop
+        local.get 0
+        local.get 2
+        local.get 1
+        local.get 2
+        local.get 1
+        i32.lt_s
+        select
+        i32.add
+        local.set 3
+        i32.const 0
+        local.get 3
+        i32.store
+        i32.const 4
+        i32.const 1
+        i32.store
+        i32.const 0
+        i32.const 0
+        i32.const 1
+        i32.const 8
+        call $fd_read
+        br_if 1
+        i32.const 8
+        i32.load
+        i32.eqz
+        br_if 1
+        local.get 3
+        i32.load8_u
+        i32.const 10
+        i32.eq
+        br_if 1
+        local.get 2
+        local.get 1
+        i32.lt_s
+        if
+          local.get 2
+          i32.const 1
+          i32.add
+          local.set 2
+        end
+        br 0
+      end
+    end
+    local.get 2
+    i32.const 0
+    i32.gt_s
+    if
+      local.get 0
+      local.get 2
+      i32.add
+      i32.const 1
+      i32.sub
+      i32.load8_u
+      i32.const 13
+      i32.eq
+      if
+        local.get 2
+        i32.const 1
+        i32.sub
+        local.set 2
+      end
+    end
+    local.get 0
+    local.get 2
+    i32.add
+    i32.const 0
+    i32.store8
+    local.get 2
+  )
+  (func $parse_int (param i32) (result i64)
+    (local i64 i32 i32)
+    i32.const 12
+    i32.const 0
+    i32.store
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        i32.const 32
+        i32.ne
+        br_if 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        br 0
+      end
+    end
+    local.get 0
+    i32.load8_u
+    i32.const 45
+    i32.eq
+    if
+      i32.const 1
+      local.set 2
+      local.get 0
+      i32.const 1
+      i32.add
+      local.set 0
+    end
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        i32.const 48
+        i32.sub
+        local.tee 3
+        i32.const 9
+        i32.gt_u
+        br_if 1
+        local.get 1
+        i64.const -1
+        local.get 3
+        i64.extend_i32_u
+        i64.sub
+        i64.const 10
+        i64.div_u
+        i64.gt_u
+        if
+          i64.const 0
+          return
+        end
+        local.get 1
+        i64.const 10
+        i64.mul
+        local.get 3
+        i64.extend_i32_u
+        i64.add
+        local.set 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        br 0
+      end
+    end
+    local.get 2
+    if
+      local.get 1
+      i64.const -9223372036854775808
+      i64.gt_u
+      if
+        i64.const 0
+        return
+      end
+      i64.const 0
+      local.get 1
+      i64.sub
+      return
+    end
+    i32.const 12
+    local.get 1
+    i64.const 0
+    i64.lt_s
+    i32.store
+    local.get 1
+  )
+  (func $parse_f64 (param i32) (result f64)
+    (local f64 i32 i32 f64)
+    f64.const 1.0
+    local.set 4
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        i32.const 32
+        i32.ne
+        br_if 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        br 0
+      end
+    end
+    local.get 0
+    i32.load8_u
+    i32.const 45
+    i32.eq
+    if
+      i32.const 1
+      local.set 2
+      local.get 0
+      i32.const 1
+      i32.add
+      local.set 0
+    end
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        i32.const 48
+        i32.sub
+        local.tee 3
+        i32.const 9
+        i32.gt_u
+        br_if 1
+        local.get 1
+        f64.const 10.0
+        f64.mul
+        local.get 3
+        f64.convert_i32_u
+        f64.add
+        local.set 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        br 0
+      end
+    end
+    local.get 0
+    i32.load8_u
+    i32.const 46
+    i32.eq
+    if
+      local.get 0
+      i32.const 1
+      i32.add
+      local.set 0
+      block
+        loop
+          local.get 0
+          i32.load8_u
+          i32.const 48
+          i32.sub
+          local.tee 3
+          i32.const 9
+          i32.gt_u
+          br_if 1
+          local.get 1
+          f64.const 10.0
+          f64.mul
+          local.get 3
+          f64.convert_i32_u
+          f64.add
+          local.set 1
+          local.get 4
+          f64.const 10.0
+          f64.mul
+          local.set 4
+          local.get 0
+          i32.const 1
+          i32.add
+          local.set 0
+          br 0
+        end
+      end
+    end
+    local.get 1
+    local.get 4
+    f64.div
+    local.set 1
+    local.get 1
+    f64.neg
+    local.get 1
+    local.get 2
+    select
+  )
+  (func $str_cmp (param i32 i32) (result i32)
+    (local i32 i32)
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        local.set 2
+        local.get 1
+        i32.load8_u
+        local.set 3
+        local.get 2
+        local.get 3
+        i32.ne
+        br_if 1
+        local.get 2
+        i32.eqz
+        br_if 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        local.get 1
+        i32.const 1
+        i32.add
+        local.set 1
+        br 0
+      end
+    end
+    local.get 2
+    local.get 3
+    i32.sub
+  )
+  (func $str_copy (param i32 i32 i32)
+    (local i32 i32)
+    local.get 0
+    local.set 4
+    block
+      loop
+        local.get 1
+        i32.load8_u
+        local.tee 3
+        i32.eqz
+        br_if 1
+        local.get 2
+        i32.const 1
+        i32.le_s
+        if
+          block
+            loop
+              local.get 0
+              local.get 4
+              i32.le_u
+              br_if 1
+              local.get 1
+              i32.load8_u
+              i32.const 192
+              i32.and
+              i32.const 128
+              i32.ne
+              br_if 1
+              local.get 0
+              i32.const 1
+              i32.sub
+              local.set 0
+              local.get 1
+              i32.const 1
+              i32.sub
+              local.set 1
+              br 0
+            end
+          end
+          br 2
+        end
+        local.get 0
+        local.get 3
+        i32.store8
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        local.get 1
+        i32.const 1
+        i32.add
+        local.set 1
+        local.get 2
+        i32.const 1
+        i32.sub
+        local.set 2
+        br 0
+      end
+    end
+    local.get 0
+    i32.const 0
+    i32.store8
+  )
+  (func $fit (param i64 i32 i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 2
+    i64.eqz
+    local.get 3
+    i64.const -1
+    i64.eq
+    i32.and
+    if
+      local.get 1
+      i32.eqz
+      local.get 0
+      i64.const 0
+      i64.lt_s
+      i32.and
+      if
+      end
+      local.get 0
+      return
+    end
+    local.get 1
+    local.get 0
+    i64.const 0
+    i64.lt_s
+    i32.and
+    if
+      local.get 0
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.and
+      local.set 6
+      local.get 6
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.sub
+      i64.const 1
+      i64.sub
+      local.get 6
+      local.get 6
+      local.get 3
+      i64.gt_s
+      select
+      return
+    end
+    local.get 0
+    local.get 2
+    i64.lt_s
+    if
+      local.get 0
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.and
+      local.set 6
+      local.get 6
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.sub
+      i64.const 1
+      i64.sub
+      local.get 6
+      local.get 6
+      local.get 3
+      i64.gt_s
+      select
+      return
+    end
+    local.get 0
+    local.get 3
+    i64.gt_s
+    if
+      local.get 0
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.and
+      local.set 6
+      local.get 6
+      local.get 3
+      local.get 2
+      i64.sub
+      i64.sub
+      i64.const 1
+      i64.sub
+      local.get 6
+      local.get 6
+      local.get 3
+      i64.gt_s
+      select
+      return
+    end
+    local.get 0
+  )
+  (func $fit_float (param f64 f64 f64 i64 i64 i32 i32) (result i64)
+    local.get 0
+    local.get 0
+    f64.ne
+    if
+      i64.const 0
+      return
+    end
+    local.get 0
+    f64.trunc
+    local.set 0
+    local.get 0
+    local.get 1
+    f64.lt
+    if
+      local.get 3
+      return
+    end
+    local.get 0
+    local.get 2
+    f64.ge
+    if
+      local.get 4
+      return
+    end
+    local.get 0
+    f64.const 0.0
+    f64.lt
+    if
+      local.get 0
+      i64.trunc_f64_s
+      return
+    end
+    local.get 0
+    i64.trunc_f64_u
+  )
+  (func $add_i64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.add
+    local.set 4
+    local.get 0
+    local.get 4
+    i64.xor
+    local.get 1
+    local.get 4
+    i64.xor
+    i64.and
+    i64.const 0
+    i64.lt_s
+    if
+    end
+    local.get 4
+  )
+  (func $sub_i64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.sub
+    local.set 4
+    local.get 0
+    local.get 1
+    i64.xor
+    local.get 0
+    local.get 4
+    i64.xor
+    i64.and
+    i64.const 0
+    i64.lt_s
+    if
+    end
+    local.get 4
+  )
+  (func $mul_i64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.mul
+    local.set 4
+    local.get 0
+    i64.const -1
+    i64.eq
+    if
+      local.get 1
+      i64.const -9223372036854775808
+      i64.eq
+      i64.extend_i32_u
+      local.set 4
+    else
+      local.get 0
+      i64.eqz
+      if
+        i64.const 0
+        local.set 4
+      else
+        local.get 4
+        local.get 0
+        i64.div_s
+        local.get 1
+        i64.ne
+        i64.extend_i32_u
+        local.set 4
+      end
+    end
+    local.get 4
+    i64.eqz
+    i32.eqz
+    local.get 0
+    local.get 1
+    i64.mul
+    local.set 4
+    if
+    end
+    local.get 4
+  )
+  (func $div_i64 (param i64 i64 i32 i32) (result i64)
+    local.get 0
+    i64.const -9223372036854775808
+    i64.eq
+    local.get 1
+    i64.const -1
+    i64.eq
+    i32.and
+    if
+      i64.const -9223372036854775808
+      return
+    end
+    local.get 0
+    local.get 1
+    i64.div_s
+  )
+  (func $add_u64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.add
+    local.set 4
+    local.get 4
+    local.get 0
+    i64.lt_u
+    if
+    end
+    local.get 4
+  )
+  (func $sub_u64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.sub
+    local.set 4
+    local.get 0
+    local.get 1
+    i64.lt_u
+    if
+    end
+    local.get 4
+  )
+  (func $mul_u64 (param i64 i64 i32 i32) (result i64)
+    (local i64)
+    local.get 0
+    local.get 1
+    i64.mul
+    local.set 4
+    local.get 0
+    i64.eqz
+    if
+      i64.const 0
+      local.set 4
+    else
+      local.get 4
+      local.get 0
+      i64.div_u
+      local.get 1
+      i64.ne
+      i64.extend_i32_u
+      local.set 4
+    end
+    local.get 4
+    i64.eqz
+    i32.eqz
+    local.get 0
+    local.get 1
+    i64.mul
+    local.set 4
+    if
+    end
+    local.get 4
+  )
+  (func $compare (param i64 i32 i64 i32) (result i32)
+    local.get 1
+    i32.eqz
+    local.get 0
+    i64.const 0
+    i64.lt_s
+    i32.and
+    local.get 3
+    i32.eqz
+    local.get 2
+    i64.const 0
+    i64.lt_s
+    i32.and
+    i32.ne
+    if
+      i32.const -1
+      i32.const 1
+      local.get 1
+      i32.eqz
+      local.get 0
+      i64.const 0
+      i64.lt_s
+      i32.and
+      select
+      return
+    end
+    local.get 1
+    local.get 3
+    i32.or
+    if
+      local.get 0
+      local.get 2
+      i64.gt_u
+      local.get 0
+      local.get 2
+      i64.lt_u
+      i32.sub
+      return
+    end
+    local.get 0
+    local.get 2
+    i64.gt_s
+    local.get 0
+    local.get 2
+    i64.lt_s
+    i32.sub
+  )
+  (func $str_put (param i32 i32 i32 i32) (result i32)
+    (local i32 i32)
+    local.get 1
+    local.get 3
+    i32.add
+    i32.const 256
+    i32.ge_u
+    if
+      i32.const 1
+      local.set 5
+      i32.const 255
+      local.get 1
+      i32.sub
+      local.set 3
+      block
+        loop
+          local.get 3
+          i32.eqz
+          br_if 1
+          local.get 2
+          local.get 3
+          i32.add
+          i32.load8_u
+          i32.const 192
+          i32.and
+          i32.const 128
+          i32.ne
+          br_if 1
+          local.get 3
+          i32.const 1
+          i32.sub
+          local.set 3
+          br 0
+        end
+      end
+    end
+    block
+      loop
+        local.get 4
+        local.get 3
+        i32.ge_u
+        br_if 1
+        local.get 0
+        local.get 1
+        i32.add
+        local.get 4
+        i32.add
+        local.get 2
+        local.get 4
+        i32.add
+        i32.load8_u
+        i32.store8
+        local.get 4
+        i32.const 1
+        i32.add
+        local.set 4
+        br 0
+      end
+    end
+    local.get 0
+    local.get 1
+    i32.add
+    local.get 3
+    i32.add
+    i32.const 0
+    i32.store8
+    i32.const 255
+    local.get 1
+    local.get 3
+    i32.add
+    local.get 5
+    select
+  )
+  (func $str_cat (param i32 i32 i32) (result i32)
+    local.get 2
+    local.get 0
+    local.get 1
+    local.get 1
+    call $strlen
+    call $str_put
+  )
+  (func $str_chars (param i32) (result i64)
+    (local i64 i32)
+    block
+      loop
+        local.get 0
+        i32.load8_u
+        local.tee 2
+        i32.eqz
+        br_if 1
+        local.get 1
+        local.get 2
+        i32.const 192
+        i32.and
+        i32.const 128
+        i32.ne
+        i64.extend_i32_u
+        i64.add
+        local.set 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        br 0
+      end
+    end
+    local.get 1
+  )
+  (func $str_offset (param i32 i64) (result i32)
+    (local i32 i64 i32)
+    local.get 1
+    i64.const 0
+    i64.lt_s
+    if
+      i64.const 0
+      local.set 1
+    end
+    block
+      loop
+        local.get 0
+        local.get 2
+        i32.add
+        i32.load8_u
+        local.tee 4
+        i32.eqz
+        br_if 1
+        local.get 4
+        i32.const 192
+        i32.and
+        i32.const 128
+        i32.ne
+        if
+          local.get 3
+          local.get 1
+          i64.eq
+          br_if 2
+          local.get 3
+          i64.const 1
+          i64.add
+          local.set 3
+        end
+        local.get 2
+        i32.const 1
+        i32.add
+        local.set 2
+        br 0
+      end
+    end
+    local.get 2
+  )
+  (func $str_slice (param i32 i32 i64 i64) (result i32)
+    (local i32 i32)
+    local.get 1
+    local.get 2
+    call $str_offset
+    local.set 4
+    local.get 1
+    local.get 3
+    call $str_offset
+    local.set 5
+    local.get 0
+    i32.const 0
+    i32.store8
+    local.get 5
+    local.get 4
+    i32.gt_u
+    if
+      local.get 0
+      i32.const 0
+      local.get 1
+      local.get 4
+      i32.add
+      local.get 5
+      local.get 4
+      i32.sub
+      call $str_put
+      drop
+    end
+    local.get 0
+  )
+  (func $str_case (param i32 i32 i32 i32) (result i32)
+    (local i32 i32)
+    local.get 0
+    i32.const 0
+    local.get 1
+    local.get 1
+    call $strlen
+    call $str_put
+    drop
+    local.get 0
+    local.set 4
+    block
+      loop
+        local.get 4
+        i32.load8_u
+        local.tee 5
+        i32.eqz
+        br_if 1
+        local.get 5
+        local.get 2
+        i32.sub
+        i32.const 26
+        i32.lt_u
+        if
+          local.get 4
+          local.get 5
+          local.get 3
+          i32.add
+          i32.store8
+        end
+        local.get 4
+        i32.const 1
+        i32.add
+        local.set 4
+        br 0
+      end
+    end
+    local.get 0
+  )
+  (func $str_trim (param i32 i32) (result i32)
+    (local i32)
+    block
+      loop
+        local.get 1
+        i32.load8_u
+        call $blank
+        i32.eqz
+        br_if 1
+        local.get 1
+        i32.const 1
+        i32.add
+        local.set 1
+        br 0
+      end
+    end
+    local.get 1
+    local.get 1
+    call $strlen
+    i32.add
+    local.set 2
+    block
+      loop
+        local.get 2
+        local.get 1
+        i32.le_u
+        br_if 1
+        local.get 2
+        i32.const 1
+        i32.sub
+        i32.load8_u
+        call $blank
+        i32.eqz
+        br_if 1
+        local.get 2
+        i32.const 1
+        i32.sub
+        local.set 2
+        br 0
+      end
+    end
+    local.get 0
+    i32.const 0
+    local.get 1
+    local.get 2
+    local.get 1
+    i32.sub
+    call $str_put
+    drop
+    local.get 0
+  )
+  (func $str_find (param i32 i32) (result i32)
+    (local i32 i32)
+    block
+      loop
+        local.get 0
+        local.set 2
+        local.get 1
+        local.set 3
+        block
+          loop
+            local.get 3
+            i32.load8_u
+            i32.eqz
+            br_if 3
+            local.get 2
+            i32.load8_u
+            local.get 3
+            i32.load8_u
+            i32.ne
+            br_if 1
+            local.get 2
+            i32.const 1
+            i32.add
+            local.set 2
+            local.get 3
+            i32.const 1
+            i32.add
+            local.set 3
+            br 0
+          end
+        end
+        local.get 0
+        i32.load8_u
+        i32.eqz
+        if
+          i32.const 0
+          local.set 0
+          br 2
+        end
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        br 0
+      end
+    end
+    local.get 0
+  )
+  (func $str_prefix (param i32 i32) (result i32)
+    block
+      loop
+        local.get 1
+        i32.load8_u
+        i32.eqz
+        br_if 1
+        local.get 0
+        i32.load8_u
+        local.get 1
+        i32.load8_u
+        i32.ne
+        br_if 1
+        local.get 0
+        i32.const 1
+        i32.add
+        local.set 0
+        local.get 1
+        i32.const 1
+        i32.add
+        local.set 1
+        br 0
+      end
+    end
+    local.get 1
+    i32.load8_u
+    i32.eqz
+  )
+  (func $str_replace (param i32 i32 i32 i32) (result i32)
+    (local i32 i32 i32)
+    local.get 2
+    call $strlen
+    local.set 6
+    local.get 6
+    if
+      block
+        loop
+          local.get 1
+          local.get 2
+          call $str_find
+          local.tee 5
+          i32.eqz
+          br_if 1
+          local.get 0
+          local.get 4
+          local.get 1
+          local.get 5
+          local.get 1
+          i32.sub
+          call $str_put
+          local.set 4
+          local.get 0
+          local.get 4
+          local.get 3
+          local.get 3
+          call $strlen
+          call $str_put
+          local.set 4
+          local.get 5
+          local.get 6
+          i32.add
+          local.set 1
+          br 0
+        end
+      end
+    end
+    local.get 0
+    local.get 4
+    local.get 1
+    local.get 1
+    call $strlen
+    call $str_put
+    drop
+    local.get 0
+  )
+  (func $blank (param i32) (result i32)
+    local.get 0
+    i32.const 32
+    i32.eq
+    local.get 0
+    i32.const 9
+    i32.eq
+    i32.or
+    local.get 0
+    i32.const 10
+    i32.eq
+    i32.or
+    local.get 0
+    i32.const 13
+    i32.eq
+    i32.or
+  )
+  (func $_start (export "_start")
+    i32.const 3072
+    i32.const 4869
+    i32.const 256
+    call $str_copy
+    i32.const 3328
+    i32.const 4887
+    i32.const 3072
+    call $str_trim
+    i32.const 256
+    call $str_copy
+    i32.const 3584
+    i32.const 5143
+    i32.const 3328
+    i32.const 97
+    i32.const -32
+    call $str_case
+    i32.const 256
+    call $str_copy
+    i32.const 3840
+    i32.const 5399
+    i32.const 3328
+    i64.const 0
+    i64.const 5
+    call $str_slice
+    i32.const 256
+    call $str_copy
+    i32.const 4096
+    i32.const 5655
+    i32.const 3328
+    i64.const -2
+    i64.const 100
+    call $str_slice
+    i32.const 256
+    call $str_copy
+    i32.const 4352
+    i32.const 0
+    i32.const 3840
+    i32.const 5911
+    call $str_cat
+    i32.const 6167
+    i32.const 5911
+    call $str_cat
+    i32.const 3584
+    i32.const 5911
+    call $str_cat
+    i32.const 6170
+    i32.const 5911
+    call $str_cat
+    drop
+    i32.const 5911
+    i32.const 256
+    call $str_copy
+    i32.const 4608
+    i32.const 6172
+    i32.const 3328
+    i32.const 6428
+    i32.const 6430
+    call $str_replace
+    i32.const 256
+    call $str_copy
+    i32.const 4864
+    i32.const 6432
+    i32.const 6688
+    i32.const 65
+    i32.const 32
+    call $str_case
+    i32.const 5
+    call $str_copy
+    i32.const 3328
+    call $str_chars
+    i32.const 0
+    i64.const -128
+    i64.const 127
+    i32.const 6695
+    i32.const 6697
+    call $fit
+    global.set $n
+    i32.const 3328
+    i32.const 6700
+    call $str_find
+    i32.const 0
+    i32.ne
+    i64.extend_i32_u
+    global.set $found
+    i32.const 1
+    i32.const 4352
+    call $print_cstr
+    i32.const 1
+    i32.const 61
+    i32.const 1
+    call $write
+    i32.const 1
+    i32.const 4096
+    call $print_cstr
+    i32.const 1
+    i32.const 61
+    i32.const 1
+    call $write
+    i32.const 1
+    i32.const 4608
+    call $print_cstr
+    i32.const 1
+    i32.const 6705
+    i32.const 5
+    call $write
+    i32.const 1
+    i32.const 4608
+    call $str_chars
+    call $print_i64
+    i32.const 1
+    i32.const 6711
+    i32.const 11
+    call $write
+    i32.const 1
+    i32.const 61
+    i32.const 1
+    call $write
+    i32.const 1
+    i32.const 4864
+    call $print_cstr
+    i32.const 1
+    i32.const 61
+    i32.const 1
+    call $write
+    i32.const 1
+    global.get $n
+    call $print_i64
+    i32.const 1
+    i32.const 61
+    i32.const 1
+    call $write
+    global.get $found
+    i64.eqz
+    if
+      i32.const 1
+      i32.const 6723
+      i32.const 5
+      call $write
+    else
+      i32.const 1
+      i32.const 6729
+      i32.const 4
+      call $write
+    end
+    i32.const 1
+    i32.const 61
+    i32.const 1
+    call $write
+    i32.const 1
+    i32.const 6723
+    i32.const 6729
+    i32.const 3584
+    i32.const 6734
+    call $str_prefix
+    i64.extend_i32_u
+    i64.eqz
+    select
+    call $print_cstr
+    i32.const 1
+    i32.const 61
+    i32.const 1
+    call $write
+    i32.const 3072
+    call $str_chars
+    global.get $n
+    i64.gt_s
+    i32.const 3328
+    i32.const 6738
+    call $str_prefix
+    i64.extend_i32_u
+    i64.eqz
+    i32.eqz
+    i32.and
+    if
+      i32.const 1
+      i32.const 6742
+      i32.const 3840
+      i32.const 97
+      i32.const -32
+      call $str_case
+      call $print_cstr
+      i32.const 1
+      i32.const 6998
+      i32.const 12
+      call $write
+      i32.const 1
+      i32.const 61
+      i32.const 1
+      call $write
+    end
+  )
+)
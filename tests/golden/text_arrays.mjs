@@ -0,0 +1,104 @@
+// Generated by the Ven compiler
+import fs from "node:fs";
+
+const __byte = Buffer.alloc(1);
+function __readLine() {
+  const bytes = [];
+  for (;;) {
+    let read = 0;
+    try {
+      read = fs.readSync(0, __byte, 0, 1, null);
+    } catch (e) {
+      if (e.code === "EAGAIN") continue;
+      if (e.code === "EOF") break;
+      throw e;
+    }
+    if (read === 0 || __byte[0] === 10) break;
+    bytes.push(__byte[0]);
+  }
+  if (bytes[bytes.length - 1] === 13) bytes.pop();
+  // A line holds at most 255 bytes, as on the native targets, and a character
+  // cut in two is dropped whole
+  let end = Math.min(bytes.length, 255);
+  while (end > 0 && end < bytes.length && (bytes[end] & 0xc0) === 0x80) end--;
+  return Buffer.from(bytes.slice(0, end)).toString("utf8");
+}
+
+// Integers are BigInts. Text that is not a whole number from the i64 minimum to
+// the u64 maximum reads as 0.
+function __toInt(text) {
+  const trimmed = text.trim();
+  if (!/^[+-]?[0-9]+$/.test(trimmed)) return 0n;
+  const value = BigInt(trimmed);
+  return value < -(2n ** 63n) || value >= 2n ** 64n ? 0n : value;
+}
+
+function __fit(value, min, max, name, type, line) {
+  if (value >= min && value <= max) return value;
+  const span = max - min + 1n;
+  return (((value - min) % span) + span) % span + min;
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+function __fitFloat(value, min, max, name, type, line) {
+  if (Number.isNaN(value)) return 0n;
+  const whole = Math.trunc(value);
+  return whole < Number(min) ? min : whole >= Number(max) + 1 ? max : BigInt(whole);
+}
+
+function __index(index, length, name, line) {
+  const i = BigInt(index);
+  if (i < 0n || i >= BigInt(length)) {
+    process.stderr.write(`runtime error: index ${i} is out of bounds for '${name}' (length ${length}) on line ${line}\n`);
+    process.exit(1);
+  }
+  return Number(i);
+}
+
+function __fitText(text, size) {
+  const bytes = Buffer.from(String(text));
+  if (bytes.length <= size) return text;
+  let end = size;
+  while (end > 0 && (bytes[end] & 0xc0) === 0x80) end--;
+  return bytes.subarray(0, end).toString("utf8");
+}
+
+function __slice(text, start, end) {
+  const chars = Array.from(text);
+  const clamp = (index) => (index < 0n ? 0 : index > BigInt(chars.length) ? chars.length : Number(index));
+  const [from, to] = [clamp(start), clamp(end)];
+  return to <= from ? "" : chars.slice(from, to).join("");
+}
+
+function __upper(text) {
+  return text.replace(/[a-z]+/g, (letters) => letters.toUpperCase());
+}
+
+function __lower(text) {
+  return text.replace(/[A-Z]+/g, (letters) => letters.toLowerCase());
+}
+
+function __trim(text) {
+  return text.replace(/^[ \t\n\r]+|[ \t\n\r]+$/g, "");
+}
+
+// An empty `from` leaves the text as it is
+function __replace(text, from, to) {
+  return from === "" ? text : text.split(from).join(to);
+}
+
+let words, greeting, part, word;
+
+words = ["kiwi", "fig"];
+greeting = "hello";
+part = __slice(greeting, 1n, 3n);
+words.push(__upper(part));
+for (let __loop0 = 0; __loop0 < words.length; __loop0++) {
+  word = words[__loop0];
+  if ((BigInt(Array.from(word).length) > 3n) || word.includes("EL")) {
+    process.stdout.write(`${word} has ${BigInt(Array.from(word).length)} characters\n`);
+  }
+}
+process.stdout.write(`${__slice(greeting, 1n, 3n)}\n`);
+process.stdout.write(`${words[__index(0, words.length, "words", 12)]}\n`);
+process.stdout.write(`${BigInt(words.length)} words, last ${words[__index(2, words.length, "words", 13)]}\n`);
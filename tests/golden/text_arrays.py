@@ -0,0 +1,107 @@
+# Generated by the Ven compiler
+import sys
+
+
+def _read_line():
+    # A line holds at most 255 bytes, as on the native targets
+    line = sys.stdin.readline().rstrip("\r\n").encode()
+    return line[:255].decode(errors="ignore")
+
+
+# Text that is not a whole number from the i64 minimum to the u64 maximum reads as 0
+def _to_int(text):
+    try:
+        value = int(text.strip())
+    except ValueError:
+        return 0
+    return value if -(2**63) <= value < 2**64 else 0
+
+
+def _to_float(text):
+    try:
+        return float(text.strip())
+    except ValueError:
+        return 0.0
+
+
+def _int_div(a, b):
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+
+
+def _fit(value, lo, hi, name, type, line):
+    return (value - lo) % (hi - lo + 1) + lo
+
+
+# Floats never wrap: they are truncated and clamp at the limits
+def _fit_float(value, lo, hi, name, type, line):
+    if value != value:
+        return 0
+    if value <= lo - 1:
+        return lo
+    if value >= hi + 1:
+        return hi
+    return int(value)
+
+
+def _shown(value):
+    if isinstance(value, bool):
+        return str(value).lower()
+    return _float_text(value) if isinstance(value, float) else str(value)
+
+
+def _index(index, length, name, line):
+    if not 0 <= index < length:
+        sys.stdout.flush()
+        print(f"runtime error: index {index} is out of bounds for '{name}' (length {length}) on line {line}", file=sys.stderr)
+        sys.exit(1)
+    return index
+
+
+def _show(items):
+    return "[" + ", ".join(_shown(item) for item in items) + "]"
+
+
+def _fit_text(text, size):
+    data = str(text).encode()
+    if len(data) <= size:
+        return text
+    # A character cut in two is dropped whole
+    return data[:size].decode(errors="ignore")
+
+
+_UPPER = {c: c - 32 for c in range(97, 123)}
+_LOWER = {c: c + 32 for c in range(65, 91)}
+
+
+def _slice(text, start, end):
+    return text[max(start, 0):max(end, 0)]
+
+
+def _trim(text):
+    return text.strip(" \t\n\r")
+
+
+# An empty `old` leaves the text as it is
+def _replace(text, old, new):
+    return text.replace(old, new) if old else text
+
+
+words = []
+greeting = ""
+part = ""
+word = ""
+
+words = ["kiwi", "fig"]
+greeting = "hello"
+part = _slice(greeting, 1, 3)
+words.append(part.translate(_UPPER))
+_loop0 = 0
+while _loop0 < len(words):
+    word = words[_loop0]
+    if (len(word) > 3) or (("EL" in word)):
+        print(f"{word} has {len(word)} characters")
+    _loop0 += 1
+print(_slice(greeting, 1, 3))
+print(words[_index(0, len(words), 'words', 12)])
+print(f"{len(words)} words, last {words[_index(2, len(words), 'words', 13)]}")
@@ -0,0 +1,113 @@
+// Generated by the Ven compiler
+#![allow(dead_code, unused_mut, unused_assignments, unused_variables, unused_parens)]
+use std::io::BufRead;
+
+fn ven_read_line() -> String {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).unwrap_or(0);
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+    // A line holds at most 255 bytes, as on the native targets
+    let mut end = line.len().min(255);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line.truncate(end);
+    line
+}
+
+// Text that is not a whole number from i64::MIN to u64::MAX reads as 0
+fn ven_to_int(text: &str) -> i128 {
+    text.trim()
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (i64::MIN as i128..=u64::MAX as i128).contains(value))
+        .unwrap_or(0)
+}
+
+fn ven_to_float(text: &str) -> f64 {
+    text.trim().parse().unwrap_or(0.0)
+}
+
+fn ven_to_bool(text: &str) -> bool {
+    text.trim() == "true"
+}
+
+fn ven_fit(value: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    if value < min || value > max {
+        let span = max - min + 1;
+        let low = value.rem_euclid(span);
+        return if low > max { low - span } else { low };
+    }
+    value
+}
+
+// Floats never wrap: they are truncated and clamp at the limits
+fn ven_fit_float(value: f64, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    (value as i128).clamp(min, max)
+}
+
+// Only the product of two large u64 values is too big for i128
+fn ven_mul(a: i128, b: i128, min: i128, max: i128, name: &str, ty: &str, line: u32) -> i128 {
+    match a.checked_mul(b) {
+        Some(product) => ven_fit(product, min, max, name, ty, line),
+        None => (a as u64).wrapping_mul(b as u64) as i128,
+    }
+}
+
+fn ven_index(index: i128, len: usize, name: &str, line: u32) -> usize {
+    if index < 0 || index >= len as i128 {
+        eprintln!("runtime error: index {} is out of bounds for '{}' (length {}) on line {}", index, name, len, line);
+        std::process::exit(1);
+    }
+    index as usize
+}
+
+fn ven_fit_text(mut text: String, size: usize) -> String {
+    if text.len() > size {
+        let mut end = size;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+    text
+}
+
+// The characters from `start` up to `end`, both clamped to the text
+fn ven_slice(text: &str, start: i128, end: i128) -> String {
+    let offset = |index: i128| {
+        let index = index.clamp(0, usize::MAX as i128) as usize;
+        text.char_indices().nth(index).map_or(text.len(), |(offset, _)| offset)
+    };
+    let (start, end) = (offset(start), offset(end));
+    if end <= start { String::new() } else { text[start..end].to_string() }
+}
+
+// An empty `from` leaves the text as it is
+fn ven_replace(text: &str, from: &str, to: &str) -> String {
+    if from.is_empty() { text.to_string() } else { text.replace(from, to) }
+}
+
+fn main() {
+    let mut words: Vec<String> = Vec::new();
+    let mut greeting = String::new();
+    let mut part = String::new();
+    let mut word = String::new();
+    words = vec!["kiwi".to_string(), "fig".to_string()];
+    greeting = "hello".to_string();
+    part = ven_slice(greeting.as_str(), 1 as i128, 3 as i128).to_string();
+    words.push(part.as_str().to_ascii_uppercase().to_string());
+    let mut ven_loop1 = 0;
+    while ven_loop1 < words.len() {
+        word = words[ven_loop1].clone();
+        if (((word.chars().count() as i64) > 3) || word.as_str().contains("EL")) {
+            println!("{} has {} characters", word, (word.chars().count() as i64));
+        }
+        ven_loop1 += 1;
+    }
+    println!("{}", ven_slice(greeting.as_str(), 1 as i128, 3 as i128));
+    println!("{}", words[ven_index(0 as i128, words.len(), "words", 12)]);
+    println!("{} words, last {}", (words.len() as i64), words[ven_index(2 as i128, words.len(), "words", 13)]);
+}
@@ -0,0 +1,95 @@
+//! `ven lint` on files on disk: rules, `ven:allow` comments and `.venlint` scoping.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use common::scratch;
+
+/// The rules `ven lint` warns about in `path`, in order, and its exit code.
+fn lint(path: &Path, flags: &[&str]) -> (Vec<String>, i32) {
+    let output = Command::new(env!("CARGO_BIN_EXE_ven"))
+        .arg("lint")
+        .args(flags)
+        .arg(path)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let rules = stderr
+        .lines()
+        .filter_map(|line| line.rsplit_once(" [")?.1.strip_suffix(']').map(str::to_string))
+        .collect();
+    (rules, output.status.code().unwrap())
+}
+
+#[test]
+fn each_rule_is_reported() {
+    let dir = scratch("rules");
+    let cases = [
+        ("@ a i 1\n", "unused_variable"),
+        ("@@ a i 1\n>> a\n", "never_mutated"),
+        ("?(1 < 2) {\n>> \"yes\"\n}\n", "constant_condition"),
+        ("?(\"1\" == 1) {\n>> 1\n}\n", "mixed_comparison"),
+        ("@@ a i 1\n* a / 0\n>> a\n", "division_by_zero"),
+        ("@ a i 1\n>> a\n@ a i 2\n>> a\n", "shadowed_declaration"),
+        ("@ xs i[] [1, 2]\n>> \"{xs:>5}\"\n", "undefined_placeholder"),
+        ("; ven:allow(no_such_rule)\n>> 1\n", "unknown_rule"),
+    ];
+    for (source, rule) in cases {
+        let path = dir.join(format!("{}.ven", rule));
+        fs::write(&path, source).unwrap();
+        let (rules, _) = lint(&path, &[]);
+        assert!(rules.iter().any(|found| found == rule), "{:?} gave {:?}, not {}", source, rules, rule);
+    }
+}
+
+#[test]
+fn warnings_only_fail_with_deny_warnings() {
+    let dir = scratch("deny");
+    let path = dir.join("unused.ven");
+    fs::write(&path, "@ a i 1\n").unwrap();
+    assert_eq!(lint(&path, &[]), (vec!["unused_variable".to_string()], 0));
+    assert_eq!(lint(&path, &["--deny-warnings"]), (vec!["unused_variable".to_string()], 1));
+
+    let clean = dir.join("clean.ven");
+    fs::write(&clean, "@ a i 1\n>> a\n").unwrap();
+    assert_eq!(lint(&clean, &["--deny-warnings"]), (Vec::new(), 0));
+}
+
+#[test]
+fn allow_comments_cover_their_line_or_file() {
+    let dir = scratch("allow");
+    let line = dir.join("line.ven");
+    fs::write(&line, "@ a i 1 ; ven:allow(unused_variable)\n@ b i 2\n").unwrap();
+    assert_eq!(lint(&line, &[]).0, vec!["unused_variable"]);
+
+    let file = dir.join("file.ven");
+    fs::write(&file, "; ven:allow(unused_variable)\n@ a i 1\n@ b i 2\n").unwrap();
+    assert_eq!(lint(&file, &[]).0, Vec::<String>::new());
+}
+
+#[test]
+fn venlint_applies_to_its_directory_and_below() {
+    let dir = scratch("venlint");
+    let source = "@ a i 1\n@@ b i 2\n>> b\n";
+    fs::create_dir_all(dir.join("sub/deeper")).unwrap();
+    fs::write(dir.join("sub/.venlint"), "; quieter here\nallow = never_mutated\n").unwrap();
+    for path in ["top.ven", "sub/mid.ven", "sub/deeper/low.ven"] {
+        fs::write(dir.join(path), source).unwrap();
+    }
+
+    assert_eq!(lint(&dir.join("top.ven"), &[]).0, vec!["unused_variable", "never_mutated"]);
+    assert_eq!(lint(&dir.join("sub/mid.ven"), &[]).0, vec!["unused_variable"]);
+    assert_eq!(lint(&dir.join("sub/deeper/low.ven"), &[]).0, vec!["unused_variable"]);
+}
+
+#[test]
+fn bad_venlint_files_are_errors() {
+    let dir = scratch("bad_venlint");
+    fs::write(dir.join(".venlint"), "allow = bogus\n").unwrap();
+    let path = dir.join("main.ven");
+    fs::write(&path, "@ a i 1\n").unwrap();
+    assert_eq!(lint(&path, &[]), (Vec::new(), 1));
+}
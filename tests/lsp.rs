@@ -0,0 +1,136 @@
+//! `ven lsp` over its stdin and stdout: one session per test, checked frame by frame.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const URI: &str = "file:///main.ven";
+
+fn frame(body: &str) -> String {
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+fn request(id: u32, method: &str, params: &str) -> String {
+    frame(&format!(r#"{{"jsonrpc":"2.0","id":{},"method":"{}","params":{}}}"#, id, method, params))
+}
+
+fn notification(method: &str, params: &str) -> String {
+    frame(&format!(r#"{{"jsonrpc":"2.0","method":"{}","params":{}}}"#, method, params))
+}
+
+fn open(text: &str) -> String {
+    let text = text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+    notification(
+        "textDocument/didOpen",
+        &format!(r#"{{"textDocument":{{"uri":"{}","languageId":"ven","version":1,"text":"{}"}}}}"#, URI, text),
+    )
+}
+
+fn at(line: u32, character: u32) -> String {
+    format!(r#"{{"textDocument":{{"uri":"{}"}},"position":{{"line":{},"character":{}}}}}"#, URI, line, character)
+}
+
+/// Feeds `input` to a fresh server and returns the body of every message it
+/// sent back, with its exit code.
+fn session(input: &[u8]) -> (Vec<String>, i32) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ven"))
+        .arg("lsp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    let output = child.wait_with_output().unwrap();
+    let mut rest = String::from_utf8(output.stdout).unwrap();
+    let mut bodies = Vec::new();
+    while let Some((header, after)) = rest.split_once("\r\n\r\n") {
+        let length: usize = header.trim_start_matches("Content-Length: ").parse().unwrap();
+        bodies.push(after[..length].to_string());
+        rest = after[length..].to_string();
+    }
+    (bodies, output.status.code().unwrap())
+}
+
+/// The body of the reply to request `id`.
+fn reply(bodies: &[String], id: u32) -> &str {
+    let marker = format!(r#""id":{},"#, id);
+    bodies.iter().find(|body| body.contains(&marker)).unwrap()
+}
+
+#[test]
+fn requests_are_answered() {
+    let input = [
+        request(1, "initialize", "{}"),
+        notification("initialized", "{}"),
+        open("@ a i 5\n>> \"{a}\"\n>> \"{b}\"\n"),
+        request(2, "textDocument/hover", &at(1, 5)),
+        request(3, "textDocument/definition", &at(1, 5)),
+        request(4, "textDocument/completion", &at(1, 5)),
+        request(5, "textDocument/documentSymbol", &at(0, 0)),
+        request(6, "textDocument/frobnicate", "{}"),
+        request(7, "shutdown", "null"),
+        notification("exit", "null"),
+    ]
+    .concat();
+    let (bodies, code) = session(input.as_bytes());
+    assert_eq!(code, 0);
+
+    assert!(reply(&bodies, 1).contains(r#""hoverProvider":true"#));
+    let diagnostics = bodies.iter().find(|body| body.contains("publishDiagnostics")).unwrap();
+    assert!(diagnostics.contains(r#""code":"V0001""#), "{}", diagnostics);
+    assert!(diagnostics.contains(r#""start":{"line":2,"character":5}"#), "{}", diagnostics);
+    assert!(reply(&bodies, 2).contains("Immutable `i64` variable, declared on line 1"));
+    assert!(reply(&bodies, 3).contains(r#""start":{"line":0,"character":2}"#));
+    assert!(reply(&bodies, 4).contains(r#""label":"a""#));
+    assert!(reply(&bodies, 5).contains(r#""name":"a""#));
+    assert!(reply(&bodies, 6).contains(r#""code":-32601"#));
+    assert!(reply(&bodies, 7).contains(r#""result":null"#));
+}
+
+#[test]
+fn incremental_edits_update_the_diagnostics() {
+    let change = format!(
+        r#"{{"textDocument":{{"uri":"{}","version":2}},"contentChanges":[{{"range":{{"start":{{"line":1,"character":5}},"end":{{"line":1,"character":6}}}},"text":"a"}}]}}"#,
+        URI
+    );
+    let input = [
+        request(1, "initialize", "{}"),
+        open("@ a i 5\n>> \"{b}\"\n"),
+        notification("textDocument/didChange", &change),
+        request(2, "textDocument/hover", &at(1, 5)),
+        notification("exit", "null"),
+    ]
+    .concat();
+    let (bodies, _) = session(input.as_bytes());
+    let published: Vec<&String> = bodies.iter().filter(|body| body.contains("publishDiagnostics")).collect();
+    assert_eq!(published.len(), 2);
+    assert!(published[0].contains("V0001"));
+    assert!(published[1].contains(r#""diagnostics":[]"#), "{}", published[1]);
+    assert!(reply(&bodies, 2).contains("@ a i64"));
+}
+
+#[test]
+fn exit_without_shutdown_fails() {
+    let (_, code) = session(notification("exit", "null").as_bytes());
+    assert_eq!(code, 1);
+}
+
+#[test]
+fn bad_frames_are_answered_and_the_server_keeps_going() {
+    let deep = "[".repeat(100_000);
+    let mut input = Vec::new();
+    input.extend_from_slice(b"Content-Length: 4\r\n\r\n\xff\xfe{}");
+    input.extend_from_slice(frame("{not json").as_bytes());
+    input.extend_from_slice(frame(&deep).as_bytes());
+    input.extend_from_slice(b"Content-Type: none\r\n\r\n");
+    input.extend_from_slice(frame(r#"{"jsonrpc":"2.0","id":9}"#).as_bytes());
+    input.extend_from_slice(request(1, "initialize", "{}").as_bytes());
+    input.extend_from_slice(request(2, "shutdown", "null").as_bytes());
+    input.extend_from_slice(notification("exit", "null").as_bytes());
+    let (bodies, code) = session(&input);
+    assert_eq!(code, 0);
+
+    let parse_errors = bodies.iter().filter(|body| body.contains(r#""code":-32700"#)).count();
+    assert_eq!(parse_errors, 4, "{:#?}", bodies);
+    assert!(reply(&bodies, 9).contains(r#""code":-32600"#));
+    assert!(reply(&bodies, 1).contains("capabilities"));
+}
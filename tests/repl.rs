@@ -0,0 +1,96 @@
+//! `ven repl` fed from a pipe: entries, brace continuation and errors.
+
+mod common;
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use common::scratch;
+
+/// Runs a REPL on `input` with its own home directory, so the history file
+/// stays out of the real one.
+fn repl(name: &str, input: &str) -> Output {
+    let home = scratch(name);
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ven"))
+        .arg("repl")
+        .env("HOME", &home)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// What the entries printed, without the banner and prompts.
+fn printed(output: &Output) -> Vec<String> {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = stdout.replace("ven> ", "").replace("...> ", "");
+    stdout.lines().skip(1).filter(|line| !line.is_empty()).map(str::to_string).collect()
+}
+
+#[test]
+fn variables_carry_over_between_entries() {
+    let output = repl("carry", "@@ n i 1\n* n + 41\n>> n\n:quit\n");
+    assert_eq!(printed(&output), ["42"]);
+}
+
+#[test]
+fn open_braces_continue_the_entry() {
+    let output = repl("braces", "@@ n i 1\n?(n < 2) {\n>> \"in {n}\"\n* n + 1\n}\n>> n\n:quit\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("...> ").count(), 3, "{}", stdout);
+    assert_eq!(printed(&output), ["in 1", "2"]);
+}
+
+#[test]
+fn braces_in_text_do_not_continue_the_entry() {
+    let output = repl("placeholders", "@ a i 7\n>> \"{a} and {\"\n:quit\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("...> "), "{}", stdout);
+    assert_eq!(printed(&output), ["7 and {"]);
+}
+
+#[test]
+fn errors_do_not_end_the_session() {
+    let output = repl("errors", "?(1 <) {\n}\n>> \"still here\"\n:quit\n");
+    assert!(!output.stderr.is_empty());
+    assert_eq!(printed(&output), ["still here"]);
+}
+
+#[test]
+fn entries_are_kept_in_the_history_file() {
+    let input = "?(1 < 2) {\n>> 1\n}\n:quit\n";
+    repl("history", input);
+    let home = Path::new(env!("CARGO_TARGET_TMPDIR")).join("repl/history");
+    let history = fs::read_to_string(home.join(".ven_history")).unwrap();
+    assert_eq!(history, "?(1 < 2) {\\n>> 1\\n}\n");
+}
+
+#[test]
+fn entries_get_the_compiler_checks() {
+    let output = repl("checks", ">> \"{b}\"\n@ a i 1\n* a + 1\n@ c u8 300\n>> \"done\"\n:quit\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for code in ["V0001", "V0003", "V0013"] {
+        assert!(stderr.contains(code), "no {} in {}", code, stderr);
+    }
+    assert!(stderr.contains("at line 1"), "{}", stderr);
+    assert_eq!(printed(&output), ["done"]);
+}
+
+#[test]
+fn misspelt_names_get_a_suggestion() {
+    let output = repl("suggest", "@@ count i 1\n>> \"{cont}\"\n:quit\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("did you mean `count`?"), "{}", stderr);
+}
+
+#[test]
+fn earlier_declarations_are_in_scope() {
+    let output = repl("scope", "@@ xs i[] [1, 2]\n* xs[] = 3\n>> xs[2]\n>> len(xs)\n>> \"{xs[0]}\"\n:quit\n");
+    assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(printed(&output), ["3", "3", "1"]);
+}
@@ -0,0 +1,68 @@
+//! Programs the compiler must reject before any backend sees them.
+
+use ven::{Options, Target, compile};
+
+/// The codes `source` is rejected with on `target`, or `None` if it compiles.
+fn rejected(source: &str, target: Target) -> Option<Vec<&'static str>> {
+    let diagnostics = compile(source, target, Options::default()).err()?;
+    Some(diagnostics.errors.iter().map(|error| error.code()).collect())
+}
+
+#[test]
+fn out_of_range_literals_are_rejected_on_every_target() {
+    for source in ["@ x u8 300\n>> x\n", "@ c u8 -1\n>> c\n", "@@ n i8 -129\n>> n\n"] {
+        for target in Target::all() {
+            assert_eq!(
+                rejected(source, target),
+                Some(vec!["V0013"]),
+                "{:?} on {}",
+                source,
+                target.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn mistyped_initializers_are_rejected() {
+    let target = Target::from_name("c").unwrap();
+    assert_eq!(rejected("@ age i \"thirty\"\n>> age\n", target), Some(vec!["V0002"]));
+    assert_eq!(rejected("@ tag c[2] \"abc\"\n>> tag\n", target), Some(vec!["V0002"]));
+}
+
+#[test]
+fn values_that_fit_compile() {
+    let target = Target::from_name("c").unwrap();
+    assert_eq!(rejected("@ x u8 255\n@ c i8 -128\n>> \"{x} {c}\"\n", target), None);
+    assert_eq!(rejected("@ a i 5\n>> \"{-a}\"\n", target), None);
+}
+
+#[test]
+fn undeclared_names_are_rejected() {
+    let target = Target::from_name("c").unwrap();
+    assert_eq!(rejected("@ total i count\n>> total\n", target), Some(vec!["V0001"]));
+    assert_eq!(rejected(">> \"{b}\"\n", target), Some(vec!["V0001"]));
+    assert_eq!(rejected(">> \"{-b}\"\n", target), Some(vec!["V0001"]));
+    assert_eq!(rejected("* ghost + 1\n", target), Some(vec!["V0001"]));
+    assert_eq!(rejected(">> \"{nmae} {cont}\"\n", target), Some(vec!["V0001", "V0001"]));
+    // The declaration is dropped with its own error, so the use is not reported too
+    assert_eq!(rejected("@ age int 20\n>> \"{age}\"\n", target), Some(vec!["V0012"]));
+}
+
+#[test]
+fn changes_to_immutable_variables_are_rejected() {
+    let target = Target::from_name("py").unwrap();
+    assert_eq!(rejected("@ count i 0\n* count + 1\n>> count\n", target), Some(vec!["V0003"]));
+    assert_eq!(rejected("@@ count i 0\n* count + 1\n>> count\n", target), None);
+    assert_eq!(rejected("?(1 > 0) {\n@@ inner i 1\n* inner + 1\n}\n", target), None);
+}
+
+#[test]
+fn invalid_format_specs_are_rejected() {
+    let target = Target::from_name("js").unwrap();
+    assert_eq!(rejected("@ a i 7\n>> \"[{a:^4}]\"\n", target), Some(vec!["V0020"]));
+    assert_eq!(rejected("@ a i 7\n>> \"[{a:1000}]\"\n", target), Some(vec!["V0020"]));
+    // The name before the spec is still resolved
+    assert_eq!(rejected(">> \"[{b:^4}]\"\n", target), Some(vec!["V0020", "V0001"]));
+    assert_eq!(rejected("@ a i 7\n>> \"[{a:>4}]\"\n", target), None);
+}